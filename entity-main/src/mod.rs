@@ -3,13 +3,18 @@
 pub mod prelude;
 
 pub mod billing_line_items;
+pub mod chronic_registries;
 pub mod feature_flags;
 pub mod feature_usage_logs;
 pub mod features;
+pub mod file_access_logs;
+pub mod files;
 pub mod global_system_logs;
+pub mod immunization_reminders;
 pub mod insurance_dependents;
 pub mod insurance_providers;
 pub mod patient_insurance;
+pub mod patient_screenings;
 pub mod patients;
 pub mod payment_transactions;
 pub mod sea_orm_active_enums;
@@ -19,3 +24,4 @@ pub mod subscriptions;
 pub mod tenant_features;
 pub mod tenants;
 pub mod usage_metrics;
+pub mod vaccine_schedules;
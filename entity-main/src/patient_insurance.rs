@@ -33,6 +33,7 @@ pub struct Model {
     pub out_of_pocket_max: Option<Decimal>,
     #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
     pub out_of_pocket_met_ytd: Decimal,
+    pub accumulators_reset_on: Option<Date>,
     pub verification_status: VerificationStatus,
     pub verified_at: Option<DateTime>,
     pub is_primary: bool,
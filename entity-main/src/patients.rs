@@ -45,9 +45,15 @@ pub struct Model {
     pub created_at: DateTime,
     pub updated_at: DateTime,
     #[sea_orm(has_many)]
+    pub files: HasMany<super::files::Entity>,
+    #[sea_orm(has_many)]
+    pub immunization_reminders: HasMany<super::immunization_reminders::Entity>,
+    #[sea_orm(has_many)]
     pub insurance_dependents: HasMany<super::insurance_dependents::Entity>,
     #[sea_orm(has_many)]
     pub patient_insurances: HasMany<super::patient_insurance::Entity>,
+    #[sea_orm(has_many)]
+    pub patient_screenings: HasMany<super::patient_screenings::Entity>,
 }
 
 impl ActiveModelBehavior for ActiveModel {}
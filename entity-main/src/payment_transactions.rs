@@ -22,6 +22,7 @@ pub struct Model {
     pub payment_method: PaymentMethod,
     #[sea_orm(column_type = "Text", nullable)]
     pub invoice_url: Option<String>,
+    pub invoice_file_pid: Option<Uuid>,
     #[sea_orm(column_type = "Text", nullable)]
     pub description: Option<String>,
     #[sea_orm(column_type = "Text", nullable)]
@@ -1,13 +1,18 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 2.0.0-rc.18
 
 pub use super::billing_line_items::Entity as BillingLineItems;
+pub use super::chronic_registries::Entity as ChronicRegistries;
 pub use super::feature_flags::Entity as FeatureFlags;
 pub use super::feature_usage_logs::Entity as FeatureUsageLogs;
 pub use super::features::Entity as Features;
+pub use super::file_access_logs::Entity as FileAccessLogs;
+pub use super::files::Entity as Files;
 pub use super::global_system_logs::Entity as GlobalSystemLogs;
+pub use super::immunization_reminders::Entity as ImmunizationReminders;
 pub use super::insurance_dependents::Entity as InsuranceDependents;
 pub use super::insurance_providers::Entity as InsuranceProviders;
 pub use super::patient_insurance::Entity as PatientInsurance;
+pub use super::patient_screenings::Entity as PatientScreenings;
 pub use super::patients::Entity as Patients;
 pub use super::payment_transactions::Entity as PaymentTransactions;
 pub use super::subscription_plan_features::Entity as SubscriptionPlanFeatures;
@@ -16,3 +21,4 @@ pub use super::subscriptions::Entity as Subscriptions;
 pub use super::tenant_features::Entity as TenantFeatures;
 pub use super::tenants::Entity as Tenants;
 pub use super::usage_metrics::Entity as UsageMetrics;
+pub use super::vaccine_schedules::Entity as VaccineSchedules;
@@ -42,6 +42,30 @@ pub enum BillingItemType {
     Custom,
 }
 #[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(
+    rs_type = "String",
+    db_type = "Enum",
+    enum_name = "chronic_control_indicator"
+)]
+pub enum ChronicControlIndicator {
+    #[sea_orm(string_value = "blood_pressure")]
+    BloodPressure,
+    #[sea_orm(string_value = "hba1c")]
+    Hba1c,
+    #[sea_orm(string_value = "viral_load")]
+    ViralLoad,
+}
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "file_visibility")]
+pub enum FileVisibility {
+    #[sea_orm(string_value = "private")]
+    Private,
+    #[sea_orm(string_value = "tenant")]
+    Tenant,
+    #[sea_orm(string_value = "public")]
+    Public,
+}
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
 #[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "gender")]
 pub enum Gender {
     #[sea_orm(string_value = "male")]
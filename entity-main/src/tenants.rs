@@ -48,6 +48,8 @@ pub struct Model {
     #[sea_orm(has_many)]
     pub feature_usage_logs: HasMany<super::feature_usage_logs::Entity>,
     #[sea_orm(has_many)]
+    pub files: HasMany<super::files::Entity>,
+    #[sea_orm(has_many)]
     pub global_system_logs: HasMany<super::global_system_logs::Entity>,
     #[sea_orm(has_many)]
     pub payment_transactions: HasMany<super::payment_transactions::Entity>,
@@ -1,3 +1,69 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 2.0.0-rc.18
 
 pub mod prelude;
+
+pub mod admissions;
+pub mod anc_profiles;
+pub mod anc_visits;
+pub mod bed_transfers;
+pub mod beds;
+pub mod bill_payments;
+pub mod billable_services;
+pub mod cart_items;
+pub mod cashier_reconciliations;
+pub mod clinical_tasks;
+pub mod controlled_reconciliations;
+pub mod controlled_register_entries;
+pub mod coupon_redemptions;
+pub mod coupons;
+pub mod defaulter_tracing_tasks;
+pub mod deliveries;
+pub mod delivery_riders;
+pub mod delivery_zones;
+pub mod dispense_items;
+pub mod dispenses;
+pub mod encounters;
+pub mod facility_mpesa_settings;
+pub mod goods_received_items;
+pub mod goods_received_notes;
+pub mod growth_measurements;
+pub mod immunization_records;
+pub mod insurance_claim_attachments;
+pub mod insurance_claim_lines;
+pub mod insurance_claims;
+pub mod insurance_remittances;
+pub mod lab_orders;
+pub mod message_attachments;
+pub mod message_threads;
+pub mod online_order_items;
+pub mod online_orders;
+pub mod patient_bills;
+pub mod patient_charges;
+pub mod patient_diagnoses;
+pub mod pharmacy_products;
+pub mod pharmacy_stores;
+pub mod preauthorization_attachments;
+pub mod preauthorization_lines;
+pub mod preauthorizations;
+pub mod prescription_items;
+pub mod prescriptions;
+pub mod price_lists;
+pub mod purchase_order_items;
+pub mod purchase_orders;
+pub mod queue_stage_visits;
+pub mod queue_tickets;
+pub mod registry_enrolments;
+pub mod registry_follow_ups;
+pub mod remittance_allocations;
+pub mod screening_results;
+pub mod sea_orm_active_enums;
+pub mod service_prices;
+pub mod stock_alerts;
+pub mod stock_batches;
+pub mod stock_movements;
+pub mod supplier_prices;
+pub mod suppliers;
+pub mod telemedicine_sessions;
+pub mod thread_messages;
+pub mod triage_assessments;
+pub mod wards;
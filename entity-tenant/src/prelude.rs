@@ -1 +1,66 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 2.0.0-rc.18
+
+pub use super::admissions::Entity as Admissions;
+pub use super::anc_profiles::Entity as AncProfiles;
+pub use super::anc_visits::Entity as AncVisits;
+pub use super::bed_transfers::Entity as BedTransfers;
+pub use super::beds::Entity as Beds;
+pub use super::bill_payments::Entity as BillPayments;
+pub use super::billable_services::Entity as BillableServices;
+pub use super::cart_items::Entity as CartItems;
+pub use super::cashier_reconciliations::Entity as CashierReconciliations;
+pub use super::clinical_tasks::Entity as ClinicalTasks;
+pub use super::controlled_reconciliations::Entity as ControlledReconciliations;
+pub use super::controlled_register_entries::Entity as ControlledRegisterEntries;
+pub use super::coupon_redemptions::Entity as CouponRedemptions;
+pub use super::coupons::Entity as Coupons;
+pub use super::defaulter_tracing_tasks::Entity as DefaulterTracingTasks;
+pub use super::deliveries::Entity as Deliveries;
+pub use super::delivery_riders::Entity as DeliveryRiders;
+pub use super::delivery_zones::Entity as DeliveryZones;
+pub use super::dispense_items::Entity as DispenseItems;
+pub use super::dispenses::Entity as Dispenses;
+pub use super::encounters::Entity as Encounters;
+pub use super::facility_mpesa_settings::Entity as FacilityMpesaSettings;
+pub use super::goods_received_items::Entity as GoodsReceivedItems;
+pub use super::goods_received_notes::Entity as GoodsReceivedNotes;
+pub use super::growth_measurements::Entity as GrowthMeasurements;
+pub use super::immunization_records::Entity as ImmunizationRecords;
+pub use super::insurance_claim_attachments::Entity as InsuranceClaimAttachments;
+pub use super::insurance_claim_lines::Entity as InsuranceClaimLines;
+pub use super::insurance_claims::Entity as InsuranceClaims;
+pub use super::insurance_remittances::Entity as InsuranceRemittances;
+pub use super::lab_orders::Entity as LabOrders;
+pub use super::message_attachments::Entity as MessageAttachments;
+pub use super::message_threads::Entity as MessageThreads;
+pub use super::online_order_items::Entity as OnlineOrderItems;
+pub use super::online_orders::Entity as OnlineOrders;
+pub use super::patient_bills::Entity as PatientBills;
+pub use super::patient_charges::Entity as PatientCharges;
+pub use super::patient_diagnoses::Entity as PatientDiagnoses;
+pub use super::pharmacy_products::Entity as PharmacyProducts;
+pub use super::pharmacy_stores::Entity as PharmacyStores;
+pub use super::preauthorization_attachments::Entity as PreauthorizationAttachments;
+pub use super::preauthorization_lines::Entity as PreauthorizationLines;
+pub use super::preauthorizations::Entity as Preauthorizations;
+pub use super::prescription_items::Entity as PrescriptionItems;
+pub use super::prescriptions::Entity as Prescriptions;
+pub use super::price_lists::Entity as PriceLists;
+pub use super::purchase_order_items::Entity as PurchaseOrderItems;
+pub use super::purchase_orders::Entity as PurchaseOrders;
+pub use super::queue_stage_visits::Entity as QueueStageVisits;
+pub use super::queue_tickets::Entity as QueueTickets;
+pub use super::registry_enrolments::Entity as RegistryEnrolments;
+pub use super::registry_follow_ups::Entity as RegistryFollowUps;
+pub use super::remittance_allocations::Entity as RemittanceAllocations;
+pub use super::screening_results::Entity as ScreeningResults;
+pub use super::service_prices::Entity as ServicePrices;
+pub use super::stock_alerts::Entity as StockAlerts;
+pub use super::stock_batches::Entity as StockBatches;
+pub use super::stock_movements::Entity as StockMovements;
+pub use super::supplier_prices::Entity as SupplierPrices;
+pub use super::suppliers::Entity as Suppliers;
+pub use super::telemedicine_sessions::Entity as TelemedicineSessions;
+pub use super::thread_messages::Entity as ThreadMessages;
+pub use super::triage_assessments::Entity as TriageAssessments;
+pub use super::wards::Entity as Wards;
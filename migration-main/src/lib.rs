@@ -17,6 +17,14 @@ mod m20251201_203800_create_patient_insurance_table;
 mod m20251201_203842_create_insurance_dependents_table;
 mod m20251201_210232_create_global_system_logs_table;
 mod m20251201_210712_create_usage_metrics_table;
+mod m20251215_091204_create_files_table;
+mod m20251215_092730_create_file_access_logs_table;
+mod m20251216_081145_create_vaccine_schedules_table;
+mod m20251216_081932_create_immunization_reminders_table;
+mod m20251218_070412_create_chronic_registries_table;
+mod m20251219_083405_create_patient_screenings_table;
+mod m20260106_070215_add_accumulator_reset_to_patient_insurance;
+mod m20260109_070530_add_invoice_file_pid_to_payment_transactions;
 
 pub struct Migrator;
 
@@ -41,6 +49,14 @@ impl MigratorTrait for Migrator {
             Box::new(m20251201_203842_create_insurance_dependents_table::Migration),
             Box::new(m20251201_210232_create_global_system_logs_table::Migration),
             Box::new(m20251201_210712_create_usage_metrics_table::Migration),
+            Box::new(m20251215_091204_create_files_table::Migration),
+            Box::new(m20251215_092730_create_file_access_logs_table::Migration),
+            Box::new(m20251216_081145_create_vaccine_schedules_table::Migration),
+            Box::new(m20251216_081932_create_immunization_reminders_table::Migration),
+            Box::new(m20251218_070412_create_chronic_registries_table::Migration),
+            Box::new(m20251219_083405_create_patient_screenings_table::Migration),
+            Box::new(m20260106_070215_add_accumulator_reset_to_patient_insurance::Migration),
+            Box::new(m20260109_070530_add_invoice_file_pid_to_payment_transactions::Migration),
         ]
     }
 }
@@ -0,0 +1,141 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("file_visibility"))
+                    .values([
+                        Alias::new("private"),
+                        Alias::new("tenant"),
+                        Alias::new("public"),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Files::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Files::Id))
+                    .col(
+                        uuid_uniq(Files::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(text_uniq(Files::StorageKey))
+                    .col(string_null(Files::OriginalName).string_len(255))
+                    .col(string(Files::ContentType).string_len(255))
+                    .col(big_integer(Files::SizeBytes).default(0))
+                    .col(string_null(Files::Category).string_len(100))
+                    .col(uuid_null(Files::OwnerSsoUserId))
+                    .col(integer_null(Files::TenantId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-files-tenant_id")
+                            .from(Files::Table, Files::TenantId)
+                            .to(Tenants::Table, Tenants::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .col(integer_null(Files::PatientId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-files-patient_id")
+                            .from(Files::Table, Files::PatientId)
+                            .to(Patients::Table, Patients::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        enumeration(
+                            Files::Visibility,
+                            Alias::new("file_visibility"),
+                            vec![
+                                Alias::new("private"),
+                                Alias::new("tenant"),
+                                Alias::new("public"),
+                            ],
+                        )
+                        .default("private"),
+                    )
+                    .col(timestamp_null(Files::DeletedAt))
+                    .col(
+                        timestamp(Files::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(Files::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_files_owner_sso_user_id = Index::create()
+            .name("idx_files_owner_sso_user_id")
+            .table(Files::Table)
+            .col(Files::OwnerSsoUserId)
+            .to_owned();
+
+        let _idx_files_tenant_id = Index::create()
+            .name("idx_files_tenant_id")
+            .table(Files::Table)
+            .col(Files::TenantId)
+            .to_owned();
+
+        let _idx_files_patient_id = Index::create()
+            .name("idx_files_patient_id")
+            .table(Files::Table)
+            .col(Files::PatientId)
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Files::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(Alias::new("file_visibility")).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Files {
+    Table,
+    Id,
+    Pid,
+    StorageKey,
+    OriginalName,
+    ContentType,
+    SizeBytes,
+    Category,
+    OwnerSsoUserId,
+    TenantId,
+    PatientId,
+    Visibility,
+    DeletedAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Tenants {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Patients {
+    Table,
+    Id,
+}
@@ -0,0 +1,84 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(FileAccessLogs::Table)
+                    .if_not_exists()
+                    .col(pk_auto(FileAccessLogs::Id))
+                    .col(
+                        uuid_uniq(FileAccessLogs::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer(FileAccessLogs::FileId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-file_access_logs-file_id")
+                            .from(FileAccessLogs::Table, FileAccessLogs::FileId)
+                            .to(Files::Table, Files::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(uuid_null(FileAccessLogs::SsoUserId))
+                    .col(uuid_null(FileAccessLogs::TenantPid))
+                    .col(string(FileAccessLogs::Action).string_len(50))
+                    .col(boolean(FileAccessLogs::Granted))
+                    .col(text_null(FileAccessLogs::Reason))
+                    .col(string_null(FileAccessLogs::IpAddress))
+                    .col(text_null(FileAccessLogs::UserAgent))
+                    .col(
+                        timestamp(FileAccessLogs::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_file_access_logs_file_id = Index::create()
+            .name("idx_file_access_logs_file_id")
+            .table(FileAccessLogs::Table)
+            .col(FileAccessLogs::FileId)
+            .to_owned();
+
+        let _idx_file_access_logs_sso_user_id = Index::create()
+            .name("idx_file_access_logs_sso_user_id")
+            .table(FileAccessLogs::Table)
+            .col(FileAccessLogs::SsoUserId)
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(FileAccessLogs::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum FileAccessLogs {
+    Table,
+    Id,
+    Pid,
+    FileId,
+    SsoUserId,
+    TenantPid,
+    Action,
+    Granted,
+    Reason,
+    IpAddress,
+    UserAgent,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Files {
+    Table,
+    Id,
+}
@@ -0,0 +1,83 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(VaccineSchedules::Table)
+                    .if_not_exists()
+                    .col(pk_auto(VaccineSchedules::Id))
+                    .col(
+                        uuid_uniq(VaccineSchedules::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(string(VaccineSchedules::VaccineCode).string_len(50))
+                    .col(string(VaccineSchedules::VaccineName).string_len(255))
+                    .col(integer(VaccineSchedules::DoseNumber))
+                    .col(integer(VaccineSchedules::RecommendedAgeDays))
+                    .col(integer(VaccineSchedules::OverdueAfterDays).default(14))
+                    .col(enumeration_null(
+                        VaccineSchedules::Gender,
+                        Alias::new("gender"),
+                        vec![
+                            Alias::new("male"),
+                            Alias::new("female"),
+                            Alias::new("other"),
+                            Alias::new("prefer_not_to_say"),
+                        ],
+                    ))
+                    .col(text_null(VaccineSchedules::Notes))
+                    .col(boolean(VaccineSchedules::IsActive).default(true))
+                    .col(timestamp_null(VaccineSchedules::DeletedAt))
+                    .col(
+                        timestamp(VaccineSchedules::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(VaccineSchedules::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _uniq_vaccine_schedule_dose = Index::create()
+            .name("uniq_vaccine_schedule_dose")
+            .table(VaccineSchedules::Table)
+            .col(VaccineSchedules::VaccineCode)
+            .col(VaccineSchedules::DoseNumber)
+            .unique()
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(VaccineSchedules::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum VaccineSchedules {
+    Table,
+    Id,
+    Pid,
+    VaccineCode,
+    VaccineName,
+    DoseNumber,
+    RecommendedAgeDays,
+    OverdueAfterDays,
+    Gender,
+    Notes,
+    IsActive,
+    DeletedAt,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,91 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ImmunizationReminders::Table)
+                    .if_not_exists()
+                    .col(pk_auto(ImmunizationReminders::Id))
+                    .col(
+                        uuid_uniq(ImmunizationReminders::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer(ImmunizationReminders::PatientId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-immunization_reminders-patient_id")
+                            .from(
+                                ImmunizationReminders::Table,
+                                ImmunizationReminders::PatientId,
+                            )
+                            .to(Patients::Table, Patients::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(integer(ImmunizationReminders::VaccineScheduleId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-immunization_reminders-vaccine_schedule_id")
+                            .from(
+                                ImmunizationReminders::Table,
+                                ImmunizationReminders::VaccineScheduleId,
+                            )
+                            .to(VaccineSchedules::Table, VaccineSchedules::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(date(ImmunizationReminders::DueDate))
+                    .col(string(ImmunizationReminders::Channel).string_len(20))
+                    .col(
+                        timestamp(ImmunizationReminders::SentAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _uniq_immunization_reminder = Index::create()
+            .name("uniq_immunization_reminder")
+            .table(ImmunizationReminders::Table)
+            .col(ImmunizationReminders::PatientId)
+            .col(ImmunizationReminders::VaccineScheduleId)
+            .unique()
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ImmunizationReminders::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ImmunizationReminders {
+    Table,
+    Id,
+    Pid,
+    PatientId,
+    VaccineScheduleId,
+    DueDate,
+    Channel,
+    SentAt,
+}
+
+#[derive(DeriveIden)]
+enum Patients {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum VaccineSchedules {
+    Table,
+    Id,
+}
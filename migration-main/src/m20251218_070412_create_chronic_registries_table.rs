@@ -0,0 +1,99 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("chronic_control_indicator"))
+                    .values([
+                        Alias::new("blood_pressure"),
+                        Alias::new("hba1c"),
+                        Alias::new("viral_load"),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ChronicRegistries::Table)
+                    .if_not_exists()
+                    .col(pk_auto(ChronicRegistries::Id))
+                    .col(
+                        uuid_uniq(ChronicRegistries::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(string_uniq(ChronicRegistries::Code).string_len(20))
+                    .col(string(ChronicRegistries::Name).string_len(255))
+                    .col(text_null(ChronicRegistries::Description))
+                    .col(array(
+                        ChronicRegistries::DiagnosisCodes,
+                        ColumnType::String(StringLen::None),
+                    ))
+                    .col(enumeration(
+                        ChronicRegistries::ControlIndicator,
+                        Alias::new("chronic_control_indicator"),
+                        vec![
+                            Alias::new("blood_pressure"),
+                            Alias::new("hba1c"),
+                            Alias::new("viral_load"),
+                        ],
+                    ))
+                    .col(integer(ChronicRegistries::FollowUpIntervalDays).default(30))
+                    .col(integer(ChronicRegistries::DefaulterAfterDays).default(30))
+                    .col(boolean(ChronicRegistries::IsActive).default(true))
+                    .col(timestamp_null(ChronicRegistries::DeletedAt))
+                    .col(
+                        timestamp(ChronicRegistries::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(ChronicRegistries::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ChronicRegistries::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(Alias::new("chronic_control_indicator"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ChronicRegistries {
+    Table,
+    Id,
+    Pid,
+    Code,
+    Name,
+    Description,
+    DiagnosisCodes,
+    ControlIndicator,
+    FollowUpIntervalDays,
+    DefaulterAfterDays,
+    IsActive,
+    DeletedAt,
+    CreatedAt,
+    UpdatedAt,
+}
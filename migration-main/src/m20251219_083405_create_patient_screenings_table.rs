@@ -0,0 +1,87 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PatientScreenings::Table)
+                    .if_not_exists()
+                    .col(pk_auto(PatientScreenings::Id))
+                    .col(
+                        uuid_uniq(PatientScreenings::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer(PatientScreenings::PatientId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-patient_screenings-patient_id")
+                            .from(PatientScreenings::Table, PatientScreenings::PatientId)
+                            .to(Patients::Table, Patients::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(string(PatientScreenings::Instrument).string_len(20))
+                    .col(json_binary(PatientScreenings::Answers))
+                    .col(integer(PatientScreenings::TotalScore))
+                    .col(string(PatientScreenings::Severity).string_len(50))
+                    .col(boolean(PatientScreenings::SafetyFlag).default(false))
+                    .col(
+                        timestamp(PatientScreenings::CompletedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(timestamp_null(PatientScreenings::DeletedAt))
+                    .col(
+                        timestamp(PatientScreenings::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(PatientScreenings::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_patient_screenings_patient_id = Index::create()
+            .name("idx_patient_screenings_patient_id")
+            .table(PatientScreenings::Table)
+            .col(PatientScreenings::PatientId)
+            .col(PatientScreenings::Instrument)
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PatientScreenings::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PatientScreenings {
+    Table,
+    Id,
+    Pid,
+    PatientId,
+    Instrument,
+    Answers,
+    TotalScore,
+    Severity,
+    SafetyFlag,
+    CompletedAt,
+    DeletedAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Patients {
+    Table,
+    Id,
+}
@@ -1,6 +1,82 @@
 pub use sea_orm_migration::prelude::*;
 
 // mod m20220101_000001_create_table;
+mod m20251216_080512_create_immunization_records_table;
+mod m20251217_093015_create_anc_profiles_table;
+mod m20251217_093642_create_anc_visits_table;
+mod m20251217_094208_create_growth_measurements_table;
+mod m20251218_071020_create_patient_diagnoses_table;
+mod m20251218_071544_create_registry_enrolments_table;
+mod m20251218_072133_create_registry_follow_ups_table;
+mod m20251218_072710_create_defaulter_tracing_tasks_table;
+mod m20251219_084012_create_screening_results_table;
+mod m20251219_084537_create_clinical_tasks_table;
+mod m20251220_071205_create_wards_table;
+mod m20251220_071630_create_beds_table;
+mod m20251220_072148_create_admissions_table;
+mod m20251220_072611_create_bed_transfers_table;
+mod m20251221_063012_create_queue_tickets_table;
+mod m20251221_063540_create_queue_stage_visits_table;
+mod m20251221_064105_create_triage_assessments_table;
+mod m20251222_081015_create_encounters_table;
+mod m20251222_081540_create_telemedicine_sessions_table;
+mod m20251223_090210_create_message_threads_table;
+mod m20251223_090735_create_thread_messages_table;
+mod m20251223_091248_create_message_attachments_table;
+mod m20251224_080105_create_pharmacy_products_table;
+mod m20251224_080540_create_pharmacy_stores_table;
+mod m20251224_081012_create_stock_batches_table;
+mod m20251224_081437_create_stock_movements_table;
+mod m20251226_070110_add_unit_price_to_pharmacy_products;
+mod m20251226_070545_create_prescriptions_table;
+mod m20251226_071020_create_prescription_items_table;
+mod m20251226_071455_create_dispenses_table;
+mod m20251226_071930_create_dispense_items_table;
+mod m20251226_072415_create_patient_charges_table;
+mod m20251227_060105_create_suppliers_table;
+mod m20251227_060540_create_purchase_orders_table;
+mod m20251227_061015_create_purchase_order_items_table;
+mod m20251227_061450_create_goods_received_notes_table;
+mod m20251227_061925_create_goods_received_items_table;
+mod m20251227_062400_create_supplier_prices_table;
+mod m20251228_070105_add_alert_contacts_to_pharmacy_stores;
+mod m20251228_070540_create_stock_alerts_table;
+mod m20251229_080105_add_controlled_flag_to_pharmacy_products;
+mod m20251229_080540_create_controlled_register_entries_table;
+mod m20251229_081015_create_controlled_reconciliations_table;
+mod m20251230_070105_add_online_sale_flags_to_pharmacy_products;
+mod m20251230_070540_create_cart_items_table;
+mod m20251230_071015_create_online_orders_table;
+mod m20251230_071450_create_online_order_items_table;
+mod m20251231_070105_create_coupons_table;
+mod m20251231_070540_create_coupon_redemptions_table;
+mod m20251231_071015_add_discount_to_online_orders;
+mod m20260102_070105_create_delivery_zones_table;
+mod m20260102_070540_create_delivery_riders_table;
+mod m20260102_071015_create_deliveries_table;
+mod m20260102_071450_add_delivery_fee_to_online_orders;
+mod m20260103_070105_create_billable_services_table;
+mod m20260103_070540_create_price_lists_table;
+mod m20260103_071015_create_service_prices_table;
+mod m20260103_071450_add_service_to_pharmacy_products;
+mod m20260103_071925_create_lab_orders_table;
+mod m20260103_072400_add_pricing_to_patient_charges;
+mod m20260104_081210_create_facility_mpesa_settings_table;
+mod m20260104_081645_create_patient_bills_table;
+mod m20260104_082120_create_cashier_reconciliations_table;
+mod m20260104_082555_create_bill_payments_table;
+mod m20260104_083030_add_bill_to_patient_charges;
+mod m20260105_090015_create_insurance_claims_table;
+mod m20260105_090440_create_insurance_claim_lines_table;
+mod m20260105_090905_create_insurance_claim_attachments_table;
+mod m20260105_091330_create_insurance_remittances_table;
+mod m20260105_091750_create_remittance_allocations_table;
+mod m20260106_070640_add_deductible_to_patient_bills;
+mod m20260107_081015_create_preauthorizations_table;
+mod m20260107_081420_create_preauthorization_lines_table;
+mod m20260107_081825_create_preauthorization_attachments_table;
+mod m20260107_082230_add_preauth_numbers_to_insurance_claims;
+mod m20260108_070215_add_deductible_posted_to_insurance_claims;
 
 pub struct Migrator;
 
@@ -9,6 +85,82 @@ impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
         vec![
             // Box::new(m20220101_000001_create_table::Migration),
+            Box::new(m20251216_080512_create_immunization_records_table::Migration),
+            Box::new(m20251217_093015_create_anc_profiles_table::Migration),
+            Box::new(m20251217_093642_create_anc_visits_table::Migration),
+            Box::new(m20251217_094208_create_growth_measurements_table::Migration),
+            Box::new(m20251218_071020_create_patient_diagnoses_table::Migration),
+            Box::new(m20251218_071544_create_registry_enrolments_table::Migration),
+            Box::new(m20251218_072133_create_registry_follow_ups_table::Migration),
+            Box::new(m20251218_072710_create_defaulter_tracing_tasks_table::Migration),
+            Box::new(m20251219_084012_create_screening_results_table::Migration),
+            Box::new(m20251219_084537_create_clinical_tasks_table::Migration),
+            Box::new(m20251220_071205_create_wards_table::Migration),
+            Box::new(m20251220_071630_create_beds_table::Migration),
+            Box::new(m20251220_072148_create_admissions_table::Migration),
+            Box::new(m20251220_072611_create_bed_transfers_table::Migration),
+            Box::new(m20251221_063012_create_queue_tickets_table::Migration),
+            Box::new(m20251221_063540_create_queue_stage_visits_table::Migration),
+            Box::new(m20251221_064105_create_triage_assessments_table::Migration),
+            Box::new(m20251222_081015_create_encounters_table::Migration),
+            Box::new(m20251222_081540_create_telemedicine_sessions_table::Migration),
+            Box::new(m20251223_090210_create_message_threads_table::Migration),
+            Box::new(m20251223_090735_create_thread_messages_table::Migration),
+            Box::new(m20251223_091248_create_message_attachments_table::Migration),
+            Box::new(m20251224_080105_create_pharmacy_products_table::Migration),
+            Box::new(m20251224_080540_create_pharmacy_stores_table::Migration),
+            Box::new(m20251224_081012_create_stock_batches_table::Migration),
+            Box::new(m20251224_081437_create_stock_movements_table::Migration),
+            Box::new(m20251226_070110_add_unit_price_to_pharmacy_products::Migration),
+            Box::new(m20251226_070545_create_prescriptions_table::Migration),
+            Box::new(m20251226_071020_create_prescription_items_table::Migration),
+            Box::new(m20251226_071455_create_dispenses_table::Migration),
+            Box::new(m20251226_071930_create_dispense_items_table::Migration),
+            Box::new(m20251226_072415_create_patient_charges_table::Migration),
+            Box::new(m20251227_060105_create_suppliers_table::Migration),
+            Box::new(m20251227_060540_create_purchase_orders_table::Migration),
+            Box::new(m20251227_061015_create_purchase_order_items_table::Migration),
+            Box::new(m20251227_061450_create_goods_received_notes_table::Migration),
+            Box::new(m20251227_061925_create_goods_received_items_table::Migration),
+            Box::new(m20251227_062400_create_supplier_prices_table::Migration),
+            Box::new(m20251228_070105_add_alert_contacts_to_pharmacy_stores::Migration),
+            Box::new(m20251228_070540_create_stock_alerts_table::Migration),
+            Box::new(m20251229_080105_add_controlled_flag_to_pharmacy_products::Migration),
+            Box::new(m20251229_080540_create_controlled_register_entries_table::Migration),
+            Box::new(m20251229_081015_create_controlled_reconciliations_table::Migration),
+            Box::new(m20251230_070105_add_online_sale_flags_to_pharmacy_products::Migration),
+            Box::new(m20251230_070540_create_cart_items_table::Migration),
+            Box::new(m20251230_071015_create_online_orders_table::Migration),
+            Box::new(m20251230_071450_create_online_order_items_table::Migration),
+            Box::new(m20251231_070105_create_coupons_table::Migration),
+            Box::new(m20251231_070540_create_coupon_redemptions_table::Migration),
+            Box::new(m20251231_071015_add_discount_to_online_orders::Migration),
+            Box::new(m20260102_070105_create_delivery_zones_table::Migration),
+            Box::new(m20260102_070540_create_delivery_riders_table::Migration),
+            Box::new(m20260102_071015_create_deliveries_table::Migration),
+            Box::new(m20260102_071450_add_delivery_fee_to_online_orders::Migration),
+            Box::new(m20260103_070105_create_billable_services_table::Migration),
+            Box::new(m20260103_070540_create_price_lists_table::Migration),
+            Box::new(m20260103_071015_create_service_prices_table::Migration),
+            Box::new(m20260103_071450_add_service_to_pharmacy_products::Migration),
+            Box::new(m20260103_071925_create_lab_orders_table::Migration),
+            Box::new(m20260103_072400_add_pricing_to_patient_charges::Migration),
+            Box::new(m20260104_081210_create_facility_mpesa_settings_table::Migration),
+            Box::new(m20260104_081645_create_patient_bills_table::Migration),
+            Box::new(m20260104_082120_create_cashier_reconciliations_table::Migration),
+            Box::new(m20260104_082555_create_bill_payments_table::Migration),
+            Box::new(m20260104_083030_add_bill_to_patient_charges::Migration),
+            Box::new(m20260105_090015_create_insurance_claims_table::Migration),
+            Box::new(m20260105_090440_create_insurance_claim_lines_table::Migration),
+            Box::new(m20260105_090905_create_insurance_claim_attachments_table::Migration),
+            Box::new(m20260105_091330_create_insurance_remittances_table::Migration),
+            Box::new(m20260105_091750_create_remittance_allocations_table::Migration),
+            Box::new(m20260106_070640_add_deductible_to_patient_bills::Migration),
+            Box::new(m20260107_081015_create_preauthorizations_table::Migration),
+            Box::new(m20260107_081420_create_preauthorization_lines_table::Migration),
+            Box::new(m20260107_081825_create_preauthorization_attachments_table::Migration),
+            Box::new(m20260107_082230_add_preauth_numbers_to_insurance_claims::Migration),
+            Box::new(m20260108_070215_add_deductible_posted_to_insurance_claims::Migration),
         ]
     }
 }
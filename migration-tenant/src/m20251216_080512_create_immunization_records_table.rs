@@ -0,0 +1,85 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ImmunizationRecords::Table)
+                    .if_not_exists()
+                    .col(pk_auto(ImmunizationRecords::Id))
+                    .col(
+                        uuid_uniq(ImmunizationRecords::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(uuid(ImmunizationRecords::PatientPid))
+                    .col(uuid_null(ImmunizationRecords::SchedulePid))
+                    .col(string(ImmunizationRecords::VaccineCode).string_len(50))
+                    .col(string(ImmunizationRecords::VaccineName).string_len(255))
+                    .col(integer(ImmunizationRecords::DoseNumber))
+                    .col(string_null(ImmunizationRecords::LotNumber).string_len(100))
+                    .col(string_null(ImmunizationRecords::Site).string_len(50))
+                    .col(timestamp(ImmunizationRecords::AdministeredAt))
+                    .col(string(ImmunizationRecords::AdministeringFacility).string_len(255))
+                    .col(uuid_null(ImmunizationRecords::AdministeredBy))
+                    .col(text_null(ImmunizationRecords::Notes))
+                    .col(timestamp_null(ImmunizationRecords::DeletedAt))
+                    .col(
+                        timestamp(ImmunizationRecords::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(ImmunizationRecords::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_immunization_records_patient_pid = Index::create()
+            .name("idx_immunization_records_patient_pid")
+            .table(ImmunizationRecords::Table)
+            .col(ImmunizationRecords::PatientPid)
+            .to_owned();
+
+        let _idx_immunization_records_vaccine_dose = Index::create()
+            .name("idx_immunization_records_vaccine_dose")
+            .table(ImmunizationRecords::Table)
+            .col(ImmunizationRecords::VaccineCode)
+            .col(ImmunizationRecords::DoseNumber)
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ImmunizationRecords::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ImmunizationRecords {
+    Table,
+    Id,
+    Pid,
+    PatientPid,
+    SchedulePid,
+    VaccineCode,
+    VaccineName,
+    DoseNumber,
+    LotNumber,
+    Site,
+    AdministeredAt,
+    AdministeringFacility,
+    AdministeredBy,
+    Notes,
+    DeletedAt,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,118 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("anc_profile_status"))
+                    .values([
+                        Alias::new("active"),
+                        Alias::new("delivered"),
+                        Alias::new("closed"),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(AncProfiles::Table)
+                    .if_not_exists()
+                    .col(pk_auto(AncProfiles::Id))
+                    .col(
+                        uuid_uniq(AncProfiles::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(uuid(AncProfiles::PatientPid))
+                    .col(date(AncProfiles::Lmp))
+                    .col(date(AncProfiles::Edd))
+                    .col(integer(AncProfiles::Gravida))
+                    .col(integer(AncProfiles::Para))
+                    .col(array_null(
+                        AncProfiles::RiskFactors,
+                        ColumnType::String(StringLen::None),
+                    ))
+                    .col(
+                        enumeration(
+                            AncProfiles::Status,
+                            Alias::new("anc_profile_status"),
+                            vec![
+                                Alias::new("active"),
+                                Alias::new("delivered"),
+                                Alias::new("closed"),
+                            ],
+                        )
+                        .default("active"),
+                    )
+                    .col(date_null(AncProfiles::OutcomeDate))
+                    .col(text_null(AncProfiles::Notes))
+                    .col(uuid_null(AncProfiles::CreatedBy))
+                    .col(timestamp_null(AncProfiles::DeletedAt))
+                    .col(
+                        timestamp(AncProfiles::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(AncProfiles::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_anc_profiles_patient_pid = Index::create()
+            .name("idx_anc_profiles_patient_pid")
+            .table(AncProfiles::Table)
+            .col(AncProfiles::PatientPid)
+            .to_owned();
+
+        let _idx_anc_profiles_status = Index::create()
+            .name("idx_anc_profiles_status")
+            .table(AncProfiles::Table)
+            .col(AncProfiles::Status)
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AncProfiles::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(Alias::new("anc_profile_status"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AncProfiles {
+    Table,
+    Id,
+    Pid,
+    PatientPid,
+    Lmp,
+    Edd,
+    Gravida,
+    Para,
+    RiskFactors,
+    Status,
+    OutcomeDate,
+    Notes,
+    CreatedBy,
+    DeletedAt,
+    CreatedAt,
+    UpdatedAt,
+}
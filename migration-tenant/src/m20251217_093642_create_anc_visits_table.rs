@@ -0,0 +1,102 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AncVisits::Table)
+                    .if_not_exists()
+                    .col(pk_auto(AncVisits::Id))
+                    .col(
+                        uuid_uniq(AncVisits::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer(AncVisits::AncProfileId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-anc_visits-anc_profile_id")
+                            .from(AncVisits::Table, AncVisits::AncProfileId)
+                            .to(AncProfiles::Table, AncProfiles::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(integer(AncVisits::ContactNumber))
+                    .col(date(AncVisits::VisitDate))
+                    .col(integer(AncVisits::GestationWeeks))
+                    .col(decimal_null(AncVisits::WeightKg).decimal_len(5, 2))
+                    .col(integer_null(AncVisits::BpSystolic))
+                    .col(integer_null(AncVisits::BpDiastolic))
+                    .col(decimal_null(AncVisits::FundalHeightCm).decimal_len(4, 1))
+                    .col(integer_null(AncVisits::FetalHeartRate))
+                    .col(decimal_null(AncVisits::HemoglobinGDl).decimal_len(4, 1))
+                    .col(string_null(AncVisits::UrineProtein).string_len(20))
+                    .col(array_null(
+                        AncVisits::DangerSigns,
+                        ColumnType::String(StringLen::None),
+                    ))
+                    .col(date_null(AncVisits::NextVisitDate))
+                    .col(text_null(AncVisits::Notes))
+                    .col(uuid_null(AncVisits::RecordedBy))
+                    .col(timestamp_null(AncVisits::DeletedAt))
+                    .col(
+                        timestamp(AncVisits::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(AncVisits::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_anc_visits_anc_profile_id = Index::create()
+            .name("idx_anc_visits_anc_profile_id")
+            .table(AncVisits::Table)
+            .col(AncVisits::AncProfileId)
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AncVisits::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AncVisits {
+    Table,
+    Id,
+    Pid,
+    AncProfileId,
+    ContactNumber,
+    VisitDate,
+    GestationWeeks,
+    WeightKg,
+    BpSystolic,
+    BpDiastolic,
+    FundalHeightCm,
+    FetalHeartRate,
+    HemoglobinGDl,
+    UrineProtein,
+    DangerSigns,
+    NextVisitDate,
+    Notes,
+    RecordedBy,
+    DeletedAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum AncProfiles {
+    Table,
+    Id,
+}
@@ -0,0 +1,85 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(GrowthMeasurements::Table)
+                    .if_not_exists()
+                    .col(pk_auto(GrowthMeasurements::Id))
+                    .col(
+                        uuid_uniq(GrowthMeasurements::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(uuid(GrowthMeasurements::PatientPid))
+                    .col(date(GrowthMeasurements::MeasuredOn))
+                    .col(integer(GrowthMeasurements::AgeDays))
+                    .col(decimal_null(GrowthMeasurements::WeightKg).decimal_len(5, 2))
+                    .col(decimal_null(GrowthMeasurements::HeightCm).decimal_len(5, 1))
+                    .col(decimal_null(GrowthMeasurements::MuacCm).decimal_len(4, 1))
+                    .col(boolean(GrowthMeasurements::Oedema).default(false))
+                    .col(decimal_null(GrowthMeasurements::WeightForAgeZ).decimal_len(5, 2))
+                    .col(decimal_null(GrowthMeasurements::HeightForAgeZ).decimal_len(5, 2))
+                    .col(decimal_null(GrowthMeasurements::WeightForHeightZ).decimal_len(5, 2))
+                    .col(string(GrowthMeasurements::NutritionStatus).string_len(50))
+                    .col(array_null(
+                        GrowthMeasurements::Flags,
+                        ColumnType::String(StringLen::None),
+                    ))
+                    .col(uuid_null(GrowthMeasurements::RecordedBy))
+                    .col(timestamp_null(GrowthMeasurements::DeletedAt))
+                    .col(
+                        timestamp(GrowthMeasurements::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(GrowthMeasurements::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_growth_measurements_patient_pid = Index::create()
+            .name("idx_growth_measurements_patient_pid")
+            .table(GrowthMeasurements::Table)
+            .col(GrowthMeasurements::PatientPid)
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(GrowthMeasurements::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GrowthMeasurements {
+    Table,
+    Id,
+    Pid,
+    PatientPid,
+    MeasuredOn,
+    AgeDays,
+    WeightKg,
+    HeightCm,
+    MuacCm,
+    Oedema,
+    WeightForAgeZ,
+    HeightForAgeZ,
+    WeightForHeightZ,
+    NutritionStatus,
+    Flags,
+    RecordedBy,
+    DeletedAt,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,66 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PatientDiagnoses::Table)
+                    .if_not_exists()
+                    .col(pk_auto(PatientDiagnoses::Id))
+                    .col(
+                        uuid_uniq(PatientDiagnoses::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(uuid(PatientDiagnoses::PatientPid))
+                    .col(string(PatientDiagnoses::Code).string_len(20))
+                    .col(string_null(PatientDiagnoses::Description).string_len(255))
+                    .col(date(PatientDiagnoses::DiagnosedOn))
+                    .col(uuid_null(PatientDiagnoses::RecordedBy))
+                    .col(timestamp_null(PatientDiagnoses::DeletedAt))
+                    .col(
+                        timestamp(PatientDiagnoses::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(PatientDiagnoses::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_patient_diagnoses_patient_pid = Index::create()
+            .name("idx_patient_diagnoses_patient_pid")
+            .table(PatientDiagnoses::Table)
+            .col(PatientDiagnoses::PatientPid)
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PatientDiagnoses::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PatientDiagnoses {
+    Table,
+    Id,
+    Pid,
+    PatientPid,
+    Code,
+    Description,
+    DiagnosedOn,
+    RecordedBy,
+    DeletedAt,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,121 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("registry_enrolment_status"))
+                    .values([
+                        Alias::new("active"),
+                        Alias::new("transferred_out"),
+                        Alias::new("lost_to_follow_up"),
+                        Alias::new("died"),
+                        Alias::new("exited"),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(RegistryEnrolments::Table)
+                    .if_not_exists()
+                    .col(pk_auto(RegistryEnrolments::Id))
+                    .col(
+                        uuid_uniq(RegistryEnrolments::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(uuid(RegistryEnrolments::RegistryPid))
+                    .col(uuid(RegistryEnrolments::PatientPid))
+                    .col(string(RegistryEnrolments::DiagnosisCode).string_len(20))
+                    .col(date(RegistryEnrolments::EnrolledOn))
+                    .col(
+                        enumeration(
+                            RegistryEnrolments::Status,
+                            Alias::new("registry_enrolment_status"),
+                            vec![
+                                Alias::new("active"),
+                                Alias::new("transferred_out"),
+                                Alias::new("lost_to_follow_up"),
+                                Alias::new("died"),
+                                Alias::new("exited"),
+                            ],
+                        )
+                        .default("active"),
+                    )
+                    .col(date_null(RegistryEnrolments::LastFollowUpOn))
+                    .col(date_null(RegistryEnrolments::NextFollowUpOn))
+                    .col(date_null(RegistryEnrolments::ExitedOn))
+                    .col(text_null(RegistryEnrolments::ExitReason))
+                    .col(uuid_null(RegistryEnrolments::EnrolledBy))
+                    .col(timestamp_null(RegistryEnrolments::DeletedAt))
+                    .col(
+                        timestamp(RegistryEnrolments::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(RegistryEnrolments::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _uniq_registry_enrolments_patient = Index::create()
+            .name("uniq_registry_enrolments_patient")
+            .table(RegistryEnrolments::Table)
+            .col(RegistryEnrolments::RegistryPid)
+            .col(RegistryEnrolments::PatientPid)
+            .unique()
+            .to_owned();
+
+        let _idx_registry_enrolments_next_follow_up_on = Index::create()
+            .name("idx_registry_enrolments_next_follow_up_on")
+            .table(RegistryEnrolments::Table)
+            .col(RegistryEnrolments::NextFollowUpOn)
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(RegistryEnrolments::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(Alias::new("registry_enrolment_status"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum RegistryEnrolments {
+    Table,
+    Id,
+    Pid,
+    RegistryPid,
+    PatientPid,
+    DiagnosisCode,
+    EnrolledOn,
+    Status,
+    LastFollowUpOn,
+    NextFollowUpOn,
+    ExitedOn,
+    ExitReason,
+    EnrolledBy,
+    DeletedAt,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,93 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(RegistryFollowUps::Table)
+                    .if_not_exists()
+                    .col(pk_auto(RegistryFollowUps::Id))
+                    .col(
+                        uuid_uniq(RegistryFollowUps::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer(RegistryFollowUps::EnrolmentId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-registry_follow_ups-enrolment_id")
+                            .from(RegistryFollowUps::Table, RegistryFollowUps::EnrolmentId)
+                            .to(RegistryEnrolments::Table, RegistryEnrolments::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(date(RegistryFollowUps::VisitDate))
+                    .col(integer_null(RegistryFollowUps::BpSystolic))
+                    .col(integer_null(RegistryFollowUps::BpDiastolic))
+                    .col(decimal_null(RegistryFollowUps::Hba1c).decimal_len(4, 1))
+                    .col(decimal_null(RegistryFollowUps::FastingGlucose).decimal_len(5, 1))
+                    .col(integer_null(RegistryFollowUps::ViralLoad))
+                    .col(integer_null(RegistryFollowUps::Cd4Count))
+                    .col(string_null(RegistryFollowUps::Adherence).string_len(20))
+                    .col(date_null(RegistryFollowUps::NextFollowUpOn))
+                    .col(text_null(RegistryFollowUps::Notes))
+                    .col(uuid_null(RegistryFollowUps::RecordedBy))
+                    .col(timestamp_null(RegistryFollowUps::DeletedAt))
+                    .col(
+                        timestamp(RegistryFollowUps::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(RegistryFollowUps::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_registry_follow_ups_enrolment_id = Index::create()
+            .name("idx_registry_follow_ups_enrolment_id")
+            .table(RegistryFollowUps::Table)
+            .col(RegistryFollowUps::EnrolmentId)
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(RegistryFollowUps::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum RegistryFollowUps {
+    Table,
+    Id,
+    Pid,
+    EnrolmentId,
+    VisitDate,
+    BpSystolic,
+    BpDiastolic,
+    Hba1c,
+    FastingGlucose,
+    ViralLoad,
+    Cd4Count,
+    Adherence,
+    NextFollowUpOn,
+    Notes,
+    RecordedBy,
+    DeletedAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum RegistryEnrolments {
+    Table,
+    Id,
+}
@@ -0,0 +1,125 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("tracing_task_status"))
+                    .values([
+                        Alias::new("pending"),
+                        Alias::new("contacted"),
+                        Alias::new("returned"),
+                        Alias::new("unreachable"),
+                        Alias::new("closed"),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(DefaulterTracingTasks::Table)
+                    .if_not_exists()
+                    .col(pk_auto(DefaulterTracingTasks::Id))
+                    .col(
+                        uuid_uniq(DefaulterTracingTasks::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer(DefaulterTracingTasks::EnrolmentId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-defaulter_tracing_tasks-enrolment_id")
+                            .from(
+                                DefaulterTracingTasks::Table,
+                                DefaulterTracingTasks::EnrolmentId,
+                            )
+                            .to(RegistryEnrolments::Table, RegistryEnrolments::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(date(DefaulterTracingTasks::MissedFollowUpOn))
+                    .col(
+                        enumeration(
+                            DefaulterTracingTasks::Status,
+                            Alias::new("tracing_task_status"),
+                            vec![
+                                Alias::new("pending"),
+                                Alias::new("contacted"),
+                                Alias::new("returned"),
+                                Alias::new("unreachable"),
+                                Alias::new("closed"),
+                            ],
+                        )
+                        .default("pending"),
+                    )
+                    .col(integer(DefaulterTracingTasks::Attempts).default(0))
+                    .col(timestamp_null(DefaulterTracingTasks::SmsSentAt))
+                    .col(text_null(DefaulterTracingTasks::OutcomeNotes))
+                    .col(uuid_null(DefaulterTracingTasks::AssignedTo))
+                    .col(timestamp_null(DefaulterTracingTasks::ClosedAt))
+                    .col(
+                        timestamp(DefaulterTracingTasks::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(DefaulterTracingTasks::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _uniq_defaulter_tracing_tasks_missed = Index::create()
+            .name("uniq_defaulter_tracing_tasks_missed")
+            .table(DefaulterTracingTasks::Table)
+            .col(DefaulterTracingTasks::EnrolmentId)
+            .col(DefaulterTracingTasks::MissedFollowUpOn)
+            .unique()
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(DefaulterTracingTasks::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(Alias::new("tracing_task_status"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum DefaulterTracingTasks {
+    Table,
+    Id,
+    Pid,
+    EnrolmentId,
+    MissedFollowUpOn,
+    Status,
+    Attempts,
+    SmsSentAt,
+    OutcomeNotes,
+    AssignedTo,
+    ClosedAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum RegistryEnrolments {
+    Table,
+    Id,
+}
@@ -0,0 +1,90 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ScreeningResults::Table)
+                    .if_not_exists()
+                    .col(pk_auto(ScreeningResults::Id))
+                    .col(
+                        uuid_uniq(ScreeningResults::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(uuid(ScreeningResults::PatientPid))
+                    .col(string(ScreeningResults::Instrument).string_len(20))
+                    .col(json_binary(ScreeningResults::Answers))
+                    .col(integer(ScreeningResults::TotalScore))
+                    .col(string(ScreeningResults::Severity).string_len(50))
+                    .col(boolean(ScreeningResults::SafetyFlag).default(false))
+                    .col(string(ScreeningResults::Source).string_len(20))
+                    .col(uuid_null(ScreeningResults::SelfScreeningPid))
+                    .col(uuid_null(ScreeningResults::AdministeredBy))
+                    .col(timestamp(ScreeningResults::CompletedAt))
+                    .col(uuid_null(ScreeningResults::ReviewedBy))
+                    .col(timestamp_null(ScreeningResults::ReviewedAt))
+                    .col(text_null(ScreeningResults::Notes))
+                    .col(timestamp_null(ScreeningResults::DeletedAt))
+                    .col(
+                        timestamp(ScreeningResults::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(ScreeningResults::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_screening_results_patient_pid = Index::create()
+            .name("idx_screening_results_patient_pid")
+            .table(ScreeningResults::Table)
+            .col(ScreeningResults::PatientPid)
+            .col(ScreeningResults::Instrument)
+            .to_owned();
+
+        let _uniq_screening_results_self_screening_pid = Index::create()
+            .name("uniq_screening_results_self_screening_pid")
+            .table(ScreeningResults::Table)
+            .col(ScreeningResults::SelfScreeningPid)
+            .unique()
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ScreeningResults::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ScreeningResults {
+    Table,
+    Id,
+    Pid,
+    PatientPid,
+    Instrument,
+    Answers,
+    TotalScore,
+    Severity,
+    SafetyFlag,
+    Source,
+    SelfScreeningPid,
+    AdministeredBy,
+    CompletedAt,
+    ReviewedBy,
+    ReviewedAt,
+    Notes,
+    DeletedAt,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,148 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("clinical_task_priority"))
+                    .values([
+                        Alias::new("low"),
+                        Alias::new("normal"),
+                        Alias::new("high"),
+                        Alias::new("urgent"),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("clinical_task_status"))
+                    .values([
+                        Alias::new("open"),
+                        Alias::new("in_progress"),
+                        Alias::new("completed"),
+                        Alias::new("cancelled"),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ClinicalTasks::Table)
+                    .if_not_exists()
+                    .col(pk_auto(ClinicalTasks::Id))
+                    .col(
+                        uuid_uniq(ClinicalTasks::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(uuid(ClinicalTasks::PatientPid))
+                    .col(string(ClinicalTasks::Title).string_len(255))
+                    .col(text_null(ClinicalTasks::Description))
+                    .col(
+                        enumeration(
+                            ClinicalTasks::Priority,
+                            Alias::new("clinical_task_priority"),
+                            vec![
+                                Alias::new("low"),
+                                Alias::new("normal"),
+                                Alias::new("high"),
+                                Alias::new("urgent"),
+                            ],
+                        )
+                        .default("normal"),
+                    )
+                    .col(
+                        enumeration(
+                            ClinicalTasks::Status,
+                            Alias::new("clinical_task_status"),
+                            vec![
+                                Alias::new("open"),
+                                Alias::new("in_progress"),
+                                Alias::new("completed"),
+                                Alias::new("cancelled"),
+                            ],
+                        )
+                        .default("open"),
+                    )
+                    .col(string(ClinicalTasks::SourceType).string_len(50))
+                    .col(uuid_null(ClinicalTasks::SourcePid))
+                    .col(uuid_null(ClinicalTasks::AssignedTo))
+                    .col(timestamp_null(ClinicalTasks::DueAt))
+                    .col(uuid_null(ClinicalTasks::CompletedBy))
+                    .col(timestamp_null(ClinicalTasks::CompletedAt))
+                    .col(text_null(ClinicalTasks::Resolution))
+                    .col(
+                        timestamp(ClinicalTasks::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(ClinicalTasks::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_clinical_tasks_status = Index::create()
+            .name("idx_clinical_tasks_status")
+            .table(ClinicalTasks::Table)
+            .col(ClinicalTasks::Status)
+            .col(ClinicalTasks::Priority)
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ClinicalTasks::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(Alias::new("clinical_task_status"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(Alias::new("clinical_task_priority"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ClinicalTasks {
+    Table,
+    Id,
+    Pid,
+    PatientPid,
+    Title,
+    Description,
+    Priority,
+    Status,
+    SourceType,
+    SourcePid,
+    AssignedTo,
+    DueAt,
+    CompletedBy,
+    CompletedAt,
+    Resolution,
+    CreatedAt,
+    UpdatedAt,
+}
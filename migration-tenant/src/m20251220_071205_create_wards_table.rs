@@ -0,0 +1,56 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Wards::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Wards::Id))
+                    .col(
+                        uuid_uniq(Wards::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(string_uniq(Wards::Name).string_len(100))
+                    .col(string_null(Wards::WardType).string_len(50))
+                    .col(text_null(Wards::Description))
+                    .col(boolean(Wards::IsActive).default(true))
+                    .col(timestamp_null(Wards::DeletedAt))
+                    .col(
+                        timestamp(Wards::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(Wards::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Wards::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Wards {
+    Table,
+    Id,
+    Pid,
+    Name,
+    WardType,
+    Description,
+    IsActive,
+    DeletedAt,
+    CreatedAt,
+    UpdatedAt,
+}
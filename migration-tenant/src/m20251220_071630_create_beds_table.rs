@@ -0,0 +1,108 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("bed_status"))
+                    .values([
+                        Alias::new("free"),
+                        Alias::new("occupied"),
+                        Alias::new("cleaning"),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Beds::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Beds::Id))
+                    .col(
+                        uuid_uniq(Beds::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer(Beds::WardId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-beds-ward_id")
+                            .from(Beds::Table, Beds::WardId)
+                            .to(Wards::Table, Wards::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(string(Beds::BedNumber).string_len(20))
+                    .col(
+                        enumeration(
+                            Beds::Status,
+                            Alias::new("bed_status"),
+                            vec![
+                                Alias::new("free"),
+                                Alias::new("occupied"),
+                                Alias::new("cleaning"),
+                            ],
+                        )
+                        .default("free"),
+                    )
+                    .col(boolean(Beds::IsActive).default(true))
+                    .col(timestamp_null(Beds::DeletedAt))
+                    .col(
+                        timestamp(Beds::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(Beds::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_beds_ward_id_bed_number = Index::create()
+            .name("idx_beds_ward_id_bed_number")
+            .table(Beds::Table)
+            .col(Beds::WardId)
+            .col(Beds::BedNumber)
+            .unique()
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Beds::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(Alias::new("bed_status")).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Beds {
+    Table,
+    Id,
+    Pid,
+    WardId,
+    BedNumber,
+    Status,
+    IsActive,
+    DeletedAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Wards {
+    Table,
+    Id,
+}
@@ -0,0 +1,157 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("admission_status"))
+                    .values([Alias::new("admitted"), Alias::new("discharged")])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("discharge_disposition"))
+                    .values([
+                        Alias::new("home"),
+                        Alias::new("referred"),
+                        Alias::new("against_medical_advice"),
+                        Alias::new("absconded"),
+                        Alias::new("deceased"),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Admissions::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Admissions::Id))
+                    .col(
+                        uuid_uniq(Admissions::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(uuid(Admissions::PatientPid))
+                    .col(uuid_null(Admissions::EncounterPid))
+                    .col(integer(Admissions::BedId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-admissions-bed_id")
+                            .from(Admissions::Table, Admissions::BedId)
+                            .to(Beds::Table, Beds::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(
+                        enumeration(
+                            Admissions::Status,
+                            Alias::new("admission_status"),
+                            vec![Alias::new("admitted"), Alias::new("discharged")],
+                        )
+                        .default("admitted"),
+                    )
+                    .col(text(Admissions::AdmissionReason))
+                    .col(string_null(Admissions::AdmittingDiagnosis).string_len(255))
+                    .col(
+                        timestamp(Admissions::AdmittedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(uuid_null(Admissions::AdmittedBy))
+                    .col(timestamp_null(Admissions::DischargedAt))
+                    .col(uuid_null(Admissions::DischargedBy))
+                    .col(enumeration_null(
+                        Admissions::DischargeDisposition,
+                        Alias::new("discharge_disposition"),
+                        vec![
+                            Alias::new("home"),
+                            Alias::new("referred"),
+                            Alias::new("against_medical_advice"),
+                            Alias::new("absconded"),
+                            Alias::new("deceased"),
+                        ],
+                    ))
+                    .col(string_null(Admissions::DischargeDiagnosis).string_len(255))
+                    .col(text_null(Admissions::DischargeSummary))
+                    .col(text_null(Admissions::DischargeInstructions))
+                    .col(uuid_null(Admissions::DischargeDocumentFilePid))
+                    .col(integer_null(Admissions::LengthOfStayDays))
+                    .col(
+                        timestamp(Admissions::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(Admissions::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_admissions_patient_pid = Index::create()
+            .name("idx_admissions_patient_pid")
+            .table(Admissions::Table)
+            .col(Admissions::PatientPid)
+            .col(Admissions::Status)
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Admissions::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(Alias::new("discharge_disposition"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(Alias::new("admission_status")).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Admissions {
+    Table,
+    Id,
+    Pid,
+    PatientPid,
+    EncounterPid,
+    BedId,
+    Status,
+    AdmissionReason,
+    AdmittingDiagnosis,
+    AdmittedAt,
+    AdmittedBy,
+    DischargedAt,
+    DischargedBy,
+    DischargeDisposition,
+    DischargeDiagnosis,
+    DischargeSummary,
+    DischargeInstructions,
+    DischargeDocumentFilePid,
+    LengthOfStayDays,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Beds {
+    Table,
+    Id,
+}
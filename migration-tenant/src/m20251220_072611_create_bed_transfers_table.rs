@@ -0,0 +1,94 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(BedTransfers::Table)
+                    .if_not_exists()
+                    .col(pk_auto(BedTransfers::Id))
+                    .col(
+                        uuid_uniq(BedTransfers::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer(BedTransfers::AdmissionId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-bed_transfers-admission_id")
+                            .from(BedTransfers::Table, BedTransfers::AdmissionId)
+                            .to(Admissions::Table, Admissions::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(integer(BedTransfers::FromBedId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-bed_transfers-from_bed_id")
+                            .from(BedTransfers::Table, BedTransfers::FromBedId)
+                            .to(Beds::Table, Beds::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(integer(BedTransfers::ToBedId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-bed_transfers-to_bed_id")
+                            .from(BedTransfers::Table, BedTransfers::ToBedId)
+                            .to(Beds::Table, Beds::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(text_null(BedTransfers::Reason))
+                    .col(uuid_null(BedTransfers::TransferredBy))
+                    .col(
+                        timestamp(BedTransfers::TransferredAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(BedTransfers::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(BedTransfers::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(BedTransfers::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum BedTransfers {
+    Table,
+    Id,
+    Pid,
+    AdmissionId,
+    FromBedId,
+    ToBedId,
+    Reason,
+    TransferredBy,
+    TransferredAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Admissions {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Beds {
+    Table,
+    Id,
+}
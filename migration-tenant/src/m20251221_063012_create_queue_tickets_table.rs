@@ -0,0 +1,182 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("triage_priority"))
+                    .values([
+                        Alias::new("emergency"),
+                        Alias::new("urgent"),
+                        Alias::new("routine"),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("queue_stage"))
+                    .values([
+                        Alias::new("triage"),
+                        Alias::new("consultation"),
+                        Alias::new("pharmacy"),
+                        Alias::new("cashier"),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("queue_ticket_status"))
+                    .values([
+                        Alias::new("waiting"),
+                        Alias::new("called"),
+                        Alias::new("in_service"),
+                        Alias::new("completed"),
+                        Alias::new("cancelled"),
+                        Alias::new("no_show"),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(QueueTickets::Table)
+                    .if_not_exists()
+                    .col(pk_auto(QueueTickets::Id))
+                    .col(
+                        uuid_uniq(QueueTickets::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(uuid(QueueTickets::PatientPid))
+                    .col(date(QueueTickets::QueueDate))
+                    .col(integer(QueueTickets::TicketSequence))
+                    .col(string(QueueTickets::TicketNumber).string_len(20))
+                    .col(
+                        enumeration(
+                            QueueTickets::Priority,
+                            Alias::new("triage_priority"),
+                            vec![
+                                Alias::new("emergency"),
+                                Alias::new("urgent"),
+                                Alias::new("routine"),
+                            ],
+                        )
+                        .default("routine"),
+                    )
+                    .col(
+                        enumeration(
+                            QueueTickets::Stage,
+                            Alias::new("queue_stage"),
+                            vec![
+                                Alias::new("triage"),
+                                Alias::new("consultation"),
+                                Alias::new("pharmacy"),
+                                Alias::new("cashier"),
+                            ],
+                        )
+                        .default("triage"),
+                    )
+                    .col(
+                        enumeration(
+                            QueueTickets::Status,
+                            Alias::new("queue_ticket_status"),
+                            vec![
+                                Alias::new("waiting"),
+                                Alias::new("called"),
+                                Alias::new("in_service"),
+                                Alias::new("completed"),
+                                Alias::new("cancelled"),
+                                Alias::new("no_show"),
+                            ],
+                        )
+                        .default("waiting"),
+                    )
+                    .col(string_null(QueueTickets::Room).string_len(50))
+                    .col(
+                        timestamp(QueueTickets::CheckedInAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(uuid_null(QueueTickets::CheckedInBy))
+                    .col(timestamp_null(QueueTickets::CompletedAt))
+                    .col(text_null(QueueTickets::CancelReason))
+                    .col(text_null(QueueTickets::Notes))
+                    .col(
+                        timestamp(QueueTickets::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(QueueTickets::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_queue_tickets_queue_date_stage = Index::create()
+            .name("idx_queue_tickets_queue_date_stage")
+            .table(QueueTickets::Table)
+            .col(QueueTickets::QueueDate)
+            .col(QueueTickets::Stage)
+            .col(QueueTickets::Status)
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(QueueTickets::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(Alias::new("queue_ticket_status"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(Alias::new("queue_stage")).to_owned())
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(Alias::new("triage_priority")).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum QueueTickets {
+    Table,
+    Id,
+    Pid,
+    PatientPid,
+    QueueDate,
+    TicketSequence,
+    TicketNumber,
+    Priority,
+    Stage,
+    Status,
+    Room,
+    CheckedInAt,
+    CheckedInBy,
+    CompletedAt,
+    CancelReason,
+    Notes,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,87 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(QueueStageVisits::Table)
+                    .if_not_exists()
+                    .col(pk_auto(QueueStageVisits::Id))
+                    .col(
+                        uuid_uniq(QueueStageVisits::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer(QueueStageVisits::TicketId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-queue_stage_visits-ticket_id")
+                            .from(QueueStageVisits::Table, QueueStageVisits::TicketId)
+                            .to(QueueTickets::Table, QueueTickets::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(enumeration(
+                        QueueStageVisits::Stage,
+                        Alias::new("queue_stage"),
+                        vec![
+                            Alias::new("triage"),
+                            Alias::new("consultation"),
+                            Alias::new("pharmacy"),
+                            Alias::new("cashier"),
+                        ],
+                    ))
+                    .col(
+                        timestamp(QueueStageVisits::QueuedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(timestamp_null(QueueStageVisits::CalledAt))
+                    .col(timestamp_null(QueueStageVisits::StartedAt))
+                    .col(timestamp_null(QueueStageVisits::CompletedAt))
+                    .col(uuid_null(QueueStageVisits::HandledBy))
+                    .col(string_null(QueueStageVisits::Room).string_len(50))
+                    .col(
+                        timestamp(QueueStageVisits::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(QueueStageVisits::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(QueueStageVisits::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum QueueStageVisits {
+    Table,
+    Id,
+    Pid,
+    TicketId,
+    Stage,
+    QueuedAt,
+    CalledAt,
+    StartedAt,
+    CompletedAt,
+    HandledBy,
+    Room,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum QueueTickets {
+    Table,
+    Id,
+}
@@ -0,0 +1,93 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TriageAssessments::Table)
+                    .if_not_exists()
+                    .col(pk_auto(TriageAssessments::Id))
+                    .col(
+                        uuid_uniq(TriageAssessments::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer(TriageAssessments::TicketId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-triage_assessments-ticket_id")
+                            .from(TriageAssessments::Table, TriageAssessments::TicketId)
+                            .to(QueueTickets::Table, QueueTickets::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(uuid(TriageAssessments::PatientPid))
+                    .col(enumeration(
+                        TriageAssessments::Priority,
+                        Alias::new("triage_priority"),
+                        vec![
+                            Alias::new("emergency"),
+                            Alias::new("urgent"),
+                            Alias::new("routine"),
+                        ],
+                    ))
+                    .col(text(TriageAssessments::ChiefComplaint))
+                    .col(decimal_null(TriageAssessments::Temperature).decimal_len(4, 1))
+                    .col(integer_null(TriageAssessments::PulseRate))
+                    .col(integer_null(TriageAssessments::RespiratoryRate))
+                    .col(integer_null(TriageAssessments::BpSystolic))
+                    .col(integer_null(TriageAssessments::BpDiastolic))
+                    .col(integer_null(TriageAssessments::OxygenSaturation))
+                    .col(decimal_null(TriageAssessments::WeightKg).decimal_len(5, 2))
+                    .col(text_null(TriageAssessments::Notes))
+                    .col(uuid_null(TriageAssessments::AssessedBy))
+                    .col(
+                        timestamp(TriageAssessments::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(TriageAssessments::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TriageAssessments::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TriageAssessments {
+    Table,
+    Id,
+    Pid,
+    TicketId,
+    PatientPid,
+    Priority,
+    ChiefComplaint,
+    Temperature,
+    PulseRate,
+    RespiratoryRate,
+    BpSystolic,
+    BpDiastolic,
+    OxygenSaturation,
+    WeightKg,
+    Notes,
+    AssessedBy,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum QueueTickets {
+    Table,
+    Id,
+}
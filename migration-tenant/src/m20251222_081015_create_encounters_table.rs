@@ -0,0 +1,98 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("encounter_type"))
+                    .values([
+                        Alias::new("outpatient"),
+                        Alias::new("inpatient"),
+                        Alias::new("telemedicine"),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Encounters::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Encounters::Id))
+                    .col(
+                        uuid_uniq(Encounters::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(uuid(Encounters::PatientPid))
+                    .col(
+                        enumeration(
+                            Encounters::EncounterType,
+                            Alias::new("encounter_type"),
+                            vec![
+                                Alias::new("outpatient"),
+                                Alias::new("inpatient"),
+                                Alias::new("telemedicine"),
+                            ],
+                        )
+                        .default("outpatient"),
+                    )
+                    .col(uuid_null(Encounters::PractitionerId))
+                    .col(timestamp(Encounters::StartedAt))
+                    .col(timestamp_null(Encounters::EndedAt))
+                    .col(text_null(Encounters::Reason))
+                    .col(text_null(Encounters::Notes))
+                    .col(
+                        timestamp(Encounters::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(Encounters::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_encounters_patient_pid = Index::create()
+            .name("idx_encounters_patient_pid")
+            .table(Encounters::Table)
+            .col(Encounters::PatientPid)
+            .col(Encounters::StartedAt)
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Encounters::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(Alias::new("encounter_type")).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Encounters {
+    Table,
+    Id,
+    Pid,
+    PatientPid,
+    EncounterType,
+    PractitionerId,
+    StartedAt,
+    EndedAt,
+    Reason,
+    Notes,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,136 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("telemedicine_session_status"))
+                    .values([
+                        Alias::new("scheduled"),
+                        Alias::new("waiting"),
+                        Alias::new("in_call"),
+                        Alias::new("ended"),
+                        Alias::new("cancelled"),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TelemedicineSessions::Table)
+                    .if_not_exists()
+                    .col(pk_auto(TelemedicineSessions::Id))
+                    .col(
+                        uuid_uniq(TelemedicineSessions::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(uuid(TelemedicineSessions::PatientPid))
+                    .col(uuid(TelemedicineSessions::PractitionerId))
+                    .col(timestamp(TelemedicineSessions::ScheduledAt))
+                    .col(text_null(TelemedicineSessions::Reason))
+                    .col(
+                        enumeration(
+                            TelemedicineSessions::Status,
+                            Alias::new("telemedicine_session_status"),
+                            vec![
+                                Alias::new("scheduled"),
+                                Alias::new("waiting"),
+                                Alias::new("in_call"),
+                                Alias::new("ended"),
+                                Alias::new("cancelled"),
+                            ],
+                        )
+                        .default("scheduled"),
+                    )
+                    .col(timestamp_null(TelemedicineSessions::PatientJoinedAt))
+                    .col(timestamp_null(TelemedicineSessions::PractitionerJoinedAt))
+                    .col(timestamp_null(TelemedicineSessions::StartedAt))
+                    .col(timestamp_null(TelemedicineSessions::EndedAt))
+                    .col(integer_null(TelemedicineSessions::DurationSeconds))
+                    .col(text_null(TelemedicineSessions::CallNotes))
+                    .col(text_null(TelemedicineSessions::CancelReason))
+                    .col(integer_null(TelemedicineSessions::EncounterId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-telemedicine_sessions-encounter_id")
+                            .from(
+                                TelemedicineSessions::Table,
+                                TelemedicineSessions::EncounterId,
+                            )
+                            .to(Encounters::Table, Encounters::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .col(uuid_null(TelemedicineSessions::CreatedBy))
+                    .col(
+                        timestamp(TelemedicineSessions::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(TelemedicineSessions::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_telemedicine_sessions_practitioner = Index::create()
+            .name("idx_telemedicine_sessions_practitioner")
+            .table(TelemedicineSessions::Table)
+            .col(TelemedicineSessions::PractitionerId)
+            .col(TelemedicineSessions::ScheduledAt)
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TelemedicineSessions::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(Alias::new("telemedicine_session_status"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TelemedicineSessions {
+    Table,
+    Id,
+    Pid,
+    PatientPid,
+    PractitionerId,
+    ScheduledAt,
+    Reason,
+    Status,
+    PatientJoinedAt,
+    PractitionerJoinedAt,
+    StartedAt,
+    EndedAt,
+    DurationSeconds,
+    CallNotes,
+    CancelReason,
+    EncounterId,
+    CreatedBy,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Encounters {
+    Table,
+    Id,
+}
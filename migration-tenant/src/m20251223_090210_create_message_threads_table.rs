@@ -0,0 +1,149 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("message_thread_status"))
+                    .values([Alias::new("open"), Alias::new("closed")])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("message_sender_type"))
+                    .values([Alias::new("patient"), Alias::new("staff")])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(MessageThreads::Table)
+                    .if_not_exists()
+                    .col(pk_auto(MessageThreads::Id))
+                    .col(
+                        uuid_uniq(MessageThreads::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(uuid(MessageThreads::PatientPid))
+                    .col(integer_null(MessageThreads::EncounterId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-message_threads-encounter_id")
+                            .from(MessageThreads::Table, MessageThreads::EncounterId)
+                            .to(Encounters::Table, Encounters::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .col(string(MessageThreads::Subject).string_len(200))
+                    .col(
+                        enumeration(
+                            MessageThreads::Status,
+                            Alias::new("message_thread_status"),
+                            vec![Alias::new("open"), Alias::new("closed")],
+                        )
+                        .default("open"),
+                    )
+                    .col(uuid_null(MessageThreads::AssignedTo))
+                    .col(timestamp_null(MessageThreads::AssignedAt))
+                    .col(
+                        timestamp(MessageThreads::LastMessageAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(enumeration(
+                        MessageThreads::LastMessageFrom,
+                        Alias::new("message_sender_type"),
+                        vec![Alias::new("patient"), Alias::new("staff")],
+                    ))
+                    .col(timestamp_null(MessageThreads::AwaitingReplySince))
+                    .col(timestamp_null(MessageThreads::ReplyDueAt))
+                    .col(timestamp_null(MessageThreads::SlaBreachedAt))
+                    .col(timestamp_null(MessageThreads::ClosedAt))
+                    .col(uuid_null(MessageThreads::ClosedBy))
+                    .col(
+                        timestamp(MessageThreads::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(MessageThreads::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_message_threads_patient_pid = Index::create()
+            .name("idx_message_threads_patient_pid")
+            .table(MessageThreads::Table)
+            .col(MessageThreads::PatientPid)
+            .to_owned();
+
+        let _idx_message_threads_reply_due_at = Index::create()
+            .name("idx_message_threads_reply_due_at")
+            .table(MessageThreads::Table)
+            .col(MessageThreads::Status)
+            .col(MessageThreads::ReplyDueAt)
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(MessageThreads::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(Alias::new("message_sender_type"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(Alias::new("message_thread_status"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum MessageThreads {
+    Table,
+    Id,
+    Pid,
+    PatientPid,
+    EncounterId,
+    Subject,
+    Status,
+    AssignedTo,
+    AssignedAt,
+    LastMessageAt,
+    LastMessageFrom,
+    AwaitingReplySince,
+    ReplyDueAt,
+    SlaBreachedAt,
+    ClosedAt,
+    ClosedBy,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Encounters {
+    Table,
+    Id,
+}
@@ -0,0 +1,84 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ThreadMessages::Table)
+                    .if_not_exists()
+                    .col(pk_auto(ThreadMessages::Id))
+                    .col(
+                        uuid_uniq(ThreadMessages::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer(ThreadMessages::ThreadId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-thread_messages-thread_id")
+                            .from(ThreadMessages::Table, ThreadMessages::ThreadId)
+                            .to(MessageThreads::Table, MessageThreads::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(enumeration(
+                        ThreadMessages::SenderType,
+                        Alias::new("message_sender_type"),
+                        vec![Alias::new("patient"), Alias::new("staff")],
+                    ))
+                    .col(uuid(ThreadMessages::SenderId))
+                    .col(text(ThreadMessages::Body))
+                    .col(timestamp_null(ThreadMessages::ReadAt))
+                    .col(uuid_null(ThreadMessages::ReadBy))
+                    .col(
+                        timestamp(ThreadMessages::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(ThreadMessages::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_thread_messages_thread_id = Index::create()
+            .name("idx_thread_messages_thread_id")
+            .table(ThreadMessages::Table)
+            .col(ThreadMessages::ThreadId)
+            .col(ThreadMessages::CreatedAt)
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ThreadMessages::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ThreadMessages {
+    Table,
+    Id,
+    Pid,
+    ThreadId,
+    SenderType,
+    SenderId,
+    Body,
+    ReadAt,
+    ReadBy,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum MessageThreads {
+    Table,
+    Id,
+}
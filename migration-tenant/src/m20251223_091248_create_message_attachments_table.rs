@@ -0,0 +1,64 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(MessageAttachments::Table)
+                    .if_not_exists()
+                    .col(pk_auto(MessageAttachments::Id))
+                    .col(
+                        uuid_uniq(MessageAttachments::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer(MessageAttachments::MessageId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-message_attachments-message_id")
+                            .from(MessageAttachments::Table, MessageAttachments::MessageId)
+                            .to(ThreadMessages::Table, ThreadMessages::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(uuid(MessageAttachments::FilePid))
+                    .col(string(MessageAttachments::OriginalName))
+                    .col(string(MessageAttachments::ContentType))
+                    .col(big_integer(MessageAttachments::SizeBytes))
+                    .col(
+                        timestamp(MessageAttachments::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(MessageAttachments::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum MessageAttachments {
+    Table,
+    Id,
+    Pid,
+    MessageId,
+    FilePid,
+    OriginalName,
+    ContentType,
+    SizeBytes,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum ThreadMessages {
+    Table,
+    Id,
+}
@@ -0,0 +1,83 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PharmacyProducts::Table)
+                    .if_not_exists()
+                    .col(pk_auto(PharmacyProducts::Id))
+                    .col(
+                        uuid_uniq(PharmacyProducts::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(string_null(PharmacyProducts::Sku).string_len(50))
+                    .col(string(PharmacyProducts::GenericName).string_len(150))
+                    .col(string_null(PharmacyProducts::BrandName).string_len(150))
+                    .col(string_null(PharmacyProducts::Strength).string_len(50))
+                    .col(string(PharmacyProducts::DosageForm).string_len(50))
+                    .col(integer(PharmacyProducts::PackSize).default(1))
+                    .col(string(PharmacyProducts::Unit).string_len(30))
+                    .col(string_null(PharmacyProducts::Category).string_len(100))
+                    .col(integer(PharmacyProducts::ReorderLevel).default(0))
+                    .col(boolean(PharmacyProducts::IsActive).default(true))
+                    .col(timestamp_null(PharmacyProducts::DeletedAt))
+                    .col(
+                        timestamp(PharmacyProducts::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(PharmacyProducts::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_pharmacy_products_sku = Index::create()
+            .name("idx_pharmacy_products_sku")
+            .table(PharmacyProducts::Table)
+            .col(PharmacyProducts::Sku)
+            .unique()
+            .to_owned();
+
+        let _idx_pharmacy_products_generic_name = Index::create()
+            .name("idx_pharmacy_products_generic_name")
+            .table(PharmacyProducts::Table)
+            .col(PharmacyProducts::GenericName)
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PharmacyProducts::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PharmacyProducts {
+    Table,
+    Id,
+    Pid,
+    Sku,
+    GenericName,
+    BrandName,
+    Strength,
+    DosageForm,
+    PackSize,
+    Unit,
+    Category,
+    ReorderLevel,
+    IsActive,
+    DeletedAt,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,95 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("pharmacy_store_type"))
+                    .values([
+                        Alias::new("main_store"),
+                        Alias::new("dispensary"),
+                        Alias::new("ward"),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(PharmacyStores::Table)
+                    .if_not_exists()
+                    .col(pk_auto(PharmacyStores::Id))
+                    .col(
+                        uuid_uniq(PharmacyStores::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(string(PharmacyStores::Name).string_len(100))
+                    .col(string_uniq(PharmacyStores::Code).string_len(20))
+                    .col(
+                        enumeration(
+                            PharmacyStores::StoreType,
+                            Alias::new("pharmacy_store_type"),
+                            vec![
+                                Alias::new("main_store"),
+                                Alias::new("dispensary"),
+                                Alias::new("ward"),
+                            ],
+                        )
+                        .default("dispensary"),
+                    )
+                    .col(string_null(PharmacyStores::Location).string_len(150))
+                    .col(uuid_null(PharmacyStores::ManagerId))
+                    .col(boolean(PharmacyStores::IsActive).default(true))
+                    .col(timestamp_null(PharmacyStores::DeletedAt))
+                    .col(
+                        timestamp(PharmacyStores::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(PharmacyStores::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PharmacyStores::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(Alias::new("pharmacy_store_type"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PharmacyStores {
+    Table,
+    Id,
+    Pid,
+    Name,
+    Code,
+    StoreType,
+    Location,
+    ManagerId,
+    IsActive,
+    DeletedAt,
+    CreatedAt,
+    UpdatedAt,
+}
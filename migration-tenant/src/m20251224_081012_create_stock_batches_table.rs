@@ -0,0 +1,72 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(StockBatches::Table)
+                    .if_not_exists()
+                    .col(pk_auto(StockBatches::Id))
+                    .col(
+                        uuid_uniq(StockBatches::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer(StockBatches::ProductId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-stock_batches-product_id")
+                            .from(StockBatches::Table, StockBatches::ProductId)
+                            .to(PharmacyProducts::Table, PharmacyProducts::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(string(StockBatches::BatchNumber).string_len(50))
+                    .col(date(StockBatches::ExpiryDate))
+                    .col(decimal_null(StockBatches::UnitCost).decimal_len(12, 2))
+                    .col(
+                        timestamp(StockBatches::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_stock_batches_product_id_batch_number = Index::create()
+            .name("idx_stock_batches_product_id_batch_number")
+            .table(StockBatches::Table)
+            .col(StockBatches::ProductId)
+            .col(StockBatches::BatchNumber)
+            .unique()
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(StockBatches::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum StockBatches {
+    Table,
+    Id,
+    Pid,
+    ProductId,
+    BatchNumber,
+    ExpiryDate,
+    UnitCost,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum PharmacyProducts {
+    Table,
+    Id,
+}
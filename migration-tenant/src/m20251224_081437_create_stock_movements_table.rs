@@ -0,0 +1,150 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("stock_movement_type"))
+                    .values([
+                        Alias::new("receipt"),
+                        Alias::new("dispense"),
+                        Alias::new("transfer_in"),
+                        Alias::new("transfer_out"),
+                        Alias::new("adjustment"),
+                        Alias::new("wastage"),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(StockMovements::Table)
+                    .if_not_exists()
+                    .col(pk_auto(StockMovements::Id))
+                    .col(
+                        uuid_uniq(StockMovements::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer(StockMovements::StoreId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-stock_movements-store_id")
+                            .from(StockMovements::Table, StockMovements::StoreId)
+                            .to(PharmacyStores::Table, PharmacyStores::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(integer(StockMovements::ProductId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-stock_movements-product_id")
+                            .from(StockMovements::Table, StockMovements::ProductId)
+                            .to(PharmacyProducts::Table, PharmacyProducts::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(integer(StockMovements::BatchId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-stock_movements-batch_id")
+                            .from(StockMovements::Table, StockMovements::BatchId)
+                            .to(StockBatches::Table, StockBatches::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(enumeration(
+                        StockMovements::MovementType,
+                        Alias::new("stock_movement_type"),
+                        vec![
+                            Alias::new("receipt"),
+                            Alias::new("dispense"),
+                            Alias::new("transfer_in"),
+                            Alias::new("transfer_out"),
+                            Alias::new("adjustment"),
+                            Alias::new("wastage"),
+                        ],
+                    ))
+                    .col(integer(StockMovements::Quantity))
+                    .col(uuid_null(StockMovements::TransferPid))
+                    .col(string_null(StockMovements::Reference).string_len(100))
+                    .col(text_null(StockMovements::Reason))
+                    .col(uuid(StockMovements::PerformedBy))
+                    .col(
+                        timestamp(StockMovements::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_stock_movements_store_id_batch_id = Index::create()
+            .name("idx_stock_movements_store_id_batch_id")
+            .table(StockMovements::Table)
+            .col(StockMovements::StoreId)
+            .col(StockMovements::BatchId)
+            .to_owned();
+
+        let _idx_stock_movements_product_id_created_at = Index::create()
+            .name("idx_stock_movements_product_id_created_at")
+            .table(StockMovements::Table)
+            .col(StockMovements::ProductId)
+            .col(StockMovements::CreatedAt)
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(StockMovements::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(Alias::new("stock_movement_type"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum StockMovements {
+    Table,
+    Id,
+    Pid,
+    StoreId,
+    ProductId,
+    BatchId,
+    MovementType,
+    Quantity,
+    TransferPid,
+    Reference,
+    Reason,
+    PerformedBy,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum PharmacyStores {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum PharmacyProducts {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum StockBatches {
+    Table,
+    Id,
+}
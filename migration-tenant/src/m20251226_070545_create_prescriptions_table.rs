@@ -0,0 +1,124 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("prescription_status"))
+                    .values([
+                        Alias::new("pending"),
+                        Alias::new("partially_dispensed"),
+                        Alias::new("dispensed"),
+                        Alias::new("cancelled"),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Prescriptions::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Prescriptions::Id))
+                    .col(
+                        uuid_uniq(Prescriptions::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(uuid(Prescriptions::PatientPid))
+                    .col(integer_null(Prescriptions::EncounterId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-prescriptions-encounter_id")
+                            .from(Prescriptions::Table, Prescriptions::EncounterId)
+                            .to(Encounters::Table, Encounters::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .col(uuid(Prescriptions::PrescriberId))
+                    .col(
+                        enumeration(
+                            Prescriptions::Status,
+                            Alias::new("prescription_status"),
+                            vec![
+                                Alias::new("pending"),
+                                Alias::new("partially_dispensed"),
+                                Alias::new("dispensed"),
+                                Alias::new("cancelled"),
+                            ],
+                        )
+                        .default("pending"),
+                    )
+                    .col(text_null(Prescriptions::Notes))
+                    .col(
+                        timestamp(Prescriptions::PrescribedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(timestamp_null(Prescriptions::CancelledAt))
+                    .col(uuid_null(Prescriptions::CancelledBy))
+                    .col(text_null(Prescriptions::CancelReason))
+                    .col(
+                        timestamp(Prescriptions::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(Prescriptions::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_prescriptions_patient_pid_status = Index::create()
+            .name("idx_prescriptions_patient_pid_status")
+            .table(Prescriptions::Table)
+            .col(Prescriptions::PatientPid)
+            .col(Prescriptions::Status)
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Prescriptions::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(Alias::new("prescription_status"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Prescriptions {
+    Table,
+    Id,
+    Pid,
+    PatientPid,
+    EncounterId,
+    PrescriberId,
+    Status,
+    Notes,
+    PrescribedAt,
+    CancelledAt,
+    CancelledBy,
+    CancelReason,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Encounters {
+    Table,
+    Id,
+}
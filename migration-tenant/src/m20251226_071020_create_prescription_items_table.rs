@@ -0,0 +1,94 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PrescriptionItems::Table)
+                    .if_not_exists()
+                    .col(pk_auto(PrescriptionItems::Id))
+                    .col(
+                        uuid_uniq(PrescriptionItems::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer(PrescriptionItems::PrescriptionId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-prescription_items-prescription_id")
+                            .from(PrescriptionItems::Table, PrescriptionItems::PrescriptionId)
+                            .to(Prescriptions::Table, Prescriptions::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(integer_null(PrescriptionItems::ProductId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-prescription_items-product_id")
+                            .from(PrescriptionItems::Table, PrescriptionItems::ProductId)
+                            .to(PharmacyProducts::Table, PharmacyProducts::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .col(string(PrescriptionItems::DrugName).string_len(200))
+                    .col(string(PrescriptionItems::Dose).string_len(100))
+                    .col(string_null(PrescriptionItems::Route).string_len(50))
+                    .col(string(PrescriptionItems::Frequency).string_len(100))
+                    .col(integer_null(PrescriptionItems::DurationDays))
+                    .col(integer(PrescriptionItems::Quantity))
+                    .col(integer(PrescriptionItems::QuantityDispensed).default(0))
+                    .col(text_null(PrescriptionItems::Instructions))
+                    .col(
+                        timestamp(PrescriptionItems::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(PrescriptionItems::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PrescriptionItems::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PrescriptionItems {
+    Table,
+    Id,
+    Pid,
+    PrescriptionId,
+    ProductId,
+    DrugName,
+    Dose,
+    Route,
+    Frequency,
+    DurationDays,
+    Quantity,
+    QuantityDispensed,
+    Instructions,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Prescriptions {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum PharmacyProducts {
+    Table,
+    Id,
+}
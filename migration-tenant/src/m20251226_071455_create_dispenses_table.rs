@@ -0,0 +1,86 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Dispenses::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Dispenses::Id))
+                    .col(
+                        uuid_uniq(Dispenses::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer(Dispenses::PrescriptionId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-dispenses-prescription_id")
+                            .from(Dispenses::Table, Dispenses::PrescriptionId)
+                            .to(Prescriptions::Table, Prescriptions::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(integer(Dispenses::StoreId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-dispenses-store_id")
+                            .from(Dispenses::Table, Dispenses::StoreId)
+                            .to(PharmacyStores::Table, PharmacyStores::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(uuid(Dispenses::PatientPid))
+                    .col(uuid(Dispenses::DispensedBy))
+                    .col(text_null(Dispenses::Notes))
+                    .col(uuid_null(Dispenses::LabelDocumentFilePid))
+                    .col(
+                        timestamp(Dispenses::DispensedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(Dispenses::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Dispenses::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Dispenses {
+    Table,
+    Id,
+    Pid,
+    PrescriptionId,
+    StoreId,
+    PatientPid,
+    DispensedBy,
+    Notes,
+    LabelDocumentFilePid,
+    DispensedAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Prescriptions {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum PharmacyStores {
+    Table,
+    Id,
+}
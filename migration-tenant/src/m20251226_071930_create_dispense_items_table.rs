@@ -0,0 +1,126 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(DispenseItems::Table)
+                    .if_not_exists()
+                    .col(pk_auto(DispenseItems::Id))
+                    .col(
+                        uuid_uniq(DispenseItems::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer(DispenseItems::DispenseId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-dispense_items-dispense_id")
+                            .from(DispenseItems::Table, DispenseItems::DispenseId)
+                            .to(Dispenses::Table, Dispenses::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(integer(DispenseItems::PrescriptionItemId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-dispense_items-prescription_item_id")
+                            .from(DispenseItems::Table, DispenseItems::PrescriptionItemId)
+                            .to(PrescriptionItems::Table, PrescriptionItems::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(integer(DispenseItems::ProductId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-dispense_items-product_id")
+                            .from(DispenseItems::Table, DispenseItems::ProductId)
+                            .to(PharmacyProducts::Table, PharmacyProducts::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(integer(DispenseItems::BatchId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-dispense_items-batch_id")
+                            .from(DispenseItems::Table, DispenseItems::BatchId)
+                            .to(StockBatches::Table, StockBatches::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(integer(DispenseItems::StockMovementId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-dispense_items-stock_movement_id")
+                            .from(DispenseItems::Table, DispenseItems::StockMovementId)
+                            .to(StockMovements::Table, StockMovements::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(integer(DispenseItems::Quantity))
+                    .col(decimal(DispenseItems::UnitPrice).decimal_len(12, 2))
+                    .col(boolean(DispenseItems::IsSubstitution).default(false))
+                    .col(text_null(DispenseItems::SubstitutionReason))
+                    .col(
+                        timestamp(DispenseItems::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(DispenseItems::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum DispenseItems {
+    Table,
+    Id,
+    Pid,
+    DispenseId,
+    PrescriptionItemId,
+    ProductId,
+    BatchId,
+    StockMovementId,
+    Quantity,
+    UnitPrice,
+    IsSubstitution,
+    SubstitutionReason,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Dispenses {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum PrescriptionItems {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum PharmacyProducts {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum StockBatches {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum StockMovements {
+    Table,
+    Id,
+}
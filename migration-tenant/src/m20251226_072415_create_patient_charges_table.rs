@@ -0,0 +1,106 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("patient_charge_status"))
+                    .values([
+                        Alias::new("pending"),
+                        Alias::new("billed"),
+                        Alias::new("void"),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(PatientCharges::Table)
+                    .if_not_exists()
+                    .col(pk_auto(PatientCharges::Id))
+                    .col(
+                        uuid_uniq(PatientCharges::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(uuid(PatientCharges::PatientPid))
+                    .col(string(PatientCharges::SourceType).string_len(30))
+                    .col(uuid(PatientCharges::SourcePid))
+                    .col(string(PatientCharges::Description).string_len(255))
+                    .col(integer(PatientCharges::Quantity))
+                    .col(decimal(PatientCharges::UnitPrice).decimal_len(12, 2))
+                    .col(decimal(PatientCharges::TotalAmount).decimal_len(12, 2))
+                    .col(
+                        enumeration(
+                            PatientCharges::Status,
+                            Alias::new("patient_charge_status"),
+                            vec![
+                                Alias::new("pending"),
+                                Alias::new("billed"),
+                                Alias::new("void"),
+                            ],
+                        )
+                        .default("pending"),
+                    )
+                    .col(uuid_null(PatientCharges::CreatedBy))
+                    .col(
+                        timestamp(PatientCharges::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(PatientCharges::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_patient_charges_patient_pid_status = Index::create()
+            .name("idx_patient_charges_patient_pid_status")
+            .table(PatientCharges::Table)
+            .col(PatientCharges::PatientPid)
+            .col(PatientCharges::Status)
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PatientCharges::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(Alias::new("patient_charge_status"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PatientCharges {
+    Table,
+    Id,
+    Pid,
+    PatientPid,
+    SourceType,
+    SourcePid,
+    Description,
+    Quantity,
+    UnitPrice,
+    TotalAmount,
+    Status,
+    CreatedBy,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,68 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Suppliers::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Suppliers::Id))
+                    .col(
+                        uuid_uniq(Suppliers::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(string(Suppliers::Name).string_len(150))
+                    .col(string_uniq(Suppliers::Code).string_len(20))
+                    .col(string_null(Suppliers::ContactPerson).string_len(100))
+                    .col(string_null(Suppliers::Phone).string_len(20))
+                    .col(string_null(Suppliers::Email).string_len(100))
+                    .col(text_null(Suppliers::Address))
+                    .col(string_null(Suppliers::PaymentTerms).string_len(100))
+                    .col(integer(Suppliers::LeadTimeDays).default(14))
+                    .col(boolean(Suppliers::IsActive).default(true))
+                    .col(timestamp_null(Suppliers::DeletedAt))
+                    .col(
+                        timestamp(Suppliers::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(Suppliers::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Suppliers::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Suppliers {
+    Table,
+    Id,
+    Pid,
+    Name,
+    Code,
+    ContactPerson,
+    Phone,
+    Email,
+    Address,
+    PaymentTerms,
+    LeadTimeDays,
+    IsActive,
+    DeletedAt,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,154 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("purchase_order_status"))
+                    .values([
+                        Alias::new("draft"),
+                        Alias::new("submitted"),
+                        Alias::new("approved"),
+                        Alias::new("partially_received"),
+                        Alias::new("received"),
+                        Alias::new("cancelled"),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(PurchaseOrders::Table)
+                    .if_not_exists()
+                    .col(pk_auto(PurchaseOrders::Id))
+                    .col(
+                        uuid_uniq(PurchaseOrders::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(string_uniq(PurchaseOrders::OrderNumber).string_len(30))
+                    .col(integer(PurchaseOrders::SupplierId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-purchase_orders-supplier_id")
+                            .from(PurchaseOrders::Table, PurchaseOrders::SupplierId)
+                            .to(Suppliers::Table, Suppliers::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(integer(PurchaseOrders::StoreId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-purchase_orders-store_id")
+                            .from(PurchaseOrders::Table, PurchaseOrders::StoreId)
+                            .to(PharmacyStores::Table, PharmacyStores::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(
+                        enumeration(
+                            PurchaseOrders::Status,
+                            Alias::new("purchase_order_status"),
+                            vec![
+                                Alias::new("draft"),
+                                Alias::new("submitted"),
+                                Alias::new("approved"),
+                                Alias::new("partially_received"),
+                                Alias::new("received"),
+                                Alias::new("cancelled"),
+                            ],
+                        )
+                        .default("draft"),
+                    )
+                    .col(date_null(PurchaseOrders::ExpectedDate))
+                    .col(text_null(PurchaseOrders::Notes))
+                    .col(
+                        decimal(PurchaseOrders::TotalAmount)
+                            .decimal_len(12, 2)
+                            .default(0),
+                    )
+                    .col(uuid(PurchaseOrders::CreatedBy))
+                    .col(timestamp_null(PurchaseOrders::SubmittedAt))
+                    .col(timestamp_null(PurchaseOrders::ApprovedAt))
+                    .col(uuid_null(PurchaseOrders::ApprovedBy))
+                    .col(timestamp_null(PurchaseOrders::ReceivedAt))
+                    .col(timestamp_null(PurchaseOrders::CancelledAt))
+                    .col(uuid_null(PurchaseOrders::CancelledBy))
+                    .col(text_null(PurchaseOrders::CancelReason))
+                    .col(
+                        timestamp(PurchaseOrders::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(PurchaseOrders::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_purchase_orders_supplier_id_status = Index::create()
+            .name("idx_purchase_orders_supplier_id_status")
+            .table(PurchaseOrders::Table)
+            .col(PurchaseOrders::SupplierId)
+            .col(PurchaseOrders::Status)
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PurchaseOrders::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(Alias::new("purchase_order_status"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PurchaseOrders {
+    Table,
+    Id,
+    Pid,
+    OrderNumber,
+    SupplierId,
+    StoreId,
+    Status,
+    ExpectedDate,
+    Notes,
+    TotalAmount,
+    CreatedBy,
+    SubmittedAt,
+    ApprovedAt,
+    ApprovedBy,
+    ReceivedAt,
+    CancelledAt,
+    CancelledBy,
+    CancelReason,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Suppliers {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum PharmacyStores {
+    Table,
+    Id,
+}
@@ -0,0 +1,87 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PurchaseOrderItems::Table)
+                    .if_not_exists()
+                    .col(pk_auto(PurchaseOrderItems::Id))
+                    .col(
+                        uuid_uniq(PurchaseOrderItems::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer(PurchaseOrderItems::PurchaseOrderId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-purchase_order_items-purchase_order_id")
+                            .from(
+                                PurchaseOrderItems::Table,
+                                PurchaseOrderItems::PurchaseOrderId,
+                            )
+                            .to(PurchaseOrders::Table, PurchaseOrders::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(integer(PurchaseOrderItems::ProductId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-purchase_order_items-product_id")
+                            .from(PurchaseOrderItems::Table, PurchaseOrderItems::ProductId)
+                            .to(PharmacyProducts::Table, PharmacyProducts::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(integer(PurchaseOrderItems::QuantityOrdered))
+                    .col(integer(PurchaseOrderItems::QuantityReceived).default(0))
+                    .col(decimal(PurchaseOrderItems::UnitCost).decimal_len(12, 2))
+                    .col(
+                        timestamp(PurchaseOrderItems::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(PurchaseOrderItems::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PurchaseOrderItems::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PurchaseOrderItems {
+    Table,
+    Id,
+    Pid,
+    PurchaseOrderId,
+    ProductId,
+    QuantityOrdered,
+    QuantityReceived,
+    UnitCost,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum PurchaseOrders {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum PharmacyProducts {
+    Table,
+    Id,
+}
@@ -0,0 +1,107 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(GoodsReceivedNotes::Table)
+                    .if_not_exists()
+                    .col(pk_auto(GoodsReceivedNotes::Id))
+                    .col(
+                        uuid_uniq(GoodsReceivedNotes::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(string_uniq(GoodsReceivedNotes::GrnNumber).string_len(30))
+                    .col(integer(GoodsReceivedNotes::PurchaseOrderId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-goods_received_notes-purchase_order_id")
+                            .from(
+                                GoodsReceivedNotes::Table,
+                                GoodsReceivedNotes::PurchaseOrderId,
+                            )
+                            .to(PurchaseOrders::Table, PurchaseOrders::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(integer(GoodsReceivedNotes::SupplierId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-goods_received_notes-supplier_id")
+                            .from(GoodsReceivedNotes::Table, GoodsReceivedNotes::SupplierId)
+                            .to(Suppliers::Table, Suppliers::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(integer(GoodsReceivedNotes::StoreId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-goods_received_notes-store_id")
+                            .from(GoodsReceivedNotes::Table, GoodsReceivedNotes::StoreId)
+                            .to(PharmacyStores::Table, PharmacyStores::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(string_null(GoodsReceivedNotes::DeliveryNoteNumber).string_len(50))
+                    .col(
+                        decimal(GoodsReceivedNotes::TotalAmount)
+                            .decimal_len(12, 2)
+                            .default(0),
+                    )
+                    .col(text_null(GoodsReceivedNotes::Notes))
+                    .col(uuid(GoodsReceivedNotes::ReceivedBy))
+                    .col(timestamp(GoodsReceivedNotes::ReceivedAt))
+                    .col(
+                        timestamp(GoodsReceivedNotes::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(GoodsReceivedNotes::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GoodsReceivedNotes {
+    Table,
+    Id,
+    Pid,
+    GrnNumber,
+    PurchaseOrderId,
+    SupplierId,
+    StoreId,
+    DeliveryNoteNumber,
+    TotalAmount,
+    Notes,
+    ReceivedBy,
+    ReceivedAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum PurchaseOrders {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Suppliers {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum PharmacyStores {
+    Table,
+    Id,
+}
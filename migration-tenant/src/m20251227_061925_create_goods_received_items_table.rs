@@ -0,0 +1,131 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(GoodsReceivedItems::Table)
+                    .if_not_exists()
+                    .col(pk_auto(GoodsReceivedItems::Id))
+                    .col(
+                        uuid_uniq(GoodsReceivedItems::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer(GoodsReceivedItems::GoodsReceivedNoteId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-goods_received_items-goods_received_note_id")
+                            .from(
+                                GoodsReceivedItems::Table,
+                                GoodsReceivedItems::GoodsReceivedNoteId,
+                            )
+                            .to(GoodsReceivedNotes::Table, GoodsReceivedNotes::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(integer(GoodsReceivedItems::PurchaseOrderItemId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-goods_received_items-purchase_order_item_id")
+                            .from(
+                                GoodsReceivedItems::Table,
+                                GoodsReceivedItems::PurchaseOrderItemId,
+                            )
+                            .to(PurchaseOrderItems::Table, PurchaseOrderItems::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(integer(GoodsReceivedItems::ProductId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-goods_received_items-product_id")
+                            .from(GoodsReceivedItems::Table, GoodsReceivedItems::ProductId)
+                            .to(PharmacyProducts::Table, PharmacyProducts::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(integer(GoodsReceivedItems::BatchId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-goods_received_items-batch_id")
+                            .from(GoodsReceivedItems::Table, GoodsReceivedItems::BatchId)
+                            .to(StockBatches::Table, StockBatches::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(integer(GoodsReceivedItems::StockMovementId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-goods_received_items-stock_movement_id")
+                            .from(
+                                GoodsReceivedItems::Table,
+                                GoodsReceivedItems::StockMovementId,
+                            )
+                            .to(StockMovements::Table, StockMovements::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(integer(GoodsReceivedItems::Quantity))
+                    .col(decimal(GoodsReceivedItems::UnitCost).decimal_len(12, 2))
+                    .col(
+                        timestamp(GoodsReceivedItems::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(GoodsReceivedItems::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GoodsReceivedItems {
+    Table,
+    Id,
+    Pid,
+    GoodsReceivedNoteId,
+    PurchaseOrderItemId,
+    ProductId,
+    BatchId,
+    StockMovementId,
+    Quantity,
+    UnitCost,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum GoodsReceivedNotes {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum PurchaseOrderItems {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum PharmacyProducts {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum StockBatches {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum StockMovements {
+    Table,
+    Id,
+}
@@ -0,0 +1,101 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SupplierPrices::Table)
+                    .if_not_exists()
+                    .col(pk_auto(SupplierPrices::Id))
+                    .col(
+                        uuid_uniq(SupplierPrices::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer(SupplierPrices::SupplierId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-supplier_prices-supplier_id")
+                            .from(SupplierPrices::Table, SupplierPrices::SupplierId)
+                            .to(Suppliers::Table, Suppliers::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(integer(SupplierPrices::ProductId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-supplier_prices-product_id")
+                            .from(SupplierPrices::Table, SupplierPrices::ProductId)
+                            .to(PharmacyProducts::Table, PharmacyProducts::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(decimal(SupplierPrices::UnitCost).decimal_len(12, 2))
+                    .col(integer_null(SupplierPrices::GoodsReceivedNoteId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-supplier_prices-goods_received_note_id")
+                            .from(SupplierPrices::Table, SupplierPrices::GoodsReceivedNoteId)
+                            .to(GoodsReceivedNotes::Table, GoodsReceivedNotes::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .col(uuid(SupplierPrices::RecordedBy))
+                    .col(timestamp(SupplierPrices::EffectiveAt))
+                    .col(
+                        timestamp(SupplierPrices::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_supplier_prices_product_id_effective_at = Index::create()
+            .name("idx_supplier_prices_product_id_effective_at")
+            .table(SupplierPrices::Table)
+            .col(SupplierPrices::ProductId)
+            .col(SupplierPrices::EffectiveAt)
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SupplierPrices::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SupplierPrices {
+    Table,
+    Id,
+    Pid,
+    SupplierId,
+    ProductId,
+    UnitCost,
+    GoodsReceivedNoteId,
+    RecordedBy,
+    EffectiveAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Suppliers {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum PharmacyProducts {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum GoodsReceivedNotes {
+    Table,
+    Id,
+}
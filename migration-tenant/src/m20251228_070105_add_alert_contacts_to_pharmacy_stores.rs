@@ -0,0 +1,42 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PharmacyStores::Table)
+                    .add_column_if_not_exists(
+                        string_null(PharmacyStores::AlertEmail).string_len(100),
+                    )
+                    .add_column_if_not_exists(
+                        string_null(PharmacyStores::AlertPhone).string_len(20),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PharmacyStores::Table)
+                    .drop_column(PharmacyStores::AlertEmail)
+                    .drop_column(PharmacyStores::AlertPhone)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PharmacyStores {
+    Table,
+    AlertEmail,
+    AlertPhone,
+}
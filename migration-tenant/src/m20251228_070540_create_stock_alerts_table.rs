@@ -0,0 +1,146 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("stock_alert_type"))
+                    .values([
+                        Alias::new("low_stock"),
+                        Alias::new("stock_out"),
+                        Alias::new("expiring"),
+                        Alias::new("expired"),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(StockAlerts::Table)
+                    .if_not_exists()
+                    .col(pk_auto(StockAlerts::Id))
+                    .col(
+                        uuid_uniq(StockAlerts::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer(StockAlerts::StoreId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-stock_alerts-store_id")
+                            .from(StockAlerts::Table, StockAlerts::StoreId)
+                            .to(PharmacyStores::Table, PharmacyStores::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(integer(StockAlerts::ProductId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-stock_alerts-product_id")
+                            .from(StockAlerts::Table, StockAlerts::ProductId)
+                            .to(PharmacyProducts::Table, PharmacyProducts::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(integer_null(StockAlerts::BatchId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-stock_alerts-batch_id")
+                            .from(StockAlerts::Table, StockAlerts::BatchId)
+                            .to(StockBatches::Table, StockBatches::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(enumeration(
+                        StockAlerts::AlertType,
+                        Alias::new("stock_alert_type"),
+                        vec![
+                            Alias::new("low_stock"),
+                            Alias::new("stock_out"),
+                            Alias::new("expiring"),
+                            Alias::new("expired"),
+                        ],
+                    ))
+                    .col(integer_null(StockAlerts::WindowDays))
+                    .col(big_integer(StockAlerts::Quantity))
+                    .col(integer(StockAlerts::ReorderLevel))
+                    .col(date_null(StockAlerts::ExpiryDate))
+                    .col(timestamp_null(StockAlerts::NotifiedAt))
+                    .col(timestamp_null(StockAlerts::AcknowledgedAt))
+                    .col(uuid_null(StockAlerts::AcknowledgedBy))
+                    .col(timestamp_null(StockAlerts::ResolvedAt))
+                    .col(
+                        timestamp(StockAlerts::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(StockAlerts::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_stock_alerts_store_id_resolved_at = Index::create()
+            .name("idx_stock_alerts_store_id_resolved_at")
+            .table(StockAlerts::Table)
+            .col(StockAlerts::StoreId)
+            .col(StockAlerts::ResolvedAt)
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(StockAlerts::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(Alias::new("stock_alert_type")).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum StockAlerts {
+    Table,
+    Id,
+    Pid,
+    StoreId,
+    ProductId,
+    BatchId,
+    AlertType,
+    WindowDays,
+    Quantity,
+    ReorderLevel,
+    ExpiryDate,
+    NotifiedAt,
+    AcknowledgedAt,
+    AcknowledgedBy,
+    ResolvedAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum PharmacyStores {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum PharmacyProducts {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum StockBatches {
+    Table,
+    Id,
+}
@@ -0,0 +1,42 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PharmacyProducts::Table)
+                    .add_column_if_not_exists(
+                        boolean(PharmacyProducts::IsControlled).default(false),
+                    )
+                    .add_column_if_not_exists(
+                        string_null(PharmacyProducts::ControlledSchedule).string_len(30),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PharmacyProducts::Table)
+                    .drop_column(PharmacyProducts::IsControlled)
+                    .drop_column(PharmacyProducts::ControlledSchedule)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PharmacyProducts {
+    Table,
+    IsControlled,
+    ControlledSchedule,
+}
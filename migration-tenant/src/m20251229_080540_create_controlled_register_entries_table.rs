@@ -0,0 +1,188 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ControlledRegisterEntries::Table)
+                    .if_not_exists()
+                    .col(pk_auto(ControlledRegisterEntries::Id))
+                    .col(
+                        uuid_uniq(ControlledRegisterEntries::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer(ControlledRegisterEntries::StoreId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-controlled_register_entries-store_id")
+                            .from(
+                                ControlledRegisterEntries::Table,
+                                ControlledRegisterEntries::StoreId,
+                            )
+                            .to(PharmacyStores::Table, PharmacyStores::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(integer(ControlledRegisterEntries::ProductId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-controlled_register_entries-product_id")
+                            .from(
+                                ControlledRegisterEntries::Table,
+                                ControlledRegisterEntries::ProductId,
+                            )
+                            .to(PharmacyProducts::Table, PharmacyProducts::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(integer(ControlledRegisterEntries::BatchId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-controlled_register_entries-batch_id")
+                            .from(
+                                ControlledRegisterEntries::Table,
+                                ControlledRegisterEntries::BatchId,
+                            )
+                            .to(StockBatches::Table, StockBatches::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(integer_uniq(ControlledRegisterEntries::StockMovementId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-controlled_register_entries-stock_movement_id")
+                            .from(
+                                ControlledRegisterEntries::Table,
+                                ControlledRegisterEntries::StockMovementId,
+                            )
+                            .to(StockMovements::Table, StockMovements::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(enumeration(
+                        ControlledRegisterEntries::MovementType,
+                        Alias::new("stock_movement_type"),
+                        vec![
+                            Alias::new("receipt"),
+                            Alias::new("dispense"),
+                            Alias::new("transfer_in"),
+                            Alias::new("transfer_out"),
+                            Alias::new("adjustment"),
+                            Alias::new("wastage"),
+                        ],
+                    ))
+                    .col(integer(ControlledRegisterEntries::Quantity))
+                    .col(big_integer(ControlledRegisterEntries::BalanceAfter))
+                    .col(string_null(ControlledRegisterEntries::Reference).string_len(100))
+                    .col(text_null(ControlledRegisterEntries::Reason))
+                    .col(uuid(ControlledRegisterEntries::PerformedBy))
+                    .col(uuid_null(ControlledRegisterEntries::WitnessedBy))
+                    .col(timestamp_null(ControlledRegisterEntries::WitnessedAt))
+                    .col(
+                        timestamp(ControlledRegisterEntries::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_controlled_register_entries_store_id_product_id = Index::create()
+            .name("idx_controlled_register_entries_store_id_product_id")
+            .table(ControlledRegisterEntries::Table)
+            .col(ControlledRegisterEntries::StoreId)
+            .col(ControlledRegisterEntries::ProductId)
+            .to_owned();
+
+        // The register is a legal record: rows can never be deleted, and the
+        // only change allowed is the one-time witness co-signature.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE OR REPLACE FUNCTION controlled_register_entries_immutable()
+                RETURNS trigger AS $$
+                BEGIN
+                    IF TG_OP = 'UPDATE'
+                        AND OLD.witnessed_by IS NULL
+                        AND NEW.witnessed_by IS NOT NULL
+                        AND (to_jsonb(NEW) - 'witnessed_by' - 'witnessed_at')
+                            = (to_jsonb(OLD) - 'witnessed_by' - 'witnessed_at')
+                    THEN
+                        RETURN NEW;
+                    END IF;
+                    RAISE EXCEPTION 'controlled register entries are immutable';
+                END;
+                $$ LANGUAGE plpgsql;
+
+                CREATE TRIGGER controlled_register_entries_immutable
+                BEFORE UPDATE OR DELETE ON controlled_register_entries
+                FOR EACH ROW EXECUTE FUNCTION controlled_register_entries_immutable();
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(ControlledRegisterEntries::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("DROP FUNCTION IF EXISTS controlled_register_entries_immutable();")
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum ControlledRegisterEntries {
+    Table,
+    Id,
+    Pid,
+    StoreId,
+    ProductId,
+    BatchId,
+    StockMovementId,
+    MovementType,
+    Quantity,
+    BalanceAfter,
+    Reference,
+    Reason,
+    PerformedBy,
+    WitnessedBy,
+    WitnessedAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum PharmacyStores {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum PharmacyProducts {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum StockBatches {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum StockMovements {
+    Table,
+    Id,
+}
@@ -0,0 +1,167 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("controlled_reconciliation_status"))
+                    .values([
+                        Alias::new("balanced"),
+                        Alias::new("discrepancy"),
+                        Alias::new("investigating"),
+                        Alias::new("resolved"),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ControlledReconciliations::Table)
+                    .if_not_exists()
+                    .col(pk_auto(ControlledReconciliations::Id))
+                    .col(
+                        uuid_uniq(ControlledReconciliations::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer(ControlledReconciliations::StoreId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-controlled_reconciliations-store_id")
+                            .from(
+                                ControlledReconciliations::Table,
+                                ControlledReconciliations::StoreId,
+                            )
+                            .to(PharmacyStores::Table, PharmacyStores::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(integer(ControlledReconciliations::ProductId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-controlled_reconciliations-product_id")
+                            .from(
+                                ControlledReconciliations::Table,
+                                ControlledReconciliations::ProductId,
+                            )
+                            .to(PharmacyProducts::Table, PharmacyProducts::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(big_integer(ControlledReconciliations::RegisterBalance))
+                    .col(big_integer(ControlledReconciliations::CountedQuantity))
+                    .col(big_integer(ControlledReconciliations::Variance))
+                    .col(enumeration(
+                        ControlledReconciliations::Status,
+                        Alias::new("controlled_reconciliation_status"),
+                        vec![
+                            Alias::new("balanced"),
+                            Alias::new("discrepancy"),
+                            Alias::new("investigating"),
+                            Alias::new("resolved"),
+                        ],
+                    ))
+                    .col(text_null(ControlledReconciliations::Notes))
+                    .col(uuid(ControlledReconciliations::CountedBy))
+                    .col(uuid_null(ControlledReconciliations::WitnessedBy))
+                    .col(timestamp_null(ControlledReconciliations::WitnessedAt))
+                    .col(text_null(ControlledReconciliations::InvestigationNotes))
+                    .col(uuid_null(ControlledReconciliations::InvestigatedBy))
+                    .col(text_null(ControlledReconciliations::Resolution))
+                    .col(integer_null(ControlledReconciliations::AdjustmentEntryId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-controlled_reconciliations-adjustment_entry_id")
+                            .from(
+                                ControlledReconciliations::Table,
+                                ControlledReconciliations::AdjustmentEntryId,
+                            )
+                            .to(
+                                ControlledRegisterEntries::Table,
+                                ControlledRegisterEntries::Id,
+                            )
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(uuid_null(ControlledReconciliations::ResolvedBy))
+                    .col(timestamp_null(ControlledReconciliations::ResolvedAt))
+                    .col(
+                        timestamp(ControlledReconciliations::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(ControlledReconciliations::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(ControlledReconciliations::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(Alias::new("controlled_reconciliation_status"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ControlledReconciliations {
+    Table,
+    Id,
+    Pid,
+    StoreId,
+    ProductId,
+    RegisterBalance,
+    CountedQuantity,
+    Variance,
+    Status,
+    Notes,
+    CountedBy,
+    WitnessedBy,
+    WitnessedAt,
+    InvestigationNotes,
+    InvestigatedBy,
+    Resolution,
+    AdjustmentEntryId,
+    ResolvedBy,
+    ResolvedAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum PharmacyStores {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum PharmacyProducts {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum ControlledRegisterEntries {
+    Table,
+    Id,
+}
@@ -0,0 +1,42 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PharmacyProducts::Table)
+                    .add_column_if_not_exists(
+                        boolean(PharmacyProducts::IsSoldOnline).default(false),
+                    )
+                    .add_column_if_not_exists(
+                        boolean(PharmacyProducts::RequiresPrescription).default(false),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PharmacyProducts::Table)
+                    .drop_column(PharmacyProducts::IsSoldOnline)
+                    .drop_column(PharmacyProducts::RequiresPrescription)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PharmacyProducts {
+    Table,
+    IsSoldOnline,
+    RequiresPrescription,
+}
@@ -0,0 +1,75 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(CartItems::Table)
+                    .if_not_exists()
+                    .col(pk_auto(CartItems::Id))
+                    .col(
+                        uuid_uniq(CartItems::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(uuid(CartItems::PatientPid))
+                    .col(integer(CartItems::ProductId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-cart_items-product_id")
+                            .from(CartItems::Table, CartItems::ProductId)
+                            .to(PharmacyProducts::Table, PharmacyProducts::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(integer(CartItems::Quantity))
+                    .col(
+                        timestamp(CartItems::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(CartItems::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_cart_items_patient_pid_product_id = Index::create()
+            .name("idx_cart_items_patient_pid_product_id")
+            .table(CartItems::Table)
+            .col(CartItems::PatientPid)
+            .col(CartItems::ProductId)
+            .unique()
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CartItems::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum CartItems {
+    Table,
+    Id,
+    Pid,
+    PatientPid,
+    ProductId,
+    Quantity,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum PharmacyProducts {
+    Table,
+    Id,
+}
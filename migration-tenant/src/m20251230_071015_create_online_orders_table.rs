@@ -0,0 +1,190 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("online_order_status"))
+                    .values([
+                        Alias::new("placed"),
+                        Alias::new("verified"),
+                        Alias::new("packed"),
+                        Alias::new("out_for_delivery"),
+                        Alias::new("delivered"),
+                        Alias::new("cancelled"),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("online_payment_status"))
+                    .values([
+                        Alias::new("pending"),
+                        Alias::new("paid"),
+                        Alias::new("failed"),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(OnlineOrders::Table)
+                    .if_not_exists()
+                    .col(pk_auto(OnlineOrders::Id))
+                    .col(
+                        uuid_uniq(OnlineOrders::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(string_uniq(OnlineOrders::OrderNumber).string_len(30))
+                    .col(uuid(OnlineOrders::PatientPid))
+                    .col(enumeration(
+                        OnlineOrders::Status,
+                        Alias::new("online_order_status"),
+                        vec![
+                            Alias::new("placed"),
+                            Alias::new("verified"),
+                            Alias::new("packed"),
+                            Alias::new("out_for_delivery"),
+                            Alias::new("delivered"),
+                            Alias::new("cancelled"),
+                        ],
+                    ))
+                    .col(enumeration(
+                        OnlineOrders::PaymentStatus,
+                        Alias::new("online_payment_status"),
+                        vec![
+                            Alias::new("pending"),
+                            Alias::new("paid"),
+                            Alias::new("failed"),
+                        ],
+                    ))
+                    .col(decimal(OnlineOrders::TotalAmount).decimal_len(12, 2))
+                    .col(string(OnlineOrders::PaymentPhone).string_len(20))
+                    .col(string_null(OnlineOrders::CheckoutRequestId).string_len(100))
+                    .col(string_null(OnlineOrders::CallbackToken).string_len(64))
+                    .col(string_null(OnlineOrders::PaymentReference).string_len(50))
+                    .col(text_null(OnlineOrders::PaymentFailureReason))
+                    .col(timestamp_null(OnlineOrders::PaidAt))
+                    .col(text(OnlineOrders::DeliveryAddress))
+                    .col(string(OnlineOrders::ContactPhone).string_len(20))
+                    .col(text_null(OnlineOrders::Notes))
+                    .col(uuid_null(OnlineOrders::PrescriptionDocumentFilePid))
+                    .col(integer_null(OnlineOrders::StoreId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-online_orders-store_id")
+                            .from(OnlineOrders::Table, OnlineOrders::StoreId)
+                            .to(PharmacyStores::Table, PharmacyStores::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(timestamp_null(OnlineOrders::VerifiedAt))
+                    .col(uuid_null(OnlineOrders::VerifiedBy))
+                    .col(timestamp_null(OnlineOrders::PackedAt))
+                    .col(uuid_null(OnlineOrders::PackedBy))
+                    .col(timestamp_null(OnlineOrders::DispatchedAt))
+                    .col(uuid_null(OnlineOrders::DispatchedBy))
+                    .col(timestamp_null(OnlineOrders::DeliveredAt))
+                    .col(timestamp_null(OnlineOrders::CancelledAt))
+                    .col(uuid_null(OnlineOrders::CancelledBy))
+                    .col(text_null(OnlineOrders::CancelReason))
+                    .col(
+                        timestamp(OnlineOrders::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(OnlineOrders::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_online_orders_patient_pid = Index::create()
+            .name("idx_online_orders_patient_pid")
+            .table(OnlineOrders::Table)
+            .col(OnlineOrders::PatientPid)
+            .to_owned();
+
+        let _idx_online_orders_status = Index::create()
+            .name("idx_online_orders_status")
+            .table(OnlineOrders::Table)
+            .col(OnlineOrders::Status)
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(OnlineOrders::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(Alias::new("online_payment_status"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(Alias::new("online_order_status"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum OnlineOrders {
+    Table,
+    Id,
+    Pid,
+    OrderNumber,
+    PatientPid,
+    Status,
+    PaymentStatus,
+    TotalAmount,
+    PaymentPhone,
+    CheckoutRequestId,
+    CallbackToken,
+    PaymentReference,
+    PaymentFailureReason,
+    PaidAt,
+    DeliveryAddress,
+    ContactPhone,
+    Notes,
+    PrescriptionDocumentFilePid,
+    StoreId,
+    VerifiedAt,
+    VerifiedBy,
+    PackedAt,
+    PackedBy,
+    DispatchedAt,
+    DispatchedBy,
+    DeliveredAt,
+    CancelledAt,
+    CancelledBy,
+    CancelReason,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum PharmacyStores {
+    Table,
+    Id,
+}
@@ -0,0 +1,79 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(OnlineOrderItems::Table)
+                    .if_not_exists()
+                    .col(pk_auto(OnlineOrderItems::Id))
+                    .col(
+                        uuid_uniq(OnlineOrderItems::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer(OnlineOrderItems::OrderId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-online_order_items-order_id")
+                            .from(OnlineOrderItems::Table, OnlineOrderItems::OrderId)
+                            .to(OnlineOrders::Table, OnlineOrders::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(integer(OnlineOrderItems::ProductId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-online_order_items-product_id")
+                            .from(OnlineOrderItems::Table, OnlineOrderItems::ProductId)
+                            .to(PharmacyProducts::Table, PharmacyProducts::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(integer(OnlineOrderItems::Quantity))
+                    .col(decimal(OnlineOrderItems::UnitPrice).decimal_len(12, 2))
+                    .col(decimal(OnlineOrderItems::LineTotal).decimal_len(12, 2))
+                    .col(boolean(OnlineOrderItems::RequiresPrescription).default(false))
+                    .col(
+                        timestamp(OnlineOrderItems::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(OnlineOrderItems::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum OnlineOrderItems {
+    Table,
+    Id,
+    Pid,
+    OrderId,
+    ProductId,
+    Quantity,
+    UnitPrice,
+    LineTotal,
+    RequiresPrescription,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum OnlineOrders {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum PharmacyProducts {
+    Table,
+    Id,
+}
@@ -0,0 +1,130 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("coupon_discount_type"))
+                    .values([Alias::new("percentage"), Alias::new("fixed")])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("coupon_scope"))
+                    .values([
+                        Alias::new("order"),
+                        Alias::new("products"),
+                        Alias::new("categories"),
+                        Alias::new("services"),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Coupons::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Coupons::Id))
+                    .col(
+                        uuid_uniq(Coupons::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(string_uniq(Coupons::Code).string_len(30))
+                    .col(text_null(Coupons::Description))
+                    .col(enumeration(
+                        Coupons::DiscountType,
+                        Alias::new("coupon_discount_type"),
+                        vec![Alias::new("percentage"), Alias::new("fixed")],
+                    ))
+                    .col(decimal(Coupons::Value).decimal_len(12, 2))
+                    .col(decimal_null(Coupons::MaxDiscount).decimal_len(12, 2))
+                    .col(enumeration(
+                        Coupons::Scope,
+                        Alias::new("coupon_scope"),
+                        vec![
+                            Alias::new("order"),
+                            Alias::new("products"),
+                            Alias::new("categories"),
+                            Alias::new("services"),
+                        ],
+                    ))
+                    .col(array_null(
+                        Coupons::Targets,
+                        ColumnType::String(StringLen::None),
+                    ))
+                    .col(decimal_null(Coupons::MinOrderValue).decimal_len(12, 2))
+                    .col(integer_null(Coupons::MaxRedemptions))
+                    .col(integer_null(Coupons::MaxRedemptionsPerPatient))
+                    .col(timestamp_null(Coupons::StartsAt))
+                    .col(timestamp_null(Coupons::EndsAt))
+                    .col(boolean(Coupons::IsStackable).default(false))
+                    .col(boolean(Coupons::IsActive).default(true))
+                    .col(uuid(Coupons::CreatedBy))
+                    .col(timestamp_null(Coupons::DeletedAt))
+                    .col(
+                        timestamp(Coupons::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(Coupons::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Coupons::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(Alias::new("coupon_scope")).to_owned())
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(Alias::new("coupon_discount_type"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Coupons {
+    Table,
+    Id,
+    Pid,
+    Code,
+    Description,
+    DiscountType,
+    Value,
+    MaxDiscount,
+    Scope,
+    Targets,
+    MinOrderValue,
+    MaxRedemptions,
+    MaxRedemptionsPerPatient,
+    StartsAt,
+    EndsAt,
+    IsStackable,
+    IsActive,
+    CreatedBy,
+    DeletedAt,
+    CreatedAt,
+    UpdatedAt,
+}
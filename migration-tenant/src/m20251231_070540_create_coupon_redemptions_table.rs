@@ -0,0 +1,86 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(CouponRedemptions::Table)
+                    .if_not_exists()
+                    .col(pk_auto(CouponRedemptions::Id))
+                    .col(
+                        uuid_uniq(CouponRedemptions::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer(CouponRedemptions::CouponId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-coupon_redemptions-coupon_id")
+                            .from(CouponRedemptions::Table, CouponRedemptions::CouponId)
+                            .to(Coupons::Table, Coupons::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(uuid(CouponRedemptions::PatientPid))
+                    .col(string(CouponRedemptions::SourceType).string_len(30))
+                    .col(uuid(CouponRedemptions::SourcePid))
+                    .col(decimal(CouponRedemptions::OrderValue).decimal_len(12, 2))
+                    .col(decimal(CouponRedemptions::EligibleAmount).decimal_len(12, 2))
+                    .col(decimal(CouponRedemptions::DiscountAmount).decimal_len(12, 2))
+                    .col(timestamp_null(CouponRedemptions::VoidedAt))
+                    .col(
+                        timestamp(CouponRedemptions::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_coupon_redemptions_coupon_id_patient_pid = Index::create()
+            .name("idx_coupon_redemptions_coupon_id_patient_pid")
+            .table(CouponRedemptions::Table)
+            .col(CouponRedemptions::CouponId)
+            .col(CouponRedemptions::PatientPid)
+            .to_owned();
+
+        let _idx_coupon_redemptions_source = Index::create()
+            .name("idx_coupon_redemptions_source")
+            .table(CouponRedemptions::Table)
+            .col(CouponRedemptions::SourceType)
+            .col(CouponRedemptions::SourcePid)
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CouponRedemptions::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum CouponRedemptions {
+    Table,
+    Id,
+    Pid,
+    CouponId,
+    PatientPid,
+    SourceType,
+    SourcePid,
+    OrderValue,
+    EligibleAmount,
+    DiscountAmount,
+    VoidedAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Coupons {
+    Table,
+    Id,
+}
@@ -0,0 +1,54 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(OnlineOrders::Table)
+                    .add_column_if_not_exists(
+                        decimal(OnlineOrders::SubtotalAmount)
+                            .decimal_len(12, 2)
+                            .default(0),
+                    )
+                    .add_column_if_not_exists(
+                        decimal(OnlineOrders::DiscountAmount)
+                            .decimal_len(12, 2)
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Orders placed before coupons were undiscounted.
+        manager
+            .get_connection()
+            .execute_unprepared("UPDATE online_orders SET subtotal_amount = total_amount;")
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(OnlineOrders::Table)
+                    .drop_column(OnlineOrders::SubtotalAmount)
+                    .drop_column(OnlineOrders::DiscountAmount)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum OnlineOrders {
+    Table,
+    SubtotalAmount,
+    DiscountAmount,
+}
@@ -0,0 +1,60 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(DeliveryZones::Table)
+                    .if_not_exists()
+                    .col(pk_auto(DeliveryZones::Id))
+                    .col(
+                        uuid_uniq(DeliveryZones::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(string(DeliveryZones::Name).string_len(100))
+                    .col(decimal(DeliveryZones::MinDistanceKm).decimal_len(6, 2))
+                    .col(decimal(DeliveryZones::MaxDistanceKm).decimal_len(6, 2))
+                    .col(decimal(DeliveryZones::Fee).decimal_len(12, 2))
+                    .col(integer(DeliveryZones::EstimatedMinutes))
+                    .col(boolean(DeliveryZones::IsActive).default(true))
+                    .col(timestamp_null(DeliveryZones::DeletedAt))
+                    .col(
+                        timestamp(DeliveryZones::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(DeliveryZones::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(DeliveryZones::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum DeliveryZones {
+    Table,
+    Id,
+    Pid,
+    Name,
+    MinDistanceKm,
+    MaxDistanceKm,
+    Fee,
+    EstimatedMinutes,
+    IsActive,
+    DeletedAt,
+    CreatedAt,
+    UpdatedAt,
+}
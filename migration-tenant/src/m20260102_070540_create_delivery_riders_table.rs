@@ -0,0 +1,64 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(DeliveryRiders::Table)
+                    .if_not_exists()
+                    .col(pk_auto(DeliveryRiders::Id))
+                    .col(
+                        uuid_uniq(DeliveryRiders::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(uuid_uniq(DeliveryRiders::SsoUserId))
+                    .col(string(DeliveryRiders::Name).string_len(150))
+                    .col(string(DeliveryRiders::Phone).string_len(20))
+                    .col(string_null(DeliveryRiders::Vehicle).string_len(100))
+                    .col(boolean(DeliveryRiders::IsActive).default(true))
+                    .col(decimal_null(DeliveryRiders::LastLatitude).decimal_len(10, 8))
+                    .col(decimal_null(DeliveryRiders::LastLongitude).decimal_len(11, 8))
+                    .col(timestamp_null(DeliveryRiders::LastLocationAt))
+                    .col(timestamp_null(DeliveryRiders::DeletedAt))
+                    .col(
+                        timestamp(DeliveryRiders::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(DeliveryRiders::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(DeliveryRiders::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum DeliveryRiders {
+    Table,
+    Id,
+    Pid,
+    SsoUserId,
+    Name,
+    Phone,
+    Vehicle,
+    IsActive,
+    LastLatitude,
+    LastLongitude,
+    LastLocationAt,
+    DeletedAt,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,164 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("delivery_status"))
+                    .values([
+                        Alias::new("pending"),
+                        Alias::new("assigned"),
+                        Alias::new("picked_up"),
+                        Alias::new("delivered"),
+                        Alias::new("failed"),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Deliveries::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Deliveries::Id))
+                    .col(
+                        uuid_uniq(Deliveries::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer_uniq(Deliveries::OrderId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-deliveries-order_id")
+                            .from(Deliveries::Table, Deliveries::OrderId)
+                            .to(OnlineOrders::Table, OnlineOrders::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(integer_null(Deliveries::ZoneId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-deliveries-zone_id")
+                            .from(Deliveries::Table, Deliveries::ZoneId)
+                            .to(DeliveryZones::Table, DeliveryZones::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(integer_null(Deliveries::RiderId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-deliveries-rider_id")
+                            .from(Deliveries::Table, Deliveries::RiderId)
+                            .to(DeliveryRiders::Table, DeliveryRiders::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(enumeration(
+                        Deliveries::Status,
+                        Alias::new("delivery_status"),
+                        vec![
+                            Alias::new("pending"),
+                            Alias::new("assigned"),
+                            Alias::new("picked_up"),
+                            Alias::new("delivered"),
+                            Alias::new("failed"),
+                        ],
+                    ))
+                    .col(decimal_null(Deliveries::Latitude).decimal_len(10, 8))
+                    .col(decimal_null(Deliveries::Longitude).decimal_len(11, 8))
+                    .col(decimal_null(Deliveries::DistanceKm).decimal_len(6, 2))
+                    .col(decimal(Deliveries::Fee).decimal_len(12, 2))
+                    .col(integer_null(Deliveries::EstimatedMinutes))
+                    .col(string_null(Deliveries::OtpCode).string_len(6))
+                    .col(integer(Deliveries::OtpAttempts).default(0))
+                    .col(timestamp_null(Deliveries::AssignedAt))
+                    .col(uuid_null(Deliveries::AssignedBy))
+                    .col(timestamp_null(Deliveries::PickedUpAt))
+                    .col(timestamp_null(Deliveries::DeliveredAt))
+                    .col(string_null(Deliveries::ProofMethod).string_len(10))
+                    .col(uuid_null(Deliveries::ProofPhotoFilePid))
+                    .col(string_null(Deliveries::RecipientName).string_len(150))
+                    .col(timestamp_null(Deliveries::FailedAt))
+                    .col(text_null(Deliveries::FailureReason))
+                    .col(
+                        timestamp(Deliveries::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(Deliveries::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_deliveries_rider_id_status = Index::create()
+            .name("idx_deliveries_rider_id_status")
+            .table(Deliveries::Table)
+            .col(Deliveries::RiderId)
+            .col(Deliveries::Status)
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Deliveries::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(Alias::new("delivery_status")).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Deliveries {
+    Table,
+    Id,
+    Pid,
+    OrderId,
+    ZoneId,
+    RiderId,
+    Status,
+    Latitude,
+    Longitude,
+    DistanceKm,
+    Fee,
+    EstimatedMinutes,
+    OtpCode,
+    OtpAttempts,
+    AssignedAt,
+    AssignedBy,
+    PickedUpAt,
+    DeliveredAt,
+    ProofMethod,
+    ProofPhotoFilePid,
+    RecipientName,
+    FailedAt,
+    FailureReason,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum OnlineOrders {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum DeliveryZones {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum DeliveryRiders {
+    Table,
+    Id,
+}
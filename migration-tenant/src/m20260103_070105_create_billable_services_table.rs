@@ -0,0 +1,102 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("service_category"))
+                    .values([
+                        Alias::new("consultation"),
+                        Alias::new("procedure"),
+                        Alias::new("lab_test"),
+                        Alias::new("imaging"),
+                        Alias::new("pharmacy"),
+                        Alias::new("other"),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(BillableServices::Table)
+                    .if_not_exists()
+                    .col(pk_auto(BillableServices::Id))
+                    .col(
+                        uuid_uniq(BillableServices::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(string_uniq(BillableServices::Code).string_len(30))
+                    .col(string(BillableServices::Name).string_len(150))
+                    .col(enumeration(
+                        BillableServices::Category,
+                        Alias::new("service_category"),
+                        vec![
+                            Alias::new("consultation"),
+                            Alias::new("procedure"),
+                            Alias::new("lab_test"),
+                            Alias::new("imaging"),
+                            Alias::new("pharmacy"),
+                            Alias::new("other"),
+                        ],
+                    ))
+                    .col(text_null(BillableServices::Description))
+                    .col(enumeration_null(
+                        BillableServices::EncounterType,
+                        Alias::new("encounter_type"),
+                        vec![
+                            Alias::new("outpatient"),
+                            Alias::new("inpatient"),
+                            Alias::new("telemedicine"),
+                        ],
+                    ))
+                    .col(boolean(BillableServices::IsActive).default(true))
+                    .col(uuid(BillableServices::CreatedBy))
+                    .col(timestamp_null(BillableServices::DeletedAt))
+                    .col(
+                        timestamp(BillableServices::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(BillableServices::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(BillableServices::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(Alias::new("service_category")).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum BillableServices {
+    Table,
+    Id,
+    Pid,
+    Code,
+    Name,
+    Category,
+    Description,
+    EncounterType,
+    IsActive,
+    CreatedBy,
+    DeletedAt,
+    CreatedAt,
+    UpdatedAt,
+}
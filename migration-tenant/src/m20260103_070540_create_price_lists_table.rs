@@ -0,0 +1,88 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("payer_type"))
+                    .values([
+                        Alias::new("cash"),
+                        Alias::new("insurance"),
+                        Alias::new("corporate"),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(PriceLists::Table)
+                    .if_not_exists()
+                    .col(pk_auto(PriceLists::Id))
+                    .col(
+                        uuid_uniq(PriceLists::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(string(PriceLists::Name).string_len(150))
+                    .col(enumeration(
+                        PriceLists::PayerType,
+                        Alias::new("payer_type"),
+                        vec![
+                            Alias::new("cash"),
+                            Alias::new("insurance"),
+                            Alias::new("corporate"),
+                        ],
+                    ))
+                    .col(uuid_null(PriceLists::InsuranceProviderPid))
+                    .col(string_null(PriceLists::SchemeName).string_len(150))
+                    .col(boolean(PriceLists::IsDefault).default(false))
+                    .col(boolean(PriceLists::IsActive).default(true))
+                    .col(uuid(PriceLists::CreatedBy))
+                    .col(timestamp_null(PriceLists::DeletedAt))
+                    .col(
+                        timestamp(PriceLists::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(PriceLists::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PriceLists::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(Alias::new("payer_type")).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PriceLists {
+    Table,
+    Id,
+    Pid,
+    Name,
+    PayerType,
+    InsuranceProviderPid,
+    SchemeName,
+    IsDefault,
+    IsActive,
+    CreatedBy,
+    DeletedAt,
+    CreatedAt,
+    UpdatedAt,
+}
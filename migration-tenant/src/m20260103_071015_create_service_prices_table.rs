@@ -0,0 +1,97 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ServicePrices::Table)
+                    .if_not_exists()
+                    .col(pk_auto(ServicePrices::Id))
+                    .col(
+                        uuid_uniq(ServicePrices::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer(ServicePrices::PriceListId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-service_prices-price_list_id")
+                            .from(ServicePrices::Table, ServicePrices::PriceListId)
+                            .to(PriceLists::Table, PriceLists::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(integer(ServicePrices::ServiceId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-service_prices-service_id")
+                            .from(ServicePrices::Table, ServicePrices::ServiceId)
+                            .to(BillableServices::Table, BillableServices::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(decimal(ServicePrices::Amount).decimal_len(12, 2))
+                    .col(string(ServicePrices::Currency).string_len(3))
+                    .col(date(ServicePrices::EffectiveFrom))
+                    .col(date_null(ServicePrices::EffectiveTo))
+                    .col(uuid(ServicePrices::CreatedBy))
+                    .col(
+                        timestamp(ServicePrices::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(ServicePrices::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _uniq_service_prices_list_service_from = Index::create()
+            .name("uniq_service_prices_list_service_from")
+            .table(ServicePrices::Table)
+            .col(ServicePrices::PriceListId)
+            .col(ServicePrices::ServiceId)
+            .col(ServicePrices::EffectiveFrom)
+            .unique()
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ServicePrices::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ServicePrices {
+    Table,
+    Id,
+    Pid,
+    PriceListId,
+    ServiceId,
+    Amount,
+    Currency,
+    EffectiveFrom,
+    EffectiveTo,
+    CreatedBy,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum PriceLists {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum BillableServices {
+    Table,
+    Id,
+}
@@ -0,0 +1,51 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PharmacyProducts::Table)
+                    .add_column_if_not_exists(integer_null(PharmacyProducts::ServiceId))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk-pharmacy_products-service_id")
+                            .from_tbl(PharmacyProducts::Table)
+                            .from_col(PharmacyProducts::ServiceId)
+                            .to_tbl(BillableServices::Table)
+                            .to_col(BillableServices::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PharmacyProducts::Table)
+                    .drop_foreign_key(Alias::new("fk-pharmacy_products-service_id"))
+                    .drop_column(PharmacyProducts::ServiceId)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PharmacyProducts {
+    Table,
+    ServiceId,
+}
+
+#[derive(DeriveIden)]
+enum BillableServices {
+    Table,
+    Id,
+}
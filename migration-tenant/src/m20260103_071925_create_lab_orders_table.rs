@@ -0,0 +1,130 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("lab_order_status"))
+                    .values([
+                        Alias::new("ordered"),
+                        Alias::new("completed"),
+                        Alias::new("cancelled"),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(LabOrders::Table)
+                    .if_not_exists()
+                    .col(pk_auto(LabOrders::Id))
+                    .col(
+                        uuid_uniq(LabOrders::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(string_uniq(LabOrders::OrderNumber).string_len(30))
+                    .col(uuid(LabOrders::PatientPid))
+                    .col(integer_null(LabOrders::EncounterId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-lab_orders-encounter_id")
+                            .from(LabOrders::Table, LabOrders::EncounterId)
+                            .to(Encounters::Table, Encounters::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .col(integer(LabOrders::ServiceId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-lab_orders-service_id")
+                            .from(LabOrders::Table, LabOrders::ServiceId)
+                            .to(BillableServices::Table, BillableServices::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(enumeration(
+                        LabOrders::Status,
+                        Alias::new("lab_order_status"),
+                        vec![
+                            Alias::new("ordered"),
+                            Alias::new("completed"),
+                            Alias::new("cancelled"),
+                        ],
+                    ))
+                    .col(text_null(LabOrders::ClinicalNotes))
+                    .col(uuid(LabOrders::OrderedBy))
+                    .col(timestamp_null(LabOrders::CompletedAt))
+                    .col(uuid_null(LabOrders::CompletedBy))
+                    .col(timestamp_null(LabOrders::CancelledAt))
+                    .col(uuid_null(LabOrders::CancelledBy))
+                    .col(text_null(LabOrders::CancelReason))
+                    .col(
+                        timestamp(LabOrders::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(LabOrders::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_lab_orders_patient_pid = Index::create()
+            .name("idx_lab_orders_patient_pid")
+            .table(LabOrders::Table)
+            .col(LabOrders::PatientPid)
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(LabOrders::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(Alias::new("lab_order_status")).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum LabOrders {
+    Table,
+    Id,
+    Pid,
+    OrderNumber,
+    PatientPid,
+    EncounterId,
+    ServiceId,
+    Status,
+    ClinicalNotes,
+    OrderedBy,
+    CompletedAt,
+    CompletedBy,
+    CancelledAt,
+    CancelledBy,
+    CancelReason,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Encounters {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum BillableServices {
+    Table,
+    Id,
+}
@@ -0,0 +1,56 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PatientCharges::Table)
+                    .add_column_if_not_exists(
+                        string_null(PatientCharges::ServiceCode).string_len(30),
+                    )
+                    .add_column_if_not_exists(integer_null(PatientCharges::PriceListId))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk-patient_charges-price_list_id")
+                            .from_tbl(PatientCharges::Table)
+                            .from_col(PatientCharges::PriceListId)
+                            .to_tbl(PriceLists::Table)
+                            .to_col(PriceLists::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PatientCharges::Table)
+                    .drop_foreign_key(Alias::new("fk-patient_charges-price_list_id"))
+                    .drop_column(PatientCharges::PriceListId)
+                    .drop_column(PatientCharges::ServiceCode)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PatientCharges {
+    Table,
+    ServiceCode,
+    PriceListId,
+}
+
+#[derive(DeriveIden)]
+enum PriceLists {
+    Table,
+    Id,
+}
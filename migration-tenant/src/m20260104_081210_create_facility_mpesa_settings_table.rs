@@ -0,0 +1,62 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(FacilityMpesaSettings::Table)
+                    .if_not_exists()
+                    .col(pk_auto(FacilityMpesaSettings::Id))
+                    .col(
+                        uuid_uniq(FacilityMpesaSettings::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(string(FacilityMpesaSettings::Shortcode).string_len(20))
+                    .col(string_null(FacilityMpesaSettings::TillNumber).string_len(20))
+                    .col(string(FacilityMpesaSettings::TransactionType).string_len(40))
+                    .col(text(FacilityMpesaSettings::Passkey))
+                    .col(text(FacilityMpesaSettings::ConsumerKey))
+                    .col(text(FacilityMpesaSettings::ConsumerSecret))
+                    .col(boolean(FacilityMpesaSettings::IsActive).default(true))
+                    .col(uuid(FacilityMpesaSettings::UpdatedBy))
+                    .col(
+                        timestamp(FacilityMpesaSettings::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(FacilityMpesaSettings::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(FacilityMpesaSettings::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum FacilityMpesaSettings {
+    Table,
+    Id,
+    Pid,
+    Shortcode,
+    TillNumber,
+    TransactionType,
+    Passkey,
+    ConsumerKey,
+    ConsumerSecret,
+    IsActive,
+    UpdatedBy,
+    CreatedAt,
+    UpdatedAt,
+}
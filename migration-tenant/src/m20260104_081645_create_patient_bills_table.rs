@@ -0,0 +1,165 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("patient_bill_status"))
+                    .values([
+                        Alias::new("open"),
+                        Alias::new("finalized"),
+                        Alias::new("paid"),
+                        Alias::new("void"),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(PatientBills::Table)
+                    .if_not_exists()
+                    .col(pk_auto(PatientBills::Id))
+                    .col(
+                        uuid_uniq(PatientBills::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(string_uniq(PatientBills::BillNumber).string_len(30))
+                    .col(uuid(PatientBills::PatientPid))
+                    .col(integer_null(PatientBills::EncounterId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-patient_bills-encounter_id")
+                            .from(PatientBills::Table, PatientBills::EncounterId)
+                            .to(Encounters::Table, Encounters::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .col(enumeration(
+                        PatientBills::Status,
+                        Alias::new("patient_bill_status"),
+                        vec![
+                            Alias::new("open"),
+                            Alias::new("finalized"),
+                            Alias::new("paid"),
+                            Alias::new("void"),
+                        ],
+                    ))
+                    .col(uuid_null(PatientBills::PatientInsurancePid))
+                    .col(uuid_null(PatientBills::InsuranceProviderPid))
+                    .col(
+                        decimal(PatientBills::Subtotal)
+                            .decimal_len(12, 2)
+                            .default(0),
+                    )
+                    .col(
+                        decimal(PatientBills::DiscountAmount)
+                            .decimal_len(12, 2)
+                            .default(0),
+                    )
+                    .col(
+                        decimal(PatientBills::TotalAmount)
+                            .decimal_len(12, 2)
+                            .default(0),
+                    )
+                    .col(
+                        decimal(PatientBills::InsuranceAmount)
+                            .decimal_len(12, 2)
+                            .default(0),
+                    )
+                    .col(
+                        decimal(PatientBills::PatientAmount)
+                            .decimal_len(12, 2)
+                            .default(0),
+                    )
+                    .col(
+                        decimal(PatientBills::AmountPaid)
+                            .decimal_len(12, 2)
+                            .default(0),
+                    )
+                    .col(string(PatientBills::Currency).string_len(3))
+                    .col(text_null(PatientBills::Notes))
+                    .col(uuid(PatientBills::OpenedBy))
+                    .col(timestamp_null(PatientBills::FinalizedAt))
+                    .col(uuid_null(PatientBills::FinalizedBy))
+                    .col(timestamp_null(PatientBills::PaidAt))
+                    .col(timestamp_null(PatientBills::VoidedAt))
+                    .col(uuid_null(PatientBills::VoidedBy))
+                    .col(text_null(PatientBills::VoidReason))
+                    .col(
+                        timestamp(PatientBills::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(PatientBills::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_patient_bills_patient_pid = Index::create()
+            .name("idx_patient_bills_patient_pid")
+            .table(PatientBills::Table)
+            .col(PatientBills::PatientPid)
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PatientBills::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(Alias::new("patient_bill_status"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PatientBills {
+    Table,
+    Id,
+    Pid,
+    BillNumber,
+    PatientPid,
+    EncounterId,
+    Status,
+    PatientInsurancePid,
+    InsuranceProviderPid,
+    Subtotal,
+    DiscountAmount,
+    TotalAmount,
+    InsuranceAmount,
+    PatientAmount,
+    AmountPaid,
+    Currency,
+    Notes,
+    OpenedBy,
+    FinalizedAt,
+    FinalizedBy,
+    PaidAt,
+    VoidedAt,
+    VoidedBy,
+    VoidReason,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Encounters {
+    Table,
+    Id,
+}
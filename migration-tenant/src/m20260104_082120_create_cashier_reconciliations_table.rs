@@ -0,0 +1,76 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(CashierReconciliations::Table)
+                    .if_not_exists()
+                    .col(pk_auto(CashierReconciliations::Id))
+                    .col(
+                        uuid_uniq(CashierReconciliations::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(uuid(CashierReconciliations::CashierId))
+                    .col(date(CashierReconciliations::BusinessDate))
+                    .col(decimal(CashierReconciliations::CashExpected).decimal_len(12, 2))
+                    .col(decimal(CashierReconciliations::CashCounted).decimal_len(12, 2))
+                    .col(decimal(CashierReconciliations::CashVariance).decimal_len(12, 2))
+                    .col(decimal(CashierReconciliations::MpesaTotal).decimal_len(12, 2))
+                    .col(integer(CashierReconciliations::PaymentCount))
+                    .col(text_null(CashierReconciliations::Notes))
+                    .col(
+                        timestamp(CashierReconciliations::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(CashierReconciliations::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _uniq_cashier_reconciliations_cashier_date = Index::create()
+            .name("uniq_cashier_reconciliations_cashier_date")
+            .table(CashierReconciliations::Table)
+            .col(CashierReconciliations::CashierId)
+            .col(CashierReconciliations::BusinessDate)
+            .unique()
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(CashierReconciliations::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum CashierReconciliations {
+    Table,
+    Id,
+    Pid,
+    CashierId,
+    BusinessDate,
+    CashExpected,
+    CashCounted,
+    CashVariance,
+    MpesaTotal,
+    PaymentCount,
+    Notes,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,165 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("bill_payment_method"))
+                    .values([Alias::new("cash"), Alias::new("mpesa")])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("bill_payment_status"))
+                    .values([
+                        Alias::new("pending"),
+                        Alias::new("completed"),
+                        Alias::new("failed"),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(BillPayments::Table)
+                    .if_not_exists()
+                    .col(pk_auto(BillPayments::Id))
+                    .col(
+                        uuid_uniq(BillPayments::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer(BillPayments::BillId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-bill_payments-bill_id")
+                            .from(BillPayments::Table, BillPayments::BillId)
+                            .to(PatientBills::Table, PatientBills::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(string_uniq(BillPayments::ReceiptNumber).string_len(30))
+                    .col(enumeration(
+                        BillPayments::Method,
+                        Alias::new("bill_payment_method"),
+                        vec![Alias::new("cash"), Alias::new("mpesa")],
+                    ))
+                    .col(enumeration(
+                        BillPayments::Status,
+                        Alias::new("bill_payment_status"),
+                        vec![
+                            Alias::new("pending"),
+                            Alias::new("completed"),
+                            Alias::new("failed"),
+                        ],
+                    ))
+                    .col(decimal(BillPayments::Amount).decimal_len(12, 2))
+                    .col(decimal_null(BillPayments::TenderedAmount).decimal_len(12, 2))
+                    .col(decimal_null(BillPayments::ChangeAmount).decimal_len(12, 2))
+                    .col(boolean(BillPayments::IsDeposit).default(false))
+                    .col(string_null(BillPayments::PhoneNumber).string_len(20))
+                    .col(string_null(BillPayments::CheckoutRequestId).string_len(100))
+                    .col(string_null(BillPayments::CallbackToken).string_len(64))
+                    .col(string_null(BillPayments::MpesaReceipt).string_len(30))
+                    .col(text_null(BillPayments::FailureReason))
+                    .col(uuid(BillPayments::ReceivedBy))
+                    .col(timestamp_null(BillPayments::PaidAt))
+                    .col(uuid_null(BillPayments::ReceiptDocumentFilePid))
+                    .col(integer_null(BillPayments::ReconciliationId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-bill_payments-reconciliation_id")
+                            .from(BillPayments::Table, BillPayments::ReconciliationId)
+                            .to(CashierReconciliations::Table, CashierReconciliations::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .col(
+                        timestamp(BillPayments::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(BillPayments::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_bill_payments_checkout_request_id = Index::create()
+            .name("idx_bill_payments_checkout_request_id")
+            .table(BillPayments::Table)
+            .col(BillPayments::CheckoutRequestId)
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(BillPayments::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(Alias::new("bill_payment_status"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(Alias::new("bill_payment_method"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum BillPayments {
+    Table,
+    Id,
+    Pid,
+    BillId,
+    ReceiptNumber,
+    Method,
+    Status,
+    Amount,
+    TenderedAmount,
+    ChangeAmount,
+    IsDeposit,
+    PhoneNumber,
+    CheckoutRequestId,
+    CallbackToken,
+    MpesaReceipt,
+    FailureReason,
+    ReceivedBy,
+    PaidAt,
+    ReceiptDocumentFilePid,
+    ReconciliationId,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum PatientBills {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum CashierReconciliations {
+    Table,
+    Id,
+}
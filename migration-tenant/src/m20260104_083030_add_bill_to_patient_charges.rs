@@ -0,0 +1,51 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PatientCharges::Table)
+                    .add_column_if_not_exists(integer_null(PatientCharges::BillId))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk-patient_charges-bill_id")
+                            .from_tbl(PatientCharges::Table)
+                            .from_col(PatientCharges::BillId)
+                            .to_tbl(PatientBills::Table)
+                            .to_col(PatientBills::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PatientCharges::Table)
+                    .drop_foreign_key(Alias::new("fk-patient_charges-bill_id"))
+                    .drop_column(PatientCharges::BillId)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PatientCharges {
+    Table,
+    BillId,
+}
+
+#[derive(DeriveIden)]
+enum PatientBills {
+    Table,
+    Id,
+}
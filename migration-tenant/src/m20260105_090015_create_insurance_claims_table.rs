@@ -0,0 +1,171 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("insurance_claim_status"))
+                    .values([
+                        Alias::new("draft"),
+                        Alias::new("submitted"),
+                        Alias::new("queried"),
+                        Alias::new("approved"),
+                        Alias::new("partially_paid"),
+                        Alias::new("paid"),
+                        Alias::new("rejected"),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(InsuranceClaims::Table)
+                    .if_not_exists()
+                    .col(pk_auto(InsuranceClaims::Id))
+                    .col(
+                        uuid_uniq(InsuranceClaims::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(string_uniq(InsuranceClaims::ClaimNumber).string_len(30))
+                    .col(integer(InsuranceClaims::BillId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-insurance_claims-bill_id")
+                            .from(InsuranceClaims::Table, InsuranceClaims::BillId)
+                            .to(PatientBills::Table, PatientBills::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(uuid(InsuranceClaims::PatientPid))
+                    .col(uuid(InsuranceClaims::PatientInsurancePid))
+                    .col(uuid(InsuranceClaims::InsuranceProviderPid))
+                    .col(enumeration(
+                        InsuranceClaims::Status,
+                        Alias::new("insurance_claim_status"),
+                        vec![
+                            Alias::new("draft"),
+                            Alias::new("submitted"),
+                            Alias::new("queried"),
+                            Alias::new("approved"),
+                            Alias::new("partially_paid"),
+                            Alias::new("paid"),
+                            Alias::new("rejected"),
+                        ],
+                    ))
+                    .col(array(
+                        InsuranceClaims::DiagnosisCodes,
+                        ColumnType::String(StringLen::None),
+                    ))
+                    .col(decimal(InsuranceClaims::GrossAmount).decimal_len(12, 2))
+                    .col(
+                        decimal(InsuranceClaims::CopayAmount)
+                            .decimal_len(12, 2)
+                            .default(0),
+                    )
+                    .col(
+                        decimal(InsuranceClaims::DeductibleAmount)
+                            .decimal_len(12, 2)
+                            .default(0),
+                    )
+                    .col(decimal(InsuranceClaims::ClaimedAmount).decimal_len(12, 2))
+                    .col(decimal_null(InsuranceClaims::ApprovedAmount).decimal_len(12, 2))
+                    .col(
+                        decimal(InsuranceClaims::PaidAmount)
+                            .decimal_len(12, 2)
+                            .default(0),
+                    )
+                    .col(string(InsuranceClaims::Currency).string_len(3))
+                    .col(string_null(InsuranceClaims::InsurerReference).string_len(100))
+                    .col(text_null(InsuranceClaims::QueryNotes))
+                    .col(text_null(InsuranceClaims::RejectionReason))
+                    .col(text_null(InsuranceClaims::Notes))
+                    .col(uuid(InsuranceClaims::CreatedBy))
+                    .col(timestamp_null(InsuranceClaims::SubmittedAt))
+                    .col(uuid_null(InsuranceClaims::SubmittedBy))
+                    .col(timestamp_null(InsuranceClaims::QueriedAt))
+                    .col(timestamp_null(InsuranceClaims::ApprovedAt))
+                    .col(timestamp_null(InsuranceClaims::RejectedAt))
+                    .col(timestamp_null(InsuranceClaims::PaidAt))
+                    .col(
+                        timestamp(InsuranceClaims::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(InsuranceClaims::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let _idx_insurance_claims_provider_status = Index::create()
+            .name("idx_insurance_claims_provider_status")
+            .table(InsuranceClaims::Table)
+            .col(InsuranceClaims::InsuranceProviderPid)
+            .col(InsuranceClaims::Status)
+            .to_owned();
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(InsuranceClaims::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(Alias::new("insurance_claim_status"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum InsuranceClaims {
+    Table,
+    Id,
+    Pid,
+    ClaimNumber,
+    BillId,
+    PatientPid,
+    PatientInsurancePid,
+    InsuranceProviderPid,
+    Status,
+    DiagnosisCodes,
+    GrossAmount,
+    CopayAmount,
+    DeductibleAmount,
+    ClaimedAmount,
+    ApprovedAmount,
+    PaidAmount,
+    Currency,
+    InsurerReference,
+    QueryNotes,
+    RejectionReason,
+    Notes,
+    CreatedBy,
+    SubmittedAt,
+    SubmittedBy,
+    QueriedAt,
+    ApprovedAt,
+    RejectedAt,
+    PaidAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum PatientBills {
+    Table,
+    Id,
+}
@@ -0,0 +1,90 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(InsuranceClaimLines::Table)
+                    .if_not_exists()
+                    .col(pk_auto(InsuranceClaimLines::Id))
+                    .col(
+                        uuid_uniq(InsuranceClaimLines::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer(InsuranceClaimLines::ClaimId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-insurance_claim_lines-claim_id")
+                            .from(InsuranceClaimLines::Table, InsuranceClaimLines::ClaimId)
+                            .to(InsuranceClaims::Table, InsuranceClaims::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(integer_null(InsuranceClaimLines::ChargeId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-insurance_claim_lines-charge_id")
+                            .from(InsuranceClaimLines::Table, InsuranceClaimLines::ChargeId)
+                            .to(PatientCharges::Table, PatientCharges::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .col(string_null(InsuranceClaimLines::ServiceCode).string_len(30))
+                    .col(string(InsuranceClaimLines::Description))
+                    .col(integer(InsuranceClaimLines::Quantity))
+                    .col(decimal(InsuranceClaimLines::UnitPrice).decimal_len(12, 2))
+                    .col(decimal(InsuranceClaimLines::TotalAmount).decimal_len(12, 2))
+                    .col(decimal_null(InsuranceClaimLines::ApprovedAmount).decimal_len(12, 2))
+                    .col(
+                        timestamp(InsuranceClaimLines::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(InsuranceClaimLines::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(InsuranceClaimLines::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum InsuranceClaimLines {
+    Table,
+    Id,
+    Pid,
+    ClaimId,
+    ChargeId,
+    ServiceCode,
+    Description,
+    Quantity,
+    UnitPrice,
+    TotalAmount,
+    ApprovedAmount,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum InsuranceClaims {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum PatientCharges {
+    Table,
+    Id,
+}
@@ -0,0 +1,75 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(InsuranceClaimAttachments::Table)
+                    .if_not_exists()
+                    .col(pk_auto(InsuranceClaimAttachments::Id))
+                    .col(
+                        uuid_uniq(InsuranceClaimAttachments::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer(InsuranceClaimAttachments::ClaimId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-insurance_claim_attachments-claim_id")
+                            .from(
+                                InsuranceClaimAttachments::Table,
+                                InsuranceClaimAttachments::ClaimId,
+                            )
+                            .to(InsuranceClaims::Table, InsuranceClaims::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(uuid(InsuranceClaimAttachments::FilePid))
+                    .col(string(InsuranceClaimAttachments::OriginalName))
+                    .col(string(InsuranceClaimAttachments::ContentType))
+                    .col(big_integer(InsuranceClaimAttachments::SizeBytes))
+                    .col(uuid(InsuranceClaimAttachments::UploadedBy))
+                    .col(
+                        timestamp(InsuranceClaimAttachments::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(InsuranceClaimAttachments::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum InsuranceClaimAttachments {
+    Table,
+    Id,
+    Pid,
+    ClaimId,
+    FilePid,
+    OriginalName,
+    ContentType,
+    SizeBytes,
+    UploadedBy,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum InsuranceClaims {
+    Table,
+    Id,
+}
@@ -0,0 +1,70 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(InsuranceRemittances::Table)
+                    .if_not_exists()
+                    .col(pk_auto(InsuranceRemittances::Id))
+                    .col(
+                        uuid_uniq(InsuranceRemittances::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(string_uniq(InsuranceRemittances::RemittanceNumber).string_len(30))
+                    .col(uuid(InsuranceRemittances::InsuranceProviderPid))
+                    .col(string(InsuranceRemittances::Reference).string_len(100))
+                    .col(date(InsuranceRemittances::PaymentDate))
+                    .col(decimal(InsuranceRemittances::Amount).decimal_len(12, 2))
+                    .col(
+                        decimal(InsuranceRemittances::AllocatedAmount)
+                            .decimal_len(12, 2)
+                            .default(0),
+                    )
+                    .col(string(InsuranceRemittances::Currency).string_len(3))
+                    .col(text_null(InsuranceRemittances::Notes))
+                    .col(uuid(InsuranceRemittances::ReceivedBy))
+                    .col(
+                        timestamp(InsuranceRemittances::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(InsuranceRemittances::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(InsuranceRemittances::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum InsuranceRemittances {
+    Table,
+    Id,
+    Pid,
+    RemittanceNumber,
+    InsuranceProviderPid,
+    Reference,
+    PaymentDate,
+    Amount,
+    AllocatedAmount,
+    Currency,
+    Notes,
+    ReceivedBy,
+    CreatedAt,
+    UpdatedAt,
+}
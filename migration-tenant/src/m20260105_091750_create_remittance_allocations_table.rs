@@ -0,0 +1,80 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(RemittanceAllocations::Table)
+                    .if_not_exists()
+                    .col(pk_auto(RemittanceAllocations::Id))
+                    .col(
+                        uuid_uniq(RemittanceAllocations::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer(RemittanceAllocations::RemittanceId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-remittance_allocations-remittance_id")
+                            .from(
+                                RemittanceAllocations::Table,
+                                RemittanceAllocations::RemittanceId,
+                            )
+                            .to(InsuranceRemittances::Table, InsuranceRemittances::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(integer(RemittanceAllocations::ClaimId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-remittance_allocations-claim_id")
+                            .from(RemittanceAllocations::Table, RemittanceAllocations::ClaimId)
+                            .to(InsuranceClaims::Table, InsuranceClaims::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .col(decimal(RemittanceAllocations::Amount).decimal_len(12, 2))
+                    .col(uuid(RemittanceAllocations::AllocatedBy))
+                    .col(
+                        timestamp(RemittanceAllocations::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(RemittanceAllocations::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum RemittanceAllocations {
+    Table,
+    Id,
+    Pid,
+    RemittanceId,
+    ClaimId,
+    Amount,
+    AllocatedBy,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum InsuranceRemittances {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum InsuranceClaims {
+    Table,
+    Id,
+}
@@ -0,0 +1,46 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PatientBills::Table)
+                    .add_column_if_not_exists(
+                        decimal(PatientBills::DeductibleAmount)
+                            .decimal_len(12, 2)
+                            .default(0),
+                    )
+                    .add_column_if_not_exists(
+                        decimal(PatientBills::OutOfPocketApplied)
+                            .decimal_len(12, 2)
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PatientBills::Table)
+                    .drop_column(PatientBills::DeductibleAmount)
+                    .drop_column(PatientBills::OutOfPocketApplied)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PatientBills {
+    Table,
+    DeductibleAmount,
+    OutOfPocketApplied,
+}
@@ -0,0 +1,149 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("preauthorization_status"))
+                    .values([
+                        Alias::new("pending"),
+                        Alias::new("approved"),
+                        Alias::new("denied"),
+                        Alias::new("expired"),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Preauthorizations::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Preauthorizations::Id))
+                    .col(
+                        uuid_uniq(Preauthorizations::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(string_uniq(Preauthorizations::PreauthNumber).string_len(30))
+                    .col(uuid(Preauthorizations::PatientPid))
+                    .col(uuid(Preauthorizations::PatientInsurancePid))
+                    .col(uuid(Preauthorizations::InsuranceProviderPid))
+                    .col(uuid_null(Preauthorizations::EncounterPid))
+                    .col(date_null(Preauthorizations::PlannedDate))
+                    .col(enumeration(
+                        Preauthorizations::Status,
+                        Alias::new("preauthorization_status"),
+                        vec![
+                            Alias::new("pending"),
+                            Alias::new("approved"),
+                            Alias::new("denied"),
+                            Alias::new("expired"),
+                        ],
+                    ))
+                    .col(array(
+                        Preauthorizations::DiagnosisCodes,
+                        ColumnType::String(StringLen::None),
+                    ))
+                    .col(text_null(Preauthorizations::ClinicalNotes))
+                    .col(decimal(Preauthorizations::EstimatedAmount).decimal_len(12, 2))
+                    .col(decimal_null(Preauthorizations::ApprovedAmount).decimal_len(12, 2))
+                    .col(string(Preauthorizations::Currency).string_len(3))
+                    .col(string_null(Preauthorizations::AuthorizationNumber).string_len(100))
+                    .col(string_null(Preauthorizations::InsurerReference).string_len(100))
+                    .col(text_null(Preauthorizations::DenialReason))
+                    .col(date_null(Preauthorizations::ValidUntil))
+                    .col(integer_null(Preauthorizations::ClaimId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-preauthorizations-claim_id")
+                            .from(Preauthorizations::Table, Preauthorizations::ClaimId)
+                            .to(InsuranceClaims::Table, InsuranceClaims::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .col(uuid(Preauthorizations::RequestedBy))
+                    .col(timestamp_null(Preauthorizations::SubmittedAt))
+                    .col(timestamp_null(Preauthorizations::DecidedAt))
+                    .col(uuid_null(Preauthorizations::DecidedBy))
+                    .col(
+                        timestamp(Preauthorizations::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .col(
+                        timestamp(Preauthorizations::UpdatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_preauthorizations_patient_status")
+                    .table(Preauthorizations::Table)
+                    .col(Preauthorizations::PatientPid)
+                    .col(Preauthorizations::Status)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Preauthorizations::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(Alias::new("preauthorization_status"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Preauthorizations {
+    Table,
+    Id,
+    Pid,
+    PreauthNumber,
+    PatientPid,
+    PatientInsurancePid,
+    InsuranceProviderPid,
+    EncounterPid,
+    PlannedDate,
+    Status,
+    DiagnosisCodes,
+    ClinicalNotes,
+    EstimatedAmount,
+    ApprovedAmount,
+    Currency,
+    AuthorizationNumber,
+    InsurerReference,
+    DenialReason,
+    ValidUntil,
+    ClaimId,
+    RequestedBy,
+    SubmittedAt,
+    DecidedAt,
+    DecidedBy,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum InsuranceClaims {
+    Table,
+    Id,
+}
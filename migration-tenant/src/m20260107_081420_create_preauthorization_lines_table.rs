@@ -0,0 +1,89 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PreauthorizationLines::Table)
+                    .if_not_exists()
+                    .col(pk_auto(PreauthorizationLines::Id))
+                    .col(
+                        uuid_uniq(PreauthorizationLines::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer(PreauthorizationLines::PreauthorizationId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-preauthorization_lines-preauthorization_id")
+                            .from(
+                                PreauthorizationLines::Table,
+                                PreauthorizationLines::PreauthorizationId,
+                            )
+                            .to(Preauthorizations::Table, Preauthorizations::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(integer_null(PreauthorizationLines::ServiceId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-preauthorization_lines-service_id")
+                            .from(
+                                PreauthorizationLines::Table,
+                                PreauthorizationLines::ServiceId,
+                            )
+                            .to(BillableServices::Table, BillableServices::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .col(string(PreauthorizationLines::ServiceCode).string_len(30))
+                    .col(string(PreauthorizationLines::Description))
+                    .col(integer(PreauthorizationLines::Quantity))
+                    .col(decimal(PreauthorizationLines::UnitPrice).decimal_len(12, 2))
+                    .col(decimal(PreauthorizationLines::TotalAmount).decimal_len(12, 2))
+                    .col(
+                        timestamp(PreauthorizationLines::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PreauthorizationLines::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PreauthorizationLines {
+    Table,
+    Id,
+    Pid,
+    PreauthorizationId,
+    ServiceId,
+    ServiceCode,
+    Description,
+    Quantity,
+    UnitPrice,
+    TotalAmount,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Preauthorizations {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum BillableServices {
+    Table,
+    Id,
+}
@@ -0,0 +1,75 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PreauthorizationAttachments::Table)
+                    .if_not_exists()
+                    .col(pk_auto(PreauthorizationAttachments::Id))
+                    .col(
+                        uuid_uniq(PreauthorizationAttachments::Pid)
+                            .default(SimpleExpr::Custom("gen_random_uuid()".into())),
+                    )
+                    .col(integer(PreauthorizationAttachments::PreauthorizationId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-preauthorization_attachments-preauthorization_id")
+                            .from(
+                                PreauthorizationAttachments::Table,
+                                PreauthorizationAttachments::PreauthorizationId,
+                            )
+                            .to(Preauthorizations::Table, Preauthorizations::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(uuid(PreauthorizationAttachments::FilePid))
+                    .col(string(PreauthorizationAttachments::OriginalName))
+                    .col(string(PreauthorizationAttachments::ContentType))
+                    .col(big_integer(PreauthorizationAttachments::SizeBytes))
+                    .col(uuid(PreauthorizationAttachments::UploadedBy))
+                    .col(
+                        timestamp(PreauthorizationAttachments::CreatedAt)
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(PreauthorizationAttachments::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PreauthorizationAttachments {
+    Table,
+    Id,
+    Pid,
+    PreauthorizationId,
+    FilePid,
+    OriginalName,
+    ContentType,
+    SizeBytes,
+    UploadedBy,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Preauthorizations {
+    Table,
+    Id,
+}
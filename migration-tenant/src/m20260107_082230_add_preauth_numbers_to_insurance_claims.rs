@@ -0,0 +1,41 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(InsuranceClaims::Table)
+                    .add_column_if_not_exists(
+                        array(
+                            InsuranceClaims::PreauthNumbers,
+                            ColumnType::String(StringLen::None),
+                        )
+                        .default(SimpleExpr::Custom("'{}'".into())),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(InsuranceClaims::Table)
+                    .drop_column(InsuranceClaims::PreauthNumbers)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum InsuranceClaims {
+    Table,
+    PreauthNumbers,
+}
@@ -0,0 +1,50 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(InsuranceClaims::Table)
+                    .add_column_if_not_exists(
+                        decimal(InsuranceClaims::DeductiblePosted)
+                            .decimal_len(12, 2)
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Approvals before this column already posted their deductible.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "UPDATE insurance_claims SET deductible_posted = deductible_amount \
+                 WHERE status IN ('approved', 'partially_paid', 'paid');",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(InsuranceClaims::Table)
+                    .drop_column(InsuranceClaims::DeductiblePosted)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum InsuranceClaims {
+    Table,
+    DeductiblePosted,
+}
@@ -1,10 +1,34 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use actix_web::web;
 use sea_orm::DatabaseConnection;
 use serde_json::json;
 use tokio_cron_scheduler::{Job, JobScheduler};
+use uuid::Uuid;
 
-use crate::utils::api_response::ApiResponse;
+use crate::{
+    cron_jobs::{
+        defaulter_tracing::process_defaulter_tracing,
+        immunization_reminders::process_immunization_reminders,
+        inpatient_metrics::process_inpatient_metrics,
+        insurance_accumulators::{process_accumulator_postings, process_accumulator_reset},
+        insurance_expiry::process_insurance_expiry,
+        message_sla::process_message_sla,
+        preauthorization_expiry::process_preauthorization_expiry,
+        queue_metrics::process_queue_metrics,
+        stock_alerts::process_stock_alerts,
+    },
+    utils::{api_response::ApiResponse, message_queue::MessageQueue},
+};
 
-pub async fn init_cron_jobs(db: &DatabaseConnection) -> Result<(), ApiResponse> {
+pub async fn init_cron_jobs(
+    db: &DatabaseConnection,
+    tenant_dbs: &Arc<RwLock<HashMap<Uuid, DatabaseConnection>>>,
+    message_queue: &web::Data<MessageQueue>,
+) -> Result<(), ApiResponse> {
     let sched = JobScheduler::new().await.map_err(|err| {
         log::error!("Failed to create scheduler: {}", err);
         ApiResponse::new(500, json!({ "message": "Failed to create scheduler" }))
@@ -20,7 +44,220 @@ pub async fn init_cron_jobs(db: &DatabaseConnection) -> Result<(), ApiResponse>
     // })?;
 
     // sched.add(job).await?;
-    // sched.start().await?;
+
+    let reminder_db = db.clone();
+    let reminder_tenant_dbs = tenant_dbs.clone();
+    let reminder_queue = message_queue.clone();
+    let immunization_reminders = Job::new_async("0 0 8 * * *", move |_uuid, _l| {
+        let db = reminder_db.clone();
+        let tenant_dbs = reminder_tenant_dbs.clone();
+        let message_queue = reminder_queue.clone();
+        Box::pin(async move {
+            if let Err(err) = process_immunization_reminders(&db, &tenant_dbs, &message_queue).await
+            {
+                log::error!("Immunization reminder error: {}", err);
+            }
+        })
+    })
+    .map_err(|err| {
+        log::error!("Failed to create immunization reminder job: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create cron job" }))
+    })?;
+
+    sched.add(immunization_reminders).await.map_err(|err| {
+        log::error!("Failed to schedule immunization reminders: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to schedule cron job" }))
+    })?;
+
+    let tracing_db = db.clone();
+    let tracing_tenant_dbs = tenant_dbs.clone();
+    let tracing_queue = message_queue.clone();
+    let defaulter_tracing = Job::new_async("0 30 8 * * *", move |_uuid, _l| {
+        let db = tracing_db.clone();
+        let tenant_dbs = tracing_tenant_dbs.clone();
+        let message_queue = tracing_queue.clone();
+        Box::pin(async move {
+            if let Err(err) = process_defaulter_tracing(&db, &tenant_dbs, &message_queue).await {
+                log::error!("Defaulter tracing error: {}", err);
+            }
+        })
+    })
+    .map_err(|err| {
+        log::error!("Failed to create defaulter tracing job: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create cron job" }))
+    })?;
+
+    sched.add(defaulter_tracing).await.map_err(|err| {
+        log::error!("Failed to schedule defaulter tracing: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to schedule cron job" }))
+    })?;
+
+    let metrics_db = db.clone();
+    let metrics_tenant_dbs = tenant_dbs.clone();
+    let inpatient_metrics = Job::new_async("0 15 0 * * *", move |_uuid, _l| {
+        let db = metrics_db.clone();
+        let tenant_dbs = metrics_tenant_dbs.clone();
+        Box::pin(async move {
+            if let Err(err) = process_inpatient_metrics(&db, &tenant_dbs).await {
+                log::error!("Inpatient metrics error: {}", err);
+            }
+        })
+    })
+    .map_err(|err| {
+        log::error!("Failed to create inpatient metrics job: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create cron job" }))
+    })?;
+
+    sched.add(inpatient_metrics).await.map_err(|err| {
+        log::error!("Failed to schedule inpatient metrics: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to schedule cron job" }))
+    })?;
+
+    let queue_db = db.clone();
+    let queue_tenant_dbs = tenant_dbs.clone();
+    let queue_metrics = Job::new_async("0 20 0 * * *", move |_uuid, _l| {
+        let db = queue_db.clone();
+        let tenant_dbs = queue_tenant_dbs.clone();
+        Box::pin(async move {
+            if let Err(err) = process_queue_metrics(&db, &tenant_dbs).await {
+                log::error!("Queue metrics error: {}", err);
+            }
+        })
+    })
+    .map_err(|err| {
+        log::error!("Failed to create queue metrics job: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create cron job" }))
+    })?;
+
+    sched.add(queue_metrics).await.map_err(|err| {
+        log::error!("Failed to schedule queue metrics: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to schedule cron job" }))
+    })?;
+
+    let sla_tenant_dbs = tenant_dbs.clone();
+    let message_sla = Job::new_async("0 */15 * * * *", move |_uuid, _l| {
+        let tenant_dbs = sla_tenant_dbs.clone();
+        Box::pin(async move {
+            if let Err(err) = process_message_sla(&tenant_dbs).await {
+                log::error!("Message SLA error: {}", err);
+            }
+        })
+    })
+    .map_err(|err| {
+        log::error!("Failed to create message SLA job: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create cron job" }))
+    })?;
+
+    sched.add(message_sla).await.map_err(|err| {
+        log::error!("Failed to schedule message SLA: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to schedule cron job" }))
+    })?;
+
+    let alerts_db = db.clone();
+    let alerts_tenant_dbs = tenant_dbs.clone();
+    let alerts_queue = message_queue.clone();
+    let stock_alerts = Job::new_async("0 0 7 * * *", move |_uuid, _l| {
+        let db = alerts_db.clone();
+        let tenant_dbs = alerts_tenant_dbs.clone();
+        let message_queue = alerts_queue.clone();
+        Box::pin(async move {
+            if let Err(err) = process_stock_alerts(&db, &tenant_dbs, &message_queue).await {
+                log::error!("Stock alerts error: {}", err);
+            }
+        })
+    })
+    .map_err(|err| {
+        log::error!("Failed to create stock alerts job: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create cron job" }))
+    })?;
+
+    sched.add(stock_alerts).await.map_err(|err| {
+        log::error!("Failed to schedule stock alerts: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to schedule cron job" }))
+    })?;
+
+    let expiry_db = db.clone();
+    let insurance_expiry = Job::new_async("0 5 0 * * *", move |_uuid, _l| {
+        let db = expiry_db.clone();
+        Box::pin(async move {
+            if let Err(err) = process_insurance_expiry(&db).await {
+                log::error!("Insurance expiry error: {}", err);
+            }
+        })
+    })
+    .map_err(|err| {
+        log::error!("Failed to create insurance expiry job: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create cron job" }))
+    })?;
+
+    sched.add(insurance_expiry).await.map_err(|err| {
+        log::error!("Failed to schedule insurance expiry: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to schedule cron job" }))
+    })?;
+
+    let accumulators_db = db.clone();
+    let insurance_accumulators = Job::new_async("0 10 0 * * *", move |_uuid, _l| {
+        let db = accumulators_db.clone();
+        Box::pin(async move {
+            if let Err(err) = process_accumulator_reset(&db).await {
+                log::error!("Insurance accumulator reset error: {}", err);
+            }
+        })
+    })
+    .map_err(|err| {
+        log::error!("Failed to create insurance accumulator job: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create cron job" }))
+    })?;
+
+    sched.add(insurance_accumulators).await.map_err(|err| {
+        log::error!("Failed to schedule insurance accumulator reset: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to schedule cron job" }))
+    })?;
+
+    let postings_db = db.clone();
+    let postings_tenant_dbs = tenant_dbs.clone();
+    let accumulator_postings = Job::new_async("0 40 * * * *", move |_uuid, _l| {
+        let db = postings_db.clone();
+        let tenant_dbs = postings_tenant_dbs.clone();
+        Box::pin(async move {
+            if let Err(err) = process_accumulator_postings(&db, &tenant_dbs).await {
+                log::error!("Insurance accumulator posting error: {}", err);
+            }
+        })
+    })
+    .map_err(|err| {
+        log::error!("Failed to create accumulator posting job: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create cron job" }))
+    })?;
+
+    sched.add(accumulator_postings).await.map_err(|err| {
+        log::error!("Failed to schedule accumulator postings: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to schedule cron job" }))
+    })?;
+
+    let preauth_tenant_dbs = tenant_dbs.clone();
+    let preauthorization_expiry = Job::new_async("0 15 0 * * *", move |_uuid, _l| {
+        let tenant_dbs = preauth_tenant_dbs.clone();
+        Box::pin(async move {
+            if let Err(err) = process_preauthorization_expiry(&tenant_dbs).await {
+                log::error!("Pre-authorization expiry error: {}", err);
+            }
+        })
+    })
+    .map_err(|err| {
+        log::error!("Failed to create pre-authorization expiry job: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create cron job" }))
+    })?;
+
+    sched.add(preauthorization_expiry).await.map_err(|err| {
+        log::error!("Failed to schedule pre-authorization expiry: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to schedule cron job" }))
+    })?;
+
+    sched.start().await.map_err(|err| {
+        log::error!("Failed to start scheduler: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to start scheduler" }))
+    })?;
 
     Ok(())
 }
@@ -0,0 +1,214 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use actix_web::web;
+use chrono::Utc;
+use sea_orm::DatabaseConnection;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        main::{
+            self,
+            migrations::sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set},
+        },
+        tenant::{
+            self,
+            entities::sea_orm_active_enums::{RegistryEnrolmentStatus, TracingTaskStatus},
+        },
+    },
+    handlers::services::{
+        chronic_care::{days_overdue, fetch_active_registries},
+        patients::{allows_sms, sms_phone_number},
+    },
+    utils::{
+        api_response::ApiResponse,
+        message_queue::{MessageQueue, MessageType},
+    },
+};
+
+/// Opens a tracing task for every registry patient who has missed their
+/// follow-up by more than the registry's defaulter threshold, and texts them
+/// once per missed appointment. The message never names the programme, so it
+/// is safe to land on a shared phone.
+pub async fn process_defaulter_tracing(
+    db: &DatabaseConnection,
+    tenant_dbs: &Arc<RwLock<HashMap<Uuid, DatabaseConnection>>>,
+    message_queue: &web::Data<MessageQueue>,
+) -> Result<(), ApiResponse> {
+    let today = Utc::now().date_naive();
+    let registries = fetch_active_registries(db)
+        .await?
+        .into_iter()
+        .map(|registry| (registry.pid, registry))
+        .collect::<HashMap<_, _>>();
+
+    if registries.is_empty() {
+        return Ok(());
+    }
+
+    let tenant_dbs: Vec<(Uuid, DatabaseConnection)> = tenant_dbs
+        .read()
+        .unwrap()
+        .iter()
+        .map(|(tenant_id, db)| (*tenant_id, db.clone()))
+        .collect();
+
+    for (sso_tenant_id, tenant_db) in tenant_dbs {
+        if let Err(err) = trace_tenant_defaulters(
+            db,
+            &tenant_db,
+            sso_tenant_id,
+            &registries,
+            today,
+            message_queue,
+        )
+        .await
+        {
+            log::error!(
+                "Defaulter tracing failed for tenant {}: {}",
+                sso_tenant_id,
+                err
+            );
+        }
+    }
+
+    Ok(())
+}
+
+async fn trace_tenant_defaulters(
+    db: &DatabaseConnection,
+    tenant_db: &DatabaseConnection,
+    sso_tenant_id: Uuid,
+    registries: &HashMap<Uuid, main::entities::chronic_registries::Model>,
+    today: chrono::NaiveDate,
+    message_queue: &web::Data<MessageQueue>,
+) -> Result<(), ApiResponse> {
+    let enrolments = tenant::entities::registry_enrolments::Entity::find()
+        .filter(
+            tenant::entities::registry_enrolments::Column::Status
+                .eq(RegistryEnrolmentStatus::Active),
+        )
+        .filter(tenant::entities::registry_enrolments::Column::DeletedAt.is_null())
+        .filter(tenant::entities::registry_enrolments::Column::NextFollowUpOn.lt(today))
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch overdue enrolments: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch enrolments" }))
+        })?;
+
+    let defaulters = enrolments
+        .into_iter()
+        .filter(|enrolment| {
+            registries
+                .get(&enrolment.registry_pid)
+                .is_some_and(|registry| {
+                    days_overdue(enrolment, today)
+                        .is_some_and(|days| days > registry.defaulter_after_days as i64)
+                })
+        })
+        .collect::<Vec<_>>();
+
+    if defaulters.is_empty() {
+        return Ok(());
+    }
+
+    let facility = main::entities::tenants::Entity::find()
+        .filter(main::entities::tenants::Column::SsoTenantId.eq(sso_tenant_id))
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch tenant {}: {}", sso_tenant_id, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch tenant" }))
+        })?
+        .map(|tenant| tenant.name)
+        .unwrap_or_else(|| "the clinic".to_string());
+
+    for enrolment in defaulters {
+        let Some(missed_on) = enrolment.next_follow_up_on else {
+            continue;
+        };
+
+        let existing = tenant::entities::defaulter_tracing_tasks::Entity::find()
+            .filter(tenant::entities::defaulter_tracing_tasks::Column::EnrolmentId.eq(enrolment.id))
+            .filter(
+                tenant::entities::defaulter_tracing_tasks::Column::MissedFollowUpOn.eq(missed_on),
+            )
+            .one(tenant_db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to check tracing tasks: {}", err);
+                ApiResponse::new(500, json!({ "message": "Failed to check tracing tasks" }))
+            })?;
+
+        if existing.is_some() {
+            continue;
+        }
+
+        let task = tenant::entities::defaulter_tracing_tasks::ActiveModel {
+            enrolment_id: Set(enrolment.id),
+            missed_follow_up_on: Set(missed_on),
+            status: Set(TracingTaskStatus::Pending),
+            ..Default::default()
+        }
+        .insert(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to create tracing task: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to create tracing task" }))
+        })?;
+
+        let patient = main::entities::patients::Entity::find_by_pid(enrolment.patient_pid)
+            .filter(main::entities::patients::Column::DeletedAt.is_null())
+            .one(db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to fetch patient {}: {}", enrolment.patient_pid, err);
+                ApiResponse::new(500, json!({ "message": "Failed to fetch patient" }))
+            })?;
+
+        let Some(patient) = patient.filter(allows_sms) else {
+            continue;
+        };
+        let Some(phone_number) = sms_phone_number(&patient) else {
+            continue;
+        };
+
+        let message = format!(
+            "Hi {}, we missed you at your clinic appointment at {} on {}. Please visit us or call to book a new date.",
+            patient.first_name.as_deref().unwrap_or("there"),
+            facility,
+            missed_on.format("%d %b %Y")
+        );
+
+        if let Err(err) = message_queue
+            .send_message(MessageType::SMS {
+                phone_number,
+                message,
+            })
+            .await
+        {
+            log::error!(
+                "Failed to queue defaulter tracing SMS for patient {}: {}",
+                patient.pid,
+                err
+            );
+            continue;
+        }
+
+        let mut active_model: tenant::entities::defaulter_tracing_tasks::ActiveModel = task.into();
+        active_model.sms_sent_at = Set(Some(Utc::now().naive_utc()));
+        active_model.attempts = Set(1);
+        active_model.updated_at = Set(Utc::now().naive_utc());
+        active_model.update(tenant_db).await.map_err(|err| {
+            log::error!("Failed to update tracing task: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to update tracing task" }))
+        })?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,188 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use actix_web::web;
+use chrono::{Duration, Utc};
+use sea_orm::DatabaseConnection;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    db::main::{
+        self,
+        migrations::sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set},
+    },
+    handlers::services::{
+        immunizations::{
+            DoseStatus, dose_status, fetch_patient_immunization_records, record_matches_schedule,
+        },
+        patients::{allows_sms, sms_phone_number},
+    },
+    utils::{
+        api_response::ApiResponse,
+        message_queue::{MessageQueue, MessageType},
+    },
+};
+
+/// How far ahead of the due date a reminder goes out.
+const REMINDER_LEAD_DAYS: i64 = 7;
+
+/// Texts patients about doses falling due within the next week. Each
+/// scheduled dose is only ever reminded once.
+pub async fn process_immunization_reminders(
+    db: &DatabaseConnection,
+    tenant_dbs: &Arc<RwLock<HashMap<Uuid, DatabaseConnection>>>,
+    message_queue: &web::Data<MessageQueue>,
+) -> Result<(), ApiResponse> {
+    let today = Utc::now().date_naive();
+    let schedules = main::entities::vaccine_schedules::Entity::find()
+        .filter(main::entities::vaccine_schedules::Column::IsActive.eq(true))
+        .filter(main::entities::vaccine_schedules::Column::DeletedAt.is_null())
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch vaccine schedules: {}", err);
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to fetch vaccine schedules" }),
+            )
+        })?;
+
+    let oldest_due = schedules
+        .iter()
+        .map(|schedule| schedule.recommended_age_days)
+        .max()
+        .unwrap_or(0);
+    let earliest_dob = today - Duration::days(oldest_due as i64);
+
+    let patients = main::entities::patients::Entity::find()
+        .filter(main::entities::patients::Column::DeletedAt.is_null())
+        .filter(main::entities::patients::Column::PhoneNumber.is_not_null())
+        .filter(main::entities::patients::Column::Dob.gte(earliest_dob))
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to fetch patients for immunization reminders: {}",
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to fetch patients" }))
+        })?;
+
+    let reminded: Vec<(i32, i32)> = main::entities::immunization_reminders::Entity::find()
+        .filter(
+            main::entities::immunization_reminders::Column::DueDate
+                .gte(today - Duration::days(365)),
+        )
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch immunization reminders: {}", err);
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to fetch immunization reminders" }),
+            )
+        })?
+        .into_iter()
+        .map(|reminder| (reminder.patient_id, reminder.vaccine_schedule_id))
+        .collect();
+
+    for patient in patients {
+        if !allows_sms(&patient) {
+            continue;
+        }
+
+        let Some(phone_number) = sms_phone_number(&patient) else {
+            continue;
+        };
+
+        let upcoming = schedules
+            .iter()
+            .filter(|schedule| schedule.gender.is_none() || schedule.gender == patient.gender)
+            .filter(|schedule| !reminded.contains(&(patient.id, schedule.id)))
+            .filter_map(|schedule| match dose_status(patient.dob, schedule, today) {
+                (Some(due_date), DoseStatus::Upcoming | DoseStatus::Due)
+                    if due_date - today <= Duration::days(REMINDER_LEAD_DAYS) =>
+                {
+                    Some((schedule, due_date))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        if upcoming.is_empty() {
+            continue;
+        }
+
+        let records = fetch_patient_immunization_records(tenant_dbs, patient.pid).await?;
+        let pending = upcoming
+            .into_iter()
+            .filter(|(schedule, _)| {
+                !records
+                    .iter()
+                    .any(|record| record_matches_schedule(record, schedule))
+            })
+            .collect::<Vec<_>>();
+
+        if pending.is_empty() {
+            continue;
+        }
+
+        let doses = pending
+            .iter()
+            .map(|(schedule, due_date)| {
+                format!(
+                    "{} dose {} on {}",
+                    schedule.vaccine_name,
+                    schedule.dose_number,
+                    due_date.format("%d %b %Y")
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let message = format!(
+            "Hi {}, immunization reminder: {}. Please visit your nearest health facility.",
+            patient.first_name.as_deref().unwrap_or("there"),
+            doses
+        );
+
+        if let Err(err) = message_queue
+            .send_message(MessageType::SMS {
+                phone_number,
+                message,
+            })
+            .await
+        {
+            log::error!(
+                "Failed to queue immunization reminder for patient {}: {}",
+                patient.pid,
+                err
+            );
+            continue;
+        }
+
+        for (schedule, due_date) in pending {
+            main::entities::immunization_reminders::ActiveModel {
+                patient_id: Set(patient.id),
+                vaccine_schedule_id: Set(schedule.id),
+                due_date: Set(due_date),
+                channel: Set("sms".to_string()),
+                ..Default::default()
+            }
+            .insert(db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to record immunization reminder: {}", err);
+                ApiResponse::new(
+                    500,
+                    json!({ "message": "Failed to record immunization reminder" }),
+                )
+            })?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,149 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use chrono::{Duration, NaiveDate, Utc};
+use sea_orm::DatabaseConnection;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        main::{
+            self,
+            migrations::sea_orm::{ColumnTrait, EntityTrait, QueryFilter},
+        },
+        tenant::{
+            self,
+            entities::sea_orm_active_enums::{AdmissionStatus, BedStatus},
+        },
+    },
+    handlers::services::usage_metrics::record_daily_metric,
+    utils::api_response::ApiResponse,
+};
+
+/// Writes yesterday's inpatient figures for every facility into
+/// `usage_metrics`: discharges, total bed-days with the average length of
+/// stay, and a bed occupancy snapshot. Days that already have a row are
+/// skipped, so a rerun is harmless.
+pub async fn process_inpatient_metrics(
+    db: &DatabaseConnection,
+    tenant_dbs: &Arc<RwLock<HashMap<Uuid, DatabaseConnection>>>,
+) -> Result<(), ApiResponse> {
+    let day = Utc::now().date_naive() - Duration::days(1);
+
+    let tenant_dbs: Vec<(Uuid, DatabaseConnection)> = tenant_dbs
+        .read()
+        .unwrap()
+        .iter()
+        .map(|(tenant_id, db)| (*tenant_id, db.clone()))
+        .collect();
+
+    for (sso_tenant_id, tenant_db) in tenant_dbs {
+        if let Err(err) = record_tenant_metrics(db, &tenant_db, sso_tenant_id, day).await {
+            log::error!(
+                "Inpatient metrics failed for tenant {}: {}",
+                sso_tenant_id,
+                err
+            );
+        }
+    }
+
+    Ok(())
+}
+
+async fn record_tenant_metrics(
+    db: &DatabaseConnection,
+    tenant_db: &DatabaseConnection,
+    sso_tenant_id: Uuid,
+    day: NaiveDate,
+) -> Result<(), ApiResponse> {
+    let Some(facility) = main::entities::tenants::Entity::find()
+        .filter(main::entities::tenants::Column::SsoTenantId.eq(sso_tenant_id))
+        .filter(main::entities::tenants::Column::DeletedAt.is_null())
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch tenant {}: {}", sso_tenant_id, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch tenant" }))
+        })?
+    else {
+        return Ok(());
+    };
+
+    let period_start = day.and_time(Default::default());
+    let period_end = period_start + Duration::days(1);
+
+    let discharges = tenant::entities::admissions::Entity::find()
+        .filter(tenant::entities::admissions::Column::Status.eq(AdmissionStatus::Discharged))
+        .filter(tenant::entities::admissions::Column::DischargedAt.gte(period_start))
+        .filter(tenant::entities::admissions::Column::DischargedAt.lt(period_end))
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch discharges: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch discharges" }))
+        })?;
+
+    let beds = tenant::entities::beds::Entity::find()
+        .filter(tenant::entities::beds::Column::IsActive.eq(true))
+        .filter(tenant::entities::beds::Column::DeletedAt.is_null())
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch beds: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch beds" }))
+        })?;
+
+    if discharges.is_empty() && beds.is_empty() {
+        return Ok(());
+    }
+
+    let mut stays = discharges
+        .iter()
+        .filter_map(|admission| admission.length_of_stay_days)
+        .collect::<Vec<_>>();
+    stays.sort_unstable();
+
+    let bed_days: i64 = stays.iter().map(|days| *days as i64).sum();
+    let average = if stays.is_empty() {
+        0.0
+    } else {
+        (bed_days as f64 / stays.len() as f64 * 10.0).round() / 10.0
+    };
+    let median = stays.get(stays.len() / 2).copied();
+
+    let occupied = beds
+        .iter()
+        .filter(|bed| bed.status == BedStatus::Occupied)
+        .count();
+
+    let metrics = [
+        ("inpatient_discharges", discharges.len() as i64, None),
+        (
+            "inpatient_length_of_stay_days",
+            bed_days,
+            Some(json!({
+                "discharges": stays.len(),
+                "average_days": average,
+                "median_days": median,
+                "longest_days": stays.last(),
+            })),
+        ),
+        (
+            "inpatient_occupied_beds",
+            occupied as i64,
+            Some(json!({
+                "total_beds": beds.len(),
+                "captured_at": Utc::now().naive_utc(),
+            })),
+        ),
+    ];
+
+    for (metric_type, value, metadata) in metrics {
+        record_daily_metric(db, &facility, metric_type, day, value, metadata).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+use crate::{
+    handlers::services::{
+        insurance_claims::post_pending_deductibles, patient_bills::post_pending_out_of_pocket,
+        patient_insurance::reset_insurance_accumulators,
+    },
+    utils::api_response::ApiResponse,
+};
+
+/// Starts each policy's new plan year with empty deductible and
+/// out-of-pocket accumulators.
+pub async fn process_accumulator_reset(db: &DatabaseConnection) -> Result<(), ApiResponse> {
+    let reset = reset_insurance_accumulators(db).await?;
+
+    if reset > 0 {
+        log::info!("{} insurance accumulators reset", reset);
+    }
+
+    Ok(())
+}
+
+/// Retries the accumulator postings of bills and claims whose card could
+/// not be updated when they were paid, approved or rejected.
+pub async fn process_accumulator_postings(
+    db: &DatabaseConnection,
+    tenant_dbs: &Arc<RwLock<HashMap<Uuid, DatabaseConnection>>>,
+) -> Result<(), ApiResponse> {
+    let tenant_dbs: Vec<(Uuid, DatabaseConnection)> = tenant_dbs
+        .read()
+        .unwrap()
+        .iter()
+        .map(|(tenant_id, db)| (*tenant_id, db.clone()))
+        .collect();
+
+    for (sso_tenant_id, tenant_db) in tenant_dbs {
+        let posted = match (
+            post_pending_out_of_pocket(&tenant_db, db).await,
+            post_pending_deductibles(&tenant_db, db).await,
+        ) {
+            (Ok(bills), Ok(claims)) => bills + claims,
+            (Err(err), _) | (_, Err(err)) => {
+                log::error!(
+                    "Accumulator postings failed for tenant {}: {}",
+                    sso_tenant_id,
+                    err
+                );
+                continue;
+            }
+        };
+
+        if posted > 0 {
+            log::info!(
+                "{} pending accumulator postings made for tenant {}",
+                posted,
+                sso_tenant_id
+            );
+        }
+    }
+
+    Ok(())
+}
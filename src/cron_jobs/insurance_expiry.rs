@@ -0,0 +1,18 @@
+use sea_orm::DatabaseConnection;
+
+use crate::{
+    handlers::services::patient_insurance::expire_lapsed_insurance,
+    utils::api_response::ApiResponse,
+};
+
+/// Moves insurance cards to expired once their cover has ended, so they are
+/// no longer billed to.
+pub async fn process_insurance_expiry(db: &DatabaseConnection) -> Result<(), ApiResponse> {
+    let expired = expire_lapsed_insurance(db).await?;
+
+    if expired > 0 {
+        log::info!("{} insurance cards expired", expired);
+    }
+
+    Ok(())
+}
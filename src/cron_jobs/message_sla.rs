@@ -0,0 +1,72 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use chrono::Utc;
+use sea_orm::DatabaseConnection;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    db::tenant::{
+        self,
+        entities::sea_orm_active_enums::MessageThreadStatus,
+        migrations::sea_orm::{ColumnTrait, EntityTrait, QueryFilter, Set},
+    },
+    utils::api_response::ApiResponse,
+};
+
+/// Stamps `sla_breached_at` on open threads whose reply deadline has passed.
+/// The stamp stays after the thread is answered so breaches can be reported
+/// on later; a new patient message clears it when it restarts the clock.
+pub async fn process_message_sla(
+    tenant_dbs: &Arc<RwLock<HashMap<Uuid, DatabaseConnection>>>,
+) -> Result<(), ApiResponse> {
+    let tenant_dbs: Vec<(Uuid, DatabaseConnection)> = tenant_dbs
+        .read()
+        .unwrap()
+        .iter()
+        .map(|(tenant_id, db)| (*tenant_id, db.clone()))
+        .collect();
+
+    for (sso_tenant_id, tenant_db) in tenant_dbs {
+        if let Err(err) = flag_breached_threads(&tenant_db).await {
+            log::error!(
+                "Message SLA check failed for tenant {}: {}",
+                sso_tenant_id,
+                err
+            );
+        }
+    }
+
+    Ok(())
+}
+
+async fn flag_breached_threads(tenant_db: &DatabaseConnection) -> Result<(), ApiResponse> {
+    let now = Utc::now().naive_utc();
+
+    let result = tenant::entities::message_threads::Entity::update_many()
+        .set(tenant::entities::message_threads::ActiveModel {
+            sla_breached_at: Set(Some(now)),
+            ..Default::default()
+        })
+        .filter(tenant::entities::message_threads::Column::Status.eq(MessageThreadStatus::Open))
+        .filter(tenant::entities::message_threads::Column::ReplyDueAt.lt(now))
+        .filter(tenant::entities::message_threads::Column::SlaBreachedAt.is_null())
+        .exec(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to flag breached message threads: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to check message SLA" }))
+        })?;
+
+    if result.rows_affected > 0 {
+        log::info!(
+            "{} message threads breached the reply SLA",
+            result.rows_affected
+        );
+    }
+
+    Ok(())
+}
@@ -1,2 +1,11 @@
 pub mod all;
+pub mod defaulter_tracing;
+pub mod immunization_reminders;
+pub mod inpatient_metrics;
+pub mod insurance_accumulators;
+pub mod insurance_expiry;
+pub mod message_sla;
+pub mod preauthorization_expiry;
+pub mod queue_metrics;
+pub mod stock_alerts;
 pub mod trial_expiry;
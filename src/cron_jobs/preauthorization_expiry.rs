@@ -0,0 +1,47 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+use crate::{
+    handlers::services::preauthorizations::expire_preauthorizations,
+    utils::api_response::ApiResponse,
+};
+
+/// Expires approved pre-authorizations that ran past their validity
+/// without being used on a claim.
+pub async fn process_preauthorization_expiry(
+    tenant_dbs: &Arc<RwLock<HashMap<Uuid, DatabaseConnection>>>,
+) -> Result<(), ApiResponse> {
+    let tenant_dbs: Vec<(Uuid, DatabaseConnection)> = tenant_dbs
+        .read()
+        .unwrap()
+        .iter()
+        .map(|(tenant_id, db)| (*tenant_id, db.clone()))
+        .collect();
+
+    for (sso_tenant_id, tenant_db) in tenant_dbs {
+        match expire_preauthorizations(&tenant_db).await {
+            Ok(expired) if expired > 0 => {
+                log::info!(
+                    "{} pre-authorizations expired for tenant {}",
+                    expired,
+                    sso_tenant_id
+                );
+            }
+            Ok(_) => {}
+            Err(err) => {
+                log::error!(
+                    "Pre-authorization expiry failed for tenant {}: {}",
+                    sso_tenant_id,
+                    err
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
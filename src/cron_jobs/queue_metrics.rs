@@ -0,0 +1,138 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use chrono::{Duration, NaiveDate, Utc};
+use sea_orm::DatabaseConnection;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        main::{
+            self,
+            migrations::sea_orm::{ColumnTrait, EntityTrait, QueryFilter},
+        },
+        tenant,
+    },
+    handlers::services::{
+        queue::{QUEUE_STAGES, stage_slug, wait_seconds},
+        usage_metrics::record_daily_metric,
+    },
+    utils::api_response::ApiResponse,
+};
+
+/// Writes yesterday's queue figures for every facility into `usage_metrics`:
+/// tickets issued and, per stage, the average wait before being called.
+/// Days that already have a row are skipped, so a rerun is harmless.
+pub async fn process_queue_metrics(
+    db: &DatabaseConnection,
+    tenant_dbs: &Arc<RwLock<HashMap<Uuid, DatabaseConnection>>>,
+) -> Result<(), ApiResponse> {
+    let day = Utc::now().date_naive() - Duration::days(1);
+
+    let tenant_dbs: Vec<(Uuid, DatabaseConnection)> = tenant_dbs
+        .read()
+        .unwrap()
+        .iter()
+        .map(|(tenant_id, db)| (*tenant_id, db.clone()))
+        .collect();
+
+    for (sso_tenant_id, tenant_db) in tenant_dbs {
+        if let Err(err) = record_tenant_metrics(db, &tenant_db, sso_tenant_id, day).await {
+            log::error!("Queue metrics failed for tenant {}: {}", sso_tenant_id, err);
+        }
+    }
+
+    Ok(())
+}
+
+async fn record_tenant_metrics(
+    db: &DatabaseConnection,
+    tenant_db: &DatabaseConnection,
+    sso_tenant_id: Uuid,
+    day: NaiveDate,
+) -> Result<(), ApiResponse> {
+    let Some(facility) = main::entities::tenants::Entity::find()
+        .filter(main::entities::tenants::Column::SsoTenantId.eq(sso_tenant_id))
+        .filter(main::entities::tenants::Column::DeletedAt.is_null())
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch tenant {}: {}", sso_tenant_id, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch tenant" }))
+        })?
+    else {
+        return Ok(());
+    };
+
+    let tickets = tenant::entities::queue_tickets::Entity::find()
+        .filter(tenant::entities::queue_tickets::Column::QueueDate.eq(day))
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch queue tickets: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch queue tickets" }))
+        })?;
+
+    if tickets.is_empty() {
+        return Ok(());
+    }
+
+    let period_start = day.and_time(Default::default());
+    let period_end = period_start + Duration::days(1);
+
+    let visits = tenant::entities::queue_stage_visits::Entity::find()
+        .filter(tenant::entities::queue_stage_visits::Column::QueuedAt.gte(period_start))
+        .filter(tenant::entities::queue_stage_visits::Column::QueuedAt.lt(period_end))
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch stage visits: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch stage visits" }))
+        })?;
+
+    record_daily_metric(
+        db,
+        &facility,
+        "queue_tickets",
+        day,
+        tickets.len() as i64,
+        None,
+    )
+    .await?;
+
+    for stage in QUEUE_STAGES {
+        // Patients never called by the end of the day count up to midnight.
+        let mut waits = visits
+            .iter()
+            .filter(|visit| visit.stage == stage)
+            .map(|visit| wait_seconds(visit, period_end))
+            .collect::<Vec<_>>();
+
+        if waits.is_empty() {
+            continue;
+        }
+
+        waits.sort_unstable();
+        let average = waits.iter().sum::<i64>() / waits.len() as i64;
+
+        record_daily_metric(
+            db,
+            &facility,
+            &format!("queue_wait_{}", stage_slug(&stage)),
+            day,
+            average,
+            Some(json!({
+                "unit": "seconds",
+                "patients": waits.len(),
+                "median_seconds": waits[waits.len() / 2],
+                "longest_seconds": waits.last(),
+            })),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,462 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, RwLock},
+};
+
+use actix_web::web;
+use chrono::{NaiveDate, Utc};
+use sea_orm::DatabaseConnection;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        main,
+        tenant::{
+            self,
+            entities::sea_orm_active_enums::StockAlertType,
+            migrations::sea_orm::{
+                ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QuerySelect, Set,
+            },
+        },
+    },
+    handlers::services::{
+        pharmacy::batch_balances,
+        stock_alerts::{stock_alert_digest_html, stock_alert_summary},
+    },
+    utils::{
+        api_response::ApiResponse,
+        constants::STOCK_EXPIRY_ALERT_DAYS,
+        message_queue::{MessageQueue, MessageType},
+    },
+};
+
+/// A stock problem found by the scan. It matches an open alert when the
+/// store, product, batch, type and expiry window are the same.
+struct StockCondition {
+    product_id: i32,
+    batch_id: Option<i32>,
+    alert_type: StockAlertType,
+    window_days: Option<i32>,
+    quantity: i64,
+    reorder_level: i32,
+    expiry_date: Option<NaiveDate>,
+}
+
+impl StockCondition {
+    fn matches(&self, alert: &tenant::entities::stock_alerts::Model) -> bool {
+        alert.product_id == self.product_id
+            && alert.batch_id == self.batch_id
+            && alert.alert_type == self.alert_type
+            && alert.window_days == self.window_days
+    }
+}
+
+/// Scans every active store for stock-outs, stock at or below the reorder
+/// level, and batches expired or expiring within one of the configured
+/// windows. Each problem keeps a single open alert until it clears, so a
+/// product that stays low is reported once rather than every day; a batch
+/// moving into a shorter expiry window opens a new alert. New alerts are
+/// sent to each store's alert contacts as one email and one SMS digest.
+pub async fn process_stock_alerts(
+    db: &DatabaseConnection,
+    tenant_dbs: &Arc<RwLock<HashMap<Uuid, DatabaseConnection>>>,
+    message_queue: &web::Data<MessageQueue>,
+) -> Result<(), ApiResponse> {
+    let tenant_dbs: Vec<(Uuid, DatabaseConnection)> = tenant_dbs
+        .read()
+        .unwrap()
+        .iter()
+        .map(|(tenant_id, db)| (*tenant_id, db.clone()))
+        .collect();
+
+    for (sso_tenant_id, tenant_db) in tenant_dbs {
+        if let Err(err) = scan_tenant_stock(db, &tenant_db, sso_tenant_id, message_queue).await {
+            log::error!(
+                "Stock alert scan failed for tenant {}: {}",
+                sso_tenant_id,
+                err
+            );
+        }
+    }
+
+    Ok(())
+}
+
+async fn scan_tenant_stock(
+    db: &DatabaseConnection,
+    tenant_db: &DatabaseConnection,
+    sso_tenant_id: Uuid,
+    message_queue: &web::Data<MessageQueue>,
+) -> Result<(), ApiResponse> {
+    let stores = tenant::entities::pharmacy_stores::Entity::find()
+        .filter(tenant::entities::pharmacy_stores::Column::DeletedAt.is_null())
+        .filter(tenant::entities::pharmacy_stores::Column::IsActive.eq(true))
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch stores: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch stores" }))
+        })?;
+
+    if stores.is_empty() {
+        return Ok(());
+    }
+
+    let products: HashMap<i32, tenant::entities::pharmacy_products::Model> =
+        tenant::entities::pharmacy_products::Entity::find()
+            .filter(tenant::entities::pharmacy_products::Column::DeletedAt.is_null())
+            .filter(tenant::entities::pharmacy_products::Column::IsActive.eq(true))
+            .all(tenant_db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to fetch products: {}", err);
+                ApiResponse::new(500, json!({ "message": "Failed to fetch products" }))
+            })?
+            .into_iter()
+            .map(|product| (product.id, product))
+            .collect();
+
+    let today = Utc::now().date_naive();
+    for store in &stores {
+        let conditions = stock_conditions(tenant_db, store.id, &products, today).await?;
+        sync_alerts(tenant_db, store.id, conditions).await?;
+    }
+
+    send_digests(
+        db,
+        tenant_db,
+        sso_tenant_id,
+        &stores,
+        &products,
+        message_queue,
+    )
+    .await
+}
+
+async fn stock_conditions(
+    tenant_db: &DatabaseConnection,
+    store_id: i32,
+    products: &HashMap<i32, tenant::entities::pharmacy_products::Model>,
+    today: NaiveDate,
+) -> Result<Vec<StockCondition>, ApiResponse> {
+    // Only products the store has ever held can run out there.
+    let stocked: HashSet<i32> = tenant::entities::stock_movements::Entity::find()
+        .select_only()
+        .column(tenant::entities::stock_movements::Column::ProductId)
+        .distinct()
+        .filter(tenant::entities::stock_movements::Column::StoreId.eq(store_id))
+        .into_tuple::<i32>()
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch stocked products: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to scan stock" }))
+        })?
+        .into_iter()
+        .collect();
+
+    let balances = batch_balances(tenant_db, Some(store_id), None).await?;
+    let batches = tenant::entities::stock_batches::Entity::find()
+        .filter(
+            tenant::entities::stock_batches::Column::Id
+                .is_in(balances.iter().map(|(_, batch_id, _)| *batch_id)),
+        )
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch batches: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to scan stock" }))
+        })?
+        .into_iter()
+        .map(|batch| (batch.id, batch))
+        .collect::<HashMap<_, _>>();
+
+    let mut conditions = Vec::new();
+    let mut usable: HashMap<i32, i64> = HashMap::new();
+    for (_, batch_id, on_hand) in balances {
+        let Some(batch) = batches.get(&batch_id) else {
+            continue;
+        };
+        let Some(product) = products.get(&batch.product_id) else {
+            continue;
+        };
+
+        let days_left = (batch.expiry_date - today).num_days();
+        let (alert_type, window_days) = if days_left < 0 {
+            (StockAlertType::Expired, None)
+        } else {
+            *usable.entry(product.id).or_default() += on_hand;
+            match STOCK_EXPIRY_ALERT_DAYS
+                .iter()
+                .find(|window| days_left <= **window)
+            {
+                Some(window) => (StockAlertType::Expiring, Some(*window as i32)),
+                None => continue,
+            }
+        };
+
+        conditions.push(StockCondition {
+            product_id: product.id,
+            batch_id: Some(batch.id),
+            alert_type,
+            window_days,
+            quantity: on_hand,
+            reorder_level: product.reorder_level,
+            expiry_date: Some(batch.expiry_date),
+        });
+    }
+
+    for product_id in stocked {
+        let Some(product) = products.get(&product_id) else {
+            continue;
+        };
+
+        let quantity = usable.get(&product_id).copied().unwrap_or(0);
+        let alert_type = if quantity <= 0 {
+            StockAlertType::StockOut
+        } else if quantity <= product.reorder_level as i64 {
+            StockAlertType::LowStock
+        } else {
+            continue;
+        };
+
+        conditions.push(StockCondition {
+            product_id,
+            batch_id: None,
+            alert_type,
+            window_days: None,
+            quantity,
+            reorder_level: product.reorder_level,
+            expiry_date: None,
+        });
+    }
+
+    Ok(conditions)
+}
+
+/// Opens alerts for new conditions, refreshes the quantity on ones that are
+/// still open and resolves those that no longer apply.
+async fn sync_alerts(
+    tenant_db: &DatabaseConnection,
+    store_id: i32,
+    conditions: Vec<StockCondition>,
+) -> Result<(), ApiResponse> {
+    let open = tenant::entities::stock_alerts::Entity::find()
+        .filter(tenant::entities::stock_alerts::Column::StoreId.eq(store_id))
+        .filter(tenant::entities::stock_alerts::Column::ResolvedAt.is_null())
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch open stock alerts: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch stock alerts" }))
+        })?;
+
+    let now = Utc::now().naive_utc();
+    let resolved = open
+        .iter()
+        .filter(|alert| !conditions.iter().any(|condition| condition.matches(alert)))
+        .map(|alert| alert.id)
+        .collect::<Vec<_>>();
+
+    if !resolved.is_empty() {
+        tenant::entities::stock_alerts::Entity::update_many()
+            .set(tenant::entities::stock_alerts::ActiveModel {
+                resolved_at: Set(Some(now)),
+                updated_at: Set(now),
+                ..Default::default()
+            })
+            .filter(tenant::entities::stock_alerts::Column::Id.is_in(resolved))
+            .exec(tenant_db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to resolve stock alerts: {}", err);
+                ApiResponse::new(500, json!({ "message": "Failed to resolve stock alerts" }))
+            })?;
+    }
+
+    for condition in conditions {
+        match open.iter().find(|alert| condition.matches(alert)) {
+            Some(alert) if alert.quantity == condition.quantity => {}
+            Some(alert) => {
+                tenant::entities::stock_alerts::ActiveModel {
+                    id: Set(alert.id),
+                    quantity: Set(condition.quantity),
+                    updated_at: Set(now),
+                    ..Default::default()
+                }
+                .update(tenant_db)
+                .await
+                .map_err(|err| {
+                    log::error!("Failed to update stock alert {}: {}", alert.pid, err);
+                    ApiResponse::new(500, json!({ "message": "Failed to update stock alert" }))
+                })?;
+            }
+            None => {
+                tenant::entities::stock_alerts::ActiveModel {
+                    store_id: Set(store_id),
+                    product_id: Set(condition.product_id),
+                    batch_id: Set(condition.batch_id),
+                    alert_type: Set(condition.alert_type),
+                    window_days: Set(condition.window_days),
+                    quantity: Set(condition.quantity),
+                    reorder_level: Set(condition.reorder_level),
+                    expiry_date: Set(condition.expiry_date),
+                    ..Default::default()
+                }
+                .insert(tenant_db)
+                .await
+                .map_err(|err| {
+                    log::error!("Failed to create stock alert: {}", err);
+                    ApiResponse::new(500, json!({ "message": "Failed to create stock alert" }))
+                })?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Sends each store's alerts that have not been notified yet. Alerts stay
+/// pending for stores without contacts so they go out once one is set.
+async fn send_digests(
+    db: &DatabaseConnection,
+    tenant_db: &DatabaseConnection,
+    sso_tenant_id: Uuid,
+    stores: &[tenant::entities::pharmacy_stores::Model],
+    products: &HashMap<i32, tenant::entities::pharmacy_products::Model>,
+    message_queue: &web::Data<MessageQueue>,
+) -> Result<(), ApiResponse> {
+    let pending = tenant::entities::stock_alerts::Entity::find()
+        .filter(tenant::entities::stock_alerts::Column::ResolvedAt.is_null())
+        .filter(tenant::entities::stock_alerts::Column::NotifiedAt.is_null())
+        .filter(tenant::entities::stock_alerts::Column::AcknowledgedAt.is_null())
+        .find_also_related(tenant::entities::stock_batches::Entity)
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch pending stock alerts: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch stock alerts" }))
+        })?;
+
+    if pending.is_empty() {
+        return Ok(());
+    }
+
+    let facility = main::entities::tenants::Entity::find()
+        .filter(main::entities::tenants::Column::SsoTenantId.eq(sso_tenant_id))
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch tenant {}: {}", sso_tenant_id, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch tenant" }))
+        })?
+        .map(|tenant| tenant.name)
+        .unwrap_or_else(|| "your facility".to_string());
+
+    for store in stores {
+        if store.alert_email.is_none() && store.alert_phone.is_none() {
+            continue;
+        }
+
+        let alerts = pending
+            .iter()
+            .filter(|(alert, _)| alert.store_id == store.id)
+            .collect::<Vec<_>>();
+        if alerts.is_empty() {
+            continue;
+        }
+
+        let mut sections: Vec<(&str, Vec<String>)> = vec![
+            ("Out of stock", Vec::new()),
+            ("Low stock", Vec::new()),
+            ("Expired", Vec::new()),
+            ("Expiring soon", Vec::new()),
+        ];
+        for (alert, batch) in &alerts {
+            let Some(product) = products.get(&alert.product_id) else {
+                continue;
+            };
+            let section = match alert.alert_type {
+                StockAlertType::StockOut => 0,
+                StockAlertType::LowStock => 1,
+                StockAlertType::Expired => 2,
+                StockAlertType::Expiring => 3,
+            };
+            sections[section]
+                .1
+                .push(stock_alert_summary(alert, product, batch.as_ref()));
+        }
+
+        let mut delivered = false;
+        if let Some(to) = store.alert_email.clone() {
+            match message_queue
+                .send_message(MessageType::Email {
+                    to,
+                    subject: format!("Stock alerts: {} ({})", store.name, facility),
+                    html: stock_alert_digest_html(&facility, store, &sections),
+                })
+                .await
+            {
+                Ok(()) => delivered = true,
+                Err(err) => log::error!(
+                    "Failed to queue stock alert email for store {}: {}",
+                    store.pid,
+                    err
+                ),
+            }
+        }
+
+        if let Some(phone_number) = store.alert_phone.clone() {
+            let counts = sections
+                .iter()
+                .filter(|(_, lines)| !lines.is_empty())
+                .map(|(title, lines)| format!("{} {}", lines.len(), title.to_lowercase()))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let message = format!(
+                "{} {} stock alerts: {}. Check the pharmacy dashboard for details.",
+                facility, store.name, counts
+            );
+
+            match message_queue
+                .send_message(MessageType::SMS {
+                    phone_number,
+                    message,
+                })
+                .await
+            {
+                Ok(()) => delivered = true,
+                Err(err) => log::error!(
+                    "Failed to queue stock alert SMS for store {}: {}",
+                    store.pid,
+                    err
+                ),
+            }
+        }
+
+        if !delivered {
+            continue;
+        }
+
+        let now = Utc::now().naive_utc();
+        tenant::entities::stock_alerts::Entity::update_many()
+            .set(tenant::entities::stock_alerts::ActiveModel {
+                notified_at: Set(Some(now)),
+                updated_at: Set(now),
+                ..Default::default()
+            })
+            .filter(
+                tenant::entities::stock_alerts::Column::Id
+                    .is_in(alerts.iter().map(|(alert, _)| alert.id)),
+            )
+            .exec(tenant_db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to mark stock alerts notified: {}", err);
+                ApiResponse::new(500, json!({ "message": "Failed to update stock alerts" }))
+            })?;
+    }
+
+    Ok(())
+}
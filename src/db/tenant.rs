@@ -1,5 +1,5 @@
 pub mod migrations {
-    pub use migration_main::*;
+    pub use migration_tenant::*;
 }
 
 pub mod entities {
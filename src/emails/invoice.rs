@@ -2,6 +2,7 @@ use actix_web::HttpRequest;
 use chrono::{Datelike, Utc};
 use rust_decimal::Decimal;
 use serde_json::json;
+use uuid::Uuid;
 
 use crate::{
     emails::config::email_configs,
@@ -24,7 +25,7 @@ pub async fn send_invoice_email(
     domain: Option<String>,
     subject: Option<String>,
     html: Option<String>,
-) -> Result<String, ApiResponse> {
+) -> Result<Uuid, ApiResponse> {
     let (
         email_logo,
         email_privacy,
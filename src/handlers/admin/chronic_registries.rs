@@ -0,0 +1,370 @@
+use std::collections::HashMap;
+
+use actix_web::{HttpRequest, web};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    db::main::{
+        self,
+        entities::sea_orm_active_enums::ChronicControlIndicator,
+        migrations::sea_orm::{
+            ActiveModelTrait, ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter,
+            QueryOrder, Set,
+        },
+    },
+    handlers::services::chronic_care::{chronic_registry_json, normalize_diagnosis_code},
+    utils::{
+        api_response::ApiResponse, app_state::AppState, pagination::PaginationParams,
+        permission::has_permission, validator_error::ValidationError,
+    },
+};
+
+pub async fn index(
+    app_state: web::Data<AppState>,
+    query: web::Query<PaginationParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let mut stmt = main::entities::chronic_registries::Entity::find();
+
+    if !has_permission("view_archived_chronic_registries", &req).await? {
+        stmt = stmt.filter(main::entities::chronic_registries::Column::DeletedAt.is_null());
+    }
+
+    if let Some(term) = &query.search {
+        use main::migrations::{Expr, extension::postgres::PgExpr};
+
+        let like = format!("%{}%", term);
+        stmt = stmt.filter(
+            Condition::any()
+                .add(
+                    Expr::col(main::entities::chronic_registries::Column::Code).ilike(like.clone()),
+                )
+                .add(
+                    Expr::col(main::entities::chronic_registries::Column::Name).ilike(like.clone()),
+                ),
+        );
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(10).clamp(1, 100);
+    let paginator = stmt
+        .order_by_asc(main::entities::chronic_registries::Column::Code)
+        .paginate(&app_state.main_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
+
+    let results = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?
+        .iter()
+        .map(chronic_registry_json)
+        .collect::<Vec<_>>();
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "chronic_registries": results,
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "Chronic registries fetched successfully",
+        }),
+    ))
+}
+
+pub async fn show(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+) -> Result<ApiResponse, ApiResponse> {
+    let registry = find_registry(&app_state, path.into_inner(), false).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "chronic_registry": chronic_registry_json(&registry),
+            "message": "Chronic registry fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ChronicRegistryData {
+    pub code: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub diagnosis_codes: Vec<String>,
+    pub control_indicator: ChronicControlIndicator,
+    pub follow_up_interval_days: Option<i32>,
+    pub defaulter_after_days: Option<i32>,
+    pub is_active: Option<bool>,
+}
+
+impl ChronicRegistryData {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if self.code.trim().is_empty() {
+            errors.insert("code".to_string(), "Code is required".to_string());
+        }
+
+        if self.name.trim().is_empty() {
+            errors.insert("name".to_string(), "Name is required".to_string());
+        }
+
+        if self
+            .diagnosis_codes
+            .iter()
+            .all(|code| normalize_diagnosis_code(code).is_empty())
+        {
+            errors.insert(
+                "diagnosis_codes".to_string(),
+                "At least one diagnosis code is required".to_string(),
+            );
+        }
+
+        if self.follow_up_interval_days.is_some_and(|days| days < 1) {
+            errors.insert(
+                "follow_up_interval_days".to_string(),
+                "Follow-up interval must be at least 1 day".to_string(),
+            );
+        }
+
+        if self.defaulter_after_days.is_some_and(|days| days < 0) {
+            errors.insert(
+                "defaulter_after_days".to_string(),
+                "Defaulter threshold must be greater than or equal to 0 days".to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+
+    fn normalized_codes(&self) -> Vec<String> {
+        let mut codes = self
+            .diagnosis_codes
+            .iter()
+            .map(|code| normalize_diagnosis_code(code))
+            .filter(|code| !code.is_empty())
+            .collect::<Vec<_>>();
+        codes.sort();
+        codes.dedup();
+        codes
+    }
+}
+
+pub async fn create(
+    app_state: web::Data<AppState>,
+    data: web::Json<ChronicRegistryData>,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate() {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let code = data.code.trim().to_uppercase();
+    ensure_unique_code(&app_state, &code, None).await?;
+
+    let registry = main::entities::chronic_registries::ActiveModel {
+        code: Set(code),
+        name: Set(data.name.trim().to_string()),
+        description: Set(data.description.clone()),
+        diagnosis_codes: Set(data.normalized_codes()),
+        control_indicator: Set(data.control_indicator.clone()),
+        follow_up_interval_days: Set(data.follow_up_interval_days.unwrap_or(30)),
+        defaulter_after_days: Set(data.defaulter_after_days.unwrap_or(30)),
+        is_active: Set(data.is_active.unwrap_or(true)),
+        ..Default::default()
+    }
+    .insert(&app_state.main_db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to create chronic registry: {}", err);
+        ApiResponse::new(
+            500,
+            json!({ "message": "Failed to create chronic registry" }),
+        )
+    })?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "chronic_registry": chronic_registry_json(&registry),
+            "message": "Chronic registry created successfully",
+        }),
+    ))
+}
+
+pub async fn edit(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<ChronicRegistryData>,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate() {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let registry = find_registry(&app_state, path.into_inner(), false).await?;
+
+    let code = data.code.trim().to_uppercase();
+    ensure_unique_code(&app_state, &code, Some(registry.id)).await?;
+
+    let mut active_model: main::entities::chronic_registries::ActiveModel =
+        registry.to_owned().into();
+    active_model.code = Set(code);
+    active_model.name = Set(data.name.trim().to_string());
+    active_model.description = Set(data.description.clone());
+    active_model.diagnosis_codes = Set(data.normalized_codes());
+    active_model.control_indicator = Set(data.control_indicator.clone());
+    active_model.follow_up_interval_days = Set(data
+        .follow_up_interval_days
+        .unwrap_or(registry.follow_up_interval_days));
+    active_model.defaulter_after_days = Set(data
+        .defaulter_after_days
+        .unwrap_or(registry.defaulter_after_days));
+    active_model.is_active = Set(data.is_active.unwrap_or(registry.is_active));
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let registry = active_model
+        .update(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to update chronic registry: {}", err);
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to update chronic registry" }),
+            )
+        })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "chronic_registry": chronic_registry_json(&registry),
+            "message": "Chronic registry updated successfully",
+        }),
+    ))
+}
+
+pub async fn destroy(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+) -> Result<ApiResponse, ApiResponse> {
+    let registry = find_registry(&app_state, path.into_inner(), false).await?;
+
+    let mut active_model: main::entities::chronic_registries::ActiveModel = registry.into();
+    active_model.deleted_at = Set(Some(Utc::now().naive_utc()));
+    active_model.updated_at = Set(Utc::now().naive_utc());
+    active_model
+        .update(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to delete chronic registry: {}", err);
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to delete chronic registry" }),
+            )
+        })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({ "message": "Chronic registry deleted successfully" }),
+    ))
+}
+
+pub async fn restore(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+) -> Result<ApiResponse, ApiResponse> {
+    let registry = find_registry(&app_state, path.into_inner(), true).await?;
+
+    let mut active_model: main::entities::chronic_registries::ActiveModel = registry.into();
+    active_model.deleted_at = Set(None);
+    active_model.updated_at = Set(Utc::now().naive_utc());
+    active_model
+        .update(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to restore chronic registry: {}", err);
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to restore chronic registry" }),
+            )
+        })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({ "message": "Chronic registry restored successfully" }),
+    ))
+}
+
+async fn find_registry(
+    app_state: &AppState,
+    pid: Uuid,
+    archived: bool,
+) -> Result<main::entities::chronic_registries::Model, ApiResponse> {
+    let mut stmt = main::entities::chronic_registries::Entity::find_by_pid(pid);
+
+    stmt = if archived {
+        stmt.filter(main::entities::chronic_registries::Column::DeletedAt.is_not_null())
+    } else {
+        stmt.filter(main::entities::chronic_registries::Column::DeletedAt.is_null())
+    };
+
+    stmt.one(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch chronic registry {}: {}", pid, err);
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to fetch chronic registry" }),
+            )
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Chronic registry not found" })))
+}
+
+async fn ensure_unique_code(
+    app_state: &AppState,
+    code: &str,
+    exclude_id: Option<i32>,
+) -> Result<(), ApiResponse> {
+    let mut stmt = main::entities::chronic_registries::Entity::find()
+        .filter(main::entities::chronic_registries::Column::Code.eq(code));
+
+    if let Some(id) = exclude_id {
+        stmt = stmt.filter(main::entities::chronic_registries::Column::Id.ne(id));
+    }
+
+    let exists = stmt.count(&app_state.main_db).await.map_err(|err| {
+        log::error!("Failed to check chronic registry uniqueness: {}", err);
+        ApiResponse::new(
+            500,
+            json!({ "message": "Failed to validate chronic registry" }),
+        )
+    })? > 0;
+
+    if exists {
+        let mut errors = HashMap::new();
+        errors.insert(
+            "code".to_string(),
+            format!("A registry with code {} already exists", code),
+        );
+        return Err(ApiResponse::new(400, json!(ValidationError { errors })));
+    }
+
+    Ok(())
+}
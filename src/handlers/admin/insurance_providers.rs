@@ -0,0 +1,382 @@
+use std::collections::HashMap;
+
+use actix_multipart::Multipart;
+use actix_web::{HttpRequest, web};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::main::{
+        self,
+        migrations::sea_orm::{
+            ActiveModelTrait, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+            Set,
+        },
+    },
+    handlers::services::insurance_providers::{
+        SupportedPlan, clean_supported_plans, ensure_unique_provider_name, provider_form,
+        provider_json,
+    },
+    utils::{
+        api_response::ApiResponse, app_state::AppState, crypto::encrypt_json,
+        pagination::PaginationParams, permission::has_permission, validator_error::ValidationError,
+    },
+};
+
+pub async fn index(
+    app_state: web::Data<AppState>,
+    query: web::Query<PaginationParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let mut stmt = main::entities::insurance_providers::Entity::find();
+
+    if !has_permission("view_archived_insurance_providers", &req).await? {
+        stmt = stmt.filter(main::entities::insurance_providers::Column::DeletedAt.is_null());
+    }
+
+    if let Some(term) = &query.search {
+        use main::migrations::{Expr, extension::postgres::PgExpr};
+
+        stmt = stmt.filter(
+            Expr::col(main::entities::insurance_providers::Column::Name)
+                .ilike(format!("%{}%", term)),
+        );
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(50).clamp(1, 100);
+    let paginator = stmt
+        .order_by_asc(main::entities::insurance_providers::Column::Name)
+        .paginate(&app_state.main_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
+
+    let providers = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let mut results = Vec::with_capacity(providers.len());
+    for provider in &providers {
+        results.push(provider_json(provider, true));
+    }
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "insurance_providers": results,
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "Insurance providers fetched successfully",
+        }),
+    ))
+}
+
+pub async fn show(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+) -> Result<ApiResponse, ApiResponse> {
+    let provider = find_provider(&app_state, path.into_inner(), false).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "insurance_provider": provider_json(&provider, true),
+            "message": "Insurance provider fetched successfully",
+        }),
+    ))
+}
+
+/// Registers an insurer. Sent as multipart so a logo can come with it;
+/// API credentials are encrypted before they are stored.
+pub async fn create(
+    app_state: web::Data<AppState>,
+    payload: Multipart,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let data = provider_form(payload, &req, &app_state, true).await?;
+
+    let name = data.name.as_deref().unwrap_or_default().trim().to_string();
+    ensure_unique_provider_name(&app_state.main_db, &name, None).await?;
+
+    let supported_plans = match &data.supported_plans {
+        Some(plans) => Some(plans_json(plans)?),
+        None => None,
+    };
+
+    let provider = main::entities::insurance_providers::ActiveModel {
+        name: Set(name),
+        email: Set(optional_text(&data.email)),
+        country_code: Set(optional_text(&data.country_code)),
+        phone_number: Set(optional_text(&data.phone_number)),
+        website: Set(optional_text(&data.website)),
+        address: Set(optional_text(&data.address)),
+        api_endpoint: Set(optional_text(&data.api_endpoint)),
+        api_credentials: Set(encrypted_credentials(data.api_credentials.as_ref())?),
+        supported_plans: Set(supported_plans),
+        logo_url: Set(data.logo_url.clone()),
+        ..Default::default()
+    }
+    .insert(&app_state.main_db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to create insurance provider: {}", err);
+        ApiResponse::new(
+            500,
+            json!({ "message": "Failed to create insurance provider" }),
+        )
+    })?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "insurance_provider": provider_json(&provider, true),
+            "message": "Insurance provider created successfully",
+        }),
+    ))
+}
+
+/// Updates the fields sent. Credentials are replaced only when
+/// `api_credentials` is sent, and cleared when it is empty.
+pub async fn edit(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    payload: Multipart,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let provider = find_provider(&app_state, path.into_inner(), false).await?;
+    let data = provider_form(payload, &req, &app_state, false).await?;
+
+    let provider_pid = provider.pid;
+    let provider_id = provider.id;
+    let mut active_model: main::entities::insurance_providers::ActiveModel = provider.into();
+
+    if let Some(name) = data.name.as_deref().map(str::trim) {
+        ensure_unique_provider_name(&app_state.main_db, name, Some(provider_id)).await?;
+        active_model.name = Set(name.to_string());
+    }
+    if data.email.is_some() {
+        active_model.email = Set(optional_text(&data.email));
+    }
+    if data.country_code.is_some() {
+        active_model.country_code = Set(optional_text(&data.country_code));
+    }
+    if data.phone_number.is_some() {
+        active_model.phone_number = Set(optional_text(&data.phone_number));
+    }
+    if data.website.is_some() {
+        active_model.website = Set(optional_text(&data.website));
+    }
+    if data.address.is_some() {
+        active_model.address = Set(optional_text(&data.address));
+    }
+    if data.api_endpoint.is_some() {
+        active_model.api_endpoint = Set(optional_text(&data.api_endpoint));
+    }
+    if let Some(credentials) = &data.api_credentials {
+        active_model.api_credentials = Set(encrypted_credentials(Some(credentials))?);
+    }
+    if let Some(plans) = &data.supported_plans {
+        active_model.supported_plans = Set(Some(plans_json(plans)?));
+    }
+    if data.logo_url.is_some() {
+        active_model.logo_url = Set(data.logo_url.clone());
+    }
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let provider = active_model
+        .update(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to update insurance provider {}: {}",
+                provider_pid,
+                err
+            );
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to update insurance provider" }),
+            )
+        })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "insurance_provider": provider_json(&provider, true),
+            "message": "Insurance provider updated successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct SupportedPlansData {
+    pub supported_plans: Vec<SupportedPlan>,
+}
+
+/// Replaces the provider's plan list.
+pub async fn update_plans(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<SupportedPlansData>,
+) -> Result<ApiResponse, ApiResponse> {
+    let provider = find_provider(&app_state, path.into_inner(), false).await?;
+    let supported_plans = plans_json(&data.supported_plans)?;
+
+    let provider_pid = provider.pid;
+    let mut active_model: main::entities::insurance_providers::ActiveModel = provider.into();
+    active_model.supported_plans = Set(Some(supported_plans));
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let provider = active_model
+        .update(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to update plans of {}: {}", provider_pid, err);
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to update supported plans" }),
+            )
+        })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "insurance_provider": provider_json(&provider, true),
+            "message": "Supported plans updated successfully",
+        }),
+    ))
+}
+
+/// Archives the provider. Cards already on it keep working; it is no
+/// longer offered to patients adding insurance.
+pub async fn destroy(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+) -> Result<ApiResponse, ApiResponse> {
+    let provider = find_provider(&app_state, path.into_inner(), false).await?;
+
+    let provider_pid = provider.pid;
+    let mut active_model: main::entities::insurance_providers::ActiveModel = provider.into();
+    active_model.deleted_at = Set(Some(Utc::now().naive_utc()));
+    active_model.updated_at = Set(Utc::now().naive_utc());
+    active_model
+        .update(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to delete insurance provider {}: {}",
+                provider_pid,
+                err
+            );
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to delete insurance provider" }),
+            )
+        })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({ "message": "Insurance provider deleted successfully" }),
+    ))
+}
+
+pub async fn restore(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+) -> Result<ApiResponse, ApiResponse> {
+    let provider = find_provider(&app_state, path.into_inner(), true).await?;
+    ensure_unique_provider_name(&app_state.main_db, &provider.name, Some(provider.id)).await?;
+
+    let provider_pid = provider.pid;
+    let mut active_model: main::entities::insurance_providers::ActiveModel = provider.into();
+    active_model.deleted_at = Set(None);
+    active_model.updated_at = Set(Utc::now().naive_utc());
+    active_model
+        .update(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to restore insurance provider {}: {}",
+                provider_pid,
+                err
+            );
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to restore insurance provider" }),
+            )
+        })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({ "message": "Insurance provider restored successfully" }),
+    ))
+}
+
+async fn find_provider(
+    app_state: &AppState,
+    pid: Uuid,
+    archived: bool,
+) -> Result<main::entities::insurance_providers::Model, ApiResponse> {
+    let mut stmt = main::entities::insurance_providers::Entity::find_by_pid(pid);
+
+    stmt = if archived {
+        stmt.filter(main::entities::insurance_providers::Column::DeletedAt.is_not_null())
+    } else {
+        stmt.filter(main::entities::insurance_providers::Column::DeletedAt.is_null())
+    };
+
+    stmt.one(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch insurance provider {}: {}", pid, err);
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to fetch insurance provider" }),
+            )
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Insurance provider not found" })))
+}
+
+fn optional_text(value: &Option<String>) -> Option<String> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+fn plans_json(plans: &[SupportedPlan]) -> Result<Value, ApiResponse> {
+    let plans = clean_supported_plans(plans).map_err(|err| {
+        ApiResponse::new(
+            400,
+            json!(ValidationError {
+                errors: HashMap::from([("supported_plans".to_string(), err)]),
+            }),
+        )
+    })?;
+
+    Ok(json!(plans))
+}
+
+fn encrypted_credentials(credentials: Option<&Value>) -> Result<Option<Value>, ApiResponse> {
+    match credentials {
+        None | Some(Value::Null) => Ok(None),
+        Some(credentials) => encrypt_json(credentials).map(Some).map_err(|err| {
+            log::error!("Failed to encrypt insurance provider credentials: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to store API credentials" }))
+        }),
+    }
+}
@@ -1,3 +1,6 @@
+pub mod billing_line_items;
+pub mod chronic_registries;
+pub mod insurance_providers;
 pub mod patients;
 pub mod payments;
 pub mod subscription_plans;
@@ -5,4 +8,4 @@ pub mod subscriptions;
 pub mod tenant_applications;
 pub mod tenants;
 pub mod users;
-pub mod billing_line_items;
+pub mod vaccine_schedules;
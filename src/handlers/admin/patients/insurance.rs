@@ -7,10 +7,14 @@ use crate::{
         self,
         migrations::sea_orm::{ColumnTrait, EntityTrait, QueryFilter},
     },
+    handlers::services::insurance_dependents::{
+        DependentData, DependentParams, create_dependent, deactivate_dependent, list_dependents,
+        update_dependent,
+    },
     handlers::services::patient_insurance::{
         create_patient_insurance, delete_permanently_patient_insurance, destroy_patient_insurance,
         edit_patient_insurance, fetch_patient_insurance, fetch_patient_insurances,
-        restore_patient_insurance, set_primary_patient_insurance,
+        restore_patient_insurance, set_primary_patient_insurance, verify_patient_insurance,
     },
     utils::{
         api_response::ApiResponse, app_state::AppState, pagination::PaginationParams,
@@ -111,3 +115,43 @@ pub async fn delete_permanently(
 
     delete_permanently_patient_insurance(&app_state, insurance_id).await
 }
+
+pub async fn verify(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+) -> Result<ApiResponse, ApiResponse> {
+    let insurance_id = path.into_inner();
+
+    verify_patient_insurance(&app_state, insurance_id, None).await
+}
+
+pub async fn index_dependents(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    query: web::Query<DependentParams>,
+) -> Result<ApiResponse, ApiResponse> {
+    list_dependents(&app_state.main_db, path.into_inner(), None, &query).await
+}
+
+pub async fn add_dependent(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<DependentData>,
+) -> Result<ApiResponse, ApiResponse> {
+    create_dependent(&app_state.main_db, path.into_inner(), None, &data).await
+}
+
+pub async fn edit_dependent(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<DependentData>,
+) -> Result<ApiResponse, ApiResponse> {
+    update_dependent(&app_state.main_db, path.into_inner(), None, &data).await
+}
+
+pub async fn deactivate_dependent_cover(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+) -> Result<ApiResponse, ApiResponse> {
+    deactivate_dependent(&app_state.main_db, path.into_inner(), None).await
+}
@@ -31,6 +31,7 @@ pub struct PaymentTransactionsResultData {
     pub status: String,
     pub payment_method: String,
     pub invoice_url: Option<String>,
+    pub invoice_file_pid: Option<Uuid>,
     pub description: Option<String>,
     pub failure_reason: Option<String>,
     pub metadata: Option<serde_json::Value>,
@@ -159,6 +160,7 @@ pub async fn show(
         .column(main::entities::payment_transactions::Column::Status)
         .column(main::entities::payment_transactions::Column::PaymentMethod)
         .column(main::entities::payment_transactions::Column::InvoiceUrl)
+        .column(main::entities::payment_transactions::Column::InvoiceFilePid)
         .column(main::entities::payment_transactions::Column::Description)
         .column(main::entities::payment_transactions::Column::FailureReason)
         .column(main::entities::payment_transactions::Column::Metadata)
@@ -204,6 +206,7 @@ pub async fn show(
                 "status": payment_transaction.status,
                 "payment_method": payment_transaction.payment_method,
                 "invoice_url": payment_transaction.invoice_url,
+                "invoice_file_pid": payment_transaction.invoice_file_pid,
                 "description": payment_transaction.description,
                 "failure_reason": payment_transaction.failure_reason,
                 "metadata": payment_transaction.metadata,
@@ -255,6 +258,7 @@ pub async fn show_by_tenant(
                 "status": p.status,
                 "payment_method": p.payment_method,
                 "invoice_url": p.invoice_url,
+                "invoice_file_pid": p.invoice_file_pid,
                 "description": p.description,
                 "failure_reason": p.failure_reason,
                 "metadata": p.metadata,
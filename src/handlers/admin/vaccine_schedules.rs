@@ -0,0 +1,390 @@
+use std::collections::HashMap;
+
+use actix_web::{HttpRequest, web};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::main::{
+        self,
+        entities::sea_orm_active_enums::Gender,
+        migrations::sea_orm::{
+            ActiveModelTrait, ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter,
+            QueryOrder, Set,
+        },
+    },
+    utils::{
+        api_response::ApiResponse, app_state::AppState, pagination::PaginationParams,
+        permission::has_permission, validator_error::ValidationError,
+    },
+};
+
+fn vaccine_schedule_json(schedule: &main::entities::vaccine_schedules::Model) -> Value {
+    json!({
+        "pid": schedule.pid,
+        "vaccine_code": schedule.vaccine_code,
+        "vaccine_name": schedule.vaccine_name,
+        "dose_number": schedule.dose_number,
+        "recommended_age_days": schedule.recommended_age_days,
+        "overdue_after_days": schedule.overdue_after_days,
+        "gender": schedule.gender,
+        "notes": schedule.notes,
+        "is_active": schedule.is_active,
+        "created_at": schedule.created_at,
+        "updated_at": schedule.updated_at,
+        "deleted_at": schedule.deleted_at,
+    })
+}
+
+pub async fn index(
+    app_state: web::Data<AppState>,
+    query: web::Query<PaginationParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let mut stmt = main::entities::vaccine_schedules::Entity::find();
+
+    if !has_permission("view_archived_vaccine_schedules", &req).await? {
+        stmt = stmt.filter(main::entities::vaccine_schedules::Column::DeletedAt.is_null());
+    }
+
+    if let Some(term) = &query.search {
+        use main::migrations::{Expr, extension::postgres::PgExpr};
+
+        let like = format!("%{}%", term);
+        stmt = stmt.filter(
+            Condition::any()
+                .add(
+                    Expr::col(main::entities::vaccine_schedules::Column::VaccineCode)
+                        .ilike(like.clone()),
+                )
+                .add(
+                    Expr::col(main::entities::vaccine_schedules::Column::VaccineName)
+                        .ilike(like.clone()),
+                ),
+        );
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(50).clamp(1, 100);
+    let paginator = stmt
+        .order_by_asc(main::entities::vaccine_schedules::Column::RecommendedAgeDays)
+        .order_by_asc(main::entities::vaccine_schedules::Column::VaccineCode)
+        .order_by_asc(main::entities::vaccine_schedules::Column::DoseNumber)
+        .paginate(&app_state.main_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
+
+    let results = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?
+        .iter()
+        .map(vaccine_schedule_json)
+        .collect::<Vec<_>>();
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "vaccine_schedules": results,
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "Vaccine schedules fetched successfully",
+        }),
+    ))
+}
+
+pub async fn show(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+) -> Result<ApiResponse, ApiResponse> {
+    let schedule = find_schedule(&app_state, path.into_inner(), false).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "vaccine_schedule": vaccine_schedule_json(&schedule),
+            "message": "Vaccine schedule fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct VaccineScheduleData {
+    pub vaccine_code: String,
+    pub vaccine_name: String,
+    pub dose_number: i32,
+    pub recommended_age_days: i32,
+    pub overdue_after_days: Option<i32>,
+    pub gender: Option<Gender>,
+    pub notes: Option<String>,
+    pub is_active: Option<bool>,
+}
+
+impl VaccineScheduleData {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if self.vaccine_code.trim().is_empty() {
+            errors.insert(
+                "vaccine_code".to_string(),
+                "Vaccine code is required".to_string(),
+            );
+        }
+
+        if self.vaccine_name.trim().is_empty() {
+            errors.insert(
+                "vaccine_name".to_string(),
+                "Vaccine name is required".to_string(),
+            );
+        }
+
+        if self.dose_number < 0 {
+            errors.insert(
+                "dose_number".to_string(),
+                "Dose number must be greater than or equal to 0".to_string(),
+            );
+        }
+
+        if self.recommended_age_days < 0 {
+            errors.insert(
+                "recommended_age_days".to_string(),
+                "Recommended age must be greater than or equal to 0 days".to_string(),
+            );
+        }
+
+        if self.overdue_after_days.unwrap_or(0) < 0 {
+            errors.insert(
+                "overdue_after_days".to_string(),
+                "Overdue window must be greater than or equal to 0 days".to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+pub async fn create(
+    app_state: web::Data<AppState>,
+    data: web::Json<VaccineScheduleData>,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate() {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let vaccine_code = data.vaccine_code.trim().to_uppercase();
+    ensure_unique_dose(&app_state, &vaccine_code, data.dose_number, None).await?;
+
+    let schedule = main::entities::vaccine_schedules::ActiveModel {
+        vaccine_code: Set(vaccine_code),
+        vaccine_name: Set(data.vaccine_name.trim().to_string()),
+        dose_number: Set(data.dose_number),
+        recommended_age_days: Set(data.recommended_age_days),
+        overdue_after_days: Set(data.overdue_after_days.unwrap_or(14)),
+        gender: Set(data.gender.clone()),
+        notes: Set(data.notes.clone()),
+        is_active: Set(data.is_active.unwrap_or(true)),
+        ..Default::default()
+    }
+    .insert(&app_state.main_db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to create vaccine schedule: {}", err);
+        ApiResponse::new(
+            500,
+            json!({ "message": "Failed to create vaccine schedule" }),
+        )
+    })?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "vaccine_schedule": vaccine_schedule_json(&schedule),
+            "message": "Vaccine schedule created successfully",
+        }),
+    ))
+}
+
+pub async fn edit(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<VaccineScheduleData>,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate() {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let schedule = find_schedule(&app_state, path.into_inner(), false).await?;
+
+    let vaccine_code = data.vaccine_code.trim().to_uppercase();
+    ensure_unique_dose(
+        &app_state,
+        &vaccine_code,
+        data.dose_number,
+        Some(schedule.id),
+    )
+    .await?;
+
+    let mut active_model: main::entities::vaccine_schedules::ActiveModel =
+        schedule.to_owned().into();
+    active_model.vaccine_code = Set(vaccine_code);
+    active_model.vaccine_name = Set(data.vaccine_name.trim().to_string());
+    active_model.dose_number = Set(data.dose_number);
+    active_model.recommended_age_days = Set(data.recommended_age_days);
+    active_model.overdue_after_days = Set(data
+        .overdue_after_days
+        .unwrap_or(schedule.overdue_after_days));
+    active_model.gender = Set(data.gender.clone());
+    active_model.notes = Set(data.notes.clone());
+    active_model.is_active = Set(data.is_active.unwrap_or(schedule.is_active));
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let schedule = active_model
+        .update(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to update vaccine schedule: {}", err);
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to update vaccine schedule" }),
+            )
+        })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "vaccine_schedule": vaccine_schedule_json(&schedule),
+            "message": "Vaccine schedule updated successfully",
+        }),
+    ))
+}
+
+pub async fn destroy(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+) -> Result<ApiResponse, ApiResponse> {
+    let schedule = find_schedule(&app_state, path.into_inner(), false).await?;
+
+    let mut active_model: main::entities::vaccine_schedules::ActiveModel = schedule.into();
+    active_model.deleted_at = Set(Some(Utc::now().naive_utc()));
+    active_model.updated_at = Set(Utc::now().naive_utc());
+    active_model
+        .update(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to delete vaccine schedule: {}", err);
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to delete vaccine schedule" }),
+            )
+        })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({ "message": "Vaccine schedule deleted successfully" }),
+    ))
+}
+
+pub async fn restore(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+) -> Result<ApiResponse, ApiResponse> {
+    let schedule = find_schedule(&app_state, path.into_inner(), true).await?;
+
+    let mut active_model: main::entities::vaccine_schedules::ActiveModel = schedule.into();
+    active_model.deleted_at = Set(None);
+    active_model.updated_at = Set(Utc::now().naive_utc());
+    active_model
+        .update(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to restore vaccine schedule: {}", err);
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to restore vaccine schedule" }),
+            )
+        })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({ "message": "Vaccine schedule restored successfully" }),
+    ))
+}
+
+async fn find_schedule(
+    app_state: &AppState,
+    pid: Uuid,
+    archived: bool,
+) -> Result<main::entities::vaccine_schedules::Model, ApiResponse> {
+    let mut stmt = main::entities::vaccine_schedules::Entity::find_by_pid(pid);
+
+    stmt = if archived {
+        stmt.filter(main::entities::vaccine_schedules::Column::DeletedAt.is_not_null())
+    } else {
+        stmt.filter(main::entities::vaccine_schedules::Column::DeletedAt.is_null())
+    };
+
+    stmt.one(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch vaccine schedule {}: {}", pid, err);
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to fetch vaccine schedule" }),
+            )
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Vaccine schedule not found" })))
+}
+
+async fn ensure_unique_dose(
+    app_state: &AppState,
+    vaccine_code: &str,
+    dose_number: i32,
+    exclude_id: Option<i32>,
+) -> Result<(), ApiResponse> {
+    let mut stmt = main::entities::vaccine_schedules::Entity::find()
+        .filter(main::entities::vaccine_schedules::Column::VaccineCode.eq(vaccine_code))
+        .filter(main::entities::vaccine_schedules::Column::DoseNumber.eq(dose_number));
+
+    if let Some(id) = exclude_id {
+        stmt = stmt.filter(main::entities::vaccine_schedules::Column::Id.ne(id));
+    }
+
+    let exists = stmt.count(&app_state.main_db).await.map_err(|err| {
+        log::error!("Failed to check vaccine schedule uniqueness: {}", err);
+        ApiResponse::new(
+            500,
+            json!({ "message": "Failed to validate vaccine schedule" }),
+        )
+    })? > 0;
+
+    if exists {
+        let mut errors = HashMap::new();
+        errors.insert(
+            "dose_number".to_string(),
+            format!(
+                "Dose {} of {} is already scheduled",
+                dose_number, vaccine_code
+            ),
+        );
+        return Err(ApiResponse::new(400, json!(ValidationError { errors })));
+    }
+
+    Ok(())
+}
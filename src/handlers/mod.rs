@@ -1,7 +1,9 @@
 pub mod admin;
 pub mod auth;
 pub mod health;
+pub mod queue;
 pub mod services;
 pub mod shared;
+pub mod telemedicine;
 pub mod tenant;
 pub mod user;
@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+use actix_web::{HttpResponse, get, web};
+use futures_util::StreamExt;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::mpsc;
+
+use crate::{
+    handlers::services::queue::{
+        parse_stage, redeem_stream_token, stage_queue_snapshot, stage_slug, subscribe_queue_updates,
+    },
+    utils::{api_response::ApiResponse, app_state::AppState},
+};
+
+/// Live screens get a fresh snapshot at least this often so wait times keep
+/// ticking and idle proxies do not drop the connection.
+const LIVE_REFRESH_SECS: u64 = 30;
+
+#[derive(Deserialize, Debug)]
+pub struct LiveQueueParams {
+    pub token: String,
+}
+
+/// Server-Sent Events feed for waiting-room screens and clinician worklists.
+/// `EventSource` cannot send an Authorization header, so the feed is
+/// authorised by the one-time stream token issued from the tenant queue
+/// endpoints. Sends a `queue` event with the full snapshot whenever the
+/// stage changes, and at least every `LIVE_REFRESH_SECS`.
+#[get("/queue/live")]
+async fn live_queue(
+    app_state: web::Data<AppState>,
+    query: web::Query<LiveQueueParams>,
+) -> Result<HttpResponse, ApiResponse> {
+    let ticket = redeem_stream_token(&app_state.redis, &query.token)
+        .await?
+        .ok_or_else(|| {
+            ApiResponse::new(401, json!({ "message": "Invalid or expired stream token" }))
+        })?;
+
+    let stage = parse_stage(&ticket.stage)
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Queue not found" })))?;
+    let tenant_db = app_state
+        .tenant_db(ticket.sso_tenant_id)
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Facility not found" })))?;
+    let main_db = app_state.main_db.clone();
+    let display = ticket.display;
+
+    let pubsub = subscribe_queue_updates(&app_state.redis, ticket.tenant_pid, &stage).await?;
+    let (tx, rx) = mpsc::channel::<web::Bytes>(8);
+
+    actix_web::rt::spawn(async move {
+        let mut updates = pubsub.into_on_message();
+        let mut refresh = tokio::time::interval(Duration::from_secs(LIVE_REFRESH_SECS));
+
+        loop {
+            tokio::select! {
+                update = updates.next() => {
+                    if update.is_none() {
+                        break;
+                    }
+                }
+                _ = refresh.tick() => {}
+            }
+
+            let event = match stage_queue_snapshot(&main_db, &tenant_db, &stage, display).await {
+                Ok(snapshot) => format!("event: queue\ndata: {}\n\n", snapshot),
+                Err(err) => {
+                    log::error!("Failed to build live {} queue: {}", stage_slug(&stage), err);
+                    ": queue unavailable\n\n".to_string()
+                }
+            };
+
+            // The receiver is dropped once the client disconnects.
+            if tx.send(web::Bytes::from(event)).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let stream = futures::stream::unfold(rx, |mut rx| async move {
+        rx.recv()
+            .await
+            .map(|bytes| (Ok::<_, actix_web::Error>(bytes), rx))
+    });
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(("Cache-Control", "no-cache"))
+        .insert_header(("X-Accel-Buffering", "no"))
+        .streaming(stream))
+}
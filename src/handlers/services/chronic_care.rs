@@ -0,0 +1,237 @@
+use chrono::{Duration, NaiveDate};
+use rust_decimal::{Decimal, prelude::FromPrimitive};
+use serde::Serialize;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        main::{
+            self,
+            entities::sea_orm_active_enums::ChronicControlIndicator,
+            migrations::sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter},
+        },
+        tenant::{
+            self,
+            entities::sea_orm_active_enums::RegistryEnrolmentStatus,
+            migrations::sea_orm::{ActiveModelTrait, Set},
+        },
+    },
+    utils::api_response::ApiResponse,
+};
+
+/// Programme targets: BP below 140/90 mmHg, HbA1c below 7%, and an HIV viral
+/// load below 1000 copies/mL.
+const BP_SYSTOLIC_TARGET: i32 = 140;
+const BP_DIASTOLIC_TARGET: i32 = 90;
+const HBA1C_TARGET: f64 = 7.0;
+const VIRAL_LOAD_TARGET: i32 = 1000;
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ControlStatus {
+    Controlled,
+    Uncontrolled,
+    NoData,
+}
+
+/// Uppercases an ICD-10 code and drops the dot so `e11.9` and `E119` compare equal.
+pub fn normalize_diagnosis_code(code: &str) -> String {
+    code.trim()
+        .chars()
+        .filter(|c| *c != '.')
+        .collect::<String>()
+        .to_uppercase()
+}
+
+/// A diagnosis enrols into a registry when it starts with any of the
+/// registry's code prefixes.
+pub fn registry_matches(registry: &main::entities::chronic_registries::Model, code: &str) -> bool {
+    let code = normalize_diagnosis_code(code);
+    registry
+        .diagnosis_codes
+        .iter()
+        .any(|prefix| code.starts_with(&normalize_diagnosis_code(prefix)))
+}
+
+/// Judges control from the most recent follow-up that captured the registry's
+/// indicator. `follow_ups` must be ordered oldest first.
+pub fn control_status<'a>(
+    indicator: &ChronicControlIndicator,
+    follow_ups: &'a [tenant::entities::registry_follow_ups::Model],
+) -> (
+    ControlStatus,
+    Option<&'a tenant::entities::registry_follow_ups::Model>,
+) {
+    let latest = follow_ups.iter().rev().find(|f| match indicator {
+        ChronicControlIndicator::BloodPressure => {
+            f.bp_systolic.is_some() && f.bp_diastolic.is_some()
+        }
+        ChronicControlIndicator::Hba1c => f.hba1c.is_some(),
+        ChronicControlIndicator::ViralLoad => f.viral_load.is_some(),
+    });
+
+    let Some(follow_up) = latest else {
+        return (ControlStatus::NoData, None);
+    };
+
+    let controlled = match indicator {
+        ChronicControlIndicator::BloodPressure => {
+            follow_up.bp_systolic.unwrap_or_default() < BP_SYSTOLIC_TARGET
+                && follow_up.bp_diastolic.unwrap_or_default() < BP_DIASTOLIC_TARGET
+        }
+        ChronicControlIndicator::Hba1c => follow_up
+            .hba1c
+            .is_some_and(|hba1c| hba1c < Decimal::from_f64(HBA1C_TARGET).unwrap_or_default()),
+        ChronicControlIndicator::ViralLoad => follow_up
+            .viral_load
+            .is_some_and(|viral_load| viral_load < VIRAL_LOAD_TARGET),
+    };
+
+    let status = if controlled {
+        ControlStatus::Controlled
+    } else {
+        ControlStatus::Uncontrolled
+    };
+
+    (status, Some(follow_up))
+}
+
+/// Days past the booked follow-up date, or `None` while the patient is not late.
+pub fn days_overdue(
+    enrolment: &tenant::entities::registry_enrolments::Model,
+    today: NaiveDate,
+) -> Option<i64> {
+    let next = enrolment.next_follow_up_on?;
+    let days = (today - next).num_days();
+    (days > 0).then_some(days)
+}
+
+pub async fn fetch_active_registries(
+    db: &DatabaseConnection,
+) -> Result<Vec<main::entities::chronic_registries::Model>, ApiResponse> {
+    main::entities::chronic_registries::Entity::find()
+        .filter(main::entities::chronic_registries::Column::IsActive.eq(true))
+        .filter(main::entities::chronic_registries::Column::DeletedAt.is_null())
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch chronic registries: {}", err);
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to fetch chronic registries" }),
+            )
+        })
+}
+
+/// Enrols the patient into every active registry the diagnosis qualifies for.
+/// Patients already on a registry, in any status, are left as they are.
+pub async fn enrol_matching_registries(
+    tenant_db: &tenant::migrations::sea_orm::DatabaseConnection,
+    registries: &[main::entities::chronic_registries::Model],
+    patient_pid: Uuid,
+    diagnosis_code: &str,
+    enrolled_on: NaiveDate,
+    enrolled_by: Option<Uuid>,
+) -> Result<Vec<tenant::entities::registry_enrolments::Model>, ApiResponse> {
+    let mut enrolments = Vec::new();
+
+    for registry in registries
+        .iter()
+        .filter(|registry| registry_matches(registry, diagnosis_code))
+    {
+        let existing = tenant::entities::registry_enrolments::Entity::find()
+            .filter(tenant::entities::registry_enrolments::Column::RegistryPid.eq(registry.pid))
+            .filter(tenant::entities::registry_enrolments::Column::PatientPid.eq(patient_pid))
+            .filter(tenant::entities::registry_enrolments::Column::DeletedAt.is_null())
+            .one(tenant_db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to check registry enrolment: {}", err);
+                ApiResponse::new(500, json!({ "message": "Failed to enrol patient" }))
+            })?;
+
+        if existing.is_some() {
+            continue;
+        }
+
+        let enrolment = tenant::entities::registry_enrolments::ActiveModel {
+            registry_pid: Set(registry.pid),
+            patient_pid: Set(patient_pid),
+            diagnosis_code: Set(normalize_diagnosis_code(diagnosis_code)),
+            enrolled_on: Set(enrolled_on),
+            status: Set(RegistryEnrolmentStatus::Active),
+            next_follow_up_on: Set(Some(
+                enrolled_on + Duration::days(registry.follow_up_interval_days as i64),
+            )),
+            enrolled_by: Set(enrolled_by),
+            ..Default::default()
+        }
+        .insert(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to enrol patient {} into registry {}: {}",
+                patient_pid,
+                registry.code,
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to enrol patient" }))
+        })?;
+
+        enrolments.push(enrolment);
+    }
+
+    Ok(enrolments)
+}
+
+pub fn chronic_registry_json(registry: &main::entities::chronic_registries::Model) -> Value {
+    json!({
+        "pid": registry.pid,
+        "code": registry.code,
+        "name": registry.name,
+        "description": registry.description,
+        "diagnosis_codes": registry.diagnosis_codes,
+        "control_indicator": registry.control_indicator,
+        "follow_up_interval_days": registry.follow_up_interval_days,
+        "defaulter_after_days": registry.defaulter_after_days,
+        "is_active": registry.is_active,
+        "created_at": registry.created_at,
+        "updated_at": registry.updated_at,
+        "deleted_at": registry.deleted_at,
+    })
+}
+
+pub fn registry_enrolment_json(enrolment: &tenant::entities::registry_enrolments::Model) -> Value {
+    json!({
+        "pid": enrolment.pid,
+        "registry_pid": enrolment.registry_pid,
+        "patient_pid": enrolment.patient_pid,
+        "diagnosis_code": enrolment.diagnosis_code,
+        "enrolled_on": enrolment.enrolled_on,
+        "status": enrolment.status,
+        "last_follow_up_on": enrolment.last_follow_up_on,
+        "next_follow_up_on": enrolment.next_follow_up_on,
+        "exited_on": enrolment.exited_on,
+        "exit_reason": enrolment.exit_reason,
+        "created_at": enrolment.created_at,
+        "updated_at": enrolment.updated_at,
+    })
+}
+
+pub fn registry_follow_up_json(follow_up: &tenant::entities::registry_follow_ups::Model) -> Value {
+    json!({
+        "pid": follow_up.pid,
+        "visit_date": follow_up.visit_date,
+        "bp_systolic": follow_up.bp_systolic,
+        "bp_diastolic": follow_up.bp_diastolic,
+        "hba1c": follow_up.hba1c,
+        "fasting_glucose": follow_up.fasting_glucose,
+        "viral_load": follow_up.viral_load,
+        "cd4_count": follow_up.cd4_count,
+        "adherence": follow_up.adherence,
+        "next_follow_up_on": follow_up.next_follow_up_on,
+        "notes": follow_up.notes,
+        "created_at": follow_up.created_at,
+    })
+}
@@ -0,0 +1,65 @@
+use chrono::NaiveDateTime;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::tenant::{
+        self,
+        entities::sea_orm_active_enums::{ClinicalTaskPriority, ClinicalTaskStatus},
+        migrations::sea_orm::{ActiveModelTrait, DatabaseConnection, Set},
+    },
+    utils::api_response::ApiResponse,
+};
+
+pub struct NewClinicalTask {
+    pub patient_pid: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub priority: ClinicalTaskPriority,
+    pub source_type: &'static str,
+    pub source_pid: Option<Uuid>,
+    pub due_at: Option<NaiveDateTime>,
+}
+
+pub async fn create_clinical_task(
+    tenant_db: &DatabaseConnection,
+    task: NewClinicalTask,
+) -> Result<tenant::entities::clinical_tasks::Model, ApiResponse> {
+    tenant::entities::clinical_tasks::ActiveModel {
+        patient_pid: Set(task.patient_pid),
+        title: Set(task.title),
+        description: Set(task.description),
+        priority: Set(task.priority),
+        status: Set(ClinicalTaskStatus::Open),
+        source_type: Set(task.source_type.to_string()),
+        source_pid: Set(task.source_pid),
+        due_at: Set(task.due_at),
+        ..Default::default()
+    }
+    .insert(tenant_db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to create clinical task: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create clinical task" }))
+    })
+}
+
+pub fn clinical_task_json(task: &tenant::entities::clinical_tasks::Model) -> Value {
+    json!({
+        "pid": task.pid,
+        "patient_pid": task.patient_pid,
+        "title": task.title,
+        "description": task.description,
+        "priority": task.priority,
+        "status": task.status,
+        "source_type": task.source_type,
+        "source_pid": task.source_pid,
+        "assigned_to": task.assigned_to,
+        "due_at": task.due_at,
+        "completed_by": task.completed_by,
+        "completed_at": task.completed_at,
+        "resolution": task.resolution,
+        "created_at": task.created_at,
+        "updated_at": task.updated_at,
+    })
+}
@@ -0,0 +1,214 @@
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::tenant::{
+        self,
+        migrations::sea_orm::{
+            ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
+            QueryFilter, QuerySelect, Set,
+        },
+    },
+    handlers::services::pharmacy::{lock_product_batches, product_label},
+    utils::api_response::ApiResponse,
+};
+
+/// Writes the register entry for a movement of a controlled product. Other
+/// products are left alone. The balance is taken from the movement ledger so
+/// the register always agrees with stock on hand.
+pub async fn record_register_entry<C: ConnectionTrait>(
+    db: &C,
+    movement: &tenant::entities::stock_movements::Model,
+) -> Result<Option<tenant::entities::controlled_register_entries::Model>, ApiResponse> {
+    let is_controlled =
+        tenant::entities::pharmacy_products::Entity::find_by_id(movement.product_id)
+            .select_only()
+            .column(tenant::entities::pharmacy_products::Column::IsControlled)
+            .into_tuple::<bool>()
+            .one(db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to fetch product {}: {}", movement.product_id, err);
+                ApiResponse::new(500, json!({ "message": "Failed to record stock movement" }))
+            })?
+            .unwrap_or(false);
+
+    if !is_controlled {
+        return Ok(None);
+    }
+
+    // Serialises concurrent movements of the product so each entry's
+    // balance includes every entry written before it.
+    lock_product_batches(db, movement.product_id).await?;
+    let balance_after = ledger_balance(db, movement.store_id, movement.product_id).await?;
+
+    tenant::entities::controlled_register_entries::ActiveModel {
+        store_id: Set(movement.store_id),
+        product_id: Set(movement.product_id),
+        batch_id: Set(movement.batch_id),
+        stock_movement_id: Set(movement.id),
+        movement_type: Set(movement.movement_type.clone()),
+        quantity: Set(movement.quantity),
+        balance_after: Set(balance_after),
+        reference: Set(movement.reference.clone()),
+        reason: Set(movement.reason.clone()),
+        performed_by: Set(movement.performed_by),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .map(Some)
+    .map_err(|err| {
+        log::error!(
+            "Failed to write register entry for movement {}: {}",
+            movement.pid,
+            err
+        );
+        ApiResponse::new(500, json!({ "message": "Failed to record stock movement" }))
+    })
+}
+
+/// Stock of a product held in a store across all of its batches, expired
+/// ones included, as the register counts them.
+pub async fn ledger_balance<C: ConnectionTrait>(
+    db: &C,
+    store_id: i32,
+    product_id: i32,
+) -> Result<i64, ApiResponse> {
+    Ok(tenant::entities::stock_movements::Entity::find()
+        .select_only()
+        .column_as(
+            tenant::entities::stock_movements::Column::Quantity.sum(),
+            "balance",
+        )
+        .filter(tenant::entities::stock_movements::Column::StoreId.eq(store_id))
+        .filter(tenant::entities::stock_movements::Column::ProductId.eq(product_id))
+        .into_tuple::<Option<i64>>()
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to sum stock of product {}: {}", product_id, err);
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to fetch register balance" }),
+            )
+        })?
+        .flatten()
+        .unwrap_or(0))
+}
+
+pub async fn find_controlled_product(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+) -> Result<tenant::entities::pharmacy_products::Model, ApiResponse> {
+    let product = tenant::entities::pharmacy_products::Entity::find_by_pid(pid)
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch product {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch product" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Product not found" })))?;
+
+    if !product.is_controlled {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Product is not a controlled substance" }),
+        ));
+    }
+
+    Ok(product)
+}
+
+pub async fn find_reconciliation(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+) -> Result<tenant::entities::controlled_reconciliations::Model, ApiResponse> {
+    tenant::entities::controlled_reconciliations::Entity::find_by_pid(pid)
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch reconciliation {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch reconciliation" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Reconciliation not found" })))
+}
+
+/// Received quantities go in `quantity_in`, issued ones in `quantity_out`,
+/// the way paper registers keep them.
+pub fn register_entry_json(
+    entry: &tenant::entities::controlled_register_entries::Model,
+    product: Option<&tenant::entities::pharmacy_products::Model>,
+    batch: Option<&tenant::entities::stock_batches::Model>,
+) -> Value {
+    json!({
+        "pid": entry.pid,
+        "entry_number": entry.id,
+        "product": product.map(|product| json!({
+            "pid": product.pid,
+            "label": product_label(product),
+            "unit": product.unit,
+            "controlled_schedule": product.controlled_schedule,
+        })),
+        "batch": batch.map(|batch| json!({
+            "pid": batch.pid,
+            "batch_number": batch.batch_number,
+            "expiry_date": batch.expiry_date,
+        })),
+        "movement_type": entry.movement_type,
+        "quantity_in": entry.quantity.max(0),
+        "quantity_out": (-entry.quantity).max(0),
+        "balance_after": entry.balance_after,
+        "reference": entry.reference,
+        "reason": entry.reason,
+        "performed_by": entry.performed_by,
+        "witnessed_by": entry.witnessed_by,
+        "witnessed_at": entry.witnessed_at,
+        "created_at": entry.created_at,
+    })
+}
+
+pub fn reconciliation_json(
+    reconciliation: &tenant::entities::controlled_reconciliations::Model,
+    product: Option<&tenant::entities::pharmacy_products::Model>,
+    store: Option<&tenant::entities::pharmacy_stores::Model>,
+) -> Value {
+    json!({
+        "pid": reconciliation.pid,
+        "store": store.map(|store| json!({
+            "pid": store.pid,
+            "name": store.name,
+            "code": store.code,
+        })),
+        "product": product.map(|product| json!({
+            "pid": product.pid,
+            "label": product_label(product),
+            "unit": product.unit,
+        })),
+        "register_balance": reconciliation.register_balance,
+        "counted_quantity": reconciliation.counted_quantity,
+        "variance": reconciliation.variance,
+        "status": reconciliation.status,
+        "notes": reconciliation.notes,
+        "counted_by": reconciliation.counted_by,
+        "witnessed_by": reconciliation.witnessed_by,
+        "witnessed_at": reconciliation.witnessed_at,
+        "investigation_notes": reconciliation.investigation_notes,
+        "investigated_by": reconciliation.investigated_by,
+        "resolution": reconciliation.resolution,
+        "has_adjustment": reconciliation.adjustment_entry_id.is_some(),
+        "resolved_by": reconciliation.resolved_by,
+        "resolved_at": reconciliation.resolved_at,
+        "created_at": reconciliation.created_at,
+        "updated_at": reconciliation.updated_at,
+    })
+}
+
+/// Quotes a CSV field when it contains a delimiter, quote or line break.
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
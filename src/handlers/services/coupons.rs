@@ -0,0 +1,361 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
+use rust_decimal::Decimal;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::tenant::{
+        self,
+        entities::sea_orm_active_enums::{CouponDiscountType, CouponScope},
+        migrations::sea_orm::{
+            ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
+            PaginatorTrait, QueryFilter, QuerySelect, Set,
+        },
+    },
+    utils::{api_response::ApiResponse, validator_error::ValidationError},
+};
+
+/// `source_type` of redemptions made on online store orders.
+pub const ONLINE_ORDER_SOURCE: &str = "online_order";
+
+/// One priced line of a basket. A coupon discounts the lines its scope
+/// covers: products by pid, product categories, or facility services by
+/// code.
+#[derive(Debug, Clone, Default)]
+pub struct CouponLine {
+    pub product_pid: Option<Uuid>,
+    pub category: Option<String>,
+    pub service_code: Option<String>,
+    pub amount: Decimal,
+}
+
+/// A coupon that passed every check, with what it takes off the basket.
+#[derive(Debug, Clone)]
+pub struct AppliedCoupon {
+    pub coupon: tenant::entities::coupons::Model,
+    pub eligible_amount: Decimal,
+    pub discount_amount: Decimal,
+}
+
+/// Trims, upper-cases and de-duplicates the codes a patient entered.
+pub fn normalize_codes(codes: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for code in codes.iter().flat_map(|code| code.split(',')) {
+        let code = code.trim().to_uppercase();
+        if !code.is_empty() && !normalized.contains(&code) {
+            normalized.push(code);
+        }
+    }
+    normalized
+}
+
+/// Checks the codes against the basket and works out each discount. The
+/// coupon rows are locked, so inside a transaction the usage counts cannot
+/// change before the redemptions are recorded. Coupons are applied in the
+/// order given and together never take more than the basket total.
+pub async fn apply_coupons<C: ConnectionTrait>(
+    db: &C,
+    codes: &[String],
+    patient_pid: Uuid,
+    lines: &[CouponLine],
+) -> Result<Vec<AppliedCoupon>, ApiResponse> {
+    let codes = normalize_codes(codes);
+    if codes.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let coupons = tenant::entities::coupons::Entity::find()
+        .filter(tenant::entities::coupons::Column::Code.is_in(codes.clone()))
+        .filter(tenant::entities::coupons::Column::DeletedAt.is_null())
+        .lock_exclusive()
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch coupons {:?}: {}", codes, err);
+            ApiResponse::new(500, json!({ "message": "Failed to apply coupons" }))
+        })?;
+
+    let order_value: Decimal = lines.iter().map(|line| line.amount).sum();
+    let now = Utc::now().naive_utc();
+    let mut remaining = order_value;
+    let mut applied = Vec::new();
+
+    for code in &codes {
+        let Some(coupon) = coupons.iter().find(|coupon| &coupon.code == code) else {
+            return Err(coupon_error(format!("{} is not a valid coupon", code)));
+        };
+
+        if !coupon.is_active
+            || coupon.starts_at.is_some_and(|starts_at| now < starts_at)
+            || coupon.ends_at.is_some_and(|ends_at| now > ends_at)
+        {
+            return Err(coupon_error(format!("{} is not currently valid", code)));
+        }
+
+        if codes.len() > 1 && !coupon.is_stackable {
+            return Err(coupon_error(format!(
+                "{} cannot be combined with other coupons",
+                code
+            )));
+        }
+
+        if let Some(min_order_value) = coupon.min_order_value
+            && order_value < min_order_value
+        {
+            return Err(coupon_error(format!(
+                "{} needs an order of at least {}",
+                code, min_order_value
+            )));
+        }
+
+        if let Some(max_redemptions) = coupon.max_redemptions
+            && redemption_count(db, coupon.id, None).await? >= max_redemptions as u64
+        {
+            return Err(coupon_error(format!("{} has been fully redeemed", code)));
+        }
+
+        if let Some(max_per_patient) = coupon.max_redemptions_per_patient
+            && redemption_count(db, coupon.id, Some(patient_pid)).await? >= max_per_patient as u64
+        {
+            return Err(coupon_error(format!(
+                "You have already used {} the maximum number of times",
+                code
+            )));
+        }
+
+        let eligible_amount = eligible_amount(coupon, lines);
+        if eligible_amount <= Decimal::ZERO {
+            return Err(coupon_error(format!(
+                "{} does not apply to anything in this order",
+                code
+            )));
+        }
+
+        let discount_amount = coupon_discount(coupon, eligible_amount).min(remaining);
+        remaining -= discount_amount;
+        applied.push(AppliedCoupon {
+            coupon: coupon.clone(),
+            eligible_amount,
+            discount_amount,
+        });
+    }
+
+    Ok(applied)
+}
+
+/// Records one redemption per applied coupon against the order or bill
+/// they were applied to.
+pub async fn record_redemptions<C: ConnectionTrait>(
+    db: &C,
+    applied: &[AppliedCoupon],
+    patient_pid: Uuid,
+    source_type: &str,
+    source_pid: Uuid,
+    order_value: Decimal,
+) -> Result<(), ApiResponse> {
+    for coupon in applied {
+        tenant::entities::coupon_redemptions::ActiveModel {
+            coupon_id: Set(coupon.coupon.id),
+            patient_pid: Set(patient_pid),
+            source_type: Set(source_type.to_string()),
+            source_pid: Set(source_pid),
+            order_value: Set(order_value),
+            eligible_amount: Set(coupon.eligible_amount),
+            discount_amount: Set(coupon.discount_amount),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to record redemption of {}: {}",
+                coupon.coupon.code,
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to apply coupons" }))
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Voids the redemptions of a cancelled order or bill so the uses count
+/// again towards the coupon's limits.
+pub async fn void_redemptions<C: ConnectionTrait>(
+    db: &C,
+    source_type: &str,
+    source_pid: Uuid,
+) -> Result<(), ApiResponse> {
+    tenant::entities::coupon_redemptions::Entity::update_many()
+        .col_expr(
+            tenant::entities::coupon_redemptions::Column::VoidedAt,
+            tenant::migrations::Expr::value(Utc::now().naive_utc()),
+        )
+        .filter(tenant::entities::coupon_redemptions::Column::SourceType.eq(source_type))
+        .filter(tenant::entities::coupon_redemptions::Column::SourcePid.eq(source_pid))
+        .filter(tenant::entities::coupon_redemptions::Column::VoidedAt.is_null())
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to void redemptions of {} {}: {}",
+                source_type,
+                source_pid,
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to release coupons" }))
+        })?;
+
+    Ok(())
+}
+
+/// Redemptions of a coupon that have not been voided, optionally for one
+/// patient only.
+pub async fn redemption_count<C: ConnectionTrait>(
+    db: &C,
+    coupon_id: i32,
+    patient_pid: Option<Uuid>,
+) -> Result<u64, ApiResponse> {
+    let mut stmt = tenant::entities::coupon_redemptions::Entity::find()
+        .filter(tenant::entities::coupon_redemptions::Column::CouponId.eq(coupon_id))
+        .filter(tenant::entities::coupon_redemptions::Column::VoidedAt.is_null());
+
+    if let Some(patient_pid) = patient_pid {
+        stmt =
+            stmt.filter(tenant::entities::coupon_redemptions::Column::PatientPid.eq(patient_pid));
+    }
+
+    stmt.count(db).await.map_err(|err| {
+        log::error!(
+            "Failed to count redemptions of coupon {}: {}",
+            coupon_id,
+            err
+        );
+        ApiResponse::new(500, json!({ "message": "Failed to apply coupons" }))
+    })
+}
+
+/// The part of the basket the coupon's scope covers.
+pub fn eligible_amount(coupon: &tenant::entities::coupons::Model, lines: &[CouponLine]) -> Decimal {
+    let targets = coupon.targets.clone().unwrap_or_default();
+    let targeted = |value: Option<&str>| {
+        value.is_some_and(|value| {
+            targets
+                .iter()
+                .any(|target| target.eq_ignore_ascii_case(value.trim()))
+        })
+    };
+
+    lines
+        .iter()
+        .filter(|line| match coupon.scope {
+            CouponScope::Order => true,
+            CouponScope::Products => {
+                targeted(line.product_pid.map(|pid| pid.to_string()).as_deref())
+            }
+            CouponScope::Categories => targeted(line.category.as_deref()),
+            CouponScope::Services => targeted(line.service_code.as_deref()),
+        })
+        .map(|line| line.amount)
+        .sum()
+}
+
+/// A percentage of the eligible amount, capped at `max_discount`, or a
+/// fixed amount no larger than what it applies to.
+pub fn coupon_discount(
+    coupon: &tenant::entities::coupons::Model,
+    eligible_amount: Decimal,
+) -> Decimal {
+    match coupon.discount_type {
+        CouponDiscountType::Percentage => {
+            let discount = (eligible_amount * coupon.value / Decimal::from(100)).round_dp(2);
+            coupon
+                .max_discount
+                .map_or(discount, |max_discount| discount.min(max_discount))
+        }
+        CouponDiscountType::Fixed => coupon.value.min(eligible_amount),
+    }
+}
+
+pub fn applied_coupons_json(applied: &[AppliedCoupon]) -> Value {
+    json!(
+        applied
+            .iter()
+            .map(|coupon| json!({
+                "code": coupon.coupon.code,
+                "description": coupon.coupon.description,
+                "eligible_amount": coupon.eligible_amount,
+                "discount_amount": coupon.discount_amount,
+            }))
+            .collect::<Vec<_>>()
+    )
+}
+
+pub async fn find_coupon(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+) -> Result<tenant::entities::coupons::Model, ApiResponse> {
+    tenant::entities::coupons::Entity::find_by_pid(pid)
+        .filter(tenant::entities::coupons::Column::DeletedAt.is_null())
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch coupon {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch coupon" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Coupon not found" })))
+}
+
+pub fn coupon_json(coupon: &tenant::entities::coupons::Model) -> Value {
+    json!({
+        "pid": coupon.pid,
+        "code": coupon.code,
+        "description": coupon.description,
+        "discount_type": coupon.discount_type,
+        "value": coupon.value,
+        "max_discount": coupon.max_discount,
+        "scope": coupon.scope,
+        "targets": coupon.targets,
+        "min_order_value": coupon.min_order_value,
+        "max_redemptions": coupon.max_redemptions,
+        "max_redemptions_per_patient": coupon.max_redemptions_per_patient,
+        "starts_at": coupon.starts_at,
+        "ends_at": coupon.ends_at,
+        "is_stackable": coupon.is_stackable,
+        "is_active": coupon.is_active,
+        "created_at": coupon.created_at,
+        "updated_at": coupon.updated_at,
+    })
+}
+
+pub fn redemption_json(
+    redemption: &tenant::entities::coupon_redemptions::Model,
+    coupon: Option<&tenant::entities::coupons::Model>,
+) -> Value {
+    json!({
+        "pid": redemption.pid,
+        "coupon": coupon.map(|coupon| json!({
+            "pid": coupon.pid,
+            "code": coupon.code,
+        })),
+        "patient_pid": redemption.patient_pid,
+        "source_type": redemption.source_type,
+        "source_pid": redemption.source_pid,
+        "order_value": redemption.order_value,
+        "eligible_amount": redemption.eligible_amount,
+        "discount_amount": redemption.discount_amount,
+        "voided_at": redemption.voided_at,
+        "created_at": redemption.created_at,
+    })
+}
+
+fn coupon_error(message: String) -> ApiResponse {
+    ApiResponse::new(
+        400,
+        json!(ValidationError {
+            errors: HashMap::from([("coupon_codes".to_string(), message)]),
+        }),
+    )
+}
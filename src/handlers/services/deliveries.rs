@@ -0,0 +1,321 @@
+use std::collections::HashMap;
+
+use chrono::{Duration, NaiveDateTime, Utc};
+use rust_decimal::{
+    Decimal,
+    prelude::{FromPrimitive, ToPrimitive},
+};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        main,
+        tenant::{
+            self,
+            entities::sea_orm_active_enums::DeliveryStatus,
+            migrations::sea_orm::{
+                ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
+                QueryFilter, QueryOrder, Set,
+            },
+        },
+    },
+    utils::{
+        api_response::ApiResponse, constants::DELIVERY_AVERAGE_SPEED_KMH,
+        validator_error::ValidationError,
+    },
+};
+
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// Tries a rider gets at the delivery code before it stops being
+/// accepted and the handover has to be proven with a photo.
+pub const MAX_OTP_ATTEMPTS: i32 = 5;
+
+/// What delivery to a location costs and how long it takes. Facilities
+/// without zones deliver free, wherever the patient is.
+#[derive(Debug, Clone)]
+pub struct DeliveryQuote {
+    pub zone: Option<tenant::entities::delivery_zones::Model>,
+    pub distance_km: Option<Decimal>,
+    pub fee: Decimal,
+    pub estimated_minutes: Option<i32>,
+}
+
+/// Great-circle distance between two points given as (latitude, longitude).
+pub fn distance_km(from: (f64, f64), to: (f64, f64)) -> f64 {
+    let (lat1, lon1) = (from.0.to_radians(), from.1.to_radians());
+    let (lat2, lon2) = (to.0.to_radians(), to.1.to_radians());
+
+    let a = ((lat2 - lat1) / 2.0).sin().powi(2)
+        + lat1.cos() * lat2.cos() * ((lon2 - lon1) / 2.0).sin().powi(2);
+
+    2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+}
+
+/// Prices delivery from the facility to the patient's pin by the zone the
+/// straight-line distance falls in.
+pub async fn delivery_quote(
+    tenant_db: &DatabaseConnection,
+    facility: &main::entities::tenants::Model,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+) -> Result<DeliveryQuote, ApiResponse> {
+    let zones = tenant::entities::delivery_zones::Entity::find()
+        .filter(tenant::entities::delivery_zones::Column::DeletedAt.is_null())
+        .filter(tenant::entities::delivery_zones::Column::IsActive.eq(true))
+        .order_by_asc(tenant::entities::delivery_zones::Column::MinDistanceKm)
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch delivery zones: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to price delivery" }))
+        })?;
+
+    let facility_location = facility
+        .latitude
+        .and_then(|lat| lat.to_f64())
+        .zip(facility.longitude.and_then(|lng| lng.to_f64()));
+    let distance = facility_location
+        .zip(latitude.zip(longitude))
+        .map(|(from, to)| distance_km(from, to));
+
+    if zones.is_empty() {
+        return Ok(DeliveryQuote {
+            zone: None,
+            distance_km: distance.and_then(|d| Decimal::from_f64(d).map(|d| d.round_dp(2))),
+            fee: Decimal::ZERO,
+            estimated_minutes: None,
+        });
+    }
+
+    if latitude.is_none() || longitude.is_none() {
+        return Err(ApiResponse::new(
+            400,
+            json!(ValidationError {
+                errors: HashMap::from([(
+                    "delivery_location".to_string(),
+                    "Pick the delivery location on the map".to_string(),
+                )]),
+            }),
+        ));
+    }
+
+    let Some(distance) = distance else {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "The facility has not set its location, so delivery cannot be priced" }),
+        ));
+    };
+
+    let distance = Decimal::from_f64(distance).unwrap_or_default().round_dp(2);
+    let zone = zones
+        .into_iter()
+        .find(|zone| zone.min_distance_km <= distance && distance <= zone.max_distance_km)
+        .ok_or_else(|| {
+            ApiResponse::new(
+                409,
+                json!({
+                    "message": "We do not deliver to this location",
+                    "distance_km": distance,
+                }),
+            )
+        })?;
+
+    Ok(DeliveryQuote {
+        fee: zone.fee,
+        estimated_minutes: Some(zone.estimated_minutes),
+        distance_km: Some(distance),
+        zone: Some(zone),
+    })
+}
+
+/// A six-digit code the patient gives the rider to confirm delivery.
+pub fn delivery_otp() -> String {
+    format!("{:06}", Uuid::new_v4().as_u128() % 1_000_000)
+}
+
+/// When the rider should arrive. On the road, the rider's last reported
+/// position and an average speed are used; without a position, the zone's
+/// estimate from pickup.
+pub fn delivery_eta(
+    delivery: &tenant::entities::deliveries::Model,
+    rider: Option<&tenant::entities::delivery_riders::Model>,
+) -> Option<NaiveDateTime> {
+    if delivery.status != DeliveryStatus::PickedUp {
+        return None;
+    }
+
+    let destination = delivery
+        .latitude
+        .and_then(|lat| lat.to_f64())
+        .zip(delivery.longitude.and_then(|lng| lng.to_f64()));
+    let position = rider.and_then(|rider| {
+        rider
+            .last_latitude
+            .and_then(|lat| lat.to_f64())
+            .zip(rider.last_longitude.and_then(|lng| lng.to_f64()))
+    });
+
+    match (position, destination) {
+        (Some(position), Some(destination)) => {
+            let minutes = distance_km(position, destination) / *DELIVERY_AVERAGE_SPEED_KMH * 60.0;
+            Some(Utc::now().naive_utc() + Duration::minutes(minutes.ceil() as i64))
+        }
+        _ => delivery
+            .picked_up_at
+            .zip(delivery.estimated_minutes)
+            .map(|(picked_up_at, minutes)| picked_up_at + Duration::minutes(minutes as i64)),
+    }
+}
+
+pub async fn find_order_delivery<C: ConnectionTrait>(
+    db: &C,
+    order_id: i32,
+) -> Result<Option<tenant::entities::deliveries::Model>, ApiResponse> {
+    tenant::entities::deliveries::Entity::find()
+        .filter(tenant::entities::deliveries::Column::OrderId.eq(order_id))
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch delivery of order {}: {}", order_id, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch delivery" }))
+        })
+}
+
+/// Stops the delivery of a cancelled order so it drops off the rider's
+/// list.
+pub async fn cancel_order_delivery<C: ConnectionTrait>(
+    db: &C,
+    order_id: i32,
+) -> Result<(), ApiResponse> {
+    let Some(delivery) = find_order_delivery(db, order_id).await? else {
+        return Ok(());
+    };
+
+    if !matches!(
+        delivery.status,
+        DeliveryStatus::Pending | DeliveryStatus::Assigned
+    ) {
+        return Ok(());
+    }
+
+    let mut active_model: tenant::entities::deliveries::ActiveModel = delivery.into();
+    active_model.status = Set(DeliveryStatus::Failed);
+    active_model.failed_at = Set(Some(Utc::now().naive_utc()));
+    active_model.failure_reason = Set(Some("Order cancelled".to_string()));
+    active_model.updated_at = Set(Utc::now().naive_utc());
+    active_model.update(db).await.map_err(|err| {
+        log::error!("Failed to cancel delivery of order {}: {}", order_id, err);
+        ApiResponse::new(500, json!({ "message": "Failed to cancel delivery" }))
+    })?;
+
+    Ok(())
+}
+
+pub async fn find_delivery(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+) -> Result<tenant::entities::deliveries::Model, ApiResponse> {
+    tenant::entities::deliveries::Entity::find_by_pid(pid)
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch delivery {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch delivery" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Delivery not found" })))
+}
+
+pub async fn find_rider(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+) -> Result<tenant::entities::delivery_riders::Model, ApiResponse> {
+    tenant::entities::delivery_riders::Entity::find_by_pid(pid)
+        .filter(tenant::entities::delivery_riders::Column::DeletedAt.is_null())
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch rider {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch rider" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Rider not found" })))
+}
+
+pub async fn find_zone(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+) -> Result<tenant::entities::delivery_zones::Model, ApiResponse> {
+    tenant::entities::delivery_zones::Entity::find_by_pid(pid)
+        .filter(tenant::entities::delivery_zones::Column::DeletedAt.is_null())
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch delivery zone {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch delivery zone" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Delivery zone not found" })))
+}
+
+pub fn zone_json(zone: &tenant::entities::delivery_zones::Model) -> Value {
+    json!({
+        "pid": zone.pid,
+        "name": zone.name,
+        "min_distance_km": zone.min_distance_km,
+        "max_distance_km": zone.max_distance_km,
+        "fee": zone.fee,
+        "estimated_minutes": zone.estimated_minutes,
+        "is_active": zone.is_active,
+        "created_at": zone.created_at,
+        "updated_at": zone.updated_at,
+    })
+}
+
+pub fn rider_json(rider: &tenant::entities::delivery_riders::Model) -> Value {
+    json!({
+        "pid": rider.pid,
+        "sso_user_id": rider.sso_user_id,
+        "name": rider.name,
+        "phone": rider.phone,
+        "vehicle": rider.vehicle,
+        "is_active": rider.is_active,
+        "last_latitude": rider.last_latitude,
+        "last_longitude": rider.last_longitude,
+        "last_location_at": rider.last_location_at,
+        "created_at": rider.created_at,
+        "updated_at": rider.updated_at,
+    })
+}
+
+/// The delivery for staff and riders. The OTP is left out; only the patient
+/// sees it.
+pub fn delivery_json(
+    delivery: &tenant::entities::deliveries::Model,
+    rider: Option<&tenant::entities::delivery_riders::Model>,
+) -> Value {
+    json!({
+        "pid": delivery.pid,
+        "status": delivery.status,
+        "rider": rider.map(|rider| json!({
+            "pid": rider.pid,
+            "name": rider.name,
+            "phone": rider.phone,
+            "vehicle": rider.vehicle,
+        })),
+        "latitude": delivery.latitude,
+        "longitude": delivery.longitude,
+        "distance_km": delivery.distance_km,
+        "fee": delivery.fee,
+        "estimated_minutes": delivery.estimated_minutes,
+        "eta": delivery_eta(delivery, rider),
+        "assigned_at": delivery.assigned_at,
+        "picked_up_at": delivery.picked_up_at,
+        "delivered_at": delivery.delivered_at,
+        "proof_method": delivery.proof_method,
+        "recipient_name": delivery.recipient_name,
+        "failed_at": delivery.failed_at,
+        "failure_reason": delivery.failure_reason,
+        "created_at": delivery.created_at,
+        "updated_at": delivery.updated_at,
+    })
+}
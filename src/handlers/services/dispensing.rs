@@ -0,0 +1,141 @@
+use chrono::NaiveDateTime;
+use serde_json::{Value, json};
+
+use crate::{
+    db::{main, tenant},
+    handlers::services::{
+        pharmacy::{batch_json, product_label},
+        prescriptions::dosage_instructions,
+    },
+    utils::html::escape_html,
+};
+
+/// One dispensed medicine as it appears on its label.
+pub struct DispenseLabel<'a> {
+    pub item: &'a tenant::entities::prescription_items::Model,
+    pub product: &'a tenant::entities::pharmacy_products::Model,
+    pub batches: Vec<&'a tenant::entities::stock_batches::Model>,
+    pub quantity: i32,
+}
+
+pub fn dispense_item_json(
+    item: &tenant::entities::dispense_items::Model,
+    product: Option<&tenant::entities::pharmacy_products::Model>,
+    batch: Option<&tenant::entities::stock_batches::Model>,
+) -> Value {
+    json!({
+        "pid": item.pid,
+        "product": product.map(|product| json!({
+            "pid": product.pid,
+            "label": product_label(product),
+            "unit": product.unit,
+        })),
+        "batch": batch.map(|batch| batch_json(batch, None)),
+        "quantity": item.quantity,
+        "unit_price": item.unit_price,
+        "is_substitution": item.is_substitution,
+        "substitution_reason": item.substitution_reason,
+        "created_at": item.created_at,
+    })
+}
+
+pub fn dispense_json(dispense: &tenant::entities::dispenses::Model, items: Vec<Value>) -> Value {
+    json!({
+        "pid": dispense.pid,
+        "patient_pid": dispense.patient_pid,
+        "dispensed_by": dispense.dispensed_by,
+        "notes": dispense.notes,
+        "items": items,
+        "has_labels": dispense.label_document_file_pid.is_some(),
+        "dispensed_at": dispense.dispensed_at,
+    })
+}
+
+pub fn dispense_label_json(label: &DispenseLabel) -> Value {
+    json!({
+        "medicine": product_label(label.product),
+        "quantity": label.quantity,
+        "unit": label.product.unit,
+        "directions": dosage_instructions(label.item),
+        "instructions": label.item.instructions,
+        "batches": label
+            .batches
+            .iter()
+            .map(|batch| json!({
+                "batch_number": batch.batch_number,
+                "expiry_date": batch.expiry_date,
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+/// A sheet of medicine labels, one per dispensed item.
+pub fn dispense_labels_html(
+    facility_name: &str,
+    patient: &main::entities::patients::Model,
+    dispensed_at: NaiveDateTime,
+    labels: &[DispenseLabel],
+) -> String {
+    let full_name = [
+        &patient.first_name,
+        &patient.middle_name,
+        &patient.last_name,
+    ]
+    .iter()
+    .filter_map(|name| name.as_deref())
+    .collect::<Vec<_>>()
+    .join(" ");
+
+    let mut cards = String::new();
+    for label in labels {
+        let batches = label
+            .batches
+            .iter()
+            .map(|batch| {
+                format!(
+                    "{} (exp {})",
+                    escape_html(&batch.batch_number),
+                    batch.expiry_date.format("%m/%Y")
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        cards.push_str(&format!(
+            r#"<div style="border: 1px solid #333; border-radius: 6px; padding: 12px; margin-bottom: 12px; width: 420px;">
+                <div style="font-size: 12px; color: #666;">{} &middot; {}</div>
+                <div style="font-size: 16px; font-weight: bold; margin: 6px 0;">{}</div>
+                <div style="font-size: 14px;">Qty: {} {}</div>
+                <div style="font-size: 15px; margin: 8px 0;"><strong>{}</strong></div>
+                <div style="font-size: 13px;">{}</div>
+                <div style="font-size: 12px; margin-top: 8px;">Patient: {}</div>
+                <div style="font-size: 11px; color: #666;">Batch: {}</div>
+            </div>"#,
+            escape_html(facility_name),
+            dispensed_at.format("%d %b %Y"),
+            escape_html(&product_label(label.product)),
+            label.quantity,
+            escape_html(&label.product.unit),
+            escape_html(&dosage_instructions(label.item)),
+            escape_html(label.item.instructions.as_deref().unwrap_or("")),
+            escape_html(&full_name),
+            batches,
+        ));
+    }
+
+    format!(
+        r#"
+        <!doctype html>
+        <html>
+            <head>
+                <meta charset="utf-8" />
+                <title>Medicine Labels</title>
+            </head>
+            <body style="font-family: Arial, sans-serif; color: #222; padding: 16px;">
+                {}
+            </body>
+        </html>
+        "#,
+        cards
+    )
+}
@@ -0,0 +1,56 @@
+use serde_json::json;
+
+use crate::{
+    db::main::{
+        self,
+        migrations::sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter},
+    },
+    utils::api_response::ApiResponse,
+};
+
+/// Whether a facility has a module switched on in `tenant_features`.
+pub async fn tenant_has_feature(
+    db: &DatabaseConnection,
+    tenant_id: i32,
+    code: &str,
+) -> Result<bool, ApiResponse> {
+    let feature = main::entities::tenant_features::Entity::find()
+        .inner_join(main::entities::features::Entity)
+        .filter(main::entities::tenant_features::Column::TenantId.eq(tenant_id))
+        .filter(main::entities::tenant_features::Column::IsEnabled.eq(true))
+        .filter(main::entities::tenant_features::Column::DeletedAt.is_null())
+        .filter(main::entities::features::Column::Code.eq(code))
+        .filter(main::entities::features::Column::IsActive.eq(true))
+        .filter(main::entities::features::Column::DeletedAt.is_null())
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to check feature {} for tenant {}: {}",
+                code,
+                tenant_id,
+                err
+            );
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to check facility features" }),
+            )
+        })?;
+
+    Ok(feature.is_some())
+}
+
+pub async fn require_tenant_feature(
+    db: &DatabaseConnection,
+    tenant_id: i32,
+    code: &str,
+) -> Result<(), ApiResponse> {
+    if tenant_has_feature(db, tenant_id, code).await? {
+        Ok(())
+    } else {
+        Err(ApiResponse::new(
+            403,
+            json!({ "message": "This feature is not enabled for the facility" }),
+        ))
+    }
+}
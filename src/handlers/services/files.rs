@@ -0,0 +1,179 @@
+use actix_web::HttpRequest;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    db::main::{
+        self,
+        entities::sea_orm_active_enums::FileVisibility,
+        migrations::sea_orm::{
+            ActiveModelTrait, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QuerySelect,
+            Set,
+        },
+    },
+    utils::{
+        api_response::ApiResponse,
+        app_state::AppState,
+        jwt::{Claims, get_logged_in_user_claims},
+        multipart::get_presigned_url,
+        permission::has_permission,
+    },
+};
+
+/// Presigns a download link for the registered file `pid` once the caller is
+/// allowed to read it. Every attempt, granted or refused, is access-logged.
+pub async fn authorized_file_url(
+    app_state: &AppState,
+    req: &HttpRequest,
+    pid: Uuid,
+    expiry_secs: u64,
+) -> Result<(String, main::entities::files::Model), ApiResponse> {
+    let claims = get_logged_in_user_claims(req)?;
+
+    let file = main::entities::files::Entity::find_by_pid(pid)
+        .filter(main::entities::files::Column::DeletedAt.is_null())
+        .one(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch file {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch file" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "File not found" })))?;
+
+    let access = authorize_file_access(app_state, req, &claims, &file).await?;
+
+    log_file_access(app_state, req, &claims, file.id, "download", &access).await?;
+
+    if let Err(reason) = access {
+        log::warn!(
+            "Denied download of file {} to user {}: {}",
+            file.pid,
+            claims.sub,
+            reason
+        );
+        return Err(ApiResponse::new(
+            403,
+            json!({ "message": "You do not have access to this file" }),
+        ));
+    }
+
+    let url = get_presigned_url(app_state, &file.storage_key, expiry_secs).await?;
+
+    Ok((url, file))
+}
+
+/// Decides whether the caller may read `file`. `Ok` carries the rule that
+/// granted access and `Err` the reason it was refused; both end up in the
+/// access log.
+async fn authorize_file_access(
+    app_state: &AppState,
+    req: &HttpRequest,
+    claims: &Claims,
+    file: &main::entities::files::Model,
+) -> Result<Result<&'static str, &'static str>, ApiResponse> {
+    if file.owner_sso_user_id == Some(claims.sub) {
+        return Ok(Ok("owner"));
+    }
+
+    if let Some(patient_id) = file.patient_id {
+        let patient_sso_user_id = main::entities::patients::Entity::find_by_id(patient_id)
+            .select_only()
+            .column(main::entities::patients::Column::SsoUserId)
+            .into_tuple::<Option<Uuid>>()
+            .one(&app_state.main_db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to fetch file patient: {}", err);
+                ApiResponse::new(500, json!({ "message": "Failed to fetch file" }))
+            })?
+            .flatten();
+
+        if patient_sso_user_id == Some(claims.sub) {
+            return Ok(Ok("patient"));
+        }
+    }
+
+    if file.visibility == FileVisibility::Public {
+        return Ok(Ok("public"));
+    }
+
+    let same_tenant = match (file.tenant_id, claims.tenant_pid) {
+        (Some(file_tenant_id), Some(tenant_pid)) => {
+            main::entities::tenants::Entity::find()
+                .filter(main::entities::tenants::Column::Id.eq(file_tenant_id))
+                .filter(main::entities::tenants::Column::Pid.eq(tenant_pid))
+                .count(&app_state.main_db)
+                .await
+                .map_err(|err| {
+                    log::error!("Failed to fetch file tenant: {}", err);
+                    ApiResponse::new(500, json!({ "message": "Failed to fetch file" }))
+                })?
+                > 0
+        }
+        _ => false,
+    };
+
+    if same_tenant {
+        if file.visibility == FileVisibility::Tenant {
+            return Ok(Ok("tenant"));
+        }
+
+        if has_permission("download_tenant_files", req).await? {
+            return Ok(Ok("tenant_permission"));
+        }
+    }
+
+    if has_permission("download_any_file", req).await? {
+        return Ok(Ok("global_permission"));
+    }
+
+    Ok(Err(if same_tenant {
+        "missing download_tenant_files permission"
+    } else {
+        "not owner, patient or tenant member"
+    }))
+}
+
+async fn log_file_access(
+    app_state: &AppState,
+    req: &HttpRequest,
+    claims: &Claims,
+    file_id: i32,
+    action: &str,
+    access: &Result<&'static str, &'static str>,
+) -> Result<(), ApiResponse> {
+    let ip_address = req
+        .connection_info()
+        .realip_remote_addr()
+        .map(|ip| ip.to_string());
+    let user_agent = req
+        .headers()
+        .get("User-Agent")
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_string());
+
+    let (granted, reason) = match access {
+        Ok(rule) => (true, rule),
+        Err(reason) => (false, reason),
+    };
+
+    main::entities::file_access_logs::ActiveModel {
+        file_id: Set(file_id),
+        sso_user_id: Set(Some(claims.sub)),
+        tenant_pid: Set(claims.tenant_pid),
+        action: Set(action.to_string()),
+        granted: Set(granted),
+        reason: Set(Some(reason.to_string())),
+        ip_address: Set(ip_address),
+        user_agent: Set(user_agent),
+        ..Default::default()
+    }
+    .insert(&app_state.main_db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to log file access: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to log file access" }))
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,291 @@
+use std::{collections::HashMap, sync::RwLock};
+
+use chrono::{Duration, NaiveDate, Utc};
+use serde::Serialize;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        main::{
+            self,
+            entities::sea_orm_active_enums::Gender,
+            migrations::sea_orm::{
+                ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+            },
+        },
+        tenant,
+    },
+    utils::{api_response::ApiResponse, app_state::AppState, html::escape_html},
+};
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DoseStatus {
+    Completed,
+    Upcoming,
+    Due,
+    Overdue,
+    Unknown,
+}
+
+/// Works out when a scheduled dose falls due for a child born on `dob` and
+/// where it stands on `today`. Without a date of birth nothing can be computed.
+pub fn dose_status(
+    dob: Option<NaiveDate>,
+    schedule: &main::entities::vaccine_schedules::Model,
+    today: NaiveDate,
+) -> (Option<NaiveDate>, DoseStatus) {
+    let Some(dob) = dob else {
+        return (None, DoseStatus::Unknown);
+    };
+
+    let due_date = dob + Duration::days(schedule.recommended_age_days as i64);
+    let overdue_date = due_date + Duration::days(schedule.overdue_after_days as i64);
+
+    let status = if today < due_date {
+        DoseStatus::Upcoming
+    } else if today <= overdue_date {
+        DoseStatus::Due
+    } else {
+        DoseStatus::Overdue
+    };
+
+    (Some(due_date), status)
+}
+
+pub fn record_matches_schedule(
+    record: &tenant::entities::immunization_records::Model,
+    schedule: &main::entities::vaccine_schedules::Model,
+) -> bool {
+    record.schedule_pid == Some(schedule.pid)
+        || (record
+            .vaccine_code
+            .eq_ignore_ascii_case(&schedule.vaccine_code)
+            && record.dose_number == schedule.dose_number)
+}
+
+pub async fn fetch_active_vaccine_schedules(
+    db: &DatabaseConnection,
+    gender: Option<&Gender>,
+) -> Result<Vec<main::entities::vaccine_schedules::Model>, ApiResponse> {
+    let mut applies_to =
+        Condition::any().add(main::entities::vaccine_schedules::Column::Gender.is_null());
+    if let Some(gender) = gender {
+        applies_to =
+            applies_to.add(main::entities::vaccine_schedules::Column::Gender.eq(gender.clone()));
+    }
+
+    main::entities::vaccine_schedules::Entity::find()
+        .filter(main::entities::vaccine_schedules::Column::IsActive.eq(true))
+        .filter(main::entities::vaccine_schedules::Column::DeletedAt.is_null())
+        .filter(applies_to)
+        .order_by_asc(main::entities::vaccine_schedules::Column::RecommendedAgeDays)
+        .order_by_asc(main::entities::vaccine_schedules::Column::VaccineCode)
+        .order_by_asc(main::entities::vaccine_schedules::Column::DoseNumber)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch vaccine schedules: {}", err);
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to fetch vaccine schedules" }),
+            )
+        })
+}
+
+/// Collects a patient's immunization records from every facility database so
+/// the history follows the patient rather than the facility that gave the dose.
+pub async fn fetch_patient_immunization_records(
+    tenant_dbs: &RwLock<HashMap<Uuid, tenant::migrations::sea_orm::DatabaseConnection>>,
+    patient_pid: Uuid,
+) -> Result<Vec<tenant::entities::immunization_records::Model>, ApiResponse> {
+    let tenant_dbs: Vec<(Uuid, tenant::migrations::sea_orm::DatabaseConnection)> = tenant_dbs
+        .read()
+        .unwrap()
+        .iter()
+        .map(|(tenant_id, db)| (*tenant_id, db.clone()))
+        .collect();
+
+    let mut records = Vec::new();
+
+    for (tenant_id, tenant_db) in tenant_dbs {
+        match tenant::entities::immunization_records::Entity::find()
+            .filter(tenant::entities::immunization_records::Column::PatientPid.eq(patient_pid))
+            .filter(tenant::entities::immunization_records::Column::DeletedAt.is_null())
+            .all(&tenant_db)
+            .await
+        {
+            Ok(mut tenant_records) => records.append(&mut tenant_records),
+            Err(err) => {
+                log::error!(
+                    "Failed to fetch immunization records for patient {} from tenant {}: {}",
+                    patient_pid,
+                    tenant_id,
+                    err
+                );
+            }
+        }
+    }
+
+    records.sort_by_key(|record| record.administered_at);
+
+    Ok(records)
+}
+
+pub fn immunization_record_json(record: &tenant::entities::immunization_records::Model) -> Value {
+    json!({
+        "pid": record.pid,
+        "patient_pid": record.patient_pid,
+        "schedule_pid": record.schedule_pid,
+        "vaccine_code": record.vaccine_code,
+        "vaccine_name": record.vaccine_name,
+        "dose_number": record.dose_number,
+        "lot_number": record.lot_number,
+        "site": record.site,
+        "administered_at": record.administered_at,
+        "administering_facility": record.administering_facility,
+        "notes": record.notes,
+        "created_at": record.created_at,
+        "updated_at": record.updated_at,
+    })
+}
+
+/// Builds the patient's immunization card: every dose given anywhere plus the
+/// national schedule with due and overdue doses worked out from `dob`.
+pub async fn build_immunization_summary(
+    app_state: &AppState,
+    patient: &main::entities::patients::Model,
+) -> Result<Value, ApiResponse> {
+    let records = fetch_patient_immunization_records(&app_state.tenant_dbs, patient.pid).await?;
+    let schedules =
+        fetch_active_vaccine_schedules(&app_state.main_db, patient.gender.as_ref()).await?;
+    let today = Utc::now().date_naive();
+
+    let mut due = 0;
+    let mut overdue = 0;
+
+    let schedule = schedules
+        .iter()
+        .map(|schedule| {
+            let (due_date, mut status) = dose_status(patient.dob, schedule, today);
+            let record = records
+                .iter()
+                .find(|record| record_matches_schedule(record, schedule));
+
+            if record.is_some() {
+                status = DoseStatus::Completed;
+            }
+
+            match status {
+                DoseStatus::Due => due += 1,
+                DoseStatus::Overdue => overdue += 1,
+                _ => {}
+            }
+
+            json!({
+                "schedule_pid": schedule.pid,
+                "vaccine_code": schedule.vaccine_code,
+                "vaccine_name": schedule.vaccine_name,
+                "dose_number": schedule.dose_number,
+                "due_date": due_date,
+                "status": status,
+                "record_pid": record.map(|r| r.pid),
+                "administered_at": record.map(|r| r.administered_at),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    Ok(json!({
+        "patient": {
+            "pid": patient.pid,
+            "first_name": patient.first_name,
+            "last_name": patient.last_name,
+            "dob": patient.dob,
+        },
+        "records": records.iter().map(immunization_record_json).collect::<Vec<_>>(),
+        "schedule": schedule,
+        "due_count": due,
+        "overdue_count": overdue,
+    }))
+}
+
+pub fn immunization_certificate_html(
+    patient: &main::entities::patients::Model,
+    records: &[tenant::entities::immunization_records::Model],
+) -> String {
+    let full_name = [
+        &patient.first_name,
+        &patient.middle_name,
+        &patient.last_name,
+    ]
+    .iter()
+    .filter_map(|name| name.as_deref())
+    .collect::<Vec<_>>()
+    .join(" ");
+
+    let mut rows = String::new();
+    for record in records {
+        rows.push_str(&format!(
+            r#"<tr>
+                <td style="padding: 8px; border: 1px solid #ddd;">{}</td>
+                <td style="padding: 8px; border: 1px solid #ddd; text-align:center;">{}</td>
+                <td style="padding: 8px; border: 1px solid #ddd;">{}</td>
+                <td style="padding: 8px; border: 1px solid #ddd;">{}</td>
+                <td style="padding: 8px; border: 1px solid #ddd;">{}</td>
+            </tr>"#,
+            escape_html(&record.vaccine_name),
+            record.dose_number,
+            record.administered_at.format("%d %b %Y"),
+            escape_html(record.lot_number.as_deref().unwrap_or("-")),
+            escape_html(&record.administering_facility),
+        ));
+    }
+
+    if rows.is_empty() {
+        rows.push_str(
+            r#"<tr><td colspan="5" style="padding: 8px; border: 1px solid #ddd; text-align:center;">No immunizations recorded</td></tr>"#,
+        );
+    }
+
+    format!(
+        r#"
+        <!doctype html>
+        <html>
+            <head>
+                <meta charset="utf-8" />
+                <title>Immunization Certificate</title>
+            </head>
+            <body style="font-family: Arial, sans-serif; color: #333; padding: 24px;">
+                <h2 style="text-align:center; margin-bottom: 4px;">Immunization Certificate</h2>
+                <p style="text-align:center; margin-top: 0; color: #666;">Issued {}</p>
+                <table style="width: 100%; margin-bottom: 16px;">
+                    <tr><td><strong>Name:</strong> {}</td><td><strong>Date of birth:</strong> {}</td></tr>
+                    <tr><td><strong>Patient ID:</strong> {}</td><td><strong>National ID:</strong> {}</td></tr>
+                </table>
+                <table style="width: 100%; border-collapse: collapse; font-size: 13px;">
+                    <thead>
+                        <tr style="background: #f5f5f5;">
+                            <th style="padding: 8px; border: 1px solid #ddd; text-align:left;">Vaccine</th>
+                            <th style="padding: 8px; border: 1px solid #ddd;">Dose</th>
+                            <th style="padding: 8px; border: 1px solid #ddd; text-align:left;">Date</th>
+                            <th style="padding: 8px; border: 1px solid #ddd; text-align:left;">Lot</th>
+                            <th style="padding: 8px; border: 1px solid #ddd; text-align:left;">Facility</th>
+                        </tr>
+                    </thead>
+                    <tbody>{}</tbody>
+                </table>
+            </body>
+        </html>
+        "#,
+        Utc::now().format("%d %b %Y"),
+        escape_html(&full_name),
+        patient
+            .dob
+            .map(|dob| dob.format("%d %b %Y").to_string())
+            .unwrap_or_else(|| "-".to_string()),
+        patient.pid,
+        escape_html(patient.national_id.as_deref().unwrap_or("-")),
+        rows,
+    )
+}
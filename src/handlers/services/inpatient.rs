@@ -0,0 +1,268 @@
+use chrono::NaiveDateTime;
+use serde_json::{Value, json};
+
+use crate::{
+    db::{
+        main,
+        tenant::{
+            self,
+            entities::sea_orm_active_enums::{BedStatus, DischargeDisposition},
+            migrations::sea_orm::{
+                ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QuerySelect, Set,
+            },
+        },
+    },
+    utils::{api_response::ApiResponse, html::escape_html},
+};
+
+/// Length of stay in calendar days between admission and discharge. Patients
+/// discharged on the day they were admitted count as one day.
+pub fn length_of_stay_days(admitted_at: NaiveDateTime, discharged_at: NaiveDateTime) -> i32 {
+    (discharged_at.date() - admitted_at.date())
+        .num_days()
+        .max(1) as i32
+}
+
+/// Locks the bed's row until the surrounding transaction ends, so concurrent
+/// admissions, transfers and discharges touching it run one after another.
+async fn lock_bed<C: ConnectionTrait>(db: &C, bed_id: i32) -> Result<(), ApiResponse> {
+    tenant::entities::beds::Entity::find_by_id(bed_id)
+        .lock_exclusive()
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to lock bed {}: {}", bed_id, err);
+            ApiResponse::new(500, json!({ "message": "Failed to update bed" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Bed not found" })))?;
+
+    Ok(())
+}
+
+/// Marks a free bed as occupied. Must run in a transaction: the bed is locked
+/// and the status check is part of the update, so two admissions racing for
+/// the same bed cannot both win.
+pub async fn claim_bed<C: ConnectionTrait>(db: &C, bed_id: i32) -> Result<(), ApiResponse> {
+    lock_bed(db, bed_id).await?;
+
+    let result = tenant::entities::beds::Entity::update_many()
+        .set(tenant::entities::beds::ActiveModel {
+            status: Set(BedStatus::Occupied),
+            updated_at: Set(chrono::Utc::now().naive_utc()),
+            ..Default::default()
+        })
+        .filter(tenant::entities::beds::Column::Id.eq(bed_id))
+        .filter(tenant::entities::beds::Column::Status.eq(BedStatus::Free))
+        .filter(tenant::entities::beds::Column::IsActive.eq(true))
+        .filter(tenant::entities::beds::Column::DeletedAt.is_null())
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to claim bed {}: {}", bed_id, err);
+            ApiResponse::new(500, json!({ "message": "Failed to assign bed" }))
+        })?;
+
+    if result.rows_affected == 0 {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Bed is not available" }),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Sends an occupied bed to cleaning once its patient has left it. Must run in
+/// the same transaction as the admission change that moved the patient out.
+pub async fn vacate_bed<C: ConnectionTrait>(db: &C, bed_id: i32) -> Result<(), ApiResponse> {
+    lock_bed(db, bed_id).await?;
+
+    let result = tenant::entities::beds::Entity::update_many()
+        .set(tenant::entities::beds::ActiveModel {
+            status: Set(BedStatus::Cleaning),
+            updated_at: Set(chrono::Utc::now().naive_utc()),
+            ..Default::default()
+        })
+        .filter(tenant::entities::beds::Column::Id.eq(bed_id))
+        .filter(tenant::entities::beds::Column::Status.eq(BedStatus::Occupied))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to vacate bed {}: {}", bed_id, err);
+            ApiResponse::new(500, json!({ "message": "Failed to update bed" }))
+        })?;
+
+    if result.rows_affected == 0 {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Bed is not occupied" }),
+        ));
+    }
+
+    Ok(())
+}
+
+pub fn ward_json(ward: &tenant::entities::wards::Model) -> Value {
+    json!({
+        "pid": ward.pid,
+        "name": ward.name,
+        "ward_type": ward.ward_type,
+        "description": ward.description,
+        "is_active": ward.is_active,
+        "created_at": ward.created_at,
+        "updated_at": ward.updated_at,
+    })
+}
+
+pub fn bed_json(bed: &tenant::entities::beds::Model) -> Value {
+    json!({
+        "pid": bed.pid,
+        "bed_number": bed.bed_number,
+        "status": bed.status,
+        "is_active": bed.is_active,
+        "created_at": bed.created_at,
+        "updated_at": bed.updated_at,
+    })
+}
+
+pub fn admission_json(
+    admission: &tenant::entities::admissions::Model,
+    ward: Option<&tenant::entities::wards::Model>,
+    bed: Option<&tenant::entities::beds::Model>,
+) -> Value {
+    json!({
+        "pid": admission.pid,
+        "patient_pid": admission.patient_pid,
+        "encounter_pid": admission.encounter_pid,
+        "ward": ward.map(|ward| json!({ "pid": ward.pid, "name": ward.name })),
+        "bed": bed.map(|bed| json!({ "pid": bed.pid, "bed_number": bed.bed_number })),
+        "status": admission.status,
+        "admission_reason": admission.admission_reason,
+        "admitting_diagnosis": admission.admitting_diagnosis,
+        "admitted_at": admission.admitted_at,
+        "admitted_by": admission.admitted_by,
+        "discharged_at": admission.discharged_at,
+        "discharged_by": admission.discharged_by,
+        "discharge_disposition": admission.discharge_disposition,
+        "discharge_diagnosis": admission.discharge_diagnosis,
+        "discharge_summary": admission.discharge_summary,
+        "discharge_instructions": admission.discharge_instructions,
+        "has_discharge_document": admission.discharge_document_file_pid.is_some(),
+        "length_of_stay_days": admission.length_of_stay_days,
+        "created_at": admission.created_at,
+        "updated_at": admission.updated_at,
+    })
+}
+
+pub fn bed_transfer_json(
+    transfer: &tenant::entities::bed_transfers::Model,
+    from_bed: Option<&str>,
+    to_bed: Option<&str>,
+) -> Value {
+    json!({
+        "pid": transfer.pid,
+        "from_bed": from_bed,
+        "to_bed": to_bed,
+        "reason": transfer.reason,
+        "transferred_by": transfer.transferred_by,
+        "transferred_at": transfer.transferred_at,
+    })
+}
+
+/// Builds the printable discharge summary. `movements` lists each bed the
+/// patient occupied as `(ward name, bed number, moved in at)`, oldest first.
+pub fn discharge_summary_html(
+    facility_name: &str,
+    patient: &main::entities::patients::Model,
+    admission: &tenant::entities::admissions::Model,
+    movements: &[(String, String, NaiveDateTime)],
+) -> String {
+    let full_name = [
+        &patient.first_name,
+        &patient.middle_name,
+        &patient.last_name,
+    ]
+    .iter()
+    .filter_map(|name| name.as_deref())
+    .collect::<Vec<_>>()
+    .join(" ");
+
+    let mut rows = String::new();
+    for (ward, bed, moved_at) in movements {
+        rows.push_str(&format!(
+            r#"<tr>
+                <td style="padding: 8px; border: 1px solid #ddd;">{}</td>
+                <td style="padding: 8px; border: 1px solid #ddd;">{}</td>
+                <td style="padding: 8px; border: 1px solid #ddd;">{}</td>
+            </tr>"#,
+            moved_at.format("%d %b %Y %H:%M"),
+            escape_html(ward),
+            escape_html(bed),
+        ));
+    }
+
+    let disposition = match admission.discharge_disposition {
+        Some(DischargeDisposition::Home) => "Discharged home",
+        Some(DischargeDisposition::Referred) => "Referred",
+        Some(DischargeDisposition::AgainstMedicalAdvice) => "Left against medical advice",
+        Some(DischargeDisposition::Absconded) => "Absconded",
+        Some(DischargeDisposition::Deceased) => "Deceased",
+        None => "-",
+    };
+
+    format!(
+        r#"
+        <!doctype html>
+        <html>
+            <head>
+                <meta charset="utf-8" />
+                <title>Discharge Summary</title>
+            </head>
+            <body style="font-family: Arial, sans-serif; color: #333; padding: 24px;">
+                <h2 style="text-align:center; margin-bottom: 4px;">Discharge Summary</h2>
+                <p style="text-align:center; margin-top: 0; color: #666;">{}</p>
+                <table style="width: 100%; margin-bottom: 16px;">
+                    <tr><td><strong>Name:</strong> {}</td><td><strong>Patient ID:</strong> {}</td></tr>
+                    <tr><td><strong>Admitted:</strong> {}</td><td><strong>Discharged:</strong> {}</td></tr>
+                    <tr><td><strong>Length of stay:</strong> {} day(s)</td><td><strong>Disposition:</strong> {}</td></tr>
+                </table>
+                <h4>Reason for admission</h4>
+                <p>{}</p>
+                <h4>Diagnosis</h4>
+                <p>Admitting: {}<br />Discharge: {}</p>
+                <h4>Ward stay</h4>
+                <table style="width: 100%; border-collapse: collapse; font-size: 13px;">
+                    <thead>
+                        <tr style="background: #f5f5f5;">
+                            <th style="padding: 8px; border: 1px solid #ddd; text-align:left;">From</th>
+                            <th style="padding: 8px; border: 1px solid #ddd; text-align:left;">Ward</th>
+                            <th style="padding: 8px; border: 1px solid #ddd; text-align:left;">Bed</th>
+                        </tr>
+                    </thead>
+                    <tbody>{}</tbody>
+                </table>
+                <h4>Hospital course</h4>
+                <p>{}</p>
+                <h4>Instructions on discharge</h4>
+                <p>{}</p>
+            </body>
+        </html>
+        "#,
+        escape_html(facility_name),
+        escape_html(&full_name),
+        patient.pid,
+        admission.admitted_at.format("%d %b %Y %H:%M"),
+        admission
+            .discharged_at
+            .map(|at| at.format("%d %b %Y %H:%M").to_string())
+            .unwrap_or_else(|| "-".to_string()),
+        admission.length_of_stay_days.unwrap_or_default(),
+        disposition,
+        escape_html(&admission.admission_reason),
+        escape_html(admission.admitting_diagnosis.as_deref().unwrap_or("-")),
+        escape_html(admission.discharge_diagnosis.as_deref().unwrap_or("-")),
+        rows,
+        escape_html(admission.discharge_summary.as_deref().unwrap_or("-")),
+        escape_html(admission.discharge_instructions.as_deref().unwrap_or("-")),
+    )
+}
@@ -0,0 +1,600 @@
+use std::collections::HashMap;
+
+use actix_multipart::Multipart;
+use actix_web::HttpRequest;
+use chrono::{NaiveDate, Utc};
+use futures::StreamExt;
+use rust_decimal::Decimal;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        main::entities::sea_orm_active_enums::FileVisibility,
+        tenant::{
+            self,
+            entities::sea_orm_active_enums::InsuranceClaimStatus,
+            migrations::sea_orm::{
+                ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection,
+                EntityTrait, QueryFilter, QueryOrder, Set,
+            },
+        },
+    },
+    handlers::services::{
+        chronic_care::normalize_diagnosis_code, messaging::PendingAttachment,
+        patient_insurance::post_to_accumulators,
+    },
+    utils::{
+        api_response::ApiResponse,
+        app_state::AppState,
+        constants::MAX_FILE_SIZE,
+        multipart::{field_to_byte, upload_file},
+        validator_error::ValidationError,
+    },
+};
+
+/// Claims the insurer still owes on, counted in the aging report.
+pub const OUTSTANDING_CLAIM_STATUSES: [InsuranceClaimStatus; 4] = [
+    InsuranceClaimStatus::Submitted,
+    InsuranceClaimStatus::Queried,
+    InsuranceClaimStatus::Approved,
+    InsuranceClaimStatus::PartiallyPaid,
+];
+
+/// Claims whose deductible counts towards the patient's card.
+const DEDUCTIBLE_CLAIM_STATUSES: [InsuranceClaimStatus; 3] = [
+    InsuranceClaimStatus::Approved,
+    InsuranceClaimStatus::PartiallyPaid,
+    InsuranceClaimStatus::Paid,
+];
+
+pub const MAX_DIAGNOSIS_CODES: usize = 12;
+
+const MAX_ATTACHMENTS: usize = 10;
+
+const ALLOWED_ATTACHMENT_TYPES: [&str; 4] =
+    ["image/jpeg", "image/png", "image/webp", "application/pdf"];
+
+/// Days since submission that each aging bucket starts at.
+const AGING_BUCKETS: [(i64, &str); 4] =
+    [(0, "0_30"), (31, "31_60"), (61, "61_90"), (91, "over_90")];
+
+pub async fn find_claim(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+) -> Result<tenant::entities::insurance_claims::Model, ApiResponse> {
+    tenant::entities::insurance_claims::Entity::find_by_pid(pid)
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch claim {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch claim" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Claim not found" })))
+}
+
+pub async fn claim_lines<C: ConnectionTrait>(
+    db: &C,
+    claim_id: i32,
+) -> Result<Vec<tenant::entities::insurance_claim_lines::Model>, ApiResponse> {
+    tenant::entities::insurance_claim_lines::Entity::find()
+        .filter(tenant::entities::insurance_claim_lines::Column::ClaimId.eq(claim_id))
+        .order_by_asc(tenant::entities::insurance_claim_lines::Column::Id)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch lines of claim {}: {}", claim_id, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch claim" }))
+        })
+}
+
+pub async fn claim_attachments<C: ConnectionTrait>(
+    db: &C,
+    claim_id: i32,
+) -> Result<Vec<tenant::entities::insurance_claim_attachments::Model>, ApiResponse> {
+    tenant::entities::insurance_claim_attachments::Entity::find()
+        .filter(tenant::entities::insurance_claim_attachments::Column::ClaimId.eq(claim_id))
+        .order_by_asc(tenant::entities::insurance_claim_attachments::Column::CreatedAt)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch attachments of claim {}: {}", claim_id, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch claim" }))
+        })
+}
+
+pub async fn claim_allocations<C: ConnectionTrait>(
+    db: &C,
+    claim_id: i32,
+) -> Result<
+    Vec<(
+        tenant::entities::remittance_allocations::Model,
+        Option<tenant::entities::insurance_remittances::Model>,
+    )>,
+    ApiResponse,
+> {
+    tenant::entities::remittance_allocations::Entity::find()
+        .filter(tenant::entities::remittance_allocations::Column::ClaimId.eq(claim_id))
+        .find_also_related(tenant::entities::insurance_remittances::Entity)
+        .order_by_asc(tenant::entities::remittance_allocations::Column::CreatedAt)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch remittances of claim {}: {}", claim_id, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch claim" }))
+        })
+}
+
+/// Normalizes, de-duplicates and checks ICD-10 codes, keeping their order.
+pub fn clean_diagnosis_codes(codes: &[String]) -> Result<Vec<String>, String> {
+    let mut cleaned: Vec<String> = Vec::with_capacity(codes.len());
+
+    for code in codes {
+        let code = normalize_diagnosis_code(code);
+        if code.is_empty() {
+            continue;
+        }
+        if code.len() > 20 || !code.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(format!("{} is not a valid ICD-10 code", code));
+        }
+        if !cleaned.contains(&code) {
+            cleaned.push(code);
+        }
+    }
+
+    if cleaned.len() > MAX_DIAGNOSIS_CODES {
+        return Err(format!(
+            "At most {} diagnosis codes are allowed",
+            MAX_DIAGNOSIS_CODES
+        ));
+    }
+
+    Ok(cleaned)
+}
+
+/// What the insurer still owes: the approved amount once adjudicated,
+/// otherwise the amount claimed.
+pub fn claim_outstanding(claim: &tenant::entities::insurance_claims::Model) -> Decimal {
+    if !OUTSTANDING_CLAIM_STATUSES.contains(&claim.status) {
+        return Decimal::ZERO;
+    }
+
+    (claim.approved_amount.unwrap_or(claim.claimed_amount) - claim.paid_amount).max(Decimal::ZERO)
+}
+
+/// The deductible an adjudicated claim puts on the patient's card: all of
+/// it once approved, none while pending or after a rejection.
+fn claim_deductible(claim: &tenant::entities::insurance_claims::Model) -> Decimal {
+    if DEDUCTIBLE_CLAIM_STATUSES.contains(&claim.status) {
+        claim.deductible_amount
+    } else {
+        Decimal::ZERO
+    }
+}
+
+/// Brings the card's deductible accumulator in line with the claim's
+/// status, posting an approval or reversing it after a rejection. What has
+/// been posted is kept on the claim, so the accumulator posting job can
+/// safely call it again when the card could not be updated.
+pub async fn post_claim_deductible(
+    tenant_db: &DatabaseConnection,
+    main_db: &DatabaseConnection,
+    claim: &tenant::entities::insurance_claims::Model,
+) -> Result<(), ApiResponse> {
+    let target = claim_deductible(claim);
+    let amount = target - claim.deductible_posted;
+    if amount.is_zero() {
+        return Ok(());
+    }
+
+    let set_posted = |from: Decimal, to: Decimal| {
+        tenant::entities::insurance_claims::Entity::update_many()
+            .set(tenant::entities::insurance_claims::ActiveModel {
+                deductible_posted: Set(to),
+                ..Default::default()
+            })
+            .filter(tenant::entities::insurance_claims::Column::Id.eq(claim.id))
+            .filter(tenant::entities::insurance_claims::Column::DeductiblePosted.eq(from))
+            .exec(tenant_db)
+    };
+    let posting_error = |err| {
+        log::error!(
+            "Failed to post deductible of {}: {}",
+            claim.claim_number,
+            err
+        );
+        ApiResponse::new(
+            500,
+            json!({ "message": "Failed to update insurance accumulators" }),
+        )
+    };
+
+    // Claimed on the claim first so concurrent calls cannot post twice,
+    // and handed back if the card cannot be updated.
+    let claimed = set_posted(claim.deductible_posted, target)
+        .await
+        .map_err(posting_error)?;
+    if claimed.rows_affected == 0 {
+        return Ok(());
+    }
+
+    if let Err(err) =
+        post_to_accumulators(main_db, claim.patient_insurance_pid, amount, Decimal::ZERO).await
+    {
+        set_posted(target, claim.deductible_posted)
+            .await
+            .map_err(posting_error)?;
+        return Err(err);
+    }
+
+    Ok(())
+}
+
+/// Retries the deductible postings of claims whose card accumulator does
+/// not yet reflect their status, returning how many were posted.
+pub async fn post_pending_deductibles(
+    tenant_db: &DatabaseConnection,
+    main_db: &DatabaseConnection,
+) -> Result<usize, ApiResponse> {
+    use tenant::migrations::{Expr, ExprTrait};
+
+    let claims = tenant::entities::insurance_claims::Entity::find()
+        .filter(
+            Condition::any()
+                .add(
+                    Condition::all()
+                        .add(
+                            tenant::entities::insurance_claims::Column::Status
+                                .is_in(DEDUCTIBLE_CLAIM_STATUSES),
+                        )
+                        .add(
+                            Expr::col(tenant::entities::insurance_claims::Column::DeductiblePosted)
+                                .ne(Expr::col(
+                                    tenant::entities::insurance_claims::Column::DeductibleAmount,
+                                )),
+                        ),
+                )
+                .add(
+                    Condition::all()
+                        .add(
+                            tenant::entities::insurance_claims::Column::Status
+                                .is_not_in(DEDUCTIBLE_CLAIM_STATUSES),
+                        )
+                        .add(
+                            tenant::entities::insurance_claims::Column::DeductiblePosted
+                                .ne(Decimal::ZERO),
+                        ),
+                ),
+        )
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch claims with pending postings: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch claims" }))
+        })?;
+
+    let mut posted = 0;
+    for claim in claims {
+        if post_claim_deductible(tenant_db, main_db, &claim)
+            .await
+            .is_ok()
+        {
+            posted += 1;
+        }
+    }
+
+    Ok(posted)
+}
+
+/// Adds an insurer payment to the claim, marking it paid once the approved
+/// amount is covered.
+pub async fn apply_remittance<C: ConnectionTrait>(
+    db: &C,
+    claim: tenant::entities::insurance_claims::Model,
+    amount: Decimal,
+) -> Result<tenant::entities::insurance_claims::Model, ApiResponse> {
+    let claim_number = claim.claim_number.clone();
+    let paid_amount = claim.paid_amount + amount;
+    let settled = paid_amount >= claim.approved_amount.unwrap_or(claim.claimed_amount);
+    let now = Utc::now().naive_utc();
+
+    let mut active_model: tenant::entities::insurance_claims::ActiveModel = claim.into();
+    active_model.paid_amount = Set(paid_amount);
+    if settled {
+        active_model.status = Set(InsuranceClaimStatus::Paid);
+        active_model.paid_at = Set(Some(now));
+    } else {
+        active_model.status = Set(InsuranceClaimStatus::PartiallyPaid);
+    }
+    active_model.updated_at = Set(now);
+
+    active_model.update(db).await.map_err(|err| {
+        log::error!("Failed to record remittance on {}: {}", claim_number, err);
+        ApiResponse::new(500, json!({ "message": "Failed to allocate remittance" }))
+    })
+}
+
+/// The aging bucket for a claim submitted `days` ago.
+pub fn aging_bucket(days: i64) -> &'static str {
+    AGING_BUCKETS
+        .iter()
+        .rev()
+        .find(|(from, _)| days >= *from)
+        .map(|(_, bucket)| *bucket)
+        .unwrap_or(AGING_BUCKETS[0].1)
+}
+
+pub fn empty_aging_buckets() -> HashMap<&'static str, Decimal> {
+    AGING_BUCKETS
+        .iter()
+        .map(|(_, bucket)| (*bucket, Decimal::ZERO))
+        .collect()
+}
+
+/// Documents sent with a claim or pre-authorization, posted as multipart.
+#[derive(Default)]
+pub struct ClaimAttachmentForm {
+    pub attachments: Vec<PendingAttachment>,
+}
+
+impl ClaimAttachmentForm {
+    pub async fn from_multipart(mut payload: Multipart) -> Result<Self, ApiResponse> {
+        let mut form = ClaimAttachmentForm::default();
+
+        while let Some(Ok(mut field)) = payload.next().await {
+            let content_disposition = field.content_disposition().cloned();
+            let name = content_disposition
+                .as_ref()
+                .and_then(|cd| cd.get_name())
+                .unwrap_or("")
+                .to_string();
+
+            if name != "attachments" && name != "attachments[]" {
+                continue;
+            }
+
+            let file_name = content_disposition
+                .as_ref()
+                .and_then(|cd| cd.get_filename())
+                .and_then(|name| name.rsplit(['/', '\\']).next())
+                .filter(|name| !name.is_empty())
+                .map(|name| name.to_string())
+                .unwrap_or_else(|| format!("{}.bin", Uuid::new_v4()));
+            let content_type = field
+                .content_type()
+                .map(|ct| ct.to_string())
+                .unwrap_or_else(|| "application/octet-stream".to_string());
+            let content = field_to_byte(&mut field).await?;
+
+            if !content.is_empty() {
+                form.attachments.push(PendingAttachment {
+                    file_name,
+                    content_type,
+                    content,
+                });
+            }
+        }
+
+        Ok(form)
+    }
+
+    pub fn validate(&self, existing: usize) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if self.attachments.is_empty() {
+            errors.insert(
+                "attachments".to_string(),
+                "Attach at least one file".to_string(),
+            );
+        } else if existing + self.attachments.len() > MAX_ATTACHMENTS {
+            errors.insert(
+                "attachments".to_string(),
+                format!("At most {} attachments are allowed", MAX_ATTACHMENTS),
+            );
+        } else if let Some(attachment) = self
+            .attachments
+            .iter()
+            .find(|a| !ALLOWED_ATTACHMENT_TYPES.contains(&a.content_type.as_str()))
+        {
+            errors.insert(
+                "attachments".to_string(),
+                format!(
+                    "{} is not a supported file type; attach images or PDFs",
+                    attachment.file_name
+                ),
+            );
+        } else if let Some(attachment) = self
+            .attachments
+            .iter()
+            .find(|a| a.content.len() as u64 > *MAX_FILE_SIZE)
+        {
+            errors.insert(
+                "attachments".to_string(),
+                format!("{} is too large", attachment.file_name),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+pub async fn store_claim_attachments(
+    app_state: &AppState,
+    req: &HttpRequest,
+    tenant_db: &DatabaseConnection,
+    claim: &tenant::entities::insurance_claims::Model,
+    patient_id: i32,
+    form: ClaimAttachmentForm,
+    uploaded_by: Uuid,
+) -> Result<Vec<tenant::entities::insurance_claim_attachments::Model>, ApiResponse> {
+    let mut attachments = Vec::with_capacity(form.attachments.len());
+
+    for attachment in form.attachments {
+        let size_bytes = attachment.content.len() as i64;
+        let file_pid = upload_file(
+            req,
+            app_state,
+            &format!(
+                "insurance_claims/{}/{}-{}",
+                claim.pid,
+                Uuid::new_v4(),
+                attachment.file_name
+            ),
+            attachment.content,
+            &attachment.content_type,
+            Some(patient_id),
+            FileVisibility::Tenant,
+        )
+        .await?;
+
+        let saved = tenant::entities::insurance_claim_attachments::ActiveModel {
+            claim_id: Set(claim.id),
+            file_pid: Set(file_pid),
+            original_name: Set(attachment.file_name),
+            content_type: Set(attachment.content_type),
+            size_bytes: Set(size_bytes),
+            uploaded_by: Set(uploaded_by),
+            ..Default::default()
+        }
+        .insert(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to save attachment for {}: {}", claim.pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to save attachment" }))
+        })?;
+
+        attachments.push(saved);
+    }
+
+    Ok(attachments)
+}
+
+pub fn claim_json(claim: &tenant::entities::insurance_claims::Model) -> Value {
+    json!({
+        "pid": claim.pid,
+        "claim_number": claim.claim_number,
+        "patient_pid": claim.patient_pid,
+        "patient_insurance_pid": claim.patient_insurance_pid,
+        "insurance_provider_pid": claim.insurance_provider_pid,
+        "status": claim.status,
+        "diagnosis_codes": claim.diagnosis_codes,
+        "gross_amount": claim.gross_amount,
+        "copay_amount": claim.copay_amount,
+        "deductible_amount": claim.deductible_amount,
+        "claimed_amount": claim.claimed_amount,
+        "approved_amount": claim.approved_amount,
+        "paid_amount": claim.paid_amount,
+        "outstanding_amount": claim_outstanding(claim),
+        "currency": claim.currency,
+        "insurer_reference": claim.insurer_reference,
+        "query_notes": claim.query_notes,
+        "rejection_reason": claim.rejection_reason,
+        "notes": claim.notes,
+        "created_by": claim.created_by,
+        "submitted_at": claim.submitted_at,
+        "submitted_by": claim.submitted_by,
+        "queried_at": claim.queried_at,
+        "approved_at": claim.approved_at,
+        "rejected_at": claim.rejected_at,
+        "paid_at": claim.paid_at,
+        "preauth_numbers": claim.preauth_numbers,
+        "created_at": claim.created_at,
+        "updated_at": claim.updated_at,
+    })
+}
+
+pub fn claim_detail_json(
+    claim: &tenant::entities::insurance_claims::Model,
+    bill_pid: Uuid,
+    lines: &[tenant::entities::insurance_claim_lines::Model],
+    attachments: &[tenant::entities::insurance_claim_attachments::Model],
+    allocations: &[(
+        tenant::entities::remittance_allocations::Model,
+        Option<tenant::entities::insurance_remittances::Model>,
+    )],
+) -> Value {
+    let mut value = claim_json(claim);
+    value["bill_pid"] = json!(bill_pid);
+    value["lines"] = json!(lines.iter().map(claim_line_json).collect::<Vec<_>>());
+    value["attachments"] = json!(
+        attachments
+            .iter()
+            .map(claim_attachment_json)
+            .collect::<Vec<_>>()
+    );
+    value["remittances"] = json!(
+        allocations
+            .iter()
+            .map(|(allocation, remittance)| json!({
+                "pid": allocation.pid,
+                "amount": allocation.amount,
+                "remittance_pid": remittance.as_ref().map(|r| r.pid),
+                "remittance_number": remittance.as_ref().map(|r| r.remittance_number.clone()),
+                "reference": remittance.as_ref().map(|r| r.reference.clone()),
+                "payment_date": remittance.as_ref().map(|r| r.payment_date),
+                "created_at": allocation.created_at,
+            }))
+            .collect::<Vec<_>>()
+    );
+    value
+}
+
+pub fn claim_line_json(line: &tenant::entities::insurance_claim_lines::Model) -> Value {
+    json!({
+        "pid": line.pid,
+        "service_code": line.service_code,
+        "description": line.description,
+        "quantity": line.quantity,
+        "unit_price": line.unit_price,
+        "total_amount": line.total_amount,
+        "approved_amount": line.approved_amount,
+    })
+}
+
+pub fn claim_attachment_json(
+    attachment: &tenant::entities::insurance_claim_attachments::Model,
+) -> Value {
+    json!({
+        "pid": attachment.pid,
+        "original_name": attachment.original_name,
+        "content_type": attachment.content_type,
+        "size_bytes": attachment.size_bytes,
+        "uploaded_by": attachment.uploaded_by,
+        "created_at": attachment.created_at,
+    })
+}
+
+pub fn remittance_json(remittance: &tenant::entities::insurance_remittances::Model) -> Value {
+    json!({
+        "pid": remittance.pid,
+        "remittance_number": remittance.remittance_number,
+        "insurance_provider_pid": remittance.insurance_provider_pid,
+        "reference": remittance.reference,
+        "payment_date": remittance.payment_date,
+        "amount": remittance.amount,
+        "allocated_amount": remittance.allocated_amount,
+        "unallocated_amount": remittance.amount - remittance.allocated_amount,
+        "currency": remittance.currency,
+        "notes": remittance.notes,
+        "received_by": remittance.received_by,
+        "created_at": remittance.created_at,
+        "updated_at": remittance.updated_at,
+    })
+}
+
+/// Days between submission and the report date, for aging.
+pub fn days_outstanding(
+    claim: &tenant::entities::insurance_claims::Model,
+    as_of: NaiveDate,
+) -> i64 {
+    let submitted_on = claim
+        .submitted_at
+        .map(|submitted_at| submitted_at.date())
+        .unwrap_or_else(|| claim.created_at.date());
+
+    (as_of - submitted_on).num_days().max(0)
+}
@@ -0,0 +1,511 @@
+use std::collections::HashMap;
+
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::main::{
+        self,
+        entities::sea_orm_active_enums::{Gender, InsuranceDependentRelationship},
+        migrations::sea_orm::{
+            ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+            QueryOrder, Set, TransactionTrait,
+        },
+    },
+    utils::{api_response::ApiResponse, validator_error::ValidationError},
+};
+
+type DependentModel = main::entities::insurance_dependents::Model;
+type PolicyModel = main::entities::patient_insurance::Model;
+
+#[derive(Deserialize, Debug)]
+pub struct DependentParams {
+    /// Include dependents whose cover has been deactivated.
+    pub include_inactive: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct DependentData {
+    /// An existing patient to add; staff only. Leave out to register a new
+    /// patient from the details below.
+    pub dependent_patient_pid: Option<Uuid>,
+    pub first_name: Option<String>,
+    pub middle_name: Option<String>,
+    pub last_name: Option<String>,
+    pub dob: Option<NaiveDate>,
+    pub gender: Option<Gender>,
+    pub national_id: Option<String>,
+    pub relationship: Option<InsuranceDependentRelationship>,
+    pub coverage_details: Option<String>,
+    pub is_active: Option<bool>,
+}
+
+impl DependentData {
+    pub fn validate(&self, is_create: bool) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if is_create && self.dependent_patient_pid.is_none() {
+            for (field, value, label) in [
+                ("first_name", &self.first_name, "First name"),
+                ("last_name", &self.last_name, "Last name"),
+            ] {
+                match value.as_deref().map(str::trim) {
+                    None | Some("") => {
+                        errors.insert(field.to_string(), format!("{} is required", label));
+                    }
+                    Some(name) if name.chars().count() > 100 => {
+                        errors.insert(
+                            field.to_string(),
+                            format!("{} must be at most 100 characters", label),
+                        );
+                    }
+                    _ => {}
+                }
+            }
+
+            match self.dob {
+                None => {
+                    errors.insert("dob".to_string(), "Date of birth is required".to_string());
+                }
+                Some(dob) if dob > Utc::now().date_naive() => {
+                    errors.insert(
+                        "dob".to_string(),
+                        "Date of birth cannot be in the future".to_string(),
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        if is_create && self.relationship.is_none() {
+            errors.insert(
+                "relationship".to_string(),
+                "Relationship is required".to_string(),
+            );
+        }
+
+        if self
+            .national_id
+            .as_ref()
+            .is_some_and(|id| id.trim().chars().count() > 50)
+        {
+            errors.insert(
+                "national_id".to_string(),
+                "National ID must be at most 50 characters".to_string(),
+            );
+        }
+
+        if self
+            .coverage_details
+            .as_ref()
+            .is_some_and(|details| details.chars().count() > 1000)
+        {
+            errors.insert(
+                "coverage_details".to_string(),
+                "Coverage details must be at most 1000 characters".to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+/// The policy, limited to the holder's own when `patient_id` is given.
+pub async fn find_policy(
+    main_db: &DatabaseConnection,
+    insurance_pid: Uuid,
+    patient_id: Option<i32>,
+) -> Result<main::entities::patient_insurance::Model, ApiResponse> {
+    let mut stmt = main::entities::patient_insurance::Entity::find_by_pid(insurance_pid)
+        .filter(main::entities::patient_insurance::Column::DeletedAt.is_null());
+
+    if let Some(patient_id) = patient_id {
+        stmt = stmt.filter(main::entities::patient_insurance::Column::PatientId.eq(patient_id));
+    }
+
+    stmt.one(main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch insurance {}: {}", insurance_pid, err);
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to fetch patient insurance" }),
+            )
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Patient insurance not found" })))
+}
+
+/// The dependent with the policy it is on, limited to the holder's own
+/// policies when `patient_id` is given.
+async fn find_dependent(
+    main_db: &DatabaseConnection,
+    dependent_pid: Uuid,
+    patient_id: Option<i32>,
+) -> Result<(DependentModel, PolicyModel), ApiResponse> {
+    let mut stmt = main::entities::insurance_dependents::Entity::find()
+        .find_also_related(main::entities::patient_insurance::Entity)
+        .filter(main::entities::insurance_dependents::Column::Pid.eq(dependent_pid))
+        .filter(main::entities::insurance_dependents::Column::DeletedAt.is_null())
+        .filter(main::entities::patient_insurance::Column::DeletedAt.is_null());
+
+    if let Some(patient_id) = patient_id {
+        stmt = stmt.filter(main::entities::patient_insurance::Column::PatientId.eq(patient_id));
+    }
+
+    let found = stmt.one(main_db).await.map_err(|err| {
+        log::error!("Failed to fetch dependent {}: {}", dependent_pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to fetch dependent" }))
+    })?;
+
+    match found {
+        Some((dependent, Some(policy))) => Ok((dependent, policy)),
+        _ => Err(ApiResponse::new(
+            404,
+            json!({ "message": "Dependent not found" }),
+        )),
+    }
+}
+
+async fn dependent_patient(
+    main_db: &DatabaseConnection,
+    dependent: &DependentModel,
+) -> Result<Option<main::entities::patients::Model>, ApiResponse> {
+    main::entities::patients::Entity::find_by_id(dependent.dependent_patient_id)
+        .one(main_db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to fetch patient of dependent {}: {}",
+                dependent.pid,
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to fetch dependent" }))
+        })
+}
+
+pub async fn list_dependents(
+    main_db: &DatabaseConnection,
+    insurance_pid: Uuid,
+    patient_id: Option<i32>,
+    params: &DependentParams,
+) -> Result<ApiResponse, ApiResponse> {
+    let policy = find_policy(main_db, insurance_pid, patient_id).await?;
+
+    let mut stmt = main::entities::insurance_dependents::Entity::find()
+        .find_also_related(main::entities::patients::Entity)
+        .filter(main::entities::insurance_dependents::Column::InsuranceId.eq(policy.id))
+        .filter(main::entities::insurance_dependents::Column::DeletedAt.is_null());
+
+    if !params.include_inactive.unwrap_or(false) {
+        stmt = stmt.filter(main::entities::insurance_dependents::Column::IsActive.eq(true));
+    }
+
+    let dependents = stmt
+        .order_by_asc(main::entities::insurance_dependents::Column::CreatedAt)
+        .all(main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch dependents of {}: {}", insurance_pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch dependents" }))
+        })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "dependents": dependents
+                .iter()
+                .map(|(dependent, patient)| dependent_json(dependent, &policy, patient.as_ref()))
+                .collect::<Vec<_>>(),
+            "message": "Dependents fetched successfully",
+        }),
+    ))
+}
+
+/// Adds a dependent to the policy. A dependent without a patient record is
+/// registered first; for staff, one whose national ID is already on file is
+/// matched to that record instead. Policy holders (`patient_id` given) can
+/// only register new people: linking someone else's record would expose
+/// their details and extend cover to them without their consent, so a
+/// national ID already on file is refused, and the national ID they give is
+/// not stored until the facility has verified it.
+pub async fn create_dependent(
+    main_db: &DatabaseConnection,
+    insurance_pid: Uuid,
+    patient_id: Option<i32>,
+    data: &DependentData,
+) -> Result<ApiResponse, ApiResponse> {
+    data.validate(true)
+        .map_err(|err| ApiResponse::new(400, json!(err)))?;
+
+    if patient_id.is_some() && data.dependent_patient_pid.is_some() {
+        return Err(ApiResponse::new(
+            400,
+            json!(ValidationError {
+                errors: HashMap::from([(
+                    "dependent_patient_pid".to_string(),
+                    "Ask the facility to add an existing patient as your dependent".to_string(),
+                )]),
+            }),
+        ));
+    }
+
+    let policy = find_policy(main_db, insurance_pid, patient_id).await?;
+
+    let national_id = data
+        .national_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|id| !id.is_empty());
+
+    let existing = match (data.dependent_patient_pid, national_id) {
+        (Some(pid), _) => Some(
+            main::entities::patients::Entity::find_by_pid(pid)
+                .filter(main::entities::patients::Column::DeletedAt.is_null())
+                .one(main_db)
+                .await
+                .map_err(|err| {
+                    log::error!("Failed to fetch patient {}: {}", pid, err);
+                    ApiResponse::new(500, json!({ "message": "Failed to fetch patient" }))
+                })?
+                .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Patient not found" })))?,
+        ),
+        (None, Some(national_id)) => {
+            // National IDs are unique across deleted records too.
+            let patient = main::entities::patients::Entity::find()
+                .filter(main::entities::patients::Column::NationalId.eq(national_id))
+                .one(main_db)
+                .await
+                .map_err(|err| {
+                    log::error!("Failed to match dependent by national ID: {}", err);
+                    ApiResponse::new(500, json!({ "message": "Failed to fetch patient" }))
+                })?;
+
+            match patient {
+                Some(_) if patient_id.is_some() => {
+                    return Err(ApiResponse::new(
+                        409,
+                        json!({
+                            "message": "A patient with this national ID is already registered. Ask the facility to add them as your dependent",
+                        }),
+                    ));
+                }
+                Some(patient) if patient.deleted_at.is_some() => {
+                    return Err(ApiResponse::new(
+                        409,
+                        json!({ "message": "The national ID belongs to a deleted patient record" }),
+                    ));
+                }
+                patient => patient,
+            }
+        }
+        (None, None) => None,
+    };
+
+    if existing
+        .as_ref()
+        .is_some_and(|patient| patient.id == policy.patient_id)
+    {
+        return Err(ApiResponse::new(
+            400,
+            json!({ "message": "The policy holder cannot be their own dependent" }),
+        ));
+    }
+
+    if let Some(patient) = &existing {
+        let linked = main::entities::insurance_dependents::Entity::find()
+            .filter(main::entities::insurance_dependents::Column::InsuranceId.eq(policy.id))
+            .filter(main::entities::insurance_dependents::Column::DependentPatientId.eq(patient.id))
+            .filter(main::entities::insurance_dependents::Column::DeletedAt.is_null())
+            .one(main_db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to check dependents of {}: {}", insurance_pid, err);
+                ApiResponse::new(500, json!({ "message": "Failed to add dependent" }))
+            })?;
+
+        if let Some(linked) = linked {
+            return Err(ApiResponse::new(
+                409,
+                json!({
+                    "message": "The patient is already a dependent on this policy",
+                    "dependent_pid": linked.pid,
+                }),
+            ));
+        }
+    }
+
+    let txn = main_db.begin().await.map_err(|err| {
+        log::error!("Failed to start dependent transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to add dependent" }))
+    })?;
+
+    let patient = match existing {
+        Some(patient) => patient,
+        None => main::entities::patients::ActiveModel {
+            first_name: Set(data
+                .first_name
+                .as_deref()
+                .map(|name| name.trim().to_string())),
+            middle_name: Set(data
+                .middle_name
+                .as_deref()
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(str::to_string)),
+            last_name: Set(data
+                .last_name
+                .as_deref()
+                .map(|name| name.trim().to_string())),
+            dob: Set(data.dob),
+            gender: Set(data.gender.clone()),
+            national_id: Set(national_id
+                .filter(|_| patient_id.is_none())
+                .map(str::to_string)),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to register dependent patient: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to add dependent" }))
+        })?,
+    };
+
+    let dependent = main::entities::insurance_dependents::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        insurance_id: Set(policy.id),
+        dependent_patient_id: Set(patient.id),
+        relationship: Set(data.relationship.clone()),
+        coverage_details: Set(data.coverage_details.clone()),
+        is_active: Set(data.is_active.unwrap_or(true)),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to add dependent to {}: {}", insurance_pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to add dependent" }))
+    })?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit dependent on {}: {}", insurance_pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to add dependent" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "dependent": dependent_json(&dependent, &policy, Some(&patient)),
+            "message": "Dependent added successfully",
+        }),
+    ))
+}
+
+/// Updates the dependent's relationship, cover notes or active flag. The
+/// dependent's personal details belong to their patient record.
+pub async fn update_dependent(
+    main_db: &DatabaseConnection,
+    dependent_pid: Uuid,
+    patient_id: Option<i32>,
+    data: &DependentData,
+) -> Result<ApiResponse, ApiResponse> {
+    data.validate(false)
+        .map_err(|err| ApiResponse::new(400, json!(err)))?;
+
+    let (dependent, policy) = find_dependent(main_db, dependent_pid, patient_id).await?;
+
+    let mut active_model: main::entities::insurance_dependents::ActiveModel = dependent.into();
+    if let Some(relationship) = &data.relationship {
+        active_model.relationship = Set(Some(relationship.clone()));
+    }
+    if let Some(details) = &data.coverage_details {
+        active_model.coverage_details =
+            Set(Some(details.trim().to_string()).filter(|d| !d.is_empty()));
+    }
+    if let Some(is_active) = data.is_active {
+        active_model.is_active = Set(is_active);
+    }
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let dependent = active_model.update(main_db).await.map_err(|err| {
+        log::error!("Failed to update dependent {}: {}", dependent_pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to update dependent" }))
+    })?;
+    let patient = dependent_patient(main_db, &dependent).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "dependent": dependent_json(&dependent, &policy, patient.as_ref()),
+            "message": "Dependent updated successfully",
+        }),
+    ))
+}
+
+/// Ends the dependent's cover under the policy. The record is kept so it
+/// can be reactivated.
+pub async fn deactivate_dependent(
+    main_db: &DatabaseConnection,
+    dependent_pid: Uuid,
+    patient_id: Option<i32>,
+) -> Result<ApiResponse, ApiResponse> {
+    let (dependent, policy) = find_dependent(main_db, dependent_pid, patient_id).await?;
+
+    if !dependent.is_active {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "The dependent is already inactive" }),
+        ));
+    }
+
+    let mut active_model: main::entities::insurance_dependents::ActiveModel = dependent.into();
+    active_model.is_active = Set(false);
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let dependent = active_model.update(main_db).await.map_err(|err| {
+        log::error!("Failed to deactivate dependent {}: {}", dependent_pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to deactivate dependent" }))
+    })?;
+    let patient = dependent_patient(main_db, &dependent).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "dependent": dependent_json(&dependent, &policy, patient.as_ref()),
+            "message": "Dependent deactivated successfully",
+        }),
+    ))
+}
+
+pub fn dependent_json(
+    dependent: &DependentModel,
+    policy: &PolicyModel,
+    patient: Option<&main::entities::patients::Model>,
+) -> Value {
+    json!({
+        "pid": dependent.pid,
+        "insurance_pid": policy.pid,
+        "policy_number": policy.policy_number,
+        "patient": patient.map(|patient| json!({
+            "pid": patient.pid,
+            "first_name": patient.first_name,
+            "middle_name": patient.middle_name,
+            "last_name": patient.last_name,
+            "dob": patient.dob,
+            "gender": patient.gender,
+            "has_account": patient.sso_user_id.is_some(),
+        })),
+        "relationship": dependent.relationship,
+        "coverage_details": dependent.coverage_details,
+        "is_active": dependent.is_active,
+        "created_at": dependent.created_at,
+        "updated_at": dependent.updated_at,
+    })
+}
@@ -0,0 +1,435 @@
+use std::collections::HashMap;
+
+use actix_multipart::Multipart;
+use actix_web::HttpRequest;
+use futures::StreamExt;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::main::{
+        self,
+        entities::sea_orm_active_enums::FileVisibility,
+        migrations::sea_orm::{
+            ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
+        },
+    },
+    utils::{
+        api_response::ApiResponse,
+        app_state::AppState,
+        crypto::decrypt_json,
+        insurance::MOCK_ENDPOINT,
+        multipart::{field_to_byte, field_to_string, upload_file},
+        validation::validate_phone_number,
+        validator_error::ValidationError,
+    },
+};
+
+const ALLOWED_LOGO_TYPES: [&str; 3] = ["image/jpeg", "image/png", "image/webp"];
+
+const MAX_SUPPORTED_PLANS: usize = 50;
+
+/// A plan the insurer sells, kept in the provider's `supported_plans`.
+/// Benefit amounts are the plan's defaults; a member's card may differ.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SupportedPlan {
+    pub code: String,
+    pub name: String,
+    #[serde(default)]
+    pub plan_type: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub copay_amount: Option<Decimal>,
+    #[serde(default)]
+    pub deductible_amount: Option<Decimal>,
+    #[serde(default)]
+    pub out_of_pocket_max: Option<Decimal>,
+    #[serde(default = "plan_is_active")]
+    pub is_active: bool,
+}
+
+fn plan_is_active() -> bool {
+    true
+}
+
+/// Trims and checks plan definitions. Codes are upper-cased and must be
+/// unique within the provider.
+pub fn clean_supported_plans(plans: &[SupportedPlan]) -> Result<Vec<SupportedPlan>, String> {
+    if plans.len() > MAX_SUPPORTED_PLANS {
+        return Err(format!("At most {} plans are allowed", MAX_SUPPORTED_PLANS));
+    }
+
+    let mut cleaned: Vec<SupportedPlan> = Vec::with_capacity(plans.len());
+    for plan in plans {
+        let code = plan.code.trim().to_uppercase();
+        let name = plan.name.trim().to_string();
+
+        if code.is_empty() || name.is_empty() {
+            return Err("Every plan needs a code and a name".to_string());
+        }
+        if code.chars().count() > 50 || name.chars().count() > 150 {
+            return Err(format!(
+                "Plan {} must have a code of at most 50 and a name of at most 150 characters",
+                code
+            ));
+        }
+        if cleaned.iter().any(|existing| existing.code == code) {
+            return Err(format!("Plan code {} is used more than once", code));
+        }
+        if [
+            plan.copay_amount,
+            plan.deductible_amount,
+            plan.out_of_pocket_max,
+        ]
+        .iter()
+        .flatten()
+        .any(|amount| *amount < Decimal::ZERO)
+        {
+            return Err(format!("Plan {} cannot have negative amounts", code));
+        }
+
+        let optional = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+
+        cleaned.push(SupportedPlan {
+            code,
+            name,
+            plan_type: optional(&plan.plan_type),
+            description: optional(&plan.description),
+            copay_amount: plan.copay_amount.map(|amount| amount.round_dp(2)),
+            deductible_amount: plan.deductible_amount.map(|amount| amount.round_dp(2)),
+            out_of_pocket_max: plan.out_of_pocket_max.map(|amount| amount.round_dp(2)),
+            is_active: plan.is_active,
+        });
+    }
+
+    Ok(cleaned)
+}
+
+/// The provider's plans. Anything stored before plans were structured that
+/// does not read as a plan list is left out.
+pub fn supported_plans(
+    provider: &main::entities::insurance_providers::Model,
+) -> Vec<SupportedPlan> {
+    let Some(plans) = &provider.supported_plans else {
+        return Vec::new();
+    };
+
+    serde_json::from_value(plans.clone()).unwrap_or_else(|err| {
+        log::warn!("Unreadable plans on provider {}: {}", provider.pid, err);
+        Vec::new()
+    })
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ProviderData {
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub country_code: Option<String>,
+    pub phone_number: Option<String>,
+    pub website: Option<String>,
+    pub address: Option<String>,
+    pub api_endpoint: Option<String>,
+    /// A JSON object of credentials for the insurer's API. `Value::Null`
+    /// clears the stored credentials.
+    pub api_credentials: Option<Value>,
+    pub supported_plans: Option<Vec<SupportedPlan>>,
+    /// File pid of the uploaded logo.
+    pub logo_url: Option<String>,
+}
+
+impl ProviderData {
+    pub fn validate(&self, is_create: bool) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        match self.name.as_deref().map(str::trim) {
+            None if is_create => {
+                errors.insert("name".to_string(), "Name is required".to_string());
+            }
+            Some("") => {
+                errors.insert("name".to_string(), "Name is required".to_string());
+            }
+            Some(name) if name.chars().count() > 255 => {
+                errors.insert(
+                    "name".to_string(),
+                    "Name must be at most 255 characters".to_string(),
+                );
+            }
+            _ => {}
+        }
+
+        if let Some(email) = self.email.as_deref().map(str::trim)
+            && !email.is_empty()
+            && (!email.contains('@') || email.chars().count() > 255)
+        {
+            errors.insert(
+                "email".to_string(),
+                "Email must be a valid email address".to_string(),
+            );
+        }
+
+        if let Some(phone_number) = self.phone_number.as_deref().map(str::trim)
+            && !phone_number.is_empty()
+            && !validate_phone_number(phone_number)
+        {
+            errors.insert(
+                "phone_number".to_string(),
+                "Phone number is invalid".to_string(),
+            );
+        }
+
+        if let Some(website) = self.website.as_deref().map(str::trim)
+            && !website.is_empty()
+            && !is_http_url(website)
+        {
+            errors.insert(
+                "website".to_string(),
+                "Website must start with http:// or https://".to_string(),
+            );
+        }
+
+        if let Some(endpoint) = self.api_endpoint.as_deref().map(str::trim)
+            && !endpoint.is_empty()
+            && endpoint != MOCK_ENDPOINT
+            && !is_http_url(endpoint)
+        {
+            errors.insert(
+                "api_endpoint".to_string(),
+                format!(
+                    "API endpoint must start with http:// or https://, or be {}",
+                    MOCK_ENDPOINT
+                ),
+            );
+        }
+
+        if let Some(credentials) = &self.api_credentials
+            && !credentials.is_null()
+            && !credentials
+                .as_object()
+                .is_some_and(|fields| !fields.is_empty() && fields.values().all(Value::is_string))
+        {
+            errors.insert(
+                "api_credentials".to_string(),
+                "API credentials must be a JSON object of text values".to_string(),
+            );
+        }
+
+        if let Some(plans) = &self.supported_plans
+            && let Err(err) = clean_supported_plans(plans)
+        {
+            errors.insert("supported_plans".to_string(), err);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+fn is_http_url(value: &str) -> bool {
+    value.starts_with("https://") || value.starts_with("http://")
+}
+
+/// Reads the provider form. `api_credentials` and `supported_plans` are
+/// JSON text; an empty or `null` `api_credentials` clears them. The logo is
+/// uploaded as it is read.
+pub async fn provider_form(
+    mut payload: Multipart,
+    req: &HttpRequest,
+    app_state: &AppState,
+    is_create: bool,
+) -> Result<ProviderData, ApiResponse> {
+    let mut data = ProviderData::default();
+    let mut errors = HashMap::new();
+
+    while let Some(Ok(mut field)) = payload.next().await {
+        let Some(name) = field
+            .content_disposition()
+            .and_then(|disposition| disposition.get_name())
+            .map(str::to_string)
+        else {
+            continue;
+        };
+
+        match name.as_str() {
+            "name" => data.name = Some(field_to_string(&mut field).await?),
+            "email" => data.email = Some(field_to_string(&mut field).await?),
+            "country_code" => data.country_code = Some(field_to_string(&mut field).await?),
+            "phone_number" => data.phone_number = Some(field_to_string(&mut field).await?),
+            "website" => data.website = Some(field_to_string(&mut field).await?),
+            "address" => data.address = Some(field_to_string(&mut field).await?),
+            "api_endpoint" => data.api_endpoint = Some(field_to_string(&mut field).await?),
+            "api_credentials" => {
+                let text = field_to_string(&mut field).await?;
+                data.api_credentials = if text.trim().is_empty() {
+                    Some(Value::Null)
+                } else {
+                    match serde_json::from_str(&text) {
+                        Ok(credentials) => Some(credentials),
+                        Err(_) => {
+                            errors.insert(
+                                "api_credentials".to_string(),
+                                "API credentials must be valid JSON".to_string(),
+                            );
+                            None
+                        }
+                    }
+                };
+            }
+            "supported_plans" => {
+                let text = field_to_string(&mut field).await?;
+                match serde_json::from_str(&text) {
+                    Ok(plans) => data.supported_plans = Some(plans),
+                    Err(err) => {
+                        errors.insert(
+                            "supported_plans".to_string(),
+                            format!("Supported plans must be a list of plans: {}", err),
+                        );
+                    }
+                }
+            }
+            "logo" => {
+                let filename = field
+                    .content_disposition()
+                    .and_then(|disposition| disposition.get_filename())
+                    .map(str::to_string)
+                    .unwrap_or_else(|| format!("{}.bin", Uuid::new_v4()));
+                let content_type = field
+                    .content_type()
+                    .map(|ct| ct.to_string())
+                    .unwrap_or_else(|| "application/octet-stream".to_string());
+                let content = field_to_byte(&mut field).await?;
+
+                if content.is_empty() {
+                    continue;
+                }
+                if !ALLOWED_LOGO_TYPES.contains(&content_type.as_str()) {
+                    errors.insert(
+                        "logo".to_string(),
+                        "Logo must be a JPEG, PNG or WebP image".to_string(),
+                    );
+                    continue;
+                }
+
+                let file_pid = upload_file(
+                    req,
+                    app_state,
+                    &format!("insurance_provider_logo/{}-{}", Uuid::new_v4(), filename),
+                    content,
+                    &content_type,
+                    None,
+                    FileVisibility::Public,
+                )
+                .await?;
+                data.logo_url = Some(file_pid.to_string());
+            }
+            _ => {}
+        }
+    }
+
+    if let Err(err) = data.validate(is_create) {
+        errors.extend(err.errors);
+    }
+
+    if errors.is_empty() {
+        Ok(data)
+    } else {
+        Err(ApiResponse::new(400, json!(ValidationError { errors })))
+    }
+}
+
+/// Rejects a name another live provider already has.
+pub async fn ensure_unique_provider_name(
+    main_db: &DatabaseConnection,
+    name: &str,
+    exclude_id: Option<i32>,
+) -> Result<(), ApiResponse> {
+    use main::migrations::{Expr, extension::postgres::PgExpr};
+
+    let mut stmt = main::entities::insurance_providers::Entity::find()
+        .filter(Expr::col(main::entities::insurance_providers::Column::Name).ilike(name))
+        .filter(main::entities::insurance_providers::Column::DeletedAt.is_null());
+
+    if let Some(id) = exclude_id {
+        stmt = stmt.filter(main::entities::insurance_providers::Column::Id.ne(id));
+    }
+
+    let taken = stmt.count(main_db).await.map_err(|err| {
+        log::error!("Failed to check insurance provider name: {}", err);
+        ApiResponse::new(
+            500,
+            json!({ "message": "Failed to validate insurance provider" }),
+        )
+    })? > 0;
+
+    if taken {
+        return Err(ApiResponse::new(
+            400,
+            json!(ValidationError {
+                errors: HashMap::from([(
+                    "name".to_string(),
+                    format!("{} is already registered", name),
+                )]),
+            }),
+        ));
+    }
+
+    Ok(())
+}
+
+/// The provider as patients see it; the logo is fetched through
+/// `/files/download/{logo_file_pid}`. `is_admin` adds the integration
+/// settings; stored credentials are never returned, only their field names.
+pub fn provider_json(
+    provider: &main::entities::insurance_providers::Model,
+    is_admin: bool,
+) -> Value {
+    let plans = supported_plans(provider)
+        .into_iter()
+        .filter(|plan| is_admin || plan.is_active)
+        .collect::<Vec<_>>();
+
+    let mut value = json!({
+        "pid": provider.pid,
+        "name": provider.name,
+        "email": provider.email,
+        "country_code": provider.country_code,
+        "phone_number": provider.phone_number,
+        "website": provider.website,
+        "address": provider.address,
+        "logo_file_pid": provider.logo_url,
+        "supported_plans": plans,
+    });
+
+    if is_admin {
+        let credential_fields = provider
+            .api_credentials
+            .as_ref()
+            .and_then(|credentials| decrypt_json(credentials).ok())
+            .and_then(|credentials| {
+                credentials
+                    .as_object()
+                    .map(|fields| fields.keys().cloned().collect::<Vec<_>>())
+            });
+
+        value["api_endpoint"] = json!(provider.api_endpoint);
+        value["has_api_credentials"] = json!(provider.api_credentials.is_some());
+        value["api_credential_fields"] = json!(credential_fields);
+        value["created_at"] = json!(provider.created_at);
+        value["updated_at"] = json!(provider.updated_at);
+        value["deleted_at"] = json!(provider.deleted_at);
+    }
+
+    value
+}
@@ -0,0 +1,497 @@
+use std::collections::HashMap;
+
+use actix_multipart::Multipart;
+use actix_web::HttpRequest;
+use chrono::{Duration, Utc};
+use futures::StreamExt;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        main::{self, entities::sea_orm_active_enums::FileVisibility},
+        tenant::{
+            self,
+            entities::sea_orm_active_enums::{MessageSenderType, MessageThreadStatus},
+            migrations::sea_orm::{
+                ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait,
+                QueryFilter, QueryOrder, QuerySelect, Set,
+            },
+        },
+    },
+    handlers::services::patients::{allows_sms, sms_phone_number},
+    utils::{
+        api_response::ApiResponse,
+        app_state::AppState,
+        constants::{APP_NAME, MAX_FILE_SIZE},
+        message_queue::MessageType,
+        multipart::{field_to_byte, field_to_string, field_to_uuid, upload_facility_file},
+        validator_error::ValidationError,
+    },
+};
+
+/// How long a patient's message may wait for a reply from the care team.
+pub const REPLY_SLA_HOURS: i64 = 24;
+
+const MAX_ATTACHMENTS: usize = 5;
+
+const ALLOWED_ATTACHMENT_TYPES: [&str; 4] =
+    ["image/jpeg", "image/png", "image/webp", "application/pdf"];
+
+pub struct PendingAttachment {
+    pub file_name: String,
+    pub content_type: String,
+    pub content: Vec<u8>,
+}
+
+/// A new thread or reply, posted as multipart so files can ride along.
+#[derive(Default)]
+pub struct MessageForm {
+    pub tenant_pid: Option<Uuid>,
+    pub patient_pid: Option<Uuid>,
+    pub encounter_pid: Option<Uuid>,
+    pub subject: Option<String>,
+    pub body: String,
+    pub attachments: Vec<PendingAttachment>,
+}
+
+impl MessageForm {
+    pub async fn from_multipart(mut payload: Multipart) -> Result<Self, ApiResponse> {
+        let mut form = MessageForm::default();
+
+        while let Some(Ok(mut field)) = payload.next().await {
+            let content_disposition = field.content_disposition().cloned();
+            let name = content_disposition
+                .as_ref()
+                .and_then(|cd| cd.get_name())
+                .unwrap_or("")
+                .to_string();
+
+            match name.as_str() {
+                "tenant_pid" => form.tenant_pid = Some(field_to_uuid(&mut field).await?),
+                "patient_pid" => form.patient_pid = Some(field_to_uuid(&mut field).await?),
+                "encounter_pid" => form.encounter_pid = Some(field_to_uuid(&mut field).await?),
+                "subject" => form.subject = Some(field_to_string(&mut field).await?),
+                "body" => form.body = field_to_string(&mut field).await?,
+                "attachments" | "attachments[]" => {
+                    let file_name = content_disposition
+                        .as_ref()
+                        .and_then(|cd| cd.get_filename())
+                        .and_then(|name| name.rsplit(['/', '\\']).next())
+                        .filter(|name| !name.is_empty())
+                        .map(|name| name.to_string())
+                        .unwrap_or_else(|| format!("{}.bin", Uuid::new_v4()));
+                    let content_type = field
+                        .content_type()
+                        .map(|ct| ct.to_string())
+                        .unwrap_or_else(|| "application/octet-stream".to_string());
+                    let content = field_to_byte(&mut field).await?;
+
+                    if !content.is_empty() {
+                        form.attachments.push(PendingAttachment {
+                            file_name,
+                            content_type,
+                            content,
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(form)
+    }
+
+    pub fn validate(&self, new_thread: bool) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if new_thread {
+            match self.subject.as_deref().map(str::trim) {
+                None | Some("") => {
+                    errors.insert("subject".to_string(), "Subject is required".to_string());
+                }
+                Some(subject) if subject.chars().count() > 200 => {
+                    errors.insert(
+                        "subject".to_string(),
+                        "Subject must be at most 200 characters".to_string(),
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        if self.body.trim().is_empty() {
+            errors.insert("body".to_string(), "Message is required".to_string());
+        } else if self.body.chars().count() > 5000 {
+            errors.insert(
+                "body".to_string(),
+                "Message must be at most 5000 characters".to_string(),
+            );
+        }
+
+        if self.attachments.len() > MAX_ATTACHMENTS {
+            errors.insert(
+                "attachments".to_string(),
+                format!("At most {} attachments are allowed", MAX_ATTACHMENTS),
+            );
+        } else if let Some(attachment) = self
+            .attachments
+            .iter()
+            .find(|a| !ALLOWED_ATTACHMENT_TYPES.contains(&a.content_type.as_str()))
+        {
+            errors.insert(
+                "attachments".to_string(),
+                format!(
+                    "{} is not a supported file type; attach images or PDFs",
+                    attachment.file_name
+                ),
+            );
+        } else if let Some(attachment) = self
+            .attachments
+            .iter()
+            .find(|a| a.content.len() as u64 > *MAX_FILE_SIZE)
+        {
+            errors.insert(
+                "attachments".to_string(),
+                format!("{} is too large", attachment.file_name),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+/// Adds a message to a thread, stores its attachments and moves the thread's
+/// reply clock: a patient message starts it if it is not already running, a
+/// staff reply stops it. Patients pass the `facility_id` they are writing to
+/// so its staff can open their attachments.
+#[allow(clippy::too_many_arguments)]
+pub async fn post_message(
+    app_state: &AppState,
+    req: &HttpRequest,
+    facility_id: Option<i32>,
+    tenant_db: &DatabaseConnection,
+    thread: &tenant::entities::message_threads::Model,
+    patient: &main::entities::patients::Model,
+    sender_type: MessageSenderType,
+    sender_id: Uuid,
+    form: MessageForm,
+) -> Result<
+    (
+        tenant::entities::message_threads::Model,
+        tenant::entities::thread_messages::Model,
+        Vec<tenant::entities::message_attachments::Model>,
+    ),
+    ApiResponse,
+> {
+    let now = Utc::now().naive_utc();
+
+    // Alert the patient once per batch of unread replies rather than per message.
+    let notify_patient = sender_type == MessageSenderType::Staff
+        && tenant::entities::thread_messages::Entity::find()
+            .filter(tenant::entities::thread_messages::Column::ThreadId.eq(thread.id))
+            .filter(
+                tenant::entities::thread_messages::Column::SenderType.eq(MessageSenderType::Staff),
+            )
+            .filter(tenant::entities::thread_messages::Column::ReadAt.is_null())
+            .count(tenant_db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to count unread messages in {}: {}", thread.pid, err);
+                ApiResponse::new(500, json!({ "message": "Failed to send message" }))
+            })?
+            == 0;
+
+    let message = tenant::entities::thread_messages::ActiveModel {
+        thread_id: Set(thread.id),
+        sender_type: Set(sender_type.clone()),
+        sender_id: Set(sender_id),
+        body: Set(form.body.trim().to_string()),
+        ..Default::default()
+    }
+    .insert(tenant_db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to save message in {}: {}", thread.pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to send message" }))
+    })?;
+
+    let mut attachments = Vec::with_capacity(form.attachments.len());
+    for attachment in form.attachments {
+        let size_bytes = attachment.content.len() as i64;
+        let file_pid = upload_facility_file(
+            req,
+            app_state,
+            facility_id,
+            &format!(
+                "messages/{}/{}-{}",
+                thread.pid,
+                Uuid::new_v4(),
+                attachment.file_name
+            ),
+            attachment.content,
+            &attachment.content_type,
+            Some(patient.id),
+            FileVisibility::Tenant,
+        )
+        .await?;
+
+        let saved = tenant::entities::message_attachments::ActiveModel {
+            message_id: Set(message.id),
+            file_pid: Set(file_pid),
+            original_name: Set(attachment.file_name),
+            content_type: Set(attachment.content_type),
+            size_bytes: Set(size_bytes),
+            ..Default::default()
+        }
+        .insert(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to save attachment for {}: {}", message.pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to save attachment" }))
+        })?;
+
+        attachments.push(saved);
+    }
+
+    let mut active_model: tenant::entities::message_threads::ActiveModel = thread.clone().into();
+    active_model.last_message_at = Set(now);
+    active_model.last_message_from = Set(sender_type.clone());
+
+    match sender_type {
+        MessageSenderType::Patient => {
+            if thread.awaiting_reply_since.is_none() || thread.status == MessageThreadStatus::Closed
+            {
+                active_model.awaiting_reply_since = Set(Some(now));
+                active_model.reply_due_at = Set(Some(now + Duration::hours(REPLY_SLA_HOURS)));
+                active_model.sla_breached_at = Set(None);
+            }
+            active_model.status = Set(MessageThreadStatus::Open);
+            active_model.closed_at = Set(None);
+            active_model.closed_by = Set(None);
+        }
+        MessageSenderType::Staff => {
+            active_model.awaiting_reply_since = Set(None);
+            active_model.reply_due_at = Set(None);
+            mark_thread_read(tenant_db, thread, MessageSenderType::Staff, sender_id).await?;
+        }
+    }
+    active_model.updated_at = Set(now);
+
+    let thread = active_model.update(tenant_db).await.map_err(|err| {
+        log::error!("Failed to update thread {}: {}", thread.pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to send message" }))
+    })?;
+
+    if notify_patient {
+        notify_patient_of_message(app_state, patient).await;
+    }
+
+    Ok((thread, message, attachments))
+}
+
+/// Read receipts: marks the other side's unread messages as read by `reader_id`.
+pub async fn mark_thread_read(
+    tenant_db: &DatabaseConnection,
+    thread: &tenant::entities::message_threads::Model,
+    reader: MessageSenderType,
+    reader_id: Uuid,
+) -> Result<(), ApiResponse> {
+    tenant::entities::thread_messages::Entity::update_many()
+        .set(tenant::entities::thread_messages::ActiveModel {
+            read_at: Set(Some(Utc::now().naive_utc())),
+            read_by: Set(Some(reader_id)),
+            ..Default::default()
+        })
+        .filter(tenant::entities::thread_messages::Column::ThreadId.eq(thread.id))
+        .filter(tenant::entities::thread_messages::Column::SenderType.ne(reader))
+        .filter(tenant::entities::thread_messages::Column::ReadAt.is_null())
+        .exec(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to mark thread {} read: {}", thread.pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to update read receipts" }))
+        })?;
+
+    Ok(())
+}
+
+/// Unread message counts per thread for one side of the conversation.
+pub async fn unread_counts(
+    tenant_db: &DatabaseConnection,
+    thread_ids: Vec<i32>,
+    reader: MessageSenderType,
+) -> Result<HashMap<i32, i64>, ApiResponse> {
+    if thread_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let counts = tenant::entities::thread_messages::Entity::find()
+        .select_only()
+        .column(tenant::entities::thread_messages::Column::ThreadId)
+        .column_as(
+            tenant::entities::thread_messages::Column::Id.count(),
+            "unread",
+        )
+        .filter(tenant::entities::thread_messages::Column::ThreadId.is_in(thread_ids))
+        .filter(tenant::entities::thread_messages::Column::SenderType.ne(reader))
+        .filter(tenant::entities::thread_messages::Column::ReadAt.is_null())
+        .group_by(tenant::entities::thread_messages::Column::ThreadId)
+        .into_tuple::<(i32, i64)>()
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to count unread messages: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch threads" }))
+        })?;
+
+    Ok(counts.into_iter().collect())
+}
+
+/// Messages in a thread, oldest first, with their attachments.
+pub async fn fetch_thread_messages(
+    tenant_db: &DatabaseConnection,
+    thread: &tenant::entities::message_threads::Model,
+) -> Result<Vec<Value>, ApiResponse> {
+    let messages = tenant::entities::thread_messages::Entity::find()
+        .find_with_related(tenant::entities::message_attachments::Entity)
+        .filter(tenant::entities::thread_messages::Column::ThreadId.eq(thread.id))
+        .order_by_asc(tenant::entities::thread_messages::Column::CreatedAt)
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch messages for {}: {}", thread.pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch messages" }))
+        })?;
+
+    Ok(messages
+        .iter()
+        .map(|(message, attachments)| thread_message_json(message, attachments))
+        .collect())
+}
+
+/// Looks up an attachment together with the thread it belongs to.
+pub async fn find_attachment(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+) -> Result<
+    (
+        tenant::entities::message_attachments::Model,
+        tenant::entities::message_threads::Model,
+    ),
+    ApiResponse,
+> {
+    let not_found = || ApiResponse::new(404, json!({ "message": "Attachment not found" }));
+
+    let (attachment, message) = tenant::entities::message_attachments::Entity::find_by_pid(pid)
+        .find_also_related(tenant::entities::thread_messages::Entity)
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch attachment {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch attachment" }))
+        })?
+        .ok_or_else(not_found)?;
+
+    let message = message.ok_or_else(not_found)?;
+    let thread = tenant::entities::message_threads::Entity::find_by_id(message.thread_id)
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch thread for attachment {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch attachment" }))
+        })?
+        .ok_or_else(not_found)?;
+
+    Ok((attachment, thread))
+}
+
+/// SMS alerts carry no clinical detail or facility name; the patient has to
+/// log in to read the message.
+async fn notify_patient_of_message(
+    app_state: &AppState,
+    patient: &main::entities::patients::Model,
+) {
+    if !allows_sms(patient) {
+        return;
+    }
+
+    let Some(phone_number) = sms_phone_number(patient) else {
+        return;
+    };
+
+    let message = format!(
+        "You have a new secure message from your care team. Log in to {} to read it.",
+        *APP_NAME
+    );
+
+    if let Err(err) = app_state
+        .message_queue
+        .send_message(MessageType::SMS {
+            phone_number,
+            message,
+        })
+        .await
+    {
+        log::error!(
+            "Failed to queue message alert for patient {}: {}",
+            patient.pid,
+            err
+        );
+    }
+}
+
+pub fn message_thread_json(
+    thread: &tenant::entities::message_threads::Model,
+    unread_count: i64,
+) -> Value {
+    let now = Utc::now().naive_utc();
+    json!({
+        "pid": thread.pid,
+        "patient_pid": thread.patient_pid,
+        "subject": thread.subject,
+        "status": thread.status,
+        "assigned_to": thread.assigned_to,
+        "assigned_at": thread.assigned_at,
+        "last_message_at": thread.last_message_at,
+        "last_message_from": thread.last_message_from,
+        "awaiting_reply_since": thread.awaiting_reply_since,
+        "reply_due_at": thread.reply_due_at,
+        "is_overdue": thread.reply_due_at.is_some_and(|due| due < now),
+        "sla_breached_at": thread.sla_breached_at,
+        "unread_count": unread_count,
+        "closed_at": thread.closed_at,
+        "closed_by": thread.closed_by,
+        "created_at": thread.created_at,
+    })
+}
+
+pub fn thread_message_json(
+    message: &tenant::entities::thread_messages::Model,
+    attachments: &[tenant::entities::message_attachments::Model],
+) -> Value {
+    json!({
+        "pid": message.pid,
+        "sender_type": message.sender_type,
+        "sender_id": message.sender_id,
+        "body": message.body,
+        "read_at": message.read_at,
+        "read_by": message.read_by,
+        "attachments": attachments.iter().map(message_attachment_json).collect::<Vec<_>>(),
+        "created_at": message.created_at,
+    })
+}
+
+pub fn message_attachment_json(attachment: &tenant::entities::message_attachments::Model) -> Value {
+    json!({
+        "pid": attachment.pid,
+        "original_name": attachment.original_name,
+        "content_type": attachment.content_type,
+        "size_bytes": attachment.size_bytes,
+        "created_at": attachment.created_at,
+    })
+}
@@ -1,3 +1,31 @@
+pub mod chronic_care;
+pub mod clinical_tasks;
+pub mod controlled_substances;
+pub mod coupons;
+pub mod deliveries;
+pub mod dispensing;
+pub mod features;
+pub mod files;
+pub mod immunizations;
+pub mod inpatient;
+pub mod insurance_claims;
+pub mod insurance_dependents;
+pub mod insurance_providers;
+pub mod messaging;
+pub mod online_orders;
+pub mod patient_bills;
+pub mod patient_charges;
 pub mod patient_insurance;
+pub mod patients;
+pub mod pharmacy;
+pub mod preauthorizations;
+pub mod prescriptions;
+pub mod pricing;
+pub mod procurement;
+pub mod queue;
+pub mod screeners;
+pub mod stock_alerts;
+pub mod telemedicine;
 pub mod tenant_applications;
 pub mod tenants;
+pub mod usage_metrics;
@@ -0,0 +1,257 @@
+use actix_web::{HttpRequest, web};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        main::{self, entities::sea_orm_active_enums::PaymentMethod},
+        tenant::{
+            self,
+            entities::sea_orm_active_enums::{OnlineOrderStatus, OnlinePaymentStatus},
+            migrations::sea_orm::{
+                ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+            },
+        },
+    },
+    handlers::{
+        services::pharmacy::product_label,
+        tenant::payments::{PaymentResult, PaymentTransactionCreateRequest, get_payment_processor},
+    },
+    utils::{
+        api_response::ApiResponse, app_state::AppState, constants::APP_URL, jwt::get_tenant_id,
+        message_queue::MessageType,
+    },
+};
+
+/// Prescriptions may be uploaded as photos or scanned PDFs.
+pub const PRESCRIPTION_CONTENT_TYPES: [&str; 4] =
+    ["image/jpeg", "image/png", "image/webp", "application/pdf"];
+
+pub async fn find_order(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+) -> Result<tenant::entities::online_orders::Model, ApiResponse> {
+    tenant::entities::online_orders::Entity::find_by_pid(pid)
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch order {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch order" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Order not found" })))
+}
+
+/// Sends an M-Pesa STK push for the order total to the order's payment
+/// phone. The result is posted to a callback carrying the facility, since
+/// orders live in the facility's own database, and the checkout's
+/// `callback_token`.
+pub async fn request_order_payment(
+    facility_pid: Uuid,
+    order: &tenant::entities::online_orders::Model,
+    callback_token: &str,
+) -> Result<PaymentResult, ApiResponse> {
+    let data = PaymentTransactionCreateRequest {
+        amount: order.total_amount,
+        currency: "KES".to_string(),
+        payment_method: Some("mpesa".to_string()),
+        description: Some(format!("Order {}", order.order_number)),
+        country_code: Some(String::new()),
+        phone_number: Some(order.payment_phone.clone()),
+        account_reference: Some(order.order_number.clone()),
+        callback_url: Some(format!(
+            "{}/api/tenant/payments/callbacks/mpesa/orders/{}/{}",
+            APP_URL.trim_end_matches('/'),
+            facility_pid,
+            callback_token
+        )),
+        ..Default::default()
+    };
+
+    get_payment_processor(&PaymentMethod::Mpesa)
+        .process_payment(&data)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to request payment for order {}: {}",
+                order.order_number,
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to request payment" }))
+        })
+}
+
+/// The text sent to the patient about the order's current status.
+pub fn order_status_sms(
+    facility_name: &str,
+    order: &tenant::entities::online_orders::Model,
+) -> String {
+    let update = match order.status {
+        OnlineOrderStatus::Placed => match order.payment_status {
+            OnlinePaymentStatus::Paid => format!(
+                "Payment of KES {} received for order {}. Ref {}.",
+                order.total_amount,
+                order.order_number,
+                order.payment_reference.as_deref().unwrap_or("-")
+            ),
+            OnlinePaymentStatus::Failed => format!(
+                "Payment for order {} was not completed. Open the app to try again.",
+                order.order_number
+            ),
+            OnlinePaymentStatus::Pending => format!(
+                "Order {} has been placed. Complete the M-Pesa prompt to pay KES {}.",
+                order.order_number, order.total_amount
+            ),
+        },
+        OnlineOrderStatus::Verified => format!(
+            "Order {} has been checked by our pharmacist and is being prepared.",
+            order.order_number
+        ),
+        OnlineOrderStatus::Packed => format!(
+            "Order {} is packed and waiting for dispatch.",
+            order.order_number
+        ),
+        OnlineOrderStatus::OutForDelivery => {
+            format!("Order {} is out for delivery.", order.order_number)
+        }
+        OnlineOrderStatus::Delivered => {
+            format!(
+                "Order {} has been delivered. Thank you.",
+                order.order_number
+            )
+        }
+        OnlineOrderStatus::Cancelled => match &order.cancel_reason {
+            Some(reason) => format!(
+                "Order {} has been cancelled: {}",
+                order.order_number, reason
+            ),
+            None => format!("Order {} has been cancelled.", order.order_number),
+        },
+    };
+
+    format!("{}: {}", facility_name, update)
+}
+
+/// Texts the order's contact phone about its current status. Failures are
+/// logged; the order itself has already moved on.
+pub async fn notify_order_status(
+    app_state: &AppState,
+    facility_name: &str,
+    order: &tenant::entities::online_orders::Model,
+) {
+    if let Err(err) = app_state
+        .message_queue
+        .send_message(MessageType::SMS {
+            phone_number: order.contact_phone.clone(),
+            message: order_status_sms(facility_name, order),
+        })
+        .await
+    {
+        log::error!(
+            "Failed to queue status update for order {}: {}",
+            order.order_number,
+            err
+        );
+    }
+}
+
+/// The order's lines with their products, in the order they were added.
+pub async fn fetch_order_items(
+    tenant_db: &DatabaseConnection,
+    order: &tenant::entities::online_orders::Model,
+) -> Result<Vec<Value>, ApiResponse> {
+    Ok(tenant::entities::online_order_items::Entity::find()
+        .find_also_related(tenant::entities::pharmacy_products::Entity)
+        .filter(tenant::entities::online_order_items::Column::OrderId.eq(order.id))
+        .order_by_asc(tenant::entities::online_order_items::Column::Id)
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch items of order {}: {}", order.pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch order" }))
+        })?
+        .iter()
+        .map(|(item, product)| order_item_json(item, product.as_ref()))
+        .collect())
+}
+
+pub fn order_item_json(
+    item: &tenant::entities::online_order_items::Model,
+    product: Option<&tenant::entities::pharmacy_products::Model>,
+) -> Value {
+    json!({
+        "pid": item.pid,
+        "product": product.map(|product| json!({
+            "pid": product.pid,
+            "label": product_label(product),
+            "unit": product.unit,
+        })),
+        "quantity": item.quantity,
+        "unit_price": item.unit_price,
+        "line_total": item.line_total,
+        "requires_prescription": item.requires_prescription,
+    })
+}
+
+/// The steps the order has been through, in order, for tracking.
+pub fn order_timeline(order: &tenant::entities::online_orders::Model) -> Value {
+    let steps = [
+        ("placed", Some(order.created_at)),
+        ("paid", order.paid_at),
+        ("verified", order.verified_at),
+        ("packed", order.packed_at),
+        ("out_for_delivery", order.dispatched_at),
+        ("delivered", order.delivered_at),
+        ("cancelled", order.cancelled_at),
+    ];
+
+    json!(
+        steps
+            .iter()
+            .filter_map(|(step, at)| at.map(|at| json!({ "step": step, "at": at })))
+            .collect::<Vec<_>>()
+    )
+}
+
+pub fn order_json(order: &tenant::entities::online_orders::Model, items: Vec<Value>) -> Value {
+    json!({
+        "pid": order.pid,
+        "order_number": order.order_number,
+        "patient_pid": order.patient_pid,
+        "status": order.status,
+        "payment_status": order.payment_status,
+        "subtotal_amount": order.subtotal_amount,
+        "discount_amount": order.discount_amount,
+        "delivery_fee": order.delivery_fee,
+        "total_amount": order.total_amount,
+        "payment_phone": order.payment_phone,
+        "payment_reference": order.payment_reference,
+        "payment_failure_reason": order.payment_failure_reason,
+        "delivery_address": order.delivery_address,
+        "contact_phone": order.contact_phone,
+        "notes": order.notes,
+        "has_prescription": order.prescription_document_file_pid.is_some(),
+        "items": items,
+        "timeline": order_timeline(order),
+        "cancel_reason": order.cancel_reason,
+        "created_at": order.created_at,
+        "updated_at": order.updated_at,
+    })
+}
+
+/// The name of the logged-in user's facility, used to sign order texts.
+pub async fn facility_name(
+    req: &HttpRequest,
+    app_state: &web::Data<AppState>,
+) -> Result<String, ApiResponse> {
+    let (tenant_id, _, _) = get_tenant_id(req, app_state).await?;
+
+    Ok(main::entities::tenants::Entity::find_by_id(tenant_id)
+        .one(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch tenant {}: {}", tenant_id, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch facility" }))
+        })?
+        .map(|tenant| tenant.name)
+        .unwrap_or_default())
+}
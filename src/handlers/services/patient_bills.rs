@@ -0,0 +1,774 @@
+use std::collections::HashMap;
+
+use chrono::{NaiveDate, NaiveDateTime, Utc};
+use rust_decimal::Decimal;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        main,
+        tenant::{
+            self,
+            entities::sea_orm_active_enums::{
+                BillPaymentMethod, BillPaymentStatus, PatientBillStatus, PatientChargeStatus,
+            },
+            migrations::sea_orm::{
+                ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
+                QueryFilter, QueryOrder, QuerySelect, Set,
+            },
+        },
+    },
+    handlers::services::{
+        patient_charges::patient_charge_json, patient_insurance::post_to_accumulators,
+    },
+    utils::{
+        api_response::ApiResponse,
+        constants::APP_URL,
+        crypto::decrypt_string,
+        documents::document_number,
+        html::escape_html,
+        mpesa::{MpesaClient, StkPushResponse, callback_token},
+        validator_error::ValidationError,
+    },
+};
+
+/// `source_type` of coupon redemptions made on patient bills.
+pub const PATIENT_BILL_SOURCE: &str = "patient_bill";
+
+/// Daraja transaction types for a paybill and a till number.
+pub const MPESA_PAYBILL: &str = "CustomerPayBillOnline";
+pub const MPESA_BUY_GOODS: &str = "CustomerBuyGoodsOnline";
+
+pub async fn find_bill(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+) -> Result<tenant::entities::patient_bills::Model, ApiResponse> {
+    tenant::entities::patient_bills::Entity::find_by_pid(pid)
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch bill {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch bill" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Bill not found" })))
+}
+
+/// The bill the patient's new charges are collecting on, if one is open.
+pub async fn find_open_bill<C: ConnectionTrait>(
+    db: &C,
+    patient_pid: Uuid,
+) -> Result<Option<tenant::entities::patient_bills::Model>, ApiResponse> {
+    tenant::entities::patient_bills::Entity::find()
+        .filter(tenant::entities::patient_bills::Column::PatientPid.eq(patient_pid))
+        .filter(tenant::entities::patient_bills::Column::Status.eq(PatientBillStatus::Open))
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch open bill of {}: {}", patient_pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch bill" }))
+        })
+}
+
+/// Moves the patient's pending charges onto the bill.
+pub async fn collect_charges<C: ConnectionTrait>(
+    db: &C,
+    bill: &tenant::entities::patient_bills::Model,
+) -> Result<(), ApiResponse> {
+    let charges = tenant::entities::patient_charges::Entity::find()
+        .filter(tenant::entities::patient_charges::Column::PatientPid.eq(bill.patient_pid))
+        .filter(tenant::entities::patient_charges::Column::Status.eq(PatientChargeStatus::Pending))
+        .filter(tenant::entities::patient_charges::Column::BillId.is_null())
+        .lock_exclusive()
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to fetch charges for bill {}: {}",
+                bill.bill_number,
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to collect charges" }))
+        })?;
+
+    for charge in charges {
+        let mut active_model: tenant::entities::patient_charges::ActiveModel = charge.into();
+        active_model.bill_id = Set(Some(bill.id));
+        active_model.status = Set(PatientChargeStatus::Billed);
+        active_model.updated_at = Set(Utc::now().naive_utc());
+        active_model.update(db).await.map_err(|err| {
+            log::error!("Failed to bill charge on {}: {}", bill.bill_number, err);
+            ApiResponse::new(500, json!({ "message": "Failed to collect charges" }))
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Returns a void bill's charges to pending so the next bill picks them up.
+pub async fn release_charges<C: ConnectionTrait>(
+    db: &C,
+    bill: &tenant::entities::patient_bills::Model,
+) -> Result<(), ApiResponse> {
+    for charge in bill_charges(db, bill.id).await? {
+        if charge.status != PatientChargeStatus::Billed {
+            continue;
+        }
+
+        let mut active_model: tenant::entities::patient_charges::ActiveModel = charge.into();
+        active_model.bill_id = Set(None);
+        active_model.status = Set(PatientChargeStatus::Pending);
+        active_model.updated_at = Set(Utc::now().naive_utc());
+        active_model.update(db).await.map_err(|err| {
+            log::error!("Failed to release charge of {}: {}", bill.bill_number, err);
+            ApiResponse::new(500, json!({ "message": "Failed to release charges" }))
+        })?;
+    }
+
+    Ok(())
+}
+
+pub async fn bill_charges<C: ConnectionTrait>(
+    db: &C,
+    bill_id: i32,
+) -> Result<Vec<tenant::entities::patient_charges::Model>, ApiResponse> {
+    tenant::entities::patient_charges::Entity::find()
+        .filter(tenant::entities::patient_charges::Column::BillId.eq(bill_id))
+        .order_by_asc(tenant::entities::patient_charges::Column::CreatedAt)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch charges of bill {}: {}", bill_id, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch bill charges" }))
+        })
+}
+
+pub async fn bill_payments<C: ConnectionTrait>(
+    db: &C,
+    bill_id: i32,
+) -> Result<Vec<tenant::entities::bill_payments::Model>, ApiResponse> {
+    tenant::entities::bill_payments::Entity::find()
+        .filter(tenant::entities::bill_payments::Column::BillId.eq(bill_id))
+        .order_by_asc(tenant::entities::bill_payments::Column::CreatedAt)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch payments of bill {}: {}", bill_id, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch bill payments" }))
+        })
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BillSplit {
+    pub insurance_amount: Decimal,
+    pub patient_amount: Decimal,
+    /// The part of the patient's share that goes towards the deductible.
+    pub deductible_amount: Decimal,
+    pub copay_amount: Decimal,
+}
+
+/// Splits a bill between the insurer and the patient. An insured patient
+/// first pays whatever is left of the deductible for the plan year, then
+/// the copay, but never more than the out-of-pocket maximum still allows;
+/// the insurer covers the rest.
+pub fn split_bill(
+    total: Decimal,
+    cover: Option<&main::entities::patient_insurance::Model>,
+) -> BillSplit {
+    let Some(cover) = cover else {
+        return BillSplit {
+            patient_amount: total,
+            ..Default::default()
+        };
+    };
+
+    let deductible_left =
+        (cover.deductible_amount.unwrap_or_default() - cover.deductible_met_ytd).max(Decimal::ZERO);
+    let mut deductible_amount = deductible_left.min(total);
+    let mut copay_amount = cover
+        .copay_amount
+        .unwrap_or_default()
+        .max(Decimal::ZERO)
+        .min(total - deductible_amount);
+
+    if let Some(out_of_pocket_max) = cover.out_of_pocket_max {
+        let out_of_pocket_left =
+            (out_of_pocket_max - cover.out_of_pocket_met_ytd).max(Decimal::ZERO);
+        deductible_amount = deductible_amount.min(out_of_pocket_left);
+        copay_amount = copay_amount.min(out_of_pocket_left - deductible_amount);
+    }
+
+    let patient_amount = deductible_amount + copay_amount;
+    BillSplit {
+        insurance_amount: total - patient_amount,
+        patient_amount,
+        deductible_amount,
+        copay_amount,
+    }
+}
+
+/// Posts what the patient has paid towards their share of an insured bill
+/// to the card's out-of-pocket accumulator. Only the part not posted before
+/// is added, so it is safe to call after every payment; the accumulator
+/// posting job calls it again for bills whose posting failed.
+pub async fn post_out_of_pocket(
+    tenant_db: &DatabaseConnection,
+    main_db: &DatabaseConnection,
+    bill: &tenant::entities::patient_bills::Model,
+) -> Result<(), ApiResponse> {
+    let Some(insurance_pid) = bill.patient_insurance_pid else {
+        return Ok(());
+    };
+    if !matches!(
+        bill.status,
+        PatientBillStatus::Finalized | PatientBillStatus::Paid
+    ) {
+        return Ok(());
+    }
+
+    let counted = bill.amount_paid.min(bill.patient_amount);
+    let amount = counted - bill.out_of_pocket_applied;
+    if amount <= Decimal::ZERO {
+        return Ok(());
+    }
+
+    let set_applied = |from: Decimal, to: Decimal| {
+        tenant::entities::patient_bills::Entity::update_many()
+            .set(tenant::entities::patient_bills::ActiveModel {
+                out_of_pocket_applied: Set(to),
+                ..Default::default()
+            })
+            .filter(tenant::entities::patient_bills::Column::Id.eq(bill.id))
+            .filter(tenant::entities::patient_bills::Column::OutOfPocketApplied.eq(from))
+            .exec(tenant_db)
+    };
+    let posting_error = |err| {
+        log::error!(
+            "Failed to post out-of-pocket on {}: {}",
+            bill.bill_number,
+            err
+        );
+        ApiResponse::new(
+            500,
+            json!({ "message": "Failed to update insurance accumulators" }),
+        )
+    };
+
+    // Claims the amount on the bill first, so a concurrent payment cannot
+    // post the same money twice, and hands it back if the card cannot be
+    // updated so the next attempt posts it.
+    let claimed = set_applied(bill.out_of_pocket_applied, counted)
+        .await
+        .map_err(posting_error)?;
+    if claimed.rows_affected == 0 {
+        return Ok(());
+    }
+
+    if let Err(err) = post_to_accumulators(main_db, insurance_pid, Decimal::ZERO, amount).await {
+        set_applied(counted, bill.out_of_pocket_applied)
+            .await
+            .map_err(posting_error)?;
+        return Err(err);
+    }
+
+    Ok(())
+}
+
+/// Retries the out-of-pocket postings of insured bills whose payments have
+/// not all reached the card's accumulator, returning how many were posted.
+pub async fn post_pending_out_of_pocket(
+    tenant_db: &DatabaseConnection,
+    main_db: &DatabaseConnection,
+) -> Result<usize, ApiResponse> {
+    use tenant::migrations::{Expr, ExprTrait, Func};
+
+    let bills = tenant::entities::patient_bills::Entity::find()
+        .filter(tenant::entities::patient_bills::Column::PatientInsurancePid.is_not_null())
+        .filter(
+            tenant::entities::patient_bills::Column::Status
+                .is_in([PatientBillStatus::Finalized, PatientBillStatus::Paid]),
+        )
+        .filter(
+            Expr::col(tenant::entities::patient_bills::Column::OutOfPocketApplied).lt(Func::least(
+                [
+                    Expr::col(tenant::entities::patient_bills::Column::AmountPaid),
+                    Expr::col(tenant::entities::patient_bills::Column::PatientAmount),
+                ],
+            )),
+        )
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch bills with pending postings: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch bills" }))
+        })?;
+
+    let mut posted = 0;
+    for bill in bills {
+        if post_out_of_pocket(tenant_db, main_db, &bill).await.is_ok() {
+            posted += 1;
+        }
+    }
+
+    Ok(posted)
+}
+
+/// What the patient still owes. Open bills have no balance yet; payments on
+/// them are deposits.
+pub fn bill_balance(bill: &tenant::entities::patient_bills::Model) -> Decimal {
+    match bill.status {
+        PatientBillStatus::Finalized => (bill.patient_amount - bill.amount_paid).max(Decimal::ZERO),
+        _ => Decimal::ZERO,
+    }
+}
+
+/// Adds a completed payment to the bill, closing it once the patient's
+/// share is covered.
+pub async fn apply_payment<C: ConnectionTrait>(
+    db: &C,
+    bill: tenant::entities::patient_bills::Model,
+    amount: Decimal,
+) -> Result<tenant::entities::patient_bills::Model, ApiResponse> {
+    let bill_number = bill.bill_number.clone();
+    let amount_paid = bill.amount_paid + amount;
+    let settled = bill.status == PatientBillStatus::Finalized && amount_paid >= bill.patient_amount;
+
+    let mut active_model: tenant::entities::patient_bills::ActiveModel = bill.into();
+    active_model.amount_paid = Set(amount_paid);
+    if settled {
+        active_model.status = Set(PatientBillStatus::Paid);
+        active_model.paid_at = Set(Some(Utc::now().naive_utc()));
+    }
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    active_model.update(db).await.map_err(|err| {
+        log::error!("Failed to record payment on bill {}: {}", bill_number, err);
+        ApiResponse::new(500, json!({ "message": "Failed to record payment" }))
+    })
+}
+
+/// The facility's M-Pesa shortcode settings, if it has set them up.
+pub async fn facility_mpesa_settings<C: ConnectionTrait>(
+    db: &C,
+) -> Result<Option<tenant::entities::facility_mpesa_settings::Model>, ApiResponse> {
+    tenant::entities::facility_mpesa_settings::Entity::find()
+        .order_by_desc(tenant::entities::facility_mpesa_settings::Column::UpdatedAt)
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch M-Pesa settings: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch M-Pesa settings" }))
+        })
+}
+
+/// Sends an STK push for the payment through the facility's own shortcode,
+/// so the money lands in the facility's account rather than the platform's.
+/// Returns the checkout request id, or the reason M-Pesa refused.
+async fn request_bill_payment(
+    settings: &tenant::entities::facility_mpesa_settings::Model,
+    facility_pid: Uuid,
+    bill: &tenant::entities::patient_bills::Model,
+    payment: &tenant::entities::bill_payments::Model,
+) -> Result<Result<String, String>, ApiResponse> {
+    let secret = |value: &str| {
+        decrypt_string(value).map_err(|err| {
+            log::error!("Failed to decrypt M-Pesa credentials: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to request payment" }))
+        })
+    };
+    let mpesa = MpesaClient::with_credentials(
+        settings.consumer_key.clone(),
+        secret(&settings.consumer_secret)?,
+        settings.shortcode.clone(),
+        secret(&settings.passkey)?,
+    );
+
+    let timestamp = mpesa.get_timestamp();
+    let phone_number = payment
+        .phone_number
+        .clone()
+        .unwrap_or_default()
+        .replace(['+', ' '], "");
+
+    let payload = json!({
+        "BusinessShortCode": settings.shortcode,
+        "Password": mpesa.generate_password(&timestamp),
+        "Timestamp": timestamp,
+        "TransactionType": settings.transaction_type,
+        "Amount": payment.amount.normalize().to_string(),
+        "PartyA": phone_number,
+        "PartyB": settings.till_number.as_ref().unwrap_or(&settings.shortcode),
+        "PhoneNumber": phone_number,
+        "CallBackURL": format!(
+            "{}/api/tenant/payments/callbacks/mpesa/bills/{}/{}",
+            APP_URL.trim_end_matches('/'),
+            facility_pid,
+            payment.callback_token.as_deref().unwrap_or_default()
+        ),
+        "AccountReference": bill.bill_number,
+        "TransactionDesc": format!("Bill {}", bill.bill_number),
+    });
+
+    let response = mpesa.stk_push(&payload).await.map_err(|err| {
+        log::error!("M-Pesa STK push for {} failed: {}", bill.bill_number, err);
+        ApiResponse::new(500, json!({ "message": "Failed to request payment" }))
+    })?;
+
+    Ok(match response {
+        StkPushResponse::Success {
+            CheckoutRequestID, ..
+        } => Ok(CheckoutRequestID),
+        StkPushResponse::Error {
+            errorCode,
+            errorMessage,
+            ..
+        } => {
+            log::error!("M-Pesa STK error [{}]: {}", errorCode, errorMessage);
+            Err(format!("{}: {}", errorCode, errorMessage))
+        }
+    })
+}
+
+/// Records a pending M-Pesa payment on the bill and prompts the payer's
+/// phone. M-Pesa only moves whole shillings, so fractional amounts are
+/// refused rather than rounded. A refused prompt is kept as a failed payment.
+pub async fn start_mpesa_payment(
+    tenant_db: &DatabaseConnection,
+    facility_pid: Uuid,
+    bill: &tenant::entities::patient_bills::Model,
+    amount: Decimal,
+    phone_number: String,
+    received_by: Uuid,
+) -> Result<tenant::entities::bill_payments::Model, ApiResponse> {
+    if !amount.fract().is_zero() {
+        return Err(ApiResponse::new(
+            400,
+            json!(ValidationError {
+                errors: HashMap::from([(
+                    "amount".to_string(),
+                    "M-Pesa payments must be in whole shillings".to_string(),
+                )]),
+            }),
+        ));
+    }
+
+    let settings = facility_mpesa_settings(tenant_db)
+        .await?
+        .filter(|settings| settings.is_active)
+        .ok_or_else(|| {
+            ApiResponse::new(
+                409,
+                json!({ "message": "M-Pesa payments are not set up for this facility" }),
+            )
+        })?;
+
+    let payment = tenant::entities::bill_payments::ActiveModel {
+        bill_id: Set(bill.id),
+        receipt_number: Set(document_number("RCT")),
+        method: Set(BillPaymentMethod::Mpesa),
+        status: Set(BillPaymentStatus::Pending),
+        amount: Set(amount),
+        is_deposit: Set(bill.status == PatientBillStatus::Open),
+        phone_number: Set(Some(phone_number)),
+        callback_token: Set(Some(callback_token())),
+        received_by: Set(received_by),
+        ..Default::default()
+    }
+    .insert(tenant_db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to record payment on {}: {}", bill.bill_number, err);
+        ApiResponse::new(500, json!({ "message": "Failed to request payment" }))
+    })?;
+
+    let payment_pid = payment.pid;
+    let result = request_bill_payment(&settings, facility_pid, bill, &payment).await?;
+
+    let mut active_model: tenant::entities::bill_payments::ActiveModel = payment.into();
+    match &result {
+        Ok(checkout_request_id) => {
+            active_model.checkout_request_id = Set(Some(checkout_request_id.clone()));
+        }
+        Err(reason) => {
+            active_model.status = Set(BillPaymentStatus::Failed);
+            active_model.failure_reason = Set(Some(reason.clone()));
+        }
+    }
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let payment = active_model.update(tenant_db).await.map_err(|err| {
+        log::error!("Failed to update payment {}: {}", payment_pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to request payment" }))
+    })?;
+
+    match result {
+        Ok(_) => Ok(payment),
+        Err(reason) => Err(ApiResponse::new(
+            400,
+            json!({
+                "message": "M-Pesa could not start the payment",
+                "reason": reason,
+                "payment": bill_payment_json(&payment),
+            }),
+        )),
+    }
+}
+
+/// The day's completed payments a cashier has not yet reconciled.
+pub async fn unreconciled_payments<C: ConnectionTrait>(
+    db: &C,
+    cashier_id: Uuid,
+    business_date: NaiveDate,
+) -> Result<Vec<tenant::entities::bill_payments::Model>, ApiResponse> {
+    let (start, end) = day_bounds(business_date);
+
+    tenant::entities::bill_payments::Entity::find()
+        .filter(tenant::entities::bill_payments::Column::ReceivedBy.eq(cashier_id))
+        .filter(tenant::entities::bill_payments::Column::Status.eq(BillPaymentStatus::Completed))
+        .filter(tenant::entities::bill_payments::Column::PaidAt.gte(start))
+        .filter(tenant::entities::bill_payments::Column::PaidAt.lt(end))
+        .filter(tenant::entities::bill_payments::Column::ReconciliationId.is_null())
+        .order_by_asc(tenant::entities::bill_payments::Column::PaidAt)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to fetch payments of cashier {}: {}",
+                cashier_id,
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to fetch payments" }))
+        })
+}
+
+/// Cash and M-Pesa takings of a set of payments.
+pub fn payment_totals(payments: &[tenant::entities::bill_payments::Model]) -> (Decimal, Decimal) {
+    payments.iter().fold(
+        (Decimal::ZERO, Decimal::ZERO),
+        |(cash, mpesa), payment| match payment.method {
+            BillPaymentMethod::Cash => (cash + payment.amount, mpesa),
+            BillPaymentMethod::Mpesa => (cash, mpesa + payment.amount),
+        },
+    )
+}
+
+fn day_bounds(date: NaiveDate) -> (NaiveDateTime, NaiveDateTime) {
+    let start = date.and_hms_opt(0, 0, 0).unwrap_or_default();
+    (start, start + chrono::Duration::days(1))
+}
+
+pub fn bill_json(bill: &tenant::entities::patient_bills::Model) -> Value {
+    json!({
+        "pid": bill.pid,
+        "bill_number": bill.bill_number,
+        "patient_pid": bill.patient_pid,
+        "status": bill.status,
+        "patient_insurance_pid": bill.patient_insurance_pid,
+        "insurance_provider_pid": bill.insurance_provider_pid,
+        "subtotal": bill.subtotal,
+        "discount_amount": bill.discount_amount,
+        "total_amount": bill.total_amount,
+        "insurance_amount": bill.insurance_amount,
+        "patient_amount": bill.patient_amount,
+        "deductible_amount": bill.deductible_amount,
+        "amount_paid": bill.amount_paid,
+        "balance": bill_balance(bill),
+        "credit": if bill.status == PatientBillStatus::Open {
+            Decimal::ZERO
+        } else {
+            (bill.amount_paid - bill.patient_amount).max(Decimal::ZERO)
+        },
+        "currency": bill.currency,
+        "notes": bill.notes,
+        "opened_by": bill.opened_by,
+        "finalized_at": bill.finalized_at,
+        "finalized_by": bill.finalized_by,
+        "paid_at": bill.paid_at,
+        "voided_at": bill.voided_at,
+        "voided_by": bill.voided_by,
+        "void_reason": bill.void_reason,
+        "created_at": bill.created_at,
+        "updated_at": bill.updated_at,
+    })
+}
+
+/// The bill with its charges and payments.
+pub fn bill_detail_json(
+    bill: &tenant::entities::patient_bills::Model,
+    charges: &[tenant::entities::patient_charges::Model],
+    payments: &[tenant::entities::bill_payments::Model],
+) -> Value {
+    let mut value = bill_json(bill);
+    value["charges"] = json!(charges.iter().map(patient_charge_json).collect::<Vec<_>>());
+    value["payments"] = json!(payments.iter().map(bill_payment_json).collect::<Vec<_>>());
+    value
+}
+
+pub fn bill_payment_json(payment: &tenant::entities::bill_payments::Model) -> Value {
+    json!({
+        "pid": payment.pid,
+        "receipt_number": payment.receipt_number,
+        "method": payment.method,
+        "status": payment.status,
+        "amount": payment.amount,
+        "tendered_amount": payment.tendered_amount,
+        "change_amount": payment.change_amount,
+        "is_deposit": payment.is_deposit,
+        "phone_number": payment.phone_number,
+        "mpesa_receipt": payment.mpesa_receipt,
+        "failure_reason": payment.failure_reason,
+        "received_by": payment.received_by,
+        "paid_at": payment.paid_at,
+        "created_at": payment.created_at,
+    })
+}
+
+pub fn reconciliation_json(
+    reconciliation: &tenant::entities::cashier_reconciliations::Model,
+) -> Value {
+    json!({
+        "pid": reconciliation.pid,
+        "cashier_id": reconciliation.cashier_id,
+        "business_date": reconciliation.business_date,
+        "cash_expected": reconciliation.cash_expected,
+        "cash_counted": reconciliation.cash_counted,
+        "cash_variance": reconciliation.cash_variance,
+        "mpesa_total": reconciliation.mpesa_total,
+        "payment_count": reconciliation.payment_count,
+        "notes": reconciliation.notes,
+        "created_at": reconciliation.created_at,
+    })
+}
+
+/// The settings with the credentials masked; they are write-only.
+pub fn mpesa_settings_json(settings: &tenant::entities::facility_mpesa_settings::Model) -> Value {
+    json!({
+        "pid": settings.pid,
+        "shortcode": settings.shortcode,
+        "till_number": settings.till_number,
+        "transaction_type": settings.transaction_type,
+        "consumer_key": mask_secret(&settings.consumer_key),
+        "is_active": settings.is_active,
+        "updated_by": settings.updated_by,
+        "updated_at": settings.updated_at,
+    })
+}
+
+fn mask_secret(secret: &str) -> String {
+    let visible: String = secret
+        .chars()
+        .rev()
+        .take(4)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect();
+    format!("****{}", visible)
+}
+
+pub fn receipt_json(
+    facility_name: &str,
+    bill: &tenant::entities::patient_bills::Model,
+    payment: &tenant::entities::bill_payments::Model,
+) -> Value {
+    json!({
+        "facility": facility_name,
+        "receipt_number": payment.receipt_number,
+        "bill_number": bill.bill_number,
+        "patient_pid": bill.patient_pid,
+        "method": payment.method,
+        "amount": payment.amount,
+        "tendered_amount": payment.tendered_amount,
+        "change_amount": payment.change_amount,
+        "mpesa_receipt": payment.mpesa_receipt,
+        "is_deposit": payment.is_deposit,
+        "currency": bill.currency,
+        "balance": bill_balance(bill),
+        "received_by": payment.received_by,
+        "paid_at": payment.paid_at,
+    })
+}
+
+pub fn receipt_html(
+    facility_name: &str,
+    patient: &main::entities::patients::Model,
+    bill: &tenant::entities::patient_bills::Model,
+    payment: &tenant::entities::bill_payments::Model,
+) -> String {
+    let full_name = [
+        &patient.first_name,
+        &patient.middle_name,
+        &patient.last_name,
+    ]
+    .iter()
+    .filter_map(|name| name.as_deref())
+    .collect::<Vec<_>>()
+    .join(" ");
+
+    let method = match payment.method {
+        BillPaymentMethod::Cash => "Cash".to_string(),
+        BillPaymentMethod::Mpesa => format!(
+            "M-Pesa {}",
+            payment.mpesa_receipt.as_deref().unwrap_or_default()
+        ),
+    };
+
+    let change = payment
+        .change_amount
+        .filter(|change| *change > Decimal::ZERO)
+        .map(|change| {
+            format!(
+                r#"<tr><td>Change</td><td style="text-align: right;">{} {}</td></tr>"#,
+                bill.currency, change
+            )
+        })
+        .unwrap_or_default();
+
+    format!(
+        r#"
+        <!doctype html>
+        <html>
+            <head>
+                <meta charset="utf-8" />
+                <title>Receipt {}</title>
+            </head>
+            <body style="font-family: Arial, sans-serif; color: #222; padding: 16px;">
+                <div style="border: 1px solid #333; border-radius: 6px; padding: 16px; width: 380px;">
+                    <div style="font-size: 18px; font-weight: bold; text-align: center;">{}</div>
+                    <div style="font-size: 13px; text-align: center; color: #666; margin-bottom: 12px;">{}</div>
+                    <div style="font-size: 13px;">Receipt: <strong>{}</strong></div>
+                    <div style="font-size: 13px;">Bill: {}</div>
+                    <div style="font-size: 13px;">Patient: {}</div>
+                    <div style="font-size: 13px;">Date: {}</div>
+                    <table style="width: 100%; font-size: 14px; margin-top: 12px;">
+                        <tr><td>Paid by</td><td style="text-align: right;">{}</td></tr>
+                        <tr><td><strong>Amount</strong></td><td style="text-align: right;"><strong>{} {}</strong></td></tr>
+                        {}
+                        <tr><td>Balance</td><td style="text-align: right;">{} {}</td></tr>
+                    </table>
+                </div>
+            </body>
+        </html>
+        "#,
+        escape_html(&payment.receipt_number),
+        escape_html(facility_name),
+        if payment.is_deposit {
+            "Deposit Receipt"
+        } else {
+            "Payment Receipt"
+        },
+        escape_html(&payment.receipt_number),
+        escape_html(&bill.bill_number),
+        escape_html(&full_name),
+        payment
+            .paid_at
+            .unwrap_or(payment.created_at)
+            .format("%d %b %Y %H:%M"),
+        escape_html(&method),
+        bill.currency,
+        payment.amount,
+        change,
+        bill.currency,
+        bill_balance(bill),
+    )
+}
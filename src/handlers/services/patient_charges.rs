@@ -0,0 +1,80 @@
+use rust_decimal::Decimal;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::tenant::{
+        self,
+        entities::sea_orm_active_enums::PatientChargeStatus,
+        migrations::sea_orm::{ActiveModelTrait, ConnectionTrait, Set},
+    },
+    utils::api_response::ApiResponse,
+};
+
+/// Where a charge came from, so billing can trace it back.
+pub const CHARGE_SOURCE_DISPENSE: &str = "dispense";
+pub const CHARGE_SOURCE_ENCOUNTER: &str = "encounter";
+pub const CHARGE_SOURCE_LAB_ORDER: &str = "lab_order";
+
+/// What a billable charge records before it is invoiced.
+pub struct NewCharge<'a> {
+    pub patient_pid: Uuid,
+    pub source_type: &'a str,
+    pub source_pid: Uuid,
+    pub description: String,
+    pub quantity: i32,
+    pub unit_price: Decimal,
+    /// The catalogue service and price list the price came from, when it
+    /// came from the catalogue.
+    pub service_code: Option<String>,
+    pub price_list_id: Option<i32>,
+    pub created_by: Option<Uuid>,
+}
+
+/// Queues a charge against the patient. Charges stay pending until billing
+/// picks them up.
+pub async fn add_patient_charge<C: ConnectionTrait>(
+    db: &C,
+    charge: NewCharge<'_>,
+) -> Result<tenant::entities::patient_charges::Model, ApiResponse> {
+    tenant::entities::patient_charges::ActiveModel {
+        patient_pid: Set(charge.patient_pid),
+        source_type: Set(charge.source_type.to_string()),
+        source_pid: Set(charge.source_pid),
+        description: Set(charge.description.chars().take(255).collect()),
+        quantity: Set(charge.quantity),
+        unit_price: Set(charge.unit_price),
+        total_amount: Set(charge.unit_price * Decimal::from(charge.quantity)),
+        status: Set(PatientChargeStatus::Pending),
+        service_code: Set(charge.service_code),
+        price_list_id: Set(charge.price_list_id),
+        created_by: Set(charge.created_by),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .map_err(|err| {
+        log::error!(
+            "Failed to record charge for patient {}: {}",
+            charge.patient_pid,
+            err
+        );
+        ApiResponse::new(500, json!({ "message": "Failed to record charge" }))
+    })
+}
+
+pub fn patient_charge_json(charge: &tenant::entities::patient_charges::Model) -> Value {
+    json!({
+        "pid": charge.pid,
+        "patient_pid": charge.patient_pid,
+        "source_type": charge.source_type,
+        "source_pid": charge.source_pid,
+        "description": charge.description,
+        "quantity": charge.quantity,
+        "unit_price": charge.unit_price,
+        "total_amount": charge.total_amount,
+        "status": charge.status,
+        "service_code": charge.service_code,
+        "created_at": charge.created_at,
+    })
+}
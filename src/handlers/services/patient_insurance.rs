@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use actix_multipart::Multipart;
 use actix_web::{HttpRequest, web};
-use chrono::{NaiveDate, Utc};
+use chrono::{Datelike, NaiveDate, Utc};
 use futures::StreamExt;
 use rust_decimal::{Decimal, dec};
 use serde::{Deserialize, Serialize};
@@ -12,15 +12,17 @@ use uuid::Uuid;
 use crate::{
     db::main::{
         self, entities,
+        entities::sea_orm_active_enums::{FileVisibility, VerificationStatus},
         migrations::sea_orm::{
-            ActiveModelTrait, ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter,
-            QueryOrder, SelectThree, Set, TopologyStar,
+            ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait,
+            PaginatorTrait, QueryFilter, QueryOrder, SelectThree, Set, TopologyStar,
         },
     },
     utils::{
         api_response::ApiResponse,
         app_state::AppState,
         ids::get_insurance_id,
+        insurance::{EligibilityRequest, EligibilityStatus, get_insurance_adapter},
         multipart::{
             field_to_bool, field_to_byte, field_to_date, field_to_decimal, field_to_i32,
             field_to_string, field_to_uuid, upload_file,
@@ -403,16 +405,18 @@ async fn multipart_data(
                     let unique_filename =
                         format!("card_front_image/{}-{}", Uuid::new_v4(), filename);
 
-                    let full_s3_key = upload_file(
+                    let file_pid = upload_file(
                         &req,
                         &app_state,
                         &unique_filename,
                         file_data.clone(),
                         &content_type,
+                        patient_id.or(data.patient_id),
+                        FileVisibility::Private,
                     )
                     .await?;
 
-                    data.card_front_image = Some(full_s3_key);
+                    data.card_front_image = Some(file_pid.to_string());
                 }
             }
             "card_back_image" => {
@@ -421,16 +425,18 @@ async fn multipart_data(
                     let unique_filename =
                         format!("card_back_image/{}-{}", Uuid::new_v4(), filename);
 
-                    let full_s3_key = upload_file(
+                    let file_pid = upload_file(
                         &req,
                         &app_state,
                         &unique_filename,
                         file_data.clone(),
                         &content_type,
+                        patient_id.or(data.patient_id),
+                        FileVisibility::Private,
                     )
                     .await?;
 
-                    data.card_back_image = Some(full_s3_key);
+                    data.card_back_image = Some(file_pid.to_string());
                 }
             }
             _ => {}
@@ -793,3 +799,376 @@ pub async fn delete_permanently_patient_insurance(
         json!({ "message": "Role permanently deleted successfully" }),
     ))
 }
+
+/// Checks the card with its insurer and records the outcome. A lapsed
+/// card is marked expired and an unknown one invalid; benefits the insurer
+/// returns replace those on the card. When the insurer cannot be reached
+/// the card is left as it was.
+pub async fn verify_patient_insurance(
+    app_state: &web::Data<AppState>,
+    insurance_id: Uuid,
+    patient_id: Option<i32>,
+) -> Result<ApiResponse, ApiResponse> {
+    let mut stmt = main::entities::patient_insurance::Entity::find_by_pid(insurance_id)
+        .find_also_related(main::entities::patients::Entity)
+        .find_also_related(main::entities::insurance_providers::Entity)
+        .filter(main::entities::patient_insurance::Column::DeletedAt.is_null());
+
+    if let Some(patient_id) = patient_id {
+        stmt = stmt.filter(main::entities::patient_insurance::Column::PatientId.eq(patient_id));
+    }
+
+    let (insurance, patient, provider) = stmt
+        .one(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to fetch patient insurance {}: {}",
+                insurance_id,
+                err
+            );
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to fetch patient insurance" }),
+            )
+        })?
+        .ok_or_else(|| {
+            ApiResponse::new(404, json!({ "message": "Patient insurance not found" }))
+        })?;
+
+    let provider = provider.ok_or_else(|| {
+        ApiResponse::new(404, json!({ "message": "Insurance provider not found" }))
+    })?;
+
+    let adapter = get_insurance_adapter(&provider).ok_or_else(|| {
+        ApiResponse::new(
+            409,
+            json!({ "message": format!("{} does not support online verification", provider.name) }),
+        )
+    })?;
+
+    let today = Utc::now().date_naive();
+    let request = EligibilityRequest {
+        policy_number: insurance.policy_number.clone(),
+        group_number: insurance.group_number.clone(),
+        member_name: insurance.policy_holder_name.clone().or_else(|| {
+            patient.as_ref().map(|p| {
+                format!(
+                    "{} {}",
+                    p.first_name.as_deref().unwrap_or(""),
+                    p.last_name.as_deref().unwrap_or("")
+                )
+                .trim()
+                .to_string()
+            })
+        }),
+        date_of_birth: patient.as_ref().and_then(|p| p.dob),
+        service_date: today,
+    };
+
+    let eligibility = adapter.check_eligibility(&request).await.map_err(|err| {
+        log::error!(
+            "Eligibility check with {} failed for {}: {}",
+            provider.name,
+            insurance.pid,
+            err
+        );
+        ApiResponse::new(502, json!({ "message": err }))
+    })?;
+
+    let coverage_end_date = eligibility
+        .coverage_end_date
+        .or(insurance.coverage_end_date);
+    let verification_status = match eligibility.status {
+        EligibilityStatus::Eligible if coverage_end_date.is_some_and(|end| end < today) => {
+            VerificationStatus::Expired
+        }
+        EligibilityStatus::Eligible => VerificationStatus::Verified,
+        EligibilityStatus::Expired => VerificationStatus::Expired,
+        EligibilityStatus::Ineligible => VerificationStatus::Invalid,
+    };
+
+    let now = Utc::now().naive_utc();
+    let mut active_model: main::entities::patient_insurance::ActiveModel = insurance.into();
+    active_model.verification_status = Set(verification_status);
+    active_model.verified_at = Set(Some(now));
+    if let Some(plan_name) = &eligibility.plan_name {
+        active_model.plan_name = Set(Some(plan_name.clone()));
+    }
+    if eligibility.coverage_start_date.is_some() {
+        active_model.coverage_start_date = Set(eligibility.coverage_start_date);
+    }
+    if eligibility.coverage_end_date.is_some() {
+        active_model.coverage_end_date = Set(eligibility.coverage_end_date);
+    }
+    if eligibility.copay_amount.is_some() {
+        active_model.copay_amount = Set(eligibility.copay_amount);
+    }
+    if eligibility.deductible_amount.is_some() {
+        active_model.deductible_amount = Set(eligibility.deductible_amount);
+    }
+    if let Some(met) = eligibility.deductible_met_ytd {
+        active_model.deductible_met_ytd = Set(met);
+    }
+    if eligibility.out_of_pocket_max.is_some() {
+        active_model.out_of_pocket_max = Set(eligibility.out_of_pocket_max);
+    }
+    if let Some(met) = eligibility.out_of_pocket_met_ytd {
+        active_model.out_of_pocket_met_ytd = Set(met);
+    }
+    active_model.updated_at = Set(now);
+
+    let insurance = active_model
+        .update(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to record verification of {}: {}", insurance_id, err);
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to update patient insurance" }),
+            )
+        })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "insurance": {
+                "pid": insurance.pid,
+                "provider": provider.name,
+                "policy_number": insurance.policy_number,
+                "plan_name": insurance.plan_name,
+                "verification_status": insurance.verification_status,
+                "verified_at": insurance.verified_at,
+                "coverage_start_date": insurance.coverage_start_date,
+                "coverage_end_date": insurance.coverage_end_date,
+                "copay_amount": insurance.copay_amount,
+                "deductible_amount": insurance.deductible_amount,
+                "deductible_met_ytd": insurance.deductible_met_ytd,
+                "out_of_pocket_max": insurance.out_of_pocket_max,
+                "out_of_pocket_met_ytd": insurance.out_of_pocket_met_ytd,
+            },
+            "eligibility": eligibility,
+            "message": eligibility
+                .message
+                .clone()
+                .unwrap_or_else(|| "Eligibility checked successfully".to_string()),
+        }),
+    ))
+}
+
+/// Marks cards whose cover ended before today as expired.
+pub async fn expire_lapsed_insurance(main_db: &DatabaseConnection) -> Result<u64, ApiResponse> {
+    let today = Utc::now().date_naive();
+
+    let result = main::entities::patient_insurance::Entity::update_many()
+        .set(main::entities::patient_insurance::ActiveModel {
+            verification_status: Set(VerificationStatus::Expired),
+            updated_at: Set(Utc::now().naive_utc()),
+            ..Default::default()
+        })
+        .filter(main::entities::patient_insurance::Column::CoverageEndDate.lt(today))
+        .filter(
+            main::entities::patient_insurance::Column::VerificationStatus
+                .ne(VerificationStatus::Expired),
+        )
+        .filter(main::entities::patient_insurance::Column::DeletedAt.is_null())
+        .exec(main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to expire lapsed insurance: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to expire insurance" }))
+        })?;
+
+    Ok(result.rows_affected)
+}
+
+/// Moves a card's year-to-date accumulators by the given amounts. Negative
+/// amounts reverse earlier postings; neither accumulator drops below zero.
+pub async fn post_to_accumulators(
+    main_db: &DatabaseConnection,
+    insurance_pid: Uuid,
+    deductible: Decimal,
+    out_of_pocket: Decimal,
+) -> Result<(), ApiResponse> {
+    use main::migrations::{Expr, ExprTrait, Func};
+
+    if deductible.is_zero() && out_of_pocket.is_zero() {
+        return Ok(());
+    }
+
+    main::entities::patient_insurance::Entity::update_many()
+        .col_expr(
+            main::entities::patient_insurance::Column::DeductibleMetYtd,
+            Func::greatest([
+                Expr::col(main::entities::patient_insurance::Column::DeductibleMetYtd)
+                    .add(deductible),
+                Expr::value(Decimal::ZERO),
+            ])
+            .into(),
+        )
+        .col_expr(
+            main::entities::patient_insurance::Column::OutOfPocketMetYtd,
+            Func::greatest([
+                Expr::col(main::entities::patient_insurance::Column::OutOfPocketMetYtd)
+                    .add(out_of_pocket),
+                Expr::value(Decimal::ZERO),
+            ])
+            .into(),
+        )
+        .col_expr(
+            main::entities::patient_insurance::Column::UpdatedAt,
+            Expr::value(Utc::now().naive_utc()),
+        )
+        .filter(main::entities::patient_insurance::Column::Pid.eq(insurance_pid))
+        .exec(main_db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to update accumulators of insurance {}: {}",
+                insurance_pid,
+                err
+            );
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to update insurance accumulators" }),
+            )
+        })?;
+
+    Ok(())
+}
+
+/// The first day of the plan year `on` falls in. Plan years run from the
+/// anniversary of the cover start date, or from 1 January when the card has
+/// none; a 29 February start renews on 28 February in other years.
+pub fn plan_year_start(coverage_start_date: Option<NaiveDate>, on: NaiveDate) -> NaiveDate {
+    let (month, day) = coverage_start_date
+        .map(|start| (start.month(), start.day()))
+        .unwrap_or((1, 1));
+    let anniversary = |year: i32| {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .or_else(|| NaiveDate::from_ymd_opt(year, month, day - 1))
+            .unwrap_or(on)
+    };
+
+    let this_year = anniversary(on.year());
+    if this_year <= on {
+        this_year
+    } else {
+        anniversary(on.year() - 1)
+    }
+}
+
+/// Zeroes the deductible and out-of-pocket accumulators of every card whose
+/// plan year has rolled over since its last reset. Cards added during the
+/// current plan year keep what was entered on them.
+pub async fn reset_insurance_accumulators(
+    main_db: &DatabaseConnection,
+) -> Result<u64, ApiResponse> {
+    let today = Utc::now().date_naive();
+
+    let cards = main::entities::patient_insurance::Entity::find()
+        .filter(main::entities::patient_insurance::Column::DeletedAt.is_null())
+        .filter(
+            main::entities::patient_insurance::Column::VerificationStatus
+                .ne(VerificationStatus::Expired),
+        )
+        .all(main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch insurance for accumulator reset: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to reset accumulators" }))
+        })?;
+
+    let mut reset = 0;
+    for card in cards {
+        let year_start = plan_year_start(card.coverage_start_date, today);
+        if card
+            .accumulators_reset_on
+            .is_some_and(|reset_on| reset_on >= year_start)
+        {
+            continue;
+        }
+
+        let added_this_year =
+            card.accumulators_reset_on.is_none() && card.created_at.date() >= year_start;
+        let card_pid = card.pid;
+
+        let mut active_model: main::entities::patient_insurance::ActiveModel = card.into();
+        if !added_this_year {
+            active_model.deductible_met_ytd = Set(Decimal::ZERO);
+            active_model.out_of_pocket_met_ytd = Set(Decimal::ZERO);
+            reset += 1;
+        }
+        active_model.accumulators_reset_on = Set(Some(year_start));
+        active_model.updated_at = Set(Utc::now().naive_utc());
+
+        active_model.update(main_db).await.map_err(|err| {
+            log::error!(
+                "Failed to reset accumulators of insurance {}: {}",
+                card_pid,
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to reset accumulators" }))
+        })?;
+    }
+
+    Ok(reset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn plan_year_starting_on_29_february() {
+        let start = Some(date(2024, 2, 29));
+
+        assert_eq!(plan_year_start(start, date(2027, 2, 27)), date(2026, 2, 28));
+        assert_eq!(plan_year_start(start, date(2027, 2, 28)), date(2027, 2, 28));
+        assert_eq!(plan_year_start(start, date(2028, 2, 28)), date(2027, 2, 28));
+        assert_eq!(plan_year_start(start, date(2028, 2, 29)), date(2028, 2, 29));
+        assert_eq!(plan_year_start(start, date(2028, 3, 1)), date(2028, 2, 29));
+    }
+
+    #[test]
+    fn plan_year_starting_in_december() {
+        let start = Some(date(2025, 12, 15));
+
+        assert_eq!(
+            plan_year_start(start, date(2026, 1, 10)),
+            date(2025, 12, 15)
+        );
+        assert_eq!(
+            plan_year_start(start, date(2026, 12, 14)),
+            date(2025, 12, 15)
+        );
+        assert_eq!(
+            plan_year_start(start, date(2026, 12, 15)),
+            date(2026, 12, 15)
+        );
+        assert_eq!(
+            plan_year_start(start, date(2026, 12, 31)),
+            date(2026, 12, 15)
+        );
+
+        let new_years_eve = Some(date(2025, 12, 31));
+        assert_eq!(
+            plan_year_start(new_years_eve, date(2026, 1, 1)),
+            date(2025, 12, 31)
+        );
+        assert_eq!(
+            plan_year_start(new_years_eve, date(2026, 12, 31)),
+            date(2026, 12, 31)
+        );
+    }
+
+    #[test]
+    fn plan_year_without_a_start_date_is_the_calendar_year() {
+        assert_eq!(plan_year_start(None, date(2026, 1, 1)), date(2026, 1, 1));
+        assert_eq!(plan_year_start(None, date(2026, 12, 31)), date(2026, 1, 1));
+    }
+}
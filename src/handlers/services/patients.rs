@@ -0,0 +1,74 @@
+use actix_web::HttpRequest;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    db::main::{
+        self,
+        migrations::sea_orm::{ColumnTrait, QueryFilter},
+    },
+    utils::{api_response::ApiResponse, app_state::AppState, jwt::get_logged_in_user_claims},
+};
+
+/// Finds a patient who has not been deleted.
+pub async fn find_patient(
+    app_state: &AppState,
+    patient_pid: Uuid,
+) -> Result<main::entities::patients::Model, ApiResponse> {
+    main::entities::patients::Entity::find_by_pid(patient_pid)
+        .filter(main::entities::patients::Column::DeletedAt.is_null())
+        .one(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch patient {}: {}", patient_pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch patient" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Patient not found" })))
+}
+
+/// Finds the patient record of the signed-in user.
+pub async fn find_logged_in_patient(
+    app_state: &AppState,
+    req: &HttpRequest,
+) -> Result<main::entities::patients::Model, ApiResponse> {
+    let claims = get_logged_in_user_claims(req)?;
+
+    main::entities::patients::Entity::find_by_sso_user_id(claims.sub)
+        .filter(main::entities::patients::Column::DeletedAt.is_null())
+        .one(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to fetch patient for sso_user_id {}: {}",
+                claims.sub,
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to fetch patient" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Patient not found" })))
+}
+
+/// Patients who switched SMS off in their support preferences are skipped.
+pub fn allows_sms(patient: &main::entities::patients::Model) -> bool {
+    patient
+        .emergency_contact
+        .as_ref()
+        .and_then(|contact| contact.get("support_preferences"))
+        .and_then(|preferences| preferences.get("allow_sms_notifications"))
+        .and_then(|allowed| allowed.as_bool())
+        .unwrap_or(true)
+}
+
+/// The patient's phone number in international format, if one is on file.
+pub fn sms_phone_number(patient: &main::entities::patients::Model) -> Option<String> {
+    let phone_number = patient.phone_number.as_deref()?.trim();
+    if phone_number.is_empty() {
+        return None;
+    }
+
+    Some(format!(
+        "{}{}",
+        patient.country_code.as_deref().unwrap_or("").trim(),
+        phone_number
+    ))
+}
@@ -0,0 +1,469 @@
+use std::collections::HashMap;
+
+use chrono::{NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::tenant::{
+        self,
+        entities::sea_orm_active_enums::StockMovementType,
+        migrations::sea_orm::{
+            ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
+            QueryFilter, QueryOrder, QuerySelect, Set,
+        },
+    },
+    handlers::services::controlled_substances::record_register_entry,
+    utils::api_response::ApiResponse,
+};
+
+/// A quantity of one batch, either held in a store or picked from it.
+#[derive(Debug, Clone)]
+pub struct BatchQuantity {
+    pub batch: tenant::entities::stock_batches::Model,
+    pub quantity: i64,
+}
+
+/// What a movement records; `quantity` is signed, positive into the store.
+pub struct NewMovement<'a> {
+    pub store_id: i32,
+    pub batch: &'a tenant::entities::stock_batches::Model,
+    pub movement_type: StockMovementType,
+    pub quantity: i32,
+    pub transfer_pid: Option<Uuid>,
+    pub reference: Option<String>,
+    pub reason: Option<String>,
+    pub performed_by: Uuid,
+}
+
+/// Stock on hand per store and batch, summed from the movement ledger.
+/// Only positive balances are returned.
+pub async fn batch_balances<C: ConnectionTrait>(
+    db: &C,
+    store_id: Option<i32>,
+    product_ids: Option<Vec<i32>>,
+) -> Result<Vec<(i32, i32, i64)>, ApiResponse> {
+    let mut stmt = tenant::entities::stock_movements::Entity::find()
+        .select_only()
+        .column(tenant::entities::stock_movements::Column::StoreId)
+        .column(tenant::entities::stock_movements::Column::BatchId)
+        .column_as(
+            tenant::entities::stock_movements::Column::Quantity.sum(),
+            "on_hand",
+        );
+
+    if let Some(store_id) = store_id {
+        stmt = stmt.filter(tenant::entities::stock_movements::Column::StoreId.eq(store_id));
+    }
+
+    if let Some(product_ids) = product_ids {
+        stmt = stmt.filter(tenant::entities::stock_movements::Column::ProductId.is_in(product_ids));
+    }
+
+    let balances = stmt
+        .group_by(tenant::entities::stock_movements::Column::StoreId)
+        .group_by(tenant::entities::stock_movements::Column::BatchId)
+        .into_tuple::<(i32, i32, i64)>()
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to sum stock movements: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch stock on hand" }))
+        })?;
+
+    Ok(balances
+        .into_iter()
+        .filter(|(_, _, on_hand)| *on_hand > 0)
+        .collect())
+}
+
+/// A product's batches in a store, soonest expiry first. Expired batches are
+/// included so they can be written off; callers picking stock skip them.
+pub async fn store_batches<C: ConnectionTrait>(
+    db: &C,
+    store_id: i32,
+    product_id: i32,
+) -> Result<Vec<BatchQuantity>, ApiResponse> {
+    let balances: HashMap<i32, i64> = batch_balances(db, Some(store_id), Some(vec![product_id]))
+        .await?
+        .into_iter()
+        .map(|(_, batch_id, on_hand)| (batch_id, on_hand))
+        .collect();
+
+    if balances.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let batches = tenant::entities::stock_batches::Entity::find()
+        .filter(tenant::entities::stock_batches::Column::Id.is_in(balances.keys().copied()))
+        .order_by_asc(tenant::entities::stock_batches::Column::ExpiryDate)
+        .order_by_asc(tenant::entities::stock_batches::Column::Id)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to fetch batches for product {}: {}",
+                product_id,
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to fetch stock on hand" }))
+        })?;
+
+    Ok(batches
+        .into_iter()
+        .map(|batch| BatchQuantity {
+            quantity: balances.get(&batch.id).copied().unwrap_or(0),
+            batch,
+        })
+        .collect())
+}
+
+/// Usable (unexpired) stock per product, in one store or across all of them.
+pub async fn usable_stock(
+    tenant_db: &DatabaseConnection,
+    store_id: Option<i32>,
+    product_ids: Vec<i32>,
+) -> Result<HashMap<i32, i64>, ApiResponse> {
+    let balances = batch_balances(tenant_db, store_id, Some(product_ids)).await?;
+    let today = Utc::now().date_naive();
+    let batches = tenant::entities::stock_batches::Entity::find()
+        .filter(
+            tenant::entities::stock_batches::Column::Id
+                .is_in(balances.iter().map(|(_, batch_id, _)| *batch_id)),
+        )
+        .filter(tenant::entities::stock_batches::Column::ExpiryDate.gte(today))
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch batches: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch stock on hand" }))
+        })?;
+
+    let mut usable = HashMap::new();
+    for batch in batches {
+        let on_hand: i64 = balances
+            .iter()
+            .filter(|(_, batch_id, _)| *batch_id == batch.id)
+            .map(|(_, _, on_hand)| on_hand)
+            .sum();
+        *usable.entry(batch.product_id).or_default() += on_hand;
+    }
+
+    Ok(usable)
+}
+
+/// Locks a product's batch rows for the rest of the transaction so two
+/// withdrawals cannot both spend the same balance.
+pub async fn lock_product_batches<C: ConnectionTrait>(
+    db: &C,
+    product_id: i32,
+) -> Result<(), ApiResponse> {
+    tenant::entities::stock_batches::Entity::find()
+        .filter(tenant::entities::stock_batches::Column::ProductId.eq(product_id))
+        .lock_exclusive()
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to lock batches for product {}: {}", product_id, err);
+            ApiResponse::new(500, json!({ "message": "Failed to reserve stock" }))
+        })?;
+
+    Ok(())
+}
+
+/// First-expiry, first-out: takes `quantity` from the store's unexpired
+/// batches, soonest expiry first. Fails without picking anything if the
+/// store cannot cover the whole quantity.
+pub async fn pick_fefo<C: ConnectionTrait>(
+    db: &C,
+    store_id: i32,
+    product_id: i32,
+    quantity: i64,
+) -> Result<Vec<BatchQuantity>, ApiResponse> {
+    let today = Utc::now().date_naive();
+    let available: Vec<BatchQuantity> = store_batches(db, store_id, product_id)
+        .await?
+        .into_iter()
+        .filter(|line| line.batch.expiry_date >= today)
+        .collect();
+
+    let on_hand: i64 = available.iter().map(|line| line.quantity).sum();
+    if on_hand < quantity {
+        return Err(ApiResponse::new(
+            409,
+            json!({
+                "message": "Insufficient stock",
+                "requested": quantity,
+                "available": on_hand,
+            }),
+        ));
+    }
+
+    let mut remaining = quantity;
+    let mut picks = Vec::new();
+    for line in available {
+        if remaining == 0 {
+            break;
+        }
+        let take = remaining.min(line.quantity);
+        remaining -= take;
+        picks.push(BatchQuantity {
+            batch: line.batch,
+            quantity: take,
+        });
+    }
+
+    Ok(picks)
+}
+
+/// Appends a movement to the ledger. Movements are never edited; mistakes
+/// are corrected with an adjustment. Movements of controlled products are
+/// also written to the controlled substances register.
+pub async fn record_movement<C: ConnectionTrait>(
+    db: &C,
+    movement: NewMovement<'_>,
+) -> Result<tenant::entities::stock_movements::Model, ApiResponse> {
+    let movement = tenant::entities::stock_movements::ActiveModel {
+        store_id: Set(movement.store_id),
+        product_id: Set(movement.batch.product_id),
+        batch_id: Set(movement.batch.id),
+        movement_type: Set(movement.movement_type),
+        quantity: Set(movement.quantity),
+        transfer_pid: Set(movement.transfer_pid),
+        reference: Set(movement.reference),
+        reason: Set(movement.reason),
+        performed_by: Set(movement.performed_by),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to record stock movement: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to record stock movement" }))
+    })?;
+
+    record_register_entry(db, &movement).await?;
+
+    Ok(movement)
+}
+
+/// Stock on hand of a single batch in a store.
+pub async fn batch_on_hand<C: ConnectionTrait>(
+    db: &C,
+    store_id: i32,
+    batch: &tenant::entities::stock_batches::Model,
+) -> Result<i64, ApiResponse> {
+    Ok(
+        batch_balances(db, Some(store_id), Some(vec![batch.product_id]))
+            .await?
+            .into_iter()
+            .find(|(_, batch_id, _)| *batch_id == batch.id)
+            .map(|(_, _, on_hand)| on_hand)
+            .unwrap_or(0),
+    )
+}
+
+/// Books stock into a store, creating the batch the first time its number is
+/// seen for the product. A known batch number must carry the same expiry.
+#[allow(clippy::too_many_arguments)]
+pub async fn receive_stock<C: ConnectionTrait>(
+    db: &C,
+    store_id: i32,
+    product: &tenant::entities::pharmacy_products::Model,
+    batch_number: &str,
+    expiry_date: NaiveDate,
+    unit_cost: Option<Decimal>,
+    quantity: i32,
+    reference: Option<String>,
+    performed_by: Uuid,
+) -> Result<
+    (
+        tenant::entities::stock_batches::Model,
+        tenant::entities::stock_movements::Model,
+    ),
+    ApiResponse,
+> {
+    let existing = tenant::entities::stock_batches::Entity::find()
+        .filter(tenant::entities::stock_batches::Column::ProductId.eq(product.id))
+        .filter(tenant::entities::stock_batches::Column::BatchNumber.eq(batch_number))
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch batch {}: {}", batch_number, err);
+            ApiResponse::new(500, json!({ "message": "Failed to receive stock" }))
+        })?;
+
+    let batch = match existing {
+        Some(batch) if batch.expiry_date != expiry_date => {
+            return Err(ApiResponse::new(
+                409,
+                json!({
+                    "message": format!(
+                        "Batch {} is already recorded with expiry {}",
+                        batch.batch_number, batch.expiry_date
+                    ),
+                }),
+            ));
+        }
+        Some(batch) => batch,
+        None => tenant::entities::stock_batches::ActiveModel {
+            product_id: Set(product.id),
+            batch_number: Set(batch_number.to_string()),
+            expiry_date: Set(expiry_date),
+            unit_cost: Set(unit_cost),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to create batch {}: {}", batch_number, err);
+            ApiResponse::new(500, json!({ "message": "Failed to receive stock" }))
+        })?,
+    };
+
+    let movement = record_movement(
+        db,
+        NewMovement {
+            store_id,
+            batch: &batch,
+            movement_type: StockMovementType::Receipt,
+            quantity,
+            transfer_pid: None,
+            reference,
+            reason: None,
+            performed_by,
+        },
+    )
+    .await?;
+
+    Ok((batch, movement))
+}
+
+pub async fn find_product(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+) -> Result<tenant::entities::pharmacy_products::Model, ApiResponse> {
+    tenant::entities::pharmacy_products::Entity::find_by_pid(pid)
+        .filter(tenant::entities::pharmacy_products::Column::DeletedAt.is_null())
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch product {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch product" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Product not found" })))
+}
+
+pub async fn find_store(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+) -> Result<tenant::entities::pharmacy_stores::Model, ApiResponse> {
+    tenant::entities::pharmacy_stores::Entity::find_by_pid(pid)
+        .filter(tenant::entities::pharmacy_stores::Column::DeletedAt.is_null())
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch store {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch store" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Store not found" })))
+}
+
+pub async fn find_batch(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+) -> Result<tenant::entities::stock_batches::Model, ApiResponse> {
+    tenant::entities::stock_batches::Entity::find_by_pid(pid)
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch batch {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch batch" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Batch not found" })))
+}
+
+/// The product's display name, e.g. "Amoxicillin (Amoxil) 500mg capsule".
+pub fn product_label(product: &tenant::entities::pharmacy_products::Model) -> String {
+    let mut label = product.generic_name.clone();
+    if let Some(brand_name) = &product.brand_name {
+        label.push_str(&format!(" ({})", brand_name));
+    }
+    if let Some(strength) = &product.strength {
+        label.push_str(&format!(" {}", strength));
+    }
+    label.push_str(&format!(" {}", product.dosage_form));
+    label
+}
+
+pub fn product_json(product: &tenant::entities::pharmacy_products::Model) -> Value {
+    json!({
+        "pid": product.pid,
+        "sku": product.sku,
+        "generic_name": product.generic_name,
+        "brand_name": product.brand_name,
+        "strength": product.strength,
+        "dosage_form": product.dosage_form,
+        "pack_size": product.pack_size,
+        "unit": product.unit,
+        "category": product.category,
+        "reorder_level": product.reorder_level,
+        "unit_price": product.unit_price,
+        "is_controlled": product.is_controlled,
+        "controlled_schedule": product.controlled_schedule,
+        "is_sold_online": product.is_sold_online,
+        "requires_prescription": product.requires_prescription,
+        "label": product_label(product),
+        "is_active": product.is_active,
+        "created_at": product.created_at,
+        "updated_at": product.updated_at,
+    })
+}
+
+pub fn store_json(store: &tenant::entities::pharmacy_stores::Model) -> Value {
+    json!({
+        "pid": store.pid,
+        "name": store.name,
+        "code": store.code,
+        "store_type": store.store_type,
+        "location": store.location,
+        "manager_id": store.manager_id,
+        "alert_email": store.alert_email,
+        "alert_phone": store.alert_phone,
+        "is_active": store.is_active,
+        "created_at": store.created_at,
+        "updated_at": store.updated_at,
+    })
+}
+
+pub fn batch_json(batch: &tenant::entities::stock_batches::Model, on_hand: Option<i64>) -> Value {
+    let today = Utc::now().date_naive();
+    json!({
+        "pid": batch.pid,
+        "batch_number": batch.batch_number,
+        "expiry_date": batch.expiry_date,
+        "is_expired": batch.expiry_date < today,
+        "days_to_expiry": (batch.expiry_date - today).num_days(),
+        "unit_cost": batch.unit_cost,
+        "on_hand": on_hand,
+        "created_at": batch.created_at,
+    })
+}
+
+pub fn stock_movement_json(
+    movement: &tenant::entities::stock_movements::Model,
+    batch: Option<&tenant::entities::stock_batches::Model>,
+) -> Value {
+    json!({
+        "pid": movement.pid,
+        "movement_type": movement.movement_type,
+        "quantity": movement.quantity,
+        "batch": batch.map(|batch| batch_json(batch, None)),
+        "transfer_pid": movement.transfer_pid,
+        "reference": movement.reference,
+        "reason": movement.reason,
+        "performed_by": movement.performed_by,
+        "created_at": movement.created_at,
+    })
+}
@@ -0,0 +1,477 @@
+use std::collections::{HashMap, HashSet};
+
+use actix_web::HttpRequest;
+use chrono::Utc;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        main::entities::sea_orm_active_enums::FileVisibility,
+        tenant::{
+            self,
+            entities::sea_orm_active_enums::PreauthorizationStatus,
+            migrations::sea_orm::{
+                ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection,
+                EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+            },
+        },
+    },
+    handlers::services::insurance_claims::ClaimAttachmentForm,
+    utils::{
+        api_response::ApiResponse,
+        app_state::AppState,
+        insurance::{PreauthorizationDecision, PreauthorizationResponse},
+        multipart::upload_file,
+    },
+};
+
+pub async fn find_preauthorization(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+) -> Result<tenant::entities::preauthorizations::Model, ApiResponse> {
+    tenant::entities::preauthorizations::Entity::find_by_pid(pid)
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch pre-authorization {}: {}", pid, err);
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to fetch pre-authorization" }),
+            )
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Pre-authorization not found" })))
+}
+
+pub async fn preauthorization_lines<C: ConnectionTrait>(
+    db: &C,
+    preauthorization_id: i32,
+) -> Result<Vec<tenant::entities::preauthorization_lines::Model>, ApiResponse> {
+    tenant::entities::preauthorization_lines::Entity::find()
+        .filter(
+            tenant::entities::preauthorization_lines::Column::PreauthorizationId
+                .eq(preauthorization_id),
+        )
+        .order_by_asc(tenant::entities::preauthorization_lines::Column::Id)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to fetch lines of pre-authorization {}: {}",
+                preauthorization_id,
+                err
+            );
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to fetch pre-authorization" }),
+            )
+        })
+}
+
+pub async fn preauthorization_attachments<C: ConnectionTrait>(
+    db: &C,
+    preauthorization_id: i32,
+) -> Result<Vec<tenant::entities::preauthorization_attachments::Model>, ApiResponse> {
+    tenant::entities::preauthorization_attachments::Entity::find()
+        .filter(
+            tenant::entities::preauthorization_attachments::Column::PreauthorizationId
+                .eq(preauthorization_id),
+        )
+        .order_by_asc(tenant::entities::preauthorization_attachments::Column::CreatedAt)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to fetch attachments of pre-authorization {}: {}",
+                preauthorization_id,
+                err
+            );
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to fetch pre-authorization" }),
+            )
+        })
+}
+
+pub async fn store_preauthorization_attachments(
+    app_state: &AppState,
+    req: &HttpRequest,
+    tenant_db: &DatabaseConnection,
+    preauthorization: &tenant::entities::preauthorizations::Model,
+    patient_id: i32,
+    form: ClaimAttachmentForm,
+    uploaded_by: Uuid,
+) -> Result<Vec<tenant::entities::preauthorization_attachments::Model>, ApiResponse> {
+    let mut attachments = Vec::with_capacity(form.attachments.len());
+
+    for attachment in form.attachments {
+        let size_bytes = attachment.content.len() as i64;
+        let file_pid = upload_file(
+            req,
+            app_state,
+            &format!(
+                "preauthorizations/{}/{}-{}",
+                preauthorization.pid,
+                Uuid::new_v4(),
+                attachment.file_name
+            ),
+            attachment.content,
+            &attachment.content_type,
+            Some(patient_id),
+            FileVisibility::Tenant,
+        )
+        .await?;
+
+        let saved = tenant::entities::preauthorization_attachments::ActiveModel {
+            preauthorization_id: Set(preauthorization.id),
+            file_pid: Set(file_pid),
+            original_name: Set(attachment.file_name),
+            content_type: Set(attachment.content_type),
+            size_bytes: Set(size_bytes),
+            uploaded_by: Set(uploaded_by),
+            ..Default::default()
+        }
+        .insert(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to save attachment for {}: {}",
+                preauthorization.pid,
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to save attachment" }))
+        })?;
+
+        attachments.push(saved);
+    }
+
+    Ok(attachments)
+}
+
+/// Records what the insurer's system answered. A pending answer only
+/// keeps the insurer's reference; the decision is recorded later.
+pub async fn apply_insurer_decision<C: ConnectionTrait>(
+    db: &C,
+    preauthorization: tenant::entities::preauthorizations::Model,
+    response: &PreauthorizationResponse,
+) -> Result<tenant::entities::preauthorizations::Model, ApiResponse> {
+    let preauth_number = preauthorization.preauth_number.clone();
+    let estimated_amount = preauthorization.estimated_amount;
+    let now = Utc::now().naive_utc();
+
+    let mut active_model: tenant::entities::preauthorizations::ActiveModel =
+        preauthorization.into();
+    if let Some(reference) = &response.reference {
+        active_model.insurer_reference = Set(Some(reference.clone()));
+    }
+    match response.decision {
+        PreauthorizationDecision::Approved => {
+            active_model.status = Set(PreauthorizationStatus::Approved);
+            active_model.approved_amount = Set(Some(
+                response
+                    .approved_amount
+                    .unwrap_or(estimated_amount)
+                    .round_dp(2),
+            ));
+            active_model.authorization_number = Set(response
+                .authorization_number
+                .clone()
+                .or_else(|| response.reference.clone()));
+            active_model.valid_until = Set(response.valid_until);
+            active_model.decided_at = Set(Some(now));
+        }
+        PreauthorizationDecision::Denied => {
+            active_model.status = Set(PreauthorizationStatus::Denied);
+            active_model.denial_reason = Set(response.message.clone());
+            active_model.decided_at = Set(Some(now));
+        }
+        PreauthorizationDecision::Pending => {}
+    }
+    active_model.updated_at = Set(now);
+
+    active_model.update(db).await.map_err(|err| {
+        log::error!(
+            "Failed to record insurer decision on {}: {}",
+            preauth_number,
+            err
+        );
+        ApiResponse::new(
+            500,
+            json!({ "message": "Failed to update pre-authorization" }),
+        )
+    })
+}
+
+/// How many days a claim's service date may be from a pre-authorization's
+/// planned date for the two to be matched on their services.
+const PLANNED_DATE_WINDOW_DAYS: i64 = 7;
+
+/// Attaches the approved pre-authorizations for this claim's care and
+/// returns their authorization numbers. One raised for an encounter only
+/// covers that encounter's bill; one without needs a service in common
+/// with the claim and a planned date within [`PLANNED_DATE_WINDOW_DAYS`] of
+/// the service. Only those still valid on the service date and not used by
+/// another claim are taken; expired ones stay expired.
+pub async fn link_preauthorizations<C: ConnectionTrait>(
+    db: &C,
+    claim: &tenant::entities::insurance_claims::Model,
+    bill: &tenant::entities::patient_bills::Model,
+    claim_lines: &[tenant::entities::insurance_claim_lines::Model],
+) -> Result<Vec<String>, ApiResponse> {
+    let link_error = |err| {
+        log::error!(
+            "Failed to link pre-authorizations to {}: {}",
+            claim.claim_number,
+            err
+        );
+        ApiResponse::new(500, json!({ "message": "Failed to create claim" }))
+    };
+
+    let service_date = bill.created_at.date();
+
+    let candidates = tenant::entities::preauthorizations::Entity::find()
+        .filter(tenant::entities::preauthorizations::Column::PatientPid.eq(claim.patient_pid))
+        .filter(
+            tenant::entities::preauthorizations::Column::PatientInsurancePid
+                .eq(claim.patient_insurance_pid),
+        )
+        .filter(
+            tenant::entities::preauthorizations::Column::Status
+                .eq(PreauthorizationStatus::Approved),
+        )
+        .filter(tenant::entities::preauthorizations::Column::ApprovedAmount.is_not_null())
+        .filter(tenant::entities::preauthorizations::Column::ClaimId.is_null())
+        .filter(
+            Condition::any()
+                .add(tenant::entities::preauthorizations::Column::ValidUntil.is_null())
+                .add(tenant::entities::preauthorizations::Column::ValidUntil.gte(service_date)),
+        )
+        .order_by_asc(tenant::entities::preauthorizations::Column::DecidedAt)
+        .all(db)
+        .await
+        .map_err(link_error)?;
+
+    if candidates.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let encounter_pid = match bill.encounter_id {
+        Some(encounter_id) => tenant::entities::encounters::Entity::find_by_id(encounter_id)
+            .select_only()
+            .column(tenant::entities::encounters::Column::Pid)
+            .into_tuple::<Uuid>()
+            .one(db)
+            .await
+            .map_err(link_error)?,
+        None => None,
+    };
+
+    let claimed_codes = claim_lines
+        .iter()
+        .filter_map(|line| line.service_code.as_deref())
+        .collect::<HashSet<_>>();
+
+    let mut planned_codes: HashMap<i32, Vec<String>> = HashMap::new();
+    tenant::entities::preauthorization_lines::Entity::find()
+        .filter(
+            tenant::entities::preauthorization_lines::Column::PreauthorizationId.is_in(
+                candidates
+                    .iter()
+                    .filter(|preauth| preauth.encounter_pid.is_none())
+                    .map(|preauth| preauth.id),
+            ),
+        )
+        .select_only()
+        .column(tenant::entities::preauthorization_lines::Column::PreauthorizationId)
+        .column(tenant::entities::preauthorization_lines::Column::ServiceCode)
+        .into_tuple::<(i32, String)>()
+        .all(db)
+        .await
+        .map_err(link_error)?
+        .into_iter()
+        .for_each(|(preauthorization_id, service_code)| {
+            planned_codes
+                .entry(preauthorization_id)
+                .or_default()
+                .push(service_code)
+        });
+
+    let preauthorizations = candidates
+        .into_iter()
+        .filter(|preauth| match preauth.encounter_pid {
+            Some(pid) => encounter_pid == Some(pid),
+            None => {
+                let planned_date = preauth
+                    .planned_date
+                    .unwrap_or_else(|| preauth.created_at.date());
+
+                (service_date - planned_date).num_days().abs() <= PLANNED_DATE_WINDOW_DAYS
+                    && planned_codes.get(&preauth.id).is_some_and(|codes| {
+                        codes
+                            .iter()
+                            .any(|code| claimed_codes.contains(code.as_str()))
+                    })
+            }
+        })
+        .collect::<Vec<_>>();
+
+    if preauthorizations.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    tenant::entities::preauthorizations::Entity::update_many()
+        .set(tenant::entities::preauthorizations::ActiveModel {
+            claim_id: Set(Some(claim.id)),
+            updated_at: Set(Utc::now().naive_utc()),
+            ..Default::default()
+        })
+        .filter(
+            tenant::entities::preauthorizations::Column::Id
+                .is_in(preauthorizations.iter().map(|preauth| preauth.id)),
+        )
+        .exec(db)
+        .await
+        .map_err(link_error)?;
+
+    Ok(preauthorizations
+        .into_iter()
+        .map(|preauth| {
+            preauth
+                .authorization_number
+                .unwrap_or(preauth.preauth_number)
+        })
+        .collect())
+}
+
+/// Frees the pre-authorizations of a rejected claim so a corrected claim
+/// for the same care can use them.
+pub async fn unlink_preauthorizations<C: ConnectionTrait>(
+    db: &C,
+    claim: &tenant::entities::insurance_claims::Model,
+) -> Result<(), ApiResponse> {
+    tenant::entities::preauthorizations::Entity::update_many()
+        .set(tenant::entities::preauthorizations::ActiveModel {
+            claim_id: Set(None),
+            updated_at: Set(Utc::now().naive_utc()),
+            ..Default::default()
+        })
+        .filter(tenant::entities::preauthorizations::Column::ClaimId.eq(claim.id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to release pre-authorizations of {}: {}",
+                claim.claim_number,
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to update claim" }))
+        })?;
+
+    Ok(())
+}
+
+/// Marks approved pre-authorizations past their validity as expired,
+/// unless a claim has already used them.
+pub async fn expire_preauthorizations(tenant_db: &DatabaseConnection) -> Result<u64, ApiResponse> {
+    let today = Utc::now().date_naive();
+
+    let result = tenant::entities::preauthorizations::Entity::update_many()
+        .set(tenant::entities::preauthorizations::ActiveModel {
+            status: Set(PreauthorizationStatus::Expired),
+            updated_at: Set(Utc::now().naive_utc()),
+            ..Default::default()
+        })
+        .filter(
+            tenant::entities::preauthorizations::Column::Status
+                .eq(PreauthorizationStatus::Approved),
+        )
+        .filter(tenant::entities::preauthorizations::Column::ClaimId.is_null())
+        .filter(tenant::entities::preauthorizations::Column::ValidUntil.lt(today))
+        .exec(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to expire pre-authorizations: {}", err);
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to expire pre-authorizations" }),
+            )
+        })?;
+
+    Ok(result.rows_affected)
+}
+
+pub fn preauthorization_json(preauth: &tenant::entities::preauthorizations::Model) -> Value {
+    json!({
+        "pid": preauth.pid,
+        "preauth_number": preauth.preauth_number,
+        "patient_pid": preauth.patient_pid,
+        "patient_insurance_pid": preauth.patient_insurance_pid,
+        "insurance_provider_pid": preauth.insurance_provider_pid,
+        "encounter_pid": preauth.encounter_pid,
+        "planned_date": preauth.planned_date,
+        "status": preauth.status,
+        "diagnosis_codes": preauth.diagnosis_codes,
+        "clinical_notes": preauth.clinical_notes,
+        "estimated_amount": preauth.estimated_amount,
+        "approved_amount": preauth.approved_amount,
+        "currency": preauth.currency,
+        "authorization_number": preauth.authorization_number,
+        "insurer_reference": preauth.insurer_reference,
+        "denial_reason": preauth.denial_reason,
+        "valid_until": preauth.valid_until,
+        "is_linked_to_claim": preauth.claim_id.is_some(),
+        "requested_by": preauth.requested_by,
+        "submitted_at": preauth.submitted_at,
+        "decided_at": preauth.decided_at,
+        "decided_by": preauth.decided_by,
+        "created_at": preauth.created_at,
+        "updated_at": preauth.updated_at,
+    })
+}
+
+pub fn preauthorization_detail_json(
+    preauth: &tenant::entities::preauthorizations::Model,
+    claim: Option<&tenant::entities::insurance_claims::Model>,
+    lines: &[tenant::entities::preauthorization_lines::Model],
+    attachments: &[tenant::entities::preauthorization_attachments::Model],
+) -> Value {
+    let mut value = preauthorization_json(preauth);
+    value["claim"] = json!(claim.map(|claim| json!({
+        "pid": claim.pid,
+        "claim_number": claim.claim_number,
+        "status": claim.status,
+    })));
+    value["lines"] = json!(
+        lines
+            .iter()
+            .map(|line| json!({
+                "pid": line.pid,
+                "service_code": line.service_code,
+                "description": line.description,
+                "quantity": line.quantity,
+                "unit_price": line.unit_price,
+                "total_amount": line.total_amount,
+            }))
+            .collect::<Vec<_>>()
+    );
+    value["attachments"] = json!(
+        attachments
+            .iter()
+            .map(preauthorization_attachment_json)
+            .collect::<Vec<_>>()
+    );
+    value
+}
+
+pub fn preauthorization_attachment_json(
+    attachment: &tenant::entities::preauthorization_attachments::Model,
+) -> Value {
+    json!({
+        "pid": attachment.pid,
+        "original_name": attachment.original_name,
+        "content_type": attachment.content_type,
+        "size_bytes": attachment.size_bytes,
+        "uploaded_by": attachment.uploaded_by,
+        "created_at": attachment.created_at,
+    })
+}
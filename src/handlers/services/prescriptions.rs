@@ -0,0 +1,120 @@
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::tenant::{
+        self,
+        entities::sea_orm_active_enums::PrescriptionStatus,
+        migrations::sea_orm::{
+            ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+        },
+    },
+    utils::api_response::ApiResponse,
+};
+
+/// Prescriptions a pharmacist can still dispense against.
+pub fn is_dispensable(status: &PrescriptionStatus) -> bool {
+    matches!(
+        status,
+        PrescriptionStatus::Pending | PrescriptionStatus::PartiallyDispensed
+    )
+}
+
+/// The status implied by how much of each item has been dispensed.
+pub fn dispensing_status(
+    items: &[tenant::entities::prescription_items::Model],
+) -> PrescriptionStatus {
+    if items
+        .iter()
+        .all(|item| item.quantity_dispensed >= item.quantity)
+    {
+        PrescriptionStatus::Dispensed
+    } else if items.iter().any(|item| item.quantity_dispensed > 0) {
+        PrescriptionStatus::PartiallyDispensed
+    } else {
+        PrescriptionStatus::Pending
+    }
+}
+
+pub async fn find_prescription(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+) -> Result<tenant::entities::prescriptions::Model, ApiResponse> {
+    tenant::entities::prescriptions::Entity::find_by_pid(pid)
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch prescription {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch prescription" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Prescription not found" })))
+}
+
+pub async fn fetch_prescription_items(
+    tenant_db: &DatabaseConnection,
+    prescription_ids: Vec<i32>,
+) -> Result<Vec<tenant::entities::prescription_items::Model>, ApiResponse> {
+    tenant::entities::prescription_items::Entity::find()
+        .filter(
+            tenant::entities::prescription_items::Column::PrescriptionId.is_in(prescription_ids),
+        )
+        .order_by_asc(tenant::entities::prescription_items::Column::Id)
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch prescription items: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch prescription" }))
+        })
+}
+
+/// "1 tablet, twice daily, oral, for 5 days", as printed on the label.
+pub fn dosage_instructions(item: &tenant::entities::prescription_items::Model) -> String {
+    let mut parts = vec![item.dose.clone(), item.frequency.clone()];
+    if let Some(route) = &item.route {
+        parts.push(route.clone());
+    }
+    if let Some(days) = item.duration_days {
+        parts.push(format!(
+            "for {} day{}",
+            days,
+            if days == 1 { "" } else { "s" }
+        ));
+    }
+    parts.join(", ")
+}
+
+pub fn prescription_item_json(item: &tenant::entities::prescription_items::Model) -> Value {
+    json!({
+        "pid": item.pid,
+        "drug_name": item.drug_name,
+        "dose": item.dose,
+        "route": item.route,
+        "frequency": item.frequency,
+        "duration_days": item.duration_days,
+        "quantity": item.quantity,
+        "quantity_dispensed": item.quantity_dispensed,
+        "quantity_remaining": (item.quantity - item.quantity_dispensed).max(0),
+        "instructions": item.instructions,
+        "dosage_instructions": dosage_instructions(item),
+    })
+}
+
+pub fn prescription_json(
+    prescription: &tenant::entities::prescriptions::Model,
+    items: &[tenant::entities::prescription_items::Model],
+) -> Value {
+    json!({
+        "pid": prescription.pid,
+        "patient_pid": prescription.patient_pid,
+        "prescriber_id": prescription.prescriber_id,
+        "status": prescription.status,
+        "notes": prescription.notes,
+        "prescribed_at": prescription.prescribed_at,
+        "items": items.iter().map(prescription_item_json).collect::<Vec<_>>(),
+        "cancelled_at": prescription.cancelled_at,
+        "cancelled_by": prescription.cancelled_by,
+        "cancel_reason": prescription.cancel_reason,
+        "created_at": prescription.created_at,
+        "updated_at": prescription.updated_at,
+    })
+}
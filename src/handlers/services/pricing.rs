@@ -0,0 +1,527 @@
+use actix_web::{HttpRequest, web};
+use chrono::{NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        main::{self, entities::sea_orm_active_enums::VerificationStatus},
+        tenant::{
+            self,
+            entities::sea_orm_active_enums::{EncounterType, PayerType},
+            migrations::sea_orm::{
+                ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, EntityTrait, JoinType,
+                QueryFilter, QueryOrder, QuerySelect, RelationTrait,
+            },
+        },
+    },
+    handlers::services::patient_charges::{CHARGE_SOURCE_ENCOUNTER, NewCharge, add_patient_charge},
+    utils::{api_response::ApiResponse, app_state::AppState, jwt::get_tenant_id},
+};
+
+/// A service priced for one payer on one day.
+#[derive(Debug, Clone)]
+pub struct ServicePrice {
+    pub service: tenant::entities::billable_services::Model,
+    /// The list the price came from; the default cash list when the payer's
+    /// own list does not price the service.
+    pub price_list: tenant::entities::price_lists::Model,
+    pub amount: Decimal,
+    pub currency: String,
+}
+
+/// The facility's currency. Prices are entered and charged in it.
+pub async fn facility_currency(
+    req: &HttpRequest,
+    app_state: &web::Data<AppState>,
+) -> Result<String, ApiResponse> {
+    let (tenant_id, _, _) = get_tenant_id(req, app_state).await?;
+
+    main::entities::tenants::Entity::find_by_id(tenant_id)
+        .one(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch tenant {}: {}", tenant_id, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch facility" }))
+        })?
+        .map(|tenant| tenant.currency)
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Tenant not found" })))
+}
+
+/// The price list a patient is billed on. A list chosen by staff wins;
+/// otherwise the list of the patient's verified primary insurer, falling
+/// back to the default cash list.
+pub async fn payer_price_list<C: ConnectionTrait>(
+    db: &C,
+    main_db: &DatabaseConnection,
+    patient_pid: Uuid,
+    requested: Option<Uuid>,
+) -> Result<Option<tenant::entities::price_lists::Model>, ApiResponse> {
+    if let Some(pid) = requested {
+        return active_price_lists()
+            .filter(tenant::entities::price_lists::Column::Pid.eq(pid))
+            .one(db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to fetch price list {}: {}", pid, err);
+                ApiResponse::new(500, json!({ "message": "Failed to fetch price list" }))
+            })?
+            .map(Some)
+            .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Price list not found" })));
+    }
+
+    if let Some(insurer_pid) = primary_insurer_pid(main_db, patient_pid).await? {
+        let price_list = active_price_lists()
+            .filter(tenant::entities::price_lists::Column::PayerType.eq(PayerType::Insurance))
+            .filter(tenant::entities::price_lists::Column::InsuranceProviderPid.eq(insurer_pid))
+            .order_by_asc(tenant::entities::price_lists::Column::CreatedAt)
+            .one(db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to fetch price list of {}: {}", insurer_pid, err);
+                ApiResponse::new(500, json!({ "message": "Failed to fetch price list" }))
+            })?;
+
+        if price_list.is_some() {
+            return Ok(price_list);
+        }
+    }
+
+    default_price_list(db).await
+}
+
+/// The cash list used when nothing else applies.
+pub async fn default_price_list<C: ConnectionTrait>(
+    db: &C,
+) -> Result<Option<tenant::entities::price_lists::Model>, ApiResponse> {
+    active_price_lists()
+        .filter(tenant::entities::price_lists::Column::IsDefault.eq(true))
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch default price list: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch price list" }))
+        })
+}
+
+/// The price of a service on a list on the given day, in the facility's
+/// currency. Services the list does not price are charged at the default
+/// cash price.
+pub async fn service_price<C: ConnectionTrait>(
+    db: &C,
+    service: &tenant::entities::billable_services::Model,
+    price_list: Option<&tenant::entities::price_lists::Model>,
+    currency: &str,
+    on: NaiveDate,
+) -> Result<Option<ServicePrice>, ApiResponse> {
+    let default_list = match price_list {
+        Some(price_list) if price_list.is_default => None,
+        _ => default_price_list(db).await?,
+    };
+
+    for price_list in price_list.into_iter().chain(default_list.as_ref()) {
+        if let Some(price) = effective_price(db, price_list.id, service.id, currency, on).await? {
+            return Ok(Some(ServicePrice {
+                service: service.clone(),
+                price_list: price_list.clone(),
+                amount: price.amount,
+                currency: price.currency,
+            }));
+        }
+    }
+
+    Ok(None)
+}
+
+/// The price in force on a day: started on or before it and not yet ended.
+pub async fn effective_price<C: ConnectionTrait>(
+    db: &C,
+    price_list_id: i32,
+    service_id: i32,
+    currency: &str,
+    on: NaiveDate,
+) -> Result<Option<tenant::entities::service_prices::Model>, ApiResponse> {
+    tenant::entities::service_prices::Entity::find()
+        .filter(tenant::entities::service_prices::Column::PriceListId.eq(price_list_id))
+        .filter(tenant::entities::service_prices::Column::ServiceId.eq(service_id))
+        .filter(tenant::entities::service_prices::Column::Currency.eq(currency))
+        .filter(tenant::entities::service_prices::Column::EffectiveFrom.lte(on))
+        .filter(
+            Condition::any()
+                .add(tenant::entities::service_prices::Column::EffectiveTo.is_null())
+                .add(tenant::entities::service_prices::Column::EffectiveTo.gte(on)),
+        )
+        .order_by_desc(tenant::entities::service_prices::Column::EffectiveFrom)
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to fetch price of service {} on list {}: {}",
+                service_id,
+                price_list_id,
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to fetch price" }))
+        })
+}
+
+/// Charges a priced service to the patient.
+pub async fn charge_service<C: ConnectionTrait>(
+    db: &C,
+    price: &ServicePrice,
+    patient_pid: Uuid,
+    source_type: &str,
+    source_pid: Uuid,
+    quantity: i32,
+    created_by: Option<Uuid>,
+) -> Result<tenant::entities::patient_charges::Model, ApiResponse> {
+    add_patient_charge(
+        db,
+        NewCharge {
+            patient_pid,
+            source_type,
+            source_pid,
+            description: price.service.name.clone(),
+            quantity,
+            unit_price: price.amount,
+            service_code: Some(price.service.code.clone()),
+            price_list_id: Some(price.price_list.id),
+            created_by,
+        },
+    )
+    .await
+}
+
+/// The catalogue price of a product linked to a service. Unlinked or
+/// unpriced products are charged at their own unit price.
+pub async fn product_price<C: ConnectionTrait>(
+    db: &C,
+    product: &tenant::entities::pharmacy_products::Model,
+    price_list: Option<&tenant::entities::price_lists::Model>,
+    currency: &str,
+    on: NaiveDate,
+) -> Result<Option<ServicePrice>, ApiResponse> {
+    let Some(service_id) = product.service_id else {
+        return Ok(None);
+    };
+
+    let service = tenant::entities::billable_services::Entity::find_by_id(service_id)
+        .filter(tenant::entities::billable_services::Column::IsActive.eq(true))
+        .filter(tenant::entities::billable_services::Column::DeletedAt.is_null())
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch service {}: {}", service_id, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch service" }))
+        })?;
+
+    match service {
+        Some(service) => service_price(db, &service, price_list, currency, on).await,
+        None => Ok(None),
+    }
+}
+
+/// The consultation charged when an encounter of this type is recorded, if
+/// the facility bills for one.
+pub async fn encounter_service<C: ConnectionTrait>(
+    db: &C,
+    encounter_type: EncounterType,
+) -> Result<Option<tenant::entities::billable_services::Model>, ApiResponse> {
+    tenant::entities::billable_services::Entity::find()
+        .filter(tenant::entities::billable_services::Column::EncounterType.eq(encounter_type))
+        .filter(tenant::entities::billable_services::Column::IsActive.eq(true))
+        .filter(tenant::entities::billable_services::Column::DeletedAt.is_null())
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch encounter service: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch service" }))
+        })
+}
+
+/// Charges the consultation for an encounter. Encounters are never held up
+/// by billing: when the facility has no consultation service or no price
+/// for the payer, nothing is charged.
+pub async fn charge_encounter<C: ConnectionTrait>(
+    db: &C,
+    main_db: &DatabaseConnection,
+    currency: &str,
+    encounter: &tenant::entities::encounters::Model,
+) -> Result<Option<tenant::entities::patient_charges::Model>, ApiResponse> {
+    let Some(service) = encounter_service(db, encounter.encounter_type.clone()).await? else {
+        return Ok(None);
+    };
+
+    let price_list = payer_price_list(db, main_db, encounter.patient_pid, None).await?;
+    let Some(price) = service_price(
+        db,
+        &service,
+        price_list.as_ref(),
+        currency,
+        encounter.started_at.date(),
+    )
+    .await?
+    else {
+        log::warn!(
+            "Service {} has no price in {}; encounter {} was not charged",
+            service.code,
+            currency,
+            encounter.pid
+        );
+        return Ok(None);
+    };
+
+    charge_service(
+        db,
+        &price,
+        encounter.patient_pid,
+        CHARGE_SOURCE_ENCOUNTER,
+        encounter.pid,
+        1,
+        encounter.practitioner_id,
+    )
+    .await
+    .map(Some)
+}
+
+pub async fn find_service(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+) -> Result<tenant::entities::billable_services::Model, ApiResponse> {
+    tenant::entities::billable_services::Entity::find_by_pid(pid)
+        .filter(tenant::entities::billable_services::Column::DeletedAt.is_null())
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch service {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch service" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Service not found" })))
+}
+
+pub async fn find_price_list(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+) -> Result<tenant::entities::price_lists::Model, ApiResponse> {
+    tenant::entities::price_lists::Entity::find_by_pid(pid)
+        .filter(tenant::entities::price_lists::Column::DeletedAt.is_null())
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch price list {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch price list" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Price list not found" })))
+}
+
+pub fn service_json(service: &tenant::entities::billable_services::Model) -> Value {
+    json!({
+        "pid": service.pid,
+        "code": service.code,
+        "name": service.name,
+        "category": service.category,
+        "description": service.description,
+        "encounter_type": service.encounter_type,
+        "is_active": service.is_active,
+        "created_at": service.created_at,
+        "updated_at": service.updated_at,
+    })
+}
+
+pub fn price_list_json(price_list: &tenant::entities::price_lists::Model) -> Value {
+    json!({
+        "pid": price_list.pid,
+        "name": price_list.name,
+        "payer_type": price_list.payer_type,
+        "insurance_provider_pid": price_list.insurance_provider_pid,
+        "scheme_name": price_list.scheme_name,
+        "is_default": price_list.is_default,
+        "is_active": price_list.is_active,
+        "created_at": price_list.created_at,
+        "updated_at": price_list.updated_at,
+    })
+}
+
+pub fn service_price_json(
+    price: &tenant::entities::service_prices::Model,
+    service: Option<&tenant::entities::billable_services::Model>,
+) -> Value {
+    json!({
+        "pid": price.pid,
+        "service": service.map(|service| json!({
+            "pid": service.pid,
+            "code": service.code,
+            "name": service.name,
+        })),
+        "amount": price.amount,
+        "currency": price.currency,
+        "effective_from": price.effective_from,
+        "effective_to": price.effective_to,
+        "created_at": price.created_at,
+    })
+}
+
+pub fn quoted_price_json(price: &ServicePrice) -> Value {
+    json!({
+        "service": service_json(&price.service),
+        "price_list": {
+            "pid": price.price_list.pid,
+            "name": price.price_list.name,
+            "payer_type": price.price_list.payer_type,
+        },
+        "amount": price.amount,
+        "currency": price.currency,
+    })
+}
+
+fn active_price_lists() -> tenant::migrations::sea_orm::Select<tenant::entities::price_lists::Entity>
+{
+    tenant::entities::price_lists::Entity::find()
+        .filter(tenant::entities::price_lists::Column::DeletedAt.is_null())
+        .filter(tenant::entities::price_lists::Column::IsActive.eq(true))
+}
+
+/// The patient's verified primary cover that is in force today, with its
+/// insurer. A patient without cover of their own is covered as an active
+/// dependent on someone else's policy, the holder's primary policy first.
+pub async fn primary_insurance(
+    main_db: &DatabaseConnection,
+    patient_pid: Uuid,
+) -> Result<
+    Option<(
+        main::entities::patient_insurance::Model,
+        main::entities::insurance_providers::Model,
+    )>,
+    ApiResponse,
+> {
+    let lookup_error = |err| {
+        log::error!(
+            "Failed to fetch insurance of patient {}: {}",
+            patient_pid,
+            err
+        );
+        ApiResponse::new(
+            500,
+            json!({ "message": "Failed to fetch patient insurance" }),
+        )
+    };
+
+    let own = cover_in_force()
+        .inner_join(main::entities::patients::Entity)
+        .find_also_related(main::entities::insurance_providers::Entity)
+        .filter(main::entities::patients::Column::Pid.eq(patient_pid))
+        .filter(main::entities::patient_insurance::Column::IsPrimary.eq(true))
+        .one(main_db)
+        .await
+        .map_err(lookup_error)?;
+
+    let cover = match own {
+        Some(cover) => Some(cover),
+        None => cover_in_force()
+            .inner_join(main::entities::insurance_dependents::Entity)
+            .join(
+                JoinType::InnerJoin,
+                main::entities::insurance_dependents::Relation::Patients.def(),
+            )
+            .find_also_related(main::entities::insurance_providers::Entity)
+            .filter(main::entities::patients::Column::Pid.eq(patient_pid))
+            .filter(main::entities::insurance_dependents::Column::IsActive.eq(true))
+            .filter(main::entities::insurance_dependents::Column::DeletedAt.is_null())
+            .order_by_desc(main::entities::patient_insurance::Column::IsPrimary)
+            .order_by_desc(main::entities::patient_insurance::Column::CreatedAt)
+            .one(main_db)
+            .await
+            .map_err(lookup_error)?,
+    };
+
+    Ok(cover.and_then(|(cover, provider)| provider.map(|provider| (cover, provider))))
+}
+
+/// A verified policy in force today that covers the patient, either as
+/// their own card or through an active dependent link.
+pub async fn patient_cover(
+    main_db: &DatabaseConnection,
+    patient_pid: Uuid,
+    insurance_pid: Uuid,
+) -> Result<
+    Option<(
+        main::entities::patient_insurance::Model,
+        main::entities::insurance_providers::Model,
+    )>,
+    ApiResponse,
+> {
+    let lookup_error = |err| {
+        log::error!(
+            "Failed to fetch insurance {} of patient {}: {}",
+            insurance_pid,
+            patient_pid,
+            err
+        );
+        ApiResponse::new(
+            500,
+            json!({ "message": "Failed to fetch patient insurance" }),
+        )
+    };
+
+    let own = cover_in_force()
+        .inner_join(main::entities::patients::Entity)
+        .find_also_related(main::entities::insurance_providers::Entity)
+        .filter(main::entities::patients::Column::Pid.eq(patient_pid))
+        .filter(main::entities::patient_insurance::Column::Pid.eq(insurance_pid))
+        .one(main_db)
+        .await
+        .map_err(lookup_error)?;
+
+    let cover = match own {
+        Some(cover) => Some(cover),
+        None => cover_in_force()
+            .inner_join(main::entities::insurance_dependents::Entity)
+            .join(
+                JoinType::InnerJoin,
+                main::entities::insurance_dependents::Relation::Patients.def(),
+            )
+            .find_also_related(main::entities::insurance_providers::Entity)
+            .filter(main::entities::patients::Column::Pid.eq(patient_pid))
+            .filter(main::entities::patient_insurance::Column::Pid.eq(insurance_pid))
+            .filter(main::entities::insurance_dependents::Column::IsActive.eq(true))
+            .filter(main::entities::insurance_dependents::Column::DeletedAt.is_null())
+            .one(main_db)
+            .await
+            .map_err(lookup_error)?,
+    };
+
+    Ok(cover.and_then(|(cover, provider)| provider.map(|provider| (cover, provider))))
+}
+
+/// Verified cover that is in force today.
+fn cover_in_force() -> main::migrations::sea_orm::Select<main::entities::patient_insurance::Entity>
+{
+    let today = Utc::now().date_naive();
+
+    main::entities::patient_insurance::Entity::find()
+        .filter(main::entities::patient_insurance::Column::DeletedAt.is_null())
+        .filter(
+            main::entities::patient_insurance::Column::VerificationStatus
+                .eq(VerificationStatus::Verified),
+        )
+        .filter(
+            Condition::any()
+                .add(main::entities::patient_insurance::Column::CoverageStartDate.is_null())
+                .add(main::entities::patient_insurance::Column::CoverageStartDate.lte(today)),
+        )
+        .filter(
+            Condition::any()
+                .add(main::entities::patient_insurance::Column::CoverageEndDate.is_null())
+                .add(main::entities::patient_insurance::Column::CoverageEndDate.gte(today)),
+        )
+}
+
+async fn primary_insurer_pid(
+    main_db: &DatabaseConnection,
+    patient_pid: Uuid,
+) -> Result<Option<Uuid>, ApiResponse> {
+    Ok(primary_insurance(main_db, patient_pid)
+        .await?
+        .map(|(_, provider)| provider.pid))
+}
@@ -0,0 +1,283 @@
+use std::collections::HashMap;
+
+use chrono::{Duration, NaiveDateTime, Utc};
+use rust_decimal::Decimal;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::tenant::{
+        self,
+        entities::sea_orm_active_enums::{PurchaseOrderStatus, StockMovementType},
+        migrations::sea_orm::{
+            ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
+            QueryFilter, QuerySelect, Set,
+        },
+    },
+    handlers::services::pharmacy::{batch_json, product_label},
+    utils::api_response::ApiResponse,
+};
+
+/// Orders that are still waiting on the supplier.
+pub const OPEN_ORDER_STATUSES: [PurchaseOrderStatus; 3] = [
+    PurchaseOrderStatus::Submitted,
+    PurchaseOrderStatus::Approved,
+    PurchaseOrderStatus::PartiallyReceived,
+];
+
+/// Appends a price to a supplier's history for a product. Prices are never
+/// edited, so the history shows how a supplier's cost moved over time.
+pub async fn record_supplier_price<C: ConnectionTrait>(
+    db: &C,
+    supplier_id: i32,
+    product_id: i32,
+    unit_cost: Decimal,
+    goods_received_note_id: Option<i32>,
+    recorded_by: Uuid,
+    effective_at: NaiveDateTime,
+) -> Result<tenant::entities::supplier_prices::Model, ApiResponse> {
+    tenant::entities::supplier_prices::ActiveModel {
+        supplier_id: Set(supplier_id),
+        product_id: Set(product_id),
+        unit_cost: Set(unit_cost),
+        goods_received_note_id: Set(goods_received_note_id),
+        recorded_by: Set(recorded_by),
+        effective_at: Set(effective_at),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .map_err(|err| {
+        log::error!(
+            "Failed to record price of product {} from supplier {}: {}",
+            product_id,
+            supplier_id,
+            err
+        );
+        ApiResponse::new(500, json!({ "message": "Failed to record supplier price" }))
+    })
+}
+
+/// Average units dispensed per day over the last `days` days, per product.
+pub async fn average_daily_consumption(
+    tenant_db: &DatabaseConnection,
+    store_id: Option<i32>,
+    days: i64,
+) -> Result<HashMap<i32, f64>, ApiResponse> {
+    let since = Utc::now().naive_utc() - Duration::days(days);
+    let mut stmt = tenant::entities::stock_movements::Entity::find()
+        .select_only()
+        .column(tenant::entities::stock_movements::Column::ProductId)
+        .column_as(
+            tenant::entities::stock_movements::Column::Quantity.sum(),
+            "dispensed",
+        )
+        .filter(
+            tenant::entities::stock_movements::Column::MovementType.eq(StockMovementType::Dispense),
+        )
+        .filter(tenant::entities::stock_movements::Column::CreatedAt.gte(since));
+
+    if let Some(store_id) = store_id {
+        stmt = stmt.filter(tenant::entities::stock_movements::Column::StoreId.eq(store_id));
+    }
+
+    let totals = stmt
+        .group_by(tenant::entities::stock_movements::Column::ProductId)
+        .into_tuple::<(i32, i64)>()
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to sum dispensed stock: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to calculate consumption" }))
+        })?;
+
+    // Dispense movements are negative; consumption is reported as positive.
+    Ok(totals
+        .into_iter()
+        .map(|(product_id, dispensed)| (product_id, -dispensed as f64 / days as f64))
+        .collect())
+}
+
+/// Units ordered but not yet received per product on open purchase orders.
+pub async fn quantities_on_order(
+    tenant_db: &DatabaseConnection,
+    store_id: Option<i32>,
+) -> Result<HashMap<i32, i64>, ApiResponse> {
+    let mut stmt = tenant::entities::purchase_order_items::Entity::find()
+        .inner_join(tenant::entities::purchase_orders::Entity)
+        .filter(tenant::entities::purchase_orders::Column::Status.is_in(OPEN_ORDER_STATUSES));
+
+    if let Some(store_id) = store_id {
+        stmt = stmt.filter(tenant::entities::purchase_orders::Column::StoreId.eq(store_id));
+    }
+
+    let items = stmt.all(tenant_db).await.map_err(|err| {
+        log::error!("Failed to fetch open purchase order items: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to fetch stock on order" }))
+    })?;
+
+    let mut on_order = HashMap::new();
+    for item in items {
+        *on_order.entry(item.product_id).or_default() +=
+            (item.quantity_ordered - item.quantity_received).max(0) as i64;
+    }
+
+    Ok(on_order)
+}
+
+pub async fn find_supplier(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+) -> Result<tenant::entities::suppliers::Model, ApiResponse> {
+    tenant::entities::suppliers::Entity::find_by_pid(pid)
+        .filter(tenant::entities::suppliers::Column::DeletedAt.is_null())
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch supplier {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch supplier" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Supplier not found" })))
+}
+
+pub async fn find_purchase_order(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+) -> Result<tenant::entities::purchase_orders::Model, ApiResponse> {
+    tenant::entities::purchase_orders::Entity::find_by_pid(pid)
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch purchase order {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch purchase order" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Purchase order not found" })))
+}
+
+pub fn supplier_json(supplier: &tenant::entities::suppliers::Model) -> Value {
+    json!({
+        "pid": supplier.pid,
+        "name": supplier.name,
+        "code": supplier.code,
+        "contact_person": supplier.contact_person,
+        "phone": supplier.phone,
+        "email": supplier.email,
+        "address": supplier.address,
+        "payment_terms": supplier.payment_terms,
+        "lead_time_days": supplier.lead_time_days,
+        "is_active": supplier.is_active,
+        "created_at": supplier.created_at,
+        "updated_at": supplier.updated_at,
+    })
+}
+
+pub fn purchase_order_item_json(
+    item: &tenant::entities::purchase_order_items::Model,
+    product: Option<&tenant::entities::pharmacy_products::Model>,
+) -> Value {
+    json!({
+        "pid": item.pid,
+        "product": product.map(|product| json!({
+            "pid": product.pid,
+            "label": product_label(product),
+            "unit": product.unit,
+            "pack_size": product.pack_size,
+        })),
+        "quantity_ordered": item.quantity_ordered,
+        "quantity_received": item.quantity_received,
+        "quantity_outstanding": (item.quantity_ordered - item.quantity_received).max(0),
+        "unit_cost": item.unit_cost,
+        "line_total": item.unit_cost * Decimal::from(item.quantity_ordered),
+    })
+}
+
+pub fn purchase_order_json(
+    order: &tenant::entities::purchase_orders::Model,
+    supplier: Option<&tenant::entities::suppliers::Model>,
+    store: Option<&tenant::entities::pharmacy_stores::Model>,
+    items: Vec<Value>,
+) -> Value {
+    json!({
+        "pid": order.pid,
+        "order_number": order.order_number,
+        "supplier": supplier.map(|supplier| json!({
+            "pid": supplier.pid,
+            "name": supplier.name,
+            "code": supplier.code,
+        })),
+        "store": store.map(|store| json!({
+            "pid": store.pid,
+            "name": store.name,
+            "code": store.code,
+        })),
+        "status": order.status,
+        "expected_date": order.expected_date,
+        "notes": order.notes,
+        "total_amount": order.total_amount,
+        "items": items,
+        "created_by": order.created_by,
+        "submitted_at": order.submitted_at,
+        "approved_at": order.approved_at,
+        "approved_by": order.approved_by,
+        "received_at": order.received_at,
+        "cancelled_at": order.cancelled_at,
+        "cancelled_by": order.cancelled_by,
+        "cancel_reason": order.cancel_reason,
+        "created_at": order.created_at,
+        "updated_at": order.updated_at,
+    })
+}
+
+pub fn goods_received_item_json(
+    item: &tenant::entities::goods_received_items::Model,
+    product: Option<&tenant::entities::pharmacy_products::Model>,
+    batch: Option<&tenant::entities::stock_batches::Model>,
+) -> Value {
+    json!({
+        "pid": item.pid,
+        "product": product.map(|product| json!({
+            "pid": product.pid,
+            "label": product_label(product),
+            "unit": product.unit,
+        })),
+        "batch": batch.map(|batch| batch_json(batch, None)),
+        "quantity": item.quantity,
+        "unit_cost": item.unit_cost,
+        "line_total": item.unit_cost * Decimal::from(item.quantity),
+    })
+}
+
+pub fn goods_received_note_json(
+    grn: &tenant::entities::goods_received_notes::Model,
+    order: Option<&tenant::entities::purchase_orders::Model>,
+    items: Vec<Value>,
+) -> Value {
+    json!({
+        "pid": grn.pid,
+        "grn_number": grn.grn_number,
+        "purchase_order": order.map(|order| json!({
+            "pid": order.pid,
+            "order_number": order.order_number,
+            "status": order.status,
+        })),
+        "delivery_note_number": grn.delivery_note_number,
+        "total_amount": grn.total_amount,
+        "notes": grn.notes,
+        "items": items,
+        "received_by": grn.received_by,
+        "received_at": grn.received_at,
+    })
+}
+
+pub fn supplier_price_json(
+    price: &tenant::entities::supplier_prices::Model,
+    grn: Option<&tenant::entities::goods_received_notes::Model>,
+) -> Value {
+    json!({
+        "pid": price.pid,
+        "unit_cost": price.unit_cost,
+        "grn_number": grn.map(|grn| grn.grn_number.clone()),
+        "recorded_by": price.recorded_by,
+        "effective_at": price.effective_at,
+    })
+}
@@ -0,0 +1,360 @@
+use std::collections::HashMap;
+
+use chrono::{NaiveDateTime, Utc};
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        main,
+        tenant::{
+            self,
+            entities::sea_orm_active_enums::{QueueStage, QueueTicketStatus, TriagePriority},
+            migrations::sea_orm::{
+                ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+            },
+        },
+    },
+    utils::{api_response::ApiResponse, constants::APP_URL},
+};
+
+/// Stages in the order a typical outpatient visit moves through them.
+pub const QUEUE_STAGES: [QueueStage; 4] = [
+    QueueStage::Triage,
+    QueueStage::Consultation,
+    QueueStage::Pharmacy,
+    QueueStage::Cashier,
+];
+
+pub const ACTIVE_TICKET_STATUSES: [QueueTicketStatus; 3] = [
+    QueueTicketStatus::Waiting,
+    QueueTicketStatus::Called,
+    QueueTicketStatus::InService,
+];
+
+pub fn stage_slug(stage: &QueueStage) -> &'static str {
+    match stage {
+        QueueStage::Triage => "triage",
+        QueueStage::Consultation => "consultation",
+        QueueStage::Pharmacy => "pharmacy",
+        QueueStage::Cashier => "cashier",
+    }
+}
+
+pub fn parse_stage(slug: &str) -> Option<QueueStage> {
+    QUEUE_STAGES
+        .into_iter()
+        .find(|stage| stage_slug(stage) == slug)
+}
+
+pub fn priority_rank(priority: &TriagePriority) -> u8 {
+    match priority {
+        TriagePriority::Emergency => 0,
+        TriagePriority::Urgent => 1,
+        TriagePriority::Routine => 2,
+    }
+}
+
+/// Seconds a patient waited at a stage before being called, or so far if
+/// they are still waiting.
+pub fn wait_seconds(
+    visit: &tenant::entities::queue_stage_visits::Model,
+    now: NaiveDateTime,
+) -> i64 {
+    let until = visit.called_at.or(visit.started_at).unwrap_or(now);
+    (until - visit.queued_at).num_seconds().max(0)
+}
+
+/// Stream tokens are for opening the feed straight away, not for sharing.
+const STREAM_TOKEN_TTL_SECS: u64 = 60;
+
+/// What a stream token grants: one live feed of one stage's queue.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StreamTicket {
+    pub sso_tenant_id: Uuid,
+    pub tenant_pid: Uuid,
+    pub stage: String,
+    pub display: bool,
+}
+
+fn stream_token_key(token: &str) -> String {
+    format!("queue:stream-token:{}", token)
+}
+
+fn queue_channel(tenant_pid: Uuid, stage: &QueueStage) -> String {
+    format!("queue:{}:{}", tenant_pid, stage_slug(stage))
+}
+
+/// Tells live queue subscribers that a stage has changed. Failures are only
+/// logged: a missed push is corrected on the next one, and the change itself
+/// is already saved.
+pub async fn publish_queue_update(redis: &redis::Client, tenant_pid: Uuid, stage: &QueueStage) {
+    let mut conn = match redis.get_multiplexed_async_connection().await {
+        Ok(conn) => conn,
+        Err(err) => {
+            log::error!("Failed to get Redis connection: {}", err);
+            return;
+        }
+    };
+
+    let channel = queue_channel(tenant_pid, stage);
+    if let Err(err) = conn.publish::<_, _, ()>(&channel, "updated").await {
+        log::error!("Failed to publish queue update on {}: {}", channel, err);
+    }
+}
+
+/// Issues a one-time token for a stage's live feed along with the URL the
+/// client should open.
+pub async fn issue_stream_token(
+    redis: &redis::Client,
+    ticket: &StreamTicket,
+) -> Result<Value, ApiResponse> {
+    let token = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+    let payload = serde_json::to_string(ticket).map_err(|err| {
+        log::error!("Failed to serialize stream ticket: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to issue stream token" }))
+    })?;
+
+    let mut conn = redis
+        .get_multiplexed_async_connection()
+        .await
+        .map_err(|err| {
+            log::error!("Failed to get Redis connection: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to issue stream token" }))
+        })?;
+    conn.set_ex::<_, _, ()>(stream_token_key(&token), payload, STREAM_TOKEN_TTL_SECS)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to store stream token: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to issue stream token" }))
+        })?;
+
+    Ok(json!({
+        "token": token,
+        "expires_in": STREAM_TOKEN_TTL_SECS,
+        "stream_url": format!(
+            "{}/api/public/queue/live?token={}",
+            APP_URL.trim_end_matches('/'),
+            token
+        ),
+    }))
+}
+
+/// Exchanges a stream token for its ticket. The token is deleted in the same
+/// step, so it can only be used once.
+pub async fn redeem_stream_token(
+    redis: &redis::Client,
+    token: &str,
+) -> Result<Option<StreamTicket>, ApiResponse> {
+    let mut conn = redis
+        .get_multiplexed_async_connection()
+        .await
+        .map_err(|err| {
+            log::error!("Failed to get Redis connection: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to open live queue" }))
+        })?;
+    let payload: Option<String> = conn.get_del(stream_token_key(token)).await.map_err(|err| {
+        log::error!("Failed to redeem stream token: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to open live queue" }))
+    })?;
+
+    Ok(payload.and_then(|payload| serde_json::from_str(&payload).ok()))
+}
+
+/// Subscribes to updates for one stage's queue.
+pub async fn subscribe_queue_updates(
+    redis: &redis::Client,
+    tenant_pid: Uuid,
+    stage: &QueueStage,
+) -> Result<redis::aio::PubSub, ApiResponse> {
+    let mut pubsub = redis.get_async_pubsub().await.map_err(|err| {
+        log::error!("Failed to open Redis pub/sub connection: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to open live queue" }))
+    })?;
+
+    pubsub
+        .subscribe(queue_channel(tenant_pid, stage))
+        .await
+        .map_err(|err| {
+            log::error!("Failed to subscribe to queue updates: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to open live queue" }))
+        })?;
+
+    Ok(pubsub)
+}
+
+/// Patients currently queued at a stage with their open stage visit, highest
+/// priority first and then by how long they have waited.
+pub async fn fetch_stage_queue(
+    tenant_db: &DatabaseConnection,
+    stage: &QueueStage,
+) -> Result<
+    Vec<(
+        tenant::entities::queue_tickets::Model,
+        tenant::entities::queue_stage_visits::Model,
+    )>,
+    ApiResponse,
+> {
+    let tickets = tenant::entities::queue_tickets::Entity::find()
+        .find_with_related(tenant::entities::queue_stage_visits::Entity)
+        .filter(tenant::entities::queue_tickets::Column::Stage.eq(stage.clone()))
+        .filter(tenant::entities::queue_tickets::Column::Status.is_in(ACTIVE_TICKET_STATUSES))
+        .order_by_asc(tenant::entities::queue_tickets::Column::CheckedInAt)
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch {} queue: {}", stage_slug(stage), err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch queue" }))
+        })?;
+
+    let mut queue = tickets
+        .into_iter()
+        .filter_map(|(ticket, visits)| {
+            visits
+                .into_iter()
+                .find(|visit| visit.stage == *stage && visit.completed_at.is_none())
+                .map(|visit| (ticket, visit))
+        })
+        .collect::<Vec<_>>();
+
+    queue.sort_by(|(a, a_visit), (b, b_visit)| {
+        priority_rank(&a.priority)
+            .cmp(&priority_rank(&b.priority))
+            .then(a_visit.queued_at.cmp(&b_visit.queued_at))
+    });
+
+    Ok(queue)
+}
+
+/// Builds the payload pushed to queue screens. Waiting-room displays get
+/// ticket numbers and rooms only; clinician worklists also get the patient.
+pub async fn stage_queue_snapshot(
+    main_db: &DatabaseConnection,
+    tenant_db: &DatabaseConnection,
+    stage: &QueueStage,
+    display: bool,
+) -> Result<Value, ApiResponse> {
+    let queue = fetch_stage_queue(tenant_db, stage).await?;
+    let now = Utc::now().naive_utc();
+
+    let patients: HashMap<Uuid, main::entities::patients::Model> = if display || queue.is_empty() {
+        HashMap::new()
+    } else {
+        main::entities::patients::Entity::find()
+            .filter(
+                main::entities::patients::Column::Pid
+                    .is_in(queue.iter().map(|(ticket, _)| ticket.patient_pid)),
+            )
+            .all(main_db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to fetch queued patients: {}", err);
+                ApiResponse::new(500, json!({ "message": "Failed to fetch queue" }))
+            })?
+            .into_iter()
+            .map(|patient| (patient.pid, patient))
+            .collect()
+    };
+
+    let entries = queue
+        .iter()
+        .enumerate()
+        .map(|(index, (ticket, visit))| {
+            let mut entry = json!({
+                "position": index + 1,
+                "ticket_number": ticket.ticket_number,
+                "status": ticket.status,
+                "room": visit.room,
+            });
+
+            if !display {
+                entry["ticket_pid"] = json!(ticket.pid);
+                entry["priority"] = json!(ticket.priority);
+                entry["queued_at"] = json!(visit.queued_at);
+                entry["wait_seconds"] = json!(wait_seconds(visit, now));
+                entry["patient"] = patients
+                    .get(&ticket.patient_pid)
+                    .map(|patient| {
+                        json!({
+                            "pid": patient.pid,
+                            "first_name": patient.first_name,
+                            "last_name": patient.last_name,
+                            "gender": patient.gender,
+                            "dob": patient.dob,
+                        })
+                    })
+                    .unwrap_or_else(|| json!({ "pid": ticket.patient_pid }));
+            }
+
+            entry
+        })
+        .collect::<Vec<_>>();
+
+    Ok(json!({
+        "stage": stage,
+        "as_at": now,
+        "waiting": queue
+            .iter()
+            .filter(|(ticket, _)| ticket.status == QueueTicketStatus::Waiting)
+            .count(),
+        "queue": entries,
+    }))
+}
+
+pub fn queue_ticket_json(ticket: &tenant::entities::queue_tickets::Model) -> Value {
+    json!({
+        "pid": ticket.pid,
+        "patient_pid": ticket.patient_pid,
+        "queue_date": ticket.queue_date,
+        "ticket_number": ticket.ticket_number,
+        "priority": ticket.priority,
+        "stage": ticket.stage,
+        "status": ticket.status,
+        "room": ticket.room,
+        "checked_in_at": ticket.checked_in_at,
+        "checked_in_by": ticket.checked_in_by,
+        "completed_at": ticket.completed_at,
+        "cancel_reason": ticket.cancel_reason,
+        "notes": ticket.notes,
+        "created_at": ticket.created_at,
+        "updated_at": ticket.updated_at,
+    })
+}
+
+pub fn stage_visit_json(visit: &tenant::entities::queue_stage_visits::Model) -> Value {
+    let now = Utc::now().naive_utc();
+    json!({
+        "pid": visit.pid,
+        "stage": visit.stage,
+        "queued_at": visit.queued_at,
+        "called_at": visit.called_at,
+        "started_at": visit.started_at,
+        "completed_at": visit.completed_at,
+        "handled_by": visit.handled_by,
+        "room": visit.room,
+        "wait_seconds": wait_seconds(visit, now),
+        "service_seconds": visit
+            .started_at
+            .map(|started| (visit.completed_at.unwrap_or(now) - started).num_seconds()),
+    })
+}
+
+pub fn triage_assessment_json(assessment: &tenant::entities::triage_assessments::Model) -> Value {
+    json!({
+        "pid": assessment.pid,
+        "priority": assessment.priority,
+        "chief_complaint": assessment.chief_complaint,
+        "temperature": assessment.temperature,
+        "pulse_rate": assessment.pulse_rate,
+        "respiratory_rate": assessment.respiratory_rate,
+        "bp_systolic": assessment.bp_systolic,
+        "bp_diastolic": assessment.bp_diastolic,
+        "oxygen_saturation": assessment.oxygen_saturation,
+        "weight_kg": assessment.weight_kg,
+        "notes": assessment.notes,
+        "assessed_by": assessment.assessed_by,
+        "created_at": assessment.created_at,
+    })
+}
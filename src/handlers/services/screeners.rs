@@ -0,0 +1,676 @@
+use std::collections::BTreeMap;
+
+use chrono::{Duration, Utc};
+use serde_json::{Value, json};
+
+use crate::{
+    db::tenant::{
+        self, entities::sea_orm_active_enums::ClinicalTaskPriority,
+        migrations::sea_orm::DatabaseConnection,
+    },
+    handlers::services::clinical_tasks::{NewClinicalTask, create_clinical_task},
+    utils::api_response::ApiResponse,
+};
+
+/// Response options shared by the PHQ-9 and GAD-7.
+const FREQUENCY_OPTIONS: &[(&str, i32)] = &[
+    ("Not at all", 0),
+    ("Several days", 1),
+    ("More than half the days", 2),
+    ("Nearly every day", 3),
+];
+
+const AUDIT_FREQUENCY_OPTIONS: &[(&str, i32)] = &[
+    ("Never", 0),
+    ("Less than monthly", 1),
+    ("Monthly", 2),
+    ("Weekly", 3),
+    ("Daily or almost daily", 4),
+];
+
+const AUDIT_INJURY_OPTIONS: &[(&str, i32)] = &[
+    ("No", 0),
+    ("Yes, but not in the last year", 2),
+    ("Yes, during the last year", 4),
+];
+
+pub struct ScreenerItem {
+    pub text: &'static str,
+    pub options: &'static [(&'static str, i32)],
+}
+
+/// A severity band covers scores up to and including `max_score`. Bands that
+/// `escalate` raise a high-priority review task for the clinician.
+pub struct SeverityBand {
+    pub max_score: i32,
+    pub severity: &'static str,
+    pub escalate: bool,
+}
+
+pub struct Screener {
+    pub code: &'static str,
+    pub name: &'static str,
+    pub instructions: &'static str,
+    pub items: &'static [ScreenerItem],
+    pub bands: &'static [SeverityBand],
+    /// Zero-based index of the self-harm question; any non-zero answer is
+    /// treated as a positive safety screen.
+    pub safety_item: Option<usize>,
+}
+
+pub struct ScreeningScore {
+    pub total_score: i32,
+    pub severity: &'static str,
+    pub safety_flag: bool,
+    pub escalate: bool,
+}
+
+const PHQ9: Screener = Screener {
+    code: "phq9",
+    name: "Patient Health Questionnaire (PHQ-9)",
+    instructions: "Over the last 2 weeks, how often have you been bothered by any of the following problems?",
+    items: &[
+        ScreenerItem {
+            text: "Little interest or pleasure in doing things",
+            options: FREQUENCY_OPTIONS,
+        },
+        ScreenerItem {
+            text: "Feeling down, depressed, or hopeless",
+            options: FREQUENCY_OPTIONS,
+        },
+        ScreenerItem {
+            text: "Trouble falling or staying asleep, or sleeping too much",
+            options: FREQUENCY_OPTIONS,
+        },
+        ScreenerItem {
+            text: "Feeling tired or having little energy",
+            options: FREQUENCY_OPTIONS,
+        },
+        ScreenerItem {
+            text: "Poor appetite or overeating",
+            options: FREQUENCY_OPTIONS,
+        },
+        ScreenerItem {
+            text: "Feeling bad about yourself, or that you are a failure or have let yourself or your family down",
+            options: FREQUENCY_OPTIONS,
+        },
+        ScreenerItem {
+            text: "Trouble concentrating on things, such as reading the newspaper or watching television",
+            options: FREQUENCY_OPTIONS,
+        },
+        ScreenerItem {
+            text: "Moving or speaking so slowly that other people could have noticed, or the opposite, being so fidgety or restless that you have been moving around a lot more than usual",
+            options: FREQUENCY_OPTIONS,
+        },
+        ScreenerItem {
+            text: "Thoughts that you would be better off dead, or of hurting yourself in some way",
+            options: FREQUENCY_OPTIONS,
+        },
+    ],
+    bands: &[
+        SeverityBand {
+            max_score: 4,
+            severity: "minimal",
+            escalate: false,
+        },
+        SeverityBand {
+            max_score: 9,
+            severity: "mild",
+            escalate: false,
+        },
+        SeverityBand {
+            max_score: 14,
+            severity: "moderate",
+            escalate: false,
+        },
+        SeverityBand {
+            max_score: 19,
+            severity: "moderately_severe",
+            escalate: true,
+        },
+        SeverityBand {
+            max_score: 27,
+            severity: "severe",
+            escalate: true,
+        },
+    ],
+    safety_item: Some(8),
+};
+
+const GAD7: Screener = Screener {
+    code: "gad7",
+    name: "Generalized Anxiety Disorder (GAD-7)",
+    instructions: "Over the last 2 weeks, how often have you been bothered by the following problems?",
+    items: &[
+        ScreenerItem {
+            text: "Feeling nervous, anxious, or on edge",
+            options: FREQUENCY_OPTIONS,
+        },
+        ScreenerItem {
+            text: "Not being able to stop or control worrying",
+            options: FREQUENCY_OPTIONS,
+        },
+        ScreenerItem {
+            text: "Worrying too much about different things",
+            options: FREQUENCY_OPTIONS,
+        },
+        ScreenerItem {
+            text: "Trouble relaxing",
+            options: FREQUENCY_OPTIONS,
+        },
+        ScreenerItem {
+            text: "Being so restless that it is hard to sit still",
+            options: FREQUENCY_OPTIONS,
+        },
+        ScreenerItem {
+            text: "Becoming easily annoyed or irritable",
+            options: FREQUENCY_OPTIONS,
+        },
+        ScreenerItem {
+            text: "Feeling afraid, as if something awful might happen",
+            options: FREQUENCY_OPTIONS,
+        },
+    ],
+    bands: &[
+        SeverityBand {
+            max_score: 4,
+            severity: "minimal",
+            escalate: false,
+        },
+        SeverityBand {
+            max_score: 9,
+            severity: "mild",
+            escalate: false,
+        },
+        SeverityBand {
+            max_score: 14,
+            severity: "moderate",
+            escalate: false,
+        },
+        SeverityBand {
+            max_score: 21,
+            severity: "severe",
+            escalate: true,
+        },
+    ],
+    safety_item: None,
+};
+
+const AUDIT: Screener = Screener {
+    code: "audit",
+    name: "Alcohol Use Disorders Identification Test (AUDIT)",
+    instructions: "Please answer the following questions about your use of alcoholic beverages during the past year.",
+    items: &[
+        ScreenerItem {
+            text: "How often do you have a drink containing alcohol?",
+            options: &[
+                ("Never", 0),
+                ("Monthly or less", 1),
+                ("2 to 4 times a month", 2),
+                ("2 to 3 times a week", 3),
+                ("4 or more times a week", 4),
+            ],
+        },
+        ScreenerItem {
+            text: "How many drinks containing alcohol do you have on a typical day when you are drinking?",
+            options: &[
+                ("1 or 2", 0),
+                ("3 or 4", 1),
+                ("5 or 6", 2),
+                ("7 to 9", 3),
+                ("10 or more", 4),
+            ],
+        },
+        ScreenerItem {
+            text: "How often do you have six or more drinks on one occasion?",
+            options: AUDIT_FREQUENCY_OPTIONS,
+        },
+        ScreenerItem {
+            text: "How often during the last year have you found that you were not able to stop drinking once you had started?",
+            options: AUDIT_FREQUENCY_OPTIONS,
+        },
+        ScreenerItem {
+            text: "How often during the last year have you failed to do what was normally expected of you because of drinking?",
+            options: AUDIT_FREQUENCY_OPTIONS,
+        },
+        ScreenerItem {
+            text: "How often during the last year have you needed a first drink in the morning to get yourself going after a heavy drinking session?",
+            options: AUDIT_FREQUENCY_OPTIONS,
+        },
+        ScreenerItem {
+            text: "How often during the last year have you had a feeling of guilt or remorse after drinking?",
+            options: AUDIT_FREQUENCY_OPTIONS,
+        },
+        ScreenerItem {
+            text: "How often during the last year have you been unable to remember what happened the night before because you had been drinking?",
+            options: AUDIT_FREQUENCY_OPTIONS,
+        },
+        ScreenerItem {
+            text: "Have you or someone else been injured because of your drinking?",
+            options: AUDIT_INJURY_OPTIONS,
+        },
+        ScreenerItem {
+            text: "Has a relative, friend, doctor or other health worker been concerned about your drinking or suggested you cut down?",
+            options: AUDIT_INJURY_OPTIONS,
+        },
+    ],
+    bands: &[
+        SeverityBand {
+            max_score: 7,
+            severity: "low_risk",
+            escalate: false,
+        },
+        SeverityBand {
+            max_score: 15,
+            severity: "hazardous",
+            escalate: false,
+        },
+        SeverityBand {
+            max_score: 19,
+            severity: "harmful",
+            escalate: true,
+        },
+        SeverityBand {
+            max_score: 40,
+            severity: "possible_dependence",
+            escalate: true,
+        },
+    ],
+    safety_item: None,
+};
+
+const EPDS: Screener = Screener {
+    code: "epds",
+    name: "Edinburgh Postnatal Depression Scale (EPDS)",
+    instructions: "Please choose the answer that comes closest to how you have felt in the past 7 days, not just how you feel today.",
+    items: &[
+        ScreenerItem {
+            text: "I have been able to laugh and see the funny side of things",
+            options: &[
+                ("As much as I always could", 0),
+                ("Not quite so much now", 1),
+                ("Definitely not so much now", 2),
+                ("Not at all", 3),
+            ],
+        },
+        ScreenerItem {
+            text: "I have looked forward with enjoyment to things",
+            options: &[
+                ("As much as I ever did", 0),
+                ("Rather less than I used to", 1),
+                ("Definitely less than I used to", 2),
+                ("Hardly at all", 3),
+            ],
+        },
+        ScreenerItem {
+            text: "I have blamed myself unnecessarily when things went wrong",
+            options: &[
+                ("Yes, most of the time", 3),
+                ("Yes, some of the time", 2),
+                ("Not very often", 1),
+                ("No, never", 0),
+            ],
+        },
+        ScreenerItem {
+            text: "I have been anxious or worried for no good reason",
+            options: &[
+                ("No, not at all", 0),
+                ("Hardly ever", 1),
+                ("Yes, sometimes", 2),
+                ("Yes, very often", 3),
+            ],
+        },
+        ScreenerItem {
+            text: "I have felt scared or panicky for no very good reason",
+            options: &[
+                ("Yes, quite a lot", 3),
+                ("Yes, sometimes", 2),
+                ("No, not much", 1),
+                ("No, not at all", 0),
+            ],
+        },
+        ScreenerItem {
+            text: "Things have been getting on top of me",
+            options: &[
+                (
+                    "Yes, most of the time I haven't been able to cope at all",
+                    3,
+                ),
+                ("Yes, sometimes I haven't been coping as well as usual", 2),
+                ("No, most of the time I have coped quite well", 1),
+                ("No, I have been coping as well as ever", 0),
+            ],
+        },
+        ScreenerItem {
+            text: "I have been so unhappy that I have had difficulty sleeping",
+            options: &[
+                ("Yes, most of the time", 3),
+                ("Yes, sometimes", 2),
+                ("Not very often", 1),
+                ("No, not at all", 0),
+            ],
+        },
+        ScreenerItem {
+            text: "I have felt sad or miserable",
+            options: &[
+                ("Yes, most of the time", 3),
+                ("Yes, quite often", 2),
+                ("Not very often", 1),
+                ("No, not at all", 0),
+            ],
+        },
+        ScreenerItem {
+            text: "I have been so unhappy that I have been crying",
+            options: &[
+                ("Yes, most of the time", 3),
+                ("Yes, quite often", 2),
+                ("Only occasionally", 1),
+                ("No, never", 0),
+            ],
+        },
+        ScreenerItem {
+            text: "The thought of harming myself has occurred to me",
+            options: &[
+                ("Yes, quite often", 3),
+                ("Sometimes", 2),
+                ("Hardly ever", 1),
+                ("Never", 0),
+            ],
+        },
+    ],
+    bands: &[
+        SeverityBand {
+            max_score: 9,
+            severity: "low_risk",
+            escalate: false,
+        },
+        SeverityBand {
+            max_score: 12,
+            severity: "possible_depression",
+            escalate: false,
+        },
+        SeverityBand {
+            max_score: 30,
+            severity: "probable_depression",
+            escalate: true,
+        },
+    ],
+    safety_item: Some(9),
+};
+
+pub const SCREENERS: [&Screener; 4] = [&PHQ9, &GAD7, &AUDIT, &EPDS];
+
+pub fn find_screener(code: &str) -> Option<&'static Screener> {
+    SCREENERS
+        .iter()
+        .copied()
+        .find(|screener| screener.code.eq_ignore_ascii_case(code.trim()))
+}
+
+/// Scores a completed screener. `answers` holds the chosen option index for
+/// each item, in order; option scores already account for reverse-scored
+/// EPDS items.
+pub fn score_screener(screener: &Screener, answers: &[usize]) -> Result<ScreeningScore, String> {
+    if answers.len() != screener.items.len() {
+        return Err(format!(
+            "{} has {} questions but {} answers were given",
+            screener.name,
+            screener.items.len(),
+            answers.len()
+        ));
+    }
+
+    let mut item_scores = Vec::with_capacity(answers.len());
+    for (index, (item, answer)) in screener.items.iter().zip(answers).enumerate() {
+        let (_, score) = item.options.get(*answer).ok_or_else(|| {
+            format!(
+                "Answer to question {} must be between 0 and {}",
+                index + 1,
+                item.options.len() - 1
+            )
+        })?;
+        item_scores.push(*score);
+    }
+
+    let total_score = item_scores.iter().sum();
+    let band = screener
+        .bands
+        .iter()
+        .find(|band| total_score <= band.max_score)
+        .or(screener.bands.last())
+        .ok_or_else(|| format!("{} has no severity bands", screener.name))?;
+
+    let safety_flag = screener
+        .safety_item
+        .is_some_and(|item| item_scores.get(item).is_some_and(|score| *score > 0));
+
+    Ok(ScreeningScore {
+        total_score,
+        severity: band.severity,
+        safety_flag,
+        escalate: safety_flag || band.escalate,
+    })
+}
+
+pub fn screener_json(screener: &Screener) -> Value {
+    json!({
+        "code": screener.code,
+        "name": screener.name,
+        "instructions": screener.instructions,
+        "items": screener
+            .items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| json!({
+                "number": index + 1,
+                "text": item.text,
+                "options": item
+                    .options
+                    .iter()
+                    .enumerate()
+                    .map(|(value, (label, _))| json!({ "value": value, "label": label }))
+                    .collect::<Vec<_>>(),
+            }))
+            .collect::<Vec<_>>(),
+        "max_score": screener.bands.last().map(|band| band.max_score),
+        "bands": screener
+            .bands
+            .iter()
+            .map(|band| json!({ "max_score": band.max_score, "severity": band.severity }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+/// Groups results into one chronological score series per instrument for
+/// plotting history charts.
+pub fn screening_chart_series<'a>(
+    results: impl Iterator<Item = (&'a str, chrono::NaiveDateTime, i32, &'a str)>,
+) -> Value {
+    let mut series: BTreeMap<&str, Vec<Value>> = BTreeMap::new();
+
+    for (instrument, completed_at, total_score, severity) in results {
+        series.entry(instrument).or_default().push(json!({
+            "completed_at": completed_at,
+            "total_score": total_score,
+            "severity": severity,
+        }));
+    }
+
+    for points in series.values_mut() {
+        points.sort_by(|a, b| {
+            a["completed_at"]
+                .as_str()
+                .unwrap_or_default()
+                .cmp(b["completed_at"].as_str().unwrap_or_default())
+        });
+    }
+
+    json!(series)
+}
+
+/// Raises a clinician task for a result that needs attention: urgent and due
+/// immediately for a positive safety question, otherwise a high-priority
+/// review within a day.
+pub async fn escalate_screening_result(
+    tenant_db: &DatabaseConnection,
+    screener: &Screener,
+    score: &ScreeningScore,
+    result: &tenant::entities::screening_results::Model,
+) -> Result<Option<tenant::entities::clinical_tasks::Model>, ApiResponse> {
+    if !score.escalate {
+        return Ok(None);
+    }
+
+    let now = Utc::now().naive_utc();
+    let task = if score.safety_flag {
+        NewClinicalTask {
+            patient_pid: result.patient_pid,
+            title: format!("Urgent: positive self-harm question on {}", screener.name),
+            description: Some(format!(
+                "The patient answered the self-harm question positively (total score {}, {}). Assess suicide risk today.",
+                score.total_score, score.severity
+            )),
+            priority: ClinicalTaskPriority::Urgent,
+            source_type: "screening_result",
+            source_pid: Some(result.pid),
+            due_at: Some(now),
+        }
+    } else {
+        NewClinicalTask {
+            patient_pid: result.patient_pid,
+            title: format!("Review {} result: {}", screener.name, score.severity),
+            description: Some(format!(
+                "Total score {} falls in the {} band.",
+                score.total_score, score.severity
+            )),
+            priority: ClinicalTaskPriority::High,
+            source_type: "screening_result",
+            source_pid: Some(result.pid),
+            due_at: Some(now + Duration::days(1)),
+        }
+    };
+
+    create_clinical_task(tenant_db, task).await.map(Some)
+}
+
+pub fn screening_result_json(result: &tenant::entities::screening_results::Model) -> Value {
+    json!({
+        "pid": result.pid,
+        "patient_pid": result.patient_pid,
+        "instrument": result.instrument,
+        "answers": result.answers,
+        "total_score": result.total_score,
+        "severity": result.severity,
+        "safety_flag": result.safety_flag,
+        "source": result.source,
+        "self_screening_pid": result.self_screening_pid,
+        "administered_by": result.administered_by,
+        "completed_at": result.completed_at,
+        "reviewed_by": result.reviewed_by,
+        "reviewed_at": result.reviewed_at,
+        "notes": result.notes,
+        "created_at": result.created_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Answers adding up to `total`, leaving the safety question at zero.
+    fn answers_for(screener: &Screener, total: i32) -> Vec<usize> {
+        let mut remaining = total;
+        let answers = screener
+            .items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let (answer, (_, score)) = item
+                    .options
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (_, score))| {
+                        *score <= remaining && (screener.safety_item != Some(index) || *score == 0)
+                    })
+                    .max_by_key(|(_, (_, score))| *score)
+                    .unwrap();
+                remaining -= score;
+                answer
+            })
+            .collect();
+        assert_eq!(remaining, 0, "{} cannot reach {}", screener.code, total);
+        answers
+    }
+
+    #[test]
+    fn severity_band_edges() {
+        let cases = [
+            (&PHQ9, 0, "minimal", false),
+            (&PHQ9, 4, "minimal", false),
+            (&PHQ9, 5, "mild", false),
+            (&PHQ9, 9, "mild", false),
+            (&PHQ9, 10, "moderate", false),
+            (&PHQ9, 14, "moderate", false),
+            (&PHQ9, 15, "moderately_severe", true),
+            (&PHQ9, 19, "moderately_severe", true),
+            (&PHQ9, 20, "severe", true),
+            (&GAD7, 14, "moderate", false),
+            (&GAD7, 15, "severe", true),
+            (&GAD7, 21, "severe", true),
+            (&AUDIT, 7, "low_risk", false),
+            (&AUDIT, 8, "hazardous", false),
+            (&AUDIT, 15, "hazardous", false),
+            (&AUDIT, 16, "harmful", true),
+            (&AUDIT, 19, "harmful", true),
+            (&AUDIT, 20, "possible_dependence", true),
+            (&AUDIT, 40, "possible_dependence", true),
+            (&EPDS, 9, "low_risk", false),
+            (&EPDS, 10, "possible_depression", false),
+            (&EPDS, 12, "possible_depression", false),
+            (&EPDS, 13, "probable_depression", true),
+        ];
+
+        for (screener, total, severity, escalate) in cases {
+            let score = score_screener(screener, &answers_for(screener, total)).unwrap();
+            assert_eq!(score.total_score, total);
+            assert_eq!(score.severity, severity, "{} at {}", screener.code, total);
+            assert_eq!(score.escalate, escalate, "{} at {}", screener.code, total);
+            assert!(!score.safety_flag);
+        }
+    }
+
+    #[test]
+    fn any_self_harm_answer_escalates() {
+        let mut answers = answers_for(&PHQ9, 0);
+        answers[8] = 1;
+
+        let score = score_screener(&PHQ9, &answers).unwrap();
+        assert_eq!(score.severity, "minimal");
+        assert!(score.safety_flag);
+        assert!(score.escalate);
+
+        let score = score_screener(&PHQ9, &[3; 9]).unwrap();
+        assert_eq!(score.total_score, 27);
+        assert_eq!(score.severity, "severe");
+    }
+
+    #[test]
+    fn invalid_answers_are_rejected() {
+        assert!(score_screener(&PHQ9, &[0; 8]).is_err());
+        assert!(score_screener(&PHQ9, &[0; 10]).is_err());
+
+        let mut answers = vec![0; 9];
+        answers[2] = 4;
+        assert_eq!(
+            score_screener(&PHQ9, &answers).err().unwrap(),
+            "Answer to question 3 must be between 0 and 3"
+        );
+
+        let mut answers = vec![0; 10];
+        answers[9] = 3;
+        assert_eq!(
+            score_screener(&AUDIT, &answers).err().unwrap(),
+            "Answer to question 10 must be between 0 and 2"
+        );
+    }
+}
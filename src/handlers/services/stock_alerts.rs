@@ -0,0 +1,121 @@
+use serde_json::{Value, json};
+
+use crate::{
+    db::tenant::{self, entities::sea_orm_active_enums::StockAlertType},
+    handlers::services::pharmacy::product_label,
+    utils::html::escape_html,
+};
+
+/// A one-line description of an alert for digests.
+pub fn stock_alert_summary(
+    alert: &tenant::entities::stock_alerts::Model,
+    product: &tenant::entities::pharmacy_products::Model,
+    batch: Option<&tenant::entities::stock_batches::Model>,
+) -> String {
+    let label = product_label(product);
+    let batch_number = batch.map(|b| b.batch_number.as_str()).unwrap_or("-");
+    let expiry = alert
+        .expiry_date
+        .map(|date| date.format("%d %b %Y").to_string())
+        .unwrap_or_default();
+
+    match alert.alert_type {
+        StockAlertType::StockOut => format!("{}: out of stock", label),
+        StockAlertType::LowStock => format!(
+            "{}: {} {} left, reorder level {}",
+            label, alert.quantity, product.unit, alert.reorder_level
+        ),
+        StockAlertType::Expiring => format!(
+            "{}: batch {} ({} {}) expires {}",
+            label, batch_number, alert.quantity, product.unit, expiry
+        ),
+        StockAlertType::Expired => format!(
+            "{}: batch {} ({} {}) expired {}",
+            label, batch_number, alert.quantity, product.unit, expiry
+        ),
+    }
+}
+
+pub fn stock_alert_json(
+    alert: &tenant::entities::stock_alerts::Model,
+    product: Option<&tenant::entities::pharmacy_products::Model>,
+    store: Option<&tenant::entities::pharmacy_stores::Model>,
+    batch: Option<&tenant::entities::stock_batches::Model>,
+) -> Value {
+    json!({
+        "pid": alert.pid,
+        "alert_type": alert.alert_type,
+        "store": store.map(|store| json!({
+            "pid": store.pid,
+            "name": store.name,
+            "code": store.code,
+        })),
+        "product": product.map(|product| json!({
+            "pid": product.pid,
+            "label": product_label(product),
+            "unit": product.unit,
+        })),
+        "batch": batch.map(|batch| json!({
+            "pid": batch.pid,
+            "batch_number": batch.batch_number,
+        })),
+        "window_days": alert.window_days,
+        "quantity": alert.quantity,
+        "reorder_level": alert.reorder_level,
+        "expiry_date": alert.expiry_date,
+        "summary": product.map(|product| stock_alert_summary(alert, product, batch)),
+        "notified_at": alert.notified_at,
+        "acknowledged_at": alert.acknowledged_at,
+        "acknowledged_by": alert.acknowledged_by,
+        "resolved_at": alert.resolved_at,
+        "created_at": alert.created_at,
+        "updated_at": alert.updated_at,
+    })
+}
+
+/// The email digest of new alerts for one store, grouped by alert type.
+pub fn stock_alert_digest_html(
+    facility_name: &str,
+    store: &tenant::entities::pharmacy_stores::Model,
+    sections: &[(&str, Vec<String>)],
+) -> String {
+    let mut body = String::new();
+    for (title, lines) in sections {
+        if lines.is_empty() {
+            continue;
+        }
+
+        body.push_str(&format!(
+            r#"<h3 style="font-size: 16px; margin: 20px 0 8px;">{} ({})</h3><ul style="padding-left: 20px; margin: 0;">"#,
+            escape_html(title),
+            lines.len()
+        ));
+        for line in lines {
+            body.push_str(&format!(
+                r#"<li style="font-size: 14px; margin-bottom: 4px;">{}</li>"#,
+                escape_html(line)
+            ));
+        }
+        body.push_str("</ul>");
+    }
+
+    format!(
+        r#"
+        <!doctype html>
+        <html>
+            <head>
+                <meta charset="utf-8" />
+                <title>Stock Alerts</title>
+            </head>
+            <body style="font-family: Arial, sans-serif; color: #222; padding: 16px;">
+                <h2 style="font-size: 20px; margin-bottom: 4px;">Stock alerts for {}</h2>
+                <div style="font-size: 13px; color: #666;">{}</div>
+                {}
+            </body>
+        </html>
+        "#,
+        escape_html(&store.name),
+        escape_html(facility_name),
+        body
+    )
+}
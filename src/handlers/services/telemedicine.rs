@@ -0,0 +1,333 @@
+use chrono::Utc;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::tenant::{
+        self,
+        entities::sea_orm_active_enums::TelemedicineSessionStatus,
+        migrations::sea_orm::{ActiveModelTrait, DatabaseConnection, Set},
+    },
+    utils::{
+        api_response::ApiResponse,
+        constants::{APP_URL, WEBRTC_ICE_SERVERS},
+    },
+};
+
+/// Code of the `features` row that switches telemedicine on for a facility.
+pub const TELEMEDICINE_FEATURE: &str = "telemedicine";
+
+/// Room tokens are for joining straight away, not for sharing ahead of time.
+const ROOM_TOKEN_TTL_SECS: u64 = 300;
+
+/// Presence keys outlive any realistic call but still clear themselves if a
+/// server dies without running its disconnect cleanup.
+const PRESENCE_TTL_SECS: i64 = 4 * 60 * 60;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ParticipantRole {
+    Patient,
+    Practitioner,
+}
+
+impl ParticipantRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ParticipantRole::Patient => "patient",
+            ParticipantRole::Practitioner => "practitioner",
+        }
+    }
+}
+
+/// What a room token grants: one participant's seat in one session.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RoomTicket {
+    pub sso_tenant_id: Uuid,
+    pub session_pid: Uuid,
+    pub role: ParticipantRole,
+    pub user_id: Uuid,
+}
+
+pub fn is_session_open(status: &TelemedicineSessionStatus) -> bool {
+    !matches!(
+        status,
+        TelemedicineSessionStatus::Ended | TelemedicineSessionStatus::Cancelled
+    )
+}
+
+fn room_token_key(token: &str) -> String {
+    format!("telemedicine:token:{}", token)
+}
+
+fn presence_key(session_pid: Uuid) -> String {
+    format!("telemedicine:presence:{}", session_pid)
+}
+
+fn signal_channel(session_pid: Uuid) -> String {
+    format!("telemedicine:signal:{}", session_pid)
+}
+
+async fn redis_connection(
+    redis: &redis::Client,
+) -> Result<redis::aio::MultiplexedConnection, ApiResponse> {
+    redis
+        .get_multiplexed_async_connection()
+        .await
+        .map_err(|err| {
+            log::error!("Failed to get Redis connection: {}", err);
+            ApiResponse::new(500, json!({ "message": "Telemedicine is unavailable" }))
+        })
+}
+
+/// Issues a one-time token for the signalling socket along with what the
+/// client needs to connect.
+pub async fn issue_room_token(
+    redis: &redis::Client,
+    ticket: &RoomTicket,
+) -> Result<Value, ApiResponse> {
+    let token = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+    let payload = serde_json::to_string(ticket).map_err(|err| {
+        log::error!("Failed to serialize room ticket: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to issue room token" }))
+    })?;
+
+    let mut conn = redis_connection(redis).await?;
+    conn.set_ex::<_, _, ()>(room_token_key(&token), payload, ROOM_TOKEN_TTL_SECS)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to store room token: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to issue room token" }))
+        })?;
+
+    Ok(json!({
+        "token": token,
+        "role": ticket.role,
+        "expires_in": ROOM_TOKEN_TTL_SECS,
+        "signal_url": format!(
+            "{}/api/public/telemedicine/signal?token={}",
+            APP_URL.replacen("http", "ws", 1),
+            token
+        ),
+        "ice_servers": [{ "urls": *WEBRTC_ICE_SERVERS }],
+    }))
+}
+
+/// Exchanges a room token for its ticket. The token is deleted in the same
+/// step, so it can only be used once.
+pub async fn redeem_room_token(
+    redis: &redis::Client,
+    token: &str,
+) -> Result<Option<RoomTicket>, ApiResponse> {
+    let mut conn = redis_connection(redis).await?;
+    let payload: Option<String> = conn.get_del(room_token_key(token)).await.map_err(|err| {
+        log::error!("Failed to redeem room token: {}", err);
+        ApiResponse::new(500, json!({ "message": "Telemedicine is unavailable" }))
+    })?;
+
+    Ok(payload.and_then(|payload| serde_json::from_str(&payload).ok()))
+}
+
+/// Relays a message to everyone connected to a session. Failures are only
+/// logged; WebRTC clients retry offers and candidates on their own.
+pub async fn publish_signal(redis: &redis::Client, session_pid: Uuid, message: &Value) {
+    let mut conn = match redis.get_multiplexed_async_connection().await {
+        Ok(conn) => conn,
+        Err(err) => {
+            log::error!("Failed to get Redis connection: {}", err);
+            return;
+        }
+    };
+
+    if let Err(err) = conn
+        .publish::<_, _, ()>(signal_channel(session_pid), message.to_string())
+        .await
+    {
+        log::error!(
+            "Failed to relay signal for session {}: {}",
+            session_pid,
+            err
+        );
+    }
+}
+
+pub async fn subscribe_signals(
+    redis: &redis::Client,
+    session_pid: Uuid,
+) -> Result<redis::aio::PubSub, ApiResponse> {
+    let mut pubsub = redis.get_async_pubsub().await.map_err(|err| {
+        log::error!("Failed to open Redis pub/sub connection: {}", err);
+        ApiResponse::new(500, json!({ "message": "Telemedicine is unavailable" }))
+    })?;
+
+    pubsub
+        .subscribe(signal_channel(session_pid))
+        .await
+        .map_err(|err| {
+            log::error!("Failed to subscribe to session {}: {}", session_pid, err);
+            ApiResponse::new(500, json!({ "message": "Telemedicine is unavailable" }))
+        })?;
+
+    Ok(pubsub)
+}
+
+/// Records a participant connecting. The session is waiting until both sides
+/// are connected and in a call from then on; the first time that happens
+/// marks the start of the call.
+pub async fn participant_joined(
+    redis: &redis::Client,
+    tenant_db: &DatabaseConnection,
+    ticket: &RoomTicket,
+) -> Result<tenant::entities::telemedicine_sessions::Model, ApiResponse> {
+    let mut conn = redis_connection(redis).await?;
+    let key = presence_key(ticket.session_pid);
+
+    let connected: usize = redis::pipe()
+        .sadd(&key, ticket.role.as_str())
+        .ignore()
+        .expire(&key, PRESENCE_TTL_SECS)
+        .ignore()
+        .scard(&key)
+        .query_async::<(usize,)>(&mut conn)
+        .await
+        .map(|(count,)| count)
+        .map_err(|err| {
+            log::error!("Failed to record presence: {}", err);
+            ApiResponse::new(500, json!({ "message": "Telemedicine is unavailable" }))
+        })?;
+
+    let session = find_session(tenant_db, ticket.session_pid).await?;
+    let now = Utc::now().naive_utc();
+    let mut active_model: tenant::entities::telemedicine_sessions::ActiveModel =
+        session.clone().into();
+
+    match ticket.role {
+        ParticipantRole::Patient if session.patient_joined_at.is_none() => {
+            active_model.patient_joined_at = Set(Some(now));
+        }
+        ParticipantRole::Practitioner if session.practitioner_joined_at.is_none() => {
+            active_model.practitioner_joined_at = Set(Some(now));
+        }
+        _ => {}
+    }
+
+    if connected >= 2 {
+        active_model.status = Set(TelemedicineSessionStatus::InCall);
+        if session.started_at.is_none() {
+            active_model.started_at = Set(Some(now));
+        }
+    } else {
+        active_model.status = Set(TelemedicineSessionStatus::Waiting);
+    }
+
+    update_session(tenant_db, active_model).await
+}
+
+/// Records a participant disconnecting. A dropped call goes back to waiting
+/// so the other side can be rejoined; only an explicit end closes it.
+pub async fn participant_left(
+    redis: &redis::Client,
+    tenant_db: &DatabaseConnection,
+    ticket: &RoomTicket,
+) -> Result<(), ApiResponse> {
+    let mut conn = redis_connection(redis).await?;
+    conn.srem::<_, _, ()>(presence_key(ticket.session_pid), ticket.role.as_str())
+        .await
+        .map_err(|err| {
+            log::error!("Failed to clear presence: {}", err);
+            ApiResponse::new(500, json!({ "message": "Telemedicine is unavailable" }))
+        })?;
+
+    let session = find_session(tenant_db, ticket.session_pid).await?;
+    if session.status == TelemedicineSessionStatus::InCall {
+        let mut active_model: tenant::entities::telemedicine_sessions::ActiveModel = session.into();
+        active_model.status = Set(TelemedicineSessionStatus::Waiting);
+        update_session(tenant_db, active_model).await?;
+    }
+
+    Ok(())
+}
+
+/// Tells connected clients the session is over and clears its presence.
+pub async fn close_session_room(redis: &redis::Client, session_pid: Uuid) {
+    publish_signal(
+        redis,
+        session_pid,
+        &json!({ "from": "server", "type": "session_ended" }),
+    )
+    .await;
+
+    match redis.get_multiplexed_async_connection().await {
+        Ok(mut conn) => {
+            if let Err(err) = conn.del::<_, ()>(presence_key(session_pid)).await {
+                log::error!("Failed to clear presence for {}: {}", session_pid, err);
+            }
+        }
+        Err(err) => log::error!("Failed to get Redis connection: {}", err),
+    }
+}
+
+pub async fn find_session(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+) -> Result<tenant::entities::telemedicine_sessions::Model, ApiResponse> {
+    tenant::entities::telemedicine_sessions::Entity::find_by_pid(pid)
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch telemedicine session {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch session" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Session not found" })))
+}
+
+pub async fn update_session(
+    tenant_db: &DatabaseConnection,
+    mut session: tenant::entities::telemedicine_sessions::ActiveModel,
+) -> Result<tenant::entities::telemedicine_sessions::Model, ApiResponse> {
+    session.updated_at = Set(Utc::now().naive_utc());
+    session.update(tenant_db).await.map_err(|err| {
+        log::error!("Failed to update telemedicine session: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to update session" }))
+    })
+}
+
+pub fn telemedicine_session_json(
+    session: &tenant::entities::telemedicine_sessions::Model,
+    encounter: Option<&tenant::entities::encounters::Model>,
+) -> Value {
+    json!({
+        "pid": session.pid,
+        "patient_pid": session.patient_pid,
+        "practitioner_id": session.practitioner_id,
+        "scheduled_at": session.scheduled_at,
+        "reason": session.reason,
+        "status": session.status,
+        "patient_joined_at": session.patient_joined_at,
+        "practitioner_joined_at": session.practitioner_joined_at,
+        "started_at": session.started_at,
+        "ended_at": session.ended_at,
+        "duration_seconds": session.duration_seconds,
+        "call_notes": session.call_notes,
+        "cancel_reason": session.cancel_reason,
+        "encounter": encounter.map(encounter_json),
+        "created_at": session.created_at,
+        "updated_at": session.updated_at,
+    })
+}
+
+pub fn encounter_json(encounter: &tenant::entities::encounters::Model) -> Value {
+    json!({
+        "pid": encounter.pid,
+        "patient_pid": encounter.patient_pid,
+        "encounter_type": encounter.encounter_type,
+        "practitioner_id": encounter.practitioner_id,
+        "started_at": encounter.started_at,
+        "ended_at": encounter.ended_at,
+        "reason": encounter.reason,
+        "notes": encounter.notes,
+        "created_at": encounter.created_at,
+    })
+}
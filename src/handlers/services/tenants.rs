@@ -13,7 +13,8 @@ use crate::{
     db::main::{
         self,
         migrations::sea_orm::{
-            ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Select, Set,
+            ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Select,
+            Set,
         },
     },
     handlers::services::tenant_applications::get_tenant_application_data,
@@ -969,3 +970,26 @@ pub async fn permanently_delete_tenant(
         json!({ "message": response.message }),
     ))
 }
+
+/// A facility a patient picked by pid, with its database. Deleted and
+/// unprovisioned facilities are not found.
+pub async fn facility_db(
+    app_state: &AppState,
+    tenant_pid: Uuid,
+) -> Result<(main::entities::tenants::Model, DatabaseConnection), ApiResponse> {
+    let facility = main::entities::tenants::Entity::find_by_pid(tenant_pid)
+        .filter(main::entities::tenants::Column::DeletedAt.is_null())
+        .one(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch tenant {}: {}", tenant_pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch facility" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Facility not found" })))?;
+
+    let tenant_db = app_state
+        .tenant_db(facility.sso_tenant_id)
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Facility not found" })))?;
+
+    Ok((facility, tenant_db))
+}
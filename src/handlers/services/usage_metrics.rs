@@ -0,0 +1,67 @@
+use chrono::{Duration, NaiveDate};
+use serde_json::{Value, json};
+
+use crate::{
+    db::main::{
+        self,
+        entities::sea_orm_active_enums::AggregationPeriod,
+        migrations::sea_orm::{
+            ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set,
+        },
+    },
+    utils::api_response::ApiResponse,
+};
+
+/// Records one daily figure for a facility. A day that already has a row for
+/// `metric_type` is left alone, so nightly jobs can be rerun safely.
+pub async fn record_daily_metric(
+    db: &DatabaseConnection,
+    facility: &main::entities::tenants::Model,
+    metric_type: &str,
+    day: NaiveDate,
+    value: i64,
+    metadata: Option<Value>,
+) -> Result<(), ApiResponse> {
+    let period_start = day.and_time(Default::default());
+
+    let existing = main::entities::usage_metrics::Entity::find()
+        .filter(main::entities::usage_metrics::Column::TenantId.eq(facility.id))
+        .filter(main::entities::usage_metrics::Column::MetricType.eq(metric_type))
+        .filter(main::entities::usage_metrics::Column::PeriodStart.eq(period_start))
+        .filter(main::entities::usage_metrics::Column::DeletedAt.is_null())
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to check usage metric {}: {}", metric_type, err);
+            ApiResponse::new(500, json!({ "message": "Failed to record usage metric" }))
+        })?;
+
+    if existing.is_some() {
+        return Ok(());
+    }
+
+    main::entities::usage_metrics::ActiveModel {
+        tenant_id: Set(Some(facility.id)),
+        metric_type: Set(metric_type.to_string()),
+        metric_value: Set(value),
+        aggregation_period: Set(AggregationPeriod::Daily),
+        period_start: Set(period_start),
+        period_end: Set(period_start + Duration::days(1)),
+        facility_id: Set(Some(facility.pid)),
+        metadata: Set(metadata),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .map_err(|err| {
+        log::error!(
+            "Failed to record usage metric {} for tenant {}: {}",
+            metric_type,
+            facility.pid,
+            err
+        );
+        ApiResponse::new(500, json!({ "message": "Failed to record usage metric" }))
+    })?;
+
+    Ok(())
+}
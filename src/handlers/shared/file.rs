@@ -1,25 +1,206 @@
-use std::collections::HashMap;
-
-use actix_web::{get, web};
+use actix_web::{HttpRequest, get, web};
 use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    db::main::{
+        self,
+        migrations::sea_orm::{
+            ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+            QuerySelect,
+        },
+    },
+    handlers::services::files::authorized_file_url,
+    utils::{
+        api_response::ApiResponse, app_state::AppState, jwt::get_logged_in_user_claims,
+        pagination::PaginationParams,
+    },
+};
+
+const PRESIGNED_URL_EXPIRY_SECS: u64 = 300;
+
+#[get("")]
+async fn index(
+    app_state: web::Data<AppState>,
+    query: web::Query<PaginationParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let claims = get_logged_in_user_claims(&req)?;
+
+    let patient_id = main::entities::patients::Entity::find()
+        .filter(main::entities::patients::Column::SsoUserId.eq(claims.sub))
+        .filter(main::entities::patients::Column::DeletedAt.is_null())
+        .select_only()
+        .column(main::entities::patients::Column::Id)
+        .into_tuple::<i32>()
+        .one(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch patient id: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch files" }))
+        })?;
+
+    let mut ownership =
+        Condition::any().add(main::entities::files::Column::OwnerSsoUserId.eq(claims.sub));
+    if let Some(patient_id) = patient_id {
+        ownership = ownership.add(main::entities::files::Column::PatientId.eq(patient_id));
+    }
+
+    let mut stmt = main::entities::files::Entity::find()
+        .filter(ownership)
+        .filter(main::entities::files::Column::DeletedAt.is_null());
 
-use crate::utils::{api_response::ApiResponse, app_state::AppState, multipart::get_presigned_url};
+    if let Some(term) = &query.search {
+        use main::migrations::{Expr, extension::postgres::PgExpr};
 
-#[get("/files/download")]
+        let like = format!("%{}%", term);
+        stmt = stmt.filter(
+            Condition::any()
+                .add(Expr::col(main::entities::files::Column::OriginalName).ilike(like.clone()))
+                .add(Expr::col(main::entities::files::Column::Category).ilike(like.clone())),
+        );
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(10).clamp(1, 100);
+    let paginator = stmt
+        .order_by_desc(main::entities::files::Column::CreatedAt)
+        .paginate(&app_state.main_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
+
+    let results = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?
+        .into_iter()
+        .map(|file| {
+            json!({
+                "pid": file.pid,
+                "original_name": file.original_name,
+                "category": file.category,
+                "content_type": file.content_type,
+                "size_bytes": file.size_bytes,
+                "visibility": file.visibility,
+                "created_at": file.created_at,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "files": results,
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "Files fetched successfully"
+        }),
+    ))
+}
+
+#[get("/download/{pid}")]
 async fn get_file_url(
     app_state: web::Data<AppState>,
-    query: web::Query<HashMap<String, String>>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
 ) -> Result<ApiResponse, ApiResponse> {
-    let file_name = query
-        .get("file_name")
-        .ok_or_else(|| ApiResponse::new(400, json!({ "message": "file_name missing" })))?;
+    let (url, file) = authorized_file_url(
+        &app_state,
+        &req,
+        path.into_inner(),
+        PRESIGNED_URL_EXPIRY_SECS,
+    )
+    .await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "url": url,
+            "expires_in": PRESIGNED_URL_EXPIRY_SECS,
+            "content_type": file.content_type,
+            "original_name": file.original_name,
+        }),
+    ))
+}
+
+pub async fn access_logs(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    query: web::Query<PaginationParams>,
+) -> Result<ApiResponse, ApiResponse> {
+    let pid = path.into_inner();
+
+    let file = main::entities::files::Entity::find_by_pid(pid)
+        .one(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch file {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch file" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "File not found" })))?;
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(10).clamp(1, 100);
+    let paginator = main::entities::file_access_logs::Entity::find()
+        .filter(main::entities::file_access_logs::Column::FileId.eq(file.id))
+        .order_by_desc(main::entities::file_access_logs::Column::CreatedAt)
+        .paginate(&app_state.main_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
 
-    let url = get_presigned_url(&app_state, file_name, 3600).await?;
+    let results = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?
+        .into_iter()
+        .map(|log| {
+            json!({
+                "pid": log.pid,
+                "sso_user_id": log.sso_user_id,
+                "tenant_pid": log.tenant_pid,
+                "action": log.action,
+                "granted": log.granted,
+                "reason": log.reason,
+                "ip_address": log.ip_address,
+                "user_agent": log.user_agent,
+                "created_at": log.created_at,
+            })
+        })
+        .collect::<Vec<_>>();
 
     Ok(ApiResponse::new(
         200,
         json!({
-            "url": url
+            "file": {
+                "pid": file.pid,
+                "original_name": file.original_name,
+                "category": file.category,
+                "visibility": file.visibility,
+            },
+            "access_logs": results,
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "File access logs fetched successfully"
         }),
     ))
 }
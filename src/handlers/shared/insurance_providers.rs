@@ -0,0 +1,69 @@
+use actix_web::{get, web};
+use serde_json::json;
+
+use crate::{
+    db::main::{
+        self,
+        migrations::sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder},
+    },
+    handlers::services::insurance_providers::provider_json,
+    utils::{api_response::ApiResponse, app_state::AppState, pagination::PaginationParams},
+};
+
+/// Insurers patients can pick from when adding a card, with their active
+/// plans.
+#[get("/insurance-providers")]
+async fn index(
+    app_state: web::Data<AppState>,
+    query: web::Query<PaginationParams>,
+) -> Result<ApiResponse, ApiResponse> {
+    let mut stmt = main::entities::insurance_providers::Entity::find()
+        .filter(main::entities::insurance_providers::Column::DeletedAt.is_null());
+
+    if let Some(term) = &query.search {
+        use main::migrations::{Expr, extension::postgres::PgExpr};
+
+        stmt = stmt.filter(
+            Expr::col(main::entities::insurance_providers::Column::Name)
+                .ilike(format!("%{}%", term)),
+        );
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(50).clamp(1, 100);
+    let paginator = stmt
+        .order_by_asc(main::entities::insurance_providers::Column::Name)
+        .paginate(&app_state.main_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
+
+    let providers = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let mut results = Vec::with_capacity(providers.len());
+    for provider in &providers {
+        results.push(provider_json(provider, false));
+    }
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "insurance_providers": results,
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "Insurance providers fetched successfully",
+        }),
+    ))
+}
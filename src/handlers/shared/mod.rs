@@ -1,4 +1,5 @@
 pub mod file;
+pub mod insurance_providers;
 pub mod profile;
 pub mod session;
 pub mod tenants;
@@ -0,0 +1,174 @@
+use actix_web::{HttpRequest, HttpResponse, get, web};
+use actix_ws::Message;
+use futures_util::StreamExt;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::{
+    handlers::services::telemedicine::{
+        find_session, is_session_open, participant_joined, participant_left, publish_signal,
+        redeem_room_token, subscribe_signals,
+    },
+    utils::{api_response::ApiResponse, app_state::AppState},
+};
+
+/// Messages a participant may relay to the other side of the call.
+const RELAYED_SIGNALS: [&str; 4] = ["offer", "answer", "ice_candidate", "hangup"];
+
+#[derive(Deserialize, Debug)]
+pub struct SignalParams {
+    pub token: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct ClientSignal {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    payload: Value,
+}
+
+/// WebRTC signalling for a telemedicine session. Browsers cannot send an
+/// Authorization header when opening a WebSocket, so the connection is
+/// authorised by the one-time room token issued from the join endpoints.
+/// SDP offers/answers and ICE candidates are relayed through Redis so the
+/// two participants may be connected to different servers.
+#[get("/telemedicine/signal")]
+async fn signal(
+    app_state: web::Data<AppState>,
+    query: web::Query<SignalParams>,
+    req: HttpRequest,
+    body: web::Payload,
+) -> Result<HttpResponse, ApiResponse> {
+    let ticket = redeem_room_token(&app_state.redis, &query.token)
+        .await?
+        .ok_or_else(|| {
+            ApiResponse::new(401, json!({ "message": "Invalid or expired room token" }))
+        })?;
+
+    let tenant_db = app_state
+        .tenant_db(ticket.sso_tenant_id)
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Facility not found" })))?;
+
+    let session = find_session(&tenant_db, ticket.session_pid).await?;
+    if !is_session_open(&session.status) {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Session is no longer open" }),
+        ));
+    }
+
+    let pubsub = subscribe_signals(&app_state.redis, ticket.session_pid).await?;
+
+    let (response, mut ws, mut messages) = actix_ws::handle(&req, body).map_err(|err| {
+        log::error!("Failed to open signalling socket: {}", err);
+        ApiResponse::new(400, json!({ "message": "Expected a WebSocket upgrade" }))
+    })?;
+
+    let redis = app_state.redis.clone();
+    let role = ticket.role.as_str();
+
+    actix_web::rt::spawn(async move {
+        let session = match participant_joined(&redis, &tenant_db, &ticket).await {
+            Ok(session) => session,
+            Err(err) => {
+                log::error!("Failed to join session {}: {}", ticket.session_pid, err);
+                let _ = ws.close(None).await;
+                return;
+            }
+        };
+
+        let welcome = json!({
+            "from": "server",
+            "type": "joined",
+            "role": role,
+            "status": session.status,
+        });
+        if ws.text(welcome.to_string()).await.is_err() {
+            let _ = participant_left(&redis, &tenant_db, &ticket).await;
+            return;
+        }
+
+        publish_signal(
+            &redis,
+            ticket.session_pid,
+            &json!({ "from": role, "type": "peer_joined", "status": session.status }),
+        )
+        .await;
+
+        let mut updates = pubsub.into_on_message();
+
+        loop {
+            tokio::select! {
+                message = messages.next() => match message {
+                    Some(Ok(Message::Text(text))) => {
+                        match serde_json::from_str::<ClientSignal>(&text) {
+                            Ok(signal) if RELAYED_SIGNALS.contains(&signal.kind.as_str()) => {
+                                publish_signal(
+                                    &redis,
+                                    ticket.session_pid,
+                                    &json!({
+                                        "from": role,
+                                        "type": signal.kind,
+                                        "payload": signal.payload,
+                                    }),
+                                )
+                                .await;
+                            }
+                            _ => {
+                                let error = json!({
+                                    "from": "server",
+                                    "type": "error",
+                                    "message": "Unsupported signalling message",
+                                });
+                                if ws.text(error.to_string()).await.is_err() {
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                    Some(Ok(Message::Ping(bytes))) => {
+                        if ws.pong(&bytes).await.is_err() {
+                            break;
+                        }
+                    }
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => {}
+                },
+                update = updates.next() => {
+                    let Some(update) = update else { break };
+                    let Ok(payload) = update.get_payload::<String>() else { continue };
+                    let Ok(signal) = serde_json::from_str::<Value>(&payload) else { continue };
+
+                    // Every subscriber hears its own messages; skip the echo.
+                    if signal["from"] == role {
+                        continue;
+                    }
+
+                    if ws.text(payload).await.is_err() {
+                        break;
+                    }
+
+                    if signal["type"] == "session_ended" {
+                        break;
+                    }
+                }
+            }
+        }
+
+        if let Err(err) = participant_left(&redis, &tenant_db, &ticket).await {
+            log::error!("Failed to leave session {}: {}", ticket.session_pid, err);
+        }
+
+        publish_signal(
+            &redis,
+            ticket.session_pid,
+            &json!({ "from": role, "type": "peer_left" }),
+        )
+        .await;
+
+        let _ = ws.close(None).await;
+    });
+
+    Ok(response)
+}
@@ -0,0 +1,1044 @@
+use std::collections::HashMap;
+
+use actix_web::{HttpRequest, web};
+use chrono::{Duration, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        main,
+        tenant::{
+            self,
+            entities::sea_orm_active_enums::{RegistryEnrolmentStatus, TracingTaskStatus},
+            migrations::sea_orm::{
+                ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+                QueryOrder, Set,
+            },
+        },
+    },
+    handlers::services::{
+        chronic_care::{
+            ControlStatus, chronic_registry_json, control_status, days_overdue,
+            enrol_matching_registries, fetch_active_registries, normalize_diagnosis_code,
+            registry_enrolment_json, registry_follow_up_json, registry_matches,
+        },
+        patients::find_patient,
+    },
+    utils::{
+        api_response::ApiResponse,
+        app_state::AppState,
+        jwt::{get_logged_in_user_claims, get_tenant_db},
+        validator_error::ValidationError,
+    },
+};
+
+/// How far ahead the follow-up due list looks by default.
+const DUE_WITHIN_DAYS: i64 = 7;
+
+#[derive(Deserialize, Debug)]
+pub struct CohortParams {
+    pub days: Option<i64>,
+    pub control: Option<ControlFilter>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ControlFilter {
+    Controlled,
+    Uncontrolled,
+    NoData,
+}
+
+impl ControlFilter {
+    fn matches(&self, status: ControlStatus) -> bool {
+        matches!(
+            (self, status),
+            (ControlFilter::Controlled, ControlStatus::Controlled)
+                | (ControlFilter::Uncontrolled, ControlStatus::Uncontrolled)
+                | (ControlFilter::NoData, ControlStatus::NoData)
+        )
+    }
+}
+
+type Cohort = Vec<(
+    tenant::entities::registry_enrolments::Model,
+    Vec<tenant::entities::registry_follow_ups::Model>,
+)>;
+
+/// Active enrolments on a registry at this facility with their follow-ups,
+/// oldest first.
+async fn fetch_cohort(
+    tenant_db: &DatabaseConnection,
+    registry_pid: Uuid,
+) -> Result<Cohort, ApiResponse> {
+    tenant::entities::registry_enrolments::Entity::find()
+        .find_with_related(tenant::entities::registry_follow_ups::Entity)
+        .filter(tenant::entities::registry_enrolments::Column::RegistryPid.eq(registry_pid))
+        .filter(
+            tenant::entities::registry_enrolments::Column::Status
+                .eq(RegistryEnrolmentStatus::Active),
+        )
+        .filter(tenant::entities::registry_enrolments::Column::DeletedAt.is_null())
+        .order_by_asc(tenant::entities::registry_enrolments::Column::NextFollowUpOn)
+        .order_by_asc(tenant::entities::registry_follow_ups::Column::VisitDate)
+        .all(tenant_db)
+        .await
+        .map(|cohort| {
+            cohort
+                .into_iter()
+                .map(|(enrolment, follow_ups)| {
+                    let follow_ups = follow_ups
+                        .into_iter()
+                        .filter(|f| f.deleted_at.is_none())
+                        .collect();
+                    (enrolment, follow_ups)
+                })
+                .collect()
+        })
+        .map_err(|err| {
+            log::error!("Failed to fetch registry cohort {}: {}", registry_pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch registry cohort" }))
+        })
+}
+
+async fn fetch_patients(
+    app_state: &AppState,
+    patient_pids: Vec<Uuid>,
+) -> Result<HashMap<Uuid, main::entities::patients::Model>, ApiResponse> {
+    if patient_pids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    main::entities::patients::Entity::find()
+        .filter(main::entities::patients::Column::Pid.is_in(patient_pids))
+        .all(&app_state.main_db)
+        .await
+        .map(|patients| patients.into_iter().map(|p| (p.pid, p)).collect())
+        .map_err(|err| {
+            log::error!("Failed to fetch patients: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch patients" }))
+        })
+}
+
+fn patient_json(patient: Option<&main::entities::patients::Model>) -> Value {
+    match patient {
+        Some(patient) => json!({
+            "pid": patient.pid,
+            "first_name": patient.first_name,
+            "last_name": patient.last_name,
+            "phone_number": patient.phone_number,
+            "country_code": patient.country_code,
+        }),
+        None => Value::Null,
+    }
+}
+
+/// Registry dashboard: cohort size, control rate, follow-ups due this week
+/// and defaulters for each programme.
+pub async fn index_registries(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let registries = fetch_active_registries(&app_state.main_db).await?;
+    let today = Utc::now().date_naive();
+
+    let mut results = Vec::new();
+    for registry in &registries {
+        let cohort = fetch_cohort(&tenant_db, registry.pid).await?;
+
+        let mut controlled = 0;
+        let mut uncontrolled = 0;
+        let mut no_data = 0;
+        let mut due = 0;
+        let mut defaulters = 0;
+
+        for (enrolment, follow_ups) in &cohort {
+            match control_status(&registry.control_indicator, follow_ups).0 {
+                ControlStatus::Controlled => controlled += 1,
+                ControlStatus::Uncontrolled => uncontrolled += 1,
+                ControlStatus::NoData => no_data += 1,
+            }
+
+            if enrolment.next_follow_up_on.is_some_and(|next| {
+                next >= today && next <= today + Duration::days(DUE_WITHIN_DAYS)
+            }) {
+                due += 1;
+            }
+
+            if days_overdue(enrolment, today)
+                .is_some_and(|days| days > registry.defaulter_after_days as i64)
+            {
+                defaulters += 1;
+            }
+        }
+
+        let measured = controlled + uncontrolled;
+        let control_rate = if measured > 0 {
+            (controlled as f64 / measured as f64 * 1000.0).round() / 10.0
+        } else {
+            0.0
+        };
+
+        results.push(json!({
+            "registry": chronic_registry_json(registry),
+            "enrolled": cohort.len(),
+            "controlled": controlled,
+            "uncontrolled": uncontrolled,
+            "no_data": no_data,
+            "control_rate": control_rate,
+            "due_this_week": due,
+            "defaulters": defaulters,
+        }));
+    }
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "registries": results,
+            "message": "Chronic registries fetched successfully",
+        }),
+    ))
+}
+
+/// Everyone active on the registry with their control status, optionally
+/// filtered to the controlled, uncontrolled or unmeasured.
+pub async fn cohort(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    query: web::Query<CohortParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let registry = find_registry(&app_state, path.into_inner()).await?;
+    let cohort = fetch_cohort(&tenant_db, registry.pid).await?;
+    let patients = fetch_patients(
+        &app_state,
+        cohort.iter().map(|(e, _)| e.patient_pid).collect(),
+    )
+    .await?;
+
+    let results = cohort
+        .iter()
+        .filter_map(|(enrolment, follow_ups)| {
+            let (status, latest) = control_status(&registry.control_indicator, follow_ups);
+            if query.control.is_some_and(|filter| !filter.matches(status)) {
+                return None;
+            }
+
+            Some(json!({
+                "enrolment": registry_enrolment_json(enrolment),
+                "patient": patient_json(patients.get(&enrolment.patient_pid)),
+                "control_status": status,
+                "latest_measurement": latest.map(registry_follow_up_json),
+            }))
+        })
+        .collect::<Vec<_>>();
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "registry": chronic_registry_json(&registry),
+            "total_items": results.len(),
+            "enrolments": results,
+            "message": "Registry cohort fetched successfully",
+        }),
+    ))
+}
+
+/// Patients whose next follow-up falls within the coming `days` (default 7).
+pub async fn follow_ups_due(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    query: web::Query<CohortParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let registry = find_registry(&app_state, path.into_inner()).await?;
+    let cohort = fetch_cohort(&tenant_db, registry.pid).await?;
+    let today = Utc::now().date_naive();
+    let until = today + Duration::days(query.days.unwrap_or(DUE_WITHIN_DAYS).max(0));
+
+    let due = cohort
+        .iter()
+        .filter(|(enrolment, _)| {
+            enrolment
+                .next_follow_up_on
+                .is_some_and(|next| next >= today && next <= until)
+        })
+        .collect::<Vec<_>>();
+
+    let patients =
+        fetch_patients(&app_state, due.iter().map(|(e, _)| e.patient_pid).collect()).await?;
+
+    let results = due
+        .iter()
+        .map(|(enrolment, follow_ups)| {
+            json!({
+                "enrolment": registry_enrolment_json(enrolment),
+                "patient": patient_json(patients.get(&enrolment.patient_pid)),
+                "control_status": control_status(&registry.control_indicator, follow_ups).0,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "registry": chronic_registry_json(&registry),
+            "from": today,
+            "until": until,
+            "total_items": results.len(),
+            "follow_ups_due": results,
+            "message": "Follow-up due list fetched successfully",
+        }),
+    ))
+}
+
+/// Cohort defaulter report: patients who missed their follow-up by more than
+/// `days`, defaulting to the registry's own threshold.
+pub async fn defaulters(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    query: web::Query<CohortParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let registry = find_registry(&app_state, path.into_inner()).await?;
+    let cohort = fetch_cohort(&tenant_db, registry.pid).await?;
+    let today = Utc::now().date_naive();
+    let threshold = query
+        .days
+        .unwrap_or(registry.defaulter_after_days as i64)
+        .max(0);
+
+    let defaulted = cohort
+        .iter()
+        .filter_map(|(enrolment, _)| {
+            days_overdue(enrolment, today)
+                .filter(|days| *days > threshold)
+                .map(|days| (enrolment, days))
+        })
+        .collect::<Vec<_>>();
+
+    let patients = fetch_patients(
+        &app_state,
+        defaulted.iter().map(|(e, _)| e.patient_pid).collect(),
+    )
+    .await?;
+
+    let results = defaulted
+        .iter()
+        .map(|(enrolment, days)| {
+            json!({
+                "enrolment": registry_enrolment_json(enrolment),
+                "patient": patient_json(patients.get(&enrolment.patient_pid)),
+                "days_overdue": days,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "registry": chronic_registry_json(&registry),
+            "threshold_days": threshold,
+            "cohort_size": cohort.len(),
+            "total_items": results.len(),
+            "defaulters": results,
+            "message": "Defaulter report fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct DiagnosisData {
+    pub patient_pid: Option<Uuid>,
+    pub code: String,
+    pub description: Option<String>,
+    pub diagnosed_on: Option<NaiveDate>,
+}
+
+impl DiagnosisData {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if self.patient_pid.is_none() {
+            errors.insert("patient_pid".to_string(), "Patient is required".to_string());
+        }
+
+        let code = normalize_diagnosis_code(&self.code);
+        if code.is_empty() {
+            errors.insert("code".to_string(), "Diagnosis code is required".to_string());
+        } else if code.len() > 20 || !code.chars().all(|c| c.is_ascii_alphanumeric()) {
+            errors.insert(
+                "code".to_string(),
+                "Diagnosis code must be a valid ICD-10 code".to_string(),
+            );
+        }
+
+        if self
+            .diagnosed_on
+            .is_some_and(|date| date > Utc::now().date_naive())
+        {
+            errors.insert(
+                "diagnosed_on".to_string(),
+                "Diagnosis date cannot be in the future".to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+/// Records a diagnosis and enrols the patient into every registry whose
+/// criteria it meets.
+pub async fn create_diagnosis(
+    app_state: web::Data<AppState>,
+    data: web::Json<DiagnosisData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate() {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let patient = find_patient(&app_state, data.patient_pid.unwrap_or_default()).await?;
+    let diagnosed_on = data.diagnosed_on.unwrap_or_else(|| Utc::now().date_naive());
+    let code = normalize_diagnosis_code(&data.code);
+
+    let diagnosis = tenant::entities::patient_diagnoses::ActiveModel {
+        patient_pid: Set(patient.pid),
+        code: Set(code.clone()),
+        description: Set(data.description.clone()),
+        diagnosed_on: Set(diagnosed_on),
+        recorded_by: Set(Some(claims.sub)),
+        ..Default::default()
+    }
+    .insert(&tenant_db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to record diagnosis: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to record diagnosis" }))
+    })?;
+
+    let registries = fetch_active_registries(&app_state.main_db).await?;
+    let enrolments = enrol_matching_registries(
+        &tenant_db,
+        &registries,
+        patient.pid,
+        &code,
+        diagnosed_on,
+        Some(claims.sub),
+    )
+    .await?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "diagnosis": diagnosis_json(&diagnosis),
+            "enrolments": enrolments.iter().map(registry_enrolment_json).collect::<Vec<_>>(),
+            "message": "Diagnosis recorded successfully",
+        }),
+    ))
+}
+
+pub async fn patient_diagnoses(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let patient = find_patient(&app_state, path.into_inner()).await?;
+
+    let diagnoses = tenant::entities::patient_diagnoses::Entity::find()
+        .filter(tenant::entities::patient_diagnoses::Column::PatientPid.eq(patient.pid))
+        .filter(tenant::entities::patient_diagnoses::Column::DeletedAt.is_null())
+        .order_by_desc(tenant::entities::patient_diagnoses::Column::DiagnosedOn)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch diagnoses: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch diagnoses" }))
+        })?;
+
+    let enrolments = tenant::entities::registry_enrolments::Entity::find()
+        .filter(tenant::entities::registry_enrolments::Column::PatientPid.eq(patient.pid))
+        .filter(tenant::entities::registry_enrolments::Column::DeletedAt.is_null())
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch registry enrolments: {}", err);
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to fetch registry enrolments" }),
+            )
+        })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "diagnoses": diagnoses.iter().map(diagnosis_json).collect::<Vec<_>>(),
+            "enrolments": enrolments.iter().map(registry_enrolment_json).collect::<Vec<_>>(),
+            "message": "Diagnoses fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct EnrolmentData {
+    pub registry_pid: Option<Uuid>,
+    pub patient_pid: Option<Uuid>,
+    pub diagnosis_code: String,
+    pub enrolled_on: Option<NaiveDate>,
+}
+
+/// Manual enrolment, e.g. for patients transferred in with an existing
+/// diagnosis. The code must still meet the registry's criteria.
+pub async fn create_enrolment(
+    app_state: web::Data<AppState>,
+    data: web::Json<EnrolmentData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let mut errors = HashMap::new();
+    if data.registry_pid.is_none() {
+        errors.insert(
+            "registry_pid".to_string(),
+            "Registry is required".to_string(),
+        );
+    }
+    if data.patient_pid.is_none() {
+        errors.insert("patient_pid".to_string(), "Patient is required".to_string());
+    }
+    if data.diagnosis_code.trim().is_empty() {
+        errors.insert(
+            "diagnosis_code".to_string(),
+            "Diagnosis code is required".to_string(),
+        );
+    }
+    if !errors.is_empty() {
+        return Err(ApiResponse::new(400, json!(ValidationError { errors })));
+    }
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let registry = find_registry(&app_state, data.registry_pid.unwrap_or_default()).await?;
+    let patient = find_patient(&app_state, data.patient_pid.unwrap_or_default()).await?;
+
+    if !registry_matches(&registry, &data.diagnosis_code) {
+        let mut errors = HashMap::new();
+        errors.insert(
+            "diagnosis_code".to_string(),
+            format!(
+                "Diagnosis code does not meet the {} enrolment criteria ({})",
+                registry.name,
+                registry.diagnosis_codes.join(", ")
+            ),
+        );
+        return Err(ApiResponse::new(400, json!(ValidationError { errors })));
+    }
+
+    let enrolments = enrol_matching_registries(
+        &tenant_db,
+        std::slice::from_ref(&registry),
+        patient.pid,
+        &data.diagnosis_code,
+        data.enrolled_on.unwrap_or_else(|| Utc::now().date_naive()),
+        Some(claims.sub),
+    )
+    .await?;
+
+    let Some(enrolment) = enrolments.first() else {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Patient is already enrolled on this registry" }),
+        ));
+    };
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "enrolment": registry_enrolment_json(enrolment),
+            "message": "Patient enrolled successfully",
+        }),
+    ))
+}
+
+pub async fn show_enrolment(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let enrolment = find_enrolment(&tenant_db, path.into_inner()).await?;
+    let registry = find_registry(&app_state, enrolment.registry_pid).await?;
+    let follow_ups = fetch_follow_ups(&tenant_db, enrolment.id).await?;
+    let (status, latest) = control_status(&registry.control_indicator, &follow_ups);
+
+    let tracing_tasks = tenant::entities::defaulter_tracing_tasks::Entity::find()
+        .filter(tenant::entities::defaulter_tracing_tasks::Column::EnrolmentId.eq(enrolment.id))
+        .order_by_desc(tenant::entities::defaulter_tracing_tasks::Column::CreatedAt)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch tracing tasks: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch tracing tasks" }))
+        })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "registry": chronic_registry_json(&registry),
+            "enrolment": registry_enrolment_json(&enrolment),
+            "control_status": status,
+            "latest_measurement": latest.map(registry_follow_up_json),
+            "follow_ups": follow_ups.iter().map(registry_follow_up_json).collect::<Vec<_>>(),
+            "tracing_tasks": tracing_tasks.iter().map(tracing_task_json).collect::<Vec<_>>(),
+            "message": "Enrolment fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct FollowUpData {
+    pub visit_date: Option<NaiveDate>,
+    pub bp_systolic: Option<i32>,
+    pub bp_diastolic: Option<i32>,
+    pub hba1c: Option<Decimal>,
+    pub fasting_glucose: Option<Decimal>,
+    pub viral_load: Option<i32>,
+    pub cd4_count: Option<i32>,
+    pub adherence: Option<String>,
+    pub next_follow_up_on: Option<NaiveDate>,
+    pub notes: Option<String>,
+}
+
+impl FollowUpData {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+        let today = Utc::now().date_naive();
+
+        if self.visit_date.is_some_and(|date| date > today) {
+            errors.insert(
+                "visit_date".to_string(),
+                "Visit date cannot be in the future".to_string(),
+            );
+        }
+
+        if self.bp_systolic.is_some() != self.bp_diastolic.is_some() {
+            errors.insert(
+                "bp_systolic".to_string(),
+                "Both systolic and diastolic pressure are required".to_string(),
+            );
+        }
+
+        if let (Some(systolic), Some(diastolic)) = (self.bp_systolic, self.bp_diastolic)
+            && systolic <= diastolic
+        {
+            errors.insert(
+                "bp_systolic".to_string(),
+                "Systolic pressure must be higher than diastolic pressure".to_string(),
+            );
+        }
+
+        if self
+            .hba1c
+            .is_some_and(|hba1c| hba1c <= Decimal::ZERO || hba1c > Decimal::from(25))
+        {
+            errors.insert(
+                "hba1c".to_string(),
+                "HbA1c must be between 0 and 25%".to_string(),
+            );
+        }
+
+        if self.viral_load.is_some_and(|vl| vl < 0) {
+            errors.insert(
+                "viral_load".to_string(),
+                "Viral load must be greater than or equal to 0".to_string(),
+            );
+        }
+
+        if let Some(adherence) = &self.adherence
+            && !["good", "fair", "poor"].contains(&adherence.as_str())
+        {
+            errors.insert(
+                "adherence".to_string(),
+                "Adherence must be one of: good, fair, poor".to_string(),
+            );
+        }
+
+        if self.next_follow_up_on.is_some_and(|date| date <= today) {
+            errors.insert(
+                "next_follow_up_on".to_string(),
+                "Next follow-up must be in the future".to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+/// Records a follow-up visit, books the next one and closes any open
+/// defaulter tracing for the patient.
+pub async fn create_follow_up(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<FollowUpData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate() {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let enrolment = find_enrolment(&tenant_db, path.into_inner()).await?;
+
+    if enrolment.status != RegistryEnrolmentStatus::Active {
+        return Err(ApiResponse::new(
+            400,
+            json!({ "message": "Follow-ups can only be recorded for active enrolments" }),
+        ));
+    }
+
+    let registry = find_registry(&app_state, enrolment.registry_pid).await?;
+    let visit_date = data.visit_date.unwrap_or_else(|| Utc::now().date_naive());
+    let next_follow_up_on = data
+        .next_follow_up_on
+        .unwrap_or_else(|| visit_date + Duration::days(registry.follow_up_interval_days as i64));
+
+    let follow_up = tenant::entities::registry_follow_ups::ActiveModel {
+        enrolment_id: Set(enrolment.id),
+        visit_date: Set(visit_date),
+        bp_systolic: Set(data.bp_systolic),
+        bp_diastolic: Set(data.bp_diastolic),
+        hba1c: Set(data.hba1c),
+        fasting_glucose: Set(data.fasting_glucose),
+        viral_load: Set(data.viral_load),
+        cd4_count: Set(data.cd4_count),
+        adherence: Set(data.adherence.clone()),
+        next_follow_up_on: Set(Some(next_follow_up_on)),
+        notes: Set(data.notes.clone()),
+        recorded_by: Set(Some(claims.sub)),
+        ..Default::default()
+    }
+    .insert(&tenant_db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to record follow-up: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to record follow-up" }))
+    })?;
+
+    if enrolment
+        .last_follow_up_on
+        .is_none_or(|last| visit_date >= last)
+    {
+        let mut active_model: tenant::entities::registry_enrolments::ActiveModel =
+            enrolment.to_owned().into();
+        active_model.last_follow_up_on = Set(Some(visit_date));
+        active_model.next_follow_up_on = Set(Some(next_follow_up_on));
+        active_model.updated_at = Set(Utc::now().naive_utc());
+        active_model.update(&tenant_db).await.map_err(|err| {
+            log::error!("Failed to update enrolment {}: {}", enrolment.pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to record follow-up" }))
+        })?;
+    }
+
+    let open_tasks = tenant::entities::defaulter_tracing_tasks::Entity::find()
+        .filter(tenant::entities::defaulter_tracing_tasks::Column::EnrolmentId.eq(enrolment.id))
+        .filter(tenant::entities::defaulter_tracing_tasks::Column::ClosedAt.is_null())
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch tracing tasks: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to record follow-up" }))
+        })?;
+
+    for task in open_tasks {
+        let mut active_model: tenant::entities::defaulter_tracing_tasks::ActiveModel = task.into();
+        active_model.status = Set(TracingTaskStatus::Returned);
+        active_model.closed_at = Set(Some(Utc::now().naive_utc()));
+        active_model.updated_at = Set(Utc::now().naive_utc());
+        active_model.update(&tenant_db).await.map_err(|err| {
+            log::error!("Failed to close tracing task: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to record follow-up" }))
+        })?;
+    }
+
+    let follow_ups = fetch_follow_ups(&tenant_db, enrolment.id).await?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "follow_up": registry_follow_up_json(&follow_up),
+            "control_status": control_status(&registry.control_indicator, &follow_ups).0,
+            "message": "Follow-up recorded successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ExitEnrolmentData {
+    pub status: RegistryEnrolmentStatus,
+    pub exit_reason: Option<String>,
+    pub exited_on: Option<NaiveDate>,
+}
+
+pub async fn exit_enrolment(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<ExitEnrolmentData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if data.status == RegistryEnrolmentStatus::Active {
+        let mut errors = HashMap::new();
+        errors.insert(
+            "status".to_string(),
+            "Exit status must be one of: transferred_out, lost_to_follow_up, died, exited"
+                .to_string(),
+        );
+        return Err(ApiResponse::new(400, json!(ValidationError { errors })));
+    }
+
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let enrolment = find_enrolment(&tenant_db, path.into_inner()).await?;
+
+    let mut active_model: tenant::entities::registry_enrolments::ActiveModel =
+        enrolment.to_owned().into();
+    active_model.status = Set(data.status.clone());
+    active_model.exit_reason = Set(data.exit_reason.clone());
+    active_model.exited_on = Set(Some(
+        data.exited_on.unwrap_or_else(|| Utc::now().date_naive()),
+    ));
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let enrolment = active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!("Failed to exit enrolment {}: {}", enrolment.pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to update enrolment" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "enrolment": registry_enrolment_json(&enrolment),
+            "message": "Enrolment updated successfully",
+        }),
+    ))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TracingTaskParams {
+    pub status: Option<TracingTaskStatus>,
+}
+
+pub async fn index_tracing_tasks(
+    app_state: web::Data<AppState>,
+    query: web::Query<TracingTaskParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let mut stmt = tenant::entities::defaulter_tracing_tasks::Entity::find()
+        .find_also_related(tenant::entities::registry_enrolments::Entity);
+
+    stmt = match &query.status {
+        Some(status) => stmt
+            .filter(tenant::entities::defaulter_tracing_tasks::Column::Status.eq(status.clone())),
+        None => stmt.filter(tenant::entities::defaulter_tracing_tasks::Column::ClosedAt.is_null()),
+    };
+
+    let tasks = stmt
+        .order_by_asc(tenant::entities::defaulter_tracing_tasks::Column::MissedFollowUpOn)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch tracing tasks: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch tracing tasks" }))
+        })?;
+
+    let patients = fetch_patients(
+        &app_state,
+        tasks
+            .iter()
+            .filter_map(|(_, enrolment)| enrolment.as_ref().map(|e| e.patient_pid))
+            .collect(),
+    )
+    .await?;
+
+    let results = tasks
+        .iter()
+        .map(|(task, enrolment)| {
+            let mut task_json = tracing_task_json(task);
+            task_json["enrolment"] = enrolment
+                .as_ref()
+                .map(registry_enrolment_json)
+                .unwrap_or(Value::Null);
+            task_json["patient"] = patient_json(
+                enrolment
+                    .as_ref()
+                    .and_then(|e| patients.get(&e.patient_pid)),
+            );
+            task_json
+        })
+        .collect::<Vec<_>>();
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "total_items": results.len(),
+            "tracing_tasks": results,
+            "message": "Tracing tasks fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct TracingTaskData {
+    pub status: Option<TracingTaskStatus>,
+    pub outcome_notes: Option<String>,
+    pub assigned_to: Option<Uuid>,
+}
+
+/// Logs a tracing attempt. Closing outcomes (returned, unreachable, closed)
+/// stamp `closed_at`.
+pub async fn edit_tracing_task(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<TracingTaskData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let pid = path.into_inner();
+
+    let task = tenant::entities::defaulter_tracing_tasks::Entity::find_by_pid(pid)
+        .one(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch tracing task {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch tracing task" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Tracing task not found" })))?;
+
+    let mut active_model: tenant::entities::defaulter_tracing_tasks::ActiveModel =
+        task.to_owned().into();
+
+    if let Some(status) = &data.status {
+        active_model.status = Set(status.clone());
+        active_model.attempts = Set(task.attempts + 1);
+        active_model.closed_at = Set(match status {
+            TracingTaskStatus::Pending | TracingTaskStatus::Contacted => None,
+            _ => Some(Utc::now().naive_utc()),
+        });
+    }
+    if let Some(outcome_notes) = &data.outcome_notes {
+        active_model.outcome_notes = Set(Some(outcome_notes.clone()));
+    }
+    if let Some(assigned_to) = data.assigned_to {
+        active_model.assigned_to = Set(Some(assigned_to));
+    }
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let task = active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!("Failed to update tracing task {}: {}", pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to update tracing task" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "tracing_task": tracing_task_json(&task),
+            "message": "Tracing task updated successfully",
+        }),
+    ))
+}
+
+fn diagnosis_json(diagnosis: &tenant::entities::patient_diagnoses::Model) -> Value {
+    json!({
+        "pid": diagnosis.pid,
+        "patient_pid": diagnosis.patient_pid,
+        "code": diagnosis.code,
+        "description": diagnosis.description,
+        "diagnosed_on": diagnosis.diagnosed_on,
+        "created_at": diagnosis.created_at,
+    })
+}
+
+fn tracing_task_json(task: &tenant::entities::defaulter_tracing_tasks::Model) -> Value {
+    json!({
+        "pid": task.pid,
+        "missed_follow_up_on": task.missed_follow_up_on,
+        "status": task.status,
+        "attempts": task.attempts,
+        "sms_sent_at": task.sms_sent_at,
+        "outcome_notes": task.outcome_notes,
+        "assigned_to": task.assigned_to,
+        "closed_at": task.closed_at,
+        "created_at": task.created_at,
+        "updated_at": task.updated_at,
+    })
+}
+
+async fn fetch_follow_ups(
+    tenant_db: &DatabaseConnection,
+    enrolment_id: i32,
+) -> Result<Vec<tenant::entities::registry_follow_ups::Model>, ApiResponse> {
+    tenant::entities::registry_follow_ups::Entity::find()
+        .filter(tenant::entities::registry_follow_ups::Column::EnrolmentId.eq(enrolment_id))
+        .filter(tenant::entities::registry_follow_ups::Column::DeletedAt.is_null())
+        .order_by_asc(tenant::entities::registry_follow_ups::Column::VisitDate)
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch follow-ups: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch follow-ups" }))
+        })
+}
+
+async fn find_enrolment(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+) -> Result<tenant::entities::registry_enrolments::Model, ApiResponse> {
+    tenant::entities::registry_enrolments::Entity::find_by_pid(pid)
+        .filter(tenant::entities::registry_enrolments::Column::DeletedAt.is_null())
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch enrolment {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch enrolment" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Enrolment not found" })))
+}
+
+async fn find_registry(
+    app_state: &AppState,
+    pid: Uuid,
+) -> Result<main::entities::chronic_registries::Model, ApiResponse> {
+    main::entities::chronic_registries::Entity::find_by_pid(pid)
+        .filter(main::entities::chronic_registries::Column::DeletedAt.is_null())
+        .one(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch chronic registry {}: {}", pid, err);
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to fetch chronic registry" }),
+            )
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Chronic registry not found" })))
+}
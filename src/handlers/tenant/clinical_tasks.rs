@@ -0,0 +1,169 @@
+use actix_web::{HttpRequest, web};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    db::tenant::{
+        self,
+        entities::sea_orm_active_enums::{ClinicalTaskPriority, ClinicalTaskStatus},
+        migrations::sea_orm::{
+            ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, Set,
+        },
+    },
+    handlers::services::clinical_tasks::clinical_task_json,
+    utils::{
+        api_response::ApiResponse,
+        app_state::AppState,
+        jwt::{get_logged_in_user_claims, get_tenant_db},
+    },
+};
+
+#[derive(Deserialize, Debug)]
+pub struct TaskParams {
+    pub status: Option<ClinicalTaskStatus>,
+    pub priority: Option<ClinicalTaskPriority>,
+    pub patient_pid: Option<Uuid>,
+    pub mine: Option<bool>,
+}
+
+/// Open work first, most urgent and oldest due date at the top.
+pub async fn index(
+    app_state: web::Data<AppState>,
+    query: web::Query<TaskParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let mut stmt = tenant::entities::clinical_tasks::Entity::find();
+
+    stmt = match &query.status {
+        Some(status) => {
+            stmt.filter(tenant::entities::clinical_tasks::Column::Status.eq(status.clone()))
+        }
+        None => stmt.filter(
+            tenant::entities::clinical_tasks::Column::Status
+                .is_in([ClinicalTaskStatus::Open, ClinicalTaskStatus::InProgress]),
+        ),
+    };
+
+    if let Some(priority) = &query.priority {
+        stmt = stmt.filter(tenant::entities::clinical_tasks::Column::Priority.eq(priority.clone()));
+    }
+
+    if let Some(patient_pid) = query.patient_pid {
+        stmt = stmt.filter(tenant::entities::clinical_tasks::Column::PatientPid.eq(patient_pid));
+    }
+
+    if query.mine.unwrap_or(false) {
+        stmt = stmt.filter(tenant::entities::clinical_tasks::Column::AssignedTo.eq(claims.sub));
+    }
+
+    let mut tasks = stmt
+        .order_by_asc(tenant::entities::clinical_tasks::Column::DueAt)
+        .order_by_asc(tenant::entities::clinical_tasks::Column::CreatedAt)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch clinical tasks: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch clinical tasks" }))
+        })?;
+
+    // Postgres sorts enums by declaration order (low..urgent); sort here so the
+    // rank stays explicit.
+    tasks.sort_by_key(|task| match task.priority {
+        ClinicalTaskPriority::Urgent => 0,
+        ClinicalTaskPriority::High => 1,
+        ClinicalTaskPriority::Normal => 2,
+        ClinicalTaskPriority::Low => 3,
+    });
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "total_items": tasks.len(),
+            "clinical_tasks": tasks.iter().map(clinical_task_json).collect::<Vec<_>>(),
+            "message": "Clinical tasks fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ClinicalTaskData {
+    pub status: Option<ClinicalTaskStatus>,
+    pub assigned_to: Option<Uuid>,
+    pub resolution: Option<String>,
+}
+
+pub async fn edit(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<ClinicalTaskData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let pid = path.into_inner();
+
+    let task = tenant::entities::clinical_tasks::Entity::find_by_pid(pid)
+        .one(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch clinical task {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch clinical task" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Clinical task not found" })))?;
+
+    if matches!(
+        data.status,
+        Some(ClinicalTaskStatus::Completed | ClinicalTaskStatus::Cancelled)
+    ) && data
+        .resolution
+        .as_deref()
+        .is_none_or(|resolution| resolution.trim().is_empty())
+    {
+        return Err(ApiResponse::new(
+            400,
+            json!({ "message": "A resolution note is required to close a task" }),
+        ));
+    }
+
+    let mut active_model: tenant::entities::clinical_tasks::ActiveModel = task.into();
+
+    if let Some(status) = &data.status {
+        active_model.status = Set(status.clone());
+        match status {
+            ClinicalTaskStatus::Completed | ClinicalTaskStatus::Cancelled => {
+                active_model.completed_by = Set(Some(claims.sub));
+                active_model.completed_at = Set(Some(Utc::now().naive_utc()));
+            }
+            _ => {
+                active_model.completed_by = Set(None);
+                active_model.completed_at = Set(None);
+            }
+        }
+    }
+    if let Some(assigned_to) = data.assigned_to {
+        active_model.assigned_to = Set(Some(assigned_to));
+    }
+    if let Some(resolution) = &data.resolution {
+        active_model.resolution = Set(Some(resolution.clone()));
+    }
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let task = active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!("Failed to update clinical task {}: {}", pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to update clinical task" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "clinical_task": clinical_task_json(&task),
+            "message": "Clinical task updated successfully",
+        }),
+    ))
+}
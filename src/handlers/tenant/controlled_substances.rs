@@ -0,0 +1,1140 @@
+use std::collections::HashMap;
+
+use actix_web::{HttpRequest, web};
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        main::{self, entities::sea_orm_active_enums::FileVisibility},
+        tenant::{
+            self,
+            entities::sea_orm_active_enums::{ControlledReconciliationStatus, StockMovementType},
+            migrations::sea_orm::{
+                ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait,
+                QueryFilter, QueryOrder, Select, Set, TransactionTrait,
+            },
+        },
+    },
+    handlers::services::{
+        controlled_substances::{
+            csv_field, find_controlled_product, find_reconciliation, ledger_balance,
+            reconciliation_json, register_entry_json,
+        },
+        files::authorized_file_url,
+        pharmacy::{
+            NewMovement, batch_on_hand, find_batch, find_store, lock_product_batches,
+            product_label, record_movement,
+        },
+    },
+    utils::{
+        api_response::ApiResponse,
+        app_state::AppState,
+        jwt::{get_logged_in_user_claims, get_tenant_db},
+        multipart::upload_file,
+        validator_error::ValidationError,
+    },
+};
+
+/// How long a register export link stays valid.
+const EXPORT_URL_EXPIRY_SECS: u64 = 600;
+
+#[derive(Deserialize, Debug)]
+pub struct RegisterParams {
+    pub store_pid: Option<Uuid>,
+    pub product_pid: Option<Uuid>,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    /// Only entries still waiting for a witness.
+    pub unwitnessed: Option<bool>,
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+}
+
+/// Register entries in the order they were written.
+pub async fn index_entries(
+    app_state: web::Data<AppState>,
+    query: web::Query<RegisterParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let stmt = register_query(&tenant_db, &query).await?;
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(50).clamp(1, 200);
+    let paginator = stmt
+        .order_by_asc(tenant::entities::controlled_register_entries::Column::Id)
+        .paginate(&tenant_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
+
+    let entries = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let (products, batches) = fetch_products_and_batches(&tenant_db, &entries).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "entries": entries
+                .iter()
+                .map(|entry| {
+                    register_entry_json(
+                        entry,
+                        products.get(&entry.product_id),
+                        batches.get(&entry.batch_id),
+                    )
+                })
+                .collect::<Vec<_>>(),
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "Register entries fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BalanceParams {
+    pub store_pid: Uuid,
+}
+
+/// The running balance of every controlled product in a store, with how
+/// many of its entries still need a witness.
+pub async fn balances(
+    app_state: web::Data<AppState>,
+    query: web::Query<BalanceParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let store = find_store(&tenant_db, query.store_pid).await?;
+
+    let products = tenant::entities::pharmacy_products::Entity::find()
+        .filter(tenant::entities::pharmacy_products::Column::IsControlled.eq(true))
+        .filter(tenant::entities::pharmacy_products::Column::DeletedAt.is_null())
+        .order_by_asc(tenant::entities::pharmacy_products::Column::GenericName)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch controlled products: {}", err);
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to fetch register balances" }),
+            )
+        })?;
+
+    let mut balances = Vec::with_capacity(products.len());
+    for product in &products {
+        let last_entry = tenant::entities::controlled_register_entries::Entity::find()
+            .filter(tenant::entities::controlled_register_entries::Column::StoreId.eq(store.id))
+            .filter(tenant::entities::controlled_register_entries::Column::ProductId.eq(product.id))
+            .order_by_desc(tenant::entities::controlled_register_entries::Column::Id)
+            .one(&tenant_db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to fetch register entries: {}", err);
+                ApiResponse::new(
+                    500,
+                    json!({ "message": "Failed to fetch register balances" }),
+                )
+            })?;
+
+        let awaiting_witness = tenant::entities::controlled_register_entries::Entity::find()
+            .filter(tenant::entities::controlled_register_entries::Column::StoreId.eq(store.id))
+            .filter(tenant::entities::controlled_register_entries::Column::ProductId.eq(product.id))
+            .filter(tenant::entities::controlled_register_entries::Column::WitnessedBy.is_null())
+            .count(&tenant_db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to count unwitnessed entries: {}", err);
+                ApiResponse::new(
+                    500,
+                    json!({ "message": "Failed to fetch register balances" }),
+                )
+            })?;
+
+        balances.push(json!({
+            "product": {
+                "pid": product.pid,
+                "label": product_label(product),
+                "unit": product.unit,
+                "controlled_schedule": product.controlled_schedule,
+            },
+            "balance": ledger_balance(&tenant_db, store.id, product.id).await?,
+            "last_entry_at": last_entry.map(|entry| entry.created_at),
+            "awaiting_witness": awaiting_witness,
+        }));
+    }
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "store": {
+                "pid": store.pid,
+                "name": store.name,
+                "code": store.code,
+            },
+            "balances": balances,
+            "message": "Register balances fetched successfully",
+        }),
+    ))
+}
+
+/// Co-signs an entry. The witness must be someone other than the person who
+/// moved the stock, and an entry can only be witnessed once.
+pub async fn witness_entry(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let pid = path.into_inner();
+
+    let entry = tenant::entities::controlled_register_entries::Entity::find_by_pid(pid)
+        .one(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch register entry {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch register entry" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Register entry not found" })))?;
+
+    if entry.performed_by == claims.sub {
+        return Err(ApiResponse::new(
+            403,
+            json!({ "message": "You cannot witness your own register entry" }),
+        ));
+    }
+
+    let now = Utc::now().naive_utc();
+    let result = tenant::entities::controlled_register_entries::Entity::update_many()
+        .set(tenant::entities::controlled_register_entries::ActiveModel {
+            witnessed_by: Set(Some(claims.sub)),
+            witnessed_at: Set(Some(now)),
+            ..Default::default()
+        })
+        .filter(tenant::entities::controlled_register_entries::Column::Id.eq(entry.id))
+        .filter(tenant::entities::controlled_register_entries::Column::WitnessedBy.is_null())
+        .exec(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to witness register entry {}: {}", pid, err);
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to witness register entry" }),
+            )
+        })?;
+
+    if result.rows_affected == 0 {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Register entry is already witnessed" }),
+        ));
+    }
+
+    let entry = tenant::entities::controlled_register_entries::Model {
+        witnessed_by: Some(claims.sub),
+        witnessed_at: Some(now),
+        ..entry
+    };
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "entry": register_entry_json(&entry, None, None),
+            "message": "Register entry witnessed successfully",
+        }),
+    ))
+}
+
+/// Exports a store's register as CSV, one row per entry, with the patient
+/// and prescriber for dispensing and the supplier for deliveries.
+pub async fn export(
+    app_state: web::Data<AppState>,
+    query: web::Query<RegisterParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let Some(store_pid) = query.store_pid else {
+        return Err(ApiResponse::new(
+            400,
+            json!(ValidationError {
+                errors: HashMap::from([("store_pid".to_string(), "Store is required".to_string())]),
+            }),
+        ));
+    };
+
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let store = find_store(&tenant_db, store_pid).await?;
+    let entries = register_query(&tenant_db, &query)
+        .await?
+        .order_by_asc(tenant::entities::controlled_register_entries::Column::Id)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch register entries: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to export register" }))
+        })?;
+
+    let (products, batches) = fetch_products_and_batches(&tenant_db, &entries).await?;
+    let counterparties = counterparties(&app_state, &tenant_db, &entries).await?;
+
+    let mut csv = String::from(
+        "Entry No,Date,Time,Store,Product,Schedule,Batch,Expiry,Transaction,Received From / Issued To,Prescriber,Reference,Quantity In,Quantity Out,Balance,Performed By,Witnessed By,Witnessed At,Reason\n",
+    );
+    for entry in &entries {
+        let product = products.get(&entry.product_id);
+        let batch = batches.get(&entry.batch_id);
+        let (counterparty, prescriber) = counterparties
+            .get(&entry.stock_movement_id)
+            .cloned()
+            .unwrap_or_default();
+
+        let row = [
+            entry.id.to_string(),
+            entry.created_at.format("%Y-%m-%d").to_string(),
+            entry.created_at.format("%H:%M").to_string(),
+            store.name.clone(),
+            product.map(product_label).unwrap_or_default(),
+            product
+                .and_then(|product| product.controlled_schedule.clone())
+                .unwrap_or_default(),
+            batch
+                .map(|batch| batch.batch_number.clone())
+                .unwrap_or_default(),
+            batch
+                .map(|batch| batch.expiry_date.to_string())
+                .unwrap_or_default(),
+            movement_label(&entry.movement_type).to_string(),
+            counterparty,
+            prescriber,
+            entry.reference.clone().unwrap_or_default(),
+            entry.quantity.max(0).to_string(),
+            (-entry.quantity).max(0).to_string(),
+            entry.balance_after.to_string(),
+            entry.performed_by.to_string(),
+            entry
+                .witnessed_by
+                .map(|witness| witness.to_string())
+                .unwrap_or_else(|| "NOT WITNESSED".to_string()),
+            entry
+                .witnessed_at
+                .map(|at| at.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_default(),
+            entry.reason.clone().unwrap_or_default(),
+        ];
+        csv.push_str(
+            &row.iter()
+                .map(|field| csv_field(field))
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+        csv.push('\n');
+    }
+
+    let file_pid = upload_file(
+        &req,
+        &app_state,
+        &format!("controlled_register/{}.csv", Uuid::new_v4()),
+        csv.into_bytes(),
+        "text/csv",
+        None,
+        FileVisibility::Tenant,
+    )
+    .await?;
+    let (url, _) = authorized_file_url(&app_state, &req, file_pid, EXPORT_URL_EXPIRY_SECS).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "file_pid": file_pid,
+            "url": url,
+            "expires_in": EXPORT_URL_EXPIRY_SECS,
+            "total_entries": entries.len(),
+            "message": "Register exported successfully",
+        }),
+    ))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ReconciliationParams {
+    pub store_pid: Option<Uuid>,
+    pub product_pid: Option<Uuid>,
+    pub status: Option<ControlledReconciliationStatus>,
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+}
+
+pub async fn index_reconciliations(
+    app_state: web::Data<AppState>,
+    query: web::Query<ReconciliationParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let mut stmt = tenant::entities::controlled_reconciliations::Entity::find()
+        .find_also_related(tenant::entities::pharmacy_products::Entity);
+
+    if let Some(store_pid) = query.store_pid {
+        let store = find_store(&tenant_db, store_pid).await?;
+        stmt =
+            stmt.filter(tenant::entities::controlled_reconciliations::Column::StoreId.eq(store.id));
+    }
+
+    if let Some(product_pid) = query.product_pid {
+        let product = find_controlled_product(&tenant_db, product_pid).await?;
+        stmt = stmt
+            .filter(tenant::entities::controlled_reconciliations::Column::ProductId.eq(product.id));
+    }
+
+    if let Some(status) = &query.status {
+        stmt = stmt.filter(
+            tenant::entities::controlled_reconciliations::Column::Status.eq(status.clone()),
+        );
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let paginator = stmt
+        .order_by_desc(tenant::entities::controlled_reconciliations::Column::CreatedAt)
+        .paginate(&tenant_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
+
+    let reconciliations = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "reconciliations": reconciliations
+                .iter()
+                .map(|(reconciliation, product)| {
+                    reconciliation_json(reconciliation, product.as_ref(), None)
+                })
+                .collect::<Vec<_>>(),
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "Reconciliations fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ReconciliationData {
+    pub store_pid: Option<Uuid>,
+    pub product_pid: Option<Uuid>,
+    /// Units physically counted across all batches.
+    pub counted_quantity: Option<i64>,
+    pub notes: Option<String>,
+}
+
+impl ReconciliationData {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if self.store_pid.is_none() {
+            errors.insert("store_pid".to_string(), "Store is required".to_string());
+        }
+
+        if self.product_pid.is_none() {
+            errors.insert("product_pid".to_string(), "Product is required".to_string());
+        }
+
+        if self.counted_quantity.is_none_or(|q| q < 0) {
+            errors.insert(
+                "counted_quantity".to_string(),
+                "Counted quantity is required and cannot be negative".to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+/// Records a physical count against the register balance. Every entry for
+/// the product must be witnessed first, so the count is compared with a
+/// register both parties have signed. A count that differs opens a
+/// discrepancy.
+pub async fn create_reconciliation(
+    app_state: web::Data<AppState>,
+    data: web::Json<ReconciliationData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate() {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let store = find_store(&tenant_db, data.store_pid.unwrap_or_default()).await?;
+    let product = find_controlled_product(&tenant_db, data.product_pid.unwrap_or_default()).await?;
+
+    let awaiting_witness = tenant::entities::controlled_register_entries::Entity::find()
+        .filter(tenant::entities::controlled_register_entries::Column::StoreId.eq(store.id))
+        .filter(tenant::entities::controlled_register_entries::Column::ProductId.eq(product.id))
+        .filter(tenant::entities::controlled_register_entries::Column::WitnessedBy.is_null())
+        .count(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to count unwitnessed entries: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to record reconciliation" }))
+        })?;
+
+    if awaiting_witness > 0 {
+        return Err(ApiResponse::new(
+            409,
+            json!({
+                "message": "Register entries must be witnessed before reconciling",
+                "awaiting_witness": awaiting_witness,
+            }),
+        ));
+    }
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start reconciliation transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to record reconciliation" }))
+    })?;
+
+    // Holds movements of the product off while the balance is read.
+    lock_product_batches(&txn, product.id).await?;
+    let register_balance = ledger_balance(&txn, store.id, product.id).await?;
+    let counted_quantity = data.counted_quantity.unwrap_or_default();
+    let variance = counted_quantity - register_balance;
+
+    let reconciliation = tenant::entities::controlled_reconciliations::ActiveModel {
+        store_id: Set(store.id),
+        product_id: Set(product.id),
+        register_balance: Set(register_balance),
+        counted_quantity: Set(counted_quantity),
+        variance: Set(variance),
+        status: Set(if variance == 0 {
+            ControlledReconciliationStatus::Balanced
+        } else {
+            ControlledReconciliationStatus::Discrepancy
+        }),
+        notes: Set(data.notes.clone()),
+        counted_by: Set(claims.sub),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to create reconciliation: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to record reconciliation" }))
+    })?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit reconciliation: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to record reconciliation" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "reconciliation": reconciliation_json(&reconciliation, Some(&product), Some(&store)),
+            "message": if variance == 0 {
+                "Stock count matches the register"
+            } else {
+                "Stock count does not match the register; a discrepancy has been opened"
+            },
+        }),
+    ))
+}
+
+pub async fn show_reconciliation(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let reconciliation = find_reconciliation(&tenant_db, path.into_inner()).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "reconciliation": reconciliation_detail(&tenant_db, &reconciliation).await?,
+            "message": "Reconciliation fetched successfully",
+        }),
+    ))
+}
+
+/// Co-signs a stock count. The witness cannot be the person who counted.
+pub async fn witness_reconciliation(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let reconciliation = find_reconciliation(&tenant_db, path.into_inner()).await?;
+
+    if reconciliation.counted_by == claims.sub {
+        return Err(ApiResponse::new(
+            403,
+            json!({ "message": "You cannot witness your own stock count" }),
+        ));
+    }
+
+    if reconciliation.witnessed_by.is_some() {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Stock count is already witnessed" }),
+        ));
+    }
+
+    let reconciliation_pid = reconciliation.pid;
+    let mut active_model: tenant::entities::controlled_reconciliations::ActiveModel =
+        reconciliation.into();
+    active_model.witnessed_by = Set(Some(claims.sub));
+    active_model.witnessed_at = Set(Some(Utc::now().naive_utc()));
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let reconciliation = active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!(
+            "Failed to witness reconciliation {}: {}",
+            reconciliation_pid,
+            err
+        );
+        ApiResponse::new(500, json!({ "message": "Failed to witness stock count" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "reconciliation": reconciliation_detail(&tenant_db, &reconciliation).await?,
+            "message": "Stock count witnessed successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct InvestigationData {
+    pub notes: Option<String>,
+}
+
+/// Adds findings to an open discrepancy. Notes are appended with a
+/// timestamp so earlier findings are kept.
+pub async fn investigate(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<InvestigationData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let Some(notes) = data
+        .notes
+        .as_deref()
+        .map(str::trim)
+        .filter(|n| !n.is_empty())
+    else {
+        return Err(ApiResponse::new(
+            400,
+            json!(ValidationError {
+                errors: HashMap::from([(
+                    "notes".to_string(),
+                    "Investigation notes are required".to_string()
+                )]),
+            }),
+        ));
+    };
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let reconciliation = find_reconciliation(&tenant_db, path.into_inner()).await?;
+
+    if !matches!(
+        reconciliation.status,
+        ControlledReconciliationStatus::Discrepancy | ControlledReconciliationStatus::Investigating
+    ) {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Only open discrepancies can be investigated" }),
+        ));
+    }
+
+    let now = Utc::now().naive_utc();
+    let entry = format!("[{}] {}", now.format("%Y-%m-%d %H:%M"), notes);
+    let investigation_notes = match &reconciliation.investigation_notes {
+        Some(existing) => format!("{}\n{}", existing, entry),
+        None => entry,
+    };
+
+    let reconciliation_pid = reconciliation.pid;
+    let mut active_model: tenant::entities::controlled_reconciliations::ActiveModel =
+        reconciliation.into();
+    active_model.status = Set(ControlledReconciliationStatus::Investigating);
+    active_model.investigation_notes = Set(Some(investigation_notes));
+    active_model.investigated_by = Set(Some(claims.sub));
+    active_model.updated_at = Set(now);
+
+    let reconciliation = active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!(
+            "Failed to update reconciliation {}: {}",
+            reconciliation_pid,
+            err
+        );
+        ApiResponse::new(500, json!({ "message": "Failed to record investigation" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "reconciliation": reconciliation_detail(&tenant_db, &reconciliation).await?,
+            "message": "Investigation recorded successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ResolutionData {
+    pub resolution: Option<String>,
+    /// The batch to adjust by the variance so stock matches the count.
+    /// Leave out when the count itself was wrong.
+    pub batch_pid: Option<Uuid>,
+}
+
+/// Closes a witnessed discrepancy. When a batch is given the variance is
+/// booked to it as an adjustment, which goes through the register like any
+/// other movement and still needs its own witness.
+pub async fn resolve(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<ResolutionData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let Some(resolution) = data
+        .resolution
+        .as_deref()
+        .map(str::trim)
+        .filter(|r| !r.is_empty())
+    else {
+        return Err(ApiResponse::new(
+            400,
+            json!(ValidationError {
+                errors: HashMap::from([(
+                    "resolution".to_string(),
+                    "Resolution is required".to_string()
+                )]),
+            }),
+        ));
+    };
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let reconciliation = find_reconciliation(&tenant_db, path.into_inner()).await?;
+
+    if !matches!(
+        reconciliation.status,
+        ControlledReconciliationStatus::Discrepancy | ControlledReconciliationStatus::Investigating
+    ) {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Only open discrepancies can be resolved" }),
+        ));
+    }
+
+    if reconciliation.witnessed_by.is_none() {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "The stock count must be witnessed before it is resolved" }),
+        ));
+    }
+
+    let batch = match data.batch_pid {
+        Some(batch_pid) => {
+            let batch = find_batch(&tenant_db, batch_pid).await?;
+            if batch.product_id != reconciliation.product_id {
+                return Err(ApiResponse::new(
+                    400,
+                    json!(ValidationError {
+                        errors: HashMap::from([(
+                            "batch_pid".to_string(),
+                            "Batch is not of the reconciled product".to_string()
+                        )]),
+                    }),
+                ));
+            }
+            Some(batch)
+        }
+        None => None,
+    };
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start resolution transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to resolve discrepancy" }))
+    })?;
+
+    let mut adjustment_entry_id = None;
+    if let Some(batch) = &batch {
+        let quantity = reconciliation.variance as i32;
+        lock_product_batches(&txn, batch.product_id).await?;
+        if quantity < 0 {
+            let on_hand = batch_on_hand(&txn, reconciliation.store_id, batch).await?;
+            if on_hand < -quantity as i64 {
+                return Err(ApiResponse::new(
+                    409,
+                    json!({
+                        "message": "Batch does not hold enough stock to absorb the variance",
+                        "requested": -quantity,
+                        "available": on_hand,
+                    }),
+                ));
+            }
+        }
+
+        let movement = record_movement(
+            &txn,
+            NewMovement {
+                store_id: reconciliation.store_id,
+                batch,
+                movement_type: StockMovementType::Adjustment,
+                quantity,
+                transfer_pid: None,
+                reference: Some(format!("Reconciliation {}", reconciliation.pid)),
+                reason: Some(resolution.to_string()),
+                performed_by: claims.sub,
+            },
+        )
+        .await?;
+
+        adjustment_entry_id = tenant::entities::controlled_register_entries::Entity::find()
+            .filter(
+                tenant::entities::controlled_register_entries::Column::StockMovementId
+                    .eq(movement.id),
+            )
+            .one(&txn)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to fetch adjustment entry: {}", err);
+                ApiResponse::new(500, json!({ "message": "Failed to resolve discrepancy" }))
+            })?
+            .map(|entry| entry.id);
+    }
+
+    let reconciliation_pid = reconciliation.pid;
+    let mut active_model: tenant::entities::controlled_reconciliations::ActiveModel =
+        reconciliation.into();
+    active_model.status = Set(ControlledReconciliationStatus::Resolved);
+    active_model.resolution = Set(Some(resolution.to_string()));
+    active_model.adjustment_entry_id = Set(adjustment_entry_id);
+    active_model.resolved_by = Set(Some(claims.sub));
+    active_model.resolved_at = Set(Some(Utc::now().naive_utc()));
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let reconciliation = active_model.update(&txn).await.map_err(|err| {
+        log::error!(
+            "Failed to resolve reconciliation {}: {}",
+            reconciliation_pid,
+            err
+        );
+        ApiResponse::new(500, json!({ "message": "Failed to resolve discrepancy" }))
+    })?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit resolution: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to resolve discrepancy" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "reconciliation": reconciliation_detail(&tenant_db, &reconciliation).await?,
+            "message": "Discrepancy resolved successfully",
+        }),
+    ))
+}
+
+fn movement_label(movement_type: &StockMovementType) -> &'static str {
+    match movement_type {
+        StockMovementType::Receipt => "Received",
+        StockMovementType::Dispense => "Dispensed",
+        StockMovementType::TransferIn => "Transfer in",
+        StockMovementType::TransferOut => "Transfer out",
+        StockMovementType::Adjustment => "Adjustment",
+        StockMovementType::Wastage => "Destroyed / wastage",
+    }
+}
+
+async fn register_query(
+    tenant_db: &DatabaseConnection,
+    query: &RegisterParams,
+) -> Result<Select<tenant::entities::controlled_register_entries::Entity>, ApiResponse> {
+    let mut stmt = tenant::entities::controlled_register_entries::Entity::find();
+
+    if let Some(store_pid) = query.store_pid {
+        let store = find_store(tenant_db, store_pid).await?;
+        stmt = stmt
+            .filter(tenant::entities::controlled_register_entries::Column::StoreId.eq(store.id));
+    }
+
+    if let Some(product_pid) = query.product_pid {
+        let product = find_controlled_product(tenant_db, product_pid).await?;
+        stmt = stmt.filter(
+            tenant::entities::controlled_register_entries::Column::ProductId.eq(product.id),
+        );
+    }
+
+    if let Some(from) = query.from {
+        stmt = stmt.filter(
+            tenant::entities::controlled_register_entries::Column::CreatedAt
+                .gte(from.and_hms_opt(0, 0, 0).unwrap_or_default()),
+        );
+    }
+
+    if let Some(to) = query.to {
+        stmt = stmt.filter(
+            tenant::entities::controlled_register_entries::Column::CreatedAt
+                .lt((to + chrono::Duration::days(1))
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap_or_default()),
+        );
+    }
+
+    if query.unwitnessed.unwrap_or(false) {
+        stmt = stmt
+            .filter(tenant::entities::controlled_register_entries::Column::WitnessedBy.is_null());
+    }
+
+    Ok(stmt)
+}
+
+async fn fetch_products_and_batches(
+    tenant_db: &DatabaseConnection,
+    entries: &[tenant::entities::controlled_register_entries::Model],
+) -> Result<
+    (
+        HashMap<i32, tenant::entities::pharmacy_products::Model>,
+        HashMap<i32, tenant::entities::stock_batches::Model>,
+    ),
+    ApiResponse,
+> {
+    let products = tenant::entities::pharmacy_products::Entity::find()
+        .filter(
+            tenant::entities::pharmacy_products::Column::Id
+                .is_in(entries.iter().map(|entry| entry.product_id)),
+        )
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch products: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch register" }))
+        })?
+        .into_iter()
+        .map(|product| (product.id, product))
+        .collect();
+
+    let batches = tenant::entities::stock_batches::Entity::find()
+        .filter(
+            tenant::entities::stock_batches::Column::Id
+                .is_in(entries.iter().map(|entry| entry.batch_id)),
+        )
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch batches: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch register" }))
+        })?
+        .into_iter()
+        .map(|batch| (batch.id, batch))
+        .collect();
+
+    Ok((products, batches))
+}
+
+/// Who each movement was received from or issued to, and the prescriber for
+/// dispensing, keyed by stock movement.
+async fn counterparties(
+    app_state: &AppState,
+    tenant_db: &DatabaseConnection,
+    entries: &[tenant::entities::controlled_register_entries::Model],
+) -> Result<HashMap<i32, (String, String)>, ApiResponse> {
+    let movement_ids = entries
+        .iter()
+        .map(|entry| entry.stock_movement_id)
+        .collect::<Vec<_>>();
+    let mut counterparties = HashMap::new();
+
+    let dispensed = tenant::entities::dispense_items::Entity::find()
+        .filter(
+            tenant::entities::dispense_items::Column::StockMovementId.is_in(movement_ids.clone()),
+        )
+        .find_also_related(tenant::entities::dispenses::Entity)
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch dispensed items: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to export register" }))
+        })?;
+
+    let prescribers: HashMap<i32, Uuid> = tenant::entities::prescriptions::Entity::find()
+        .filter(
+            tenant::entities::prescriptions::Column::Id.is_in(
+                dispensed
+                    .iter()
+                    .filter_map(|(_, dispense)| dispense.as_ref().map(|d| d.prescription_id)),
+            ),
+        )
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch prescriptions: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to export register" }))
+        })?
+        .into_iter()
+        .map(|prescription| (prescription.id, prescription.prescriber_id))
+        .collect();
+
+    let patients: HashMap<Uuid, String> = main::entities::patients::Entity::find()
+        .filter(
+            main::entities::patients::Column::Pid.is_in(
+                dispensed
+                    .iter()
+                    .filter_map(|(_, dispense)| dispense.as_ref().map(|d| d.patient_pid)),
+            ),
+        )
+        .all(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch patients: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to export register" }))
+        })?
+        .into_iter()
+        .map(|patient| {
+            let name = [
+                &patient.first_name,
+                &patient.middle_name,
+                &patient.last_name,
+            ]
+            .iter()
+            .filter_map(|name| name.as_deref())
+            .collect::<Vec<_>>()
+            .join(" ");
+            (patient.pid, name)
+        })
+        .collect();
+
+    for (item, dispense) in &dispensed {
+        let Some(dispense) = dispense else {
+            continue;
+        };
+        counterparties.insert(
+            item.stock_movement_id,
+            (
+                patients
+                    .get(&dispense.patient_pid)
+                    .cloned()
+                    .unwrap_or_else(|| dispense.patient_pid.to_string()),
+                prescribers
+                    .get(&dispense.prescription_id)
+                    .map(|prescriber| prescriber.to_string())
+                    .unwrap_or_default(),
+            ),
+        );
+    }
+
+    let received = tenant::entities::goods_received_items::Entity::find()
+        .filter(tenant::entities::goods_received_items::Column::StockMovementId.is_in(movement_ids))
+        .find_also_related(tenant::entities::goods_received_notes::Entity)
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch received items: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to export register" }))
+        })?;
+
+    let suppliers: HashMap<i32, String> = tenant::entities::suppliers::Entity::find()
+        .filter(
+            tenant::entities::suppliers::Column::Id.is_in(
+                received
+                    .iter()
+                    .filter_map(|(_, grn)| grn.as_ref().map(|grn| grn.supplier_id)),
+            ),
+        )
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch suppliers: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to export register" }))
+        })?
+        .into_iter()
+        .map(|supplier| (supplier.id, supplier.name))
+        .collect();
+
+    for (item, grn) in &received {
+        let Some(grn) = grn else {
+            continue;
+        };
+        counterparties.insert(
+            item.stock_movement_id,
+            (
+                format!(
+                    "{} ({})",
+                    suppliers.get(&grn.supplier_id).cloned().unwrap_or_default(),
+                    grn.grn_number
+                ),
+                String::new(),
+            ),
+        );
+    }
+
+    Ok(counterparties)
+}
+
+async fn reconciliation_detail(
+    tenant_db: &DatabaseConnection,
+    reconciliation: &tenant::entities::controlled_reconciliations::Model,
+) -> Result<serde_json::Value, ApiResponse> {
+    let product =
+        tenant::entities::pharmacy_products::Entity::find_by_id(reconciliation.product_id)
+            .one(tenant_db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to fetch product: {}", err);
+                ApiResponse::new(500, json!({ "message": "Failed to fetch reconciliation" }))
+            })?;
+    let store = tenant::entities::pharmacy_stores::Entity::find_by_id(reconciliation.store_id)
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch store: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch reconciliation" }))
+        })?;
+
+    Ok(reconciliation_json(
+        reconciliation,
+        product.as_ref(),
+        store.as_ref(),
+    ))
+}
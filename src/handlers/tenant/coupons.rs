@@ -0,0 +1,760 @@
+use std::collections::{HashMap, HashSet};
+
+use actix_web::{HttpRequest, web};
+use chrono::{NaiveDate, NaiveDateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::tenant::{
+        self,
+        entities::sea_orm_active_enums::{CouponDiscountType, CouponScope},
+        migrations::sea_orm::{
+            ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait,
+            PaginatorTrait, QueryFilter, QueryOrder, Select, Set,
+        },
+    },
+    handlers::services::coupons::{coupon_json, find_coupon, redemption_count, redemption_json},
+    utils::{
+        api_response::ApiResponse,
+        app_state::AppState,
+        jwt::{get_logged_in_user_claims, get_tenant_db},
+        validator_error::ValidationError,
+    },
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct CouponData {
+    pub code: Option<String>,
+    pub description: Option<String>,
+    pub discount_type: Option<CouponDiscountType>,
+    /// A percentage for percentage coupons, an amount for fixed ones.
+    pub value: Option<Decimal>,
+    /// Caps what a percentage coupon can take off.
+    pub max_discount: Option<Decimal>,
+    pub scope: Option<CouponScope>,
+    /// Product pids, category names or service codes, depending on `scope`.
+    pub targets: Option<Vec<String>>,
+    pub min_order_value: Option<Decimal>,
+    pub max_redemptions: Option<i32>,
+    pub max_redemptions_per_patient: Option<i32>,
+    pub starts_at: Option<NaiveDateTime>,
+    pub ends_at: Option<NaiveDateTime>,
+    /// Whether the coupon may be used together with other coupons.
+    pub is_stackable: Option<bool>,
+    pub is_active: Option<bool>,
+}
+
+impl CouponData {
+    pub fn validate(&self, is_create: bool) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        match self.code.as_deref().map(str::trim) {
+            None if is_create => {
+                errors.insert("code".to_string(), "Coupon code is required".to_string());
+            }
+            Some(code) if !(3..=30).contains(&code.len()) => {
+                errors.insert(
+                    "code".to_string(),
+                    "Coupon code must be between 3 and 30 characters".to_string(),
+                );
+            }
+            Some(code)
+                if !code
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') =>
+            {
+                errors.insert(
+                    "code".to_string(),
+                    "Coupon code may only contain letters, numbers, dashes and underscores"
+                        .to_string(),
+                );
+            }
+            _ => {}
+        }
+
+        if self
+            .description
+            .as_ref()
+            .is_some_and(|d| d.chars().count() > 500)
+        {
+            errors.insert(
+                "description".to_string(),
+                "Description must be at most 500 characters".to_string(),
+            );
+        }
+
+        if is_create && self.discount_type.is_none() {
+            errors.insert(
+                "discount_type".to_string(),
+                "Discount type is required".to_string(),
+            );
+        }
+
+        match self.value {
+            None if is_create => {
+                errors.insert("value".to_string(), "Value is required".to_string());
+            }
+            Some(value) if value <= Decimal::ZERO => {
+                errors.insert(
+                    "value".to_string(),
+                    "Value must be greater than zero".to_string(),
+                );
+            }
+            _ => {}
+        }
+
+        if self.max_discount.is_some_and(|m| m <= Decimal::ZERO) {
+            errors.insert(
+                "max_discount".to_string(),
+                "Maximum discount must be greater than zero".to_string(),
+            );
+        }
+
+        if is_create && self.scope.is_none() {
+            errors.insert("scope".to_string(), "Scope is required".to_string());
+        }
+
+        if self.min_order_value.is_some_and(|m| m < Decimal::ZERO) {
+            errors.insert(
+                "min_order_value".to_string(),
+                "Minimum order value cannot be negative".to_string(),
+            );
+        }
+
+        for (field, limit) in [
+            ("max_redemptions", self.max_redemptions),
+            (
+                "max_redemptions_per_patient",
+                self.max_redemptions_per_patient,
+            ),
+        ] {
+            if limit.is_some_and(|l| l < 1) {
+                errors.insert(field.to_string(), "Limit must be at least 1".to_string());
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+/// The coupon's terms once an edit is applied, checked as a whole.
+struct CouponTerms {
+    discount_type: CouponDiscountType,
+    value: Decimal,
+    scope: CouponScope,
+    targets: Vec<String>,
+    starts_at: Option<NaiveDateTime>,
+    ends_at: Option<NaiveDateTime>,
+}
+
+impl CouponTerms {
+    fn merge(data: &CouponData, coupon: Option<&tenant::entities::coupons::Model>) -> Self {
+        CouponTerms {
+            discount_type: data
+                .discount_type
+                .clone()
+                .or_else(|| coupon.map(|c| c.discount_type.clone()))
+                .unwrap_or(CouponDiscountType::Fixed),
+            value: data
+                .value
+                .or_else(|| coupon.map(|c| c.value))
+                .unwrap_or_default(),
+            scope: data
+                .scope
+                .clone()
+                .or_else(|| coupon.map(|c| c.scope.clone()))
+                .unwrap_or(CouponScope::Order),
+            targets: data
+                .targets
+                .clone()
+                .or_else(|| coupon.and_then(|c| c.targets.clone()))
+                .unwrap_or_default()
+                .iter()
+                .map(|target| target.trim().to_string())
+                .filter(|target| !target.is_empty())
+                .collect(),
+            starts_at: data.starts_at.or_else(|| coupon.and_then(|c| c.starts_at)),
+            ends_at: data.ends_at.or_else(|| coupon.and_then(|c| c.ends_at)),
+        }
+    }
+
+    /// Product targets must be products that exist; the other checks need no
+    /// lookups.
+    async fn validate(&self, tenant_db: &DatabaseConnection) -> Result<(), ApiResponse> {
+        let mut errors = HashMap::new();
+
+        if self.discount_type == CouponDiscountType::Percentage && self.value > Decimal::from(100) {
+            errors.insert(
+                "value".to_string(),
+                "A percentage cannot be more than 100".to_string(),
+            );
+        }
+
+        if self.scope == CouponScope::Order {
+            if !self.targets.is_empty() {
+                errors.insert(
+                    "targets".to_string(),
+                    "Whole-order coupons do not take targets".to_string(),
+                );
+            }
+        } else if self.targets.is_empty() {
+            errors.insert(
+                "targets".to_string(),
+                "At least one target is required for this scope".to_string(),
+            );
+        }
+
+        if self.scope == CouponScope::Products {
+            let pids: Vec<Uuid> = self
+                .targets
+                .iter()
+                .filter_map(|target| Uuid::parse_str(target).ok())
+                .collect();
+            if pids.len() != self.targets.len() {
+                errors.insert(
+                    "targets".to_string(),
+                    "Product targets must be product pids".to_string(),
+                );
+            } else if !pids.is_empty() {
+                let found = tenant::entities::pharmacy_products::Entity::find()
+                    .filter(tenant::entities::pharmacy_products::Column::Pid.is_in(pids.clone()))
+                    .filter(tenant::entities::pharmacy_products::Column::DeletedAt.is_null())
+                    .count(tenant_db)
+                    .await
+                    .map_err(|err| {
+                        log::error!("Failed to check coupon products: {}", err);
+                        ApiResponse::new(500, json!({ "message": "Failed to save coupon" }))
+                    })?;
+                if found != pids.iter().collect::<HashSet<_>>().len() as u64 {
+                    errors.insert(
+                        "targets".to_string(),
+                        "One or more products were not found".to_string(),
+                    );
+                }
+            }
+        }
+
+        if let (Some(starts_at), Some(ends_at)) = (self.starts_at, self.ends_at)
+            && ends_at <= starts_at
+        {
+            errors.insert(
+                "ends_at".to_string(),
+                "End must be after the start".to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ApiResponse::new(400, json!(ValidationError { errors })))
+        }
+    }
+
+    fn stored_targets(&self) -> Option<Vec<String>> {
+        match self.scope {
+            CouponScope::Order => None,
+            CouponScope::Products => Some(
+                self.targets
+                    .iter()
+                    .map(|target| target.to_lowercase())
+                    .collect(),
+            ),
+            _ => Some(self.targets.clone()),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CouponParams {
+    pub search: Option<String>,
+    pub is_active: Option<bool>,
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+}
+
+pub async fn index(
+    app_state: web::Data<AppState>,
+    query: web::Query<CouponParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let mut stmt = tenant::entities::coupons::Entity::find()
+        .filter(tenant::entities::coupons::Column::DeletedAt.is_null());
+
+    if let Some(is_active) = query.is_active {
+        stmt = stmt.filter(tenant::entities::coupons::Column::IsActive.eq(is_active));
+    }
+
+    if let Some(term) = &query.search {
+        use tenant::migrations::{Expr, extension::postgres::PgExpr};
+
+        let like = format!("%{}%", term);
+        stmt = stmt.filter(
+            Condition::any()
+                .add(Expr::col(tenant::entities::coupons::Column::Code).ilike(like.clone()))
+                .add(Expr::col(tenant::entities::coupons::Column::Description).ilike(like)),
+        );
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(10).clamp(1, 100);
+    let paginator = stmt
+        .order_by_desc(tenant::entities::coupons::Column::CreatedAt)
+        .paginate(&tenant_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
+
+    let coupons = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "coupons": coupons.iter().map(coupon_json).collect::<Vec<_>>(),
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "Coupons fetched successfully",
+        }),
+    ))
+}
+
+pub async fn create(
+    app_state: web::Data<AppState>,
+    data: web::Json<CouponData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate(true) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let terms = CouponTerms::merge(&data, None);
+    terms.validate(&tenant_db).await?;
+
+    let code = data
+        .code
+        .as_deref()
+        .unwrap_or_default()
+        .trim()
+        .to_uppercase();
+    ensure_code_available(&tenant_db, &code, None).await?;
+
+    let coupon = tenant::entities::coupons::ActiveModel {
+        code: Set(code),
+        description: Set(data.description.clone()),
+        discount_type: Set(terms.discount_type.clone()),
+        value: Set(terms.value),
+        max_discount: Set(data.max_discount),
+        scope: Set(terms.scope.clone()),
+        targets: Set(terms.stored_targets()),
+        min_order_value: Set(data.min_order_value),
+        max_redemptions: Set(data.max_redemptions),
+        max_redemptions_per_patient: Set(data.max_redemptions_per_patient),
+        starts_at: Set(terms.starts_at),
+        ends_at: Set(terms.ends_at),
+        is_stackable: Set(data.is_stackable.unwrap_or(false)),
+        is_active: Set(data.is_active.unwrap_or(true)),
+        created_by: Set(claims.sub),
+        ..Default::default()
+    }
+    .insert(&tenant_db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to create coupon: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create coupon" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "coupon": coupon_json(&coupon),
+            "message": "Coupon created successfully",
+        }),
+    ))
+}
+
+/// The coupon with how much it has been used.
+pub async fn show(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let coupon = find_coupon(&tenant_db, path.into_inner()).await?;
+
+    let redemptions = tenant::entities::coupon_redemptions::Entity::find()
+        .filter(tenant::entities::coupon_redemptions::Column::CouponId.eq(coupon.id))
+        .filter(tenant::entities::coupon_redemptions::Column::VoidedAt.is_null())
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch redemptions of {}: {}", coupon.pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch coupon" }))
+        })?;
+
+    let patients: HashSet<Uuid> = redemptions.iter().map(|r| r.patient_pid).collect();
+    let total_discount: Decimal = redemptions.iter().map(|r| r.discount_amount).sum();
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "coupon": coupon_json(&coupon),
+            "usage": {
+                "redemptions": redemptions.len(),
+                "remaining": coupon
+                    .max_redemptions
+                    .map(|max| (max as i64 - redemptions.len() as i64).max(0)),
+                "patients": patients.len(),
+                "total_discount": total_discount,
+            },
+            "message": "Coupon fetched successfully",
+        }),
+    ))
+}
+
+pub async fn edit(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<CouponData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate(false) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let coupon = find_coupon(&tenant_db, path.into_inner()).await?;
+    let coupon_pid = coupon.pid;
+
+    let terms = CouponTerms::merge(&data, Some(&coupon));
+    terms.validate(&tenant_db).await?;
+
+    if let Some(max_redemptions) = data.max_redemptions {
+        let used = redemption_count(&tenant_db, coupon.id, None).await?;
+        if (max_redemptions as u64) < used {
+            return Err(ApiResponse::new(
+                400,
+                json!(ValidationError {
+                    errors: HashMap::from([(
+                        "max_redemptions".to_string(),
+                        format!("Coupon has already been redeemed {} times", used),
+                    )]),
+                }),
+            ));
+        }
+    }
+
+    let mut active_model: tenant::entities::coupons::ActiveModel = coupon.into();
+
+    if let Some(code) = &data.code {
+        let code = code.trim().to_uppercase();
+        ensure_code_available(&tenant_db, &code, Some(coupon_pid)).await?;
+        active_model.code = Set(code);
+    }
+    if let Some(description) = &data.description {
+        active_model.description = Set(Some(description.clone()));
+    }
+    active_model.discount_type = Set(terms.discount_type.clone());
+    active_model.value = Set(terms.value);
+    if let Some(max_discount) = data.max_discount {
+        active_model.max_discount = Set(Some(max_discount));
+    }
+    active_model.scope = Set(terms.scope.clone());
+    active_model.targets = Set(terms.stored_targets());
+    if let Some(min_order_value) = data.min_order_value {
+        active_model.min_order_value = Set(Some(min_order_value));
+    }
+    if let Some(max_redemptions) = data.max_redemptions {
+        active_model.max_redemptions = Set(Some(max_redemptions));
+    }
+    if let Some(max_redemptions_per_patient) = data.max_redemptions_per_patient {
+        active_model.max_redemptions_per_patient = Set(Some(max_redemptions_per_patient));
+    }
+    active_model.starts_at = Set(terms.starts_at);
+    active_model.ends_at = Set(terms.ends_at);
+    if let Some(is_stackable) = data.is_stackable {
+        active_model.is_stackable = Set(is_stackable);
+    }
+    if let Some(is_active) = data.is_active {
+        active_model.is_active = Set(is_active);
+    }
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let coupon = active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!("Failed to update coupon {}: {}", coupon_pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to update coupon" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "coupon": coupon_json(&coupon),
+            "message": "Coupon updated successfully",
+        }),
+    ))
+}
+
+/// Removes the coupon from use. Its redemptions stay for reporting.
+pub async fn destroy(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let coupon = find_coupon(&tenant_db, path.into_inner()).await?;
+
+    let coupon_pid = coupon.pid;
+    let mut active_model: tenant::entities::coupons::ActiveModel = coupon.into();
+    active_model.is_active = Set(false);
+    active_model.deleted_at = Set(Some(Utc::now().naive_utc()));
+    active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!("Failed to delete coupon {}: {}", coupon_pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to delete coupon" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({ "message": "Coupon deleted successfully" }),
+    ))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct RedemptionParams {
+    pub coupon_pid: Option<Uuid>,
+    pub patient_pid: Option<Uuid>,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    /// Include redemptions of cancelled orders.
+    pub include_voided: Option<bool>,
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+}
+
+/// Every use of a coupon, newest first.
+pub async fn redemptions(
+    app_state: web::Data<AppState>,
+    query: web::Query<RedemptionParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let mut stmt = redemption_query(query.from, query.to);
+
+    if let Some(coupon_pid) = query.coupon_pid {
+        let coupon = find_coupon(&tenant_db, coupon_pid).await?;
+        stmt = stmt.filter(tenant::entities::coupon_redemptions::Column::CouponId.eq(coupon.id));
+    }
+
+    if let Some(patient_pid) = query.patient_pid {
+        stmt =
+            stmt.filter(tenant::entities::coupon_redemptions::Column::PatientPid.eq(patient_pid));
+    }
+
+    if !query.include_voided.unwrap_or(false) {
+        stmt = stmt.filter(tenant::entities::coupon_redemptions::Column::VoidedAt.is_null());
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let paginator = stmt
+        .find_also_related(tenant::entities::coupons::Entity)
+        .order_by_desc(tenant::entities::coupon_redemptions::Column::CreatedAt)
+        .paginate(&tenant_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
+
+    let redemptions = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "redemptions": redemptions
+                .iter()
+                .map(|(redemption, coupon)| redemption_json(redemption, coupon.as_ref()))
+                .collect::<Vec<_>>(),
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "Redemptions fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ReportParams {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+/// Redemptions and revenue impact per coupon over a period. Order value is
+/// what the orders were worth before discounts; an order that used two
+/// coupons counts once in the totals.
+pub async fn report(
+    app_state: web::Data<AppState>,
+    query: web::Query<ReportParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let redemptions = redemption_query(query.from, query.to)
+        .filter(tenant::entities::coupon_redemptions::Column::VoidedAt.is_null())
+        .find_also_related(tenant::entities::coupons::Entity)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch coupon redemptions: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to build coupon report" }))
+        })?;
+
+    let mut per_coupon: Vec<(i32, Value, u64, Decimal, Decimal)> = Vec::new();
+    let mut orders: HashMap<(String, Uuid), Decimal> = HashMap::new();
+    let mut total_discount = Decimal::ZERO;
+
+    for (redemption, coupon) in &redemptions {
+        total_discount += redemption.discount_amount;
+        orders.insert(
+            (redemption.source_type.clone(), redemption.source_pid),
+            redemption.order_value,
+        );
+
+        match per_coupon
+            .iter_mut()
+            .find(|(coupon_id, ..)| *coupon_id == redemption.coupon_id)
+        {
+            Some((_, _, count, discount, order_value)) => {
+                *count += 1;
+                *discount += redemption.discount_amount;
+                *order_value += redemption.order_value;
+            }
+            None => per_coupon.push((
+                redemption.coupon_id,
+                json!(coupon.as_ref().map(|coupon| json!({
+                    "pid": coupon.pid,
+                    "code": coupon.code,
+                    "description": coupon.description,
+                    "is_active": coupon.is_active,
+                    "deleted": coupon.deleted_at.is_some(),
+                }))),
+                1,
+                redemption.discount_amount,
+                redemption.order_value,
+            )),
+        }
+    }
+
+    per_coupon.sort_by_key(|(_, _, _, discount, _)| std::cmp::Reverse(*discount));
+    let gross_order_value: Decimal = orders.values().copied().sum();
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "from": query.from,
+            "to": query.to,
+            "coupons": per_coupon
+                .iter()
+                .map(|(_, coupon, count, discount, order_value)| json!({
+                    "coupon": coupon,
+                    "redemptions": count,
+                    "total_discount": discount,
+                    "gross_order_value": order_value,
+                    "net_order_value": *order_value - *discount,
+                }))
+                .collect::<Vec<_>>(),
+            "totals": {
+                "redemptions": redemptions.len(),
+                "orders": orders.len(),
+                "total_discount": total_discount,
+                "gross_order_value": gross_order_value,
+                "net_order_value": gross_order_value - total_discount,
+            },
+            "message": "Coupon report generated successfully",
+        }),
+    ))
+}
+
+fn redemption_query(
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+) -> Select<tenant::entities::coupon_redemptions::Entity> {
+    let mut stmt = tenant::entities::coupon_redemptions::Entity::find();
+
+    if let Some(from) = from {
+        stmt = stmt.filter(
+            tenant::entities::coupon_redemptions::Column::CreatedAt
+                .gte(from.and_hms_opt(0, 0, 0).unwrap_or_default()),
+        );
+    }
+
+    if let Some(to) = to {
+        stmt = stmt.filter(
+            tenant::entities::coupon_redemptions::Column::CreatedAt
+                .lt((to + chrono::Duration::days(1))
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap_or_default()),
+        );
+    }
+
+    stmt
+}
+
+async fn ensure_code_available(
+    tenant_db: &DatabaseConnection,
+    code: &str,
+    except: Option<Uuid>,
+) -> Result<(), ApiResponse> {
+    let mut stmt = tenant::entities::coupons::Entity::find()
+        .filter(tenant::entities::coupons::Column::Code.eq(code));
+
+    if let Some(pid) = except {
+        stmt = stmt.filter(tenant::entities::coupons::Column::Pid.ne(pid));
+    }
+
+    let existing = stmt.one(tenant_db).await.map_err(|err| {
+        log::error!("Failed to check coupon code {}: {}", code, err);
+        ApiResponse::new(500, json!({ "message": "Failed to save coupon" }))
+    })?;
+
+    if existing.is_some() {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "A coupon with this code already exists" }),
+        ));
+    }
+
+    Ok(())
+}
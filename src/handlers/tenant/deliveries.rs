@@ -0,0 +1,1435 @@
+use std::collections::HashMap;
+
+use actix_multipart::Multipart;
+use actix_web::{HttpRequest, web};
+use chrono::Utc;
+use futures::StreamExt;
+use rust_decimal::{Decimal, prelude::FromPrimitive};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        main::{self, entities::sea_orm_active_enums::FileVisibility},
+        tenant::{
+            self,
+            entities::sea_orm_active_enums::{DeliveryStatus, OnlineOrderStatus},
+            migrations::sea_orm::{
+                ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait,
+                PaginatorTrait, QueryFilter, QueryOrder, Set, TransactionTrait, TryIntoModel,
+            },
+        },
+    },
+    handlers::services::{
+        deliveries::{
+            MAX_OTP_ATTEMPTS, delivery_json, delivery_otp, find_delivery, find_order_delivery,
+            find_rider, find_zone, rider_json, zone_json,
+        },
+        files::authorized_file_url,
+        online_orders::{
+            PRESCRIPTION_CONTENT_TYPES, facility_name, find_order, notify_order_status, order_json,
+        },
+    },
+    utils::{
+        api_response::ApiResponse,
+        app_state::AppState,
+        constants::MAX_FILE_SIZE,
+        jwt::{get_logged_in_user_claims, get_tenant_db},
+        message_queue::MessageType,
+        multipart::{field_to_byte, field_to_string, upload_file},
+        validator_error::ValidationError,
+    },
+};
+
+const PROOF_URL_EXPIRY_SECS: u64 = 300;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ZoneData {
+    pub name: Option<String>,
+    pub min_distance_km: Option<Decimal>,
+    pub max_distance_km: Option<Decimal>,
+    pub fee: Option<Decimal>,
+    pub estimated_minutes: Option<i32>,
+    pub is_active: Option<bool>,
+}
+
+impl ZoneData {
+    pub fn validate(&self, is_create: bool) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        match self.name.as_deref().map(str::trim) {
+            None if is_create => {
+                errors.insert("name".to_string(), "Zone name is required".to_string());
+            }
+            Some("") => {
+                errors.insert("name".to_string(), "Zone name is required".to_string());
+            }
+            Some(name) if name.chars().count() > 100 => {
+                errors.insert(
+                    "name".to_string(),
+                    "Zone name must be at most 100 characters".to_string(),
+                );
+            }
+            _ => {}
+        }
+
+        for (field, distance) in [
+            ("min_distance_km", self.min_distance_km),
+            ("max_distance_km", self.max_distance_km),
+        ] {
+            match distance {
+                None if is_create => {
+                    errors.insert(field.to_string(), "Distance is required".to_string());
+                }
+                Some(distance) if distance < Decimal::ZERO || distance > Decimal::from(9999) => {
+                    errors.insert(
+                        field.to_string(),
+                        "Distance must be between 0 and 9999 km".to_string(),
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        if let (Some(min), Some(max)) = (self.min_distance_km, self.max_distance_km)
+            && max <= min
+        {
+            errors.insert(
+                "max_distance_km".to_string(),
+                "Maximum distance must be greater than the minimum".to_string(),
+            );
+        }
+
+        match self.fee {
+            None if is_create => {
+                errors.insert("fee".to_string(), "Fee is required".to_string());
+            }
+            Some(fee) if fee < Decimal::ZERO => {
+                errors.insert("fee".to_string(), "Fee cannot be negative".to_string());
+            }
+            _ => {}
+        }
+
+        match self.estimated_minutes {
+            None if is_create => {
+                errors.insert(
+                    "estimated_minutes".to_string(),
+                    "Estimated delivery time is required".to_string(),
+                );
+            }
+            Some(minutes) if !(1..=1440).contains(&minutes) => {
+                errors.insert(
+                    "estimated_minutes".to_string(),
+                    "Estimated delivery time must be between 1 and 1440 minutes".to_string(),
+                );
+            }
+            _ => {}
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+/// Delivery zones, nearest band first.
+pub async fn index_zones(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let zones = tenant::entities::delivery_zones::Entity::find()
+        .filter(tenant::entities::delivery_zones::Column::DeletedAt.is_null())
+        .order_by_asc(tenant::entities::delivery_zones::Column::MinDistanceKm)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch delivery zones: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch delivery zones" }))
+        })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "zones": zones.iter().map(zone_json).collect::<Vec<_>>(),
+            "message": "Delivery zones fetched successfully",
+        }),
+    ))
+}
+
+pub async fn create_zone(
+    app_state: web::Data<AppState>,
+    data: web::Json<ZoneData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate(true) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let min_distance_km = data.min_distance_km.unwrap_or_default();
+    let max_distance_km = data.max_distance_km.unwrap_or_default();
+    ensure_no_overlap(&tenant_db, min_distance_km, max_distance_km, None).await?;
+
+    let zone = tenant::entities::delivery_zones::ActiveModel {
+        name: Set(data.name.as_deref().unwrap_or_default().trim().to_string()),
+        min_distance_km: Set(min_distance_km),
+        max_distance_km: Set(max_distance_km),
+        fee: Set(data.fee.unwrap_or_default()),
+        estimated_minutes: Set(data.estimated_minutes.unwrap_or_default()),
+        is_active: Set(data.is_active.unwrap_or(true)),
+        ..Default::default()
+    }
+    .insert(&tenant_db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to create delivery zone: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create delivery zone" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "zone": zone_json(&zone),
+            "message": "Delivery zone created successfully",
+        }),
+    ))
+}
+
+pub async fn edit_zone(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<ZoneData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate(false) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let zone = find_zone(&tenant_db, path.into_inner()).await?;
+    let zone_pid = zone.pid;
+
+    let min_distance_km = data.min_distance_km.unwrap_or(zone.min_distance_km);
+    let max_distance_km = data.max_distance_km.unwrap_or(zone.max_distance_km);
+    if max_distance_km <= min_distance_km {
+        return Err(ApiResponse::new(
+            400,
+            json!(ValidationError {
+                errors: HashMap::from([(
+                    "max_distance_km".to_string(),
+                    "Maximum distance must be greater than the minimum".to_string(),
+                )]),
+            }),
+        ));
+    }
+    ensure_no_overlap(&tenant_db, min_distance_km, max_distance_km, Some(zone_pid)).await?;
+
+    let mut active_model: tenant::entities::delivery_zones::ActiveModel = zone.into();
+
+    if let Some(name) = &data.name {
+        active_model.name = Set(name.trim().to_string());
+    }
+    active_model.min_distance_km = Set(min_distance_km);
+    active_model.max_distance_km = Set(max_distance_km);
+    if let Some(fee) = data.fee {
+        active_model.fee = Set(fee);
+    }
+    if let Some(estimated_minutes) = data.estimated_minutes {
+        active_model.estimated_minutes = Set(estimated_minutes);
+    }
+    if let Some(is_active) = data.is_active {
+        active_model.is_active = Set(is_active);
+    }
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let zone = active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!("Failed to update delivery zone {}: {}", zone_pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to update delivery zone" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "zone": zone_json(&zone),
+            "message": "Delivery zone updated successfully",
+        }),
+    ))
+}
+
+pub async fn destroy_zone(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let zone = find_zone(&tenant_db, path.into_inner()).await?;
+
+    let zone_pid = zone.pid;
+    let mut active_model: tenant::entities::delivery_zones::ActiveModel = zone.into();
+    active_model.is_active = Set(false);
+    active_model.deleted_at = Set(Some(Utc::now().naive_utc()));
+    active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!("Failed to delete delivery zone {}: {}", zone_pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to delete delivery zone" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({ "message": "Delivery zone deleted successfully" }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct RiderData {
+    /// The staff account the rider signs in with.
+    pub sso_user_id: Option<Uuid>,
+    pub name: Option<String>,
+    pub phone: Option<String>,
+    pub vehicle: Option<String>,
+    pub is_active: Option<bool>,
+}
+
+impl RiderData {
+    pub fn validate(&self, is_create: bool) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if is_create && self.sso_user_id.is_none() {
+            errors.insert(
+                "sso_user_id".to_string(),
+                "Rider account is required".to_string(),
+            );
+        }
+
+        match self.name.as_deref().map(str::trim) {
+            None if is_create => {
+                errors.insert("name".to_string(), "Name is required".to_string());
+            }
+            Some("") => {
+                errors.insert("name".to_string(), "Name is required".to_string());
+            }
+            Some(name) if name.chars().count() > 150 => {
+                errors.insert(
+                    "name".to_string(),
+                    "Name must be at most 150 characters".to_string(),
+                );
+            }
+            _ => {}
+        }
+
+        match self.phone.as_deref().map(str::trim) {
+            None if is_create => {
+                errors.insert("phone".to_string(), "Phone is required".to_string());
+            }
+            Some(phone) if phone.is_empty() || phone.len() > 20 => {
+                errors.insert(
+                    "phone".to_string(),
+                    "Phone must be between 1 and 20 characters".to_string(),
+                );
+            }
+            _ => {}
+        }
+
+        if self
+            .vehicle
+            .as_ref()
+            .is_some_and(|v| v.chars().count() > 100)
+        {
+            errors.insert(
+                "vehicle".to_string(),
+                "Vehicle must be at most 100 characters".to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct RiderParams {
+    pub search: Option<String>,
+    pub include_inactive: Option<bool>,
+}
+
+pub async fn index_riders(
+    app_state: web::Data<AppState>,
+    query: web::Query<RiderParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let mut stmt = tenant::entities::delivery_riders::Entity::find()
+        .filter(tenant::entities::delivery_riders::Column::DeletedAt.is_null());
+
+    if !query.include_inactive.unwrap_or(false) {
+        stmt = stmt.filter(tenant::entities::delivery_riders::Column::IsActive.eq(true));
+    }
+
+    if let Some(term) = &query.search {
+        use tenant::migrations::{Expr, extension::postgres::PgExpr};
+
+        let like = format!("%{}%", term);
+        stmt = stmt.filter(
+            Condition::any()
+                .add(Expr::col(tenant::entities::delivery_riders::Column::Name).ilike(like.clone()))
+                .add(Expr::col(tenant::entities::delivery_riders::Column::Phone).ilike(like)),
+        );
+    }
+
+    let riders = stmt
+        .order_by_asc(tenant::entities::delivery_riders::Column::Name)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch riders: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch riders" }))
+        })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "riders": riders.iter().map(rider_json).collect::<Vec<_>>(),
+            "message": "Riders fetched successfully",
+        }),
+    ))
+}
+
+pub async fn create_rider(
+    app_state: web::Data<AppState>,
+    data: web::Json<RiderData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate(true) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let sso_user_id = data.sso_user_id.unwrap_or_default();
+
+    let existing = tenant::entities::delivery_riders::Entity::find()
+        .filter(tenant::entities::delivery_riders::Column::SsoUserId.eq(sso_user_id))
+        .one(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to check rider account {}: {}", sso_user_id, err);
+            ApiResponse::new(500, json!({ "message": "Failed to create rider" }))
+        })?;
+
+    if existing.is_some() {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "This account is already registered as a rider" }),
+        ));
+    }
+
+    let rider = tenant::entities::delivery_riders::ActiveModel {
+        sso_user_id: Set(sso_user_id),
+        name: Set(data.name.as_deref().unwrap_or_default().trim().to_string()),
+        phone: Set(data.phone.as_deref().unwrap_or_default().trim().to_string()),
+        vehicle: Set(data.vehicle.clone()),
+        is_active: Set(data.is_active.unwrap_or(true)),
+        ..Default::default()
+    }
+    .insert(&tenant_db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to create rider: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create rider" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "rider": rider_json(&rider),
+            "message": "Rider created successfully",
+        }),
+    ))
+}
+
+pub async fn edit_rider(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<RiderData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate(false) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let rider = find_rider(&tenant_db, path.into_inner()).await?;
+    let rider_pid = rider.pid;
+
+    let mut active_model: tenant::entities::delivery_riders::ActiveModel = rider.into();
+
+    if let Some(name) = &data.name {
+        active_model.name = Set(name.trim().to_string());
+    }
+    if let Some(phone) = &data.phone {
+        active_model.phone = Set(phone.trim().to_string());
+    }
+    if let Some(vehicle) = &data.vehicle {
+        active_model.vehicle = Set(Some(vehicle.clone()));
+    }
+    if let Some(is_active) = data.is_active {
+        active_model.is_active = Set(is_active);
+    }
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let rider = active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!("Failed to update rider {}: {}", rider_pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to update rider" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "rider": rider_json(&rider),
+            "message": "Rider updated successfully",
+        }),
+    ))
+}
+
+/// Riders still carrying an order cannot be removed.
+pub async fn destroy_rider(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let rider = find_rider(&tenant_db, path.into_inner()).await?;
+
+    let open_deliveries = tenant::entities::deliveries::Entity::find()
+        .filter(tenant::entities::deliveries::Column::RiderId.eq(rider.id))
+        .filter(
+            tenant::entities::deliveries::Column::Status
+                .is_in([DeliveryStatus::Assigned, DeliveryStatus::PickedUp]),
+        )
+        .count(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to count deliveries of rider {}: {}", rider.pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to delete rider" }))
+        })?;
+
+    if open_deliveries > 0 {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Rider has deliveries in progress" }),
+        ));
+    }
+
+    let rider_pid = rider.pid;
+    let mut active_model: tenant::entities::delivery_riders::ActiveModel = rider.into();
+    active_model.is_active = Set(false);
+    active_model.deleted_at = Set(Some(Utc::now().naive_utc()));
+    active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!("Failed to delete rider {}: {}", rider_pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to delete rider" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({ "message": "Rider deleted successfully" }),
+    ))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct DeliveryParams {
+    pub status: Option<DeliveryStatus>,
+    pub rider_pid: Option<Uuid>,
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+}
+
+pub async fn index(
+    app_state: web::Data<AppState>,
+    query: web::Query<DeliveryParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let mut stmt = tenant::entities::deliveries::Entity::find();
+
+    if let Some(status) = &query.status {
+        stmt = stmt.filter(tenant::entities::deliveries::Column::Status.eq(status.clone()));
+    }
+
+    if let Some(rider_pid) = query.rider_pid {
+        let rider = find_rider(&tenant_db, rider_pid).await?;
+        stmt = stmt.filter(tenant::entities::deliveries::Column::RiderId.eq(rider.id));
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let paginator = stmt
+        .find_also_related(tenant::entities::online_orders::Entity)
+        .order_by_desc(tenant::entities::deliveries::Column::CreatedAt)
+        .paginate(&tenant_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
+
+    let deliveries = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let riders = fetch_riders(
+        &tenant_db,
+        deliveries
+            .iter()
+            .filter_map(|(delivery, _)| delivery.rider_id)
+            .collect(),
+    )
+    .await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "deliveries": deliveries
+                .iter()
+                .map(|(delivery, order)| delivery_with_order_json(
+                    delivery,
+                    order.as_ref(),
+                    delivery.rider_id.and_then(|id| riders.get(&id)),
+                ))
+                .collect::<Vec<_>>(),
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "Deliveries fetched successfully",
+        }),
+    ))
+}
+
+/// A delivery with its order and a short-lived link to the proof photo.
+pub async fn show(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let delivery = find_delivery(&tenant_db, path.into_inner()).await?;
+    let order = find_order_by_id(&tenant_db, delivery.order_id).await?;
+    let riders = fetch_riders(&tenant_db, delivery.rider_id.into_iter().collect()).await?;
+
+    let proof_photo_url = match delivery.proof_photo_file_pid {
+        Some(file_pid) => Some(
+            authorized_file_url(&app_state, &req, file_pid, PROOF_URL_EXPIRY_SECS)
+                .await?
+                .0,
+        ),
+        None => None,
+    };
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "delivery": delivery_json(&delivery, delivery.rider_id.and_then(|id| riders.get(&id))),
+            "order": order_json(&order, Vec::new()),
+            "proof_photo_url": proof_photo_url,
+            "message": "Delivery fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct AssignData {
+    pub order_pid: Option<Uuid>,
+    pub rider_pid: Option<Uuid>,
+}
+
+/// Gives a verified or packed order to a rider. Deliveries that failed can
+/// be given to a rider again.
+pub async fn assign(
+    app_state: web::Data<AppState>,
+    data: web::Json<AssignData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let (Some(order_pid), Some(rider_pid)) = (data.order_pid, data.rider_pid) else {
+        let mut errors = HashMap::new();
+        if data.order_pid.is_none() {
+            errors.insert("order_pid".to_string(), "Order is required".to_string());
+        }
+        if data.rider_pid.is_none() {
+            errors.insert("rider_pid".to_string(), "Rider is required".to_string());
+        }
+        return Err(ApiResponse::new(400, json!(ValidationError { errors })));
+    };
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let order = find_order(&tenant_db, order_pid).await?;
+    let rider = find_rider(&tenant_db, rider_pid).await?;
+
+    if !rider.is_active {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Rider is not active" }),
+        ));
+    }
+
+    if !matches!(
+        order.status,
+        OnlineOrderStatus::Verified | OnlineOrderStatus::Packed
+    ) {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Only verified or packed orders can be assigned" }),
+        ));
+    }
+
+    let delivery = match find_order_delivery(&tenant_db, order.id).await? {
+        Some(delivery) => {
+            if !matches!(
+                delivery.status,
+                DeliveryStatus::Pending | DeliveryStatus::Assigned | DeliveryStatus::Failed
+            ) {
+                return Err(ApiResponse::new(
+                    409,
+                    json!({ "message": "Delivery is already on its way" }),
+                ));
+            }
+            delivery.into()
+        }
+        // Orders placed before deliveries were tracked.
+        None => tenant::entities::deliveries::ActiveModel {
+            order_id: Set(order.id),
+            status: Set(DeliveryStatus::Pending),
+            fee: Set(order.delivery_fee),
+            ..Default::default()
+        },
+    };
+
+    let mut active_model: tenant::entities::deliveries::ActiveModel = delivery;
+    active_model.rider_id = Set(Some(rider.id));
+    active_model.status = Set(DeliveryStatus::Assigned);
+    active_model.assigned_at = Set(Some(Utc::now().naive_utc()));
+    active_model.assigned_by = Set(Some(claims.sub));
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let delivery = active_model
+        .save(&tenant_db)
+        .await
+        .and_then(|delivery| delivery.try_into_model())
+        .map_err(|err| {
+            log::error!("Failed to assign order {}: {}", order.pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to assign rider" }))
+        })?;
+
+    if let Err(err) = app_state
+        .message_queue
+        .send_message(MessageType::SMS {
+            phone_number: rider.phone.clone(),
+            message: format!(
+                "New delivery: order {} to {}. Contact {}.",
+                order.order_number, order.delivery_address, order.contact_phone
+            ),
+        })
+        .await
+    {
+        log::error!("Failed to notify rider {}: {}", rider.pid, err);
+    }
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "delivery": delivery_json(&delivery, Some(&rider)),
+            "message": "Rider assigned successfully",
+        }),
+    ))
+}
+
+/// The logged-in rider's open deliveries, oldest first.
+pub async fn my_deliveries(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let rider = find_logged_in_rider(&req, &tenant_db).await?;
+
+    let deliveries = tenant::entities::deliveries::Entity::find()
+        .find_also_related(tenant::entities::online_orders::Entity)
+        .filter(tenant::entities::deliveries::Column::RiderId.eq(rider.id))
+        .filter(
+            tenant::entities::deliveries::Column::Status
+                .is_in([DeliveryStatus::Assigned, DeliveryStatus::PickedUp]),
+        )
+        .order_by_asc(tenant::entities::deliveries::Column::AssignedAt)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch deliveries of rider {}: {}", rider.pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch deliveries" }))
+        })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "deliveries": deliveries
+                .iter()
+                .map(|(delivery, order)| delivery_with_order_json(
+                    delivery,
+                    order.as_ref(),
+                    Some(&rider),
+                ))
+                .collect::<Vec<_>>(),
+            "message": "Deliveries fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct LocationData {
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+}
+
+/// The rider's current position, sent periodically by the rider app while
+/// on the road.
+pub async fn update_location(
+    app_state: web::Data<AppState>,
+    data: web::Json<LocationData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let mut errors = HashMap::new();
+    if !data
+        .latitude
+        .is_some_and(|lat| (-90.0..=90.0).contains(&lat))
+    {
+        errors.insert(
+            "latitude".to_string(),
+            "Latitude must be between -90 and 90".to_string(),
+        );
+    }
+    if !data
+        .longitude
+        .is_some_and(|lng| (-180.0..=180.0).contains(&lng))
+    {
+        errors.insert(
+            "longitude".to_string(),
+            "Longitude must be between -180 and 180".to_string(),
+        );
+    }
+    if !errors.is_empty() {
+        return Err(ApiResponse::new(400, json!(ValidationError { errors })));
+    }
+
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let rider = find_logged_in_rider(&req, &tenant_db).await?;
+
+    let rider_pid = rider.pid;
+    let mut active_model: tenant::entities::delivery_riders::ActiveModel = rider.into();
+    active_model.last_latitude = Set(data.latitude.and_then(Decimal::from_f64));
+    active_model.last_longitude = Set(data.longitude.and_then(Decimal::from_f64));
+    active_model.last_location_at = Set(Some(Utc::now().naive_utc()));
+    active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!("Failed to update location of rider {}: {}", rider_pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to update location" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({ "message": "Location updated successfully" }),
+    ))
+}
+
+/// The rider collects a packed order. The order goes out for delivery and
+/// the patient is texted the code to give the rider on arrival.
+pub async fn pickup(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let rider = find_logged_in_rider(&req, &tenant_db).await?;
+    let delivery = find_rider_delivery(&tenant_db, path.into_inner(), &rider).await?;
+    let order = find_order_by_id(&tenant_db, delivery.order_id).await?;
+
+    if delivery.status != DeliveryStatus::Assigned {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Delivery is not waiting for pickup" }),
+        ));
+    }
+
+    if order.status != OnlineOrderStatus::Packed {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Order has not been packed yet" }),
+        ));
+    }
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start pickup transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to record pickup" }))
+    })?;
+
+    let otp = delivery_otp();
+    let mut active_model: tenant::entities::deliveries::ActiveModel = delivery.into();
+    active_model.status = Set(DeliveryStatus::PickedUp);
+    active_model.picked_up_at = Set(Some(Utc::now().naive_utc()));
+    active_model.otp_code = Set(Some(otp.clone()));
+    active_model.otp_attempts = Set(0);
+    active_model.updated_at = Set(Utc::now().naive_utc());
+    let delivery = active_model.update(&txn).await.map_err(|err| {
+        log::error!("Failed to record pickup: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to record pickup" }))
+    })?;
+
+    let mut active_model: tenant::entities::online_orders::ActiveModel = order.into();
+    active_model.status = Set(OnlineOrderStatus::OutForDelivery);
+    active_model.dispatched_at = Set(Some(Utc::now().naive_utc()));
+    active_model.dispatched_by = Set(Some(claims.sub));
+    active_model.updated_at = Set(Utc::now().naive_utc());
+    let order = active_model.update(&txn).await.map_err(|err| {
+        log::error!("Failed to mark order as out for delivery: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to record pickup" }))
+    })?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit pickup of {}: {}", order.pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to record pickup" }))
+    })?;
+
+    if let Err(err) = app_state
+        .message_queue
+        .send_message(MessageType::SMS {
+            phone_number: order.contact_phone.clone(),
+            message: format!(
+                "{}: Order {} is out for delivery with {} ({}). Give the rider code {} when you receive it.",
+                facility_name(&req, &app_state).await?,
+                order.order_number,
+                rider.name,
+                rider.phone,
+                otp
+            ),
+        })
+        .await
+    {
+        log::error!(
+            "Failed to send delivery code for order {}: {}",
+            order.order_number,
+            err
+        );
+    }
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "delivery": delivery_json(&delivery, Some(&rider)),
+            "message": "Pickup recorded successfully",
+        }),
+    ))
+}
+
+#[derive(Debug, Default)]
+struct ProofForm {
+    otp: Option<String>,
+    recipient_name: Option<String>,
+    photo: Option<(String, String, Vec<u8>)>,
+}
+
+impl ProofForm {
+    async fn from_multipart(mut payload: Multipart) -> Result<Self, ApiResponse> {
+        let mut form = ProofForm::default();
+
+        while let Some(Ok(mut field)) = payload.next().await {
+            let content_disposition = field.content_disposition().cloned();
+            let name = content_disposition
+                .as_ref()
+                .and_then(|cd| cd.get_name())
+                .unwrap_or("")
+                .to_string();
+
+            match name.as_str() {
+                "otp" => form.otp = Some(field_to_string(&mut field).await?),
+                "recipient_name" => form.recipient_name = Some(field_to_string(&mut field).await?),
+                "photo" => {
+                    let file_name = content_disposition
+                        .as_ref()
+                        .and_then(|cd| cd.get_filename())
+                        .and_then(|name| name.rsplit(['/', '\\']).next())
+                        .filter(|name| !name.is_empty())
+                        .map(|name| name.to_string())
+                        .unwrap_or_else(|| format!("{}.bin", Uuid::new_v4()));
+                    let content_type = field
+                        .content_type()
+                        .map(|ct| ct.to_string())
+                        .unwrap_or_else(|| "application/octet-stream".to_string());
+                    let content = field_to_byte(&mut field).await?;
+
+                    if !content.is_empty() {
+                        form.photo = Some((file_name, content_type, content));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(form)
+    }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        let otp = self.otp.as_deref().map(str::trim).unwrap_or_default();
+        if otp.is_empty() && self.photo.is_none() {
+            errors.insert(
+                "otp".to_string(),
+                "Enter the patient's code or upload a photo of the handover".to_string(),
+            );
+        }
+
+        if self
+            .recipient_name
+            .as_ref()
+            .is_some_and(|n| n.chars().count() > 150)
+        {
+            errors.insert(
+                "recipient_name".to_string(),
+                "Recipient name must be at most 150 characters".to_string(),
+            );
+        }
+
+        if let Some((file_name, content_type, content)) = &self.photo {
+            if !content_type.starts_with("image/")
+                || !PRESCRIPTION_CONTENT_TYPES.contains(&content_type.as_str())
+            {
+                errors.insert(
+                    "photo".to_string(),
+                    format!("{} is not a supported image type", file_name),
+                );
+            } else if content.len() as u64 > *MAX_FILE_SIZE {
+                errors.insert("photo".to_string(), format!("{} is too large", file_name));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+/// The rider hands the order over, proven by the patient's code or, when
+/// the patient cannot give it, a photo of the handover.
+pub async fn complete(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    payload: Multipart,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let form = ProofForm::from_multipart(payload).await?;
+    if let Err(err) = form.validate() {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let rider = find_logged_in_rider(&req, &tenant_db).await?;
+    let delivery = find_rider_delivery(&tenant_db, path.into_inner(), &rider).await?;
+    let order = find_order_by_id(&tenant_db, delivery.order_id).await?;
+
+    if delivery.status != DeliveryStatus::PickedUp {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Delivery is not on its way" }),
+        ));
+    }
+
+    let otp = form.otp.as_deref().map(str::trim).unwrap_or_default();
+    let (proof_method, proof_photo_file_pid) = if !otp.is_empty() {
+        claim_otp_attempt(&tenant_db, delivery.id).await?;
+        if delivery.otp_code.as_deref() != Some(otp) {
+            return Err(ApiResponse::new(
+                400,
+                json!(ValidationError {
+                    errors: HashMap::from([(
+                        "otp".to_string(),
+                        "The code does not match".to_string()
+                    )]),
+                }),
+            ));
+        }
+        ("otp", None)
+    } else {
+        let Some((file_name, content_type, content)) = form.photo else {
+            return Err(ApiResponse::new(
+                400,
+                json!({ "message": "Proof of delivery is required" }),
+            ));
+        };
+
+        let patient_id = main::entities::patients::Entity::find_by_pid(order.patient_pid)
+            .one(&app_state.main_db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to fetch patient {}: {}", order.patient_pid, err);
+                ApiResponse::new(500, json!({ "message": "Failed to record delivery" }))
+            })?
+            .map(|patient| patient.id);
+
+        let file_pid = upload_file(
+            &req,
+            &app_state,
+            &format!("deliveries/{}/{}", order.order_number, file_name),
+            content,
+            &content_type,
+            patient_id,
+            FileVisibility::Tenant,
+        )
+        .await?;
+        ("photo", Some(file_pid))
+    };
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start delivery transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to record delivery" }))
+    })?;
+
+    let mut active_model: tenant::entities::deliveries::ActiveModel = delivery.into();
+    active_model.status = Set(DeliveryStatus::Delivered);
+    active_model.delivered_at = Set(Some(Utc::now().naive_utc()));
+    active_model.proof_method = Set(Some(proof_method.to_string()));
+    active_model.proof_photo_file_pid = Set(proof_photo_file_pid);
+    active_model.recipient_name = Set(form
+        .recipient_name
+        .as_deref()
+        .map(str::trim)
+        .filter(|n| !n.is_empty())
+        .map(str::to_string));
+    active_model.updated_at = Set(Utc::now().naive_utc());
+    let delivery = active_model.update(&txn).await.map_err(|err| {
+        log::error!("Failed to record delivery: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to record delivery" }))
+    })?;
+
+    let mut active_model: tenant::entities::online_orders::ActiveModel = order.into();
+    active_model.status = Set(OnlineOrderStatus::Delivered);
+    active_model.delivered_at = Set(Some(Utc::now().naive_utc()));
+    active_model.updated_at = Set(Utc::now().naive_utc());
+    let order = active_model.update(&txn).await.map_err(|err| {
+        log::error!("Failed to mark order as delivered: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to record delivery" }))
+    })?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit delivery of {}: {}", order.pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to record delivery" }))
+    })?;
+
+    notify_order_status(&app_state, &facility_name(&req, &app_state).await?, &order).await;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "delivery": delivery_json(&delivery, Some(&rider)),
+            "message": "Delivery recorded successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct FailData {
+    pub reason: Option<String>,
+}
+
+/// The rider could not hand the order over. The order goes back to packed
+/// so it can be given to a rider again.
+pub async fn fail(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<FailData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let reason = data
+        .reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|r| !r.is_empty())
+        .map(str::to_string);
+    let Some(reason) = reason.filter(|r| r.chars().count() <= 500) else {
+        return Err(ApiResponse::new(
+            400,
+            json!(ValidationError {
+                errors: HashMap::from([(
+                    "reason".to_string(),
+                    "A reason of at most 500 characters is required".to_string()
+                )]),
+            }),
+        ));
+    };
+
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let rider = find_logged_in_rider(&req, &tenant_db).await?;
+    let delivery = find_rider_delivery(&tenant_db, path.into_inner(), &rider).await?;
+    let order = find_order_by_id(&tenant_db, delivery.order_id).await?;
+
+    if !matches!(
+        delivery.status,
+        DeliveryStatus::Assigned | DeliveryStatus::PickedUp
+    ) {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Delivery is not in progress" }),
+        ));
+    }
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start delivery transaction: {}", err);
+        ApiResponse::new(
+            500,
+            json!({ "message": "Failed to record failed delivery" }),
+        )
+    })?;
+
+    let mut active_model: tenant::entities::deliveries::ActiveModel = delivery.into();
+    active_model.status = Set(DeliveryStatus::Failed);
+    active_model.failed_at = Set(Some(Utc::now().naive_utc()));
+    active_model.failure_reason = Set(Some(reason.clone()));
+    active_model.otp_code = Set(None);
+    active_model.updated_at = Set(Utc::now().naive_utc());
+    let delivery = active_model.update(&txn).await.map_err(|err| {
+        log::error!("Failed to record failed delivery: {}", err);
+        ApiResponse::new(
+            500,
+            json!({ "message": "Failed to record failed delivery" }),
+        )
+    })?;
+
+    let order = if order.status == OnlineOrderStatus::OutForDelivery {
+        let mut active_model: tenant::entities::online_orders::ActiveModel = order.into();
+        active_model.status = Set(OnlineOrderStatus::Packed);
+        active_model.updated_at = Set(Utc::now().naive_utc());
+        active_model.update(&txn).await.map_err(|err| {
+            log::error!("Failed to return order to packed: {}", err);
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to record failed delivery" }),
+            )
+        })?
+    } else {
+        order
+    };
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit failed delivery of {}: {}", order.pid, err);
+        ApiResponse::new(
+            500,
+            json!({ "message": "Failed to record failed delivery" }),
+        )
+    })?;
+
+    if let Err(err) = app_state
+        .message_queue
+        .send_message(MessageType::SMS {
+            phone_number: order.contact_phone.clone(),
+            message: format!(
+                "{}: We could not deliver order {} ({}). We will contact you to arrange another delivery.",
+                facility_name(&req, &app_state).await?,
+                order.order_number,
+                reason
+            ),
+        })
+        .await
+    {
+        log::error!(
+            "Failed to notify patient of failed delivery {}: {}",
+            order.order_number,
+            err
+        );
+    }
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "delivery": delivery_json(&delivery, Some(&rider)),
+            "message": "Failed delivery recorded",
+        }),
+    ))
+}
+
+fn delivery_with_order_json(
+    delivery: &tenant::entities::deliveries::Model,
+    order: Option<&tenant::entities::online_orders::Model>,
+    rider: Option<&tenant::entities::delivery_riders::Model>,
+) -> Value {
+    let mut value = delivery_json(delivery, rider);
+    value["order"] = json!(order.map(|order| json!({
+        "pid": order.pid,
+        "order_number": order.order_number,
+        "status": order.status,
+        "delivery_address": order.delivery_address,
+        "contact_phone": order.contact_phone,
+        "notes": order.notes,
+    })));
+    value
+}
+
+async fn fetch_riders(
+    tenant_db: &DatabaseConnection,
+    ids: Vec<i32>,
+) -> Result<HashMap<i32, tenant::entities::delivery_riders::Model>, ApiResponse> {
+    if ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    Ok(tenant::entities::delivery_riders::Entity::find()
+        .filter(tenant::entities::delivery_riders::Column::Id.is_in(ids))
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch riders: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch deliveries" }))
+        })?
+        .into_iter()
+        .map(|rider| (rider.id, rider))
+        .collect())
+}
+
+async fn find_order_by_id(
+    tenant_db: &DatabaseConnection,
+    id: i32,
+) -> Result<tenant::entities::online_orders::Model, ApiResponse> {
+    tenant::entities::online_orders::Entity::find_by_id(id)
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch order {}: {}", id, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch order" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Order not found" })))
+}
+
+/// The rider profile of the logged-in user.
+async fn find_logged_in_rider(
+    req: &HttpRequest,
+    tenant_db: &DatabaseConnection,
+) -> Result<tenant::entities::delivery_riders::Model, ApiResponse> {
+    let claims = get_logged_in_user_claims(req)?;
+
+    tenant::entities::delivery_riders::Entity::find()
+        .filter(tenant::entities::delivery_riders::Column::SsoUserId.eq(claims.sub))
+        .filter(tenant::entities::delivery_riders::Column::DeletedAt.is_null())
+        .filter(tenant::entities::delivery_riders::Column::IsActive.eq(true))
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch rider for {}: {}", claims.sub, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch rider" }))
+        })?
+        .ok_or_else(|| {
+            ApiResponse::new(
+                403,
+                json!({ "message": "You are not registered as a rider" }),
+            )
+        })
+}
+
+async fn find_rider_delivery(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+    rider: &tenant::entities::delivery_riders::Model,
+) -> Result<tenant::entities::deliveries::Model, ApiResponse> {
+    let delivery = find_delivery(tenant_db, pid).await?;
+    if delivery.rider_id != Some(rider.id) {
+        return Err(ApiResponse::new(
+            404,
+            json!({ "message": "Delivery not found" }),
+        ));
+    }
+
+    Ok(delivery)
+}
+
+/// Zones are distance bands; a location must fall in exactly one.
+async fn ensure_no_overlap(
+    tenant_db: &DatabaseConnection,
+    min_distance_km: Decimal,
+    max_distance_km: Decimal,
+    except: Option<Uuid>,
+) -> Result<(), ApiResponse> {
+    let mut stmt = tenant::entities::delivery_zones::Entity::find()
+        .filter(tenant::entities::delivery_zones::Column::DeletedAt.is_null())
+        .filter(tenant::entities::delivery_zones::Column::MinDistanceKm.lte(max_distance_km))
+        .filter(tenant::entities::delivery_zones::Column::MaxDistanceKm.gte(min_distance_km));
+
+    if let Some(pid) = except {
+        stmt = stmt.filter(tenant::entities::delivery_zones::Column::Pid.ne(pid));
+    }
+
+    let overlapping = stmt.one(tenant_db).await.map_err(|err| {
+        log::error!("Failed to check delivery zones: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to save delivery zone" }))
+    })?;
+
+    if let Some(zone) = overlapping {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": format!("Distances overlap the {} zone", zone.name) }),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Uses up one of the rider's tries at the delivery code before it is
+/// checked. The count is raised in the database, so parallel guesses
+/// cannot get past the limit.
+async fn claim_otp_attempt(
+    tenant_db: &DatabaseConnection,
+    delivery_id: i32,
+) -> Result<(), ApiResponse> {
+    use tenant::migrations::{Expr, ExprTrait};
+
+    let result = tenant::entities::deliveries::Entity::update_many()
+        .col_expr(
+            tenant::entities::deliveries::Column::OtpAttempts,
+            Expr::col(tenant::entities::deliveries::Column::OtpAttempts).add(1),
+        )
+        .filter(tenant::entities::deliveries::Column::Id.eq(delivery_id))
+        .filter(tenant::entities::deliveries::Column::OtpAttempts.lt(MAX_OTP_ATTEMPTS))
+        .exec(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to count code attempt for delivery {}: {}",
+                delivery_id,
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to record delivery" }))
+        })?;
+
+    if result.rows_affected == 0 {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Too many wrong codes. Take a photo of the handover instead" }),
+        ));
+    }
+
+    Ok(())
+}
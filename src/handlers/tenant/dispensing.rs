@@ -0,0 +1,853 @@
+use std::collections::HashMap;
+
+use actix_web::{HttpRequest, web};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        main::{self, entities::sea_orm_active_enums::FileVisibility},
+        tenant::{
+            self,
+            entities::sea_orm_active_enums::{PrescriptionStatus, StockMovementType},
+            migrations::sea_orm::{
+                ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait,
+                QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait,
+            },
+        },
+    },
+    handlers::services::{
+        dispensing::{
+            DispenseLabel, dispense_item_json, dispense_json, dispense_label_json,
+            dispense_labels_html,
+        },
+        files::authorized_file_url,
+        patient_charges::{
+            CHARGE_SOURCE_DISPENSE, NewCharge, add_patient_charge, patient_charge_json,
+        },
+        patients::find_patient,
+        pharmacy::{
+            NewMovement, batch_on_hand, find_batch, find_product, find_store, lock_product_batches,
+            pick_fefo, product_json, product_label, record_movement, store_json, usable_stock,
+        },
+        prescriptions::{
+            dispensing_status, fetch_prescription_items, find_prescription, is_dispensable,
+            prescription_json,
+        },
+        pricing::{facility_currency, payer_price_list, product_price},
+    },
+    utils::{
+        api_response::ApiResponse,
+        app_state::AppState,
+        html_to_image::generate_png,
+        jwt::{get_logged_in_user_claims, get_tenant_db, get_tenant_id},
+        validator_error::ValidationError,
+    },
+};
+
+const LABEL_URL_EXPIRY_SECS: u64 = 300;
+
+#[derive(Deserialize, Debug)]
+pub struct PendingParams {
+    pub patient_pid: Uuid,
+    pub store_pid: Option<Uuid>,
+}
+
+/// A patient's open prescriptions with what is still owed on each item and,
+/// when a store is given, how much usable stock it holds.
+pub async fn pending(
+    app_state: web::Data<AppState>,
+    query: web::Query<PendingParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let store = match query.store_pid {
+        Some(store_pid) => Some(find_store(&tenant_db, store_pid).await?),
+        None => None,
+    };
+
+    let prescriptions = tenant::entities::prescriptions::Entity::find()
+        .filter(tenant::entities::prescriptions::Column::PatientPid.eq(query.patient_pid))
+        .filter(tenant::entities::prescriptions::Column::Status.is_in([
+            PrescriptionStatus::Pending,
+            PrescriptionStatus::PartiallyDispensed,
+        ]))
+        .order_by_asc(tenant::entities::prescriptions::Column::PrescribedAt)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to fetch pending prescriptions for {}: {}",
+                query.patient_pid,
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to fetch prescriptions" }))
+        })?;
+
+    let items =
+        fetch_prescription_items(&tenant_db, prescriptions.iter().map(|p| p.id).collect()).await?;
+
+    let product_ids: Vec<i32> = items.iter().filter_map(|item| item.product_id).collect();
+    let products: HashMap<i32, tenant::entities::pharmacy_products::Model> =
+        tenant::entities::pharmacy_products::Entity::find()
+            .filter(tenant::entities::pharmacy_products::Column::Id.is_in(product_ids.clone()))
+            .all(&tenant_db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to fetch products: {}", err);
+                ApiResponse::new(500, json!({ "message": "Failed to fetch prescriptions" }))
+            })?
+            .into_iter()
+            .map(|product| (product.id, product))
+            .collect();
+
+    let available = match &store {
+        Some(store) => Some(usable_stock(&tenant_db, Some(store.id), product_ids).await?),
+        None => None,
+    };
+
+    let prescriptions = prescriptions
+        .iter()
+        .map(|prescription| {
+            let prescription_items: Vec<_> = items
+                .iter()
+                .filter(|item| item.prescription_id == prescription.id)
+                .cloned()
+                .collect();
+
+            let mut value = prescription_json(prescription, &prescription_items);
+            if let Some(lines) = value["items"].as_array_mut() {
+                for (line, item) in lines.iter_mut().zip(&prescription_items) {
+                    let product = item.product_id.and_then(|id| products.get(&id));
+                    line["product"] = product.map(product_json).unwrap_or(Value::Null);
+                    line["available"] = match (&available, product) {
+                        (Some(available), Some(product)) => {
+                            json!(available.get(&product.id).copied().unwrap_or(0))
+                        }
+                        _ => Value::Null,
+                    };
+                }
+            }
+            value
+        })
+        .collect::<Vec<_>>();
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "store": store.as_ref().map(store_json),
+            "prescriptions": prescriptions,
+            "message": "Pending prescriptions fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct DispenseLineData {
+    pub prescription_item_pid: Option<Uuid>,
+    /// Defaults to the prescribed product. A different product is a
+    /// substitution and needs a reason.
+    pub product_pid: Option<Uuid>,
+    /// Dispenses from this batch only; otherwise batches are picked FEFO.
+    pub batch_pid: Option<Uuid>,
+    pub quantity: Option<i32>,
+    pub substitution_reason: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct DispenseData {
+    pub prescription_pid: Option<Uuid>,
+    pub store_pid: Option<Uuid>,
+    /// Bills on this price list instead of the patient's own.
+    pub price_list_pid: Option<Uuid>,
+    pub notes: Option<String>,
+    pub items: Vec<DispenseLineData>,
+}
+
+impl DispenseData {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if self.prescription_pid.is_none() {
+            errors.insert(
+                "prescription_pid".to_string(),
+                "Prescription is required".to_string(),
+            );
+        }
+
+        if self.store_pid.is_none() {
+            errors.insert("store_pid".to_string(), "Store is required".to_string());
+        }
+
+        if self.items.is_empty() {
+            errors.insert(
+                "items".to_string(),
+                "At least one item is required".to_string(),
+            );
+        }
+
+        for (index, item) in self.items.iter().enumerate() {
+            if item.prescription_item_pid.is_none() {
+                errors.insert(
+                    format!("items.{}.prescription_item_pid", index),
+                    "Prescription item is required".to_string(),
+                );
+            }
+
+            if item.quantity.is_none_or(|q| q <= 0) {
+                errors.insert(
+                    format!("items.{}.quantity", index),
+                    "Quantity must be at least 1".to_string(),
+                );
+            }
+
+            if item
+                .substitution_reason
+                .as_ref()
+                .is_some_and(|r| r.len() > 500)
+            {
+                errors.insert(
+                    format!("items.{}.substitution_reason", index),
+                    "Substitution reason must be at most 500 characters".to_string(),
+                );
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+/// Hands out medicine against a prescription. Each line draws stock from the
+/// store, is charged to the patient at the payer's price and counts towards
+/// the item; the prescription becomes dispensed once every item is covered.
+pub async fn create(
+    app_state: web::Data<AppState>,
+    data: web::Json<DispenseData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate() {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let prescription =
+        find_prescription(&tenant_db, data.prescription_pid.unwrap_or_default()).await?;
+    let store = find_store(&tenant_db, data.store_pid.unwrap_or_default()).await?;
+
+    if !is_dispensable(&prescription.status) {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Prescription is not open for dispensing" }),
+        ));
+    }
+
+    let currency = facility_currency(&req, &app_state).await?;
+    let price_list = payer_price_list(
+        &tenant_db,
+        &app_state.main_db,
+        prescription.patient_pid,
+        data.price_list_pid,
+    )
+    .await?;
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start dispense transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to dispense" }))
+    })?;
+
+    // Locked so two pharmacists cannot dispense the same item twice.
+    let mut items: Vec<tenant::entities::prescription_items::Model> =
+        tenant::entities::prescription_items::Entity::find()
+            .filter(
+                tenant::entities::prescription_items::Column::PrescriptionId.eq(prescription.id),
+            )
+            .order_by_asc(tenant::entities::prescription_items::Column::Id)
+            .lock_exclusive()
+            .all(&txn)
+            .await
+            .map_err(|err| {
+                log::error!(
+                    "Failed to lock items of prescription {}: {}",
+                    prescription.pid,
+                    err
+                );
+                ApiResponse::new(500, json!({ "message": "Failed to dispense" }))
+            })?;
+
+    let mut errors = HashMap::new();
+    let mut lines = Vec::with_capacity(data.items.len());
+    let mut requested: HashMap<i32, i32> = HashMap::new();
+    for (index, line) in data.items.iter().enumerate() {
+        let Some(item) = items
+            .iter()
+            .find(|item| Some(item.pid) == line.prescription_item_pid)
+        else {
+            errors.insert(
+                format!("items.{}.prescription_item_pid", index),
+                "Item is not on this prescription".to_string(),
+            );
+            continue;
+        };
+
+        let product = match line.product_pid {
+            Some(product_pid) => Some(find_product(&tenant_db, product_pid).await?),
+            None => match item.product_id {
+                Some(product_id) => {
+                    tenant::entities::pharmacy_products::Entity::find_by_id(product_id)
+                        .one(&txn)
+                        .await
+                        .map_err(|err| {
+                            log::error!("Failed to fetch product {}: {}", product_id, err);
+                            ApiResponse::new(500, json!({ "message": "Failed to dispense" }))
+                        })?
+                }
+                None => None,
+            },
+        };
+        let Some(product) = product else {
+            errors.insert(
+                format!("items.{}.product_pid", index),
+                "Product is required for items not linked to the catalogue".to_string(),
+            );
+            continue;
+        };
+
+        let is_substitution = item.product_id.is_some_and(|id| id != product.id);
+        let substitution_reason = line
+            .substitution_reason
+            .as_deref()
+            .map(str::trim)
+            .filter(|r| !r.is_empty())
+            .map(str::to_string);
+        if is_substitution && substitution_reason.is_none() {
+            errors.insert(
+                format!("items.{}.substitution_reason", index),
+                "A reason is required when substituting the prescribed product".to_string(),
+            );
+        }
+
+        let quantity = line.quantity.unwrap_or_default();
+        let total = requested.entry(item.id).or_default();
+        *total += quantity;
+        if *total > item.quantity - item.quantity_dispensed {
+            errors.insert(
+                format!("items.{}.quantity", index),
+                format!(
+                    "Only {} remaining on this item",
+                    (item.quantity - item.quantity_dispensed).max(0)
+                ),
+            );
+        }
+
+        let batch = match line.batch_pid {
+            Some(batch_pid) => {
+                let batch = find_batch(&tenant_db, batch_pid).await?;
+                if batch.product_id != product.id {
+                    errors.insert(
+                        format!("items.{}.batch_pid", index),
+                        "Batch does not belong to this product".to_string(),
+                    );
+                } else if batch.expiry_date < Utc::now().date_naive() {
+                    errors.insert(
+                        format!("items.{}.batch_pid", index),
+                        "Expired stock cannot be dispensed".to_string(),
+                    );
+                }
+                Some(batch)
+            }
+            None => None,
+        };
+
+        lines.push((
+            item.id,
+            product,
+            batch,
+            quantity,
+            is_substitution,
+            substitution_reason,
+        ));
+    }
+
+    if !errors.is_empty() {
+        return Err(ApiResponse::new(400, json!(ValidationError { errors })));
+    }
+
+    let dispensed_at = Utc::now().naive_utc();
+    let dispense = tenant::entities::dispenses::ActiveModel {
+        prescription_id: Set(prescription.id),
+        store_id: Set(store.id),
+        patient_pid: Set(prescription.patient_pid),
+        dispensed_by: Set(claims.sub),
+        notes: Set(data.notes.clone()),
+        dispensed_at: Set(dispensed_at),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to create dispense: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to dispense" }))
+    })?;
+
+    let reference = format!("Dispense {}", dispense.pid);
+    let mut dispense_items = Vec::new();
+    let mut charges = Vec::with_capacity(lines.len());
+    for (item_id, product, batch, quantity, is_substitution, substitution_reason) in lines {
+        lock_product_batches(&txn, product.id).await?;
+
+        let price = product_price(
+            &txn,
+            &product,
+            price_list.as_ref(),
+            &currency,
+            dispensed_at.date(),
+        )
+        .await?;
+        let unit_price = price
+            .as_ref()
+            .map_or(product.unit_price, |price| price.amount);
+
+        let picks = match batch {
+            Some(batch) => {
+                let on_hand = batch_on_hand(&txn, store.id, &batch).await?;
+                if on_hand < quantity as i64 {
+                    return Err(ApiResponse::new(
+                        409,
+                        json!({
+                            "message": "Insufficient stock",
+                            "product": product_label(&product),
+                            "requested": quantity,
+                            "available": on_hand,
+                        }),
+                    ));
+                }
+                vec![(batch, quantity)]
+            }
+            None => pick_fefo(&txn, store.id, product.id, quantity as i64)
+                .await?
+                .into_iter()
+                .map(|pick| (pick.batch, pick.quantity as i32))
+                .collect(),
+        };
+
+        for (batch, picked) in &picks {
+            let movement = record_movement(
+                &txn,
+                NewMovement {
+                    store_id: store.id,
+                    batch,
+                    movement_type: StockMovementType::Dispense,
+                    quantity: -picked,
+                    transfer_pid: None,
+                    reference: Some(reference.clone()),
+                    reason: None,
+                    performed_by: claims.sub,
+                },
+            )
+            .await?;
+
+            let dispense_item = tenant::entities::dispense_items::ActiveModel {
+                dispense_id: Set(dispense.id),
+                prescription_item_id: Set(item_id),
+                product_id: Set(product.id),
+                batch_id: Set(batch.id),
+                stock_movement_id: Set(movement.id),
+                quantity: Set(*picked),
+                unit_price: Set(unit_price),
+                is_substitution: Set(is_substitution),
+                substitution_reason: Set(substitution_reason.clone()),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to record dispensed item: {}", err);
+                ApiResponse::new(500, json!({ "message": "Failed to dispense" }))
+            })?;
+            dispense_items.push(dispense_item_json(
+                &dispense_item,
+                Some(&product),
+                Some(batch),
+            ));
+        }
+
+        let charge = add_patient_charge(
+            &txn,
+            NewCharge {
+                patient_pid: prescription.patient_pid,
+                source_type: CHARGE_SOURCE_DISPENSE,
+                source_pid: dispense.pid,
+                description: product_label(&product),
+                quantity,
+                unit_price,
+                service_code: price.as_ref().map(|price| price.service.code.clone()),
+                price_list_id: price.as_ref().map(|price| price.price_list.id),
+                created_by: Some(claims.sub),
+            },
+        )
+        .await?;
+        charges.push(patient_charge_json(&charge));
+
+        if let Some(item) = items.iter_mut().find(|item| item.id == item_id) {
+            item.quantity_dispensed += quantity;
+            tenant::entities::prescription_items::ActiveModel {
+                id: Set(item.id),
+                quantity_dispensed: Set(item.quantity_dispensed),
+                updated_at: Set(Utc::now().naive_utc()),
+                ..Default::default()
+            }
+            .update(&txn)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to update prescription item {}: {}", item.pid, err);
+                ApiResponse::new(500, json!({ "message": "Failed to dispense" }))
+            })?;
+        }
+    }
+
+    let mut active_model: tenant::entities::prescriptions::ActiveModel =
+        prescription.clone().into();
+    active_model.status = Set(dispensing_status(&items));
+    active_model.updated_at = Set(Utc::now().naive_utc());
+    let prescription = active_model.update(&txn).await.map_err(|err| {
+        log::error!(
+            "Failed to update prescription {}: {}",
+            prescription.pid,
+            err
+        );
+        ApiResponse::new(500, json!({ "message": "Failed to dispense" }))
+    })?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit dispense: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to dispense" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "dispense": dispense_json(&dispense, dispense_items),
+            "prescription": prescription_json(&prescription, &items),
+            "charges": charges,
+            "message": "Medication dispensed successfully",
+        }),
+    ))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct DispenseParams {
+    pub patient_pid: Option<Uuid>,
+    pub prescription_pid: Option<Uuid>,
+    pub store_pid: Option<Uuid>,
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+}
+
+pub async fn index(
+    app_state: web::Data<AppState>,
+    query: web::Query<DispenseParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let mut stmt = tenant::entities::dispenses::Entity::find();
+
+    if let Some(patient_pid) = query.patient_pid {
+        stmt = stmt.filter(tenant::entities::dispenses::Column::PatientPid.eq(patient_pid));
+    }
+
+    if let Some(prescription_pid) = query.prescription_pid {
+        let prescription = find_prescription(&tenant_db, prescription_pid).await?;
+        stmt = stmt.filter(tenant::entities::dispenses::Column::PrescriptionId.eq(prescription.id));
+    }
+
+    if let Some(store_pid) = query.store_pid {
+        let store = find_store(&tenant_db, store_pid).await?;
+        stmt = stmt.filter(tenant::entities::dispenses::Column::StoreId.eq(store.id));
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let paginator = stmt
+        .order_by_desc(tenant::entities::dispenses::Column::DispensedAt)
+        .paginate(&tenant_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
+
+    let dispenses = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let mut items =
+        fetch_dispense_items(&tenant_db, dispenses.iter().map(|d| d.id).collect()).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "dispenses": dispenses
+                .iter()
+                .map(|dispense| dispense_json(dispense, items.remove(&dispense.id).unwrap_or_default()))
+                .collect::<Vec<_>>(),
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "Dispenses fetched successfully",
+        }),
+    ))
+}
+
+pub async fn show(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let dispense = find_dispense(&tenant_db, path.into_inner()).await?;
+    let mut items = fetch_dispense_items(&tenant_db, vec![dispense.id]).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "dispense": dispense_json(&dispense, items.remove(&dispense.id).unwrap_or_default()),
+            "message": "Dispense fetched successfully",
+        }),
+    ))
+}
+
+/// Medicine labels for a dispense, one per item with its dosage
+/// instructions. Generated on first request and reused for reprints.
+pub async fn labels(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let dispense = find_dispense(&tenant_db, path.into_inner()).await?;
+
+    let dispense_items = tenant::entities::dispense_items::Entity::find()
+        .filter(tenant::entities::dispense_items::Column::DispenseId.eq(dispense.id))
+        .order_by_asc(tenant::entities::dispense_items::Column::Id)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to fetch items of dispense {}: {}",
+                dispense.pid,
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to generate labels" }))
+        })?;
+
+    let prescription_items: HashMap<i32, tenant::entities::prescription_items::Model> =
+        tenant::entities::prescription_items::Entity::find()
+            .filter(
+                tenant::entities::prescription_items::Column::Id
+                    .is_in(dispense_items.iter().map(|i| i.prescription_item_id)),
+            )
+            .all(&tenant_db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to fetch prescription items: {}", err);
+                ApiResponse::new(500, json!({ "message": "Failed to generate labels" }))
+            })?
+            .into_iter()
+            .map(|item| (item.id, item))
+            .collect();
+    let (products, batches) = fetch_products_and_batches(&tenant_db, &dispense_items).await?;
+
+    let mut labels: Vec<DispenseLabel> = Vec::new();
+    for dispense_item in &dispense_items {
+        let (Some(item), Some(product), Some(batch)) = (
+            prescription_items.get(&dispense_item.prescription_item_id),
+            products.get(&dispense_item.product_id),
+            batches.get(&dispense_item.batch_id),
+        ) else {
+            continue;
+        };
+
+        match labels
+            .iter_mut()
+            .find(|label| label.item.id == item.id && label.product.id == product.id)
+        {
+            Some(label) => {
+                label.quantity += dispense_item.quantity;
+                label.batches.push(batch);
+            }
+            None => labels.push(DispenseLabel {
+                item,
+                product,
+                batches: vec![batch],
+                quantity: dispense_item.quantity,
+            }),
+        }
+    }
+
+    let document_file_pid = match dispense.label_document_file_pid {
+        Some(document_file_pid) => document_file_pid,
+        None => {
+            let patient = find_patient(&app_state, dispense.patient_pid).await?;
+            let (tenant_id, _, _) = get_tenant_id(&req, &app_state).await?;
+            let facility_name = main::entities::tenants::Entity::find_by_id(tenant_id)
+                .one(&app_state.main_db)
+                .await
+                .map_err(|err| {
+                    log::error!("Failed to fetch tenant {}: {}", tenant_id, err);
+                    ApiResponse::new(500, json!({ "message": "Failed to fetch facility" }))
+                })?
+                .map(|tenant| tenant.name)
+                .unwrap_or_default();
+
+            let html =
+                dispense_labels_html(&facility_name, &patient, dispense.dispensed_at, &labels);
+            let s3_key = format!("dispense_labels/{}.png", Uuid::new_v4());
+            let document_file_pid = generate_png(
+                &html,
+                &req,
+                &app_state,
+                &s3_key,
+                Some(patient.id),
+                FileVisibility::Tenant,
+            )
+            .await?;
+
+            tenant::entities::dispenses::ActiveModel {
+                id: Set(dispense.id),
+                label_document_file_pid: Set(Some(document_file_pid)),
+                ..Default::default()
+            }
+            .update(&tenant_db)
+            .await
+            .map_err(|err| {
+                log::error!(
+                    "Failed to save labels for dispense {}: {}",
+                    dispense.pid,
+                    err
+                );
+                ApiResponse::new(500, json!({ "message": "Failed to save labels" }))
+            })?;
+
+            document_file_pid
+        }
+    };
+
+    let (url, _) =
+        authorized_file_url(&app_state, &req, document_file_pid, LABEL_URL_EXPIRY_SECS).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "labels": labels.iter().map(dispense_label_json).collect::<Vec<_>>(),
+            "url": url,
+            "expires_in": LABEL_URL_EXPIRY_SECS,
+            "message": "Labels generated successfully",
+        }),
+    ))
+}
+
+/// Item JSON per dispense id.
+async fn fetch_dispense_items(
+    tenant_db: &DatabaseConnection,
+    dispense_ids: Vec<i32>,
+) -> Result<HashMap<i32, Vec<Value>>, ApiResponse> {
+    let dispense_items = tenant::entities::dispense_items::Entity::find()
+        .filter(tenant::entities::dispense_items::Column::DispenseId.is_in(dispense_ids))
+        .order_by_asc(tenant::entities::dispense_items::Column::Id)
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch dispense items: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch dispenses" }))
+        })?;
+    let (products, batches) = fetch_products_and_batches(tenant_db, &dispense_items).await?;
+
+    let mut items: HashMap<i32, Vec<Value>> = HashMap::new();
+    for item in &dispense_items {
+        items
+            .entry(item.dispense_id)
+            .or_default()
+            .push(dispense_item_json(
+                item,
+                products.get(&item.product_id),
+                batches.get(&item.batch_id),
+            ));
+    }
+
+    Ok(items)
+}
+
+#[allow(clippy::type_complexity)]
+async fn fetch_products_and_batches(
+    tenant_db: &DatabaseConnection,
+    dispense_items: &[tenant::entities::dispense_items::Model],
+) -> Result<
+    (
+        HashMap<i32, tenant::entities::pharmacy_products::Model>,
+        HashMap<i32, tenant::entities::stock_batches::Model>,
+    ),
+    ApiResponse,
+> {
+    let products = tenant::entities::pharmacy_products::Entity::find()
+        .filter(
+            tenant::entities::pharmacy_products::Column::Id
+                .is_in(dispense_items.iter().map(|i| i.product_id)),
+        )
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch products: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch dispenses" }))
+        })?
+        .into_iter()
+        .map(|product| (product.id, product))
+        .collect();
+
+    let batches = tenant::entities::stock_batches::Entity::find()
+        .filter(
+            tenant::entities::stock_batches::Column::Id
+                .is_in(dispense_items.iter().map(|i| i.batch_id)),
+        )
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch batches: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch dispenses" }))
+        })?
+        .into_iter()
+        .map(|batch| (batch.id, batch))
+        .collect();
+
+    Ok((products, batches))
+}
+
+async fn find_dispense(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+) -> Result<tenant::entities::dispenses::Model, ApiResponse> {
+    tenant::entities::dispenses::Entity::find_by_pid(pid)
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch dispense {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch dispense" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Dispense not found" })))
+}
@@ -0,0 +1,408 @@
+use std::collections::HashMap;
+
+use actix_web::{HttpRequest, web};
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        main,
+        tenant::{
+            self,
+            migrations::sea_orm::{
+                ActiveModelTrait, ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter,
+                QueryOrder, Set,
+            },
+        },
+    },
+    handlers::services::{
+        immunizations::{build_immunization_summary, immunization_record_json},
+        patients::find_patient,
+    },
+    utils::{
+        api_response::ApiResponse,
+        app_state::AppState,
+        jwt::{get_logged_in_user_claims, get_tenant_db, get_tenant_id},
+        pagination::PaginationParams,
+        validator_error::ValidationError,
+    },
+};
+
+const INJECTION_SITES: [&str; 7] = [
+    "left_arm",
+    "right_arm",
+    "left_thigh",
+    "right_thigh",
+    "oral",
+    "intradermal_left_arm",
+    "intradermal_right_arm",
+];
+
+pub async fn index(
+    app_state: web::Data<AppState>,
+    query: web::Query<PaginationParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let mut stmt = tenant::entities::immunization_records::Entity::find()
+        .filter(tenant::entities::immunization_records::Column::DeletedAt.is_null());
+
+    if let Some(term) = &query.search {
+        use tenant::migrations::{Expr, extension::postgres::PgExpr};
+
+        let like = format!("%{}%", term);
+        stmt = stmt.filter(
+            Condition::any()
+                .add(
+                    Expr::col(tenant::entities::immunization_records::Column::VaccineName)
+                        .ilike(like.clone()),
+                )
+                .add(
+                    Expr::col(tenant::entities::immunization_records::Column::VaccineCode)
+                        .ilike(like.clone()),
+                )
+                .add(
+                    Expr::col(tenant::entities::immunization_records::Column::LotNumber)
+                        .ilike(like.clone()),
+                ),
+        );
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(10).clamp(1, 100);
+    let paginator = stmt
+        .order_by_desc(tenant::entities::immunization_records::Column::AdministeredAt)
+        .paginate(&tenant_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
+
+    let results = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?
+        .iter()
+        .map(immunization_record_json)
+        .collect::<Vec<_>>();
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "immunizations": results,
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "Immunization records fetched successfully",
+        }),
+    ))
+}
+
+pub async fn patient_summary(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    get_tenant_db(&req, &app_state).await?;
+
+    let patient = find_patient(&app_state, path.into_inner()).await?;
+    let summary = build_immunization_summary(&app_state, &patient).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "immunizations": summary,
+            "message": "Patient immunizations fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ImmunizationRecordData {
+    pub patient_pid: Option<Uuid>,
+    pub schedule_pid: Option<Uuid>,
+    pub vaccine_code: Option<String>,
+    pub vaccine_name: Option<String>,
+    pub dose_number: Option<i32>,
+    pub lot_number: Option<String>,
+    pub site: Option<String>,
+    pub administered_at: Option<NaiveDateTime>,
+    pub administering_facility: Option<String>,
+    pub notes: Option<String>,
+}
+
+impl ImmunizationRecordData {
+    pub fn validate(&self, is_create: bool) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if is_create && self.patient_pid.is_none() {
+            errors.insert("patient_pid".to_string(), "Patient is required".to_string());
+        }
+
+        if is_create && self.schedule_pid.is_none() {
+            if self.vaccine_code.as_deref().unwrap_or("").trim().is_empty() {
+                errors.insert(
+                    "vaccine_code".to_string(),
+                    "Vaccine code is required when no schedule is selected".to_string(),
+                );
+            }
+
+            if self.vaccine_name.as_deref().unwrap_or("").trim().is_empty() {
+                errors.insert(
+                    "vaccine_name".to_string(),
+                    "Vaccine name is required when no schedule is selected".to_string(),
+                );
+            }
+
+            if self.dose_number.is_none() {
+                errors.insert(
+                    "dose_number".to_string(),
+                    "Dose number is required when no schedule is selected".to_string(),
+                );
+            }
+        }
+
+        if let Some(dose_number) = self.dose_number
+            && dose_number < 0
+        {
+            errors.insert(
+                "dose_number".to_string(),
+                "Dose number must be greater than or equal to 0".to_string(),
+            );
+        }
+
+        if let Some(site) = &self.site
+            && !INJECTION_SITES.contains(&site.as_str())
+        {
+            errors.insert(
+                "site".to_string(),
+                format!("Site must be one of: {}", INJECTION_SITES.join(", ")),
+            );
+        }
+
+        if let Some(administered_at) = self.administered_at
+            && administered_at > Utc::now().naive_utc()
+        {
+            errors.insert(
+                "administered_at".to_string(),
+                "Administration date cannot be in the future".to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+pub async fn create(
+    app_state: web::Data<AppState>,
+    data: web::Json<ImmunizationRecordData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate(true) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let (tenant_id, _, _) = get_tenant_id(&req, &app_state).await?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let patient = find_patient(&app_state, data.patient_pid.unwrap_or_default()).await?;
+
+    let (schedule_pid, vaccine_code, vaccine_name, dose_number) = match data.schedule_pid {
+        Some(schedule_pid) => {
+            let schedule = main::entities::vaccine_schedules::Entity::find_by_pid(schedule_pid)
+                .filter(main::entities::vaccine_schedules::Column::DeletedAt.is_null())
+                .one(&app_state.main_db)
+                .await
+                .map_err(|err| {
+                    log::error!("Failed to fetch vaccine schedule {}: {}", schedule_pid, err);
+                    ApiResponse::new(
+                        500,
+                        json!({ "message": "Failed to fetch vaccine schedule" }),
+                    )
+                })?
+                .ok_or_else(|| {
+                    ApiResponse::new(404, json!({ "message": "Vaccine schedule not found" }))
+                })?;
+
+            (
+                Some(schedule.pid),
+                schedule.vaccine_code,
+                schedule.vaccine_name,
+                schedule.dose_number,
+            )
+        }
+        None => (
+            None,
+            data.vaccine_code.clone().unwrap_or_default(),
+            data.vaccine_name.clone().unwrap_or_default(),
+            data.dose_number.unwrap_or_default(),
+        ),
+    };
+
+    let administering_facility = match &data.administering_facility {
+        Some(facility) if !facility.trim().is_empty() => facility.trim().to_string(),
+        _ => main::entities::tenants::Entity::find_by_id(tenant_id)
+            .one(&app_state.main_db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to fetch tenant {}: {}", tenant_id, err);
+                ApiResponse::new(500, json!({ "message": "Failed to fetch tenant" }))
+            })?
+            .map(|tenant| tenant.name)
+            .unwrap_or_default(),
+    };
+
+    let record = tenant::entities::immunization_records::ActiveModel {
+        patient_pid: Set(patient.pid),
+        schedule_pid: Set(schedule_pid),
+        vaccine_code: Set(vaccine_code.trim().to_uppercase()),
+        vaccine_name: Set(vaccine_name.trim().to_string()),
+        dose_number: Set(dose_number),
+        lot_number: Set(data.lot_number.clone()),
+        site: Set(data.site.clone()),
+        administered_at: Set(data
+            .administered_at
+            .unwrap_or_else(|| Utc::now().naive_utc())),
+        administering_facility: Set(administering_facility),
+        administered_by: Set(Some(claims.sub)),
+        notes: Set(data.notes.clone()),
+        ..Default::default()
+    }
+    .insert(&tenant_db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to create immunization record: {}", err);
+        ApiResponse::new(
+            500,
+            json!({ "message": "Failed to create immunization record" }),
+        )
+    })?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "immunization": immunization_record_json(&record),
+            "message": "Immunization recorded successfully",
+        }),
+    ))
+}
+
+pub async fn edit(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<ImmunizationRecordData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate(false) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let record = find_record(&tenant_db, path.into_inner()).await?;
+
+    let mut active_model: tenant::entities::immunization_records::ActiveModel =
+        record.to_owned().into();
+
+    if let Some(lot_number) = &data.lot_number {
+        active_model.lot_number = Set(Some(lot_number.clone()));
+    }
+    if let Some(site) = &data.site {
+        active_model.site = Set(Some(site.clone()));
+    }
+    if let Some(administered_at) = data.administered_at {
+        active_model.administered_at = Set(administered_at);
+    }
+    if let Some(facility) = &data.administering_facility
+        && !facility.trim().is_empty()
+    {
+        active_model.administering_facility = Set(facility.trim().to_string());
+    }
+    if let Some(notes) = &data.notes {
+        active_model.notes = Set(Some(notes.clone()));
+    }
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let record = active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!(
+            "Failed to update immunization record {}: {}",
+            record.pid,
+            err
+        );
+        ApiResponse::new(
+            500,
+            json!({ "message": "Failed to update immunization record" }),
+        )
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "immunization": immunization_record_json(&record),
+            "message": "Immunization record updated successfully",
+        }),
+    ))
+}
+
+pub async fn destroy(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let record = find_record(&tenant_db, path.into_inner()).await?;
+
+    let mut active_model: tenant::entities::immunization_records::ActiveModel =
+        record.to_owned().into();
+    active_model.deleted_at = Set(Some(Utc::now().naive_utc()));
+    active_model.updated_at = Set(Utc::now().naive_utc());
+    active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!(
+            "Failed to delete immunization record {}: {}",
+            record.pid,
+            err
+        );
+        ApiResponse::new(
+            500,
+            json!({ "message": "Failed to delete immunization record" }),
+        )
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({ "message": "Immunization record deleted successfully" }),
+    ))
+}
+
+async fn find_record(
+    tenant_db: &tenant::migrations::sea_orm::DatabaseConnection,
+    pid: Uuid,
+) -> Result<tenant::entities::immunization_records::Model, ApiResponse> {
+    tenant::entities::immunization_records::Entity::find_by_pid(pid)
+        .filter(tenant::entities::immunization_records::Column::DeletedAt.is_null())
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch immunization record {}: {}", pid, err);
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to fetch immunization record" }),
+            )
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Immunization record not found" })))
+}
@@ -0,0 +1,1312 @@
+use std::collections::HashMap;
+
+use actix_web::{HttpRequest, web};
+use chrono::{NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        main::{self, entities::sea_orm_active_enums::FileVisibility},
+        tenant::{
+            self,
+            entities::sea_orm_active_enums::{AdmissionStatus, BedStatus, DischargeDisposition},
+            migrations::sea_orm::{
+                ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
+                QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait,
+            },
+        },
+    },
+    handlers::services::{
+        files::authorized_file_url,
+        inpatient::{
+            admission_json, bed_json, bed_transfer_json, claim_bed, discharge_summary_html,
+            length_of_stay_days, vacate_bed, ward_json,
+        },
+        patients::find_patient,
+    },
+    utils::{
+        api_response::ApiResponse,
+        app_state::AppState,
+        html_to_image::generate_png,
+        jwt::{get_logged_in_user_claims, get_tenant_db, get_tenant_id},
+        validator_error::ValidationError,
+    },
+};
+
+const DISCHARGE_SUMMARY_URL_EXPIRY_SECS: u64 = 300;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct WardData {
+    pub name: Option<String>,
+    pub ward_type: Option<String>,
+    pub description: Option<String>,
+    pub is_active: Option<bool>,
+}
+
+impl WardData {
+    pub fn validate(&self, is_create: bool) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        match self.name.as_deref().map(str::trim) {
+            None if is_create => {
+                errors.insert("name".to_string(), "Ward name is required".to_string());
+            }
+            Some("") => {
+                errors.insert("name".to_string(), "Ward name is required".to_string());
+            }
+            Some(name) if name.len() > 100 => {
+                errors.insert(
+                    "name".to_string(),
+                    "Ward name must be at most 100 characters".to_string(),
+                );
+            }
+            _ => {}
+        }
+
+        if self.ward_type.as_ref().is_some_and(|t| t.len() > 50) {
+            errors.insert(
+                "ward_type".to_string(),
+                "Ward type must be at most 50 characters".to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+/// Wards with their bed counts by status.
+pub async fn index_wards(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let wards = fetch_wards_with_beds(&tenant_db).await?;
+
+    let wards = wards
+        .iter()
+        .map(|(ward, beds)| {
+            let mut data = ward_json(ward);
+            data["bed_count"] = json!(beds.len());
+            data["free_beds"] = json!(count_beds(beds, BedStatus::Free));
+            data
+        })
+        .collect::<Vec<_>>();
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "wards": wards,
+            "message": "Wards fetched successfully",
+        }),
+    ))
+}
+
+pub async fn create_ward(
+    app_state: web::Data<AppState>,
+    data: web::Json<WardData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate(true) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let name = data.name.as_deref().unwrap_or_default().trim().to_string();
+    ensure_ward_name_available(&tenant_db, &name, None).await?;
+
+    let ward = tenant::entities::wards::ActiveModel {
+        name: Set(name),
+        ward_type: Set(data.ward_type.clone()),
+        description: Set(data.description.clone()),
+        is_active: Set(data.is_active.unwrap_or(true)),
+        ..Default::default()
+    }
+    .insert(&tenant_db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to create ward: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create ward" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "ward": ward_json(&ward),
+            "message": "Ward created successfully",
+        }),
+    ))
+}
+
+pub async fn edit_ward(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<WardData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate(false) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let ward = find_ward(&tenant_db, path.into_inner()).await?;
+    let ward_pid = ward.pid;
+
+    let mut active_model: tenant::entities::wards::ActiveModel = ward.into();
+
+    if let Some(name) = &data.name {
+        let name = name.trim().to_string();
+        ensure_ward_name_available(&tenant_db, &name, Some(ward_pid)).await?;
+        active_model.name = Set(name);
+    }
+    if let Some(ward_type) = &data.ward_type {
+        active_model.ward_type = Set(Some(ward_type.clone()));
+    }
+    if let Some(description) = &data.description {
+        active_model.description = Set(Some(description.clone()));
+    }
+    if let Some(is_active) = data.is_active {
+        active_model.is_active = Set(is_active);
+    }
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let ward = active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!("Failed to update ward {}: {}", ward_pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to update ward" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "ward": ward_json(&ward),
+            "message": "Ward updated successfully",
+        }),
+    ))
+}
+
+/// Every bed on the ward, with the patient currently occupying it.
+pub async fn ward_beds(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let ward = find_ward(&tenant_db, path.into_inner()).await?;
+
+    let beds = tenant::entities::beds::Entity::find()
+        .filter(tenant::entities::beds::Column::WardId.eq(ward.id))
+        .filter(tenant::entities::beds::Column::DeletedAt.is_null())
+        .order_by_asc(tenant::entities::beds::Column::BedNumber)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch beds for ward {}: {}", ward.pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch beds" }))
+        })?;
+
+    let occupants = fetch_current_admissions(&tenant_db, beds.iter().map(|b| b.id).collect())
+        .await?
+        .into_iter()
+        .map(|admission| (admission.bed_id, admission))
+        .collect::<HashMap<_, _>>();
+
+    let beds = beds
+        .iter()
+        .map(|bed| {
+            let mut data = bed_json(bed);
+            data["admission"] = occupants
+                .get(&bed.id)
+                .map(|admission| {
+                    json!({
+                        "pid": admission.pid,
+                        "patient_pid": admission.patient_pid,
+                        "admitted_at": admission.admitted_at,
+                    })
+                })
+                .unwrap_or(Value::Null);
+            data
+        })
+        .collect::<Vec<_>>();
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "ward": ward_json(&ward),
+            "beds": beds,
+            "message": "Beds fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct BedData {
+    pub bed_number: String,
+}
+
+pub async fn create_bed(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<BedData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let bed_number = data.bed_number.trim().to_string();
+    if bed_number.is_empty() || bed_number.len() > 20 {
+        let mut errors = HashMap::new();
+        errors.insert(
+            "bed_number".to_string(),
+            "Bed number is required and must be at most 20 characters".to_string(),
+        );
+        return Err(ApiResponse::new(400, json!(ValidationError { errors })));
+    }
+
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let ward = find_ward(&tenant_db, path.into_inner()).await?;
+
+    let existing = tenant::entities::beds::Entity::find()
+        .filter(tenant::entities::beds::Column::WardId.eq(ward.id))
+        .filter(tenant::entities::beds::Column::BedNumber.eq(&bed_number))
+        .filter(tenant::entities::beds::Column::DeletedAt.is_null())
+        .one(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to check bed number: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to create bed" }))
+        })?;
+
+    if existing.is_some() {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "A bed with this number already exists on the ward" }),
+        ));
+    }
+
+    let bed = tenant::entities::beds::ActiveModel {
+        ward_id: Set(ward.id),
+        bed_number: Set(bed_number),
+        status: Set(BedStatus::Free),
+        ..Default::default()
+    }
+    .insert(&tenant_db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to create bed on ward {}: {}", ward.pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to create bed" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "bed": bed_json(&bed),
+            "message": "Bed created successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct BedStatusData {
+    pub status: Option<BedStatus>,
+    pub is_active: Option<bool>,
+}
+
+/// Housekeeping moves beds between free and cleaning, and can take a bed out
+/// of service. Beds only become occupied through an admission or transfer.
+pub async fn edit_bed(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<BedStatusData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if data.status == Some(BedStatus::Occupied) {
+        return Err(ApiResponse::new(
+            400,
+            json!({ "message": "Beds are occupied by admitting or transferring a patient" }),
+        ));
+    }
+
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let bed = find_bed(&tenant_db, path.into_inner()).await?;
+
+    if bed.status == BedStatus::Occupied && (data.status.is_some() || data.is_active == Some(false))
+    {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Bed is occupied; discharge or transfer the patient first" }),
+        ));
+    }
+
+    let bed_pid = bed.pid;
+    let mut active_model: tenant::entities::beds::ActiveModel = bed.into();
+    if let Some(status) = &data.status {
+        active_model.status = Set(status.clone());
+    }
+    if let Some(is_active) = data.is_active {
+        active_model.is_active = Set(is_active);
+    }
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let bed = active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!("Failed to update bed {}: {}", bed_pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to update bed" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "bed": bed_json(&bed),
+            "message": "Bed updated successfully",
+        }),
+    ))
+}
+
+/// Current occupancy of each ward.
+pub async fn census(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let wards = fetch_wards_with_beds(&tenant_db).await?;
+
+    let mut totals = (0usize, 0usize, 0usize, 0usize);
+    let wards = wards
+        .iter()
+        .filter(|(ward, _)| ward.is_active)
+        .map(|(ward, beds)| {
+            let beds = beds.iter().filter(|bed| bed.is_active).collect::<Vec<_>>();
+            let occupied = beds
+                .iter()
+                .filter(|bed| bed.status == BedStatus::Occupied)
+                .count();
+            let free = beds
+                .iter()
+                .filter(|bed| bed.status == BedStatus::Free)
+                .count();
+            let cleaning = beds
+                .iter()
+                .filter(|bed| bed.status == BedStatus::Cleaning)
+                .count();
+
+            totals.0 += beds.len();
+            totals.1 += occupied;
+            totals.2 += free;
+            totals.3 += cleaning;
+
+            json!({
+                "ward": { "pid": ward.pid, "name": ward.name, "ward_type": ward.ward_type },
+                "total_beds": beds.len(),
+                "occupied": occupied,
+                "free": free,
+                "cleaning": cleaning,
+                "occupancy_rate": occupancy_rate(occupied, beds.len()),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "as_at": Utc::now().naive_utc(),
+            "wards": wards,
+            "totals": {
+                "total_beds": totals.0,
+                "occupied": totals.1,
+                "free": totals.2,
+                "cleaning": totals.3,
+                "occupancy_rate": occupancy_rate(totals.1, totals.0),
+            },
+            "message": "Census fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AdmissionParams {
+    pub status: Option<AdmissionStatus>,
+    pub ward_pid: Option<Uuid>,
+    pub patient_pid: Option<Uuid>,
+}
+
+pub async fn index_admissions(
+    app_state: web::Data<AppState>,
+    query: web::Query<AdmissionParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let mut stmt = tenant::entities::admissions::Entity::find().filter(
+        tenant::entities::admissions::Column::Status
+            .eq(query.status.clone().unwrap_or(AdmissionStatus::Admitted)),
+    );
+
+    if let Some(patient_pid) = query.patient_pid {
+        stmt = stmt.filter(tenant::entities::admissions::Column::PatientPid.eq(patient_pid));
+    }
+
+    if let Some(ward_pid) = query.ward_pid {
+        let ward = find_ward(&tenant_db, ward_pid).await?;
+        let bed_ids = tenant::entities::beds::Entity::find()
+            .filter(tenant::entities::beds::Column::WardId.eq(ward.id))
+            .all(&tenant_db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to fetch beds for ward {}: {}", ward.pid, err);
+                ApiResponse::new(500, json!({ "message": "Failed to fetch admissions" }))
+            })?
+            .into_iter()
+            .map(|bed| bed.id)
+            .collect::<Vec<_>>();
+        stmt = stmt.filter(tenant::entities::admissions::Column::BedId.is_in(bed_ids));
+    }
+
+    let admissions = stmt
+        .order_by_desc(tenant::entities::admissions::Column::AdmittedAt)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch admissions: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch admissions" }))
+        })?;
+
+    let locations = fetch_bed_locations(&tenant_db, admissions.iter().map(|a| a.bed_id)).await?;
+
+    let admissions = admissions
+        .iter()
+        .map(|admission| {
+            let location = locations.get(&admission.bed_id);
+            admission_json(
+                admission,
+                location.map(|(ward, _)| ward),
+                location.map(|(_, bed)| bed),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "total_items": admissions.len(),
+            "admissions": admissions,
+            "message": "Admissions fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct AdmissionData {
+    pub patient_pid: Option<Uuid>,
+    pub encounter_pid: Option<Uuid>,
+    pub bed_pid: Option<Uuid>,
+    pub admission_reason: String,
+    pub admitting_diagnosis: Option<String>,
+    pub admitted_at: Option<NaiveDateTime>,
+}
+
+impl AdmissionData {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if self.patient_pid.is_none() {
+            errors.insert("patient_pid".to_string(), "Patient is required".to_string());
+        }
+
+        if self.bed_pid.is_none() {
+            errors.insert("bed_pid".to_string(), "Bed is required".to_string());
+        }
+
+        if self.admission_reason.trim().is_empty() {
+            errors.insert(
+                "admission_reason".to_string(),
+                "Reason for admission is required".to_string(),
+            );
+        }
+
+        if self
+            .admitting_diagnosis
+            .as_ref()
+            .is_some_and(|diagnosis| diagnosis.len() > 255)
+        {
+            errors.insert(
+                "admitting_diagnosis".to_string(),
+                "Diagnosis must be at most 255 characters".to_string(),
+            );
+        }
+
+        if self
+            .admitted_at
+            .is_some_and(|at| at > Utc::now().naive_utc())
+        {
+            errors.insert(
+                "admitted_at".to_string(),
+                "Admission time cannot be in the future".to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+pub async fn admit(
+    app_state: web::Data<AppState>,
+    data: web::Json<AdmissionData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate() {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let patient = find_patient(&app_state, data.patient_pid.unwrap_or_default()).await?;
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start admission transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to admit patient" }))
+    })?;
+
+    let current = tenant::entities::admissions::Entity::find()
+        .filter(tenant::entities::admissions::Column::PatientPid.eq(patient.pid))
+        .filter(tenant::entities::admissions::Column::Status.eq(AdmissionStatus::Admitted))
+        .one(&txn)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to check current admission: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to admit patient" }))
+        })?;
+
+    if current.is_some() {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Patient is already admitted" }),
+        ));
+    }
+
+    let bed = find_bed(&txn, data.bed_pid.unwrap_or_default()).await?;
+    claim_bed(&txn, bed.id).await?;
+
+    let admission = tenant::entities::admissions::ActiveModel {
+        patient_pid: Set(patient.pid),
+        encounter_pid: Set(data.encounter_pid),
+        bed_id: Set(bed.id),
+        status: Set(AdmissionStatus::Admitted),
+        admission_reason: Set(data.admission_reason.trim().to_string()),
+        admitting_diagnosis: Set(data.admitting_diagnosis.clone()),
+        admitted_at: Set(data.admitted_at.unwrap_or_else(|| Utc::now().naive_utc())),
+        admitted_by: Set(Some(claims.sub)),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to admit patient {}: {}", patient.pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to admit patient" }))
+    })?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit admission of {}: {}", patient.pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to admit patient" }))
+    })?;
+
+    let location = fetch_bed_locations(&tenant_db, [admission.bed_id]).await?;
+    let location = location.get(&admission.bed_id);
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "admission": admission_json(
+                &admission,
+                location.map(|(ward, _)| ward),
+                location.map(|(_, bed)| bed),
+            ),
+            "message": "Patient admitted successfully",
+        }),
+    ))
+}
+
+pub async fn show_admission(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let admission = find_admission(&tenant_db, path.into_inner()).await?;
+    let transfers = fetch_transfers(&tenant_db, admission.id).await?;
+
+    let bed_ids = transfers
+        .iter()
+        .flat_map(|t| [t.from_bed_id, t.to_bed_id])
+        .chain([admission.bed_id])
+        .collect::<Vec<_>>();
+    let locations = fetch_bed_locations(&tenant_db, bed_ids).await?;
+    let label = |bed_id: i32| {
+        locations
+            .get(&bed_id)
+            .map(|(ward, bed)| format!("{} / {}", ward.name, bed.bed_number))
+    };
+
+    let location = locations.get(&admission.bed_id);
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "admission": admission_json(
+                &admission,
+                location.map(|(ward, _)| ward),
+                location.map(|(_, bed)| bed),
+            ),
+            "transfers": transfers
+                .iter()
+                .map(|t| bed_transfer_json(
+                    t,
+                    label(t.from_bed_id).as_deref(),
+                    label(t.to_bed_id).as_deref(),
+                ))
+                .collect::<Vec<_>>(),
+            "length_of_stay_days": admission.length_of_stay_days.unwrap_or_else(|| {
+                length_of_stay_days(admission.admitted_at, Utc::now().naive_utc())
+            }),
+            "message": "Admission fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct TransferData {
+    pub bed_pid: Option<Uuid>,
+    pub reason: Option<String>,
+}
+
+pub async fn transfer(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<TransferData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let Some(bed_pid) = data.bed_pid else {
+        let mut errors = HashMap::new();
+        errors.insert("bed_pid".to_string(), "Bed is required".to_string());
+        return Err(ApiResponse::new(400, json!(ValidationError { errors })));
+    };
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start transfer transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to transfer patient" }))
+    })?;
+
+    let admission = lock_open_admission(&txn, path.into_inner()).await?;
+    let bed = find_bed(&txn, bed_pid).await?;
+    if bed.id == admission.bed_id {
+        return Err(ApiResponse::new(
+            400,
+            json!({ "message": "Patient is already in this bed" }),
+        ));
+    }
+
+    claim_bed(&txn, bed.id).await?;
+
+    let from_bed_id = admission.bed_id;
+    let admission_id = admission.id;
+    let mut active_model: tenant::entities::admissions::ActiveModel = admission.into();
+    active_model.bed_id = Set(bed.id);
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let admission = update_open_admission(
+        &txn,
+        admission_id,
+        active_model,
+        "Failed to transfer patient",
+    )
+    .await?;
+
+    tenant::entities::bed_transfers::ActiveModel {
+        admission_id: Set(admission.id),
+        from_bed_id: Set(from_bed_id),
+        to_bed_id: Set(bed.id),
+        reason: Set(data.reason.clone()),
+        transferred_by: Set(Some(claims.sub)),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to record transfer for {}: {}", admission.pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to record transfer" }))
+    })?;
+
+    vacate_bed(&txn, from_bed_id).await?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit transfer of {}: {}", admission.pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to transfer patient" }))
+    })?;
+
+    let location = fetch_bed_locations(&tenant_db, [admission.bed_id]).await?;
+    let location = location.get(&admission.bed_id);
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "admission": admission_json(
+                &admission,
+                location.map(|(ward, _)| ward),
+                location.map(|(_, bed)| bed),
+            ),
+            "message": "Patient transferred successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct DischargeData {
+    pub disposition: Option<DischargeDisposition>,
+    pub discharge_diagnosis: Option<String>,
+    pub discharge_summary: Option<String>,
+    pub discharge_instructions: Option<String>,
+    pub discharged_at: Option<NaiveDateTime>,
+}
+
+impl DischargeData {
+    pub fn validate(&self, admitted_at: NaiveDateTime) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if self.disposition.is_none() {
+            errors.insert(
+                "disposition".to_string(),
+                "Discharge disposition is required".to_string(),
+            );
+        }
+
+        if self
+            .discharge_summary
+            .as_deref()
+            .is_none_or(|summary| summary.trim().is_empty())
+        {
+            errors.insert(
+                "discharge_summary".to_string(),
+                "A summary of the hospital stay is required".to_string(),
+            );
+        }
+
+        if self
+            .discharge_diagnosis
+            .as_ref()
+            .is_some_and(|diagnosis| diagnosis.len() > 255)
+        {
+            errors.insert(
+                "discharge_diagnosis".to_string(),
+                "Diagnosis must be at most 255 characters".to_string(),
+            );
+        }
+
+        if let Some(discharged_at) = self.discharged_at {
+            if discharged_at > Utc::now().naive_utc() {
+                errors.insert(
+                    "discharged_at".to_string(),
+                    "Discharge time cannot be in the future".to_string(),
+                );
+            } else if discharged_at < admitted_at {
+                errors.insert(
+                    "discharged_at".to_string(),
+                    "Discharge time cannot be before admission".to_string(),
+                );
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+/// Closes the admission, frees the bed for cleaning and renders the discharge
+/// summary document.
+pub async fn discharge(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<DischargeData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start discharge transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to discharge patient" }))
+    })?;
+
+    let admission = lock_open_admission(&txn, path.into_inner()).await?;
+
+    if let Err(err) = data.validate(admission.admitted_at) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let patient = find_patient(&app_state, admission.patient_pid).await?;
+    let discharged_at = data.discharged_at.unwrap_or_else(|| Utc::now().naive_utc());
+
+    let mut active_model: tenant::entities::admissions::ActiveModel = admission.clone().into();
+    active_model.status = Set(AdmissionStatus::Discharged);
+    active_model.discharged_at = Set(Some(discharged_at));
+    active_model.discharged_by = Set(Some(claims.sub));
+    active_model.discharge_disposition = Set(data.disposition.clone());
+    active_model.discharge_diagnosis = Set(data.discharge_diagnosis.clone());
+    active_model.discharge_summary = Set(data.discharge_summary.clone());
+    active_model.discharge_instructions = Set(data.discharge_instructions.clone());
+    active_model.length_of_stay_days = Set(Some(length_of_stay_days(
+        admission.admitted_at,
+        discharged_at,
+    )));
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let admission = update_open_admission(
+        &txn,
+        admission.id,
+        active_model,
+        "Failed to discharge patient",
+    )
+    .await?;
+    vacate_bed(&txn, admission.bed_id).await?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit discharge of {}: {}", admission.pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to discharge patient" }))
+    })?;
+
+    let transfers = fetch_transfers(&tenant_db, admission.id).await?;
+    let bed_ids = transfers
+        .iter()
+        .flat_map(|t| [t.from_bed_id, t.to_bed_id])
+        .chain([admission.bed_id])
+        .collect::<Vec<_>>();
+    let locations = fetch_bed_locations(&tenant_db, bed_ids).await?;
+
+    let first_bed_id = transfers
+        .first()
+        .map(|t| t.from_bed_id)
+        .unwrap_or(admission.bed_id);
+    let movements = std::iter::once((first_bed_id, admission.admitted_at))
+        .chain(transfers.iter().map(|t| (t.to_bed_id, t.transferred_at)))
+        .filter_map(|(bed_id, at)| {
+            locations
+                .get(&bed_id)
+                .map(|(ward, bed)| (ward.name.clone(), bed.bed_number.clone(), at))
+        })
+        .collect::<Vec<_>>();
+
+    let (tenant_id, _, _) = get_tenant_id(&req, &app_state).await?;
+    let facility_name = main::entities::tenants::Entity::find_by_id(tenant_id)
+        .one(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch tenant {}: {}", tenant_id, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch facility" }))
+        })?
+        .map(|tenant| tenant.name)
+        .unwrap_or_default();
+
+    let html = discharge_summary_html(&facility_name, &patient, &admission, &movements);
+    let s3_key = format!("discharge_summaries/{}.png", Uuid::new_v4());
+    let document_file_pid = generate_png(
+        &html,
+        &req,
+        &app_state,
+        &s3_key,
+        Some(patient.id),
+        FileVisibility::Tenant,
+    )
+    .await?;
+
+    let admission_pid = admission.pid;
+    let mut active_model: tenant::entities::admissions::ActiveModel = admission.into();
+    active_model.discharge_document_file_pid = Set(Some(document_file_pid));
+    let admission = active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!(
+            "Failed to save discharge summary for {}: {}",
+            admission_pid,
+            err
+        );
+        ApiResponse::new(
+            500,
+            json!({ "message": "Failed to save discharge summary" }),
+        )
+    })?;
+
+    let (url, _) = authorized_file_url(
+        &app_state,
+        &req,
+        document_file_pid,
+        DISCHARGE_SUMMARY_URL_EXPIRY_SECS,
+    )
+    .await?;
+
+    let location = locations.get(&admission.bed_id);
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "admission": admission_json(
+                &admission,
+                location.map(|(ward, _)| ward),
+                location.map(|(_, bed)| bed),
+            ),
+            "discharge_summary_url": url,
+            "expires_in": DISCHARGE_SUMMARY_URL_EXPIRY_SECS,
+            "message": "Patient discharged successfully",
+        }),
+    ))
+}
+
+pub async fn discharge_summary(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let admission = find_admission(&tenant_db, path.into_inner()).await?;
+
+    let Some(document_file_pid) = admission.discharge_document_file_pid else {
+        return Err(ApiResponse::new(
+            404,
+            json!({ "message": "Discharge summary not found" }),
+        ));
+    };
+
+    let (url, _) = authorized_file_url(
+        &app_state,
+        &req,
+        document_file_pid,
+        DISCHARGE_SUMMARY_URL_EXPIRY_SECS,
+    )
+    .await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "url": url,
+            "expires_in": DISCHARGE_SUMMARY_URL_EXPIRY_SECS,
+            "message": "Discharge summary fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct MetricParams {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+/// Daily inpatient metrics recorded for this facility by the nightly job.
+pub async fn metrics(
+    app_state: web::Data<AppState>,
+    query: web::Query<MetricParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let (tenant_id, _, _) = get_tenant_id(&req, &app_state).await?;
+
+    let mut stmt = main::entities::usage_metrics::Entity::find()
+        .filter(main::entities::usage_metrics::Column::TenantId.eq(tenant_id))
+        .filter(main::entities::usage_metrics::Column::MetricType.starts_with("inpatient_"))
+        .filter(main::entities::usage_metrics::Column::DeletedAt.is_null());
+
+    if let Some(from) = query.from {
+        stmt = stmt.filter(
+            main::entities::usage_metrics::Column::PeriodStart
+                .gte(from.and_time(Default::default())),
+        );
+    }
+
+    if let Some(to) = query.to {
+        stmt = stmt.filter(
+            main::entities::usage_metrics::Column::PeriodStart.lte(to.and_time(Default::default())),
+        );
+    }
+
+    let metrics = stmt
+        .order_by_asc(main::entities::usage_metrics::Column::PeriodStart)
+        .all(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch inpatient metrics: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch metrics" }))
+        })?;
+
+    let metrics = metrics
+        .iter()
+        .map(|metric| {
+            json!({
+                "metric_type": metric.metric_type,
+                "metric_value": metric.metric_value,
+                "period_start": metric.period_start,
+                "period_end": metric.period_end,
+                "metadata": metric.metadata,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "metrics": metrics,
+            "message": "Inpatient metrics fetched successfully",
+        }),
+    ))
+}
+
+fn occupancy_rate(occupied: usize, total: usize) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    (occupied as f64 / total as f64 * 1000.0).round() / 10.0
+}
+
+fn count_beds(beds: &[tenant::entities::beds::Model], status: BedStatus) -> usize {
+    beds.iter()
+        .filter(|bed| bed.is_active && bed.status == status)
+        .count()
+}
+
+async fn fetch_wards_with_beds(
+    tenant_db: &DatabaseConnection,
+) -> Result<
+    Vec<(
+        tenant::entities::wards::Model,
+        Vec<tenant::entities::beds::Model>,
+    )>,
+    ApiResponse,
+> {
+    tenant::entities::wards::Entity::find()
+        .find_with_related(tenant::entities::beds::Entity)
+        .filter(tenant::entities::wards::Column::DeletedAt.is_null())
+        .order_by_asc(tenant::entities::wards::Column::Name)
+        .all(tenant_db)
+        .await
+        .map(|wards| {
+            wards
+                .into_iter()
+                .map(|(ward, beds)| {
+                    let beds = beds
+                        .into_iter()
+                        .filter(|bed| bed.deleted_at.is_none())
+                        .collect();
+                    (ward, beds)
+                })
+                .collect()
+        })
+        .map_err(|err| {
+            log::error!("Failed to fetch wards: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch wards" }))
+        })
+}
+
+async fn fetch_current_admissions(
+    tenant_db: &DatabaseConnection,
+    bed_ids: Vec<i32>,
+) -> Result<Vec<tenant::entities::admissions::Model>, ApiResponse> {
+    if bed_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    tenant::entities::admissions::Entity::find()
+        .filter(tenant::entities::admissions::Column::BedId.is_in(bed_ids))
+        .filter(tenant::entities::admissions::Column::Status.eq(AdmissionStatus::Admitted))
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch current admissions: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch admissions" }))
+        })
+}
+
+/// Looks up the ward and bed for each bed id.
+async fn fetch_bed_locations(
+    tenant_db: &DatabaseConnection,
+    bed_ids: impl IntoIterator<Item = i32>,
+) -> Result<
+    HashMap<
+        i32,
+        (
+            tenant::entities::wards::Model,
+            tenant::entities::beds::Model,
+        ),
+    >,
+    ApiResponse,
+> {
+    let mut bed_ids = bed_ids.into_iter().collect::<Vec<_>>();
+    bed_ids.sort_unstable();
+    bed_ids.dedup();
+
+    if bed_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    tenant::entities::beds::Entity::find()
+        .find_also_related(tenant::entities::wards::Entity)
+        .filter(tenant::entities::beds::Column::Id.is_in(bed_ids))
+        .all(tenant_db)
+        .await
+        .map(|beds| {
+            beds.into_iter()
+                .filter_map(|(bed, ward)| ward.map(|ward| (bed.id, (ward, bed))))
+                .collect()
+        })
+        .map_err(|err| {
+            log::error!("Failed to fetch bed locations: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch beds" }))
+        })
+}
+
+async fn fetch_transfers(
+    tenant_db: &DatabaseConnection,
+    admission_id: i32,
+) -> Result<Vec<tenant::entities::bed_transfers::Model>, ApiResponse> {
+    tenant::entities::bed_transfers::Entity::find()
+        .filter(tenant::entities::bed_transfers::Column::AdmissionId.eq(admission_id))
+        .order_by_asc(tenant::entities::bed_transfers::Column::TransferredAt)
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch bed transfers: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch transfers" }))
+        })
+}
+
+async fn ensure_ward_name_available(
+    tenant_db: &DatabaseConnection,
+    name: &str,
+    except: Option<Uuid>,
+) -> Result<(), ApiResponse> {
+    let mut stmt = tenant::entities::wards::Entity::find()
+        .filter(tenant::entities::wards::Column::Name.eq(name));
+
+    if let Some(pid) = except {
+        stmt = stmt.filter(tenant::entities::wards::Column::Pid.ne(pid));
+    }
+
+    let existing = stmt.one(tenant_db).await.map_err(|err| {
+        log::error!("Failed to check ward name: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to save ward" }))
+    })?;
+
+    if existing.is_some() {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "A ward with this name already exists" }),
+        ));
+    }
+
+    Ok(())
+}
+
+async fn find_ward(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+) -> Result<tenant::entities::wards::Model, ApiResponse> {
+    tenant::entities::wards::Entity::find_by_pid(pid)
+        .filter(tenant::entities::wards::Column::DeletedAt.is_null())
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch ward {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch ward" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Ward not found" })))
+}
+
+async fn find_bed<C: ConnectionTrait>(
+    tenant_db: &C,
+    pid: Uuid,
+) -> Result<tenant::entities::beds::Model, ApiResponse> {
+    tenant::entities::beds::Entity::find_by_pid(pid)
+        .filter(tenant::entities::beds::Column::DeletedAt.is_null())
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch bed {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch bed" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Bed not found" })))
+}
+
+/// Finds an admission that is still open and locks its row until the
+/// transaction ends, so it cannot be transferred and discharged at once.
+async fn lock_open_admission<C: ConnectionTrait>(
+    db: &C,
+    pid: Uuid,
+) -> Result<tenant::entities::admissions::Model, ApiResponse> {
+    let admission = tenant::entities::admissions::Entity::find_by_pid(pid)
+        .lock_exclusive()
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch admission {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch admission" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Admission not found" })))?;
+
+    if admission.status != AdmissionStatus::Admitted {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Patient has already been discharged" }),
+        ));
+    }
+
+    Ok(admission)
+}
+
+/// Saves changes to an admission only while it is still open. The status is
+/// part of the update, so an admission is never discharged twice.
+async fn update_open_admission<C: ConnectionTrait>(
+    db: &C,
+    admission_id: i32,
+    active_model: tenant::entities::admissions::ActiveModel,
+    failure_message: &str,
+) -> Result<tenant::entities::admissions::Model, ApiResponse> {
+    let admissions = tenant::entities::admissions::Entity::update_many()
+        .set(active_model)
+        .filter(tenant::entities::admissions::Column::Id.eq(admission_id))
+        .filter(tenant::entities::admissions::Column::Status.eq(AdmissionStatus::Admitted))
+        .exec_with_returning(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to update admission {}: {}", admission_id, err);
+            ApiResponse::new(500, json!({ "message": failure_message }))
+        })?;
+
+    admissions.into_iter().next().ok_or_else(|| {
+        ApiResponse::new(
+            409,
+            json!({ "message": "Patient has already been discharged" }),
+        )
+    })
+}
+
+async fn find_admission(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+) -> Result<tenant::entities::admissions::Model, ApiResponse> {
+    tenant::entities::admissions::Entity::find_by_pid(pid)
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch admission {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch admission" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Admission not found" })))
+}
@@ -0,0 +1,1525 @@
+use std::collections::{BTreeMap, HashMap};
+
+use actix_multipart::Multipart;
+use actix_web::{HttpRequest, web};
+use chrono::{NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        main,
+        tenant::{
+            self,
+            entities::sea_orm_active_enums::{InsuranceClaimStatus, PatientBillStatus},
+            migrations::sea_orm::{
+                ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, DatabaseTransaction,
+                EntityTrait, ModelTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+                TransactionTrait,
+            },
+        },
+    },
+    handlers::services::{
+        files::authorized_file_url,
+        insurance_claims::{
+            ClaimAttachmentForm, MAX_DIAGNOSIS_CODES, OUTSTANDING_CLAIM_STATUSES, aging_bucket,
+            apply_remittance, claim_allocations, claim_attachment_json, claim_attachments,
+            claim_detail_json, claim_json, claim_lines, claim_outstanding, clean_diagnosis_codes,
+            days_outstanding, empty_aging_buckets, find_claim, post_claim_deductible,
+            remittance_json, store_claim_attachments,
+        },
+        patient_bills::{bill_charges, find_bill},
+        patient_insurance::verify_patient_insurance,
+        preauthorizations::{link_preauthorizations, unlink_preauthorizations},
+        pricing::facility_currency,
+    },
+    utils::{
+        api_response::ApiResponse,
+        app_state::AppState,
+        documents::document_number,
+        jwt::{get_logged_in_user_claims, get_tenant_db},
+        validator_error::ValidationError,
+    },
+};
+
+const ATTACHMENT_URL_EXPIRY_SECS: u64 = 300;
+
+type AgingBuckets = HashMap<&'static str, Decimal>;
+
+#[derive(Deserialize, Debug)]
+pub struct ClaimParams {
+    pub status: Option<InsuranceClaimStatus>,
+    pub insurance_provider_pid: Option<Uuid>,
+    pub patient_pid: Option<Uuid>,
+    pub search: Option<String>,
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+}
+
+pub async fn index(
+    app_state: web::Data<AppState>,
+    query: web::Query<ClaimParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let mut stmt = tenant::entities::insurance_claims::Entity::find();
+
+    if let Some(status) = &query.status {
+        stmt = stmt.filter(tenant::entities::insurance_claims::Column::Status.eq(status.clone()));
+    }
+
+    if let Some(provider_pid) = query.insurance_provider_pid {
+        stmt = stmt.filter(
+            tenant::entities::insurance_claims::Column::InsuranceProviderPid.eq(provider_pid),
+        );
+    }
+
+    if let Some(patient_pid) = query.patient_pid {
+        stmt = stmt.filter(tenant::entities::insurance_claims::Column::PatientPid.eq(patient_pid));
+    }
+
+    if let Some(term) = &query.search {
+        use tenant::migrations::{Expr, extension::postgres::PgExpr};
+
+        stmt = stmt.filter(
+            Condition::any()
+                .add(
+                    Expr::col(tenant::entities::insurance_claims::Column::ClaimNumber)
+                        .ilike(format!("%{}%", term)),
+                )
+                .add(
+                    Expr::col(tenant::entities::insurance_claims::Column::InsurerReference)
+                        .ilike(format!("%{}%", term)),
+                ),
+        );
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let paginator = stmt
+        .order_by_desc(tenant::entities::insurance_claims::Column::CreatedAt)
+        .paginate(&tenant_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
+
+    let claims = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "claims": claims.iter().map(claim_json).collect::<Vec<_>>(),
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "Claims fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ClaimData {
+    pub bill_pid: Option<Uuid>,
+    /// ICD-10 codes. Defaults to the patient's recorded diagnoses.
+    pub diagnosis_codes: Option<Vec<String>>,
+    /// Deductible the patient bears on this claim, taken off the amount
+    /// claimed. Defaults to the deductible applied when the bill was split.
+    pub deductible_amount: Option<Decimal>,
+    pub notes: Option<String>,
+}
+
+impl ClaimData {
+    pub fn validate(&self, is_create: bool) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if is_create && self.bill_pid.is_none() {
+            errors.insert("bill_pid".to_string(), "Bill is required".to_string());
+        }
+
+        if let Some(codes) = &self.diagnosis_codes
+            && let Err(err) = clean_diagnosis_codes(codes)
+        {
+            errors.insert("diagnosis_codes".to_string(), err);
+        }
+
+        if self
+            .deductible_amount
+            .is_some_and(|amount| amount < Decimal::ZERO)
+        {
+            errors.insert(
+                "deductible_amount".to_string(),
+                "Deductible cannot be negative".to_string(),
+            );
+        }
+
+        if self
+            .notes
+            .as_ref()
+            .is_some_and(|n| n.chars().count() > 1000)
+        {
+            errors.insert(
+                "notes".to_string(),
+                "Notes must be at most 1000 characters".to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+/// Drafts a claim for the insurer's share of a finalized bill, with a line
+/// per billed charge. A bill has one live claim; a new one can be drafted
+/// only after the last was rejected. The patient's approved
+/// pre-authorizations on the bill's policy are attached to it.
+pub async fn create(
+    app_state: web::Data<AppState>,
+    data: web::Json<ClaimData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate(true) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let user = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let bill = find_bill(&tenant_db, data.bill_pid.unwrap_or_default()).await?;
+
+    let (patient_insurance_pid, insurance_provider_pid) = match (
+        &bill.status,
+        bill.patient_insurance_pid,
+        bill.insurance_provider_pid,
+    ) {
+        (PatientBillStatus::Finalized | PatientBillStatus::Paid, Some(cover), Some(provider))
+            if bill.insurance_amount > Decimal::ZERO =>
+        {
+            (cover, provider)
+        }
+        _ => {
+            return Err(ApiResponse::new(
+                409,
+                json!({ "message": "Only finalized bills with an insurance share can be claimed" }),
+            ));
+        }
+    };
+
+    let live_claim = tenant::entities::insurance_claims::Entity::find()
+        .filter(tenant::entities::insurance_claims::Column::BillId.eq(bill.id))
+        .filter(
+            tenant::entities::insurance_claims::Column::Status.ne(InsuranceClaimStatus::Rejected),
+        )
+        .one(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch claims of bill {}: {}", bill.pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to create claim" }))
+        })?;
+
+    if let Some(claim) = live_claim {
+        return Err(ApiResponse::new(
+            409,
+            json!({
+                "message": format!("The bill is already claimed on {}", claim.claim_number),
+                "claim_pid": claim.pid,
+            }),
+        ));
+    }
+
+    let insurance_share = bill.insurance_amount + bill.deductible_amount;
+    let deductible_amount = data
+        .deductible_amount
+        .unwrap_or(bill.deductible_amount)
+        .round_dp(2);
+    if deductible_amount >= insurance_share {
+        return Err(ApiResponse::new(
+            400,
+            json!(ValidationError {
+                errors: HashMap::from([(
+                    "deductible_amount".to_string(),
+                    "Deductible must be less than the insurance share".to_string(),
+                )]),
+            }),
+        ));
+    }
+
+    let diagnosis_codes = match &data.diagnosis_codes {
+        Some(codes) => clean_diagnosis_codes(codes).unwrap_or_default(),
+        None => {
+            let recorded = tenant::entities::patient_diagnoses::Entity::find()
+                .filter(
+                    tenant::entities::patient_diagnoses::Column::PatientPid.eq(bill.patient_pid),
+                )
+                .filter(tenant::entities::patient_diagnoses::Column::DeletedAt.is_null())
+                .order_by_desc(tenant::entities::patient_diagnoses::Column::DiagnosedOn)
+                .all(&tenant_db)
+                .await
+                .map_err(|err| {
+                    log::error!("Failed to fetch diagnoses of {}: {}", bill.patient_pid, err);
+                    ApiResponse::new(500, json!({ "message": "Failed to create claim" }))
+                })?
+                .into_iter()
+                .map(|diagnosis| diagnosis.code)
+                .collect::<Vec<_>>();
+
+            let mut codes = clean_diagnosis_codes(&recorded).unwrap_or_default();
+            codes.truncate(MAX_DIAGNOSIS_CODES);
+            codes
+        }
+    };
+
+    let charges = bill_charges(&tenant_db, bill.id).await?;
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start claim transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create claim" }))
+    })?;
+
+    let claim = tenant::entities::insurance_claims::ActiveModel {
+        claim_number: Set(document_number("CLM")),
+        bill_id: Set(bill.id),
+        patient_pid: Set(bill.patient_pid),
+        patient_insurance_pid: Set(patient_insurance_pid),
+        insurance_provider_pid: Set(insurance_provider_pid),
+        status: Set(InsuranceClaimStatus::Draft),
+        diagnosis_codes: Set(diagnosis_codes),
+        gross_amount: Set(bill.total_amount),
+        copay_amount: Set(bill.patient_amount - bill.deductible_amount),
+        deductible_amount: Set(deductible_amount),
+        claimed_amount: Set(insurance_share - deductible_amount),
+        currency: Set(bill.currency.clone()),
+        notes: Set(data.notes.clone()),
+        created_by: Set(user.sub),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to create claim for bill {}: {}", bill.pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to create claim" }))
+    })?;
+
+    if !charges.is_empty() {
+        tenant::entities::insurance_claim_lines::Entity::insert_many(charges.iter().map(
+            |charge| tenant::entities::insurance_claim_lines::ActiveModel {
+                claim_id: Set(claim.id),
+                charge_id: Set(Some(charge.id)),
+                service_code: Set(charge.service_code.clone()),
+                description: Set(charge.description.clone()),
+                quantity: Set(charge.quantity),
+                unit_price: Set(charge.unit_price),
+                total_amount: Set(charge.total_amount),
+                ..Default::default()
+            },
+        ))
+        .exec(&txn)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to add lines to claim {}: {}",
+                claim.claim_number,
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to create claim" }))
+        })?;
+    }
+
+    let lines = claim_lines(&txn, claim.id).await?;
+
+    // Approved pre-authorizations for this care are quoted on the claim.
+    let preauth_numbers = link_preauthorizations(&txn, &claim, &bill, &lines).await?;
+    let claim = if preauth_numbers.is_empty() {
+        claim
+    } else {
+        let claim_number = claim.claim_number.clone();
+        let mut active_model: tenant::entities::insurance_claims::ActiveModel = claim.into();
+        active_model.preauth_numbers = Set(preauth_numbers);
+        active_model.update(&txn).await.map_err(|err| {
+            log::error!(
+                "Failed to add pre-authorizations to {}: {}",
+                claim_number,
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to create claim" }))
+        })?
+    };
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit claim {}: {}", claim.claim_number, err);
+        ApiResponse::new(500, json!({ "message": "Failed to create claim" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "claim": claim_detail_json(&claim, bill.pid, &lines, &[], &[]),
+            "message": "Claim drafted successfully",
+        }),
+    ))
+}
+
+pub async fn show(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let claim = find_claim(&tenant_db, path.into_inner()).await?;
+
+    let bill_pid = tenant::entities::patient_bills::Entity::find_by_id(claim.bill_id)
+        .one(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch bill of claim {}: {}", claim.pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch claim" }))
+        })?
+        .map(|bill| bill.pid)
+        .unwrap_or_default();
+    let lines = claim_lines(&tenant_db, claim.id).await?;
+    let attachments = claim_attachments(&tenant_db, claim.id).await?;
+    let allocations = claim_allocations(&tenant_db, claim.id).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "claim": claim_detail_json(&claim, bill_pid, &lines, &attachments, &allocations),
+            "message": "Claim fetched successfully",
+        }),
+    ))
+}
+
+/// Corrects a draft, or a claim the insurer has queried, before it is
+/// sent again.
+pub async fn edit(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<ClaimData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate(false) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let claim = find_claim(&tenant_db, path.into_inner()).await?;
+
+    if !matches!(
+        claim.status,
+        InsuranceClaimStatus::Draft | InsuranceClaimStatus::Queried
+    ) {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Only draft or queried claims can be edited" }),
+        ));
+    }
+
+    let insurance_share = claim.claimed_amount + claim.deductible_amount;
+    let claim_number = claim.claim_number.clone();
+    let mut active_model: tenant::entities::insurance_claims::ActiveModel = claim.into();
+
+    if let Some(codes) = &data.diagnosis_codes {
+        active_model.diagnosis_codes = Set(clean_diagnosis_codes(codes).unwrap_or_default());
+    }
+
+    if let Some(deductible_amount) = data.deductible_amount {
+        let deductible_amount = deductible_amount.round_dp(2);
+        if deductible_amount >= insurance_share {
+            return Err(ApiResponse::new(
+                400,
+                json!(ValidationError {
+                    errors: HashMap::from([(
+                        "deductible_amount".to_string(),
+                        "Deductible must be less than the insurance share".to_string(),
+                    )]),
+                }),
+            ));
+        }
+        active_model.deductible_amount = Set(deductible_amount);
+        active_model.claimed_amount = Set(insurance_share - deductible_amount);
+    }
+
+    if data.notes.is_some() {
+        active_model.notes = Set(data.notes.clone());
+    }
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let claim = active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!("Failed to update claim {}: {}", claim_number, err);
+        ApiResponse::new(500, json!({ "message": "Failed to update claim" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "claim": claim_json(&claim),
+            "message": "Claim updated successfully",
+        }),
+    ))
+}
+
+/// Deletes a claim that was never sent.
+pub async fn destroy(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let claim = find_claim(&tenant_db, path.into_inner()).await?;
+
+    if claim.status != InsuranceClaimStatus::Draft {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Only draft claims can be deleted" }),
+        ));
+    }
+
+    let claim_number = claim.claim_number.clone();
+    claim.delete(&tenant_db).await.map_err(|err| {
+        log::error!("Failed to delete claim {}: {}", claim_number, err);
+        ApiResponse::new(500, json!({ "message": "Failed to delete claim" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({ "message": "Claim deleted successfully" }),
+    ))
+}
+
+/// Attaches supporting documents, such as lab results or referral letters.
+pub async fn upload_attachments(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    payload: Multipart,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let user = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let claim = find_claim(&tenant_db, path.into_inner()).await?;
+
+    if !matches!(
+        claim.status,
+        InsuranceClaimStatus::Draft | InsuranceClaimStatus::Queried
+    ) {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Documents can only be attached to draft or queried claims" }),
+        ));
+    }
+
+    let form = ClaimAttachmentForm::from_multipart(payload).await?;
+    let existing = claim_attachments(&tenant_db, claim.id).await?;
+    if let Err(err) = form.validate(existing.len()) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let patient = main::entities::patients::Entity::find_by_pid(claim.patient_pid)
+        .one(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch patient {}: {}", claim.patient_pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch patient" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Patient not found" })))?;
+
+    let attachments = store_claim_attachments(
+        &app_state, &req, &tenant_db, &claim, patient.id, form, user.sub,
+    )
+    .await?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "attachments": attachments.iter().map(claim_attachment_json).collect::<Vec<_>>(),
+            "message": "Documents attached successfully",
+        }),
+    ))
+}
+
+pub async fn attachment(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let attachment_pid = path.into_inner();
+
+    let attachment =
+        tenant::entities::insurance_claim_attachments::Entity::find_by_pid(attachment_pid)
+            .one(&tenant_db)
+            .await
+            .map_err(|err| {
+                log::error!(
+                    "Failed to fetch claim attachment {}: {}",
+                    attachment_pid,
+                    err
+                );
+                ApiResponse::new(500, json!({ "message": "Failed to fetch attachment" }))
+            })?
+            .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Attachment not found" })))?;
+
+    let (url, _) = authorized_file_url(
+        &app_state,
+        &req,
+        attachment.file_pid,
+        ATTACHMENT_URL_EXPIRY_SECS,
+    )
+    .await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "url": url,
+            "original_name": attachment.original_name,
+            "content_type": attachment.content_type,
+            "expires_in": ATTACHMENT_URL_EXPIRY_SECS,
+            "message": "Attachment link generated successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ClaimStatusData {
+    /// The insurer's own reference for the claim.
+    pub insurer_reference: Option<String>,
+    /// The insurer's query, or the reason it rejected the claim.
+    pub notes: Option<String>,
+    pub approved_amount: Option<Decimal>,
+    pub lines: Vec<ClaimLineApproval>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ClaimLineApproval {
+    pub line_pid: Option<Uuid>,
+    pub approved_amount: Option<Decimal>,
+}
+
+/// Sends a draft to the insurer, or resends a queried claim once answered.
+pub async fn submit(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<ClaimStatusData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let user = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let claim = find_claim(&tenant_db, path.into_inner()).await?;
+
+    if !matches!(
+        claim.status,
+        InsuranceClaimStatus::Draft | InsuranceClaimStatus::Queried
+    ) {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Only draft or queried claims can be submitted" }),
+        ));
+    }
+
+    if claim.diagnosis_codes.is_empty() {
+        return Err(ApiResponse::new(
+            400,
+            json!(ValidationError {
+                errors: HashMap::from([(
+                    "diagnosis_codes".to_string(),
+                    "Add at least one diagnosis code before submitting".to_string(),
+                )]),
+            }),
+        ));
+    }
+
+    let now = Utc::now().naive_utc();
+    let claim_number = claim.claim_number.clone();
+    let first_submission = claim.submitted_at.is_none();
+    let mut active_model: tenant::entities::insurance_claims::ActiveModel = claim.into();
+    active_model.status = Set(InsuranceClaimStatus::Submitted);
+    // Aging runs from the first submission, not from answers to queries.
+    if first_submission {
+        active_model.submitted_at = Set(Some(now));
+        active_model.submitted_by = Set(Some(user.sub));
+    }
+    if let Some(reference) = data.insurer_reference.as_deref().map(str::trim)
+        && !reference.is_empty()
+    {
+        active_model.insurer_reference = Set(Some(reference.to_string()));
+    }
+    active_model.updated_at = Set(now);
+
+    let claim = active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!("Failed to submit claim {}: {}", claim_number, err);
+        ApiResponse::new(500, json!({ "message": "Failed to submit claim" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "claim": claim_json(&claim),
+            "message": "Claim submitted successfully",
+        }),
+    ))
+}
+
+/// Records the insurer asking for more information on a submitted claim.
+pub async fn query(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<ClaimStatusData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let notes = required_notes(&data, "The insurer's query is required")?;
+
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let claim = find_claim(&tenant_db, path.into_inner()).await?;
+
+    if claim.status != InsuranceClaimStatus::Submitted {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Only submitted claims can be queried" }),
+        ));
+    }
+
+    let now = Utc::now().naive_utc();
+    let claim_number = claim.claim_number.clone();
+    let mut active_model: tenant::entities::insurance_claims::ActiveModel = claim.into();
+    active_model.status = Set(InsuranceClaimStatus::Queried);
+    active_model.query_notes = Set(Some(notes));
+    active_model.queried_at = Set(Some(now));
+    active_model.updated_at = Set(now);
+
+    let claim = active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!("Failed to query claim {}: {}", claim_number, err);
+        ApiResponse::new(500, json!({ "message": "Failed to update claim" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "claim": claim_json(&claim),
+            "message": "Claim marked as queried",
+        }),
+    ))
+}
+
+/// Records the insurer's approval. The approved amount defaults to the
+/// sum of line approvals when lines are given, otherwise to the amount
+/// claimed. The claim's deductible is posted to the patient's card.
+pub async fn approve(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<ClaimStatusData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let claim = find_claim(&tenant_db, path.into_inner()).await?;
+
+    if !matches!(
+        claim.status,
+        InsuranceClaimStatus::Submitted | InsuranceClaimStatus::Queried
+    ) {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Only submitted or queried claims can be approved" }),
+        ));
+    }
+
+    let lines = claim_lines(&tenant_db, claim.id).await?;
+    let mut line_approvals = HashMap::new();
+    for approval in &data.lines {
+        let line = lines
+            .iter()
+            .find(|line| Some(line.pid) == approval.line_pid)
+            .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Claim line not found" })))?;
+
+        match approval.approved_amount {
+            Some(amount) if amount >= Decimal::ZERO && amount <= line.total_amount => {
+                line_approvals.insert(line.id, amount.round_dp(2));
+            }
+            _ => {
+                return Err(ApiResponse::new(
+                    400,
+                    json!(ValidationError {
+                        errors: HashMap::from([(
+                            "lines".to_string(),
+                            format!(
+                                "Approved amount for {} must be between 0 and {}",
+                                line.description, line.total_amount
+                            ),
+                        )]),
+                    }),
+                ));
+            }
+        }
+    }
+
+    let approved_amount = match data.approved_amount {
+        Some(amount) => amount.round_dp(2),
+        None if !line_approvals.is_empty() => line_approvals.values().copied().sum(),
+        None => claim.claimed_amount,
+    };
+
+    if approved_amount <= Decimal::ZERO || approved_amount > claim.claimed_amount {
+        return Err(ApiResponse::new(
+            400,
+            json!(ValidationError {
+                errors: HashMap::from([(
+                    "approved_amount".to_string(),
+                    format!(
+                        "Approved amount must be more than 0 and at most {}; reject the claim instead of approving nothing",
+                        claim.claimed_amount
+                    ),
+                )]),
+            }),
+        ));
+    }
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start claim transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to approve claim" }))
+    })?;
+
+    for (line_id, amount) in line_approvals {
+        tenant::entities::insurance_claim_lines::ActiveModel {
+            id: Set(line_id),
+            approved_amount: Set(Some(amount)),
+            updated_at: Set(Utc::now().naive_utc()),
+            ..Default::default()
+        }
+        .update(&txn)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to approve claim line {}: {}", line_id, err);
+            ApiResponse::new(500, json!({ "message": "Failed to approve claim" }))
+        })?;
+    }
+
+    let now = Utc::now().naive_utc();
+    let claim_number = claim.claim_number.clone();
+    let mut active_model: tenant::entities::insurance_claims::ActiveModel = claim.into();
+    active_model.status = Set(InsuranceClaimStatus::Approved);
+    active_model.approved_amount = Set(Some(approved_amount));
+    active_model.approved_at = Set(Some(now));
+    if let Some(reference) = data.insurer_reference.as_deref().map(str::trim)
+        && !reference.is_empty()
+    {
+        active_model.insurer_reference = Set(Some(reference.to_string()));
+    }
+    active_model.updated_at = Set(now);
+
+    let claim = active_model.update(&txn).await.map_err(|err| {
+        log::error!("Failed to approve claim {}: {}", claim_number, err);
+        ApiResponse::new(500, json!({ "message": "Failed to approve claim" }))
+    })?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit claim {}: {}", claim_number, err);
+        ApiResponse::new(500, json!({ "message": "Failed to approve claim" }))
+    })?;
+
+    // The approval stands either way; a failed posting is retried by the
+    // accumulator posting job.
+    if let Err(err) = post_claim_deductible(&tenant_db, &app_state.main_db, &claim).await {
+        log::warn!(
+            "Deductible of approved claim {} left for retry: {}",
+            claim.claim_number,
+            err
+        );
+    }
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "claim": claim_json(&claim),
+            "message": "Claim approved successfully",
+        }),
+    ))
+}
+
+/// Records the insurer turning the claim down. Claims the insurer has
+/// already paid on cannot be rejected. Rejecting an approved claim takes its
+/// deductible back off the patient's card. Its pre-authorizations are freed
+/// for a corrected claim.
+pub async fn reject(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<ClaimStatusData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let reason = required_notes(&data, "Rejection reason is required")?;
+
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let claim = find_claim(&tenant_db, path.into_inner()).await?;
+
+    if !matches!(
+        claim.status,
+        InsuranceClaimStatus::Submitted
+            | InsuranceClaimStatus::Queried
+            | InsuranceClaimStatus::Approved
+    ) || claim.paid_amount > Decimal::ZERO
+    {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Only submitted, queried or unpaid approved claims can be rejected" }),
+        ));
+    }
+
+    let now = Utc::now().naive_utc();
+    let claim_number = claim.claim_number.clone();
+    let mut active_model: tenant::entities::insurance_claims::ActiveModel = claim.into();
+    active_model.status = Set(InsuranceClaimStatus::Rejected);
+    active_model.rejection_reason = Set(Some(reason));
+    active_model.rejected_at = Set(Some(now));
+    active_model.updated_at = Set(now);
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start claim transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to update claim" }))
+    })?;
+
+    let claim = active_model.update(&txn).await.map_err(|err| {
+        log::error!("Failed to reject claim {}: {}", claim_number, err);
+        ApiResponse::new(500, json!({ "message": "Failed to update claim" }))
+    })?;
+    unlink_preauthorizations(&txn, &claim).await?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit claim {}: {}", claim_number, err);
+        ApiResponse::new(500, json!({ "message": "Failed to update claim" }))
+    })?;
+
+    // Reverses the deductible an approval posted; retried by the accumulator
+    // posting job if the card cannot be updated now.
+    if let Err(err) = post_claim_deductible(&tenant_db, &app_state.main_db, &claim).await {
+        log::warn!(
+            "Deductible reversal of rejected claim {} left for retry: {}",
+            claim.claim_number,
+            err
+        );
+    }
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "claim": claim_json(&claim),
+            "message": "Claim marked as rejected",
+        }),
+    ))
+}
+
+/// Checks a patient's card with the insurer at the front desk, before
+/// the visit is billed to it.
+pub async fn verify_eligibility(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+) -> Result<ApiResponse, ApiResponse> {
+    verify_patient_insurance(&app_state, path.into_inner(), None).await
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AgingParams {
+    /// Defaults to today.
+    pub as_of: Option<NaiveDate>,
+}
+
+/// Outstanding claims by insurer, bucketed by days since submission.
+pub async fn aging(
+    app_state: web::Data<AppState>,
+    query: web::Query<AgingParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let as_of = query.as_of.unwrap_or_else(|| Utc::now().date_naive());
+
+    let claims = tenant::entities::insurance_claims::Entity::find()
+        .filter(
+            tenant::entities::insurance_claims::Column::Status.is_in(OUTSTANDING_CLAIM_STATUSES),
+        )
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch outstanding claims: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to build aging report" }))
+        })?;
+
+    let provider_pids = claims
+        .iter()
+        .map(|claim| claim.insurance_provider_pid)
+        .collect::<Vec<_>>();
+    let providers: HashMap<Uuid, String> = main::entities::insurance_providers::Entity::find()
+        .filter(main::entities::insurance_providers::Column::Pid.is_in(provider_pids))
+        .all(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch insurance providers: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to build aging report" }))
+        })?
+        .into_iter()
+        .map(|provider| (provider.pid, provider.name))
+        .collect();
+
+    // (currency, insurer) so totals in different currencies are never added.
+    let mut rows: BTreeMap<(String, Uuid), (AgingBuckets, u64)> = BTreeMap::new();
+    let mut totals: BTreeMap<String, AgingBuckets> = BTreeMap::new();
+
+    for claim in &claims {
+        let outstanding = claim_outstanding(claim);
+        if outstanding <= Decimal::ZERO {
+            continue;
+        }
+        let bucket = aging_bucket(days_outstanding(claim, as_of));
+
+        let (buckets, count) = rows
+            .entry((claim.currency.clone(), claim.insurance_provider_pid))
+            .or_insert_with(|| (empty_aging_buckets(), 0));
+        *buckets.entry(bucket).or_default() += outstanding;
+        *count += 1;
+
+        *totals
+            .entry(claim.currency.clone())
+            .or_insert_with(empty_aging_buckets)
+            .entry(bucket)
+            .or_default() += outstanding;
+    }
+
+    let insurers = rows
+        .iter()
+        .map(|((currency, provider_pid), (buckets, count))| {
+            json!({
+                "insurance_provider_pid": provider_pid,
+                "insurance_provider_name": providers.get(provider_pid),
+                "currency": currency,
+                "claim_count": count,
+                "buckets": buckets,
+                "total_outstanding": buckets.values().copied().sum::<Decimal>(),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let totals = totals
+        .iter()
+        .map(|(currency, buckets)| {
+            json!({
+                "currency": currency,
+                "buckets": buckets,
+                "total_outstanding": buckets.values().copied().sum::<Decimal>(),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "as_of": as_of,
+            "insurers": insurers,
+            "totals": totals,
+            "message": "Claims aging report generated successfully",
+        }),
+    ))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct RemittanceParams {
+    pub insurance_provider_pid: Option<Uuid>,
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+}
+
+pub async fn index_remittances(
+    app_state: web::Data<AppState>,
+    query: web::Query<RemittanceParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let mut stmt = tenant::entities::insurance_remittances::Entity::find();
+
+    if let Some(provider_pid) = query.insurance_provider_pid {
+        stmt = stmt.filter(
+            tenant::entities::insurance_remittances::Column::InsuranceProviderPid.eq(provider_pid),
+        );
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let paginator = stmt
+        .order_by_desc(tenant::entities::insurance_remittances::Column::PaymentDate)
+        .paginate(&tenant_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
+
+    let remittances = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "remittances": remittances.iter().map(remittance_json).collect::<Vec<_>>(),
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "Remittances fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct RemittanceData {
+    pub insurance_provider_pid: Option<Uuid>,
+    /// The insurer's payment or EFT reference.
+    pub reference: Option<String>,
+    pub payment_date: Option<NaiveDate>,
+    pub amount: Option<Decimal>,
+    pub currency: Option<String>,
+    pub notes: Option<String>,
+    pub allocations: Vec<AllocationData>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct AllocationData {
+    pub claim_pid: Option<Uuid>,
+    pub amount: Option<Decimal>,
+}
+
+impl RemittanceData {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if self.insurance_provider_pid.is_none() {
+            errors.insert(
+                "insurance_provider_pid".to_string(),
+                "Insurer is required".to_string(),
+            );
+        }
+
+        match self.reference.as_deref().map(str::trim) {
+            None | Some("") => {
+                errors.insert(
+                    "reference".to_string(),
+                    "Payment reference is required".to_string(),
+                );
+            }
+            Some(reference) if reference.chars().count() > 100 => {
+                errors.insert(
+                    "reference".to_string(),
+                    "Payment reference must be at most 100 characters".to_string(),
+                );
+            }
+            _ => {}
+        }
+
+        if self.payment_date.is_none() {
+            errors.insert(
+                "payment_date".to_string(),
+                "Payment date is required".to_string(),
+            );
+        }
+
+        if self.amount.is_none_or(|amount| amount <= Decimal::ZERO) {
+            errors.insert(
+                "amount".to_string(),
+                "Amount must be greater than zero".to_string(),
+            );
+        }
+
+        if let Some(error) = allocations_error(&self.allocations) {
+            errors.insert("allocations".to_string(), error);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+/// Records a payment received from an insurer and, optionally, spreads it
+/// over that insurer's approved claims.
+pub async fn create_remittance(
+    app_state: web::Data<AppState>,
+    data: web::Json<RemittanceData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate() {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let user = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let provider_pid = data.insurance_provider_pid.unwrap_or_default();
+
+    main::entities::insurance_providers::Entity::find_by_pid(provider_pid)
+        .filter(main::entities::insurance_providers::Column::DeletedAt.is_null())
+        .one(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to fetch insurance provider {}: {}",
+                provider_pid,
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to fetch insurer" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Insurer not found" })))?;
+
+    let currency = match &data.currency {
+        Some(currency) => currency.trim().to_uppercase(),
+        None => facility_currency(&req, &app_state).await?,
+    };
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start remittance transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to record remittance" }))
+    })?;
+
+    let remittance = tenant::entities::insurance_remittances::ActiveModel {
+        remittance_number: Set(document_number("REM")),
+        insurance_provider_pid: Set(provider_pid),
+        reference: Set(data
+            .reference
+            .clone()
+            .unwrap_or_default()
+            .trim()
+            .to_string()),
+        payment_date: Set(data.payment_date.unwrap_or_default()),
+        amount: Set(data.amount.unwrap_or_default().round_dp(2)),
+        currency: Set(currency),
+        notes: Set(data.notes.clone()),
+        received_by: Set(user.sub),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to record remittance: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to record remittance" }))
+    })?;
+
+    let (remittance, claims) = allocate(&txn, remittance, &data.allocations, user.sub).await?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit remittance: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to record remittance" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "remittance": remittance_json(&remittance),
+            "claims": claims.iter().map(claim_json).collect::<Vec<_>>(),
+            "message": "Remittance recorded successfully",
+        }),
+    ))
+}
+
+pub async fn show_remittance(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let remittance = find_remittance(&tenant_db, path.into_inner()).await?;
+
+    let allocations = tenant::entities::remittance_allocations::Entity::find()
+        .filter(tenant::entities::remittance_allocations::Column::RemittanceId.eq(remittance.id))
+        .find_also_related(tenant::entities::insurance_claims::Entity)
+        .order_by_asc(tenant::entities::remittance_allocations::Column::CreatedAt)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch allocations of {}: {}", remittance.pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch remittance" }))
+        })?;
+
+    let mut value = remittance_json(&remittance);
+    value["allocations"] = json!(
+        allocations
+            .iter()
+            .map(|(allocation, claim)| json!({
+                "pid": allocation.pid,
+                "amount": allocation.amount,
+                "claim_pid": claim.as_ref().map(|c| c.pid),
+                "claim_number": claim.as_ref().map(|c| c.claim_number.clone()),
+                "allocated_by": allocation.allocated_by,
+                "created_at": allocation.created_at,
+            }))
+            .collect::<Vec<_>>()
+    );
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "remittance": value,
+            "message": "Remittance fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct AllocateData {
+    pub allocations: Vec<AllocationData>,
+}
+
+/// Allocates what is left of a remittance to more claims.
+pub async fn allocate_remittance(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<AllocateData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let error = if data.allocations.is_empty() {
+        Some("Add at least one allocation".to_string())
+    } else {
+        allocations_error(&data.allocations)
+    };
+    if let Some(error) = error {
+        return Err(ApiResponse::new(
+            400,
+            json!(ValidationError {
+                errors: HashMap::from([("allocations".to_string(), error)]),
+            }),
+        ));
+    }
+
+    let user = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let remittance_pid = path.into_inner();
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start remittance transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to allocate remittance" }))
+    })?;
+
+    let remittance = tenant::entities::insurance_remittances::Entity::find_by_pid(remittance_pid)
+        .lock_exclusive()
+        .one(&txn)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch remittance {}: {}", remittance_pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to allocate remittance" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Remittance not found" })))?;
+
+    let (remittance, claims) = allocate(&txn, remittance, &data.allocations, user.sub).await?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit remittance {}: {}", remittance_pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to allocate remittance" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "remittance": remittance_json(&remittance),
+            "claims": claims.iter().map(claim_json).collect::<Vec<_>>(),
+            "message": "Remittance allocated successfully",
+        }),
+    ))
+}
+
+async fn find_remittance(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+) -> Result<tenant::entities::insurance_remittances::Model, ApiResponse> {
+    tenant::entities::insurance_remittances::Entity::find_by_pid(pid)
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch remittance {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch remittance" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Remittance not found" })))
+}
+
+/// Applies allocations to the insurer's approved claims. Each allocation is
+/// capped at what the claim still has outstanding and together they cannot
+/// exceed what is left of the remittance.
+async fn allocate(
+    txn: &DatabaseTransaction,
+    remittance: tenant::entities::insurance_remittances::Model,
+    allocations: &[AllocationData],
+    allocated_by: Uuid,
+) -> Result<
+    (
+        tenant::entities::insurance_remittances::Model,
+        Vec<tenant::entities::insurance_claims::Model>,
+    ),
+    ApiResponse,
+> {
+    if allocations.is_empty() {
+        return Ok((remittance, Vec::new()));
+    }
+
+    let requested: Decimal = allocations
+        .iter()
+        .map(|allocation| allocation.amount.unwrap_or_default().round_dp(2))
+        .sum();
+    let unallocated = remittance.amount - remittance.allocated_amount;
+    if requested > unallocated {
+        return Err(ApiResponse::new(
+            400,
+            json!(ValidationError {
+                errors: HashMap::from([(
+                    "allocations".to_string(),
+                    format!("Only {} of the remittance is left to allocate", unallocated),
+                )]),
+            }),
+        ));
+    }
+
+    let mut claims = Vec::with_capacity(allocations.len());
+    for allocation in allocations {
+        let claim_pid = allocation.claim_pid.unwrap_or_default();
+        let amount = allocation.amount.unwrap_or_default().round_dp(2);
+
+        let claim = tenant::entities::insurance_claims::Entity::find_by_pid(claim_pid)
+            .lock_exclusive()
+            .one(txn)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to fetch claim {}: {}", claim_pid, err);
+                ApiResponse::new(500, json!({ "message": "Failed to allocate remittance" }))
+            })?
+            .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Claim not found" })))?;
+
+        if claim.insurance_provider_pid != remittance.insurance_provider_pid
+            || claim.currency != remittance.currency
+        {
+            return Err(ApiResponse::new(
+                400,
+                json!({
+                    "message": format!(
+                        "{} is not a {} claim with this insurer",
+                        claim.claim_number, remittance.currency
+                    ),
+                }),
+            ));
+        }
+
+        if !matches!(
+            claim.status,
+            InsuranceClaimStatus::Approved | InsuranceClaimStatus::PartiallyPaid
+        ) {
+            return Err(ApiResponse::new(
+                409,
+                json!({
+                    "message": format!("{} has not been approved", claim.claim_number),
+                }),
+            ));
+        }
+
+        let outstanding = claim_outstanding(&claim);
+        if amount > outstanding {
+            return Err(ApiResponse::new(
+                400,
+                json!({
+                    "message": format!(
+                        "{} has only {} outstanding",
+                        claim.claim_number, outstanding
+                    ),
+                }),
+            ));
+        }
+
+        tenant::entities::remittance_allocations::ActiveModel {
+            remittance_id: Set(remittance.id),
+            claim_id: Set(claim.id),
+            amount: Set(amount),
+            allocated_by: Set(allocated_by),
+            ..Default::default()
+        }
+        .insert(txn)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to allocate to claim {}: {}",
+                claim.claim_number,
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to allocate remittance" }))
+        })?;
+
+        claims.push(apply_remittance(txn, claim, amount).await?);
+    }
+
+    let remittance_number = remittance.remittance_number.clone();
+    let allocated_amount = remittance.allocated_amount + requested;
+    let mut active_model: tenant::entities::insurance_remittances::ActiveModel = remittance.into();
+    active_model.allocated_amount = Set(allocated_amount);
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let remittance = active_model.update(txn).await.map_err(|err| {
+        log::error!("Failed to update remittance {}: {}", remittance_number, err);
+        ApiResponse::new(500, json!({ "message": "Failed to allocate remittance" }))
+    })?;
+
+    Ok((remittance, claims))
+}
+
+fn allocations_error(allocations: &[AllocationData]) -> Option<String> {
+    let mut seen = Vec::with_capacity(allocations.len());
+
+    for allocation in allocations {
+        let Some(claim_pid) = allocation.claim_pid else {
+            return Some("Each allocation needs a claim".to_string());
+        };
+        if allocation
+            .amount
+            .is_none_or(|amount| amount <= Decimal::ZERO)
+        {
+            return Some("Each allocation needs an amount greater than zero".to_string());
+        }
+        if seen.contains(&claim_pid) {
+            return Some("A claim can only be allocated once per request".to_string());
+        }
+        seen.push(claim_pid);
+    }
+
+    None
+}
+
+fn required_notes(data: &ClaimStatusData, message: &str) -> Result<String, ApiResponse> {
+    data.notes
+        .as_deref()
+        .map(str::trim)
+        .filter(|notes| !notes.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| {
+            ApiResponse::new(
+                400,
+                json!(ValidationError {
+                    errors: HashMap::from([("notes".to_string(), message.to_string())]),
+                }),
+            )
+        })
+}
@@ -0,0 +1,499 @@
+use std::collections::HashMap;
+
+use actix_web::{HttpRequest, web};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        main,
+        tenant::{
+            self,
+            entities::sea_orm_active_enums::{
+                LabOrderStatus, PatientChargeStatus, ServiceCategory,
+            },
+            migrations::sea_orm::{
+                ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait,
+                QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait,
+            },
+        },
+    },
+    handlers::services::{
+        patient_charges::{CHARGE_SOURCE_LAB_ORDER, patient_charge_json},
+        pricing::{
+            charge_service, facility_currency, find_service, payer_price_list, service_price,
+        },
+    },
+    utils::{
+        api_response::ApiResponse,
+        app_state::AppState,
+        documents::document_number,
+        jwt::{get_logged_in_user_claims, get_tenant_db},
+        validator_error::ValidationError,
+    },
+};
+
+#[derive(Deserialize, Debug)]
+pub struct LabOrderParams {
+    pub patient_pid: Option<Uuid>,
+    pub status: Option<LabOrderStatus>,
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+}
+
+pub async fn index(
+    app_state: web::Data<AppState>,
+    query: web::Query<LabOrderParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let mut stmt = tenant::entities::lab_orders::Entity::find();
+
+    if let Some(patient_pid) = query.patient_pid {
+        stmt = stmt.filter(tenant::entities::lab_orders::Column::PatientPid.eq(patient_pid));
+    }
+
+    if let Some(status) = &query.status {
+        stmt = stmt.filter(tenant::entities::lab_orders::Column::Status.eq(status.clone()));
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let paginator = stmt
+        .find_also_related(tenant::entities::billable_services::Entity)
+        .order_by_desc(tenant::entities::lab_orders::Column::CreatedAt)
+        .paginate(&tenant_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
+
+    let orders = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "lab_orders": orders
+                .iter()
+                .map(|(order, service)| lab_order_json(order, service.as_ref(), None))
+                .collect::<Vec<_>>(),
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "Lab orders fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct LabOrderData {
+    pub patient_pid: Option<Uuid>,
+    /// A lab test from the service catalogue.
+    pub service_pid: Option<Uuid>,
+    pub encounter_pid: Option<Uuid>,
+    /// Prices on this list instead of the patient's own.
+    pub price_list_pid: Option<Uuid>,
+    pub clinical_notes: Option<String>,
+}
+
+impl LabOrderData {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if self.patient_pid.is_none() {
+            errors.insert("patient_pid".to_string(), "Patient is required".to_string());
+        }
+
+        if self.service_pid.is_none() {
+            errors.insert(
+                "service_pid".to_string(),
+                "Lab test is required".to_string(),
+            );
+        }
+
+        if self
+            .clinical_notes
+            .as_ref()
+            .is_some_and(|n| n.chars().count() > 2000)
+        {
+            errors.insert(
+                "clinical_notes".to_string(),
+                "Clinical notes must be at most 2000 characters".to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+/// Orders a lab test and charges it to the patient at their payer's price.
+/// Tests without a price cannot be ordered.
+pub async fn create(
+    app_state: web::Data<AppState>,
+    data: web::Json<LabOrderData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate() {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let patient_pid = data.patient_pid.unwrap_or_default();
+
+    main::entities::patients::Entity::find_by_pid(patient_pid)
+        .filter(main::entities::patients::Column::DeletedAt.is_null())
+        .one(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch patient {}: {}", patient_pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch patient" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Patient not found" })))?;
+
+    let service = find_service(&tenant_db, data.service_pid.unwrap_or_default()).await?;
+    if service.category != ServiceCategory::LabTest || !service.is_active {
+        return Err(ApiResponse::new(
+            400,
+            json!(ValidationError {
+                errors: HashMap::from([(
+                    "service_pid".to_string(),
+                    "Service is not an active lab test".to_string(),
+                )]),
+            }),
+        ));
+    }
+
+    let encounter_id = match data.encounter_pid {
+        Some(encounter_pid) => {
+            let encounter = tenant::entities::encounters::Entity::find_by_pid(encounter_pid)
+                .one(&tenant_db)
+                .await
+                .map_err(|err| {
+                    log::error!("Failed to fetch encounter {}: {}", encounter_pid, err);
+                    ApiResponse::new(500, json!({ "message": "Failed to fetch encounter" }))
+                })?
+                .filter(|encounter| encounter.patient_pid == patient_pid)
+                .ok_or_else(|| {
+                    ApiResponse::new(404, json!({ "message": "Encounter not found" }))
+                })?;
+            Some(encounter.id)
+        }
+        None => None,
+    };
+
+    let currency = facility_currency(&req, &app_state).await?;
+    let price_list = payer_price_list(
+        &tenant_db,
+        &app_state.main_db,
+        patient_pid,
+        data.price_list_pid,
+    )
+    .await?;
+    let price = service_price(
+        &tenant_db,
+        &service,
+        price_list.as_ref(),
+        &currency,
+        Utc::now().date_naive(),
+    )
+    .await?
+    .ok_or_else(|| {
+        ApiResponse::new(
+            409,
+            json!({ "message": format!("{} has no price for this payer", service.name) }),
+        )
+    })?;
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start lab order transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create lab order" }))
+    })?;
+
+    let order = tenant::entities::lab_orders::ActiveModel {
+        order_number: Set(document_number("LAB")),
+        patient_pid: Set(patient_pid),
+        encounter_id: Set(encounter_id),
+        service_id: Set(service.id),
+        status: Set(LabOrderStatus::Ordered),
+        clinical_notes: Set(data.clinical_notes.clone()),
+        ordered_by: Set(claims.sub),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to create lab order: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create lab order" }))
+    })?;
+
+    let charge = charge_service(
+        &txn,
+        &price,
+        patient_pid,
+        CHARGE_SOURCE_LAB_ORDER,
+        order.pid,
+        1,
+        Some(claims.sub),
+    )
+    .await?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit lab order {}: {}", order.order_number, err);
+        ApiResponse::new(500, json!({ "message": "Failed to create lab order" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "lab_order": lab_order_json(&order, Some(&service), Some(&charge)),
+            "message": "Lab order created successfully",
+        }),
+    ))
+}
+
+pub async fn show(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let order = find_lab_order(&tenant_db, path.into_inner()).await?;
+
+    let service = tenant::entities::billable_services::Entity::find_by_id(order.service_id)
+        .one(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch service of {}: {}", order.order_number, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch lab order" }))
+        })?;
+    let charge = find_order_charge(&tenant_db, order.pid).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "lab_order": lab_order_json(&order, service.as_ref(), charge.as_ref()),
+            "message": "Lab order fetched successfully",
+        }),
+    ))
+}
+
+pub async fn complete(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let order = find_lab_order(&tenant_db, path.into_inner()).await?;
+
+    if order.status != LabOrderStatus::Ordered {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Only ordered lab tests can be completed" }),
+        ));
+    }
+
+    let order_number = order.order_number.clone();
+    let mut active_model: tenant::entities::lab_orders::ActiveModel = order.into();
+    active_model.status = Set(LabOrderStatus::Completed);
+    active_model.completed_at = Set(Some(Utc::now().naive_utc()));
+    active_model.completed_by = Set(Some(claims.sub));
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let order = active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!("Failed to complete lab order {}: {}", order_number, err);
+        ApiResponse::new(500, json!({ "message": "Failed to complete lab order" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "lab_order": lab_order_json(&order, None, None),
+            "message": "Lab order completed successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct CancelData {
+    pub reason: Option<String>,
+}
+
+/// Cancels a test that has not been done and voids its charge. Once the
+/// charge is on a bill the order can no longer be cancelled here.
+pub async fn cancel(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<CancelData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let reason = data
+        .reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|r| !r.is_empty())
+        .ok_or_else(|| {
+            ApiResponse::new(
+                400,
+                json!(ValidationError {
+                    errors: HashMap::from([(
+                        "reason".to_string(),
+                        "Reason is required".to_string(),
+                    )]),
+                }),
+            )
+        })?
+        .to_string();
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let order = find_lab_order(&tenant_db, path.into_inner()).await?;
+
+    if order.status != LabOrderStatus::Ordered {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Only ordered lab tests can be cancelled" }),
+        ));
+    }
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start lab order transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to cancel lab order" }))
+    })?;
+
+    let charge = tenant::entities::patient_charges::Entity::find()
+        .filter(tenant::entities::patient_charges::Column::SourceType.eq(CHARGE_SOURCE_LAB_ORDER))
+        .filter(tenant::entities::patient_charges::Column::SourcePid.eq(order.pid))
+        .lock_exclusive()
+        .one(&txn)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch charge of {}: {}", order.order_number, err);
+            ApiResponse::new(500, json!({ "message": "Failed to cancel lab order" }))
+        })?;
+
+    if let Some(charge) = charge {
+        match charge.status {
+            PatientChargeStatus::Billed => {
+                return Err(ApiResponse::new(
+                    409,
+                    json!({ "message": "The test has already been billed" }),
+                ));
+            }
+            PatientChargeStatus::Pending => {
+                let mut active_model: tenant::entities::patient_charges::ActiveModel =
+                    charge.into();
+                active_model.status = Set(PatientChargeStatus::Void);
+                active_model.updated_at = Set(Utc::now().naive_utc());
+                active_model.update(&txn).await.map_err(|err| {
+                    log::error!("Failed to void charge of {}: {}", order.order_number, err);
+                    ApiResponse::new(500, json!({ "message": "Failed to cancel lab order" }))
+                })?;
+            }
+            PatientChargeStatus::Void => {}
+        }
+    }
+
+    let order_number = order.order_number.clone();
+    let mut active_model: tenant::entities::lab_orders::ActiveModel = order.into();
+    active_model.status = Set(LabOrderStatus::Cancelled);
+    active_model.cancelled_at = Set(Some(Utc::now().naive_utc()));
+    active_model.cancelled_by = Set(Some(claims.sub));
+    active_model.cancel_reason = Set(Some(reason));
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let order = active_model.update(&txn).await.map_err(|err| {
+        log::error!("Failed to cancel lab order {}: {}", order_number, err);
+        ApiResponse::new(500, json!({ "message": "Failed to cancel lab order" }))
+    })?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit lab order {}: {}", order_number, err);
+        ApiResponse::new(500, json!({ "message": "Failed to cancel lab order" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "lab_order": lab_order_json(&order, None, None),
+            "message": "Lab order cancelled successfully",
+        }),
+    ))
+}
+
+async fn find_lab_order(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+) -> Result<tenant::entities::lab_orders::Model, ApiResponse> {
+    tenant::entities::lab_orders::Entity::find_by_pid(pid)
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch lab order {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch lab order" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Lab order not found" })))
+}
+
+async fn find_order_charge(
+    tenant_db: &DatabaseConnection,
+    order_pid: Uuid,
+) -> Result<Option<tenant::entities::patient_charges::Model>, ApiResponse> {
+    tenant::entities::patient_charges::Entity::find()
+        .filter(tenant::entities::patient_charges::Column::SourceType.eq(CHARGE_SOURCE_LAB_ORDER))
+        .filter(tenant::entities::patient_charges::Column::SourcePid.eq(order_pid))
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch charge of lab order {}: {}", order_pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch lab order" }))
+        })
+}
+
+fn lab_order_json(
+    order: &tenant::entities::lab_orders::Model,
+    service: Option<&tenant::entities::billable_services::Model>,
+    charge: Option<&tenant::entities::patient_charges::Model>,
+) -> Value {
+    json!({
+        "pid": order.pid,
+        "order_number": order.order_number,
+        "patient_pid": order.patient_pid,
+        "service": service.map(|service| json!({
+            "pid": service.pid,
+            "code": service.code,
+            "name": service.name,
+        })),
+        "status": order.status,
+        "clinical_notes": order.clinical_notes,
+        "ordered_by": order.ordered_by,
+        "charge": charge.map(patient_charge_json),
+        "completed_at": order.completed_at,
+        "completed_by": order.completed_by,
+        "cancelled_at": order.cancelled_at,
+        "cancelled_by": order.cancelled_by,
+        "cancel_reason": order.cancel_reason,
+        "created_at": order.created_at,
+        "updated_at": order.updated_at,
+    })
+}
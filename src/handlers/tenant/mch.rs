@@ -0,0 +1,1081 @@
+use std::collections::HashMap;
+
+use actix_web::{HttpRequest, web};
+use chrono::{Duration, NaiveDate, Utc};
+use rust_decimal::{
+    Decimal,
+    prelude::{FromPrimitive, ToPrimitive},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        main::{
+            self,
+            entities::sea_orm_active_enums::{Gender, InsuranceDependentRelationship},
+        },
+        tenant::{
+            self,
+            entities::sea_orm_active_enums::AncProfileStatus,
+            migrations::sea_orm::{
+                ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait,
+                QueryFilter, QueryOrder, Set,
+            },
+        },
+    },
+    handlers::services::patients::find_patient,
+    utils::{
+        api_response::ApiResponse,
+        app_state::AppState,
+        growth::{growth_flags, nutrition_status, who_z_scores},
+        jwt::{get_logged_in_user_claims, get_tenant_db},
+        pagination::PaginationParams,
+        validator_error::ValidationError,
+    },
+};
+
+/// WHO 2016 ANC model: eight contacts, by gestational week.
+const ANC_CONTACT_WEEKS: [i64; 8] = [12, 20, 26, 30, 34, 36, 38, 40];
+/// A contact counts as missed once this many days pass after it was due.
+const ANC_MISSED_GRACE_DAYS: i64 = 14;
+const PREGNANCY_DAYS: i64 = 280;
+
+#[derive(Serialize, Debug)]
+struct ExpectedContact {
+    contact_number: usize,
+    gestation_weeks: i64,
+    due_date: NaiveDate,
+    status: &'static str,
+    visit_pid: Option<Uuid>,
+}
+
+/// Lays the eight-contact schedule over the visits recorded so far. A visit
+/// satisfies a contact when its gestational age falls after the previous
+/// contact and no later than two weeks past this one.
+fn expected_contacts(
+    profile: &tenant::entities::anc_profiles::Model,
+    visits: &[tenant::entities::anc_visits::Model],
+    today: NaiveDate,
+) -> Vec<ExpectedContact> {
+    let mut previous_week = 0;
+
+    ANC_CONTACT_WEEKS
+        .iter()
+        .enumerate()
+        .map(|(index, &week)| {
+            let due_date = profile.lmp + Duration::weeks(week);
+            let visit = visits.iter().find(|visit| {
+                let visit_week = visit.gestation_weeks as i64;
+                visit_week > previous_week && visit_week <= week + 2
+            });
+            previous_week = week + 2;
+
+            let status = if visit.is_some() {
+                "attended"
+            } else if profile.status != AncProfileStatus::Active {
+                "not_recorded"
+            } else if today > due_date + Duration::days(ANC_MISSED_GRACE_DAYS) {
+                "missed"
+            } else if today >= due_date - Duration::days(7) {
+                "due"
+            } else {
+                "upcoming"
+            };
+
+            ExpectedContact {
+                contact_number: index + 1,
+                gestation_weeks: week,
+                due_date,
+                status,
+                visit_pid: visit.map(|v| v.pid),
+            }
+        })
+        .collect()
+}
+
+/// Clinical warning signs picked up from a single visit's vitals and labs.
+fn anc_visit_flags(visit: &tenant::entities::anc_visits::Model) -> Vec<&'static str> {
+    let mut flags = Vec::new();
+
+    let hypertensive = visit.bp_systolic.is_some_and(|bp| bp >= 140)
+        || visit.bp_diastolic.is_some_and(|bp| bp >= 90);
+    if hypertensive {
+        flags.push("hypertension");
+    }
+
+    let proteinuria = visit
+        .urine_protein
+        .as_deref()
+        .is_some_and(|protein| !matches!(protein, "negative" | "trace"));
+    if hypertensive && proteinuria {
+        flags.push("pre_eclampsia_suspected");
+    }
+
+    if visit
+        .hemoglobin_g_dl
+        .and_then(|hb| hb.to_f64())
+        .is_some_and(|hb| hb < 11.0)
+    {
+        flags.push("anaemia");
+    }
+
+    if visit
+        .fetal_heart_rate
+        .is_some_and(|fhr| !(110..=160).contains(&fhr))
+    {
+        flags.push("abnormal_fetal_heart_rate");
+    }
+
+    if visit
+        .danger_signs
+        .as_ref()
+        .is_some_and(|signs| !signs.is_empty())
+    {
+        flags.push("danger_signs");
+    }
+
+    flags
+}
+
+fn anc_profile_json(profile: &tenant::entities::anc_profiles::Model) -> Value {
+    json!({
+        "pid": profile.pid,
+        "patient_pid": profile.patient_pid,
+        "lmp": profile.lmp,
+        "edd": profile.edd,
+        "gravida": profile.gravida,
+        "para": profile.para,
+        "risk_factors": profile.risk_factors,
+        "status": profile.status,
+        "outcome_date": profile.outcome_date,
+        "notes": profile.notes,
+        "created_at": profile.created_at,
+        "updated_at": profile.updated_at,
+    })
+}
+
+fn anc_visit_json(visit: &tenant::entities::anc_visits::Model) -> Value {
+    json!({
+        "pid": visit.pid,
+        "contact_number": visit.contact_number,
+        "visit_date": visit.visit_date,
+        "gestation_weeks": visit.gestation_weeks,
+        "weight_kg": visit.weight_kg,
+        "bp_systolic": visit.bp_systolic,
+        "bp_diastolic": visit.bp_diastolic,
+        "fundal_height_cm": visit.fundal_height_cm,
+        "fetal_heart_rate": visit.fetal_heart_rate,
+        "hemoglobin_g_dl": visit.hemoglobin_g_dl,
+        "urine_protein": visit.urine_protein,
+        "danger_signs": visit.danger_signs,
+        "next_visit_date": visit.next_visit_date,
+        "notes": visit.notes,
+        "flags": anc_visit_flags(visit),
+        "created_at": visit.created_at,
+    })
+}
+
+fn growth_measurement_json(measurement: &tenant::entities::growth_measurements::Model) -> Value {
+    json!({
+        "pid": measurement.pid,
+        "patient_pid": measurement.patient_pid,
+        "measured_on": measurement.measured_on,
+        "age_days": measurement.age_days,
+        "weight_kg": measurement.weight_kg,
+        "height_cm": measurement.height_cm,
+        "muac_cm": measurement.muac_cm,
+        "oedema": measurement.oedema,
+        "weight_for_age_z": measurement.weight_for_age_z,
+        "height_for_age_z": measurement.height_for_age_z,
+        "weight_for_height_z": measurement.weight_for_height_z,
+        "nutrition_status": measurement.nutrition_status,
+        "flags": measurement.flags,
+        "created_at": measurement.created_at,
+    })
+}
+
+pub async fn index_anc_profiles(
+    app_state: web::Data<AppState>,
+    query: web::Query<PaginationParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let mut stmt = tenant::entities::anc_profiles::Entity::find()
+        .filter(tenant::entities::anc_profiles::Column::DeletedAt.is_null());
+
+    if !query.all.unwrap_or(false) {
+        stmt = stmt
+            .filter(tenant::entities::anc_profiles::Column::Status.eq(AncProfileStatus::Active));
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(10).clamp(1, 100);
+    let paginator = stmt
+        .order_by_asc(tenant::entities::anc_profiles::Column::Edd)
+        .paginate(&tenant_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
+
+    let results = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?
+        .iter()
+        .map(anc_profile_json)
+        .collect::<Vec<_>>();
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "anc_profiles": results,
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "ANC profiles fetched successfully",
+        }),
+    ))
+}
+
+pub async fn show_anc_profile(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let profile = find_anc_profile(&tenant_db, path.into_inner()).await?;
+    let visits = fetch_anc_visits(&tenant_db, profile.id).await?;
+    let contacts = expected_contacts(&profile, &visits, Utc::now().date_naive());
+    let babies = fetch_babies(&app_state, &tenant_db, profile.patient_pid).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "anc_profile": anc_profile_json(&profile),
+            "visits": visits.iter().map(anc_visit_json).collect::<Vec<_>>(),
+            "expected_contacts": contacts,
+            "missed_contacts": contacts.iter().filter(|c| c.status == "missed").count(),
+            "babies": babies,
+            "message": "ANC profile fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct AncProfileData {
+    pub patient_pid: Option<Uuid>,
+    pub lmp: Option<NaiveDate>,
+    pub edd: Option<NaiveDate>,
+    pub gravida: Option<i32>,
+    pub para: Option<i32>,
+    pub risk_factors: Option<Vec<String>>,
+    pub status: Option<AncProfileStatus>,
+    pub outcome_date: Option<NaiveDate>,
+    pub notes: Option<String>,
+}
+
+impl AncProfileData {
+    pub fn validate(&self, is_create: bool) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+        let today = Utc::now().date_naive();
+
+        if is_create {
+            if self.patient_pid.is_none() {
+                errors.insert("patient_pid".to_string(), "Patient is required".to_string());
+            }
+
+            if self.lmp.is_none() {
+                errors.insert(
+                    "lmp".to_string(),
+                    "Last menstrual period is required".to_string(),
+                );
+            }
+
+            if self.gravida.is_none() {
+                errors.insert("gravida".to_string(), "Gravida is required".to_string());
+            }
+        }
+
+        if let Some(lmp) = self.lmp
+            && (lmp > today || lmp < today - Duration::days(PREGNANCY_DAYS + 42))
+        {
+            errors.insert(
+                "lmp".to_string(),
+                "Last menstrual period must be within the last 46 weeks".to_string(),
+            );
+        }
+
+        if let (Some(lmp), Some(edd)) = (self.lmp, self.edd)
+            && edd <= lmp
+        {
+            errors.insert(
+                "edd".to_string(),
+                "Expected delivery date must be after the last menstrual period".to_string(),
+            );
+        }
+
+        if self.gravida.is_some_and(|g| g < 1) {
+            errors.insert(
+                "gravida".to_string(),
+                "Gravida must be at least 1".to_string(),
+            );
+        }
+
+        if self.para.is_some_and(|p| p < 0) {
+            errors.insert(
+                "para".to_string(),
+                "Para must be greater than or equal to 0".to_string(),
+            );
+        }
+
+        if let (Some(gravida), Some(para)) = (self.gravida, self.para)
+            && para >= gravida
+        {
+            errors.insert(
+                "para".to_string(),
+                "Para must be less than gravida for an ongoing pregnancy".to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+pub async fn create_anc_profile(
+    app_state: web::Data<AppState>,
+    data: web::Json<AncProfileData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate(true) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let patient = find_patient(&app_state, data.patient_pid.unwrap_or_default()).await?;
+
+    if patient.gender.as_ref().is_some_and(|g| *g == Gender::Male) {
+        return Err(ApiResponse::new(
+            400,
+            json!({ "message": "ANC profiles can only be opened for female patients" }),
+        ));
+    }
+
+    let active_profiles = tenant::entities::anc_profiles::Entity::find()
+        .filter(tenant::entities::anc_profiles::Column::PatientPid.eq(patient.pid))
+        .filter(tenant::entities::anc_profiles::Column::Status.eq(AncProfileStatus::Active))
+        .filter(tenant::entities::anc_profiles::Column::DeletedAt.is_null())
+        .count(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to check active ANC profiles: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to create ANC profile" }))
+        })?;
+
+    if active_profiles > 0 {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Patient already has an active ANC profile" }),
+        ));
+    }
+
+    let lmp = data.lmp.unwrap_or_default();
+
+    let profile = tenant::entities::anc_profiles::ActiveModel {
+        patient_pid: Set(patient.pid),
+        lmp: Set(lmp),
+        edd: Set(data
+            .edd
+            .unwrap_or_else(|| lmp + Duration::days(PREGNANCY_DAYS))),
+        gravida: Set(data.gravida.unwrap_or(1)),
+        para: Set(data.para.unwrap_or(0)),
+        risk_factors: Set(data.risk_factors.clone()),
+        status: Set(AncProfileStatus::Active),
+        notes: Set(data.notes.clone()),
+        created_by: Set(Some(claims.sub)),
+        ..Default::default()
+    }
+    .insert(&tenant_db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to create ANC profile: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create ANC profile" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "anc_profile": anc_profile_json(&profile),
+            "message": "ANC profile created successfully",
+        }),
+    ))
+}
+
+pub async fn edit_anc_profile(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<AncProfileData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate(false) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let profile = find_anc_profile(&tenant_db, path.into_inner()).await?;
+
+    let mut active_model: tenant::entities::anc_profiles::ActiveModel = profile.to_owned().into();
+
+    if let Some(lmp) = data.lmp {
+        active_model.lmp = Set(lmp);
+        if data.edd.is_none() {
+            active_model.edd = Set(lmp + Duration::days(PREGNANCY_DAYS));
+        }
+    }
+    if let Some(edd) = data.edd {
+        active_model.edd = Set(edd);
+    }
+    if let Some(gravida) = data.gravida {
+        active_model.gravida = Set(gravida);
+    }
+    if let Some(para) = data.para {
+        active_model.para = Set(para);
+    }
+    if let Some(risk_factors) = &data.risk_factors {
+        active_model.risk_factors = Set(Some(risk_factors.clone()));
+    }
+    if let Some(status) = &data.status {
+        active_model.status = Set(status.clone());
+        if *status != AncProfileStatus::Active {
+            active_model.outcome_date = Set(Some(
+                data.outcome_date.unwrap_or_else(|| Utc::now().date_naive()),
+            ));
+        }
+    }
+    if let Some(notes) = &data.notes {
+        active_model.notes = Set(Some(notes.clone()));
+    }
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let profile = active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!("Failed to update ANC profile {}: {}", profile.pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to update ANC profile" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "anc_profile": anc_profile_json(&profile),
+            "message": "ANC profile updated successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct AncVisitData {
+    pub visit_date: Option<NaiveDate>,
+    pub weight_kg: Option<Decimal>,
+    pub bp_systolic: Option<i32>,
+    pub bp_diastolic: Option<i32>,
+    pub fundal_height_cm: Option<Decimal>,
+    pub fetal_heart_rate: Option<i32>,
+    pub hemoglobin_g_dl: Option<Decimal>,
+    pub urine_protein: Option<String>,
+    pub danger_signs: Option<Vec<String>>,
+    pub next_visit_date: Option<NaiveDate>,
+    pub notes: Option<String>,
+}
+
+impl AncVisitData {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if self
+            .visit_date
+            .is_some_and(|date| date > Utc::now().date_naive())
+        {
+            errors.insert(
+                "visit_date".to_string(),
+                "Visit date cannot be in the future".to_string(),
+            );
+        }
+
+        if self.weight_kg.is_some_and(|w| w <= Decimal::ZERO) {
+            errors.insert(
+                "weight_kg".to_string(),
+                "Weight must be greater than 0".to_string(),
+            );
+        }
+
+        if let (Some(systolic), Some(diastolic)) = (self.bp_systolic, self.bp_diastolic)
+            && systolic <= diastolic
+        {
+            errors.insert(
+                "bp_systolic".to_string(),
+                "Systolic pressure must be higher than diastolic pressure".to_string(),
+            );
+        }
+
+        if let Some(protein) = &self.urine_protein
+            && !["negative", "trace", "+1", "+2", "+3", "+4"].contains(&protein.as_str())
+        {
+            errors.insert(
+                "urine_protein".to_string(),
+                "Urine protein must be one of: negative, trace, +1, +2, +3, +4".to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+pub async fn create_anc_visit(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<AncVisitData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate() {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let profile = find_anc_profile(&tenant_db, path.into_inner()).await?;
+
+    if profile.status != AncProfileStatus::Active {
+        return Err(ApiResponse::new(
+            400,
+            json!({ "message": "Visits can only be recorded against an active ANC profile" }),
+        ));
+    }
+
+    let visit_date = data.visit_date.unwrap_or_else(|| Utc::now().date_naive());
+    if visit_date < profile.lmp {
+        return Err(ApiResponse::new(
+            400,
+            json!({ "message": "Visit date cannot be before the last menstrual period" }),
+        ));
+    }
+
+    let previous_visits = fetch_anc_visits(&tenant_db, profile.id).await?;
+
+    let visit = tenant::entities::anc_visits::ActiveModel {
+        anc_profile_id: Set(profile.id),
+        contact_number: Set(previous_visits.len() as i32 + 1),
+        visit_date: Set(visit_date),
+        gestation_weeks: Set(((visit_date - profile.lmp).num_days() / 7) as i32),
+        weight_kg: Set(data.weight_kg),
+        bp_systolic: Set(data.bp_systolic),
+        bp_diastolic: Set(data.bp_diastolic),
+        fundal_height_cm: Set(data.fundal_height_cm),
+        fetal_heart_rate: Set(data.fetal_heart_rate),
+        hemoglobin_g_dl: Set(data.hemoglobin_g_dl),
+        urine_protein: Set(data.urine_protein.clone()),
+        danger_signs: Set(data.danger_signs.clone()),
+        next_visit_date: Set(data.next_visit_date),
+        notes: Set(data.notes.clone()),
+        recorded_by: Set(Some(claims.sub)),
+        ..Default::default()
+    }
+    .insert(&tenant_db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to record ANC visit: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to record ANC visit" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "anc_visit": anc_visit_json(&visit),
+            "message": "ANC visit recorded successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct LinkBabyData {
+    pub baby_patient_pid: Option<Uuid>,
+    pub insurance_pid: Option<Uuid>,
+}
+
+/// Links a newborn to the mother by registering the baby as a `child`
+/// dependent on the mother's insurance policy, then closes the pregnancy.
+pub async fn link_baby(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<LinkBabyData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let Some(baby_patient_pid) = data.baby_patient_pid else {
+        let mut errors = HashMap::new();
+        errors.insert(
+            "baby_patient_pid".to_string(),
+            "Baby patient is required".to_string(),
+        );
+        return Err(ApiResponse::new(400, json!(ValidationError { errors })));
+    };
+
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let profile = find_anc_profile(&tenant_db, path.into_inner()).await?;
+    let mother = find_patient(&app_state, profile.patient_pid).await?;
+    let baby = find_patient(&app_state, baby_patient_pid).await?;
+
+    if mother.id == baby.id {
+        return Err(ApiResponse::new(
+            400,
+            json!({ "message": "A patient cannot be linked as their own baby" }),
+        ));
+    }
+
+    let mut insurance_stmt = main::entities::patient_insurance::Entity::find()
+        .filter(main::entities::patient_insurance::Column::PatientId.eq(mother.id))
+        .filter(main::entities::patient_insurance::Column::DeletedAt.is_null());
+
+    insurance_stmt = match data.insurance_pid {
+        Some(insurance_pid) => {
+            insurance_stmt.filter(main::entities::patient_insurance::Column::Pid.eq(insurance_pid))
+        }
+        None => insurance_stmt
+            .order_by_desc(main::entities::patient_insurance::Column::IsPrimary)
+            .order_by_desc(main::entities::patient_insurance::Column::CreatedAt),
+    };
+
+    let insurance = insurance_stmt
+        .one(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch insurance for patient {}: {}", mother.pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch mother's insurance" }))
+        })?
+        .ok_or_else(|| {
+            ApiResponse::new(
+                422,
+                json!({ "message": "The mother has no insurance policy to register the baby under" }),
+            )
+        })?;
+
+    let existing = main::entities::insurance_dependents::Entity::find()
+        .filter(main::entities::insurance_dependents::Column::InsuranceId.eq(insurance.id))
+        .filter(main::entities::insurance_dependents::Column::DependentPatientId.eq(baby.id))
+        .filter(main::entities::insurance_dependents::Column::DeletedAt.is_null())
+        .count(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to check insurance dependents: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to link baby" }))
+        })?;
+
+    if existing == 0 {
+        main::entities::insurance_dependents::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            insurance_id: Set(insurance.id),
+            dependent_patient_id: Set(baby.id),
+            relationship: Set(Some(InsuranceDependentRelationship::Child)),
+            is_active: Set(true),
+            ..Default::default()
+        }
+        .insert(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to create insurance dependent: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to link baby" }))
+        })?;
+    }
+
+    if profile.status == AncProfileStatus::Active {
+        let mut active_model: tenant::entities::anc_profiles::ActiveModel =
+            profile.to_owned().into();
+        active_model.status = Set(AncProfileStatus::Delivered);
+        active_model.outcome_date = Set(Some(baby.dob.unwrap_or_else(|| Utc::now().date_naive())));
+        active_model.updated_at = Set(Utc::now().naive_utc());
+        active_model.update(&tenant_db).await.map_err(|err| {
+            log::error!("Failed to close ANC profile {}: {}", profile.pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to update ANC profile" }))
+        })?;
+    }
+
+    Ok(ApiResponse::new(
+        200,
+        json!({ "message": "Baby linked to mother successfully" }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct GrowthMeasurementData {
+    pub patient_pid: Option<Uuid>,
+    pub measured_on: Option<NaiveDate>,
+    pub weight_kg: Option<Decimal>,
+    pub height_cm: Option<Decimal>,
+    pub muac_cm: Option<Decimal>,
+    pub oedema: bool,
+}
+
+impl GrowthMeasurementData {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if self.patient_pid.is_none() {
+            errors.insert("patient_pid".to_string(), "Patient is required".to_string());
+        }
+
+        if self.weight_kg.is_none() && self.height_cm.is_none() && self.muac_cm.is_none() {
+            errors.insert(
+                "weight_kg".to_string(),
+                "At least one of weight, height or MUAC is required".to_string(),
+            );
+        }
+
+        for (field, value) in [
+            ("weight_kg", self.weight_kg),
+            ("height_cm", self.height_cm),
+            ("muac_cm", self.muac_cm),
+        ] {
+            if value.is_some_and(|v| v <= Decimal::ZERO) {
+                errors.insert(
+                    field.to_string(),
+                    "Value must be greater than 0".to_string(),
+                );
+            }
+        }
+
+        if self
+            .measured_on
+            .is_some_and(|date| date > Utc::now().date_naive())
+        {
+            errors.insert(
+                "measured_on".to_string(),
+                "Measurement date cannot be in the future".to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+pub async fn create_growth_measurement(
+    app_state: web::Data<AppState>,
+    data: web::Json<GrowthMeasurementData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate() {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let patient = find_patient(&app_state, data.patient_pid.unwrap_or_default()).await?;
+
+    let Some(dob) = patient.dob else {
+        return Err(ApiResponse::new(
+            422,
+            json!({ "message": "Patient date of birth is required for growth monitoring" }),
+        ));
+    };
+
+    let measured_on = data.measured_on.unwrap_or_else(|| Utc::now().date_naive());
+    let age_days = (measured_on - dob).num_days();
+    if age_days < 0 {
+        return Err(ApiResponse::new(
+            400,
+            json!({ "message": "Measurement date cannot be before the date of birth" }),
+        ));
+    }
+
+    let z_scores = who_z_scores(
+        patient.gender.as_ref(),
+        age_days,
+        data.weight_kg.and_then(|w| w.to_f64()),
+        data.height_cm.and_then(|h| h.to_f64()),
+    );
+    let status = nutrition_status(
+        &z_scores,
+        data.muac_cm.and_then(|m| m.to_f64()),
+        data.oedema,
+        age_days,
+    );
+    let flags = growth_flags(&z_scores);
+    let to_decimal = |z: Option<f64>| z.and_then(Decimal::from_f64).map(|d| d.round_dp(2));
+
+    let measurement = tenant::entities::growth_measurements::ActiveModel {
+        patient_pid: Set(patient.pid),
+        measured_on: Set(measured_on),
+        age_days: Set(age_days as i32),
+        weight_kg: Set(data.weight_kg),
+        height_cm: Set(data.height_cm),
+        muac_cm: Set(data.muac_cm),
+        oedema: Set(data.oedema),
+        weight_for_age_z: Set(to_decimal(z_scores.weight_for_age)),
+        height_for_age_z: Set(to_decimal(z_scores.height_for_age)),
+        weight_for_height_z: Set(to_decimal(z_scores.weight_for_height)),
+        nutrition_status: Set(status.to_string()),
+        flags: Set(Some(flags)),
+        recorded_by: Set(Some(claims.sub)),
+        ..Default::default()
+    }
+    .insert(&tenant_db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to record growth measurement: {}", err);
+        ApiResponse::new(
+            500,
+            json!({ "message": "Failed to record growth measurement" }),
+        )
+    })?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "growth_measurement": growth_measurement_json(&measurement),
+            "message": "Growth measurement recorded successfully",
+        }),
+    ))
+}
+
+pub async fn patient_growth(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let patient = find_patient(&app_state, path.into_inner()).await?;
+
+    let measurements = tenant::entities::growth_measurements::Entity::find()
+        .filter(tenant::entities::growth_measurements::Column::PatientPid.eq(patient.pid))
+        .filter(tenant::entities::growth_measurements::Column::DeletedAt.is_null())
+        .order_by_asc(tenant::entities::growth_measurements::Column::MeasuredOn)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch growth measurements: {}", err);
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to fetch growth measurements" }),
+            )
+        })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "patient": {
+                "pid": patient.pid,
+                "first_name": patient.first_name,
+                "last_name": patient.last_name,
+                "dob": patient.dob,
+                "gender": patient.gender,
+            },
+            "growth_measurements": measurements.iter().map(growth_measurement_json).collect::<Vec<_>>(),
+            "latest": measurements.last().map(growth_measurement_json),
+            "message": "Growth measurements fetched successfully",
+        }),
+    ))
+}
+
+/// Worklist for the MCH clinic: active pregnancies with missed contacts and
+/// children whose latest measurement shows malnutrition.
+pub async fn alerts(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let today = Utc::now().date_naive();
+
+    let profiles = tenant::entities::anc_profiles::Entity::find()
+        .filter(tenant::entities::anc_profiles::Column::Status.eq(AncProfileStatus::Active))
+        .filter(tenant::entities::anc_profiles::Column::DeletedAt.is_null())
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch ANC profiles: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch MCH alerts" }))
+        })?;
+
+    let mut missed_visits = Vec::new();
+    for profile in &profiles {
+        let visits = fetch_anc_visits(&tenant_db, profile.id).await?;
+        let missed = expected_contacts(profile, &visits, today)
+            .into_iter()
+            .filter(|contact| contact.status == "missed")
+            .collect::<Vec<_>>();
+
+        let visit_flags = visits.last().map(anc_visit_flags).unwrap_or_default();
+
+        if !missed.is_empty() || !visit_flags.is_empty() {
+            missed_visits.push(json!({
+                "anc_profile": anc_profile_json(profile),
+                "missed_contacts": missed,
+                "latest_visit_flags": visit_flags,
+            }));
+        }
+    }
+
+    let measurements = tenant::entities::growth_measurements::Entity::find()
+        .filter(tenant::entities::growth_measurements::Column::DeletedAt.is_null())
+        .filter(
+            tenant::entities::growth_measurements::Column::MeasuredOn
+                .gte(today - Duration::days(90)),
+        )
+        .order_by_desc(tenant::entities::growth_measurements::Column::MeasuredOn)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch growth measurements: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch MCH alerts" }))
+        })?;
+
+    let mut seen = Vec::new();
+    let malnutrition = measurements
+        .iter()
+        .filter(|measurement| {
+            if seen.contains(&measurement.patient_pid) {
+                return false;
+            }
+            seen.push(measurement.patient_pid);
+            measurement.nutrition_status.ends_with("malnutrition")
+                || measurement
+                    .flags
+                    .as_ref()
+                    .is_some_and(|flags| !flags.is_empty())
+        })
+        .map(growth_measurement_json)
+        .collect::<Vec<_>>();
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "anc_alerts": missed_visits,
+            "malnutrition_alerts": malnutrition,
+            "message": "MCH alerts fetched successfully",
+        }),
+    ))
+}
+
+async fn fetch_babies(
+    app_state: &AppState,
+    tenant_db: &DatabaseConnection,
+    mother_pid: Uuid,
+) -> Result<Vec<Value>, ApiResponse> {
+    let mother = find_patient(app_state, mother_pid).await?;
+
+    let insurance_ids = main::entities::patient_insurance::Entity::find()
+        .filter(main::entities::patient_insurance::Column::PatientId.eq(mother.id))
+        .filter(main::entities::patient_insurance::Column::DeletedAt.is_null())
+        .all(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to fetch insurance for patient {}: {}",
+                mother.pid,
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to fetch linked babies" }))
+        })?
+        .into_iter()
+        .map(|insurance| insurance.id)
+        .collect::<Vec<_>>();
+
+    if insurance_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let babies = main::entities::insurance_dependents::Entity::find()
+        .find_also_related(main::entities::patients::Entity)
+        .filter(main::entities::insurance_dependents::Column::InsuranceId.is_in(insurance_ids))
+        .filter(
+            main::entities::insurance_dependents::Column::Relationship
+                .eq(InsuranceDependentRelationship::Child),
+        )
+        .filter(main::entities::insurance_dependents::Column::DeletedAt.is_null())
+        .all(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch insurance dependents: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch linked babies" }))
+        })?;
+
+    let mut results = Vec::new();
+    for (_, baby) in babies {
+        let Some(baby) = baby else { continue };
+
+        let latest = tenant::entities::growth_measurements::Entity::find()
+            .filter(tenant::entities::growth_measurements::Column::PatientPid.eq(baby.pid))
+            .filter(tenant::entities::growth_measurements::Column::DeletedAt.is_null())
+            .order_by_desc(tenant::entities::growth_measurements::Column::MeasuredOn)
+            .one(tenant_db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to fetch growth measurements: {}", err);
+                ApiResponse::new(500, json!({ "message": "Failed to fetch linked babies" }))
+            })?;
+
+        results.push(json!({
+            "pid": baby.pid,
+            "first_name": baby.first_name,
+            "last_name": baby.last_name,
+            "dob": baby.dob,
+            "gender": baby.gender,
+            "latest_growth": latest.as_ref().map(growth_measurement_json),
+        }));
+    }
+
+    Ok(results)
+}
+
+async fn fetch_anc_visits(
+    tenant_db: &DatabaseConnection,
+    anc_profile_id: i32,
+) -> Result<Vec<tenant::entities::anc_visits::Model>, ApiResponse> {
+    tenant::entities::anc_visits::Entity::find()
+        .filter(tenant::entities::anc_visits::Column::AncProfileId.eq(anc_profile_id))
+        .filter(tenant::entities::anc_visits::Column::DeletedAt.is_null())
+        .order_by_asc(tenant::entities::anc_visits::Column::VisitDate)
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch ANC visits: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch ANC visits" }))
+        })
+}
+
+async fn find_anc_profile(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+) -> Result<tenant::entities::anc_profiles::Model, ApiResponse> {
+    tenant::entities::anc_profiles::Entity::find_by_pid(pid)
+        .filter(tenant::entities::anc_profiles::Column::DeletedAt.is_null())
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch ANC profile {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch ANC profile" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "ANC profile not found" })))
+}
@@ -0,0 +1,503 @@
+use std::collections::HashMap;
+
+use actix_multipart::Multipart;
+use actix_web::{HttpRequest, web};
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    db::tenant::{
+        self,
+        entities::sea_orm_active_enums::{MessageSenderType, MessageThreadStatus},
+        migrations::sea_orm::{
+            ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait,
+            QueryFilter, QueryOrder, Set,
+        },
+    },
+    handlers::services::{
+        files::authorized_file_url,
+        messaging::{
+            MessageForm, REPLY_SLA_HOURS, fetch_thread_messages, find_attachment, mark_thread_read,
+            message_thread_json, post_message, thread_message_json, unread_counts,
+        },
+        patients::find_patient,
+        telemedicine::encounter_json,
+    },
+    utils::{
+        api_response::ApiResponse,
+        app_state::AppState,
+        jwt::{get_logged_in_user_claims, get_tenant_db},
+        validator_error::ValidationError,
+    },
+};
+
+const ATTACHMENT_URL_EXPIRY_SECS: u64 = 300;
+
+#[derive(Deserialize, Debug)]
+pub struct ThreadParams {
+    pub status: Option<MessageThreadStatus>,
+    pub patient_pid: Option<Uuid>,
+    pub assigned_to: Option<Uuid>,
+    pub mine: Option<bool>,
+    pub unassigned: Option<bool>,
+    pub overdue: Option<bool>,
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+}
+
+/// The care team's inbox, most recently active threads first.
+pub async fn index(
+    app_state: web::Data<AppState>,
+    query: web::Query<ThreadParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let mut stmt = tenant::entities::message_threads::Entity::find();
+
+    if let Some(status) = &query.status {
+        stmt = stmt.filter(tenant::entities::message_threads::Column::Status.eq(status.clone()));
+    }
+
+    if let Some(patient_pid) = query.patient_pid {
+        stmt = stmt.filter(tenant::entities::message_threads::Column::PatientPid.eq(patient_pid));
+    }
+
+    if let Some(assigned_to) = query.assigned_to {
+        stmt = stmt.filter(tenant::entities::message_threads::Column::AssignedTo.eq(assigned_to));
+    }
+
+    if query.mine.unwrap_or(false) {
+        stmt = stmt.filter(tenant::entities::message_threads::Column::AssignedTo.eq(claims.sub));
+    }
+
+    if query.unassigned.unwrap_or(false) {
+        stmt = stmt.filter(tenant::entities::message_threads::Column::AssignedTo.is_null());
+    }
+
+    if query.overdue.unwrap_or(false) {
+        stmt = stmt
+            .filter(tenant::entities::message_threads::Column::Status.eq(MessageThreadStatus::Open))
+            .filter(
+                tenant::entities::message_threads::Column::ReplyDueAt.lt(Utc::now().naive_utc()),
+            );
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(10).clamp(1, 100);
+    let paginator = stmt
+        .order_by_desc(tenant::entities::message_threads::Column::LastMessageAt)
+        .paginate(&tenant_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
+
+    let threads = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let unread = unread_counts(
+        &tenant_db,
+        threads.iter().map(|thread| thread.id).collect(),
+        MessageSenderType::Staff,
+    )
+    .await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "threads": threads
+                .iter()
+                .map(|thread| {
+                    message_thread_json(thread, unread.get(&thread.id).copied().unwrap_or(0))
+                })
+                .collect::<Vec<_>>(),
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "Threads fetched successfully",
+        }),
+    ))
+}
+
+/// Starts a thread with a patient. Staff-initiated threads do not start the
+/// reply clock; it only runs while a patient is waiting on the care team.
+pub async fn create(
+    app_state: web::Data<AppState>,
+    payload: Multipart,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let form = MessageForm::from_multipart(payload).await?;
+    if let Err(err) = form.validate(true) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let Some(patient_pid) = form.patient_pid else {
+        return Err(ApiResponse::new(
+            400,
+            json!(ValidationError {
+                errors: HashMap::from([(
+                    "patient_pid".to_string(),
+                    "Patient is required".to_string()
+                )]),
+            }),
+        ));
+    };
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let patient = find_patient(&app_state, patient_pid).await?;
+
+    let encounter_id = match form.encounter_pid {
+        Some(encounter_pid) => Some(
+            find_encounter(&tenant_db, encounter_pid, patient.pid)
+                .await?
+                .id,
+        ),
+        None => None,
+    };
+
+    let thread = tenant::entities::message_threads::ActiveModel {
+        patient_pid: Set(patient.pid),
+        encounter_id: Set(encounter_id),
+        subject: Set(form.subject.clone().unwrap_or_default().trim().to_string()),
+        status: Set(MessageThreadStatus::Open),
+        assigned_to: Set(Some(claims.sub)),
+        assigned_at: Set(Some(Utc::now().naive_utc())),
+        last_message_at: Set(Utc::now().naive_utc()),
+        last_message_from: Set(MessageSenderType::Staff),
+        ..Default::default()
+    }
+    .insert(&tenant_db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to create message thread: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create thread" }))
+    })?;
+
+    let (thread, message, attachments) = post_message(
+        &app_state,
+        &req,
+        None,
+        &tenant_db,
+        &thread,
+        &patient,
+        MessageSenderType::Staff,
+        claims.sub,
+        form,
+    )
+    .await?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "thread": message_thread_json(&thread, 0),
+            "messages": [thread_message_json(&message, &attachments)],
+            "message": "Thread created successfully",
+        }),
+    ))
+}
+
+/// Opening a thread marks the patient's messages in it as read.
+pub async fn show(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let thread = find_thread(&tenant_db, path.into_inner()).await?;
+
+    mark_thread_read(&tenant_db, &thread, MessageSenderType::Staff, claims.sub).await?;
+    let messages = fetch_thread_messages(&tenant_db, &thread).await?;
+
+    let encounter = match thread.encounter_id {
+        Some(encounter_id) => tenant::entities::encounters::Entity::find_by_id(encounter_id)
+            .one(&tenant_db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to fetch encounter {}: {}", encounter_id, err);
+                ApiResponse::new(500, json!({ "message": "Failed to fetch thread" }))
+            })?,
+        None => None,
+    };
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "thread": message_thread_json(&thread, 0),
+            "encounter": encounter.as_ref().map(encounter_json),
+            "messages": messages,
+            "message": "Thread fetched successfully",
+        }),
+    ))
+}
+
+/// Replying stops the reply clock. An unassigned thread is picked up by
+/// whoever answers it first.
+pub async fn reply(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    payload: Multipart,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let form = MessageForm::from_multipart(payload).await?;
+    if let Err(err) = form.validate(false) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let mut thread = find_thread(&tenant_db, path.into_inner()).await?;
+
+    if thread.status == MessageThreadStatus::Closed {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Thread is closed" }),
+        ));
+    }
+
+    if thread.assigned_to.is_none() {
+        let mut active_model: tenant::entities::message_threads::ActiveModel =
+            thread.clone().into();
+        active_model.assigned_to = Set(Some(claims.sub));
+        active_model.assigned_at = Set(Some(Utc::now().naive_utc()));
+        thread = update_thread(&tenant_db, active_model).await?;
+    }
+
+    let patient = find_patient(&app_state, thread.patient_pid).await?;
+    let (thread, message, attachments) = post_message(
+        &app_state,
+        &req,
+        None,
+        &tenant_db,
+        &thread,
+        &patient,
+        MessageSenderType::Staff,
+        claims.sub,
+        form,
+    )
+    .await?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "thread": message_thread_json(&thread, 0),
+            "reply": thread_message_json(&message, &attachments),
+            "message": "Reply sent successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AssignData {
+    pub assigned_to: Option<Uuid>,
+}
+
+pub async fn assign(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<AssignData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let thread = find_thread(&tenant_db, path.into_inner()).await?;
+
+    let mut active_model: tenant::entities::message_threads::ActiveModel = thread.into();
+    active_model.assigned_to = Set(data.assigned_to);
+    active_model.assigned_at = Set(data.assigned_to.map(|_| Utc::now().naive_utc()));
+    let thread = update_thread(&tenant_db, active_model).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "thread": message_thread_json(&thread, 0),
+            "message": if thread.assigned_to.is_some() {
+                "Thread assigned successfully"
+            } else {
+                "Thread unassigned successfully"
+            },
+        }),
+    ))
+}
+
+/// Closing a thread stops its reply clock. A new message from the patient
+/// reopens it.
+pub async fn close(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let thread = find_thread(&tenant_db, path.into_inner()).await?;
+
+    if thread.status == MessageThreadStatus::Closed {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Thread is already closed" }),
+        ));
+    }
+
+    let mut active_model: tenant::entities::message_threads::ActiveModel = thread.into();
+    active_model.status = Set(MessageThreadStatus::Closed);
+    active_model.closed_at = Set(Some(Utc::now().naive_utc()));
+    active_model.closed_by = Set(Some(claims.sub));
+    active_model.awaiting_reply_since = Set(None);
+    active_model.reply_due_at = Set(None);
+    let thread = update_thread(&tenant_db, active_model).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "thread": message_thread_json(&thread, 0),
+            "message": "Thread closed successfully",
+        }),
+    ))
+}
+
+pub async fn attachment(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let (attachment, _) = find_attachment(&tenant_db, path.into_inner()).await?;
+
+    let (url, _) = authorized_file_url(
+        &app_state,
+        &req,
+        attachment.file_pid,
+        ATTACHMENT_URL_EXPIRY_SECS,
+    )
+    .await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "url": url,
+            "original_name": attachment.original_name,
+            "content_type": attachment.content_type,
+            "expires_in": ATTACHMENT_URL_EXPIRY_SECS,
+            "message": "Attachment link generated successfully",
+        }),
+    ))
+}
+
+/// Reply SLA overview for the facility's inbox.
+pub async fn sla(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let now = Utc::now().naive_utc();
+
+    let open_threads = || {
+        tenant::entities::message_threads::Entity::find()
+            .filter(tenant::entities::message_threads::Column::Status.eq(MessageThreadStatus::Open))
+    };
+    let count_err = |err| {
+        log::error!("Failed to count message threads: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to fetch SLA summary" }))
+    };
+
+    let open = open_threads().count(&tenant_db).await.map_err(count_err)?;
+    let awaiting_reply = open_threads()
+        .filter(tenant::entities::message_threads::Column::AwaitingReplySince.is_not_null())
+        .count(&tenant_db)
+        .await
+        .map_err(count_err)?;
+    let overdue = open_threads()
+        .filter(tenant::entities::message_threads::Column::ReplyDueAt.lt(now))
+        .count(&tenant_db)
+        .await
+        .map_err(count_err)?;
+    let unassigned = open_threads()
+        .filter(tenant::entities::message_threads::Column::AssignedTo.is_null())
+        .count(&tenant_db)
+        .await
+        .map_err(count_err)?;
+    let breached_last_30_days = tenant::entities::message_threads::Entity::find()
+        .filter(
+            tenant::entities::message_threads::Column::SlaBreachedAt.gte(now - Duration::days(30)),
+        )
+        .count(&tenant_db)
+        .await
+        .map_err(count_err)?;
+
+    let oldest_waiting = open_threads()
+        .filter(tenant::entities::message_threads::Column::AwaitingReplySince.is_not_null())
+        .order_by_asc(tenant::entities::message_threads::Column::AwaitingReplySince)
+        .one(&tenant_db)
+        .await
+        .map_err(count_err)?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "sla": {
+                "reply_sla_hours": REPLY_SLA_HOURS,
+                "open": open,
+                "awaiting_reply": awaiting_reply,
+                "overdue": overdue,
+                "unassigned": unassigned,
+                "breached_last_30_days": breached_last_30_days,
+                "oldest_waiting_since": oldest_waiting.and_then(|thread| thread.awaiting_reply_since),
+            },
+            "message": "SLA summary fetched successfully",
+        }),
+    ))
+}
+
+async fn find_thread(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+) -> Result<tenant::entities::message_threads::Model, ApiResponse> {
+    tenant::entities::message_threads::Entity::find_by_pid(pid)
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch message thread {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch thread" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Thread not found" })))
+}
+
+async fn update_thread(
+    tenant_db: &DatabaseConnection,
+    mut thread: tenant::entities::message_threads::ActiveModel,
+) -> Result<tenant::entities::message_threads::Model, ApiResponse> {
+    thread.updated_at = Set(Utc::now().naive_utc());
+    thread.update(tenant_db).await.map_err(|err| {
+        log::error!("Failed to update message thread: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to update thread" }))
+    })
+}
+
+async fn find_encounter(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+    patient_pid: Uuid,
+) -> Result<tenant::entities::encounters::Model, ApiResponse> {
+    tenant::entities::encounters::Entity::find_by_pid(pid)
+        .filter(tenant::entities::encounters::Column::PatientPid.eq(patient_pid))
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch encounter {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch encounter" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Encounter not found" })))
+}
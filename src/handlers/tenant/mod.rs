@@ -1,6 +1,29 @@
+pub mod billing_line_items;
+pub mod chronic_care;
+pub mod clinical_tasks;
+pub mod controlled_substances;
+pub mod coupons;
+pub mod deliveries;
+pub mod dispensing;
+pub mod immunizations;
+pub mod inpatient;
+pub mod insurance_claims;
+pub mod lab_orders;
+pub mod mch;
+pub mod messages;
+pub mod online_orders;
+pub mod patient_bills;
 pub mod payments;
+pub mod pharmacy;
+pub mod preauthorizations;
+pub mod prescriptions;
+pub mod procurement;
+pub mod queue;
+pub mod screenings;
+pub mod service_catalogue;
+pub mod stock_alerts;
 pub mod subscription_plans;
 pub mod subscriptions;
+pub mod telemedicine;
 pub mod tenants;
 pub mod users;
-pub mod billing_line_items;
@@ -0,0 +1,625 @@
+use std::collections::HashMap;
+
+use actix_web::{HttpRequest, web};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        main,
+        tenant::{
+            self,
+            entities::sea_orm_active_enums::{
+                OnlineOrderStatus, OnlinePaymentStatus, StockMovementType,
+            },
+            migrations::sea_orm::{
+                ActiveModelTrait, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter,
+                QueryOrder, Set, TransactionTrait,
+            },
+        },
+    },
+    handlers::{
+        services::{
+            coupons::{ONLINE_ORDER_SOURCE, void_redemptions},
+            deliveries::{cancel_order_delivery, delivery_json, find_order_delivery},
+            files::authorized_file_url,
+            online_orders::{
+                facility_name, fetch_order_items, find_order, notify_order_status, order_json,
+            },
+            pharmacy::{
+                NewMovement, find_store, lock_product_batches, pick_fefo, product_label,
+                record_movement,
+            },
+        },
+        tenant::payments::MpesaCallbackRequest,
+    },
+    utils::{
+        api_response::ApiResponse,
+        app_state::AppState,
+        jwt::{get_logged_in_user_claims, get_tenant_db},
+        validator_error::ValidationError,
+    },
+};
+
+const PRESCRIPTION_URL_EXPIRY_SECS: u64 = 300;
+
+#[derive(Deserialize, Debug)]
+pub struct OrderParams {
+    pub status: Option<OnlineOrderStatus>,
+    pub payment_status: Option<OnlinePaymentStatus>,
+    pub search: Option<String>,
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+}
+
+/// Online orders, oldest first so the queue is worked in order.
+pub async fn index(
+    app_state: web::Data<AppState>,
+    query: web::Query<OrderParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let mut stmt = tenant::entities::online_orders::Entity::find();
+
+    if let Some(status) = &query.status {
+        stmt = stmt.filter(tenant::entities::online_orders::Column::Status.eq(status.clone()));
+    }
+
+    if let Some(payment_status) = &query.payment_status {
+        stmt = stmt.filter(
+            tenant::entities::online_orders::Column::PaymentStatus.eq(payment_status.clone()),
+        );
+    }
+
+    if let Some(term) = &query.search {
+        use tenant::migrations::{Expr, extension::postgres::PgExpr};
+
+        stmt = stmt.filter(
+            Expr::col(tenant::entities::online_orders::Column::OrderNumber)
+                .ilike(format!("%{}%", term)),
+        );
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let paginator = stmt
+        .order_by_asc(tenant::entities::online_orders::Column::CreatedAt)
+        .paginate(&tenant_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
+
+    let orders = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "orders": orders
+                .iter()
+                .map(|order| order_json(order, Vec::new()))
+                .collect::<Vec<_>>(),
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "Orders fetched successfully",
+        }),
+    ))
+}
+
+/// An order with its items, the patient and a short-lived link to the
+/// uploaded prescription.
+pub async fn show(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let order = find_order(&tenant_db, path.into_inner()).await?;
+    let items = fetch_order_items(&tenant_db, &order).await?;
+
+    let patient = main::entities::patients::Entity::find_by_pid(order.patient_pid)
+        .one(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch patient {}: {}", order.patient_pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch order" }))
+        })?;
+
+    let prescription_url = match order.prescription_document_file_pid {
+        Some(file_pid) => Some(
+            authorized_file_url(&app_state, &req, file_pid, PRESCRIPTION_URL_EXPIRY_SECS)
+                .await?
+                .0,
+        ),
+        None => None,
+    };
+
+    let delivery = find_order_delivery(&tenant_db, order.id).await?;
+    let rider = match delivery.as_ref().and_then(|delivery| delivery.rider_id) {
+        Some(rider_id) => tenant::entities::delivery_riders::Entity::find_by_id(rider_id)
+            .one(&tenant_db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to fetch rider {}: {}", rider_id, err);
+                ApiResponse::new(500, json!({ "message": "Failed to fetch order" }))
+            })?,
+        None => None,
+    };
+
+    let store = match order.store_id {
+        Some(store_id) => tenant::entities::pharmacy_stores::Entity::find_by_id(store_id)
+            .one(&tenant_db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to fetch store {}: {}", store_id, err);
+                ApiResponse::new(500, json!({ "message": "Failed to fetch order" }))
+            })?,
+        None => None,
+    };
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "order": order_json(&order, items),
+            "patient": patient.map(|patient| json!({
+                "pid": patient.pid,
+                "first_name": patient.first_name,
+                "last_name": patient.last_name,
+                "phone_number": patient.phone_number,
+            })),
+            "store": store.map(|store| json!({
+                "pid": store.pid,
+                "name": store.name,
+                "code": store.code,
+            })),
+            "delivery": delivery
+                .as_ref()
+                .map(|delivery| delivery_json(delivery, rider.as_ref())),
+            "prescription_url": prescription_url,
+            "message": "Order fetched successfully",
+        }),
+    ))
+}
+
+/// A pharmacist confirms a paid order can be supplied, having checked the
+/// prescription for any prescription-only items.
+pub async fn verify(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let order = find_order(&tenant_db, path.into_inner()).await?;
+
+    ensure_status(&order, OnlineOrderStatus::Placed)?;
+
+    if order.payment_status != OnlinePaymentStatus::Paid {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Order has not been paid" }),
+        ));
+    }
+
+    let needs_prescription = tenant::entities::online_order_items::Entity::find()
+        .filter(tenant::entities::online_order_items::Column::OrderId.eq(order.id))
+        .filter(tenant::entities::online_order_items::Column::RequiresPrescription.eq(true))
+        .count(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch items of order {}: {}", order.pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to verify order" }))
+        })?
+        > 0;
+
+    if needs_prescription && order.prescription_document_file_pid.is_none() {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Order has prescription-only items but no prescription" }),
+        ));
+    }
+
+    let mut active_model: tenant::entities::online_orders::ActiveModel = order.into();
+    active_model.status = Set(OnlineOrderStatus::Verified);
+    active_model.verified_at = Set(Some(Utc::now().naive_utc()));
+    active_model.verified_by = Set(Some(claims.sub));
+
+    update_and_notify(&app_state, &req, &tenant_db, active_model, "verified").await
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct PackData {
+    pub store_pid: Option<Uuid>,
+}
+
+/// Picks the order from a store, soonest expiry first, and takes the stock
+/// out of the store.
+pub async fn pack(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<PackData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let Some(store_pid) = data.store_pid else {
+        return Err(ApiResponse::new(
+            400,
+            json!(ValidationError {
+                errors: HashMap::from([("store_pid".to_string(), "Store is required".to_string())]),
+            }),
+        ));
+    };
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let order = find_order(&tenant_db, path.into_inner()).await?;
+    let store = find_store(&tenant_db, store_pid).await?;
+
+    ensure_status(&order, OnlineOrderStatus::Verified)?;
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start packing transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to pack order" }))
+    })?;
+
+    let items = tenant::entities::online_order_items::Entity::find()
+        .find_also_related(tenant::entities::pharmacy_products::Entity)
+        .filter(tenant::entities::online_order_items::Column::OrderId.eq(order.id))
+        .order_by_asc(tenant::entities::online_order_items::Column::Id)
+        .all(&txn)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch items of order {}: {}", order.pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to pack order" }))
+        })?;
+
+    let reference = order_reference(&order);
+    for (item, product) in &items {
+        lock_product_batches(&txn, item.product_id).await?;
+
+        let picks = pick_fefo(&txn, store.id, item.product_id, item.quantity as i64)
+            .await
+            .map_err(|err| match product {
+                Some(product) if err.status_code == 409 => ApiResponse::new(
+                    409,
+                    json!({
+                        "message": format!(
+                            "Insufficient stock of {} in {}",
+                            product_label(product),
+                            store.name
+                        ),
+                    }),
+                ),
+                _ => err,
+            })?;
+
+        for pick in &picks {
+            record_movement(
+                &txn,
+                NewMovement {
+                    store_id: store.id,
+                    batch: &pick.batch,
+                    movement_type: StockMovementType::Dispense,
+                    quantity: -(pick.quantity as i32),
+                    transfer_pid: None,
+                    reference: Some(reference.clone()),
+                    reason: None,
+                    performed_by: claims.sub,
+                },
+            )
+            .await?;
+        }
+    }
+
+    let mut active_model: tenant::entities::online_orders::ActiveModel = order.into();
+    active_model.status = Set(OnlineOrderStatus::Packed);
+    active_model.store_id = Set(Some(store.id));
+    active_model.packed_at = Set(Some(Utc::now().naive_utc()));
+    active_model.packed_by = Set(Some(claims.sub));
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let order = active_model.update(&txn).await.map_err(|err| {
+        log::error!("Failed to mark order as packed: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to pack order" }))
+    })?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit packing of order {}: {}", order.pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to pack order" }))
+    })?;
+
+    notify_order_status(&app_state, &facility_name(&req, &app_state).await?, &order).await;
+    let items = fetch_order_items(&tenant_db, &order).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "order": order_json(&order, items),
+            "message": "Order packed successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct CancelOrderData {
+    pub reason: Option<String>,
+}
+
+/// Cancels an order that has not left the pharmacy. Packed stock goes back
+/// to the batches it came from. Paid orders are flagged for a refund, which
+/// is made outside the system.
+pub async fn cancel(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<CancelOrderData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let reason = data
+        .reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|r| !r.is_empty())
+        .map(str::to_string);
+    match &reason {
+        None => {
+            return Err(ApiResponse::new(
+                400,
+                json!(ValidationError {
+                    errors: HashMap::from([(
+                        "reason".to_string(),
+                        "Reason is required".to_string()
+                    )]),
+                }),
+            ));
+        }
+        Some(reason) if reason.chars().count() > 500 => {
+            return Err(ApiResponse::new(
+                400,
+                json!(ValidationError {
+                    errors: HashMap::from([(
+                        "reason".to_string(),
+                        "Reason must be at most 500 characters".to_string()
+                    )]),
+                }),
+            ));
+        }
+        _ => {}
+    }
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let order = find_order(&tenant_db, path.into_inner()).await?;
+
+    if !matches!(
+        order.status,
+        OnlineOrderStatus::Placed | OnlineOrderStatus::Verified | OnlineOrderStatus::Packed
+    ) {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Order can no longer be cancelled" }),
+        ));
+    }
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start cancellation transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to cancel order" }))
+    })?;
+
+    if let Some(store_id) = order.store_id {
+        let movements = tenant::entities::stock_movements::Entity::find()
+            .find_also_related(tenant::entities::stock_batches::Entity)
+            .filter(tenant::entities::stock_movements::Column::StoreId.eq(store_id))
+            .filter(
+                tenant::entities::stock_movements::Column::MovementType
+                    .eq(StockMovementType::Dispense),
+            )
+            .filter(
+                tenant::entities::stock_movements::Column::Reference.eq(order_reference(&order)),
+            )
+            .all(&txn)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to fetch stock picked for {}: {}", order.pid, err);
+                ApiResponse::new(500, json!({ "message": "Failed to cancel order" }))
+            })?;
+
+        for (movement, batch) in &movements {
+            let Some(batch) = batch else {
+                continue;
+            };
+
+            lock_product_batches(&txn, batch.product_id).await?;
+            record_movement(
+                &txn,
+                NewMovement {
+                    store_id,
+                    batch,
+                    movement_type: StockMovementType::Adjustment,
+                    quantity: -movement.quantity,
+                    transfer_pid: None,
+                    reference: movement.reference.clone(),
+                    reason: Some("Online order cancelled".to_string()),
+                    performed_by: claims.sub,
+                },
+            )
+            .await?;
+        }
+    }
+
+    void_redemptions(&txn, ONLINE_ORDER_SOURCE, order.pid).await?;
+    cancel_order_delivery(&txn, order.id).await?;
+
+    let refund_due = order.payment_status == OnlinePaymentStatus::Paid;
+    let mut active_model: tenant::entities::online_orders::ActiveModel = order.into();
+    active_model.status = Set(OnlineOrderStatus::Cancelled);
+    active_model.cancelled_at = Set(Some(Utc::now().naive_utc()));
+    active_model.cancelled_by = Set(Some(claims.sub));
+    active_model.cancel_reason = Set(reason);
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let order = active_model.update(&txn).await.map_err(|err| {
+        log::error!("Failed to cancel order: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to cancel order" }))
+    })?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit cancellation of {}: {}", order.pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to cancel order" }))
+    })?;
+
+    notify_order_status(&app_state, &facility_name(&req, &app_state).await?, &order).await;
+    let items = fetch_order_items(&tenant_db, &order).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "order": order_json(&order, items),
+            "refund_due": refund_due,
+            "message": "Order cancelled successfully",
+        }),
+    ))
+}
+
+/// The M-Pesa result for an online order. The facility is in the callback
+/// path because each facility's orders live in its own database; the
+/// checkout's token must match the one stored on the order.
+pub async fn mpesa_callback(
+    app_state: web::Data<AppState>,
+    path: web::Path<(Uuid, String)>,
+    payload: web::Json<MpesaCallbackRequest>,
+) -> Result<ApiResponse, ApiResponse> {
+    log::info!("M-Pesa order callback received: {:?}", payload);
+
+    let (tenant_pid, callback_token) = path.into_inner();
+    let callback = &payload.body.stk_callback;
+
+    let facility = main::entities::tenants::Entity::find_by_pid(tenant_pid)
+        .filter(main::entities::tenants::Column::DeletedAt.is_null())
+        .one(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch tenant {}: {}", tenant_pid, err);
+            ApiResponse::new(500, json!({ "message": "Transaction lookup failed" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Transaction not found" })))?;
+
+    let tenant_db = app_state
+        .tenant_db(facility.sso_tenant_id)
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Transaction not found" })))?;
+
+    let order = tenant::entities::online_orders::Entity::find()
+        .filter(
+            tenant::entities::online_orders::Column::CheckoutRequestId
+                .eq(callback.checkout_request_id.clone()),
+        )
+        .filter(tenant::entities::online_orders::Column::CallbackToken.eq(callback_token))
+        .filter(
+            tenant::entities::online_orders::Column::PaymentStatus.eq(OnlinePaymentStatus::Pending),
+        )
+        .one(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to find order for payment: {}", err);
+            ApiResponse::new(500, json!({ "message": "Transaction lookup failed" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Transaction not found" })))?;
+
+    let receipt_number = callback.callback_metadata.as_ref().and_then(|metadata| {
+        metadata
+            .item
+            .iter()
+            .find(|item| item.name == "MpesaReceiptNumber")
+            .and_then(|item| item.value.as_str())
+            .map(str::to_string)
+    });
+
+    let order_pid = order.pid;
+    let mut active_model: tenant::entities::online_orders::ActiveModel = order.into();
+    if callback.result_code == 0 {
+        active_model.payment_status = Set(OnlinePaymentStatus::Paid);
+        active_model.payment_reference = Set(receipt_number);
+        active_model.paid_at = Set(Some(Utc::now().naive_utc()));
+    } else {
+        active_model.payment_status = Set(OnlinePaymentStatus::Failed);
+        active_model.payment_failure_reason = Set(Some(callback.result_desc.clone()));
+    }
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let order = active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!("Failed to record payment for order {}: {}", order_pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to update transaction" }))
+    })?;
+
+    if order.status == OnlineOrderStatus::Placed {
+        notify_order_status(&app_state, &facility.name, &order).await;
+    }
+
+    Ok(ApiResponse::new(
+        200,
+        json!({ "ResultCode": 0, "ResultDesc": "Callback processed successfully" }),
+    ))
+}
+
+fn ensure_status(
+    order: &tenant::entities::online_orders::Model,
+    expected: OnlineOrderStatus,
+) -> Result<(), ApiResponse> {
+    if order.status != expected {
+        return Err(ApiResponse::new(
+            409,
+            json!({
+                "message": "Order is not at the right stage for this step",
+                "status": order.status,
+            }),
+        ));
+    }
+
+    Ok(())
+}
+
+/// The stock movement reference for an order, used to find the stock picked
+/// for it.
+fn order_reference(order: &tenant::entities::online_orders::Model) -> String {
+    format!("Online order {}", order.order_number)
+}
+
+async fn update_and_notify(
+    app_state: &web::Data<AppState>,
+    req: &HttpRequest,
+    tenant_db: &tenant::migrations::sea_orm::DatabaseConnection,
+    mut active_model: tenant::entities::online_orders::ActiveModel,
+    action: &str,
+) -> Result<ApiResponse, ApiResponse> {
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let order = active_model.update(tenant_db).await.map_err(|err| {
+        log::error!("Failed to update order: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to update order" }))
+    })?;
+
+    notify_order_status(app_state, &facility_name(req, app_state).await?, &order).await;
+    let items = fetch_order_items(tenant_db, &order).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "order": order_json(&order, items),
+            "message": format!("Order {} successfully", action),
+        }),
+    ))
+}
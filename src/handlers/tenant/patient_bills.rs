@@ -0,0 +1,1507 @@
+use std::collections::HashMap;
+
+use actix_web::{HttpRequest, web};
+use chrono::{NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        main::{self, entities::sea_orm_active_enums::FileVisibility},
+        tenant::{
+            self,
+            entities::sea_orm_active_enums::{
+                BillPaymentMethod, BillPaymentStatus, PatientBillStatus, PatientChargeStatus,
+            },
+            migrations::sea_orm::{
+                ActiveModelTrait, ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter,
+                QueryOrder, QuerySelect, Set, TransactionTrait,
+            },
+        },
+    },
+    handlers::{
+        services::{
+            coupons::{
+                CouponLine, applied_coupons_json, apply_coupons, record_redemptions,
+                void_redemptions,
+            },
+            files::authorized_file_url,
+            online_orders::facility_name,
+            patient_bills::{
+                MPESA_BUY_GOODS, MPESA_PAYBILL, PATIENT_BILL_SOURCE, apply_payment, bill_balance,
+                bill_charges, bill_detail_json, bill_json, bill_payment_json, bill_payments,
+                collect_charges, facility_mpesa_settings, find_bill, find_open_bill,
+                mpesa_settings_json, payment_totals, post_out_of_pocket, receipt_html,
+                receipt_json, reconciliation_json, release_charges, split_bill,
+                start_mpesa_payment, unreconciled_payments,
+            },
+            patient_charges::patient_charge_json,
+            patients::sms_phone_number,
+            pricing::{
+                facility_currency, find_service, payer_price_list, primary_insurance,
+                quoted_price_json, service_price,
+            },
+        },
+        tenant::payments::MpesaCallbackRequest,
+    },
+    utils::{
+        api_response::ApiResponse,
+        app_state::AppState,
+        crypto::encrypt_string,
+        documents::document_number,
+        html_to_image::generate_png,
+        jwt::{get_logged_in_user_claims, get_tenant_db, get_tenant_id},
+        validation::validate_international_phone_number,
+        validator_error::ValidationError,
+    },
+};
+
+const RECEIPT_URL_EXPIRY_SECS: u64 = 300;
+
+#[derive(Deserialize, Debug)]
+pub struct BillParams {
+    pub patient_pid: Option<Uuid>,
+    pub status: Option<PatientBillStatus>,
+    pub search: Option<String>,
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+}
+
+pub async fn index(
+    app_state: web::Data<AppState>,
+    query: web::Query<BillParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let mut stmt = tenant::entities::patient_bills::Entity::find();
+
+    if let Some(patient_pid) = query.patient_pid {
+        stmt = stmt.filter(tenant::entities::patient_bills::Column::PatientPid.eq(patient_pid));
+    }
+
+    if let Some(status) = &query.status {
+        stmt = stmt.filter(tenant::entities::patient_bills::Column::Status.eq(status.clone()));
+    }
+
+    if let Some(term) = &query.search {
+        use tenant::migrations::{Expr, extension::postgres::PgExpr};
+
+        stmt = stmt.filter(
+            Condition::any().add(
+                Expr::col(tenant::entities::patient_bills::Column::BillNumber)
+                    .ilike(format!("%{}%", term)),
+            ),
+        );
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let paginator = stmt
+        .order_by_desc(tenant::entities::patient_bills::Column::CreatedAt)
+        .paginate(&tenant_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
+
+    let bills = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "bills": bills.iter().map(bill_json).collect::<Vec<_>>(),
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "Bills fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct BillData {
+    pub patient_pid: Option<Uuid>,
+    /// The visit the bill is for.
+    pub encounter_pid: Option<Uuid>,
+    pub notes: Option<String>,
+}
+
+impl BillData {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if self.patient_pid.is_none() {
+            errors.insert("patient_pid".to_string(), "Patient is required".to_string());
+        }
+
+        if self
+            .notes
+            .as_ref()
+            .is_some_and(|n| n.chars().count() > 1000)
+        {
+            errors.insert(
+                "notes".to_string(),
+                "Notes must be at most 1000 characters".to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+/// Opens a bill for the patient's visit and moves their pending charges
+/// onto it. A patient has at most one open bill; charges made later are
+/// collected when it is finalized.
+pub async fn create(
+    app_state: web::Data<AppState>,
+    data: web::Json<BillData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate() {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let patient_pid = data.patient_pid.unwrap_or_default();
+
+    main::entities::patients::Entity::find_by_pid(patient_pid)
+        .filter(main::entities::patients::Column::DeletedAt.is_null())
+        .one(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch patient {}: {}", patient_pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch patient" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Patient not found" })))?;
+
+    if let Some(bill) = find_open_bill(&tenant_db, patient_pid).await? {
+        return Err(ApiResponse::new(
+            409,
+            json!({
+                "message": format!("The patient already has an open bill, {}", bill.bill_number),
+                "bill_pid": bill.pid,
+            }),
+        ));
+    }
+
+    let encounter_id = match data.encounter_pid {
+        Some(encounter_pid) => Some(
+            tenant::entities::encounters::Entity::find_by_pid(encounter_pid)
+                .one(&tenant_db)
+                .await
+                .map_err(|err| {
+                    log::error!("Failed to fetch encounter {}: {}", encounter_pid, err);
+                    ApiResponse::new(500, json!({ "message": "Failed to fetch encounter" }))
+                })?
+                .filter(|encounter| encounter.patient_pid == patient_pid)
+                .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Encounter not found" })))?
+                .id,
+        ),
+        None => None,
+    };
+
+    let currency = facility_currency(&req, &app_state).await?;
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start bill transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to open bill" }))
+    })?;
+
+    let bill = tenant::entities::patient_bills::ActiveModel {
+        bill_number: Set(document_number("BIL")),
+        patient_pid: Set(patient_pid),
+        encounter_id: Set(encounter_id),
+        status: Set(PatientBillStatus::Open),
+        currency: Set(currency),
+        notes: Set(data.notes.clone()),
+        opened_by: Set(claims.sub),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to open bill: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to open bill" }))
+    })?;
+
+    collect_charges(&txn, &bill).await?;
+    let charges = bill_charges(&txn, bill.id).await?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit bill {}: {}", bill.bill_number, err);
+        ApiResponse::new(500, json!({ "message": "Failed to open bill" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "bill": bill_detail_json(&bill, &charges, &[]),
+            "message": "Bill opened successfully",
+        }),
+    ))
+}
+
+/// The bill with its charges and payments. For an open bill, the patient's
+/// charges made since it was opened are listed as well; they join the bill
+/// when it is finalized.
+pub async fn show(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let bill = find_bill(&tenant_db, path.into_inner()).await?;
+    let charges = bill_charges(&tenant_db, bill.id).await?;
+    let payments = bill_payments(&tenant_db, bill.id).await?;
+
+    let uncollected = if bill.status == PatientBillStatus::Open {
+        tenant::entities::patient_charges::Entity::find()
+            .filter(tenant::entities::patient_charges::Column::PatientPid.eq(bill.patient_pid))
+            .filter(
+                tenant::entities::patient_charges::Column::Status.eq(PatientChargeStatus::Pending),
+            )
+            .filter(tenant::entities::patient_charges::Column::BillId.is_null())
+            .order_by_asc(tenant::entities::patient_charges::Column::CreatedAt)
+            .all(&tenant_db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to fetch charges of {}: {}", bill.patient_pid, err);
+                ApiResponse::new(500, json!({ "message": "Failed to fetch bill" }))
+            })?
+    } else {
+        Vec::new()
+    };
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "bill": bill_detail_json(&bill, &charges, &payments),
+            "uncollected_charges": uncollected.iter().map(patient_charge_json).collect::<Vec<_>>(),
+            "message": "Bill fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct FinalizeData {
+    pub coupon_codes: Vec<String>,
+    /// Bill the patient's primary insurer, leaving the patient the copay and
+    /// any deductible still due.
+    /// Defaults to true when the patient has verified cover in force.
+    pub bill_insurance: Option<bool>,
+}
+
+/// Closes the bill to new charges and splits it between the insurer and
+/// the patient. Coupons are for self-paying patients only, since they come
+/// off what the patient pays.
+pub async fn finalize(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<FinalizeData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let bill_pid = path.into_inner();
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start bill transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to finalize bill" }))
+    })?;
+
+    let bill = tenant::entities::patient_bills::Entity::find_by_pid(bill_pid)
+        .lock_exclusive()
+        .one(&txn)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch bill {}: {}", bill_pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to finalize bill" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Bill not found" })))?;
+
+    if bill.status != PatientBillStatus::Open {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Only open bills can be finalized" }),
+        ));
+    }
+
+    collect_charges(&txn, &bill).await?;
+    let charges = bill_charges(&txn, bill.id).await?;
+    if charges.is_empty() {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "The bill has no charges" }),
+        ));
+    }
+
+    let subtotal: Decimal = charges.iter().map(|charge| charge.total_amount).sum();
+
+    let cover = if data.bill_insurance.unwrap_or(true) {
+        primary_insurance(&app_state.main_db, bill.patient_pid).await?
+    } else {
+        None
+    };
+
+    if cover.is_some() && !data.coupon_codes.is_empty() {
+        return Err(ApiResponse::new(
+            400,
+            json!(ValidationError {
+                errors: HashMap::from([(
+                    "coupon_codes".to_string(),
+                    "Coupons cannot be used on bills sent to insurance".to_string(),
+                )]),
+            }),
+        ));
+    }
+
+    let lines: Vec<CouponLine> = charges
+        .iter()
+        .map(|charge| CouponLine {
+            service_code: charge.service_code.clone(),
+            amount: charge.total_amount,
+            ..Default::default()
+        })
+        .collect();
+    let applied = apply_coupons(&txn, &data.coupon_codes, bill.patient_pid, &lines).await?;
+    record_redemptions(
+        &txn,
+        &applied,
+        bill.patient_pid,
+        PATIENT_BILL_SOURCE,
+        bill.pid,
+        subtotal,
+    )
+    .await?;
+
+    let discount_amount: Decimal = applied.iter().map(|coupon| coupon.discount_amount).sum();
+    let total_amount = subtotal - discount_amount;
+    let split = split_bill(total_amount, cover.as_ref().map(|(cover, _)| cover));
+    let settled = bill.amount_paid >= split.patient_amount;
+    let now = Utc::now().naive_utc();
+
+    let bill_number = bill.bill_number.clone();
+    let mut active_model: tenant::entities::patient_bills::ActiveModel = bill.into();
+    active_model.subtotal = Set(subtotal);
+    active_model.discount_amount = Set(discount_amount);
+    active_model.total_amount = Set(total_amount);
+    active_model.insurance_amount = Set(split.insurance_amount);
+    active_model.patient_amount = Set(split.patient_amount);
+    active_model.deductible_amount = Set(split.deductible_amount);
+    active_model.patient_insurance_pid = Set(cover.as_ref().map(|(cover, _)| cover.pid));
+    active_model.insurance_provider_pid = Set(cover.as_ref().map(|(_, provider)| provider.pid));
+    active_model.status = Set(if settled {
+        PatientBillStatus::Paid
+    } else {
+        PatientBillStatus::Finalized
+    });
+    if settled {
+        active_model.paid_at = Set(Some(now));
+    }
+    active_model.finalized_at = Set(Some(now));
+    active_model.finalized_by = Set(Some(claims.sub));
+    active_model.updated_at = Set(now);
+
+    let bill = active_model.update(&txn).await.map_err(|err| {
+        log::error!("Failed to finalize bill {}: {}", bill_number, err);
+        ApiResponse::new(500, json!({ "message": "Failed to finalize bill" }))
+    })?;
+    let payments = bill_payments(&txn, bill.id).await?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit bill {}: {}", bill_number, err);
+        ApiResponse::new(500, json!({ "message": "Failed to finalize bill" }))
+    })?;
+
+    // Deposits taken while the bill was open count once it is split. The
+    // bill stands either way; a failed posting is retried by the accumulator
+    // posting job.
+    if let Err(err) = post_out_of_pocket(&tenant_db, &app_state.main_db, &bill).await {
+        log::warn!(
+            "Out-of-pocket of finalized bill {} left for retry: {}",
+            bill.bill_number,
+            err
+        );
+    }
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "bill": bill_detail_json(&bill, &charges, &payments),
+            "coupons": applied_coupons_json(&applied),
+            "message": "Bill finalized successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct EstimateLine {
+    pub service_pid: Option<Uuid>,
+    /// Defaults to 1.
+    pub quantity: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct EstimateData {
+    pub patient_pid: Option<Uuid>,
+    /// Catalogue services priced on the patient's price list.
+    pub services: Vec<EstimateLine>,
+    /// A further amount to include, for charges not in the catalogue.
+    pub amount: Option<Decimal>,
+}
+
+impl EstimateData {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if self.patient_pid.is_none() {
+            errors.insert("patient_pid".to_string(), "Patient is required".to_string());
+        }
+
+        if self.services.is_empty() && self.amount.is_none() {
+            errors.insert(
+                "services".to_string(),
+                "Services or an amount are required".to_string(),
+            );
+        }
+
+        if self.services.iter().any(|line| line.service_pid.is_none()) {
+            errors.insert(
+                "services".to_string(),
+                "Every line needs a service".to_string(),
+            );
+        } else if self
+            .services
+            .iter()
+            .any(|line| line.quantity.is_some_and(|quantity| quantity < 1))
+        {
+            errors.insert(
+                "services".to_string(),
+                "Quantity must be at least 1".to_string(),
+            );
+        }
+
+        if self.amount.is_some_and(|amount| amount < Decimal::ZERO) {
+            errors.insert(
+                "amount".to_string(),
+                "Amount cannot be negative".to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+/// Estimates what the patient will pay for a visit before it is billed,
+/// from their primary cover's copay, deductible and out-of-pocket maximum
+/// and what has built up against them this plan year.
+pub async fn estimate(
+    app_state: web::Data<AppState>,
+    data: web::Json<EstimateData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    data.validate()
+        .map_err(|err| ApiResponse::new(400, json!(err)))?;
+
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let patient_pid = data.patient_pid.unwrap_or_default();
+    let currency = facility_currency(&req, &app_state).await?;
+    let today = Utc::now().date_naive();
+
+    let cover = primary_insurance(&app_state.main_db, patient_pid).await?;
+    let price_list = payer_price_list(&tenant_db, &app_state.main_db, patient_pid, None).await?;
+
+    let mut lines = Vec::new();
+    let mut total_amount = data.amount.unwrap_or_default().round_dp(2);
+    for line in &data.services {
+        let service = find_service(&tenant_db, line.service_pid.unwrap_or_default()).await?;
+        let price = service_price(&tenant_db, &service, price_list.as_ref(), &currency, today)
+            .await?
+            .ok_or_else(|| {
+                ApiResponse::new(
+                    404,
+                    json!({ "message": format!("{} has no price for this payer", service.name) }),
+                )
+            })?;
+
+        let quantity = line.quantity.unwrap_or(1);
+        let line_total = price.amount * Decimal::from(quantity);
+        total_amount += line_total;
+
+        let mut line_json = quoted_price_json(&price);
+        line_json["quantity"] = json!(quantity);
+        line_json["total_amount"] = json!(line_total);
+        lines.push(line_json);
+    }
+
+    let split = split_bill(total_amount, cover.as_ref().map(|(cover, _)| cover));
+
+    let insurance = cover.as_ref().map(|(cover, provider)| {
+        json!({
+            "pid": cover.pid,
+            "provider_name": provider.name,
+            "policy_number": cover.policy_number,
+            "copay_amount": cover.copay_amount,
+            "deductible_amount": cover.deductible_amount,
+            "deductible_met_ytd": cover.deductible_met_ytd,
+            "deductible_remaining": cover
+                .deductible_amount
+                .map(|deductible| (deductible - cover.deductible_met_ytd).max(Decimal::ZERO)),
+            "out_of_pocket_max": cover.out_of_pocket_max,
+            "out_of_pocket_met_ytd": cover.out_of_pocket_met_ytd,
+            "out_of_pocket_remaining": cover
+                .out_of_pocket_max
+                .map(|max| (max - cover.out_of_pocket_met_ytd).max(Decimal::ZERO)),
+        })
+    });
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "estimate": {
+                "lines": lines,
+                "total_amount": total_amount,
+                "insurance_amount": split.insurance_amount,
+                "patient_amount": split.patient_amount,
+                "deductible_amount": split.deductible_amount,
+                "copay_amount": split.copay_amount,
+                "currency": currency,
+            },
+            "insurance": insurance,
+            "message": "Estimate calculated successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct VoidData {
+    pub reason: Option<String>,
+}
+
+/// Voids a bill nothing has been paid on. Its charges go back to pending
+/// and its coupons are released.
+pub async fn void(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<VoidData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let reason = data
+        .reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|r| !r.is_empty())
+        .ok_or_else(|| {
+            ApiResponse::new(
+                400,
+                json!(ValidationError {
+                    errors: HashMap::from([(
+                        "reason".to_string(),
+                        "Reason is required".to_string(),
+                    )]),
+                }),
+            )
+        })?
+        .to_string();
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let bill = find_bill(&tenant_db, path.into_inner()).await?;
+
+    if !matches!(
+        bill.status,
+        PatientBillStatus::Open | PatientBillStatus::Finalized
+    ) {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Only open or finalized bills can be voided" }),
+        ));
+    }
+
+    if bill_payments(&tenant_db, bill.id)
+        .await?
+        .iter()
+        .any(|payment| payment.status != BillPaymentStatus::Failed)
+    {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Bills with payments cannot be voided" }),
+        ));
+    }
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start bill transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to void bill" }))
+    })?;
+
+    release_charges(&txn, &bill).await?;
+    void_redemptions(&txn, PATIENT_BILL_SOURCE, bill.pid).await?;
+
+    let bill_number = bill.bill_number.clone();
+    let mut active_model: tenant::entities::patient_bills::ActiveModel = bill.into();
+    active_model.status = Set(PatientBillStatus::Void);
+    active_model.voided_at = Set(Some(Utc::now().naive_utc()));
+    active_model.voided_by = Set(Some(claims.sub));
+    active_model.void_reason = Set(Some(reason));
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let bill = active_model.update(&txn).await.map_err(|err| {
+        log::error!("Failed to void bill {}: {}", bill_number, err);
+        ApiResponse::new(500, json!({ "message": "Failed to void bill" }))
+    })?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit bill {}: {}", bill_number, err);
+        ApiResponse::new(500, json!({ "message": "Failed to void bill" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "bill": bill_json(&bill),
+            "message": "Bill voided successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct PaymentData {
+    pub method: Option<BillPaymentMethod>,
+    pub amount: Option<Decimal>,
+    /// Cash handed over; the change is worked out from it.
+    pub tendered_amount: Option<Decimal>,
+    /// The phone prompted for M-Pesa. Defaults to the patient's own.
+    pub phone_number: Option<String>,
+}
+
+impl PaymentData {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if self.method.is_none() {
+            errors.insert(
+                "method".to_string(),
+                "Payment method is required".to_string(),
+            );
+        }
+
+        match self.amount {
+            None => {
+                errors.insert("amount".to_string(), "Amount is required".to_string());
+            }
+            Some(amount) if amount <= Decimal::ZERO => {
+                errors.insert(
+                    "amount".to_string(),
+                    "Amount must be greater than zero".to_string(),
+                );
+            }
+            _ => {}
+        }
+
+        if let (Some(tendered), Some(amount)) = (self.tendered_amount, self.amount)
+            && tendered < amount
+        {
+            errors.insert(
+                "tendered_amount".to_string(),
+                "Cash tendered cannot be less than the amount".to_string(),
+            );
+        }
+
+        if let Some(phone_number) = &self.phone_number
+            && !validate_international_phone_number(phone_number)
+        {
+            errors.insert(
+                "phone_number".to_string(),
+                "Phone number is not valid".to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+/// Takes a payment on the bill. Payments on an open bill are deposits;
+/// on a finalized bill they cannot exceed the balance. Cash is received at
+/// once; M-Pesa prompts the payer's phone through the facility's shortcode
+/// and completes when M-Pesa confirms.
+pub async fn pay(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<PaymentData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate() {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let bill = find_bill(&tenant_db, path.into_inner()).await?;
+    let amount = data.amount.unwrap_or_default().round_dp(2);
+
+    match bill.status {
+        PatientBillStatus::Open => {}
+        PatientBillStatus::Finalized if amount <= bill_balance(&bill) => {}
+        PatientBillStatus::Finalized => {
+            return Err(ApiResponse::new(
+                400,
+                json!(ValidationError {
+                    errors: HashMap::from([(
+                        "amount".to_string(),
+                        format!("Amount is more than the balance of {}", bill_balance(&bill)),
+                    )]),
+                }),
+            ));
+        }
+        _ => {
+            return Err(ApiResponse::new(
+                409,
+                json!({ "message": "The bill is not awaiting payment" }),
+            ));
+        }
+    }
+
+    if data.method == Some(BillPaymentMethod::Mpesa) {
+        let phone_number = match &data.phone_number {
+            Some(phone_number) => phone_number.clone(),
+            None => main::entities::patients::Entity::find_by_pid(bill.patient_pid)
+                .one(&app_state.main_db)
+                .await
+                .map_err(|err| {
+                    log::error!("Failed to fetch patient {}: {}", bill.patient_pid, err);
+                    ApiResponse::new(500, json!({ "message": "Failed to fetch patient" }))
+                })?
+                .as_ref()
+                .and_then(sms_phone_number)
+                .ok_or_else(|| {
+                    ApiResponse::new(
+                        400,
+                        json!(ValidationError {
+                            errors: HashMap::from([(
+                                "phone_number".to_string(),
+                                "The patient has no phone number on file".to_string(),
+                            )]),
+                        }),
+                    )
+                })?,
+        };
+
+        let (_, facility_pid, _) = get_tenant_id(&req, &app_state).await?;
+        let payment = start_mpesa_payment(
+            &tenant_db,
+            facility_pid,
+            &bill,
+            amount,
+            phone_number,
+            claims.sub,
+        )
+        .await?;
+
+        return Ok(ApiResponse::new(
+            202,
+            json!({
+                "payment": bill_payment_json(&payment),
+                "bill": bill_json(&bill),
+                "message": "Payment requested. Ask the patient to complete the M-Pesa prompt",
+            }),
+        ));
+    }
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start payment transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to record payment" }))
+    })?;
+
+    let tendered_amount = data.tendered_amount.unwrap_or(amount);
+    let payment = tenant::entities::bill_payments::ActiveModel {
+        bill_id: Set(bill.id),
+        receipt_number: Set(document_number("RCT")),
+        method: Set(BillPaymentMethod::Cash),
+        status: Set(BillPaymentStatus::Completed),
+        amount: Set(amount),
+        tendered_amount: Set(Some(tendered_amount)),
+        change_amount: Set(Some(tendered_amount - amount)),
+        is_deposit: Set(bill.status == PatientBillStatus::Open),
+        received_by: Set(claims.sub),
+        paid_at: Set(Some(Utc::now().naive_utc())),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to record payment on {}: {}", bill.bill_number, err);
+        ApiResponse::new(500, json!({ "message": "Failed to record payment" }))
+    })?;
+
+    let bill_number = bill.bill_number.clone();
+    let bill = apply_payment(&txn, bill, amount).await?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit payment on {}: {}", bill_number, err);
+        ApiResponse::new(500, json!({ "message": "Failed to record payment" }))
+    })?;
+
+    // The payment is recorded either way; a failed posting is retried by the
+    // accumulator posting job.
+    if let Err(err) = post_out_of_pocket(&tenant_db, &app_state.main_db, &bill).await {
+        log::warn!(
+            "Out-of-pocket of payment on {} left for retry: {}",
+            bill.bill_number,
+            err
+        );
+    }
+
+    let facility_name = facility_name(&req, &app_state).await?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "payment": bill_payment_json(&payment),
+            "bill": bill_json(&bill),
+            "receipt": receipt_json(&facility_name, &bill, &payment),
+            "message": "Payment recorded successfully",
+        }),
+    ))
+}
+
+/// The receipt of a completed payment, with a printable image.
+pub async fn receipt(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let payment_pid = path.into_inner();
+
+    let (payment, bill) = tenant::entities::bill_payments::Entity::find_by_pid(payment_pid)
+        .find_also_related(tenant::entities::patient_bills::Entity)
+        .one(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch payment {}: {}", payment_pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch payment" }))
+        })?
+        .and_then(|(payment, bill)| bill.map(|bill| (payment, bill)))
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Payment not found" })))?;
+
+    if payment.status != BillPaymentStatus::Completed {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Only completed payments have receipts" }),
+        ));
+    }
+
+    let facility_name = facility_name(&req, &app_state).await?;
+
+    let document_file_pid = match payment.receipt_document_file_pid {
+        Some(document_file_pid) => document_file_pid,
+        None => {
+            let patient = main::entities::patients::Entity::find_by_pid(bill.patient_pid)
+                .one(&app_state.main_db)
+                .await
+                .map_err(|err| {
+                    log::error!("Failed to fetch patient {}: {}", bill.patient_pid, err);
+                    ApiResponse::new(500, json!({ "message": "Failed to fetch patient" }))
+                })?
+                .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Patient not found" })))?;
+
+            let html = receipt_html(&facility_name, &patient, &bill, &payment);
+            let s3_key = format!("bill_receipts/{}.png", Uuid::new_v4());
+            let document_file_pid = generate_png(
+                &html,
+                &req,
+                &app_state,
+                &s3_key,
+                Some(patient.id),
+                FileVisibility::Tenant,
+            )
+            .await?;
+
+            tenant::entities::bill_payments::ActiveModel {
+                id: Set(payment.id),
+                receipt_document_file_pid: Set(Some(document_file_pid)),
+                ..Default::default()
+            }
+            .update(&tenant_db)
+            .await
+            .map_err(|err| {
+                log::error!(
+                    "Failed to save receipt for payment {}: {}",
+                    payment.pid,
+                    err
+                );
+                ApiResponse::new(500, json!({ "message": "Failed to save receipt" }))
+            })?;
+
+            document_file_pid
+        }
+    };
+
+    let (url, _) =
+        authorized_file_url(&app_state, &req, document_file_pid, RECEIPT_URL_EXPIRY_SECS).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "receipt": receipt_json(&facility_name, &bill, &payment),
+            "url": url,
+            "expires_in": RECEIPT_URL_EXPIRY_SECS,
+            "message": "Receipt generated successfully",
+        }),
+    ))
+}
+
+/// M-Pesa's result for a bill payment, posted to the facility's callback.
+/// Only the payment whose checkout carries the callback token is updated.
+pub async fn mpesa_callback(
+    app_state: web::Data<AppState>,
+    path: web::Path<(Uuid, String)>,
+    payload: web::Json<MpesaCallbackRequest>,
+) -> Result<ApiResponse, ApiResponse> {
+    log::info!("M-Pesa bill callback received: {:?}", payload);
+
+    let (tenant_pid, callback_token) = path.into_inner();
+    let callback = &payload.body.stk_callback;
+
+    let facility = main::entities::tenants::Entity::find_by_pid(tenant_pid)
+        .filter(main::entities::tenants::Column::DeletedAt.is_null())
+        .one(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch tenant {}: {}", tenant_pid, err);
+            ApiResponse::new(500, json!({ "message": "Transaction lookup failed" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Transaction not found" })))?;
+
+    let tenant_db = app_state
+        .tenant_db(facility.sso_tenant_id)
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Transaction not found" })))?;
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start payment transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to update transaction" }))
+    })?;
+
+    let payment = tenant::entities::bill_payments::Entity::find()
+        .filter(
+            tenant::entities::bill_payments::Column::CheckoutRequestId
+                .eq(callback.checkout_request_id.clone()),
+        )
+        .filter(tenant::entities::bill_payments::Column::CallbackToken.eq(callback_token))
+        .filter(tenant::entities::bill_payments::Column::Status.eq(BillPaymentStatus::Pending))
+        .lock_exclusive()
+        .one(&txn)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to find bill payment: {}", err);
+            ApiResponse::new(500, json!({ "message": "Transaction lookup failed" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Transaction not found" })))?;
+
+    let receipt_number = callback.callback_metadata.as_ref().and_then(|metadata| {
+        metadata
+            .item
+            .iter()
+            .find(|item| item.name == "MpesaReceiptNumber")
+            .and_then(|item| item.value.as_str())
+            .map(str::to_string)
+    });
+
+    let payment_pid = payment.pid;
+    let bill_id = payment.bill_id;
+    let amount = payment.amount;
+    let mut active_model: tenant::entities::bill_payments::ActiveModel = payment.into();
+    if callback.result_code == 0 {
+        active_model.status = Set(BillPaymentStatus::Completed);
+        active_model.mpesa_receipt = Set(receipt_number);
+        active_model.paid_at = Set(Some(Utc::now().naive_utc()));
+    } else {
+        active_model.status = Set(BillPaymentStatus::Failed);
+        active_model.failure_reason = Set(Some(callback.result_desc.clone()));
+    }
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    active_model.update(&txn).await.map_err(|err| {
+        log::error!("Failed to record bill payment {}: {}", payment_pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to update transaction" }))
+    })?;
+
+    let mut paid_bill = None;
+    if callback.result_code == 0 {
+        let bill = tenant::entities::patient_bills::Entity::find_by_id(bill_id)
+            .lock_exclusive()
+            .one(&txn)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to fetch bill {}: {}", bill_id, err);
+                ApiResponse::new(500, json!({ "message": "Failed to update transaction" }))
+            })?
+            .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Transaction not found" })))?;
+
+        paid_bill = Some(apply_payment(&txn, bill, amount).await?);
+    }
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit bill payment {}: {}", payment_pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to update transaction" }))
+    })?;
+
+    if let Some(bill) = paid_bill {
+        // The payment is recorded either way; a failed posting is retried
+        // by the accumulator posting job.
+        if let Err(err) = post_out_of_pocket(&tenant_db, &app_state.main_db, &bill).await {
+            log::warn!(
+                "Out-of-pocket of M-Pesa payment on {} left for retry: {}",
+                bill.bill_number,
+                err
+            );
+        }
+    }
+
+    Ok(ApiResponse::new(
+        200,
+        json!({ "ResultCode": 0, "ResultDesc": "Callback processed successfully" }),
+    ))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CashierParams {
+    /// Defaults to today.
+    pub business_date: Option<NaiveDate>,
+}
+
+/// The logged-in cashier's takings for the day that have not been
+/// reconciled yet.
+pub async fn cashier_summary(
+    app_state: web::Data<AppState>,
+    query: web::Query<CashierParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let business_date = query
+        .business_date
+        .unwrap_or_else(|| Utc::now().date_naive());
+
+    let payments = unreconciled_payments(&tenant_db, claims.sub, business_date).await?;
+    let (cash_total, mpesa_total) = payment_totals(&payments);
+
+    let reconciliation = tenant::entities::cashier_reconciliations::Entity::find()
+        .filter(tenant::entities::cashier_reconciliations::Column::CashierId.eq(claims.sub))
+        .filter(tenant::entities::cashier_reconciliations::Column::BusinessDate.eq(business_date))
+        .one(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch reconciliation: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch cashier summary" }))
+        })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "business_date": business_date,
+            "cash_total": cash_total,
+            "mpesa_total": mpesa_total,
+            "payment_count": payments.len(),
+            "payments": payments.iter().map(bill_payment_json).collect::<Vec<_>>(),
+            "reconciliation": reconciliation.as_ref().map(reconciliation_json),
+            "message": "Cashier summary fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ReconcileData {
+    pub business_date: Option<NaiveDate>,
+    /// Cash in the drawer, counted by the cashier.
+    pub cash_counted: Option<Decimal>,
+    pub notes: Option<String>,
+}
+
+/// Closes the logged-in cashier's day: the counted cash is checked against
+/// the cash received and the day's payments are tied to the
+/// reconciliation. M-Pesa takings are recorded for the report but settle
+/// straight into the facility's account.
+pub async fn reconcile(
+    app_state: web::Data<AppState>,
+    data: web::Json<ReconcileData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let cash_counted = match data.cash_counted {
+        Some(cash_counted) if cash_counted >= Decimal::ZERO => cash_counted,
+        _ => {
+            return Err(ApiResponse::new(
+                400,
+                json!(ValidationError {
+                    errors: HashMap::from([(
+                        "cash_counted".to_string(),
+                        "Counted cash is required and cannot be negative".to_string(),
+                    )]),
+                }),
+            ));
+        }
+    };
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let business_date = data
+        .business_date
+        .unwrap_or_else(|| Utc::now().date_naive());
+
+    let existing = tenant::entities::cashier_reconciliations::Entity::find()
+        .filter(tenant::entities::cashier_reconciliations::Column::CashierId.eq(claims.sub))
+        .filter(tenant::entities::cashier_reconciliations::Column::BusinessDate.eq(business_date))
+        .one(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch reconciliation: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to reconcile" }))
+        })?;
+
+    if existing.is_some() {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "This day has already been reconciled" }),
+        ));
+    }
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start reconciliation transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to reconcile" }))
+    })?;
+
+    let payments = unreconciled_payments(&txn, claims.sub, business_date).await?;
+    let (cash_expected, mpesa_total) = payment_totals(&payments);
+
+    let reconciliation = tenant::entities::cashier_reconciliations::ActiveModel {
+        cashier_id: Set(claims.sub),
+        business_date: Set(business_date),
+        cash_expected: Set(cash_expected),
+        cash_counted: Set(cash_counted),
+        cash_variance: Set(cash_counted - cash_expected),
+        mpesa_total: Set(mpesa_total),
+        payment_count: Set(payments.len() as i32),
+        notes: Set(data.notes.clone()),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to save reconciliation: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to reconcile" }))
+    })?;
+
+    tenant::entities::bill_payments::Entity::update_many()
+        .col_expr(
+            tenant::entities::bill_payments::Column::ReconciliationId,
+            tenant::migrations::Expr::value(reconciliation.id),
+        )
+        .filter(
+            tenant::entities::bill_payments::Column::Id.is_in(
+                payments
+                    .iter()
+                    .map(|payment| payment.id)
+                    .collect::<Vec<_>>(),
+            ),
+        )
+        .exec(&txn)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to link payments to reconciliation: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to reconcile" }))
+        })?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit reconciliation: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to reconcile" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "reconciliation": reconciliation_json(&reconciliation),
+            "message": "Day reconciled successfully",
+        }),
+    ))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ReconciliationParams {
+    pub cashier_id: Option<Uuid>,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+}
+
+pub async fn index_reconciliations(
+    app_state: web::Data<AppState>,
+    query: web::Query<ReconciliationParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let mut stmt = tenant::entities::cashier_reconciliations::Entity::find();
+
+    if let Some(cashier_id) = query.cashier_id {
+        stmt = stmt
+            .filter(tenant::entities::cashier_reconciliations::Column::CashierId.eq(cashier_id));
+    }
+
+    if let Some(from) = query.from {
+        stmt =
+            stmt.filter(tenant::entities::cashier_reconciliations::Column::BusinessDate.gte(from));
+    }
+
+    if let Some(to) = query.to {
+        stmt = stmt.filter(tenant::entities::cashier_reconciliations::Column::BusinessDate.lte(to));
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let paginator = stmt
+        .order_by_desc(tenant::entities::cashier_reconciliations::Column::BusinessDate)
+        .paginate(&tenant_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
+
+    let reconciliations = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "reconciliations": reconciliations.iter().map(reconciliation_json).collect::<Vec<_>>(),
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "Reconciliations fetched successfully",
+        }),
+    ))
+}
+
+pub async fn show_mpesa_settings(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let settings = facility_mpesa_settings(&tenant_db).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "mpesa_settings": settings.as_ref().map(mpesa_settings_json),
+            "message": "M-Pesa settings fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct MpesaSettingsData {
+    pub shortcode: Option<String>,
+    /// The till money goes to on a buy goods shortcode.
+    pub till_number: Option<String>,
+    pub transaction_type: Option<String>,
+    pub passkey: Option<String>,
+    pub consumer_key: Option<String>,
+    pub consumer_secret: Option<String>,
+    pub is_active: Option<bool>,
+}
+
+impl MpesaSettingsData {
+    pub fn validate(&self, is_create: bool) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        for (field, value) in [
+            ("shortcode", &self.shortcode),
+            ("till_number", &self.till_number),
+        ] {
+            if let Some(value) = value
+                && (value.is_empty()
+                    || value.len() > 20
+                    || !value.chars().all(|c| c.is_ascii_digit()))
+            {
+                errors.insert(field.to_string(), "Must be up to 20 digits".to_string());
+            }
+        }
+
+        if let Some(transaction_type) = &self.transaction_type
+            && transaction_type != MPESA_PAYBILL
+            && transaction_type != MPESA_BUY_GOODS
+        {
+            errors.insert(
+                "transaction_type".to_string(),
+                format!("Must be {} or {}", MPESA_PAYBILL, MPESA_BUY_GOODS),
+            );
+        }
+
+        if self.transaction_type.as_deref() == Some(MPESA_BUY_GOODS) && self.till_number.is_none() {
+            errors.insert(
+                "till_number".to_string(),
+                "Till number is required for buy goods".to_string(),
+            );
+        }
+
+        if is_create {
+            for (field, value) in [
+                ("shortcode", &self.shortcode),
+                ("transaction_type", &self.transaction_type),
+                ("passkey", &self.passkey),
+                ("consumer_key", &self.consumer_key),
+                ("consumer_secret", &self.consumer_secret),
+            ] {
+                if value.as_deref().is_none_or(|v| v.trim().is_empty()) {
+                    errors
+                        .entry(field.to_string())
+                        .or_insert_with(|| "This field is required".to_string());
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+fn encrypted_secret(value: &str) -> Result<String, ApiResponse> {
+    encrypt_string(value.trim()).map_err(|err| {
+        log::error!("Failed to encrypt M-Pesa credentials: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to save M-Pesa settings" }))
+    })
+}
+
+/// Sets the facility's own M-Pesa shortcode and Daraja credentials. The
+/// passkey and consumer secret are stored encrypted, and the credentials
+/// are kept when left out of an update.
+pub async fn update_mpesa_settings(
+    app_state: web::Data<AppState>,
+    data: web::Json<MpesaSettingsData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let existing = facility_mpesa_settings(&tenant_db).await?;
+
+    if let Err(err) = data.validate(existing.is_none()) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let settings = match existing {
+        Some(settings) => {
+            let mut active_model: tenant::entities::facility_mpesa_settings::ActiveModel =
+                settings.into();
+            if let Some(shortcode) = &data.shortcode {
+                active_model.shortcode = Set(shortcode.clone());
+            }
+            if let Some(transaction_type) = &data.transaction_type {
+                active_model.transaction_type = Set(transaction_type.clone());
+                if transaction_type == MPESA_PAYBILL {
+                    active_model.till_number = Set(None);
+                }
+            }
+            if let Some(till_number) = &data.till_number {
+                active_model.till_number = Set(Some(till_number.clone()));
+            }
+            if let Some(passkey) = &data.passkey {
+                active_model.passkey = Set(encrypted_secret(passkey)?);
+            }
+            if let Some(consumer_key) = &data.consumer_key {
+                active_model.consumer_key = Set(consumer_key.trim().to_string());
+            }
+            if let Some(consumer_secret) = &data.consumer_secret {
+                active_model.consumer_secret = Set(encrypted_secret(consumer_secret)?);
+            }
+            if let Some(is_active) = data.is_active {
+                active_model.is_active = Set(is_active);
+            }
+            active_model.updated_by = Set(claims.sub);
+            active_model.updated_at = Set(Utc::now().naive_utc());
+
+            active_model.update(&tenant_db).await
+        }
+        None => {
+            tenant::entities::facility_mpesa_settings::ActiveModel {
+                shortcode: Set(data.shortcode.clone().unwrap_or_default()),
+                till_number: Set(data.till_number.clone()),
+                transaction_type: Set(data.transaction_type.clone().unwrap_or_default()),
+                passkey: Set(encrypted_secret(
+                    data.passkey.as_deref().unwrap_or_default(),
+                )?),
+                consumer_key: Set(data
+                    .consumer_key
+                    .clone()
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string()),
+                consumer_secret: Set(encrypted_secret(
+                    data.consumer_secret.as_deref().unwrap_or_default(),
+                )?),
+                is_active: Set(data.is_active.unwrap_or(true)),
+                updated_by: Set(claims.sub),
+                ..Default::default()
+            }
+            .insert(&tenant_db)
+            .await
+        }
+    }
+    .map_err(|err| {
+        log::error!("Failed to save M-Pesa settings: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to save M-Pesa settings" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "mpesa_settings": mpesa_settings_json(&settings),
+            "message": "M-Pesa settings saved successfully",
+        }),
+    ))
+}
@@ -123,6 +123,7 @@ pub async fn show(
         .column(main::entities::payment_transactions::Column::Status)
         .column(main::entities::payment_transactions::Column::PaymentMethod)
         .column(main::entities::payment_transactions::Column::InvoiceUrl)
+        .column(main::entities::payment_transactions::Column::InvoiceFilePid)
         .column(main::entities::payment_transactions::Column::Description)
         .column(main::entities::payment_transactions::Column::FailureReason)
         .column(main::entities::payment_transactions::Column::Metadata)
@@ -165,6 +166,7 @@ pub async fn show(
                 "status": payment_transaction.status,
                 "payment_method": payment_transaction.payment_method,
                 "invoice_url": payment_transaction.invoice_url,
+                "invoice_file_pid": payment_transaction.invoice_file_pid,
                 "description": payment_transaction.description,
                 "failure_reason": payment_transaction.failure_reason,
                 "metadata": payment_transaction.metadata,
@@ -202,6 +204,13 @@ pub struct PaymentTransactionCreateRequest {
     // Mpesa fields
     pub country_code: Option<String>,
     pub phone_number: Option<String>,
+    /// Shown to the payer in place of the subscription reference. Set by
+    /// other checkouts, never read from the request body.
+    #[serde(skip)]
+    pub account_reference: Option<String>,
+    /// Where M-Pesa posts the result; defaults to the subscription callback.
+    #[serde(skip)]
+    pub callback_url: Option<String>,
     // PayPal fields
     pub paypal_email: Option<String>,
     // Card / Stripe fields
@@ -574,8 +583,14 @@ impl PaymentProcessor for MpesaProcessor {
             "PartyA": phone_number,
             "PartyB": (utils::constants::MPESA_SHORTCODE).clone(),
             "PhoneNumber": phone_number,
-            "CallBackURL": (utils::constants::MPESA_CALLBACK_URL).clone(),
-            "AccountReference": format!("Subscription {}", data.subscription_id),
+            "CallBackURL": data
+                .callback_url
+                .clone()
+                .unwrap_or_else(|| (utils::constants::MPESA_CALLBACK_URL).clone()),
+            "AccountReference": data
+                .account_reference
+                .clone()
+                .unwrap_or_else(|| format!("Subscription {}", data.subscription_id)),
             "TransactionDesc": data.description.as_ref().unwrap_or(&"Payment for subscription".to_string()),
         });
 
@@ -946,7 +961,7 @@ pub async fn create(
 
     let items = vec![("Subscription fee", 1, data.amount)];
 
-    let invoice_url = send_invoice_email(
+    let invoice_file_pid = send_invoice_email(
         tenant_email,
         &tenant.name,
         &invoice_number,
@@ -973,7 +988,7 @@ pub async fn create(
         currency: Set(data.currency.clone()),
         status: Set(PaymentStatus::Pending),
         payment_method: Set(payment_method.clone()),
-        invoice_url: Set(Some(invoice_url)),
+        invoice_file_pid: Set(Some(invoice_file_pid)),
         description: Set(data.description.clone()),
         failure_reason: Set(result.failure_reason.clone()),
         metadata: Set(Some(json!({
@@ -0,0 +1,1493 @@
+use std::collections::HashMap;
+
+use actix_web::{HttpRequest, web};
+use chrono::{NaiveDate, NaiveDateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    db::tenant::{
+        self,
+        entities::sea_orm_active_enums::{PharmacyStoreType, StockMovementType},
+        migrations::sea_orm::{
+            ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait,
+            PaginatorTrait, QueryFilter, QueryOrder, Set, TransactionTrait,
+        },
+    },
+    handlers::services::{
+        pharmacy::{
+            NewMovement, batch_balances, batch_json, batch_on_hand, find_batch, find_product,
+            find_store, lock_product_batches, pick_fefo, product_json, receive_stock,
+            record_movement, stock_movement_json, store_batches, store_json,
+        },
+        pricing::find_service,
+    },
+    utils::{
+        api_response::ApiResponse,
+        app_state::AppState,
+        jwt::{get_logged_in_user_claims, get_tenant_db},
+        validator_error::ValidationError,
+    },
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ProductData {
+    pub sku: Option<String>,
+    pub generic_name: Option<String>,
+    pub brand_name: Option<String>,
+    pub strength: Option<String>,
+    pub dosage_form: Option<String>,
+    pub pack_size: Option<i32>,
+    pub unit: Option<String>,
+    pub category: Option<String>,
+    pub reorder_level: Option<i32>,
+    /// Selling price per dispensing unit.
+    pub unit_price: Option<Decimal>,
+    /// Catalogue service whose payer prices replace `unit_price` when the
+    /// product is dispensed.
+    pub service_pid: Option<Uuid>,
+    /// Narcotics and psychotropics whose movements go in the controlled
+    /// substances register.
+    pub is_controlled: Option<bool>,
+    pub controlled_schedule: Option<String>,
+    /// Listed in the patient-facing online store. Controlled products are
+    /// never sold online.
+    pub is_sold_online: Option<bool>,
+    /// Online orders for the product need an uploaded prescription.
+    pub requires_prescription: Option<bool>,
+    pub is_active: Option<bool>,
+}
+
+impl ProductData {
+    pub fn validate(&self, is_create: bool) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        match self.generic_name.as_deref().map(str::trim) {
+            None if is_create => {
+                errors.insert(
+                    "generic_name".to_string(),
+                    "Generic name is required".to_string(),
+                );
+            }
+            Some("") => {
+                errors.insert(
+                    "generic_name".to_string(),
+                    "Generic name is required".to_string(),
+                );
+            }
+            Some(name) if name.len() > 150 => {
+                errors.insert(
+                    "generic_name".to_string(),
+                    "Generic name must be at most 150 characters".to_string(),
+                );
+            }
+            _ => {}
+        }
+
+        match self.dosage_form.as_deref().map(str::trim) {
+            None if is_create => {
+                errors.insert(
+                    "dosage_form".to_string(),
+                    "Dosage form is required".to_string(),
+                );
+            }
+            Some("") => {
+                errors.insert(
+                    "dosage_form".to_string(),
+                    "Dosage form is required".to_string(),
+                );
+            }
+            Some(form) if form.len() > 50 => {
+                errors.insert(
+                    "dosage_form".to_string(),
+                    "Dosage form must be at most 50 characters".to_string(),
+                );
+            }
+            _ => {}
+        }
+
+        if self.brand_name.as_ref().is_some_and(|b| b.len() > 150) {
+            errors.insert(
+                "brand_name".to_string(),
+                "Brand name must be at most 150 characters".to_string(),
+            );
+        }
+
+        if self.strength.as_ref().is_some_and(|s| s.len() > 50) {
+            errors.insert(
+                "strength".to_string(),
+                "Strength must be at most 50 characters".to_string(),
+            );
+        }
+
+        if self.sku.as_ref().is_some_and(|s| s.len() > 50) {
+            errors.insert(
+                "sku".to_string(),
+                "SKU must be at most 50 characters".to_string(),
+            );
+        }
+
+        if self
+            .unit
+            .as_ref()
+            .is_some_and(|u| u.trim().is_empty() || u.len() > 30)
+        {
+            errors.insert(
+                "unit".to_string(),
+                "Unit must be between 1 and 30 characters".to_string(),
+            );
+        }
+
+        if self.category.as_ref().is_some_and(|c| c.len() > 100) {
+            errors.insert(
+                "category".to_string(),
+                "Category must be at most 100 characters".to_string(),
+            );
+        }
+
+        if self.pack_size.is_some_and(|p| p < 1) {
+            errors.insert(
+                "pack_size".to_string(),
+                "Pack size must be at least 1".to_string(),
+            );
+        }
+
+        if self.reorder_level.is_some_and(|r| r < 0) {
+            errors.insert(
+                "reorder_level".to_string(),
+                "Reorder level cannot be negative".to_string(),
+            );
+        }
+
+        if self.unit_price.is_some_and(|p| p.is_sign_negative()) {
+            errors.insert(
+                "unit_price".to_string(),
+                "Unit price cannot be negative".to_string(),
+            );
+        }
+
+        if self
+            .controlled_schedule
+            .as_ref()
+            .is_some_and(|s| s.len() > 30)
+        {
+            errors.insert(
+                "controlled_schedule".to_string(),
+                "Controlled schedule must be at most 30 characters".to_string(),
+            );
+        }
+
+        if self.is_controlled == Some(true) && self.is_sold_online == Some(true) {
+            errors.insert(
+                "is_sold_online".to_string(),
+                "Controlled substances cannot be sold online".to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ProductParams {
+    pub search: Option<String>,
+    pub category: Option<String>,
+    pub include_inactive: Option<bool>,
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+}
+
+pub async fn index_products(
+    app_state: web::Data<AppState>,
+    query: web::Query<ProductParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let mut stmt = tenant::entities::pharmacy_products::Entity::find()
+        .filter(tenant::entities::pharmacy_products::Column::DeletedAt.is_null());
+
+    if !query.include_inactive.unwrap_or(false) {
+        stmt = stmt.filter(tenant::entities::pharmacy_products::Column::IsActive.eq(true));
+    }
+
+    if let Some(category) = &query.category {
+        stmt = stmt.filter(tenant::entities::pharmacy_products::Column::Category.eq(category));
+    }
+
+    if let Some(term) = &query.search {
+        use tenant::migrations::{Expr, extension::postgres::PgExpr};
+
+        let like = format!("%{}%", term);
+        stmt = stmt.filter(
+            Condition::any()
+                .add(
+                    Expr::col(tenant::entities::pharmacy_products::Column::GenericName)
+                        .ilike(like.clone()),
+                )
+                .add(
+                    Expr::col(tenant::entities::pharmacy_products::Column::BrandName)
+                        .ilike(like.clone()),
+                )
+                .add(Expr::col(tenant::entities::pharmacy_products::Column::Sku).ilike(like)),
+        );
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(10).clamp(1, 100);
+    let paginator = stmt
+        .order_by_asc(tenant::entities::pharmacy_products::Column::GenericName)
+        .paginate(&tenant_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
+
+    let products = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let totals = product_totals(&tenant_db, products.iter().map(|p| p.id).collect()).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "products": products
+                .iter()
+                .map(|product| {
+                    let mut data = product_json(product);
+                    data["on_hand"] = json!(totals.get(&product.id).copied().unwrap_or(0));
+                    data
+                })
+                .collect::<Vec<_>>(),
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "Products fetched successfully",
+        }),
+    ))
+}
+
+pub async fn create_product(
+    app_state: web::Data<AppState>,
+    data: web::Json<ProductData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate(true) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    if let Some(sku) = &data.sku {
+        ensure_sku_available(&tenant_db, sku, None).await?;
+    }
+    let service = match data.service_pid {
+        Some(service_pid) => Some(find_service(&tenant_db, service_pid).await?),
+        None => None,
+    };
+
+    let product = tenant::entities::pharmacy_products::ActiveModel {
+        sku: Set(data.sku.clone()),
+        generic_name: Set(data
+            .generic_name
+            .as_deref()
+            .unwrap_or_default()
+            .trim()
+            .to_string()),
+        brand_name: Set(data.brand_name.clone()),
+        strength: Set(data.strength.clone()),
+        dosage_form: Set(data
+            .dosage_form
+            .as_deref()
+            .unwrap_or_default()
+            .trim()
+            .to_string()),
+        pack_size: Set(data.pack_size.unwrap_or(1)),
+        unit: Set(data.unit.clone().unwrap_or_else(|| "unit".to_string())),
+        category: Set(data.category.clone()),
+        reorder_level: Set(data.reorder_level.unwrap_or(0)),
+        unit_price: Set(data.unit_price.unwrap_or_default()),
+        service_id: Set(service.map(|service| service.id)),
+        is_controlled: Set(data.is_controlled.unwrap_or(false)),
+        controlled_schedule: Set(data.controlled_schedule.clone()),
+        is_sold_online: Set(data.is_sold_online.unwrap_or(false)),
+        requires_prescription: Set(data.requires_prescription.unwrap_or(false)),
+        is_active: Set(data.is_active.unwrap_or(true)),
+        ..Default::default()
+    }
+    .insert(&tenant_db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to create product: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create product" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "product": product_json(&product),
+            "message": "Product created successfully",
+        }),
+    ))
+}
+
+/// The product with its stock in every store, batch by batch, soonest
+/// expiry first.
+pub async fn show_product(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let product = find_product(&tenant_db, path.into_inner()).await?;
+
+    let stores = tenant::entities::pharmacy_stores::Entity::find()
+        .filter(tenant::entities::pharmacy_stores::Column::DeletedAt.is_null())
+        .order_by_asc(tenant::entities::pharmacy_stores::Column::Name)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch stores: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch product" }))
+        })?;
+
+    let mut stock = Vec::new();
+    let mut total_on_hand = 0;
+    for store in &stores {
+        let batches = store_batches(&tenant_db, store.id, product.id).await?;
+        if batches.is_empty() {
+            continue;
+        }
+
+        let on_hand: i64 = batches.iter().map(|line| line.quantity).sum();
+        total_on_hand += on_hand;
+        stock.push(json!({
+            "store": store_json(store),
+            "on_hand": on_hand,
+            "batches": batches
+                .iter()
+                .map(|line| batch_json(&line.batch, Some(line.quantity)))
+                .collect::<Vec<_>>(),
+        }));
+    }
+
+    let mut data = product_json(&product);
+    data["on_hand"] = json!(total_on_hand);
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "product": data,
+            "stock": stock,
+            "message": "Product fetched successfully",
+        }),
+    ))
+}
+
+pub async fn edit_product(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<ProductData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate(false) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let product = find_product(&tenant_db, path.into_inner()).await?;
+    let product_pid = product.pid;
+
+    let mut active_model: tenant::entities::pharmacy_products::ActiveModel = product.into();
+
+    if let Some(sku) = &data.sku {
+        ensure_sku_available(&tenant_db, sku, Some(product_pid)).await?;
+        active_model.sku = Set(Some(sku.clone()));
+    }
+    if let Some(generic_name) = &data.generic_name {
+        active_model.generic_name = Set(generic_name.trim().to_string());
+    }
+    if let Some(brand_name) = &data.brand_name {
+        active_model.brand_name = Set(Some(brand_name.clone()));
+    }
+    if let Some(strength) = &data.strength {
+        active_model.strength = Set(Some(strength.clone()));
+    }
+    if let Some(dosage_form) = &data.dosage_form {
+        active_model.dosage_form = Set(dosage_form.trim().to_string());
+    }
+    if let Some(pack_size) = data.pack_size {
+        active_model.pack_size = Set(pack_size);
+    }
+    if let Some(unit) = &data.unit {
+        active_model.unit = Set(unit.clone());
+    }
+    if let Some(category) = &data.category {
+        active_model.category = Set(Some(category.clone()));
+    }
+    if let Some(reorder_level) = data.reorder_level {
+        active_model.reorder_level = Set(reorder_level);
+    }
+    if let Some(unit_price) = data.unit_price {
+        active_model.unit_price = Set(unit_price);
+    }
+    if let Some(service_pid) = data.service_pid {
+        active_model.service_id = Set(Some(find_service(&tenant_db, service_pid).await?.id));
+    }
+    if let Some(is_controlled) = data.is_controlled {
+        active_model.is_controlled = Set(is_controlled);
+    }
+    if let Some(controlled_schedule) = &data.controlled_schedule {
+        active_model.controlled_schedule = Set(Some(controlled_schedule.clone()));
+    }
+    if let Some(is_sold_online) = data.is_sold_online {
+        active_model.is_sold_online = Set(is_sold_online);
+    }
+    if let Some(requires_prescription) = data.requires_prescription {
+        active_model.requires_prescription = Set(requires_prescription);
+    }
+    if let Some(is_active) = data.is_active {
+        active_model.is_active = Set(is_active);
+    }
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let product = active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!("Failed to update product {}: {}", product_pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to update product" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "product": product_json(&product),
+            "message": "Product updated successfully",
+        }),
+    ))
+}
+
+/// Products still holding stock cannot be removed; write the stock off or
+/// deactivate the product instead.
+pub async fn destroy_product(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let product = find_product(&tenant_db, path.into_inner()).await?;
+
+    let totals = product_totals(&tenant_db, vec![product.id]).await?;
+    if totals.get(&product.id).copied().unwrap_or(0) > 0 {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Product still has stock on hand" }),
+        ));
+    }
+
+    let product_pid = product.pid;
+    let mut active_model: tenant::entities::pharmacy_products::ActiveModel = product.into();
+    active_model.deleted_at = Set(Some(Utc::now().naive_utc()));
+    active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!("Failed to delete product {}: {}", product_pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to delete product" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({ "message": "Product deleted successfully" }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct StoreData {
+    pub name: Option<String>,
+    pub code: Option<String>,
+    pub store_type: Option<PharmacyStoreType>,
+    pub location: Option<String>,
+    pub manager_id: Option<Uuid>,
+    /// Where the daily stock alert digest is emailed.
+    pub alert_email: Option<String>,
+    /// Where the daily stock alert digest is texted.
+    pub alert_phone: Option<String>,
+    pub is_active: Option<bool>,
+}
+
+impl StoreData {
+    pub fn validate(&self, is_create: bool) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        match self.name.as_deref().map(str::trim) {
+            None if is_create => {
+                errors.insert("name".to_string(), "Store name is required".to_string());
+            }
+            Some("") => {
+                errors.insert("name".to_string(), "Store name is required".to_string());
+            }
+            Some(name) if name.len() > 100 => {
+                errors.insert(
+                    "name".to_string(),
+                    "Store name must be at most 100 characters".to_string(),
+                );
+            }
+            _ => {}
+        }
+
+        match self.code.as_deref().map(str::trim) {
+            None if is_create => {
+                errors.insert("code".to_string(), "Store code is required".to_string());
+            }
+            Some("") => {
+                errors.insert("code".to_string(), "Store code is required".to_string());
+            }
+            Some(code) if code.len() > 20 => {
+                errors.insert(
+                    "code".to_string(),
+                    "Store code must be at most 20 characters".to_string(),
+                );
+            }
+            _ => {}
+        }
+
+        if self.location.as_ref().is_some_and(|l| l.len() > 150) {
+            errors.insert(
+                "location".to_string(),
+                "Location must be at most 150 characters".to_string(),
+            );
+        }
+
+        if let Some(email) = &self.alert_email
+            && (email.len() > 100 || !email.contains('@'))
+        {
+            errors.insert(
+                "alert_email".to_string(),
+                "Please provide a valid email address".to_string(),
+            );
+        }
+
+        if self.alert_phone.as_ref().is_some_and(|p| p.len() > 20) {
+            errors.insert(
+                "alert_phone".to_string(),
+                "Alert phone must be at most 20 characters".to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+pub async fn index_stores(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let stores = tenant::entities::pharmacy_stores::Entity::find()
+        .filter(tenant::entities::pharmacy_stores::Column::DeletedAt.is_null())
+        .order_by_asc(tenant::entities::pharmacy_stores::Column::Name)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch stores: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch stores" }))
+        })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "stores": stores.iter().map(store_json).collect::<Vec<_>>(),
+            "message": "Stores fetched successfully",
+        }),
+    ))
+}
+
+pub async fn create_store(
+    app_state: web::Data<AppState>,
+    data: web::Json<StoreData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate(true) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let code = data
+        .code
+        .as_deref()
+        .unwrap_or_default()
+        .trim()
+        .to_uppercase();
+    ensure_store_code_available(&tenant_db, &code, None).await?;
+
+    let store = tenant::entities::pharmacy_stores::ActiveModel {
+        name: Set(data.name.as_deref().unwrap_or_default().trim().to_string()),
+        code: Set(code),
+        store_type: Set(data
+            .store_type
+            .clone()
+            .unwrap_or(PharmacyStoreType::Dispensary)),
+        location: Set(data.location.clone()),
+        manager_id: Set(data.manager_id),
+        alert_email: Set(data.alert_email.as_deref().map(|e| e.trim().to_lowercase())),
+        alert_phone: Set(data.alert_phone.clone()),
+        is_active: Set(data.is_active.unwrap_or(true)),
+        ..Default::default()
+    }
+    .insert(&tenant_db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to create store: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create store" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "store": store_json(&store),
+            "message": "Store created successfully",
+        }),
+    ))
+}
+
+pub async fn edit_store(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<StoreData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate(false) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let store = find_store(&tenant_db, path.into_inner()).await?;
+    let store_pid = store.pid;
+
+    let mut active_model: tenant::entities::pharmacy_stores::ActiveModel = store.into();
+
+    if let Some(name) = &data.name {
+        active_model.name = Set(name.trim().to_string());
+    }
+    if let Some(code) = &data.code {
+        let code = code.trim().to_uppercase();
+        ensure_store_code_available(&tenant_db, &code, Some(store_pid)).await?;
+        active_model.code = Set(code);
+    }
+    if let Some(store_type) = &data.store_type {
+        active_model.store_type = Set(store_type.clone());
+    }
+    if let Some(location) = &data.location {
+        active_model.location = Set(Some(location.clone()));
+    }
+    if let Some(manager_id) = data.manager_id {
+        active_model.manager_id = Set(Some(manager_id));
+    }
+    if let Some(alert_email) = &data.alert_email {
+        active_model.alert_email = Set(Some(alert_email.trim().to_lowercase()));
+    }
+    if let Some(alert_phone) = &data.alert_phone {
+        active_model.alert_phone = Set(Some(alert_phone.clone()));
+    }
+    if let Some(is_active) = data.is_active {
+        active_model.is_active = Set(is_active);
+    }
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let store = active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!("Failed to update store {}: {}", store_pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to update store" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "store": store_json(&store),
+            "message": "Store updated successfully",
+        }),
+    ))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct StockParams {
+    pub store_pid: Uuid,
+    pub below_reorder: Option<bool>,
+}
+
+/// Stock on hand in a store per product, with the batch that will be
+/// picked next.
+pub async fn stock_on_hand(
+    app_state: web::Data<AppState>,
+    query: web::Query<StockParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let store = find_store(&tenant_db, query.store_pid).await?;
+
+    let products = tenant::entities::pharmacy_products::Entity::find()
+        .filter(tenant::entities::pharmacy_products::Column::DeletedAt.is_null())
+        .filter(tenant::entities::pharmacy_products::Column::IsActive.eq(true))
+        .order_by_asc(tenant::entities::pharmacy_products::Column::GenericName)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch products: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch stock on hand" }))
+        })?;
+
+    let balances = batch_balances(&tenant_db, Some(store.id), None).await?;
+    let batch_ids: Vec<i32> = balances.iter().map(|(_, batch_id, _)| *batch_id).collect();
+    let batches: HashMap<i32, tenant::entities::stock_batches::Model> =
+        tenant::entities::stock_batches::Entity::find()
+            .filter(tenant::entities::stock_batches::Column::Id.is_in(batch_ids))
+            .all(&tenant_db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to fetch batches: {}", err);
+                ApiResponse::new(500, json!({ "message": "Failed to fetch stock on hand" }))
+            })?
+            .into_iter()
+            .map(|batch| (batch.id, batch))
+            .collect();
+
+    let today = Utc::now().date_naive();
+    let mut by_product: HashMap<i32, Vec<(&tenant::entities::stock_batches::Model, i64)>> =
+        HashMap::new();
+    for (_, batch_id, on_hand) in &balances {
+        if let Some(batch) = batches.get(batch_id) {
+            by_product
+                .entry(batch.product_id)
+                .or_default()
+                .push((batch, *on_hand));
+        }
+    }
+
+    let stock = products
+        .iter()
+        .filter_map(|product| {
+            let mut lines = by_product.remove(&product.id).unwrap_or_default();
+            lines.sort_by_key(|(batch, _)| (batch.expiry_date, batch.id));
+
+            let on_hand: i64 = lines.iter().map(|(_, quantity)| quantity).sum();
+            let usable: i64 = lines
+                .iter()
+                .filter(|(batch, _)| batch.expiry_date >= today)
+                .map(|(_, quantity)| quantity)
+                .sum();
+            let below_reorder = usable <= product.reorder_level as i64;
+
+            if query.below_reorder.unwrap_or(false) && !below_reorder {
+                return None;
+            }
+            if on_hand == 0 && !below_reorder {
+                return None;
+            }
+
+            Some(json!({
+                "product": product_json(product),
+                "on_hand": on_hand,
+                "usable": usable,
+                "expired": on_hand - usable,
+                "below_reorder": below_reorder,
+                "next_batch": lines
+                    .iter()
+                    .find(|(batch, _)| batch.expiry_date >= today)
+                    .map(|(batch, quantity)| batch_json(batch, Some(*quantity))),
+            }))
+        })
+        .collect::<Vec<_>>();
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "store": store_json(&store),
+            "stock": stock,
+            "message": "Stock on hand fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct FefoParams {
+    pub store_pid: Uuid,
+    pub product_pid: Uuid,
+    pub quantity: i64,
+}
+
+/// Previews which batches a withdrawal of `quantity` would come from.
+pub async fn fefo_pick(
+    app_state: web::Data<AppState>,
+    query: web::Query<FefoParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if query.quantity < 1 {
+        return Err(ApiResponse::new(
+            400,
+            json!({ "message": "Quantity must be at least 1" }),
+        ));
+    }
+
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let store = find_store(&tenant_db, query.store_pid).await?;
+    let product = find_product(&tenant_db, query.product_pid).await?;
+    let picks = pick_fefo(&tenant_db, store.id, product.id, query.quantity).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "product": product_json(&product),
+            "picks": picks
+                .iter()
+                .map(|pick| json!({
+                    "batch": batch_json(&pick.batch, None),
+                    "quantity": pick.quantity,
+                }))
+                .collect::<Vec<_>>(),
+            "message": "Batches picked successfully",
+        }),
+    ))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct MovementParams {
+    pub store_pid: Option<Uuid>,
+    pub product_pid: Option<Uuid>,
+    pub movement_type: Option<StockMovementType>,
+    pub from: Option<NaiveDateTime>,
+    pub to: Option<NaiveDateTime>,
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+}
+
+/// The stock ledger, newest first.
+pub async fn index_movements(
+    app_state: web::Data<AppState>,
+    query: web::Query<MovementParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let mut stmt = tenant::entities::stock_movements::Entity::find()
+        .find_also_related(tenant::entities::stock_batches::Entity);
+
+    if let Some(store_pid) = query.store_pid {
+        let store = find_store(&tenant_db, store_pid).await?;
+        stmt = stmt.filter(tenant::entities::stock_movements::Column::StoreId.eq(store.id));
+    }
+
+    if let Some(product_pid) = query.product_pid {
+        let product = find_product(&tenant_db, product_pid).await?;
+        stmt = stmt.filter(tenant::entities::stock_movements::Column::ProductId.eq(product.id));
+    }
+
+    if let Some(movement_type) = &query.movement_type {
+        stmt = stmt.filter(
+            tenant::entities::stock_movements::Column::MovementType.eq(movement_type.clone()),
+        );
+    }
+
+    if let Some(from) = query.from {
+        stmt = stmt.filter(tenant::entities::stock_movements::Column::CreatedAt.gte(from));
+    }
+
+    if let Some(to) = query.to {
+        stmt = stmt.filter(tenant::entities::stock_movements::Column::CreatedAt.lte(to));
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let paginator = stmt
+        .order_by_desc(tenant::entities::stock_movements::Column::CreatedAt)
+        .order_by_desc(tenant::entities::stock_movements::Column::Id)
+        .paginate(&tenant_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
+
+    let movements = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "movements": movements
+                .iter()
+                .map(|(movement, batch)| stock_movement_json(movement, batch.as_ref()))
+                .collect::<Vec<_>>(),
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "Stock movements fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ReceiptData {
+    pub store_pid: Option<Uuid>,
+    pub product_pid: Option<Uuid>,
+    pub batch_number: Option<String>,
+    pub expiry_date: Option<NaiveDate>,
+    pub unit_cost: Option<Decimal>,
+    /// In the product's dispensing unit, not packs.
+    pub quantity: Option<i32>,
+    pub reference: Option<String>,
+}
+
+impl ReceiptData {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if self.store_pid.is_none() {
+            errors.insert("store_pid".to_string(), "Store is required".to_string());
+        }
+
+        if self.product_pid.is_none() {
+            errors.insert("product_pid".to_string(), "Product is required".to_string());
+        }
+
+        match self.batch_number.as_deref().map(str::trim) {
+            None | Some("") => {
+                errors.insert(
+                    "batch_number".to_string(),
+                    "Batch number is required".to_string(),
+                );
+            }
+            Some(batch_number) if batch_number.len() > 50 => {
+                errors.insert(
+                    "batch_number".to_string(),
+                    "Batch number must be at most 50 characters".to_string(),
+                );
+            }
+            _ => {}
+        }
+
+        match self.expiry_date {
+            None => {
+                errors.insert(
+                    "expiry_date".to_string(),
+                    "Expiry date is required".to_string(),
+                );
+            }
+            Some(expiry_date) if expiry_date < Utc::now().date_naive() => {
+                errors.insert(
+                    "expiry_date".to_string(),
+                    "Expired stock cannot be received".to_string(),
+                );
+            }
+            _ => {}
+        }
+
+        if self.quantity.is_none_or(|q| q <= 0) {
+            errors.insert(
+                "quantity".to_string(),
+                "Quantity must be at least 1".to_string(),
+            );
+        }
+
+        if self.unit_cost.is_some_and(|c| c.is_sign_negative()) {
+            errors.insert(
+                "unit_cost".to_string(),
+                "Unit cost cannot be negative".to_string(),
+            );
+        }
+
+        if self.reference.as_ref().is_some_and(|r| r.len() > 100) {
+            errors.insert(
+                "reference".to_string(),
+                "Reference must be at most 100 characters".to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+pub async fn receive(
+    app_state: web::Data<AppState>,
+    data: web::Json<ReceiptData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate() {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let store = find_store(&tenant_db, data.store_pid.unwrap_or_default()).await?;
+    let product = find_product(&tenant_db, data.product_pid.unwrap_or_default()).await?;
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start stock transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to receive stock" }))
+    })?;
+
+    let (batch, movement) = receive_stock(
+        &txn,
+        store.id,
+        &product,
+        data.batch_number.as_deref().unwrap_or_default().trim(),
+        data.expiry_date.unwrap_or_default(),
+        data.unit_cost,
+        data.quantity.unwrap_or_default(),
+        data.reference.clone(),
+        claims.sub,
+    )
+    .await?;
+
+    commit(txn).await?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "movement": stock_movement_json(&movement, Some(&batch)),
+            "message": "Stock received successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct TransferData {
+    pub from_store_pid: Option<Uuid>,
+    pub to_store_pid: Option<Uuid>,
+    pub product_pid: Option<Uuid>,
+    /// Moves this batch only; otherwise batches are picked FEFO.
+    pub batch_pid: Option<Uuid>,
+    pub quantity: Option<i32>,
+    pub reference: Option<String>,
+}
+
+impl TransferData {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if self.from_store_pid.is_none() {
+            errors.insert(
+                "from_store_pid".to_string(),
+                "Source store is required".to_string(),
+            );
+        }
+
+        if self.to_store_pid.is_none() {
+            errors.insert(
+                "to_store_pid".to_string(),
+                "Destination store is required".to_string(),
+            );
+        } else if self.to_store_pid == self.from_store_pid {
+            errors.insert(
+                "to_store_pid".to_string(),
+                "Destination must be a different store".to_string(),
+            );
+        }
+
+        if self.product_pid.is_none() {
+            errors.insert("product_pid".to_string(), "Product is required".to_string());
+        }
+
+        if self.quantity.is_none_or(|q| q <= 0) {
+            errors.insert(
+                "quantity".to_string(),
+                "Quantity must be at least 1".to_string(),
+            );
+        }
+
+        if self.reference.as_ref().is_some_and(|r| r.len() > 100) {
+            errors.insert(
+                "reference".to_string(),
+                "Reference must be at most 100 characters".to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+/// Moves stock between stores as a pair of movements per batch sharing one
+/// transfer id.
+pub async fn transfer(
+    app_state: web::Data<AppState>,
+    data: web::Json<TransferData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate() {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let from_store = find_store(&tenant_db, data.from_store_pid.unwrap_or_default()).await?;
+    let to_store = find_store(&tenant_db, data.to_store_pid.unwrap_or_default()).await?;
+    let product = find_product(&tenant_db, data.product_pid.unwrap_or_default()).await?;
+    let quantity = data.quantity.unwrap_or_default();
+
+    let batch = match data.batch_pid {
+        Some(batch_pid) => {
+            let batch = find_batch(&tenant_db, batch_pid).await?;
+            if batch.product_id != product.id {
+                return Err(ApiResponse::new(
+                    400,
+                    json!({ "message": "Batch does not belong to this product" }),
+                ));
+            }
+            Some(batch)
+        }
+        None => None,
+    };
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start stock transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to transfer stock" }))
+    })?;
+
+    lock_product_batches(&txn, product.id).await?;
+
+    let picks = match batch {
+        Some(batch) => {
+            let on_hand = batch_on_hand(&txn, from_store.id, &batch).await?;
+            if on_hand < quantity as i64 {
+                return Err(insufficient_stock(quantity as i64, on_hand));
+            }
+            vec![(batch, quantity)]
+        }
+        None => pick_fefo(&txn, from_store.id, product.id, quantity as i64)
+            .await?
+            .into_iter()
+            .map(|pick| (pick.batch, pick.quantity as i32))
+            .collect(),
+    };
+
+    let transfer_pid = Uuid::new_v4();
+    let mut movements = Vec::with_capacity(picks.len() * 2);
+    for (batch, quantity) in &picks {
+        for (store_id, movement_type, signed) in [
+            (from_store.id, StockMovementType::TransferOut, -quantity),
+            (to_store.id, StockMovementType::TransferIn, *quantity),
+        ] {
+            let movement = record_movement(
+                &txn,
+                NewMovement {
+                    store_id,
+                    batch,
+                    movement_type,
+                    quantity: signed,
+                    transfer_pid: Some(transfer_pid),
+                    reference: data.reference.clone(),
+                    reason: None,
+                    performed_by: claims.sub,
+                },
+            )
+            .await?;
+            movements.push(stock_movement_json(&movement, Some(batch)));
+        }
+    }
+
+    commit(txn).await?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "transfer_pid": transfer_pid,
+            "from_store": store_json(&from_store),
+            "to_store": store_json(&to_store),
+            "movements": movements,
+            "message": "Stock transferred successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct BatchMovementData {
+    pub store_pid: Option<Uuid>,
+    pub batch_pid: Option<Uuid>,
+    /// Signed for adjustments; a positive amount written off for wastage.
+    pub quantity: Option<i32>,
+    pub reason: Option<String>,
+    pub reference: Option<String>,
+}
+
+impl BatchMovementData {
+    pub fn validate(&self, signed: bool) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if self.store_pid.is_none() {
+            errors.insert("store_pid".to_string(), "Store is required".to_string());
+        }
+
+        if self.batch_pid.is_none() {
+            errors.insert("batch_pid".to_string(), "Batch is required".to_string());
+        }
+
+        match self.quantity {
+            None | Some(0) => {
+                errors.insert(
+                    "quantity".to_string(),
+                    "Quantity cannot be zero".to_string(),
+                );
+            }
+            Some(quantity) if !signed && quantity < 0 => {
+                errors.insert(
+                    "quantity".to_string(),
+                    "Quantity must be at least 1".to_string(),
+                );
+            }
+            _ => {}
+        }
+
+        if self.reason.as_deref().is_none_or(|r| r.trim().is_empty()) {
+            errors.insert("reason".to_string(), "Reason is required".to_string());
+        }
+
+        if self.reference.as_ref().is_some_and(|r| r.len() > 100) {
+            errors.insert(
+                "reference".to_string(),
+                "Reference must be at most 100 characters".to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+/// Corrects a batch's balance after a stock count. Negative quantities
+/// remove stock, positive ones add it.
+pub async fn adjust(
+    app_state: web::Data<AppState>,
+    data: web::Json<BatchMovementData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate(true) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let quantity = data.quantity.unwrap_or_default();
+    record_batch_movement(
+        &app_state,
+        &req,
+        &data,
+        StockMovementType::Adjustment,
+        quantity,
+    )
+    .await
+}
+
+/// Writes off expired, damaged or otherwise unusable stock.
+pub async fn waste(
+    app_state: web::Data<AppState>,
+    data: web::Json<BatchMovementData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate(false) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let quantity = -data.quantity.unwrap_or_default();
+    record_batch_movement(
+        &app_state,
+        &req,
+        &data,
+        StockMovementType::Wastage,
+        quantity,
+    )
+    .await
+}
+
+async fn record_batch_movement(
+    app_state: &web::Data<AppState>,
+    req: &HttpRequest,
+    data: &BatchMovementData,
+    movement_type: StockMovementType,
+    quantity: i32,
+) -> Result<ApiResponse, ApiResponse> {
+    let claims = get_logged_in_user_claims(req)?;
+    let tenant_db = get_tenant_db(req, app_state).await?;
+    let store = find_store(&tenant_db, data.store_pid.unwrap_or_default()).await?;
+    let batch = find_batch(&tenant_db, data.batch_pid.unwrap_or_default()).await?;
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start stock transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to record stock movement" }))
+    })?;
+
+    if quantity < 0 {
+        lock_product_batches(&txn, batch.product_id).await?;
+        let on_hand = batch_on_hand(&txn, store.id, &batch).await?;
+        if on_hand < -quantity as i64 {
+            return Err(insufficient_stock(-quantity as i64, on_hand));
+        }
+    }
+
+    let movement = record_movement(
+        &txn,
+        NewMovement {
+            store_id: store.id,
+            batch: &batch,
+            movement_type,
+            quantity,
+            transfer_pid: None,
+            reference: data.reference.clone(),
+            reason: data.reason.as_deref().map(|r| r.trim().to_string()),
+            performed_by: claims.sub,
+        },
+    )
+    .await?;
+
+    commit(txn).await?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "movement": stock_movement_json(&movement, Some(&batch)),
+            "message": "Stock movement recorded successfully",
+        }),
+    ))
+}
+
+fn insufficient_stock(requested: i64, available: i64) -> ApiResponse {
+    ApiResponse::new(
+        409,
+        json!({
+            "message": "Insufficient stock",
+            "requested": requested,
+            "available": available,
+        }),
+    )
+}
+
+async fn commit(txn: tenant::migrations::sea_orm::DatabaseTransaction) -> Result<(), ApiResponse> {
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit stock transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to record stock movement" }))
+    })
+}
+
+/// Total stock on hand per product across all stores.
+async fn product_totals(
+    tenant_db: &DatabaseConnection,
+    product_ids: Vec<i32>,
+) -> Result<HashMap<i32, i64>, ApiResponse> {
+    if product_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let balances = batch_balances(tenant_db, None, Some(product_ids)).await?;
+    let batch_products: HashMap<i32, i32> = tenant::entities::stock_batches::Entity::find()
+        .filter(
+            tenant::entities::stock_batches::Column::Id
+                .is_in(balances.iter().map(|(_, batch_id, _)| *batch_id)),
+        )
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch batches: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch stock on hand" }))
+        })?
+        .into_iter()
+        .map(|batch| (batch.id, batch.product_id))
+        .collect();
+
+    let mut totals = HashMap::new();
+    for (_, batch_id, on_hand) in balances {
+        if let Some(product_id) = batch_products.get(&batch_id) {
+            *totals.entry(*product_id).or_insert(0) += on_hand;
+        }
+    }
+
+    Ok(totals)
+}
+
+async fn ensure_sku_available(
+    tenant_db: &DatabaseConnection,
+    sku: &str,
+    except: Option<Uuid>,
+) -> Result<(), ApiResponse> {
+    let mut stmt = tenant::entities::pharmacy_products::Entity::find()
+        .filter(tenant::entities::pharmacy_products::Column::Sku.eq(sku))
+        .filter(tenant::entities::pharmacy_products::Column::DeletedAt.is_null());
+
+    if let Some(pid) = except {
+        stmt = stmt.filter(tenant::entities::pharmacy_products::Column::Pid.ne(pid));
+    }
+
+    let existing = stmt.one(tenant_db).await.map_err(|err| {
+        log::error!("Failed to check SKU {}: {}", sku, err);
+        ApiResponse::new(500, json!({ "message": "Failed to save product" }))
+    })?;
+
+    if existing.is_some() {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "A product with this SKU already exists" }),
+        ));
+    }
+
+    Ok(())
+}
+
+async fn ensure_store_code_available(
+    tenant_db: &DatabaseConnection,
+    code: &str,
+    except: Option<Uuid>,
+) -> Result<(), ApiResponse> {
+    let mut stmt = tenant::entities::pharmacy_stores::Entity::find()
+        .filter(tenant::entities::pharmacy_stores::Column::Code.eq(code));
+
+    if let Some(pid) = except {
+        stmt = stmt.filter(tenant::entities::pharmacy_stores::Column::Pid.ne(pid));
+    }
+
+    let existing = stmt.one(tenant_db).await.map_err(|err| {
+        log::error!("Failed to check store code {}: {}", code, err);
+        ApiResponse::new(500, json!({ "message": "Failed to save store" }))
+    })?;
+
+    if existing.is_some() {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "A store with this code already exists" }),
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,921 @@
+use std::collections::HashMap;
+
+use actix_multipart::Multipart;
+use actix_web::{HttpRequest, web};
+use chrono::{NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        main,
+        tenant::{
+            self,
+            entities::sea_orm_active_enums::PreauthorizationStatus,
+            migrations::sea_orm::{
+                ActiveModelTrait, ColumnTrait, Condition, EntityTrait, ModelTrait, PaginatorTrait,
+                QueryFilter, QueryOrder, Set, TransactionTrait,
+            },
+        },
+    },
+    handlers::services::{
+        files::authorized_file_url,
+        insurance_claims::{ClaimAttachmentForm, clean_diagnosis_codes},
+        preauthorizations::{
+            apply_insurer_decision, find_preauthorization, preauthorization_attachment_json,
+            preauthorization_attachments, preauthorization_detail_json, preauthorization_json,
+            preauthorization_lines, store_preauthorization_attachments,
+        },
+        pricing::{
+            facility_currency, find_service, patient_cover, payer_price_list, primary_insurance,
+            service_price,
+        },
+    },
+    utils::{
+        api_response::ApiResponse,
+        app_state::AppState,
+        documents::document_number,
+        insurance::{PreauthorizationLine, PreauthorizationRequest, get_insurance_adapter},
+        jwt::{get_logged_in_user_claims, get_tenant_db},
+        validator_error::ValidationError,
+    },
+};
+
+const ATTACHMENT_URL_EXPIRY_SECS: u64 = 300;
+
+#[derive(Deserialize, Debug)]
+pub struct PreauthorizationParams {
+    pub status: Option<PreauthorizationStatus>,
+    pub insurance_provider_pid: Option<Uuid>,
+    pub patient_pid: Option<Uuid>,
+    pub search: Option<String>,
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+}
+
+pub async fn index(
+    app_state: web::Data<AppState>,
+    query: web::Query<PreauthorizationParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let mut stmt = tenant::entities::preauthorizations::Entity::find();
+
+    if let Some(status) = &query.status {
+        stmt = stmt.filter(tenant::entities::preauthorizations::Column::Status.eq(status.clone()));
+    }
+
+    if let Some(provider_pid) = query.insurance_provider_pid {
+        stmt = stmt.filter(
+            tenant::entities::preauthorizations::Column::InsuranceProviderPid.eq(provider_pid),
+        );
+    }
+
+    if let Some(patient_pid) = query.patient_pid {
+        stmt = stmt.filter(tenant::entities::preauthorizations::Column::PatientPid.eq(patient_pid));
+    }
+
+    if let Some(term) = &query.search {
+        use tenant::migrations::{Expr, extension::postgres::PgExpr};
+
+        stmt = stmt.filter(
+            Condition::any()
+                .add(
+                    Expr::col(tenant::entities::preauthorizations::Column::PreauthNumber)
+                        .ilike(format!("%{}%", term)),
+                )
+                .add(
+                    Expr::col(tenant::entities::preauthorizations::Column::AuthorizationNumber)
+                        .ilike(format!("%{}%", term)),
+                ),
+        );
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let paginator = stmt
+        .order_by_desc(tenant::entities::preauthorizations::Column::CreatedAt)
+        .paginate(&tenant_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
+
+    let preauthorizations = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "preauthorizations": preauthorizations
+                .iter()
+                .map(preauthorization_json)
+                .collect::<Vec<_>>(),
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "Pre-authorizations fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct PlannedService {
+    pub service_pid: Option<Uuid>,
+    /// Defaults to 1.
+    pub quantity: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct PreauthorizationData {
+    pub patient_pid: Option<Uuid>,
+    /// The policy to request approval on. Defaults to the patient's
+    /// primary cover.
+    pub patient_insurance_pid: Option<Uuid>,
+    pub encounter_pid: Option<Uuid>,
+    pub planned_date: Option<NaiveDate>,
+    /// Catalogue services planned, priced on the patient's price list.
+    pub services: Vec<PlannedService>,
+    /// ICD-10 codes justifying the care.
+    pub diagnosis_codes: Vec<String>,
+    pub clinical_notes: Option<String>,
+}
+
+impl PreauthorizationData {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if self.patient_pid.is_none() {
+            errors.insert("patient_pid".to_string(), "Patient is required".to_string());
+        }
+
+        if self.services.is_empty() {
+            errors.insert(
+                "services".to_string(),
+                "Add at least one planned service".to_string(),
+            );
+        } else if self.services.iter().any(|line| line.service_pid.is_none()) {
+            errors.insert(
+                "services".to_string(),
+                "Every line needs a service".to_string(),
+            );
+        } else if self
+            .services
+            .iter()
+            .any(|line| line.quantity.is_some_and(|quantity| quantity < 1))
+        {
+            errors.insert(
+                "services".to_string(),
+                "Quantity must be at least 1".to_string(),
+            );
+        }
+
+        match clean_diagnosis_codes(&self.diagnosis_codes) {
+            Ok(codes) if codes.is_empty() => {
+                errors.insert(
+                    "diagnosis_codes".to_string(),
+                    "Add at least one diagnosis code".to_string(),
+                );
+            }
+            Ok(_) => {}
+            Err(err) => {
+                errors.insert("diagnosis_codes".to_string(), err);
+            }
+        }
+
+        if self
+            .planned_date
+            .is_some_and(|date| date < Utc::now().date_naive())
+        {
+            errors.insert(
+                "planned_date".to_string(),
+                "Planned date cannot be in the past".to_string(),
+            );
+        }
+
+        if self
+            .clinical_notes
+            .as_ref()
+            .is_some_and(|n| n.chars().count() > 5000)
+        {
+            errors.insert(
+                "clinical_notes".to_string(),
+                "Clinical notes must be at most 5000 characters".to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+/// Requests insurer approval for planned care. The estimate is priced from
+/// the catalogue on the patient's price list, the same way it will be
+/// billed. The policy must be verified and in force, either the patient's
+/// own or one they are an active dependent on.
+pub async fn create(
+    app_state: web::Data<AppState>,
+    data: web::Json<PreauthorizationData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate() {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let user = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let patient_pid = data.patient_pid.unwrap_or_default();
+
+    main::entities::patients::Entity::find_by_pid(patient_pid)
+        .filter(main::entities::patients::Column::DeletedAt.is_null())
+        .one(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch patient {}: {}", patient_pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch patient" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Patient not found" })))?;
+
+    let cover = match data.patient_insurance_pid {
+        Some(insurance_pid) => {
+            patient_cover(&app_state.main_db, patient_pid, insurance_pid).await?
+        }
+        None => primary_insurance(&app_state.main_db, patient_pid).await?,
+    };
+    let (cover, provider) = cover.ok_or_else(|| {
+        ApiResponse::new(
+            409,
+            json!({ "message": "The patient has no verified insurance in force to request approval on" }),
+        )
+    })?;
+
+    if let Some(encounter_pid) = data.encounter_pid {
+        tenant::entities::encounters::Entity::find_by_pid(encounter_pid)
+            .one(&tenant_db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to fetch encounter {}: {}", encounter_pid, err);
+                ApiResponse::new(500, json!({ "message": "Failed to fetch encounter" }))
+            })?
+            .filter(|encounter| encounter.patient_pid == patient_pid)
+            .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Encounter not found" })))?;
+    }
+
+    let currency = facility_currency(&req, &app_state).await?;
+    let price_date = data.planned_date.unwrap_or_else(|| Utc::now().date_naive());
+    let price_list = payer_price_list(&tenant_db, &app_state.main_db, patient_pid, None).await?;
+
+    let mut lines = Vec::with_capacity(data.services.len());
+    for line in &data.services {
+        let service = find_service(&tenant_db, line.service_pid.unwrap_or_default()).await?;
+        let price = service_price(
+            &tenant_db,
+            &service,
+            price_list.as_ref(),
+            &currency,
+            price_date,
+        )
+        .await?
+        .ok_or_else(|| {
+            ApiResponse::new(
+                404,
+                json!({ "message": format!("{} has no price for this payer", service.name) }),
+            )
+        })?;
+
+        let quantity = line.quantity.unwrap_or(1);
+        lines.push((service, quantity, price.amount));
+    }
+    let estimated_amount = lines
+        .iter()
+        .map(|(_, quantity, unit_price)| *unit_price * Decimal::from(*quantity))
+        .sum::<Decimal>();
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start pre-authorization transaction: {}", err);
+        ApiResponse::new(
+            500,
+            json!({ "message": "Failed to create pre-authorization" }),
+        )
+    })?;
+
+    let preauthorization = tenant::entities::preauthorizations::ActiveModel {
+        preauth_number: Set(document_number("PA")),
+        patient_pid: Set(patient_pid),
+        patient_insurance_pid: Set(cover.pid),
+        insurance_provider_pid: Set(provider.pid),
+        encounter_pid: Set(data.encounter_pid),
+        planned_date: Set(data.planned_date),
+        status: Set(PreauthorizationStatus::Pending),
+        diagnosis_codes: Set(clean_diagnosis_codes(&data.diagnosis_codes).unwrap_or_default()),
+        clinical_notes: Set(data.clinical_notes.clone()),
+        estimated_amount: Set(estimated_amount),
+        currency: Set(currency),
+        requested_by: Set(user.sub),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to create pre-authorization: {}", err);
+        ApiResponse::new(
+            500,
+            json!({ "message": "Failed to create pre-authorization" }),
+        )
+    })?;
+
+    tenant::entities::preauthorization_lines::Entity::insert_many(lines.iter().map(
+        |(service, quantity, unit_price)| tenant::entities::preauthorization_lines::ActiveModel {
+            preauthorization_id: Set(preauthorization.id),
+            service_id: Set(Some(service.id)),
+            service_code: Set(service.code.clone()),
+            description: Set(service.name.clone()),
+            quantity: Set(*quantity),
+            unit_price: Set(*unit_price),
+            total_amount: Set(*unit_price * Decimal::from(*quantity)),
+            ..Default::default()
+        },
+    ))
+    .exec(&txn)
+    .await
+    .map_err(|err| {
+        log::error!(
+            "Failed to add lines to {}: {}",
+            preauthorization.preauth_number,
+            err
+        );
+        ApiResponse::new(
+            500,
+            json!({ "message": "Failed to create pre-authorization" }),
+        )
+    })?;
+
+    let lines = preauthorization_lines(&txn, preauthorization.id).await?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!(
+            "Failed to commit pre-authorization {}: {}",
+            preauthorization.preauth_number,
+            err
+        );
+        ApiResponse::new(
+            500,
+            json!({ "message": "Failed to create pre-authorization" }),
+        )
+    })?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "preauthorization": preauthorization_detail_json(&preauthorization, None, &lines, &[]),
+            "message": "Pre-authorization requested successfully",
+        }),
+    ))
+}
+
+pub async fn show(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let preauthorization = find_preauthorization(&tenant_db, path.into_inner()).await?;
+
+    let claim = match preauthorization.claim_id {
+        Some(claim_id) => tenant::entities::insurance_claims::Entity::find_by_id(claim_id)
+            .one(&tenant_db)
+            .await
+            .map_err(|err| {
+                log::error!(
+                    "Failed to fetch claim of {}: {}",
+                    preauthorization.preauth_number,
+                    err
+                );
+                ApiResponse::new(
+                    500,
+                    json!({ "message": "Failed to fetch pre-authorization" }),
+                )
+            })?,
+        None => None,
+    };
+    let lines = preauthorization_lines(&tenant_db, preauthorization.id).await?;
+    let attachments = preauthorization_attachments(&tenant_db, preauthorization.id).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "preauthorization": preauthorization_detail_json(
+                &preauthorization,
+                claim.as_ref(),
+                &lines,
+                &attachments,
+            ),
+            "message": "Pre-authorization fetched successfully",
+        }),
+    ))
+}
+
+/// Deletes a request that was never sent to the insurer.
+pub async fn destroy(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let preauthorization = find_preauthorization(&tenant_db, path.into_inner()).await?;
+
+    if preauthorization.status != PreauthorizationStatus::Pending
+        || preauthorization.submitted_at.is_some()
+    {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Only pre-authorizations not yet submitted can be deleted" }),
+        ));
+    }
+
+    let preauth_number = preauthorization.preauth_number.clone();
+    preauthorization.delete(&tenant_db).await.map_err(|err| {
+        log::error!(
+            "Failed to delete pre-authorization {}: {}",
+            preauth_number,
+            err
+        );
+        ApiResponse::new(
+            500,
+            json!({ "message": "Failed to delete pre-authorization" }),
+        )
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({ "message": "Pre-authorization deleted successfully" }),
+    ))
+}
+
+/// Attaches supporting documents, such as referral letters, imaging or lab
+/// results, while the request is pending.
+pub async fn upload_attachments(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    payload: Multipart,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let user = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let preauthorization = find_preauthorization(&tenant_db, path.into_inner()).await?;
+
+    if preauthorization.status != PreauthorizationStatus::Pending {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Documents can only be attached to pending pre-authorizations" }),
+        ));
+    }
+
+    let form = ClaimAttachmentForm::from_multipart(payload).await?;
+    let existing = preauthorization_attachments(&tenant_db, preauthorization.id).await?;
+    if let Err(err) = form.validate(existing.len()) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let patient = main::entities::patients::Entity::find_by_pid(preauthorization.patient_pid)
+        .one(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to fetch patient {}: {}",
+                preauthorization.patient_pid,
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to fetch patient" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Patient not found" })))?;
+
+    let attachments = store_preauthorization_attachments(
+        &app_state,
+        &req,
+        &tenant_db,
+        &preauthorization,
+        patient.id,
+        form,
+        user.sub,
+    )
+    .await?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "attachments": attachments
+                .iter()
+                .map(preauthorization_attachment_json)
+                .collect::<Vec<_>>(),
+            "message": "Documents attached successfully",
+        }),
+    ))
+}
+
+pub async fn attachment(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let attachment_pid = path.into_inner();
+
+    let attachment =
+        tenant::entities::preauthorization_attachments::Entity::find_by_pid(attachment_pid)
+            .one(&tenant_db)
+            .await
+            .map_err(|err| {
+                log::error!(
+                    "Failed to fetch pre-authorization attachment {}: {}",
+                    attachment_pid,
+                    err
+                );
+                ApiResponse::new(500, json!({ "message": "Failed to fetch attachment" }))
+            })?
+            .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Attachment not found" })))?;
+
+    let (url, _) = authorized_file_url(
+        &app_state,
+        &req,
+        attachment.file_pid,
+        ATTACHMENT_URL_EXPIRY_SECS,
+    )
+    .await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "url": url,
+            "original_name": attachment.original_name,
+            "content_type": attachment.content_type,
+            "expires_in": ATTACHMENT_URL_EXPIRY_SECS,
+            "message": "Attachment link generated successfully",
+        }),
+    ))
+}
+
+/// Sends the request to the insurer. Insurers with an API get it
+/// electronically and their answer is recorded straight away; for the
+/// rest it is marked as sent and staff record the decision when it comes.
+/// A pending request can be sent again to ask for a decision.
+pub async fn submit(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let preauthorization = find_preauthorization(&tenant_db, path.into_inner()).await?;
+
+    if preauthorization.status != PreauthorizationStatus::Pending {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Only pending pre-authorizations can be submitted" }),
+        ));
+    }
+
+    let (insurance, provider) = main::entities::patient_insurance::Entity::find_by_pid(
+        preauthorization.patient_insurance_pid,
+    )
+    .find_also_related(main::entities::insurance_providers::Entity)
+    .one(&app_state.main_db)
+    .await
+    .map_err(|err| {
+        log::error!(
+            "Failed to fetch patient insurance {}: {}",
+            preauthorization.patient_insurance_pid,
+            err
+        );
+        ApiResponse::new(
+            500,
+            json!({ "message": "Failed to fetch patient insurance" }),
+        )
+    })?
+    .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Patient insurance not found" })))?;
+    let provider = provider.ok_or_else(|| {
+        ApiResponse::new(404, json!({ "message": "Insurance provider not found" }))
+    })?;
+
+    let now = Utc::now().naive_utc();
+    let preauth_number = preauthorization.preauth_number.clone();
+    let mut active_model: tenant::entities::preauthorizations::ActiveModel =
+        preauthorization.clone().into();
+    if preauthorization.submitted_at.is_none() {
+        active_model.submitted_at = Set(Some(now));
+    }
+    active_model.updated_at = Set(now);
+
+    let Some(adapter) = get_insurance_adapter(&provider) else {
+        let preauthorization = active_model.update(&tenant_db).await.map_err(|err| {
+            log::error!(
+                "Failed to submit pre-authorization {}: {}",
+                preauth_number,
+                err
+            );
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to submit pre-authorization" }),
+            )
+        })?;
+
+        return Ok(ApiResponse::new(
+            200,
+            json!({
+                "preauthorization": preauthorization_json(&preauthorization),
+                "message": format!(
+                    "{} does not accept online requests; record its decision once received",
+                    provider.name
+                ),
+            }),
+        ));
+    };
+
+    let patient = main::entities::patients::Entity::find_by_pid(preauthorization.patient_pid)
+        .one(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to fetch patient {}: {}",
+                preauthorization.patient_pid,
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to fetch patient" }))
+        })?;
+    let lines = preauthorization_lines(&tenant_db, preauthorization.id).await?;
+
+    let request = PreauthorizationRequest {
+        preauth_number: preauth_number.clone(),
+        policy_number: insurance.policy_number.clone(),
+        group_number: insurance.group_number.clone(),
+        member_name: patient.as_ref().map(|p| {
+            format!(
+                "{} {}",
+                p.first_name.as_deref().unwrap_or(""),
+                p.last_name.as_deref().unwrap_or("")
+            )
+            .trim()
+            .to_string()
+        }),
+        date_of_birth: patient.as_ref().and_then(|p| p.dob),
+        planned_date: preauthorization.planned_date,
+        diagnosis_codes: preauthorization.diagnosis_codes.clone(),
+        clinical_notes: preauthorization.clinical_notes.clone(),
+        estimated_amount: preauthorization.estimated_amount,
+        currency: preauthorization.currency.clone(),
+        lines: lines
+            .iter()
+            .map(|line| PreauthorizationLine {
+                service_code: line.service_code.clone(),
+                description: line.description.clone(),
+                quantity: line.quantity,
+                unit_price: line.unit_price,
+                total_amount: line.total_amount,
+            })
+            .collect(),
+    };
+
+    let response = adapter
+        .submit_preauthorization(&request)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Pre-authorization {} with {} failed: {}",
+                preauth_number,
+                provider.name,
+                err
+            );
+            ApiResponse::new(502, json!({ "message": err }))
+        })?;
+
+    let preauthorization = active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!(
+            "Failed to submit pre-authorization {}: {}",
+            preauth_number,
+            err
+        );
+        ApiResponse::new(
+            500,
+            json!({ "message": "Failed to submit pre-authorization" }),
+        )
+    })?;
+    let preauthorization = apply_insurer_decision(&tenant_db, preauthorization, &response).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "preauthorization": preauthorization_json(&preauthorization),
+            "insurer_message": response.message,
+            "message": "Pre-authorization submitted successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct PreauthorizationDecisionData {
+    /// The number the insurer issued, quoted on the eventual claim.
+    pub authorization_number: Option<String>,
+    /// Defaults to the estimated amount.
+    pub approved_amount: Option<Decimal>,
+    pub valid_until: Option<NaiveDate>,
+    /// The insurer's own reference for the request.
+    pub insurer_reference: Option<String>,
+    /// Why the insurer denied the request.
+    pub reason: Option<String>,
+}
+
+/// Records an approval received from the insurer outside the API, such as
+/// by phone or email.
+pub async fn approve(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<PreauthorizationDecisionData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let user = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let preauthorization = find_preauthorization(&tenant_db, path.into_inner()).await?;
+
+    if preauthorization.status != PreauthorizationStatus::Pending {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Only pending pre-authorizations can be approved" }),
+        ));
+    }
+
+    let mut errors = HashMap::new();
+    let authorization_number = data
+        .authorization_number
+        .as_deref()
+        .map(str::trim)
+        .filter(|number| !number.is_empty())
+        .map(str::to_string);
+    if authorization_number.is_none() {
+        errors.insert(
+            "authorization_number".to_string(),
+            "Authorization number is required".to_string(),
+        );
+    } else if authorization_number
+        .as_ref()
+        .is_some_and(|number| number.chars().count() > 100)
+    {
+        errors.insert(
+            "authorization_number".to_string(),
+            "Authorization number must be at most 100 characters".to_string(),
+        );
+    }
+
+    let approved_amount = data
+        .approved_amount
+        .unwrap_or(preauthorization.estimated_amount)
+        .round_dp(2);
+    if approved_amount <= Decimal::ZERO || approved_amount > preauthorization.estimated_amount {
+        errors.insert(
+            "approved_amount".to_string(),
+            format!(
+                "Approved amount must be more than 0 and at most {}; deny the request instead of approving nothing",
+                preauthorization.estimated_amount
+            ),
+        );
+    }
+
+    if data
+        .valid_until
+        .is_some_and(|date| date < Utc::now().date_naive())
+    {
+        errors.insert(
+            "valid_until".to_string(),
+            "Valid until cannot be in the past".to_string(),
+        );
+    }
+
+    if !errors.is_empty() {
+        return Err(ApiResponse::new(400, json!(ValidationError { errors })));
+    }
+
+    let now = Utc::now().naive_utc();
+    let preauth_number = preauthorization.preauth_number.clone();
+    let mut active_model: tenant::entities::preauthorizations::ActiveModel =
+        preauthorization.into();
+    active_model.status = Set(PreauthorizationStatus::Approved);
+    active_model.authorization_number = Set(authorization_number);
+    active_model.approved_amount = Set(Some(approved_amount));
+    active_model.valid_until = Set(data.valid_until);
+    if let Some(reference) = data.insurer_reference.as_deref().map(str::trim)
+        && !reference.is_empty()
+    {
+        active_model.insurer_reference = Set(Some(reference.to_string()));
+    }
+    active_model.decided_at = Set(Some(now));
+    active_model.decided_by = Set(Some(user.sub));
+    active_model.updated_at = Set(now);
+
+    let preauthorization = active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!(
+            "Failed to approve pre-authorization {}: {}",
+            preauth_number,
+            err
+        );
+        ApiResponse::new(
+            500,
+            json!({ "message": "Failed to update pre-authorization" }),
+        )
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "preauthorization": preauthorization_json(&preauthorization),
+            "message": "Pre-authorization approved successfully",
+        }),
+    ))
+}
+
+/// Records the insurer turning the request down.
+pub async fn deny(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<PreauthorizationDecisionData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let reason = data
+        .reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|r| !r.is_empty())
+        .ok_or_else(|| {
+            ApiResponse::new(
+                400,
+                json!(ValidationError {
+                    errors: HashMap::from([(
+                        "reason".to_string(),
+                        "Denial reason is required".to_string(),
+                    )]),
+                }),
+            )
+        })?
+        .to_string();
+
+    let user = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let preauthorization = find_preauthorization(&tenant_db, path.into_inner()).await?;
+
+    if preauthorization.status != PreauthorizationStatus::Pending {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Only pending pre-authorizations can be denied" }),
+        ));
+    }
+
+    let now = Utc::now().naive_utc();
+    let preauth_number = preauthorization.preauth_number.clone();
+    let mut active_model: tenant::entities::preauthorizations::ActiveModel =
+        preauthorization.into();
+    active_model.status = Set(PreauthorizationStatus::Denied);
+    active_model.denial_reason = Set(Some(reason));
+    if let Some(reference) = data.insurer_reference.as_deref().map(str::trim)
+        && !reference.is_empty()
+    {
+        active_model.insurer_reference = Set(Some(reference.to_string()));
+    }
+    active_model.decided_at = Set(Some(now));
+    active_model.decided_by = Set(Some(user.sub));
+    active_model.updated_at = Set(now);
+
+    let preauthorization = active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!(
+            "Failed to deny pre-authorization {}: {}",
+            preauth_number,
+            err
+        );
+        ApiResponse::new(
+            500,
+            json!({ "message": "Failed to update pre-authorization" }),
+        )
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "preauthorization": preauthorization_json(&preauthorization),
+            "message": "Pre-authorization marked as denied",
+        }),
+    ))
+}
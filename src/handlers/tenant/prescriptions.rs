@@ -0,0 +1,447 @@
+use std::collections::HashMap;
+
+use actix_web::{HttpRequest, web};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    db::tenant::{
+        self,
+        entities::sea_orm_active_enums::PrescriptionStatus,
+        migrations::sea_orm::{
+            ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait,
+            QueryFilter, QueryOrder, Set, TransactionTrait,
+        },
+    },
+    handlers::services::{
+        patients::find_patient,
+        pharmacy::{find_product, product_label},
+        prescriptions::{
+            fetch_prescription_items, find_prescription, is_dispensable, prescription_json,
+        },
+    },
+    utils::{
+        api_response::ApiResponse,
+        app_state::AppState,
+        jwt::{get_logged_in_user_claims, get_tenant_db},
+        validator_error::ValidationError,
+    },
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct PrescriptionItemData {
+    /// Links the item to the pharmacy catalogue. Free-text items are mapped
+    /// to a product when dispensed.
+    pub product_pid: Option<Uuid>,
+    pub drug_name: Option<String>,
+    pub dose: Option<String>,
+    pub route: Option<String>,
+    pub frequency: Option<String>,
+    pub duration_days: Option<i32>,
+    pub quantity: Option<i32>,
+    pub instructions: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct PrescriptionData {
+    pub patient_pid: Option<Uuid>,
+    pub encounter_pid: Option<Uuid>,
+    pub notes: Option<String>,
+    pub items: Vec<PrescriptionItemData>,
+}
+
+impl PrescriptionData {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if self.patient_pid.is_none() {
+            errors.insert("patient_pid".to_string(), "Patient is required".to_string());
+        }
+
+        if self.items.is_empty() {
+            errors.insert(
+                "items".to_string(),
+                "At least one item is required".to_string(),
+            );
+        }
+
+        for (index, item) in self.items.iter().enumerate() {
+            let blank =
+                |value: &Option<String>| value.as_deref().is_none_or(|v| v.trim().is_empty());
+
+            if item.product_pid.is_none() && blank(&item.drug_name) {
+                errors.insert(
+                    format!("items.{}.drug_name", index),
+                    "Drug name or product is required".to_string(),
+                );
+            }
+
+            if item
+                .drug_name
+                .as_ref()
+                .is_some_and(|n| n.trim().len() > 200)
+            {
+                errors.insert(
+                    format!("items.{}.drug_name", index),
+                    "Drug name must be at most 200 characters".to_string(),
+                );
+            }
+
+            if blank(&item.dose) {
+                errors.insert(
+                    format!("items.{}.dose", index),
+                    "Dose is required".to_string(),
+                );
+            } else if item.dose.as_ref().is_some_and(|d| d.trim().len() > 100) {
+                errors.insert(
+                    format!("items.{}.dose", index),
+                    "Dose must be at most 100 characters".to_string(),
+                );
+            }
+
+            if blank(&item.frequency) {
+                errors.insert(
+                    format!("items.{}.frequency", index),
+                    "Frequency is required".to_string(),
+                );
+            } else if item
+                .frequency
+                .as_ref()
+                .is_some_and(|f| f.trim().len() > 100)
+            {
+                errors.insert(
+                    format!("items.{}.frequency", index),
+                    "Frequency must be at most 100 characters".to_string(),
+                );
+            }
+
+            if item.route.as_ref().is_some_and(|r| r.trim().len() > 50) {
+                errors.insert(
+                    format!("items.{}.route", index),
+                    "Route must be at most 50 characters".to_string(),
+                );
+            }
+
+            if item.duration_days.is_some_and(|d| d <= 0) {
+                errors.insert(
+                    format!("items.{}.duration_days", index),
+                    "Duration must be at least 1 day".to_string(),
+                );
+            }
+
+            if item.quantity.is_none_or(|q| q <= 0) {
+                errors.insert(
+                    format!("items.{}.quantity", index),
+                    "Quantity must be at least 1".to_string(),
+                );
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PrescriptionParams {
+    pub patient_pid: Option<Uuid>,
+    pub status: Option<PrescriptionStatus>,
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+}
+
+pub async fn index(
+    app_state: web::Data<AppState>,
+    query: web::Query<PrescriptionParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let mut stmt = tenant::entities::prescriptions::Entity::find();
+
+    if let Some(patient_pid) = query.patient_pid {
+        stmt = stmt.filter(tenant::entities::prescriptions::Column::PatientPid.eq(patient_pid));
+    }
+
+    if let Some(status) = &query.status {
+        stmt = stmt.filter(tenant::entities::prescriptions::Column::Status.eq(status.clone()));
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let paginator = stmt
+        .order_by_desc(tenant::entities::prescriptions::Column::PrescribedAt)
+        .paginate(&tenant_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
+
+    let prescriptions = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let items =
+        fetch_prescription_items(&tenant_db, prescriptions.iter().map(|p| p.id).collect()).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "prescriptions": prescriptions
+                .iter()
+                .map(|prescription| {
+                    let items = items
+                        .iter()
+                        .filter(|item| item.prescription_id == prescription.id)
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    prescription_json(prescription, &items)
+                })
+                .collect::<Vec<_>>(),
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "Prescriptions fetched successfully",
+        }),
+    ))
+}
+
+pub async fn create(
+    app_state: web::Data<AppState>,
+    data: web::Json<PrescriptionData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate() {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let patient = find_patient(&app_state, data.patient_pid.unwrap_or_default()).await?;
+
+    let encounter_id = match data.encounter_pid {
+        Some(encounter_pid) => Some(
+            find_encounter(&tenant_db, encounter_pid, patient.pid)
+                .await?
+                .id,
+        ),
+        None => None,
+    };
+
+    let mut products = HashMap::new();
+    for item in &data.items {
+        if let Some(product_pid) = item.product_pid
+            && !products.contains_key(&product_pid)
+        {
+            let product = find_product(&tenant_db, product_pid).await?;
+            products.insert(product_pid, product);
+        }
+    }
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start prescription transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create prescription" }))
+    })?;
+
+    let prescription = tenant::entities::prescriptions::ActiveModel {
+        patient_pid: Set(patient.pid),
+        encounter_id: Set(encounter_id),
+        prescriber_id: Set(claims.sub),
+        status: Set(PrescriptionStatus::Pending),
+        notes: Set(data.notes.clone()),
+        prescribed_at: Set(Utc::now().naive_utc()),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to create prescription: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create prescription" }))
+    })?;
+
+    let mut items = Vec::with_capacity(data.items.len());
+    for item in &data.items {
+        let product = item.product_pid.and_then(|pid| products.get(&pid));
+        let drug_name = match item.drug_name.as_deref().map(str::trim) {
+            Some(name) if !name.is_empty() => name.to_string(),
+            _ => product.map(product_label).unwrap_or_default(),
+        };
+
+        let item = tenant::entities::prescription_items::ActiveModel {
+            prescription_id: Set(prescription.id),
+            product_id: Set(product.map(|p| p.id)),
+            drug_name: Set(drug_name.chars().take(200).collect()),
+            dose: Set(item.dose.clone().unwrap_or_default().trim().to_string()),
+            route: Set(item.route.as_deref().map(|r| r.trim().to_string())),
+            frequency: Set(item
+                .frequency
+                .clone()
+                .unwrap_or_default()
+                .trim()
+                .to_string()),
+            duration_days: Set(item.duration_days),
+            quantity: Set(item.quantity.unwrap_or_default()),
+            quantity_dispensed: Set(0),
+            instructions: Set(item.instructions.clone()),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to create prescription item: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to create prescription" }))
+        })?;
+        items.push(item);
+    }
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit prescription: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create prescription" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "prescription": prescription_json(&prescription, &items),
+            "message": "Prescription created successfully",
+        }),
+    ))
+}
+
+pub async fn show(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let prescription = find_prescription(&tenant_db, path.into_inner()).await?;
+    let items = fetch_prescription_items(&tenant_db, vec![prescription.id]).await?;
+
+    let dispenses = tenant::entities::dispenses::Entity::find()
+        .filter(tenant::entities::dispenses::Column::PrescriptionId.eq(prescription.id))
+        .order_by_asc(tenant::entities::dispenses::Column::DispensedAt)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to fetch dispenses for prescription {}: {}",
+                prescription.pid,
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to fetch prescription" }))
+        })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "prescription": prescription_json(&prescription, &items),
+            "dispenses": dispenses
+                .iter()
+                .map(|dispense| json!({
+                    "pid": dispense.pid,
+                    "dispensed_by": dispense.dispensed_by,
+                    "dispensed_at": dispense.dispensed_at,
+                }))
+                .collect::<Vec<_>>(),
+            "message": "Prescription fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct CancelData {
+    pub reason: Option<String>,
+}
+
+/// Stops any further dispensing. Medicine already handed out stays on the
+/// record.
+pub async fn cancel(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<CancelData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let Some(reason) = data
+        .reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|r| !r.is_empty())
+    else {
+        return Err(ApiResponse::new(
+            400,
+            json!(ValidationError {
+                errors: HashMap::from([("reason".to_string(), "Reason is required".to_string())]),
+            }),
+        ));
+    };
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let prescription = find_prescription(&tenant_db, path.into_inner()).await?;
+
+    if !is_dispensable(&prescription.status) {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Only open prescriptions can be cancelled" }),
+        ));
+    }
+
+    let mut active_model: tenant::entities::prescriptions::ActiveModel =
+        prescription.clone().into();
+    active_model.status = Set(PrescriptionStatus::Cancelled);
+    active_model.cancelled_at = Set(Some(Utc::now().naive_utc()));
+    active_model.cancelled_by = Set(Some(claims.sub));
+    active_model.cancel_reason = Set(Some(reason.to_string()));
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let prescription = active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!(
+            "Failed to cancel prescription {}: {}",
+            prescription.pid,
+            err
+        );
+        ApiResponse::new(500, json!({ "message": "Failed to cancel prescription" }))
+    })?;
+
+    let items = fetch_prescription_items(&tenant_db, vec![prescription.id]).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "prescription": prescription_json(&prescription, &items),
+            "message": "Prescription cancelled successfully",
+        }),
+    ))
+}
+
+async fn find_encounter(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+    patient_pid: Uuid,
+) -> Result<tenant::entities::encounters::Model, ApiResponse> {
+    tenant::entities::encounters::Entity::find_by_pid(pid)
+        .filter(tenant::entities::encounters::Column::PatientPid.eq(patient_pid))
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch encounter {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch encounter" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Encounter not found" })))
+}
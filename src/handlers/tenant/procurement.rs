@@ -0,0 +1,1793 @@
+use std::collections::HashMap;
+
+use actix_web::{HttpRequest, web};
+use chrono::{NaiveDate, NaiveDateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::tenant::{
+        self,
+        entities::sea_orm_active_enums::PurchaseOrderStatus,
+        migrations::sea_orm::{
+            ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait,
+            PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait,
+        },
+    },
+    handlers::services::{
+        pharmacy::{find_product, find_store, product_json, receive_stock, usable_stock},
+        procurement::{
+            OPEN_ORDER_STATUSES, average_daily_consumption, find_purchase_order, find_supplier,
+            goods_received_item_json, goods_received_note_json, purchase_order_item_json,
+            purchase_order_json, quantities_on_order, record_supplier_price, supplier_json,
+            supplier_price_json,
+        },
+    },
+    utils::{
+        api_response::ApiResponse,
+        app_state::AppState,
+        documents::document_number,
+        jwt::{get_logged_in_user_claims, get_tenant_db},
+        validator_error::ValidationError,
+    },
+};
+
+/// Lead time assumed for products no supplier has priced yet.
+const DEFAULT_LEAD_TIME_DAYS: i32 = 14;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct SupplierData {
+    pub name: Option<String>,
+    pub code: Option<String>,
+    pub contact_person: Option<String>,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub address: Option<String>,
+    pub payment_terms: Option<String>,
+    pub lead_time_days: Option<i32>,
+    pub is_active: Option<bool>,
+}
+
+impl SupplierData {
+    pub fn validate(&self, is_create: bool) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        match self.name.as_deref().map(str::trim) {
+            None if is_create => {
+                errors.insert("name".to_string(), "Supplier name is required".to_string());
+            }
+            Some("") => {
+                errors.insert("name".to_string(), "Supplier name is required".to_string());
+            }
+            Some(name) if name.len() > 150 => {
+                errors.insert(
+                    "name".to_string(),
+                    "Supplier name must be at most 150 characters".to_string(),
+                );
+            }
+            _ => {}
+        }
+
+        match self.code.as_deref().map(str::trim) {
+            None if is_create => {
+                errors.insert("code".to_string(), "Supplier code is required".to_string());
+            }
+            Some("") => {
+                errors.insert("code".to_string(), "Supplier code is required".to_string());
+            }
+            Some(code) if code.len() > 20 => {
+                errors.insert(
+                    "code".to_string(),
+                    "Supplier code must be at most 20 characters".to_string(),
+                );
+            }
+            _ => {}
+        }
+
+        if self.contact_person.as_ref().is_some_and(|c| c.len() > 100) {
+            errors.insert(
+                "contact_person".to_string(),
+                "Contact person must be at most 100 characters".to_string(),
+            );
+        }
+
+        if self.phone.as_ref().is_some_and(|p| p.len() > 20) {
+            errors.insert(
+                "phone".to_string(),
+                "Phone must be at most 20 characters".to_string(),
+            );
+        }
+
+        if let Some(email) = &self.email
+            && (email.len() > 100 || !email.contains('@'))
+        {
+            errors.insert(
+                "email".to_string(),
+                "Please provide a valid email address".to_string(),
+            );
+        }
+
+        if self.payment_terms.as_ref().is_some_and(|p| p.len() > 100) {
+            errors.insert(
+                "payment_terms".to_string(),
+                "Payment terms must be at most 100 characters".to_string(),
+            );
+        }
+
+        if self.lead_time_days.is_some_and(|d| !(0..=365).contains(&d)) {
+            errors.insert(
+                "lead_time_days".to_string(),
+                "Lead time must be between 0 and 365 days".to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SupplierParams {
+    pub search: Option<String>,
+    pub include_inactive: Option<bool>,
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+}
+
+pub async fn index_suppliers(
+    app_state: web::Data<AppState>,
+    query: web::Query<SupplierParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let mut stmt = tenant::entities::suppliers::Entity::find()
+        .filter(tenant::entities::suppliers::Column::DeletedAt.is_null());
+
+    if !query.include_inactive.unwrap_or(false) {
+        stmt = stmt.filter(tenant::entities::suppliers::Column::IsActive.eq(true));
+    }
+
+    if let Some(term) = &query.search {
+        use tenant::migrations::{Expr, extension::postgres::PgExpr};
+
+        let like = format!("%{}%", term);
+        stmt = stmt.filter(
+            Condition::any()
+                .add(Expr::col(tenant::entities::suppliers::Column::Name).ilike(like.clone()))
+                .add(Expr::col(tenant::entities::suppliers::Column::Code).ilike(like)),
+        );
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(10).clamp(1, 100);
+    let paginator = stmt
+        .order_by_asc(tenant::entities::suppliers::Column::Name)
+        .paginate(&tenant_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
+
+    let suppliers = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "suppliers": suppliers.iter().map(supplier_json).collect::<Vec<_>>(),
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "Suppliers fetched successfully",
+        }),
+    ))
+}
+
+pub async fn create_supplier(
+    app_state: web::Data<AppState>,
+    data: web::Json<SupplierData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate(true) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let code = data
+        .code
+        .as_deref()
+        .unwrap_or_default()
+        .trim()
+        .to_uppercase();
+    ensure_supplier_code_available(&tenant_db, &code, None).await?;
+
+    let supplier = tenant::entities::suppliers::ActiveModel {
+        name: Set(data.name.as_deref().unwrap_or_default().trim().to_string()),
+        code: Set(code),
+        contact_person: Set(data.contact_person.clone()),
+        phone: Set(data.phone.clone()),
+        email: Set(data.email.as_deref().map(|e| e.trim().to_lowercase())),
+        address: Set(data.address.clone()),
+        payment_terms: Set(data.payment_terms.clone()),
+        lead_time_days: Set(data.lead_time_days.unwrap_or(DEFAULT_LEAD_TIME_DAYS)),
+        is_active: Set(data.is_active.unwrap_or(true)),
+        ..Default::default()
+    }
+    .insert(&tenant_db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to create supplier: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create supplier" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "supplier": supplier_json(&supplier),
+            "message": "Supplier created successfully",
+        }),
+    ))
+}
+
+/// The supplier with their latest price for each product they have supplied
+/// or quoted.
+pub async fn show_supplier(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let supplier = find_supplier(&tenant_db, path.into_inner()).await?;
+
+    let prices = tenant::entities::supplier_prices::Entity::find()
+        .filter(tenant::entities::supplier_prices::Column::SupplierId.eq(supplier.id))
+        .find_also_related(tenant::entities::pharmacy_products::Entity)
+        .order_by_desc(tenant::entities::supplier_prices::Column::EffectiveAt)
+        .order_by_desc(tenant::entities::supplier_prices::Column::Id)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to fetch prices for supplier {}: {}",
+                supplier.pid,
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to fetch supplier" }))
+        })?;
+
+    let mut seen = Vec::new();
+    let mut latest_prices = Vec::new();
+    for (price, product) in &prices {
+        if seen.contains(&price.product_id) {
+            continue;
+        }
+        seen.push(price.product_id);
+        latest_prices.push(json!({
+            "product": product.as_ref().map(product_json),
+            "unit_cost": price.unit_cost,
+            "effective_at": price.effective_at,
+        }));
+    }
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "supplier": supplier_json(&supplier),
+            "latest_prices": latest_prices,
+            "message": "Supplier fetched successfully",
+        }),
+    ))
+}
+
+pub async fn edit_supplier(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<SupplierData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate(false) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let supplier = find_supplier(&tenant_db, path.into_inner()).await?;
+    let supplier_pid = supplier.pid;
+
+    let mut active_model: tenant::entities::suppliers::ActiveModel = supplier.into();
+
+    if let Some(name) = &data.name {
+        active_model.name = Set(name.trim().to_string());
+    }
+    if let Some(code) = &data.code {
+        let code = code.trim().to_uppercase();
+        ensure_supplier_code_available(&tenant_db, &code, Some(supplier_pid)).await?;
+        active_model.code = Set(code);
+    }
+    if let Some(contact_person) = &data.contact_person {
+        active_model.contact_person = Set(Some(contact_person.clone()));
+    }
+    if let Some(phone) = &data.phone {
+        active_model.phone = Set(Some(phone.clone()));
+    }
+    if let Some(email) = &data.email {
+        active_model.email = Set(Some(email.trim().to_lowercase()));
+    }
+    if let Some(address) = &data.address {
+        active_model.address = Set(Some(address.clone()));
+    }
+    if let Some(payment_terms) = &data.payment_terms {
+        active_model.payment_terms = Set(Some(payment_terms.clone()));
+    }
+    if let Some(lead_time_days) = data.lead_time_days {
+        active_model.lead_time_days = Set(lead_time_days);
+    }
+    if let Some(is_active) = data.is_active {
+        active_model.is_active = Set(is_active);
+    }
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let supplier = active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!("Failed to update supplier {}: {}", supplier_pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to update supplier" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "supplier": supplier_json(&supplier),
+            "message": "Supplier updated successfully",
+        }),
+    ))
+}
+
+/// Suppliers with orders still open cannot be removed.
+pub async fn destroy_supplier(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let supplier = find_supplier(&tenant_db, path.into_inner()).await?;
+
+    let open_orders = tenant::entities::purchase_orders::Entity::find()
+        .filter(tenant::entities::purchase_orders::Column::SupplierId.eq(supplier.id))
+        .filter(tenant::entities::purchase_orders::Column::Status.is_in(OPEN_ORDER_STATUSES))
+        .count(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to count orders for supplier {}: {}",
+                supplier.pid,
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to delete supplier" }))
+        })?;
+
+    if open_orders > 0 {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Supplier has open purchase orders" }),
+        ));
+    }
+
+    let supplier_pid = supplier.pid;
+    let mut active_model: tenant::entities::suppliers::ActiveModel = supplier.into();
+    active_model.deleted_at = Set(Some(Utc::now().naive_utc()));
+    active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!("Failed to delete supplier {}: {}", supplier_pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to delete supplier" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({ "message": "Supplier deleted successfully" }),
+    ))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PriceParams {
+    pub product_pid: Option<Uuid>,
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+}
+
+/// A supplier's price history, newest first.
+pub async fn supplier_prices(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    query: web::Query<PriceParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let supplier = find_supplier(&tenant_db, path.into_inner()).await?;
+
+    let mut stmt = tenant::entities::supplier_prices::Entity::find()
+        .filter(tenant::entities::supplier_prices::Column::SupplierId.eq(supplier.id));
+
+    let product = match query.product_pid {
+        Some(product_pid) => {
+            let product = find_product(&tenant_db, product_pid).await?;
+            stmt = stmt.filter(tenant::entities::supplier_prices::Column::ProductId.eq(product.id));
+            Some(product)
+        }
+        None => None,
+    };
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let paginator = stmt
+        .find_also_related(tenant::entities::goods_received_notes::Entity)
+        .order_by_desc(tenant::entities::supplier_prices::Column::EffectiveAt)
+        .order_by_desc(tenant::entities::supplier_prices::Column::Id)
+        .paginate(&tenant_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
+
+    let prices = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let products = fetch_products(&tenant_db, prices.iter().map(|(p, _)| p.product_id)).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "supplier": supplier_json(&supplier),
+            "product": product.as_ref().map(product_json),
+            "prices": prices
+                .iter()
+                .map(|(price, grn)| {
+                    let mut data = supplier_price_json(price, grn.as_ref());
+                    data["product"] = products
+                        .get(&price.product_id)
+                        .map(product_json)
+                        .unwrap_or(Value::Null);
+                    data
+                })
+                .collect::<Vec<_>>(),
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "Supplier prices fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct PriceQuoteData {
+    pub product_pid: Option<Uuid>,
+    pub unit_cost: Option<Decimal>,
+    pub effective_at: Option<NaiveDateTime>,
+}
+
+/// Records a quoted price. Prices paid on deliveries are recorded
+/// automatically when goods are received.
+pub async fn create_supplier_price(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<PriceQuoteData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let mut errors = HashMap::new();
+    if data.product_pid.is_none() {
+        errors.insert("product_pid".to_string(), "Product is required".to_string());
+    }
+    if data.unit_cost.is_none_or(|c| c.is_sign_negative()) {
+        errors.insert(
+            "unit_cost".to_string(),
+            "Unit cost is required and cannot be negative".to_string(),
+        );
+    }
+    if !errors.is_empty() {
+        return Err(ApiResponse::new(400, json!(ValidationError { errors })));
+    }
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let supplier = find_supplier(&tenant_db, path.into_inner()).await?;
+    let product = find_product(&tenant_db, data.product_pid.unwrap_or_default()).await?;
+
+    let price = record_supplier_price(
+        &tenant_db,
+        supplier.id,
+        product.id,
+        data.unit_cost.unwrap_or_default(),
+        None,
+        claims.sub,
+        data.effective_at.unwrap_or_else(|| Utc::now().naive_utc()),
+    )
+    .await?;
+
+    let mut data = supplier_price_json(&price, None);
+    data["product"] = product_json(&product);
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "price": data,
+            "message": "Supplier price recorded successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct PurchaseOrderItemData {
+    pub product_pid: Option<Uuid>,
+    /// In the product's dispensing unit, not packs.
+    pub quantity: Option<i32>,
+    /// Defaults to the supplier's latest price for the product.
+    pub unit_cost: Option<Decimal>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct PurchaseOrderData {
+    pub supplier_pid: Option<Uuid>,
+    pub store_pid: Option<Uuid>,
+    pub expected_date: Option<NaiveDate>,
+    pub notes: Option<String>,
+    /// Replaces every line on the order when given.
+    pub items: Option<Vec<PurchaseOrderItemData>>,
+}
+
+impl PurchaseOrderData {
+    pub fn validate(&self, is_create: bool) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if is_create && self.supplier_pid.is_none() {
+            errors.insert(
+                "supplier_pid".to_string(),
+                "Supplier is required".to_string(),
+            );
+        }
+
+        if is_create && self.store_pid.is_none() {
+            errors.insert(
+                "store_pid".to_string(),
+                "Receiving store is required".to_string(),
+            );
+        }
+
+        match &self.items {
+            None if is_create => {
+                errors.insert(
+                    "items".to_string(),
+                    "At least one item is required".to_string(),
+                );
+            }
+            Some(items) if items.is_empty() => {
+                errors.insert(
+                    "items".to_string(),
+                    "At least one item is required".to_string(),
+                );
+            }
+            Some(items) => {
+                let mut products = Vec::new();
+                for (index, item) in items.iter().enumerate() {
+                    match item.product_pid {
+                        None => {
+                            errors.insert(
+                                format!("items.{}.product_pid", index),
+                                "Product is required".to_string(),
+                            );
+                        }
+                        Some(product_pid) if products.contains(&product_pid) => {
+                            errors.insert(
+                                format!("items.{}.product_pid", index),
+                                "Product is already on the order".to_string(),
+                            );
+                        }
+                        Some(product_pid) => products.push(product_pid),
+                    }
+
+                    if item.quantity.is_none_or(|q| q <= 0) {
+                        errors.insert(
+                            format!("items.{}.quantity", index),
+                            "Quantity must be at least 1".to_string(),
+                        );
+                    }
+
+                    if item.unit_cost.is_some_and(|c| c.is_sign_negative()) {
+                        errors.insert(
+                            format!("items.{}.unit_cost", index),
+                            "Unit cost cannot be negative".to_string(),
+                        );
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PurchaseOrderParams {
+    pub supplier_pid: Option<Uuid>,
+    pub store_pid: Option<Uuid>,
+    pub status: Option<PurchaseOrderStatus>,
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+}
+
+pub async fn index_orders(
+    app_state: web::Data<AppState>,
+    query: web::Query<PurchaseOrderParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let mut stmt = tenant::entities::purchase_orders::Entity::find()
+        .find_also_related(tenant::entities::suppliers::Entity);
+
+    if let Some(supplier_pid) = query.supplier_pid {
+        let supplier = find_supplier(&tenant_db, supplier_pid).await?;
+        stmt = stmt.filter(tenant::entities::purchase_orders::Column::SupplierId.eq(supplier.id));
+    }
+
+    if let Some(store_pid) = query.store_pid {
+        let store = find_store(&tenant_db, store_pid).await?;
+        stmt = stmt.filter(tenant::entities::purchase_orders::Column::StoreId.eq(store.id));
+    }
+
+    if let Some(status) = &query.status {
+        stmt = stmt.filter(tenant::entities::purchase_orders::Column::Status.eq(status.clone()));
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let paginator = stmt
+        .order_by_desc(tenant::entities::purchase_orders::Column::CreatedAt)
+        .paginate(&tenant_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
+
+    let orders = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "purchase_orders": orders
+                .iter()
+                .map(|(order, supplier)| {
+                    purchase_order_json(order, supplier.as_ref(), None, Vec::new())
+                })
+                .collect::<Vec<_>>(),
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "Purchase orders fetched successfully",
+        }),
+    ))
+}
+
+/// Creates a draft order. Lines without a unit cost take the supplier's
+/// latest price for the product.
+pub async fn create_order(
+    app_state: web::Data<AppState>,
+    data: web::Json<PurchaseOrderData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate(true) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let supplier = find_supplier(&tenant_db, data.supplier_pid.unwrap_or_default()).await?;
+    let store = find_store(&tenant_db, data.store_pid.unwrap_or_default()).await?;
+
+    if !supplier.is_active {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Supplier is not active" }),
+        ));
+    }
+
+    let lines = resolve_order_lines(
+        &tenant_db,
+        supplier.id,
+        data.items.as_deref().unwrap_or_default(),
+    )
+    .await?;
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start purchase order transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create purchase order" }))
+    })?;
+
+    let order = tenant::entities::purchase_orders::ActiveModel {
+        order_number: Set(document_number("PO")),
+        supplier_id: Set(supplier.id),
+        store_id: Set(store.id),
+        status: Set(PurchaseOrderStatus::Draft),
+        expected_date: Set(data.expected_date),
+        notes: Set(data.notes.clone()),
+        total_amount: Set(order_total(&lines)),
+        created_by: Set(claims.sub),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to create purchase order: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create purchase order" }))
+    })?;
+
+    insert_order_lines(&txn, order.id, &lines).await?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit purchase order: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create purchase order" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "purchase_order": order_detail(&tenant_db, &order).await?,
+            "message": "Purchase order created successfully",
+        }),
+    ))
+}
+
+/// The order with its lines and every delivery received against it.
+pub async fn show_order(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let order = find_purchase_order(&tenant_db, path.into_inner()).await?;
+
+    let grns = tenant::entities::goods_received_notes::Entity::find()
+        .filter(tenant::entities::goods_received_notes::Column::PurchaseOrderId.eq(order.id))
+        .order_by_asc(tenant::entities::goods_received_notes::Column::ReceivedAt)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to fetch deliveries for order {}: {}",
+                order.pid,
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to fetch purchase order" }))
+        })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "purchase_order": order_detail(&tenant_db, &order).await?,
+            "goods_received_notes": grns
+                .iter()
+                .map(|grn| goods_received_note_json(grn, None, Vec::new()))
+                .collect::<Vec<_>>(),
+            "message": "Purchase order fetched successfully",
+        }),
+    ))
+}
+
+/// Only draft orders can be changed.
+pub async fn edit_order(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<PurchaseOrderData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate(false) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let order = find_purchase_order(&tenant_db, path.into_inner()).await?;
+
+    if order.status != PurchaseOrderStatus::Draft {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Only draft purchase orders can be edited" }),
+        ));
+    }
+
+    let supplier_id = match data.supplier_pid {
+        Some(supplier_pid) => find_supplier(&tenant_db, supplier_pid).await?.id,
+        None => order.supplier_id,
+    };
+
+    let lines = match &data.items {
+        Some(items) => Some(resolve_order_lines(&tenant_db, supplier_id, items).await?),
+        None => None,
+    };
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start purchase order transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to update purchase order" }))
+    })?;
+
+    let order_pid = order.pid;
+    let order_id = order.id;
+    let mut active_model: tenant::entities::purchase_orders::ActiveModel = order.into();
+    active_model.supplier_id = Set(supplier_id);
+    if let Some(store_pid) = data.store_pid {
+        active_model.store_id = Set(find_store(&tenant_db, store_pid).await?.id);
+    }
+    if let Some(expected_date) = data.expected_date {
+        active_model.expected_date = Set(Some(expected_date));
+    }
+    if let Some(notes) = &data.notes {
+        active_model.notes = Set(Some(notes.clone()));
+    }
+    if let Some(lines) = &lines {
+        tenant::entities::purchase_order_items::Entity::delete_many()
+            .filter(tenant::entities::purchase_order_items::Column::PurchaseOrderId.eq(order_id))
+            .exec(&txn)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to clear lines of order {}: {}", order_pid, err);
+                ApiResponse::new(500, json!({ "message": "Failed to update purchase order" }))
+            })?;
+        insert_order_lines(&txn, order_id, lines).await?;
+        active_model.total_amount = Set(order_total(lines));
+    }
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let order = active_model.update(&txn).await.map_err(|err| {
+        log::error!("Failed to update purchase order {}: {}", order_pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to update purchase order" }))
+    })?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit purchase order: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to update purchase order" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "purchase_order": order_detail(&tenant_db, &order).await?,
+            "message": "Purchase order updated successfully",
+        }),
+    ))
+}
+
+/// Sends a draft for approval.
+pub async fn submit_order(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let order = find_purchase_order(&tenant_db, path.into_inner()).await?;
+
+    if order.status != PurchaseOrderStatus::Draft {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Only draft purchase orders can be submitted" }),
+        ));
+    }
+
+    let mut active_model: tenant::entities::purchase_orders::ActiveModel = order.clone().into();
+    active_model.status = Set(PurchaseOrderStatus::Submitted);
+    active_model.submitted_at = Set(Some(Utc::now().naive_utc()));
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let order = active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!("Failed to submit purchase order {}: {}", order.pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to submit purchase order" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "purchase_order": order_detail(&tenant_db, &order).await?,
+            "message": "Purchase order submitted successfully",
+        }),
+    ))
+}
+
+/// Approves a submitted order so goods can be received against it.
+pub async fn approve_order(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let order = find_purchase_order(&tenant_db, path.into_inner()).await?;
+
+    if order.status != PurchaseOrderStatus::Submitted {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Only submitted purchase orders can be approved" }),
+        ));
+    }
+
+    let mut active_model: tenant::entities::purchase_orders::ActiveModel = order.clone().into();
+    active_model.status = Set(PurchaseOrderStatus::Approved);
+    active_model.approved_at = Set(Some(Utc::now().naive_utc()));
+    active_model.approved_by = Set(Some(claims.sub));
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let order = active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!("Failed to approve purchase order {}: {}", order.pid, err);
+        ApiResponse::new(
+            500,
+            json!({ "message": "Failed to approve purchase order" }),
+        )
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "purchase_order": order_detail(&tenant_db, &order).await?,
+            "message": "Purchase order approved successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct CancelOrderData {
+    pub reason: Option<String>,
+}
+
+/// Cancels an order. On a partially received order this closes the
+/// outstanding balance; what was already received stays in stock.
+pub async fn cancel_order(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<CancelOrderData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let Some(reason) = data
+        .reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|r| !r.is_empty())
+    else {
+        return Err(ApiResponse::new(
+            400,
+            json!(ValidationError {
+                errors: HashMap::from([("reason".to_string(), "Reason is required".to_string())]),
+            }),
+        ));
+    };
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let order = find_purchase_order(&tenant_db, path.into_inner()).await?;
+
+    if matches!(
+        order.status,
+        PurchaseOrderStatus::Received | PurchaseOrderStatus::Cancelled
+    ) {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Purchase order is already closed" }),
+        ));
+    }
+
+    let mut active_model: tenant::entities::purchase_orders::ActiveModel = order.clone().into();
+    active_model.status = Set(PurchaseOrderStatus::Cancelled);
+    active_model.cancelled_at = Set(Some(Utc::now().naive_utc()));
+    active_model.cancelled_by = Set(Some(claims.sub));
+    active_model.cancel_reason = Set(Some(reason.to_string()));
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let order = active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!("Failed to cancel purchase order {}: {}", order.pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to cancel purchase order" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "purchase_order": order_detail(&tenant_db, &order).await?,
+            "message": "Purchase order cancelled successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ReceiptLineData {
+    pub purchase_order_item_pid: Option<Uuid>,
+    pub batch_number: Option<String>,
+    pub expiry_date: Option<NaiveDate>,
+    pub quantity: Option<i32>,
+    /// Defaults to the price on the order.
+    pub unit_cost: Option<Decimal>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct GoodsReceiptData {
+    pub delivery_note_number: Option<String>,
+    pub notes: Option<String>,
+    pub items: Vec<ReceiptLineData>,
+}
+
+impl GoodsReceiptData {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if self
+            .delivery_note_number
+            .as_ref()
+            .is_some_and(|d| d.len() > 50)
+        {
+            errors.insert(
+                "delivery_note_number".to_string(),
+                "Delivery note number must be at most 50 characters".to_string(),
+            );
+        }
+
+        if self.items.is_empty() {
+            errors.insert(
+                "items".to_string(),
+                "At least one item is required".to_string(),
+            );
+        }
+
+        let today = Utc::now().date_naive();
+        for (index, item) in self.items.iter().enumerate() {
+            if item.purchase_order_item_pid.is_none() {
+                errors.insert(
+                    format!("items.{}.purchase_order_item_pid", index),
+                    "Order item is required".to_string(),
+                );
+            }
+
+            match item.batch_number.as_deref().map(str::trim) {
+                None | Some("") => {
+                    errors.insert(
+                        format!("items.{}.batch_number", index),
+                        "Batch number is required".to_string(),
+                    );
+                }
+                Some(batch_number) if batch_number.len() > 50 => {
+                    errors.insert(
+                        format!("items.{}.batch_number", index),
+                        "Batch number must be at most 50 characters".to_string(),
+                    );
+                }
+                _ => {}
+            }
+
+            match item.expiry_date {
+                None => {
+                    errors.insert(
+                        format!("items.{}.expiry_date", index),
+                        "Expiry date is required".to_string(),
+                    );
+                }
+                Some(expiry_date) if expiry_date < today => {
+                    errors.insert(
+                        format!("items.{}.expiry_date", index),
+                        "Expired stock cannot be received".to_string(),
+                    );
+                }
+                _ => {}
+            }
+
+            if item.quantity.is_none_or(|q| q <= 0) {
+                errors.insert(
+                    format!("items.{}.quantity", index),
+                    "Quantity must be at least 1".to_string(),
+                );
+            }
+
+            if item.unit_cost.is_some_and(|c| c.is_sign_negative()) {
+                errors.insert(
+                    format!("items.{}.unit_cost", index),
+                    "Unit cost cannot be negative".to_string(),
+                );
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+/// Records a delivery against an approved order. Each line is booked into
+/// the order's store as a batch, counts towards the order line and adds the
+/// price paid to the supplier's price history. The order becomes received
+/// once every line is delivered in full.
+pub async fn receive_goods(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<GoodsReceiptData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate() {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let order = find_purchase_order(&tenant_db, path.into_inner()).await?;
+
+    if !matches!(
+        order.status,
+        PurchaseOrderStatus::Approved | PurchaseOrderStatus::PartiallyReceived
+    ) {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Goods can only be received against approved orders" }),
+        ));
+    }
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start goods receipt transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to receive goods" }))
+    })?;
+
+    // Locked so two deliveries cannot both fill the same outstanding balance.
+    let mut items: Vec<tenant::entities::purchase_order_items::Model> =
+        tenant::entities::purchase_order_items::Entity::find()
+            .filter(tenant::entities::purchase_order_items::Column::PurchaseOrderId.eq(order.id))
+            .order_by_asc(tenant::entities::purchase_order_items::Column::Id)
+            .lock_exclusive()
+            .all(&txn)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to lock lines of order {}: {}", order.pid, err);
+                ApiResponse::new(500, json!({ "message": "Failed to receive goods" }))
+            })?;
+
+    let mut errors = HashMap::new();
+    let mut received: HashMap<i32, i32> = HashMap::new();
+    for (index, line) in data.items.iter().enumerate() {
+        let Some(item) = items
+            .iter()
+            .find(|item| Some(item.pid) == line.purchase_order_item_pid)
+        else {
+            errors.insert(
+                format!("items.{}.purchase_order_item_pid", index),
+                "Item is not on this purchase order".to_string(),
+            );
+            continue;
+        };
+
+        let total = received.entry(item.id).or_default();
+        *total += line.quantity.unwrap_or_default();
+        if *total > item.quantity_ordered - item.quantity_received {
+            errors.insert(
+                format!("items.{}.quantity", index),
+                format!(
+                    "Only {} outstanding on this item",
+                    (item.quantity_ordered - item.quantity_received).max(0)
+                ),
+            );
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(ApiResponse::new(400, json!(ValidationError { errors })));
+    }
+
+    let products = fetch_products(&txn, items.iter().map(|item| item.product_id)).await?;
+    let received_at = Utc::now().naive_utc();
+    let grn = tenant::entities::goods_received_notes::ActiveModel {
+        grn_number: Set(document_number("GRN")),
+        purchase_order_id: Set(order.id),
+        supplier_id: Set(order.supplier_id),
+        store_id: Set(order.store_id),
+        delivery_note_number: Set(data.delivery_note_number.clone()),
+        notes: Set(data.notes.clone()),
+        received_by: Set(claims.sub),
+        received_at: Set(received_at),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to create goods received note: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to receive goods" }))
+    })?;
+
+    let mut total_amount = Decimal::ZERO;
+    let mut grn_items = Vec::with_capacity(data.items.len());
+    for line in &data.items {
+        let Some(item) = items
+            .iter_mut()
+            .find(|item| Some(item.pid) == line.purchase_order_item_pid)
+        else {
+            continue;
+        };
+        let Some(product) = products.get(&item.product_id) else {
+            continue;
+        };
+
+        let quantity = line.quantity.unwrap_or_default();
+        let unit_cost = line.unit_cost.unwrap_or(item.unit_cost);
+        let (batch, movement) = receive_stock(
+            &txn,
+            order.store_id,
+            product,
+            line.batch_number.as_deref().unwrap_or_default().trim(),
+            line.expiry_date.unwrap_or_default(),
+            Some(unit_cost),
+            quantity,
+            Some(grn.grn_number.clone()),
+            claims.sub,
+        )
+        .await?;
+
+        let grn_item = tenant::entities::goods_received_items::ActiveModel {
+            goods_received_note_id: Set(grn.id),
+            purchase_order_item_id: Set(item.id),
+            product_id: Set(product.id),
+            batch_id: Set(batch.id),
+            stock_movement_id: Set(movement.id),
+            quantity: Set(quantity),
+            unit_cost: Set(unit_cost),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to record received item: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to receive goods" }))
+        })?;
+
+        item.quantity_received += quantity;
+        tenant::entities::purchase_order_items::ActiveModel {
+            id: Set(item.id),
+            quantity_received: Set(item.quantity_received),
+            updated_at: Set(Utc::now().naive_utc()),
+            ..Default::default()
+        }
+        .update(&txn)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to update order item {}: {}", item.pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to receive goods" }))
+        })?;
+
+        record_supplier_price(
+            &txn,
+            order.supplier_id,
+            product.id,
+            unit_cost,
+            Some(grn.id),
+            claims.sub,
+            received_at,
+        )
+        .await?;
+
+        total_amount += unit_cost * Decimal::from(quantity);
+        grn_items.push(goods_received_item_json(
+            &grn_item,
+            Some(product),
+            Some(&batch),
+        ));
+    }
+
+    let grn = tenant::entities::goods_received_notes::ActiveModel {
+        id: Set(grn.id),
+        total_amount: Set(total_amount),
+        ..Default::default()
+    }
+    .update(&txn)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to total goods received note {}: {}", grn.pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to receive goods" }))
+    })?;
+
+    let fully_received = items
+        .iter()
+        .all(|item| item.quantity_received >= item.quantity_ordered);
+    let mut active_model: tenant::entities::purchase_orders::ActiveModel = order.clone().into();
+    if fully_received {
+        active_model.status = Set(PurchaseOrderStatus::Received);
+        active_model.received_at = Set(Some(received_at));
+    } else {
+        active_model.status = Set(PurchaseOrderStatus::PartiallyReceived);
+    }
+    active_model.updated_at = Set(Utc::now().naive_utc());
+    let order = active_model.update(&txn).await.map_err(|err| {
+        log::error!("Failed to update purchase order {}: {}", order.pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to receive goods" }))
+    })?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit goods receipt: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to receive goods" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "goods_received_note": goods_received_note_json(&grn, Some(&order), grn_items),
+            "message": "Goods received successfully",
+        }),
+    ))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GoodsReceivedParams {
+    pub purchase_order_pid: Option<Uuid>,
+    pub supplier_pid: Option<Uuid>,
+    pub store_pid: Option<Uuid>,
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+}
+
+pub async fn index_grns(
+    app_state: web::Data<AppState>,
+    query: web::Query<GoodsReceivedParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let mut stmt = tenant::entities::goods_received_notes::Entity::find()
+        .find_also_related(tenant::entities::purchase_orders::Entity);
+
+    if let Some(purchase_order_pid) = query.purchase_order_pid {
+        let order = find_purchase_order(&tenant_db, purchase_order_pid).await?;
+        stmt = stmt
+            .filter(tenant::entities::goods_received_notes::Column::PurchaseOrderId.eq(order.id));
+    }
+
+    if let Some(supplier_pid) = query.supplier_pid {
+        let supplier = find_supplier(&tenant_db, supplier_pid).await?;
+        stmt =
+            stmt.filter(tenant::entities::goods_received_notes::Column::SupplierId.eq(supplier.id));
+    }
+
+    if let Some(store_pid) = query.store_pid {
+        let store = find_store(&tenant_db, store_pid).await?;
+        stmt = stmt.filter(tenant::entities::goods_received_notes::Column::StoreId.eq(store.id));
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let paginator = stmt
+        .order_by_desc(tenant::entities::goods_received_notes::Column::ReceivedAt)
+        .paginate(&tenant_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
+
+    let grns = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "goods_received_notes": grns
+                .iter()
+                .map(|(grn, order)| goods_received_note_json(grn, order.as_ref(), Vec::new()))
+                .collect::<Vec<_>>(),
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "Goods received notes fetched successfully",
+        }),
+    ))
+}
+
+pub async fn show_grn(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let pid = path.into_inner();
+
+    let (grn, order) = tenant::entities::goods_received_notes::Entity::find_by_pid(pid)
+        .find_also_related(tenant::entities::purchase_orders::Entity)
+        .one(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch goods received note {}: {}", pid, err);
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to fetch goods received note" }),
+            )
+        })?
+        .ok_or_else(|| {
+            ApiResponse::new(404, json!({ "message": "Goods received note not found" }))
+        })?;
+
+    let items = tenant::entities::goods_received_items::Entity::find()
+        .filter(tenant::entities::goods_received_items::Column::GoodsReceivedNoteId.eq(grn.id))
+        .find_also_related(tenant::entities::stock_batches::Entity)
+        .order_by_asc(tenant::entities::goods_received_items::Column::Id)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch items of {}: {}", grn.grn_number, err);
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to fetch goods received note" }),
+            )
+        })?;
+    let products = fetch_products(&tenant_db, items.iter().map(|(i, _)| i.product_id)).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "goods_received_note": goods_received_note_json(
+                &grn,
+                order.as_ref(),
+                items
+                    .iter()
+                    .map(|(item, batch)| {
+                        goods_received_item_json(
+                            item,
+                            products.get(&item.product_id),
+                            batch.as_ref(),
+                        )
+                    })
+                    .collect(),
+            ),
+            "message": "Goods received note fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ReorderParams {
+    pub store_pid: Option<Uuid>,
+    /// How far back to look when averaging consumption.
+    pub consumption_days: Option<i64>,
+    /// How many days of consumption a new order should cover.
+    pub cover_days: Option<i64>,
+}
+
+/// Products that should be reordered now. A product is due once its usable
+/// stock plus what is already on order falls to its reorder level plus the
+/// stock expected to be used while waiting for the supplier. The suggested
+/// quantity brings it back above that point with `cover_days` of average
+/// use on top, rounded up to whole packs.
+pub async fn reorder_report(
+    app_state: web::Data<AppState>,
+    query: web::Query<ReorderParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let consumption_days = query.consumption_days.unwrap_or(30).clamp(7, 365);
+    let cover_days = query.cover_days.unwrap_or(30).clamp(1, 365);
+
+    let store = match query.store_pid {
+        Some(store_pid) => Some(find_store(&tenant_db, store_pid).await?),
+        None => None,
+    };
+    let store_id = store.as_ref().map(|store| store.id);
+
+    let products = tenant::entities::pharmacy_products::Entity::find()
+        .filter(tenant::entities::pharmacy_products::Column::DeletedAt.is_null())
+        .filter(tenant::entities::pharmacy_products::Column::IsActive.eq(true))
+        .order_by_asc(tenant::entities::pharmacy_products::Column::GenericName)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch products: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to build reorder report" }))
+        })?;
+
+    let usable = usable_stock(
+        &tenant_db,
+        store_id,
+        products.iter().map(|p| p.id).collect(),
+    )
+    .await?;
+    let on_order = quantities_on_order(&tenant_db, store_id).await?;
+    let consumption = average_daily_consumption(&tenant_db, store_id, consumption_days).await?;
+    let preferred = preferred_suppliers(&tenant_db).await?;
+
+    let mut report = Vec::new();
+    for product in &products {
+        let usable = usable.get(&product.id).copied().unwrap_or(0);
+        let on_order = on_order.get(&product.id).copied().unwrap_or(0);
+        let daily = consumption.get(&product.id).copied().unwrap_or(0.0);
+        let supplier = preferred.get(&product.id);
+        let lead_time_days = supplier
+            .map(|(supplier, _)| supplier.lead_time_days)
+            .unwrap_or(DEFAULT_LEAD_TIME_DAYS);
+
+        let reorder_point =
+            product.reorder_level as i64 + (daily * lead_time_days as f64).ceil() as i64;
+        if usable + on_order > reorder_point {
+            continue;
+        }
+
+        let pack_size = product.pack_size.max(1) as i64;
+        let target = reorder_point + (daily * cover_days as f64).ceil() as i64;
+        let shortfall = (target - usable - on_order).max(pack_size);
+        let suggested_quantity = (shortfall + pack_size - 1) / pack_size * pack_size;
+
+        report.push(json!({
+            "product": product_json(product),
+            "usable": usable,
+            "on_order": on_order,
+            "reorder_level": product.reorder_level,
+            "average_daily_consumption": (daily * 100.0).round() / 100.0,
+            "days_of_cover": (daily > 0.0).then(|| (usable as f64 / daily).floor() as i64),
+            "lead_time_days": lead_time_days,
+            "reorder_point": reorder_point,
+            "suggested_quantity": suggested_quantity,
+            "suggested_packs": suggested_quantity / pack_size,
+            "preferred_supplier": supplier.map(|(supplier, unit_cost)| json!({
+                "pid": supplier.pid,
+                "name": supplier.name,
+                "last_unit_cost": unit_cost,
+                "estimated_cost": *unit_cost * Decimal::from(suggested_quantity),
+            })),
+        }));
+    }
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "store": store.as_ref().map(|store| json!({
+                "pid": store.pid,
+                "name": store.name,
+            })),
+            "consumption_days": consumption_days,
+            "cover_days": cover_days,
+            "items": report,
+            "message": "Reorder report generated successfully",
+        }),
+    ))
+}
+
+struct OrderLine {
+    product_id: i32,
+    quantity: i32,
+    unit_cost: Decimal,
+}
+
+fn order_total(lines: &[OrderLine]) -> Decimal {
+    lines
+        .iter()
+        .map(|line| line.unit_cost * Decimal::from(line.quantity))
+        .sum()
+}
+
+/// Looks up each line's product and fills in missing prices from the
+/// supplier's history.
+async fn resolve_order_lines(
+    tenant_db: &DatabaseConnection,
+    supplier_id: i32,
+    items: &[PurchaseOrderItemData],
+) -> Result<Vec<OrderLine>, ApiResponse> {
+    let mut errors = HashMap::new();
+    let mut lines = Vec::with_capacity(items.len());
+    for (index, item) in items.iter().enumerate() {
+        let product = find_product(tenant_db, item.product_pid.unwrap_or_default()).await?;
+
+        let unit_cost = match item.unit_cost {
+            Some(unit_cost) => Some(unit_cost),
+            None => tenant::entities::supplier_prices::Entity::find()
+                .filter(tenant::entities::supplier_prices::Column::SupplierId.eq(supplier_id))
+                .filter(tenant::entities::supplier_prices::Column::ProductId.eq(product.id))
+                .order_by_desc(tenant::entities::supplier_prices::Column::EffectiveAt)
+                .one(tenant_db)
+                .await
+                .map_err(|err| {
+                    log::error!("Failed to fetch supplier price: {}", err);
+                    ApiResponse::new(500, json!({ "message": "Failed to save purchase order" }))
+                })?
+                .map(|price| price.unit_cost),
+        };
+
+        let Some(unit_cost) = unit_cost else {
+            errors.insert(
+                format!("items.{}.unit_cost", index),
+                "Unit cost is required; this supplier has no price for the product".to_string(),
+            );
+            continue;
+        };
+
+        lines.push(OrderLine {
+            product_id: product.id,
+            quantity: item.quantity.unwrap_or_default(),
+            unit_cost,
+        });
+    }
+
+    if !errors.is_empty() {
+        return Err(ApiResponse::new(400, json!(ValidationError { errors })));
+    }
+
+    Ok(lines)
+}
+
+async fn insert_order_lines(
+    txn: &tenant::migrations::sea_orm::DatabaseTransaction,
+    purchase_order_id: i32,
+    lines: &[OrderLine],
+) -> Result<(), ApiResponse> {
+    for line in lines {
+        tenant::entities::purchase_order_items::ActiveModel {
+            purchase_order_id: Set(purchase_order_id),
+            product_id: Set(line.product_id),
+            quantity_ordered: Set(line.quantity),
+            quantity_received: Set(0),
+            unit_cost: Set(line.unit_cost),
+            ..Default::default()
+        }
+        .insert(txn)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to add purchase order line: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to save purchase order" }))
+        })?;
+    }
+
+    Ok(())
+}
+
+async fn order_detail(
+    tenant_db: &DatabaseConnection,
+    order: &tenant::entities::purchase_orders::Model,
+) -> Result<Value, ApiResponse> {
+    let supplier = tenant::entities::suppliers::Entity::find_by_id(order.supplier_id)
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch supplier {}: {}", order.supplier_id, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch purchase order" }))
+        })?;
+    let store = tenant::entities::pharmacy_stores::Entity::find_by_id(order.store_id)
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch store {}: {}", order.store_id, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch purchase order" }))
+        })?;
+    let items = tenant::entities::purchase_order_items::Entity::find()
+        .filter(tenant::entities::purchase_order_items::Column::PurchaseOrderId.eq(order.id))
+        .order_by_asc(tenant::entities::purchase_order_items::Column::Id)
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch lines of order {}: {}", order.pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch purchase order" }))
+        })?;
+    let products = fetch_products(tenant_db, items.iter().map(|item| item.product_id)).await?;
+
+    Ok(purchase_order_json(
+        order,
+        supplier.as_ref(),
+        store.as_ref(),
+        items
+            .iter()
+            .map(|item| purchase_order_item_json(item, products.get(&item.product_id)))
+            .collect(),
+    ))
+}
+
+async fn fetch_products<C: tenant::migrations::sea_orm::ConnectionTrait>(
+    db: &C,
+    product_ids: impl IntoIterator<Item = i32>,
+) -> Result<HashMap<i32, tenant::entities::pharmacy_products::Model>, ApiResponse> {
+    Ok(tenant::entities::pharmacy_products::Entity::find()
+        .filter(tenant::entities::pharmacy_products::Column::Id.is_in(product_ids))
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch products: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch products" }))
+        })?
+        .into_iter()
+        .map(|product| (product.id, product))
+        .collect())
+}
+
+/// The supplier that last delivered or quoted each product, with that price.
+async fn preferred_suppliers(
+    tenant_db: &DatabaseConnection,
+) -> Result<HashMap<i32, (tenant::entities::suppliers::Model, Decimal)>, ApiResponse> {
+    let prices = tenant::entities::supplier_prices::Entity::find()
+        .find_also_related(tenant::entities::suppliers::Entity)
+        .filter(tenant::entities::suppliers::Column::DeletedAt.is_null())
+        .filter(tenant::entities::suppliers::Column::IsActive.eq(true))
+        .order_by_desc(tenant::entities::supplier_prices::Column::EffectiveAt)
+        .order_by_desc(tenant::entities::supplier_prices::Column::Id)
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch supplier prices: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to build reorder report" }))
+        })?;
+
+    let mut preferred = HashMap::new();
+    for (price, supplier) in prices {
+        if let Some(supplier) = supplier {
+            preferred
+                .entry(price.product_id)
+                .or_insert((supplier, price.unit_cost));
+        }
+    }
+
+    Ok(preferred)
+}
+
+async fn ensure_supplier_code_available(
+    tenant_db: &DatabaseConnection,
+    code: &str,
+    except: Option<Uuid>,
+) -> Result<(), ApiResponse> {
+    let mut stmt = tenant::entities::suppliers::Entity::find()
+        .filter(tenant::entities::suppliers::Column::Code.eq(code));
+
+    if let Some(pid) = except {
+        stmt = stmt.filter(tenant::entities::suppliers::Column::Pid.ne(pid));
+    }
+
+    let existing = stmt.one(tenant_db).await.map_err(|err| {
+        log::error!("Failed to check supplier code {}: {}", code, err);
+        ApiResponse::new(500, json!({ "message": "Failed to save supplier" }))
+    })?;
+
+    if existing.is_some() {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "A supplier with this code already exists" }),
+        ));
+    }
+
+    Ok(())
+}
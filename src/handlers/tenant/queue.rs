@@ -0,0 +1,807 @@
+use std::collections::HashMap;
+
+use actix_web::{HttpRequest, web};
+use chrono::{NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    db::tenant::{
+        self,
+        entities::sea_orm_active_enums::{QueueStage, QueueTicketStatus, TriagePriority},
+        migrations::sea_orm::{
+            ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+            QueryOrder, QuerySelect, Set,
+        },
+    },
+    handlers::services::{
+        patients::find_patient,
+        queue::{
+            ACTIVE_TICKET_STATUSES, QUEUE_STAGES, StreamTicket, issue_stream_token, parse_stage,
+            publish_queue_update, queue_ticket_json, stage_queue_snapshot, stage_slug,
+            stage_visit_json, triage_assessment_json, wait_seconds,
+        },
+    },
+    utils::{
+        api_response::ApiResponse,
+        app_state::AppState,
+        jwt::{get_logged_in_user_claims, get_tenant_db, get_tenant_id},
+        validator_error::ValidationError,
+    },
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct CheckInData {
+    pub patient_pid: Option<Uuid>,
+    pub notes: Option<String>,
+}
+
+/// Issues a queue ticket and places the patient in the triage queue.
+pub async fn check_in(
+    app_state: web::Data<AppState>,
+    data: web::Json<CheckInData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let Some(patient_pid) = data.patient_pid else {
+        let mut errors = HashMap::new();
+        errors.insert("patient_pid".to_string(), "Patient is required".to_string());
+        return Err(ApiResponse::new(400, json!(ValidationError { errors })));
+    };
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let (_, tenant_pid, _) = get_tenant_id(&req, &app_state).await?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let patient = find_patient(&app_state, patient_pid).await?;
+
+    let active = tenant::entities::queue_tickets::Entity::find()
+        .filter(tenant::entities::queue_tickets::Column::PatientPid.eq(patient.pid))
+        .filter(tenant::entities::queue_tickets::Column::Status.is_in(ACTIVE_TICKET_STATUSES))
+        .one(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to check active queue ticket: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to check in patient" }))
+        })?;
+
+    if let Some(active) = active {
+        return Err(ApiResponse::new(
+            409,
+            json!({
+                "message": "Patient is already in the queue",
+                "ticket": queue_ticket_json(&active),
+            }),
+        ));
+    }
+
+    let today = Utc::now().date_naive();
+    let sequence = next_ticket_sequence(&tenant_db, today).await?;
+
+    let ticket = tenant::entities::queue_tickets::ActiveModel {
+        patient_pid: Set(patient.pid),
+        queue_date: Set(today),
+        ticket_sequence: Set(sequence),
+        ticket_number: Set(format!("{:03}", sequence)),
+        priority: Set(TriagePriority::Routine),
+        stage: Set(QueueStage::Triage),
+        status: Set(QueueTicketStatus::Waiting),
+        checked_in_by: Set(Some(claims.sub)),
+        notes: Set(data.notes.clone()),
+        ..Default::default()
+    }
+    .insert(&tenant_db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to create queue ticket: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to check in patient" }))
+    })?;
+
+    let visit = open_stage_visit(&tenant_db, &ticket, QueueStage::Triage).await?;
+    publish_queue_update(&app_state.redis, tenant_pid, &QueueStage::Triage).await;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "ticket": queue_ticket_json(&ticket),
+            "stage_visit": stage_visit_json(&visit),
+            "message": "Patient checked in successfully",
+        }),
+    ))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct QueueParams {
+    pub display: Option<bool>,
+}
+
+/// Current queue for one stage.
+pub async fn stage_queue(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<QueueParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let stage = stage_from_path(&path)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let snapshot = stage_queue_snapshot(
+        &app_state.main_db,
+        &tenant_db,
+        &stage,
+        query.display.unwrap_or(false),
+    )
+    .await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "queue": snapshot,
+            "message": "Queue fetched successfully",
+        }),
+    ))
+}
+
+/// Issues a one-time token for a stage's live feed. Browsers cannot send an
+/// Authorization header with `EventSource`, so screens fetch a token here
+/// and open the returned `stream_url`, fetching a new token to reconnect.
+pub async fn live_queue_token(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<QueueParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let stage = stage_from_path(&path)?;
+    let (_, tenant_pid, sso_tenant_id) = get_tenant_id(&req, &app_state).await?;
+
+    let stream = issue_stream_token(
+        &app_state.redis,
+        &StreamTicket {
+            sso_tenant_id,
+            tenant_pid,
+            stage: stage_slug(&stage).to_string(),
+            display: query.display.unwrap_or(false),
+        },
+    )
+    .await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "stream": stream,
+            "message": "Stream token issued successfully",
+        }),
+    ))
+}
+
+pub async fn show_ticket(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let ticket = find_ticket(&tenant_db, path.into_inner()).await?;
+
+    let visits = tenant::entities::queue_stage_visits::Entity::find()
+        .filter(tenant::entities::queue_stage_visits::Column::TicketId.eq(ticket.id))
+        .order_by_asc(tenant::entities::queue_stage_visits::Column::QueuedAt)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch stage visits for {}: {}", ticket.pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch queue ticket" }))
+        })?;
+
+    let triage = tenant::entities::triage_assessments::Entity::find()
+        .filter(tenant::entities::triage_assessments::Column::TicketId.eq(ticket.id))
+        .order_by_desc(tenant::entities::triage_assessments::Column::CreatedAt)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch triage for {}: {}", ticket.pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch queue ticket" }))
+        })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "ticket": queue_ticket_json(&ticket),
+            "stage_visits": visits.iter().map(stage_visit_json).collect::<Vec<_>>(),
+            "triage_assessments": triage.iter().map(triage_assessment_json).collect::<Vec<_>>(),
+            "message": "Queue ticket fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct CallData {
+    pub room: Option<String>,
+}
+
+/// Calls the patient to a room or counter. Calling again (a recall) keeps
+/// the original call time so wait times are not reset.
+pub async fn call(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<CallData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if data.room.as_ref().is_some_and(|room| room.len() > 50) {
+        let mut errors = HashMap::new();
+        errors.insert(
+            "room".to_string(),
+            "Room must be at most 50 characters".to_string(),
+        );
+        return Err(ApiResponse::new(400, json!(ValidationError { errors })));
+    }
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let (_, tenant_pid, _) = get_tenant_id(&req, &app_state).await?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let ticket = find_active_ticket(&tenant_db, path.into_inner()).await?;
+    let visit = find_open_visit(&tenant_db, &ticket).await?;
+    let now = Utc::now().naive_utc();
+
+    let mut visit_model: tenant::entities::queue_stage_visits::ActiveModel = visit.clone().into();
+    visit_model.called_at = Set(Some(visit.called_at.unwrap_or(now)));
+    visit_model.room = Set(data.room.clone());
+    visit_model.handled_by = Set(Some(claims.sub));
+    visit_model.updated_at = Set(now);
+    let visit = update_visit(&tenant_db, visit_model).await?;
+
+    let mut ticket_model: tenant::entities::queue_tickets::ActiveModel = ticket.into();
+    ticket_model.status = Set(QueueTicketStatus::Called);
+    ticket_model.room = Set(data.room.clone());
+    let ticket = update_ticket(&tenant_db, ticket_model).await?;
+
+    publish_queue_update(&app_state.redis, tenant_pid, &ticket.stage).await;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "ticket": queue_ticket_json(&ticket),
+            "stage_visit": stage_visit_json(&visit),
+            "message": "Patient called successfully",
+        }),
+    ))
+}
+
+pub async fn start(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let claims = get_logged_in_user_claims(&req)?;
+    let (_, tenant_pid, _) = get_tenant_id(&req, &app_state).await?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let ticket = find_active_ticket(&tenant_db, path.into_inner()).await?;
+    let visit = find_open_visit(&tenant_db, &ticket).await?;
+    let now = Utc::now().naive_utc();
+
+    let mut visit_model: tenant::entities::queue_stage_visits::ActiveModel = visit.clone().into();
+    visit_model.called_at = Set(Some(visit.called_at.unwrap_or(now)));
+    visit_model.started_at = Set(Some(visit.started_at.unwrap_or(now)));
+    visit_model.handled_by = Set(Some(claims.sub));
+    visit_model.updated_at = Set(now);
+    let visit = update_visit(&tenant_db, visit_model).await?;
+
+    let mut ticket_model: tenant::entities::queue_tickets::ActiveModel = ticket.into();
+    ticket_model.status = Set(QueueTicketStatus::InService);
+    let ticket = update_ticket(&tenant_db, ticket_model).await?;
+
+    publish_queue_update(&app_state.redis, tenant_pid, &ticket.stage).await;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "ticket": queue_ticket_json(&ticket),
+            "stage_visit": stage_visit_json(&visit),
+            "message": "Service started successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct TriageData {
+    pub priority: Option<TriagePriority>,
+    pub chief_complaint: String,
+    pub temperature: Option<Decimal>,
+    pub pulse_rate: Option<i32>,
+    pub respiratory_rate: Option<i32>,
+    pub bp_systolic: Option<i32>,
+    pub bp_diastolic: Option<i32>,
+    pub oxygen_saturation: Option<i32>,
+    pub weight_kg: Option<Decimal>,
+    pub notes: Option<String>,
+    pub next_stage: Option<QueueStage>,
+}
+
+impl TriageData {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if self.priority.is_none() {
+            errors.insert(
+                "priority".to_string(),
+                "Triage priority is required".to_string(),
+            );
+        }
+
+        if self.chief_complaint.trim().is_empty() {
+            errors.insert(
+                "chief_complaint".to_string(),
+                "Chief complaint is required".to_string(),
+            );
+        }
+
+        if self
+            .temperature
+            .is_some_and(|t| t < Decimal::from(25) || t > Decimal::from(45))
+        {
+            errors.insert(
+                "temperature".to_string(),
+                "Temperature must be between 25 and 45 °C".to_string(),
+            );
+        }
+
+        if self.pulse_rate.is_some_and(|p| !(20..=300).contains(&p)) {
+            errors.insert(
+                "pulse_rate".to_string(),
+                "Pulse rate must be between 20 and 300".to_string(),
+            );
+        }
+
+        if self
+            .respiratory_rate
+            .is_some_and(|r| !(4..=80).contains(&r))
+        {
+            errors.insert(
+                "respiratory_rate".to_string(),
+                "Respiratory rate must be between 4 and 80".to_string(),
+            );
+        }
+
+        if self.bp_systolic.is_some() != self.bp_diastolic.is_some() {
+            errors.insert(
+                "bp_systolic".to_string(),
+                "Both systolic and diastolic pressure are required".to_string(),
+            );
+        }
+
+        if let (Some(systolic), Some(diastolic)) = (self.bp_systolic, self.bp_diastolic)
+            && systolic <= diastolic
+        {
+            errors.insert(
+                "bp_systolic".to_string(),
+                "Systolic pressure must be higher than diastolic pressure".to_string(),
+            );
+        }
+
+        if self
+            .oxygen_saturation
+            .is_some_and(|s| !(50..=100).contains(&s))
+        {
+            errors.insert(
+                "oxygen_saturation".to_string(),
+                "Oxygen saturation must be between 50 and 100".to_string(),
+            );
+        }
+
+        if self
+            .weight_kg
+            .is_some_and(|w| w <= Decimal::ZERO || w > Decimal::from(500))
+        {
+            errors.insert(
+                "weight_kg".to_string(),
+                "Weight must be between 0 and 500 kg".to_string(),
+            );
+        }
+
+        if self.next_stage == Some(QueueStage::Triage) {
+            errors.insert(
+                "next_stage".to_string(),
+                "Next stage must come after triage".to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+/// Records triage, sets the ticket's priority and moves the patient on to
+/// the next stage, consultation unless another is given.
+pub async fn triage(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<TriageData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate() {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let (_, tenant_pid, _) = get_tenant_id(&req, &app_state).await?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let ticket = find_active_ticket(&tenant_db, path.into_inner()).await?;
+
+    if ticket.stage != QueueStage::Triage {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Patient is not at triage" }),
+        ));
+    }
+
+    let priority = data.priority.clone().unwrap_or(TriagePriority::Routine);
+
+    let assessment = tenant::entities::triage_assessments::ActiveModel {
+        ticket_id: Set(ticket.id),
+        patient_pid: Set(ticket.patient_pid),
+        priority: Set(priority.clone()),
+        chief_complaint: Set(data.chief_complaint.trim().to_string()),
+        temperature: Set(data.temperature),
+        pulse_rate: Set(data.pulse_rate),
+        respiratory_rate: Set(data.respiratory_rate),
+        bp_systolic: Set(data.bp_systolic),
+        bp_diastolic: Set(data.bp_diastolic),
+        oxygen_saturation: Set(data.oxygen_saturation),
+        weight_kg: Set(data.weight_kg),
+        notes: Set(data.notes.clone()),
+        assessed_by: Set(Some(claims.sub)),
+        ..Default::default()
+    }
+    .insert(&tenant_db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to record triage for {}: {}", ticket.pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to record triage" }))
+    })?;
+
+    let next_stage = data.next_stage.clone().unwrap_or(QueueStage::Consultation);
+
+    let mut ticket_model: tenant::entities::queue_tickets::ActiveModel = ticket.clone().into();
+    ticket_model.priority = Set(priority);
+    let ticket = update_ticket(&tenant_db, ticket_model).await?;
+
+    let (ticket, visit) =
+        move_ticket(&tenant_db, ticket, Some(next_stage.clone()), claims.sub).await?;
+
+    publish_queue_update(&app_state.redis, tenant_pid, &QueueStage::Triage).await;
+    publish_queue_update(&app_state.redis, tenant_pid, &next_stage).await;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "ticket": queue_ticket_json(&ticket),
+            "stage_visit": visit.as_ref().map(stage_visit_json),
+            "triage_assessment": triage_assessment_json(&assessment),
+            "message": "Triage recorded successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct AdvanceData {
+    /// Where the patient goes next; leave empty when the visit is over.
+    pub next_stage: Option<QueueStage>,
+}
+
+pub async fn advance(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<AdvanceData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let claims = get_logged_in_user_claims(&req)?;
+    let (_, tenant_pid, _) = get_tenant_id(&req, &app_state).await?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let ticket = find_active_ticket(&tenant_db, path.into_inner()).await?;
+
+    if data.next_stage.as_ref() == Some(&ticket.stage) {
+        return Err(ApiResponse::new(
+            400,
+            json!({ "message": "Patient is already at this stage" }),
+        ));
+    }
+
+    if ticket.stage == QueueStage::Triage {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Record triage to move the patient on from triage" }),
+        ));
+    }
+
+    let current_stage = ticket.stage.clone();
+    let (ticket, visit) =
+        move_ticket(&tenant_db, ticket, data.next_stage.clone(), claims.sub).await?;
+
+    publish_queue_update(&app_state.redis, tenant_pid, &current_stage).await;
+    if let Some(next_stage) = &data.next_stage {
+        publish_queue_update(&app_state.redis, tenant_pid, next_stage).await;
+    }
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "ticket": queue_ticket_json(&ticket),
+            "stage_visit": visit.as_ref().map(stage_visit_json),
+            "message": if ticket.status == QueueTicketStatus::Completed {
+                "Visit completed successfully"
+            } else {
+                "Patient moved to the next stage successfully"
+            },
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct CancelData {
+    pub reason: Option<String>,
+    pub no_show: bool,
+}
+
+pub async fn cancel(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<CancelData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let (_, tenant_pid, _) = get_tenant_id(&req, &app_state).await?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let ticket = find_active_ticket(&tenant_db, path.into_inner()).await?;
+    let now = Utc::now().naive_utc();
+
+    if let Ok(visit) = find_open_visit(&tenant_db, &ticket).await {
+        let mut visit_model: tenant::entities::queue_stage_visits::ActiveModel = visit.into();
+        visit_model.completed_at = Set(Some(now));
+        visit_model.updated_at = Set(now);
+        update_visit(&tenant_db, visit_model).await?;
+    }
+
+    let mut ticket_model: tenant::entities::queue_tickets::ActiveModel = ticket.into();
+    ticket_model.status = Set(if data.no_show {
+        QueueTicketStatus::NoShow
+    } else {
+        QueueTicketStatus::Cancelled
+    });
+    ticket_model.cancel_reason = Set(data.reason.clone());
+    ticket_model.completed_at = Set(Some(now));
+    let ticket = update_ticket(&tenant_db, ticket_model).await?;
+
+    publish_queue_update(&app_state.redis, tenant_pid, &ticket.stage).await;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "ticket": queue_ticket_json(&ticket),
+            "message": "Queue ticket cancelled successfully",
+        }),
+    ))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct WaitTimeParams {
+    pub date: Option<NaiveDate>,
+}
+
+/// Average and longest wait per stage for a day, counting patients still
+/// waiting up to now.
+pub async fn wait_times(
+    app_state: web::Data<AppState>,
+    query: web::Query<WaitTimeParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let date = query.date.unwrap_or_else(|| Utc::now().date_naive());
+    let start = date.and_time(Default::default());
+    let end = start + chrono::Duration::days(1);
+    let now = Utc::now().naive_utc();
+
+    let visits = tenant::entities::queue_stage_visits::Entity::find()
+        .filter(tenant::entities::queue_stage_visits::Column::QueuedAt.gte(start))
+        .filter(tenant::entities::queue_stage_visits::Column::QueuedAt.lt(end))
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch stage visits: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch wait times" }))
+        })?;
+
+    let stages = QUEUE_STAGES
+        .iter()
+        .map(|stage| {
+            let waits = visits
+                .iter()
+                .filter(|visit| visit.stage == *stage)
+                .map(|visit| wait_seconds(visit, now.min(end)))
+                .collect::<Vec<_>>();
+            let total: i64 = waits.iter().sum();
+
+            json!({
+                "stage": stage,
+                "patients": waits.len(),
+                "average_wait_seconds": if waits.is_empty() { 0 } else { total / waits.len() as i64 },
+                "longest_wait_seconds": waits.iter().max(),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "date": date,
+            "stages": stages,
+            "message": "Wait times fetched successfully",
+        }),
+    ))
+}
+
+fn stage_from_path(slug: &str) -> Result<QueueStage, ApiResponse> {
+    parse_stage(slug).ok_or_else(|| ApiResponse::new(404, json!({ "message": "Queue not found" })))
+}
+
+async fn next_ticket_sequence(
+    tenant_db: &DatabaseConnection,
+    date: NaiveDate,
+) -> Result<i32, ApiResponse> {
+    let last: Option<Option<i32>> = tenant::entities::queue_tickets::Entity::find()
+        .select_only()
+        .column_as(
+            tenant::entities::queue_tickets::Column::TicketSequence.max(),
+            "max",
+        )
+        .filter(tenant::entities::queue_tickets::Column::QueueDate.eq(date))
+        .into_tuple()
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch ticket sequence: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to check in patient" }))
+        })?;
+
+    Ok(last.flatten().unwrap_or_default() + 1)
+}
+
+async fn open_stage_visit(
+    tenant_db: &DatabaseConnection,
+    ticket: &tenant::entities::queue_tickets::Model,
+    stage: QueueStage,
+) -> Result<tenant::entities::queue_stage_visits::Model, ApiResponse> {
+    tenant::entities::queue_stage_visits::ActiveModel {
+        ticket_id: Set(ticket.id),
+        stage: Set(stage),
+        ..Default::default()
+    }
+    .insert(tenant_db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to queue ticket {}: {}", ticket.pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to update queue" }))
+    })
+}
+
+/// Closes the ticket's current stage and either queues it at `next_stage` or
+/// completes the visit.
+async fn move_ticket(
+    tenant_db: &DatabaseConnection,
+    ticket: tenant::entities::queue_tickets::Model,
+    next_stage: Option<QueueStage>,
+    handled_by: Uuid,
+) -> Result<
+    (
+        tenant::entities::queue_tickets::Model,
+        Option<tenant::entities::queue_stage_visits::Model>,
+    ),
+    ApiResponse,
+> {
+    let now = Utc::now().naive_utc();
+
+    if let Ok(visit) = find_open_visit(tenant_db, &ticket).await {
+        let mut visit_model: tenant::entities::queue_stage_visits::ActiveModel =
+            visit.clone().into();
+        visit_model.called_at = Set(Some(visit.called_at.unwrap_or(now)));
+        visit_model.started_at = Set(Some(visit.started_at.unwrap_or(now)));
+        visit_model.completed_at = Set(Some(now));
+        visit_model.handled_by = Set(Some(visit.handled_by.unwrap_or(handled_by)));
+        visit_model.updated_at = Set(now);
+        update_visit(tenant_db, visit_model).await?;
+    }
+
+    let mut ticket_model: tenant::entities::queue_tickets::ActiveModel = ticket.clone().into();
+    ticket_model.room = Set(None);
+
+    let visit = match next_stage {
+        Some(stage) => {
+            ticket_model.stage = Set(stage.clone());
+            ticket_model.status = Set(QueueTicketStatus::Waiting);
+            Some(open_stage_visit(tenant_db, &ticket, stage).await?)
+        }
+        None => {
+            ticket_model.status = Set(QueueTicketStatus::Completed);
+            ticket_model.completed_at = Set(Some(now));
+            None
+        }
+    };
+
+    let ticket = update_ticket(tenant_db, ticket_model).await?;
+
+    Ok((ticket, visit))
+}
+
+async fn update_ticket(
+    tenant_db: &DatabaseConnection,
+    mut ticket: tenant::entities::queue_tickets::ActiveModel,
+) -> Result<tenant::entities::queue_tickets::Model, ApiResponse> {
+    ticket.updated_at = Set(Utc::now().naive_utc());
+    ticket.update(tenant_db).await.map_err(|err| {
+        log::error!("Failed to update queue ticket: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to update queue ticket" }))
+    })
+}
+
+async fn update_visit(
+    tenant_db: &DatabaseConnection,
+    visit: tenant::entities::queue_stage_visits::ActiveModel,
+) -> Result<tenant::entities::queue_stage_visits::Model, ApiResponse> {
+    visit.update(tenant_db).await.map_err(|err| {
+        log::error!("Failed to update stage visit: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to update queue ticket" }))
+    })
+}
+
+async fn find_open_visit(
+    tenant_db: &DatabaseConnection,
+    ticket: &tenant::entities::queue_tickets::Model,
+) -> Result<tenant::entities::queue_stage_visits::Model, ApiResponse> {
+    tenant::entities::queue_stage_visits::Entity::find()
+        .filter(tenant::entities::queue_stage_visits::Column::TicketId.eq(ticket.id))
+        .filter(tenant::entities::queue_stage_visits::Column::Stage.eq(ticket.stage.clone()))
+        .filter(tenant::entities::queue_stage_visits::Column::CompletedAt.is_null())
+        .order_by_desc(tenant::entities::queue_stage_visits::Column::QueuedAt)
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch stage visit for {}: {}", ticket.pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch queue ticket" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Stage visit not found" })))
+}
+
+async fn find_ticket(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+) -> Result<tenant::entities::queue_tickets::Model, ApiResponse> {
+    tenant::entities::queue_tickets::Entity::find_by_pid(pid)
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch queue ticket {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch queue ticket" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Queue ticket not found" })))
+}
+
+async fn find_active_ticket(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+) -> Result<tenant::entities::queue_tickets::Model, ApiResponse> {
+    let ticket = find_ticket(tenant_db, pid).await?;
+
+    if !ACTIVE_TICKET_STATUSES.contains(&ticket.status) {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Queue ticket is already closed" }),
+        ));
+    }
+
+    Ok(ticket)
+}
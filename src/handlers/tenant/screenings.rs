@@ -0,0 +1,254 @@
+use actix_web::{HttpRequest, web};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    db::tenant::{
+        self,
+        migrations::sea_orm::{
+            ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+            QueryOrder, Set,
+        },
+    },
+    handlers::services::{
+        clinical_tasks::clinical_task_json,
+        patients::find_patient,
+        screeners::{
+            SCREENERS, escalate_screening_result, find_screener, score_screener, screener_json,
+            screening_chart_series, screening_result_json,
+        },
+    },
+    utils::{
+        api_response::ApiResponse,
+        app_state::AppState,
+        jwt::{get_logged_in_user_claims, get_tenant_db},
+    },
+};
+
+pub async fn instruments() -> Result<ApiResponse, ApiResponse> {
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "instruments": SCREENERS.iter().map(|s| screener_json(s)).collect::<Vec<_>>(),
+            "message": "Screening instruments fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ScreeningData {
+    pub patient_pid: Uuid,
+    pub instrument: String,
+    pub answers: Vec<usize>,
+    pub notes: Option<String>,
+}
+
+/// Scores a screener administered by a clinician and raises a task when the
+/// result needs follow-up.
+pub async fn create(
+    app_state: web::Data<AppState>,
+    data: web::Json<ScreeningData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let Some(screener) = find_screener(&data.instrument) else {
+        return Err(ApiResponse::new(
+            400,
+            json!({ "message": "Unknown screening instrument" }),
+        ));
+    };
+
+    let score = score_screener(screener, &data.answers)
+        .map_err(|message| ApiResponse::new(400, json!({ "message": message })))?;
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let patient = find_patient(&app_state, data.patient_pid).await?;
+
+    let result = tenant::entities::screening_results::ActiveModel {
+        patient_pid: Set(patient.pid),
+        instrument: Set(screener.code.to_string()),
+        answers: Set(json!(data.answers)),
+        total_score: Set(score.total_score),
+        severity: Set(score.severity.to_string()),
+        safety_flag: Set(score.safety_flag),
+        source: Set("clinician".to_string()),
+        administered_by: Set(Some(claims.sub)),
+        completed_at: Set(Utc::now().naive_utc()),
+        notes: Set(data.notes.clone()),
+        ..Default::default()
+    }
+    .insert(&tenant_db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to record screening result: {}", err);
+        ApiResponse::new(
+            500,
+            json!({ "message": "Failed to record screening result" }),
+        )
+    })?;
+
+    let task = escalate_screening_result(&tenant_db, screener, &score, &result).await?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "screening_result": screening_result_json(&result),
+            "escalation_task": task.as_ref().map(clinical_task_json),
+            "message": "Screening result recorded successfully",
+        }),
+    ))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct HistoryParams {
+    pub instrument: Option<String>,
+}
+
+/// A patient's screening history at this facility, with one score series per
+/// instrument for charting.
+pub async fn patient_history(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    query: web::Query<HistoryParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let patient = find_patient(&app_state, path.into_inner()).await?;
+
+    let mut stmt = tenant::entities::screening_results::Entity::find()
+        .filter(tenant::entities::screening_results::Column::PatientPid.eq(patient.pid))
+        .filter(tenant::entities::screening_results::Column::DeletedAt.is_null());
+
+    if let Some(screener) = query.instrument.as_deref().and_then(find_screener) {
+        stmt =
+            stmt.filter(tenant::entities::screening_results::Column::Instrument.eq(screener.code));
+    }
+
+    let results = stmt
+        .order_by_desc(tenant::entities::screening_results::Column::CompletedAt)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch screening results: {}", err);
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to fetch screening results" }),
+            )
+        })?;
+
+    let chart = screening_chart_series(results.iter().map(|r| {
+        (
+            r.instrument.as_str(),
+            r.completed_at,
+            r.total_score,
+            r.severity.as_str(),
+        )
+    }));
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "screening_results": results.iter().map(screening_result_json).collect::<Vec<_>>(),
+            "chart": chart,
+            "message": "Screening results fetched successfully",
+        }),
+    ))
+}
+
+pub async fn show(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let result = find_result(&tenant_db, path.into_inner()).await?;
+
+    let answers = find_screener(&result.instrument).map(|screener| {
+        screener
+            .items
+            .iter()
+            .zip(result.answers.as_array().cloned().unwrap_or_default())
+            .map(|(item, answer)| {
+                let option = answer
+                    .as_u64()
+                    .and_then(|index| item.options.get(index as usize));
+                json!({
+                    "question": item.text,
+                    "answer": option.map(|(label, _)| label),
+                    "score": option.map(|(_, score)| score),
+                })
+            })
+            .collect::<Vec<_>>()
+    });
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "screening_result": screening_result_json(&result),
+            "answers": answers,
+            "message": "Screening result fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ReviewData {
+    pub notes: Option<String>,
+}
+
+pub async fn review(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<ReviewData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let result = find_result(&tenant_db, path.into_inner()).await?;
+
+    let mut active_model: tenant::entities::screening_results::ActiveModel =
+        result.to_owned().into();
+    active_model.reviewed_by = Set(Some(claims.sub));
+    active_model.reviewed_at = Set(Some(Utc::now().naive_utc()));
+    if let Some(notes) = &data.notes {
+        active_model.notes = Set(Some(notes.clone()));
+    }
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let result = active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!("Failed to review screening result {}: {}", result.pid, err);
+        ApiResponse::new(
+            500,
+            json!({ "message": "Failed to review screening result" }),
+        )
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "screening_result": screening_result_json(&result),
+            "message": "Screening result reviewed successfully",
+        }),
+    ))
+}
+
+async fn find_result(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+) -> Result<tenant::entities::screening_results::Model, ApiResponse> {
+    tenant::entities::screening_results::Entity::find_by_pid(pid)
+        .filter(tenant::entities::screening_results::Column::DeletedAt.is_null())
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch screening result {}: {}", pid, err);
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to fetch screening result" }),
+            )
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Screening result not found" })))
+}
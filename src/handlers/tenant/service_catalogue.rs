@@ -0,0 +1,1014 @@
+use std::collections::HashMap;
+
+use actix_web::{HttpRequest, web};
+use chrono::{Duration, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        main,
+        tenant::{
+            self,
+            entities::sea_orm_active_enums::{EncounterType, PayerType, ServiceCategory},
+            migrations::sea_orm::{
+                ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait,
+                PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait,
+            },
+        },
+    },
+    handlers::services::pricing::{
+        effective_price, facility_currency, find_price_list, find_service, payer_price_list,
+        price_list_json, quoted_price_json, service_json, service_price, service_price_json,
+    },
+    utils::{
+        api_response::ApiResponse,
+        app_state::AppState,
+        jwt::{get_logged_in_user_claims, get_tenant_db},
+        validator_error::ValidationError,
+    },
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ServiceData {
+    pub code: Option<String>,
+    pub name: Option<String>,
+    pub category: Option<ServiceCategory>,
+    pub description: Option<String>,
+    /// Charged as the consultation whenever an encounter of this type is
+    /// recorded. One service per encounter type.
+    pub encounter_type: Option<EncounterType>,
+    pub is_active: Option<bool>,
+}
+
+impl ServiceData {
+    pub fn validate(&self, is_create: bool) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        match self.code.as_deref().map(str::trim) {
+            None if is_create => {
+                errors.insert("code".to_string(), "Code is required".to_string());
+            }
+            Some(code)
+                if code.is_empty()
+                    || code.len() > 30
+                    || !code
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') =>
+            {
+                errors.insert(
+                    "code".to_string(),
+                    "Code must be 1 to 30 letters, digits, dashes or underscores".to_string(),
+                );
+            }
+            _ => {}
+        }
+
+        match self.name.as_deref().map(str::trim) {
+            None if is_create => {
+                errors.insert("name".to_string(), "Name is required".to_string());
+            }
+            Some("") => {
+                errors.insert("name".to_string(), "Name is required".to_string());
+            }
+            Some(name) if name.chars().count() > 150 => {
+                errors.insert(
+                    "name".to_string(),
+                    "Name must be at most 150 characters".to_string(),
+                );
+            }
+            _ => {}
+        }
+
+        if is_create && self.category.is_none() {
+            errors.insert("category".to_string(), "Category is required".to_string());
+        }
+
+        if self
+            .description
+            .as_ref()
+            .is_some_and(|d| d.chars().count() > 2000)
+        {
+            errors.insert(
+                "description".to_string(),
+                "Description must be at most 2000 characters".to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ServiceParams {
+    pub search: Option<String>,
+    pub category: Option<ServiceCategory>,
+    pub include_inactive: Option<bool>,
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+}
+
+pub async fn index(
+    app_state: web::Data<AppState>,
+    query: web::Query<ServiceParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let mut stmt = tenant::entities::billable_services::Entity::find()
+        .filter(tenant::entities::billable_services::Column::DeletedAt.is_null());
+
+    if !query.include_inactive.unwrap_or(false) {
+        stmt = stmt.filter(tenant::entities::billable_services::Column::IsActive.eq(true));
+    }
+
+    if let Some(category) = &query.category {
+        stmt =
+            stmt.filter(tenant::entities::billable_services::Column::Category.eq(category.clone()));
+    }
+
+    if let Some(term) = &query.search {
+        use tenant::migrations::{Expr, extension::postgres::PgExpr};
+
+        let like = format!("%{}%", term);
+        stmt = stmt.filter(
+            Condition::any()
+                .add(
+                    Expr::col(tenant::entities::billable_services::Column::Code)
+                        .ilike(like.clone()),
+                )
+                .add(Expr::col(tenant::entities::billable_services::Column::Name).ilike(like)),
+        );
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let paginator = stmt
+        .order_by_asc(tenant::entities::billable_services::Column::Code)
+        .paginate(&tenant_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
+
+    let services = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "services": services.iter().map(service_json).collect::<Vec<_>>(),
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "Services fetched successfully",
+        }),
+    ))
+}
+
+pub async fn create(
+    app_state: web::Data<AppState>,
+    data: web::Json<ServiceData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate(true) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let code = data
+        .code
+        .as_deref()
+        .unwrap_or_default()
+        .trim()
+        .to_uppercase();
+
+    ensure_code_available(&tenant_db, &code, None).await?;
+    if let Some(encounter_type) = &data.encounter_type {
+        ensure_encounter_type_available(&tenant_db, encounter_type.clone(), None).await?;
+    }
+
+    let service = tenant::entities::billable_services::ActiveModel {
+        code: Set(code),
+        name: Set(data.name.as_deref().unwrap_or_default().trim().to_string()),
+        category: Set(data.category.clone().unwrap_or(ServiceCategory::Other)),
+        description: Set(data.description.clone()),
+        encounter_type: Set(data.encounter_type.clone()),
+        is_active: Set(data.is_active.unwrap_or(true)),
+        created_by: Set(claims.sub),
+        ..Default::default()
+    }
+    .insert(&tenant_db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to create service: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create service" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "service": service_json(&service),
+            "message": "Service created successfully",
+        }),
+    ))
+}
+
+/// The service with the price in force today on every active list.
+pub async fn show(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let service = find_service(&tenant_db, path.into_inner()).await?;
+    let currency = facility_currency(&req, &app_state).await?;
+    let today = Utc::now().date_naive();
+
+    let price_lists = tenant::entities::price_lists::Entity::find()
+        .filter(tenant::entities::price_lists::Column::DeletedAt.is_null())
+        .filter(tenant::entities::price_lists::Column::IsActive.eq(true))
+        .order_by_desc(tenant::entities::price_lists::Column::IsDefault)
+        .order_by_asc(tenant::entities::price_lists::Column::Name)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch price lists: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch service" }))
+        })?;
+
+    let mut prices = Vec::with_capacity(price_lists.len());
+    for price_list in &price_lists {
+        let price =
+            effective_price(&tenant_db, price_list.id, service.id, &currency, today).await?;
+        prices.push(json!({
+            "price_list": price_list_json(price_list),
+            "price": price.as_ref().map(|price| service_price_json(price, None)),
+        }));
+    }
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "service": service_json(&service),
+            "currency": currency,
+            "prices": prices,
+            "message": "Service fetched successfully",
+        }),
+    ))
+}
+
+pub async fn edit(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<ServiceData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate(false) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let service = find_service(&tenant_db, path.into_inner()).await?;
+    let service_pid = service.pid;
+
+    let mut active_model: tenant::entities::billable_services::ActiveModel = service.into();
+
+    if let Some(code) = &data.code {
+        let code = code.trim().to_uppercase();
+        ensure_code_available(&tenant_db, &code, Some(service_pid)).await?;
+        active_model.code = Set(code);
+    }
+    if let Some(name) = &data.name {
+        active_model.name = Set(name.trim().to_string());
+    }
+    if let Some(category) = &data.category {
+        active_model.category = Set(category.clone());
+    }
+    if let Some(description) = &data.description {
+        active_model.description = Set(Some(description.clone()));
+    }
+    if let Some(encounter_type) = &data.encounter_type {
+        ensure_encounter_type_available(&tenant_db, encounter_type.clone(), Some(service_pid))
+            .await?;
+        active_model.encounter_type = Set(Some(encounter_type.clone()));
+    }
+    if let Some(is_active) = data.is_active {
+        active_model.is_active = Set(is_active);
+    }
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let service = active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!("Failed to update service {}: {}", service_pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to update service" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "service": service_json(&service),
+            "message": "Service updated successfully",
+        }),
+    ))
+}
+
+/// Removes the service from the catalogue. Charges already made keep its
+/// code and price.
+pub async fn destroy(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let service = find_service(&tenant_db, path.into_inner()).await?;
+
+    let service_pid = service.pid;
+    let mut active_model: tenant::entities::billable_services::ActiveModel = service.into();
+    active_model.is_active = Set(false);
+    active_model.encounter_type = Set(None);
+    active_model.deleted_at = Set(Some(Utc::now().naive_utc()));
+    active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!("Failed to delete service {}: {}", service_pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to delete service" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({ "message": "Service deleted successfully" }),
+    ))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct QuoteParams {
+    pub service_pid: Uuid,
+    pub patient_pid: Uuid,
+    /// Prices on this list instead of the patient's own.
+    pub price_list_pid: Option<Uuid>,
+    pub on: Option<NaiveDate>,
+}
+
+/// What the patient's payer is charged for a service.
+pub async fn quote(
+    app_state: web::Data<AppState>,
+    query: web::Query<QuoteParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let service = find_service(&tenant_db, query.service_pid).await?;
+    let currency = facility_currency(&req, &app_state).await?;
+    let price_list = payer_price_list(
+        &tenant_db,
+        &app_state.main_db,
+        query.patient_pid,
+        query.price_list_pid,
+    )
+    .await?;
+
+    let price = service_price(
+        &tenant_db,
+        &service,
+        price_list.as_ref(),
+        &currency,
+        query.on.unwrap_or_else(|| Utc::now().date_naive()),
+    )
+    .await?
+    .ok_or_else(|| {
+        ApiResponse::new(
+            404,
+            json!({ "message": format!("{} has no price for this payer", service.name) }),
+        )
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "quote": quoted_price_json(&price),
+            "message": "Price fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct PriceListData {
+    pub name: Option<String>,
+    pub payer_type: Option<PayerType>,
+    /// The insurer billed on an insurance list.
+    pub insurance_provider_pid: Option<Uuid>,
+    /// The employer or scheme billed on a corporate list.
+    pub scheme_name: Option<String>,
+    /// The cash list used for patients without another payer. Only one list
+    /// is the default.
+    pub is_default: Option<bool>,
+    pub is_active: Option<bool>,
+}
+
+impl PriceListData {
+    pub fn validate(&self, is_create: bool) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        match self.name.as_deref().map(str::trim) {
+            None if is_create => {
+                errors.insert("name".to_string(), "Name is required".to_string());
+            }
+            Some("") => {
+                errors.insert("name".to_string(), "Name is required".to_string());
+            }
+            Some(name) if name.chars().count() > 150 => {
+                errors.insert(
+                    "name".to_string(),
+                    "Name must be at most 150 characters".to_string(),
+                );
+            }
+            _ => {}
+        }
+
+        if is_create && self.payer_type.is_none() {
+            errors.insert(
+                "payer_type".to_string(),
+                "Payer type is required".to_string(),
+            );
+        }
+
+        if self
+            .scheme_name
+            .as_ref()
+            .is_some_and(|s| s.chars().count() > 150)
+        {
+            errors.insert(
+                "scheme_name".to_string(),
+                "Scheme name must be at most 150 characters".to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+pub async fn index_price_lists(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let price_lists = tenant::entities::price_lists::Entity::find()
+        .filter(tenant::entities::price_lists::Column::DeletedAt.is_null())
+        .order_by_desc(tenant::entities::price_lists::Column::IsDefault)
+        .order_by_asc(tenant::entities::price_lists::Column::Name)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch price lists: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch price lists" }))
+        })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "price_lists": price_lists.iter().map(price_list_json).collect::<Vec<_>>(),
+            "currency": facility_currency(&req, &app_state).await?,
+            "message": "Price lists fetched successfully",
+        }),
+    ))
+}
+
+pub async fn create_price_list(
+    app_state: web::Data<AppState>,
+    data: web::Json<PriceListData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate(true) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let payer_type = data.payer_type.clone().unwrap_or(PayerType::Cash);
+    let is_default = data.is_default.unwrap_or(false);
+    let (insurance_provider_pid, scheme_name) = check_payer(
+        &app_state,
+        &payer_type,
+        data.insurance_provider_pid,
+        data.scheme_name.clone(),
+        is_default,
+    )
+    .await?;
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start price list transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create price list" }))
+    })?;
+
+    if is_default {
+        clear_default(&txn, None).await?;
+    }
+
+    let price_list = tenant::entities::price_lists::ActiveModel {
+        name: Set(data.name.as_deref().unwrap_or_default().trim().to_string()),
+        payer_type: Set(payer_type),
+        insurance_provider_pid: Set(insurance_provider_pid),
+        scheme_name: Set(scheme_name),
+        is_default: Set(is_default),
+        is_active: Set(data.is_active.unwrap_or(true)),
+        created_by: Set(claims.sub),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to create price list: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create price list" }))
+    })?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit price list: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create price list" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "price_list": price_list_json(&price_list),
+            "message": "Price list created successfully",
+        }),
+    ))
+}
+
+pub async fn edit_price_list(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<PriceListData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate(false) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let price_list = find_price_list(&tenant_db, path.into_inner()).await?;
+    let price_list_pid = price_list.pid;
+
+    let payer_type = data
+        .payer_type
+        .clone()
+        .unwrap_or(price_list.payer_type.clone());
+    let is_default = data.is_default.unwrap_or(price_list.is_default);
+    let (insurance_provider_pid, scheme_name) = check_payer(
+        &app_state,
+        &payer_type,
+        data.insurance_provider_pid
+            .or(price_list.insurance_provider_pid),
+        data.scheme_name.clone().or(price_list.scheme_name.clone()),
+        is_default,
+    )
+    .await?;
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start price list transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to update price list" }))
+    })?;
+
+    if is_default && !price_list.is_default {
+        clear_default(&txn, Some(price_list.id)).await?;
+    }
+
+    let mut active_model: tenant::entities::price_lists::ActiveModel = price_list.into();
+    if let Some(name) = &data.name {
+        active_model.name = Set(name.trim().to_string());
+    }
+    active_model.payer_type = Set(payer_type);
+    active_model.insurance_provider_pid = Set(insurance_provider_pid);
+    active_model.scheme_name = Set(scheme_name);
+    active_model.is_default = Set(is_default);
+    if let Some(is_active) = data.is_active {
+        active_model.is_active = Set(is_active);
+    }
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let price_list = active_model.update(&txn).await.map_err(|err| {
+        log::error!("Failed to update price list {}: {}", price_list_pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to update price list" }))
+    })?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit price list {}: {}", price_list_pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to update price list" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "price_list": price_list_json(&price_list),
+            "message": "Price list updated successfully",
+        }),
+    ))
+}
+
+/// The default cash list cannot be removed; make another list the default
+/// first.
+pub async fn destroy_price_list(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let price_list = find_price_list(&tenant_db, path.into_inner()).await?;
+
+    if price_list.is_default {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "The default price list cannot be deleted" }),
+        ));
+    }
+
+    let price_list_pid = price_list.pid;
+    let mut active_model: tenant::entities::price_lists::ActiveModel = price_list.into();
+    active_model.is_active = Set(false);
+    active_model.deleted_at = Set(Some(Utc::now().naive_utc()));
+    active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!("Failed to delete price list {}: {}", price_list_pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to delete price list" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({ "message": "Price list deleted successfully" }),
+    ))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PriceParams {
+    pub service_pid: Option<Uuid>,
+    /// Only prices in force on this day.
+    pub on: Option<NaiveDate>,
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+}
+
+/// Prices on a list, newest first, including past and future ones.
+pub async fn index_prices(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    query: web::Query<PriceParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let price_list = find_price_list(&tenant_db, path.into_inner()).await?;
+
+    let mut stmt = tenant::entities::service_prices::Entity::find()
+        .filter(tenant::entities::service_prices::Column::PriceListId.eq(price_list.id));
+
+    if let Some(service_pid) = query.service_pid {
+        let service = find_service(&tenant_db, service_pid).await?;
+        stmt = stmt.filter(tenant::entities::service_prices::Column::ServiceId.eq(service.id));
+    }
+
+    if let Some(on) = query.on {
+        stmt = stmt
+            .filter(tenant::entities::service_prices::Column::EffectiveFrom.lte(on))
+            .filter(
+                Condition::any()
+                    .add(tenant::entities::service_prices::Column::EffectiveTo.is_null())
+                    .add(tenant::entities::service_prices::Column::EffectiveTo.gte(on)),
+            );
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let paginator = stmt
+        .find_also_related(tenant::entities::billable_services::Entity)
+        .order_by_desc(tenant::entities::service_prices::Column::EffectiveFrom)
+        .paginate(&tenant_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
+
+    let prices = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "price_list": price_list_json(&price_list),
+            "prices": prices
+                .iter()
+                .map(|(price, service)| service_price_json(price, service.as_ref()))
+                .collect::<Vec<_>>(),
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "Prices fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct PriceData {
+    pub service_pid: Option<Uuid>,
+    pub amount: Option<Decimal>,
+    /// Defaults to today.
+    pub effective_from: Option<NaiveDate>,
+}
+
+impl PriceData {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if self.service_pid.is_none() {
+            errors.insert("service_pid".to_string(), "Service is required".to_string());
+        }
+
+        match self.amount {
+            None => {
+                errors.insert("amount".to_string(), "Amount is required".to_string());
+            }
+            Some(amount) if amount < Decimal::ZERO => {
+                errors.insert(
+                    "amount".to_string(),
+                    "Amount cannot be negative".to_string(),
+                );
+            }
+            _ => {}
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+/// Sets a service's price on a list from a date, in the facility's currency.
+/// The price in force before that date ends the day before; prices are never
+/// edited, so charges can always be traced to the price they used.
+pub async fn set_price(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<PriceData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate() {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let price_list = find_price_list(&tenant_db, path.into_inner()).await?;
+    let service = find_service(&tenant_db, data.service_pid.unwrap_or_default()).await?;
+    let currency = facility_currency(&req, &app_state).await?;
+    let effective_from = data
+        .effective_from
+        .unwrap_or_else(|| Utc::now().date_naive());
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start price transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to set price" }))
+    })?;
+
+    let prices = tenant::entities::service_prices::Entity::find()
+        .filter(tenant::entities::service_prices::Column::PriceListId.eq(price_list.id))
+        .filter(tenant::entities::service_prices::Column::ServiceId.eq(service.id))
+        .filter(
+            Condition::any()
+                .add(tenant::entities::service_prices::Column::EffectiveTo.is_null())
+                .add(tenant::entities::service_prices::Column::EffectiveTo.gte(effective_from)),
+        )
+        .lock_exclusive()
+        .all(&txn)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch prices of {}: {}", service.code, err);
+            ApiResponse::new(500, json!({ "message": "Failed to set price" }))
+        })?;
+
+    if prices
+        .iter()
+        .any(|price| price.effective_from >= effective_from)
+    {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "A price already starts on or after this date" }),
+        ));
+    }
+
+    for price in prices {
+        let mut active_model: tenant::entities::service_prices::ActiveModel = price.into();
+        active_model.effective_to = Set(Some(effective_from - Duration::days(1)));
+        active_model.updated_at = Set(Utc::now().naive_utc());
+        active_model.update(&txn).await.map_err(|err| {
+            log::error!("Failed to end price of {}: {}", service.code, err);
+            ApiResponse::new(500, json!({ "message": "Failed to set price" }))
+        })?;
+    }
+
+    let price = tenant::entities::service_prices::ActiveModel {
+        price_list_id: Set(price_list.id),
+        service_id: Set(service.id),
+        amount: Set(data.amount.unwrap_or_default()),
+        currency: Set(currency),
+        effective_from: Set(effective_from),
+        created_by: Set(claims.sub),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to set price of {}: {}", service.code, err);
+        ApiResponse::new(500, json!({ "message": "Failed to set price" }))
+    })?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit price of {}: {}", service.code, err);
+        ApiResponse::new(500, json!({ "message": "Failed to set price" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "price": service_price_json(&price, Some(&service)),
+            "message": "Price set successfully",
+        }),
+    ))
+}
+
+/// Insurance lists name the insurer and corporate lists the scheme; only a
+/// cash list can be the default.
+async fn check_payer(
+    app_state: &web::Data<AppState>,
+    payer_type: &PayerType,
+    insurance_provider_pid: Option<Uuid>,
+    scheme_name: Option<String>,
+    is_default: bool,
+) -> Result<(Option<Uuid>, Option<String>), ApiResponse> {
+    let mut errors = HashMap::new();
+
+    if is_default && *payer_type != PayerType::Cash {
+        errors.insert(
+            "is_default".to_string(),
+            "Only a cash price list can be the default".to_string(),
+        );
+    }
+
+    let payer = match payer_type {
+        PayerType::Cash => (None, None),
+        PayerType::Insurance => match insurance_provider_pid {
+            Some(pid) => {
+                let provider = main::entities::insurance_providers::Entity::find_by_pid(pid)
+                    .filter(main::entities::insurance_providers::Column::DeletedAt.is_null())
+                    .one(&app_state.main_db)
+                    .await
+                    .map_err(|err| {
+                        log::error!("Failed to fetch insurance provider {}: {}", pid, err);
+                        ApiResponse::new(500, json!({ "message": "Failed to save price list" }))
+                    })?;
+                if provider.is_none() {
+                    errors.insert(
+                        "insurance_provider_pid".to_string(),
+                        "Insurance provider not found".to_string(),
+                    );
+                }
+                (Some(pid), None)
+            }
+            None => {
+                errors.insert(
+                    "insurance_provider_pid".to_string(),
+                    "Insurance provider is required for an insurance price list".to_string(),
+                );
+                (None, None)
+            }
+        },
+        PayerType::Corporate => {
+            let scheme_name = scheme_name
+                .as_deref()
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string);
+            if scheme_name.is_none() {
+                errors.insert(
+                    "scheme_name".to_string(),
+                    "Scheme name is required for a corporate price list".to_string(),
+                );
+            }
+            (None, scheme_name)
+        }
+    };
+
+    if errors.is_empty() {
+        Ok(payer)
+    } else {
+        Err(ApiResponse::new(400, json!(ValidationError { errors })))
+    }
+}
+
+async fn clear_default<C: tenant::migrations::sea_orm::ConnectionTrait>(
+    db: &C,
+    except: Option<i32>,
+) -> Result<(), ApiResponse> {
+    let mut stmt = tenant::entities::price_lists::Entity::update_many()
+        .col_expr(
+            tenant::entities::price_lists::Column::IsDefault,
+            tenant::migrations::Expr::value(false),
+        )
+        .filter(tenant::entities::price_lists::Column::IsDefault.eq(true));
+
+    if let Some(id) = except {
+        stmt = stmt.filter(tenant::entities::price_lists::Column::Id.ne(id));
+    }
+
+    stmt.exec(db).await.map_err(|err| {
+        log::error!("Failed to clear default price list: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to save price list" }))
+    })?;
+
+    Ok(())
+}
+
+async fn ensure_code_available(
+    tenant_db: &DatabaseConnection,
+    code: &str,
+    except: Option<Uuid>,
+) -> Result<(), ApiResponse> {
+    let mut stmt = tenant::entities::billable_services::Entity::find()
+        .filter(tenant::entities::billable_services::Column::Code.eq(code));
+
+    if let Some(pid) = except {
+        stmt = stmt.filter(tenant::entities::billable_services::Column::Pid.ne(pid));
+    }
+
+    let existing = stmt.one(tenant_db).await.map_err(|err| {
+        log::error!("Failed to check service code {}: {}", code, err);
+        ApiResponse::new(500, json!({ "message": "Failed to save service" }))
+    })?;
+
+    if existing.is_some() {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": format!("Service code {} is already in use", code) }),
+        ));
+    }
+
+    Ok(())
+}
+
+async fn ensure_encounter_type_available(
+    tenant_db: &DatabaseConnection,
+    encounter_type: EncounterType,
+    except: Option<Uuid>,
+) -> Result<(), ApiResponse> {
+    let mut stmt = tenant::entities::billable_services::Entity::find()
+        .filter(tenant::entities::billable_services::Column::EncounterType.eq(encounter_type))
+        .filter(tenant::entities::billable_services::Column::DeletedAt.is_null());
+
+    if let Some(pid) = except {
+        stmt = stmt.filter(tenant::entities::billable_services::Column::Pid.ne(pid));
+    }
+
+    let existing = stmt.one(tenant_db).await.map_err(|err| {
+        log::error!("Failed to check encounter services: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to save service" }))
+    })?;
+
+    if let Some(service) = existing {
+        return Err(ApiResponse::new(
+            409,
+            json!({
+                "message": format!("{} is already charged for this encounter type", service.code)
+            }),
+        ));
+    }
+
+    Ok(())
+}
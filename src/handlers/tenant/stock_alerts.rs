@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+
+use actix_web::{HttpRequest, web};
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    db::tenant::{
+        self,
+        entities::sea_orm_active_enums::StockAlertType,
+        migrations::sea_orm::{
+            ActiveModelTrait, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+            Set,
+        },
+    },
+    handlers::services::{pharmacy::find_store, stock_alerts::stock_alert_json},
+    utils::{
+        api_response::ApiResponse,
+        app_state::AppState,
+        jwt::{get_logged_in_user_claims, get_tenant_db},
+    },
+};
+
+#[derive(Deserialize, Debug)]
+pub struct StockAlertParams {
+    pub store_pid: Option<Uuid>,
+    pub alert_type: Option<StockAlertType>,
+    pub include_resolved: Option<bool>,
+    pub include_acknowledged: Option<bool>,
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+}
+
+/// Open stock alerts, newest first. Resolved and acknowledged alerts are
+/// hidden unless asked for.
+pub async fn index(
+    app_state: web::Data<AppState>,
+    query: web::Query<StockAlertParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+
+    let mut stmt = tenant::entities::stock_alerts::Entity::find()
+        .find_also_related(tenant::entities::pharmacy_products::Entity);
+
+    if let Some(store_pid) = query.store_pid {
+        let store = find_store(&tenant_db, store_pid).await?;
+        stmt = stmt.filter(tenant::entities::stock_alerts::Column::StoreId.eq(store.id));
+    }
+
+    if let Some(alert_type) = &query.alert_type {
+        stmt =
+            stmt.filter(tenant::entities::stock_alerts::Column::AlertType.eq(alert_type.clone()));
+    }
+
+    if !query.include_resolved.unwrap_or(false) {
+        stmt = stmt.filter(tenant::entities::stock_alerts::Column::ResolvedAt.is_null());
+    }
+
+    if !query.include_acknowledged.unwrap_or(false) {
+        stmt = stmt.filter(tenant::entities::stock_alerts::Column::AcknowledgedAt.is_null());
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let paginator = stmt
+        .order_by_desc(tenant::entities::stock_alerts::Column::CreatedAt)
+        .paginate(&tenant_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
+
+    let alerts = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let stores: HashMap<i32, tenant::entities::pharmacy_stores::Model> =
+        tenant::entities::pharmacy_stores::Entity::find()
+            .filter(
+                tenant::entities::pharmacy_stores::Column::Id
+                    .is_in(alerts.iter().map(|(alert, _)| alert.store_id)),
+            )
+            .all(&tenant_db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to fetch stores: {}", err);
+                ApiResponse::new(500, json!({ "message": "Failed to fetch stock alerts" }))
+            })?
+            .into_iter()
+            .map(|store| (store.id, store))
+            .collect();
+
+    let batches: HashMap<i32, tenant::entities::stock_batches::Model> =
+        tenant::entities::stock_batches::Entity::find()
+            .filter(
+                tenant::entities::stock_batches::Column::Id
+                    .is_in(alerts.iter().filter_map(|(alert, _)| alert.batch_id)),
+            )
+            .all(&tenant_db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to fetch batches: {}", err);
+                ApiResponse::new(500, json!({ "message": "Failed to fetch stock alerts" }))
+            })?
+            .into_iter()
+            .map(|batch| (batch.id, batch))
+            .collect();
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "alerts": alerts
+                .iter()
+                .map(|(alert, product)| {
+                    stock_alert_json(
+                        alert,
+                        product.as_ref(),
+                        stores.get(&alert.store_id),
+                        alert.batch_id.and_then(|batch_id| batches.get(&batch_id)),
+                    )
+                })
+                .collect::<Vec<_>>(),
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "Stock alerts fetched successfully",
+        }),
+    ))
+}
+
+/// Marks an alert as seen. It stays open until the stock problem clears but
+/// is left out of later digests.
+pub async fn acknowledge(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let claims = get_logged_in_user_claims(&req)?;
+    let tenant_db = get_tenant_db(&req, &app_state).await?;
+    let pid = path.into_inner();
+
+    let alert = tenant::entities::stock_alerts::Entity::find_by_pid(pid)
+        .one(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch stock alert {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch stock alert" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Stock alert not found" })))?;
+
+    if alert.acknowledged_at.is_some() {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Stock alert is already acknowledged" }),
+        ));
+    }
+
+    let mut active_model: tenant::entities::stock_alerts::ActiveModel = alert.into();
+    active_model.acknowledged_at = Set(Some(Utc::now().naive_utc()));
+    active_model.acknowledged_by = Set(Some(claims.sub));
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let alert = active_model.update(&tenant_db).await.map_err(|err| {
+        log::error!("Failed to acknowledge stock alert {}: {}", pid, err);
+        ApiResponse::new(
+            500,
+            json!({ "message": "Failed to acknowledge stock alert" }),
+        )
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "alert": stock_alert_json(&alert, None, None, None),
+            "message": "Stock alert acknowledged successfully",
+        }),
+    ))
+}
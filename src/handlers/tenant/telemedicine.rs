@@ -0,0 +1,413 @@
+use std::collections::HashMap;
+
+use actix_web::{HttpRequest, web};
+use chrono::{NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        main,
+        tenant::{
+            self,
+            entities::sea_orm_active_enums::{EncounterType, TelemedicineSessionStatus},
+            migrations::sea_orm::{
+                ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+                QueryOrder, Set, TransactionTrait,
+            },
+        },
+    },
+    handlers::services::{
+        features::require_tenant_feature,
+        pricing::{charge_encounter, facility_currency},
+        telemedicine::{
+            ParticipantRole, RoomTicket, TELEMEDICINE_FEATURE, close_session_room, find_session,
+            is_session_open, issue_room_token, telemedicine_session_json, update_session,
+        },
+    },
+    utils::{
+        api_response::ApiResponse,
+        app_state::AppState,
+        jwt::{get_logged_in_user_claims, get_tenant_db, get_tenant_id},
+        validator_error::ValidationError,
+    },
+};
+
+#[derive(Deserialize, Debug)]
+pub struct SessionParams {
+    pub status: Option<TelemedicineSessionStatus>,
+    pub patient_pid: Option<Uuid>,
+    pub date: Option<NaiveDate>,
+    pub mine: Option<bool>,
+}
+
+pub async fn index(
+    app_state: web::Data<AppState>,
+    query: web::Query<SessionParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let claims = get_logged_in_user_claims(&req)?;
+    let (tenant_db, _) = telemedicine_db(&app_state, &req).await?;
+
+    let mut stmt = tenant::entities::telemedicine_sessions::Entity::find();
+
+    if let Some(status) = &query.status {
+        stmt =
+            stmt.filter(tenant::entities::telemedicine_sessions::Column::Status.eq(status.clone()));
+    }
+
+    if let Some(patient_pid) = query.patient_pid {
+        stmt = stmt
+            .filter(tenant::entities::telemedicine_sessions::Column::PatientPid.eq(patient_pid));
+    }
+
+    if let Some(date) = query.date {
+        let start = date.and_time(Default::default());
+        stmt = stmt
+            .filter(tenant::entities::telemedicine_sessions::Column::ScheduledAt.gte(start))
+            .filter(
+                tenant::entities::telemedicine_sessions::Column::ScheduledAt
+                    .lt(start + chrono::Duration::days(1)),
+            );
+    }
+
+    if query.mine.unwrap_or(false) {
+        stmt = stmt
+            .filter(tenant::entities::telemedicine_sessions::Column::PractitionerId.eq(claims.sub));
+    }
+
+    let sessions = stmt
+        .order_by_asc(tenant::entities::telemedicine_sessions::Column::ScheduledAt)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch telemedicine sessions: {}", err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch sessions" }))
+        })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "total_items": sessions.len(),
+            "sessions": sessions
+                .iter()
+                .map(|session| telemedicine_session_json(session, None))
+                .collect::<Vec<_>>(),
+            "message": "Sessions fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct SessionData {
+    pub patient_pid: Option<Uuid>,
+    /// Defaults to the user booking the session.
+    pub practitioner_id: Option<Uuid>,
+    pub scheduled_at: Option<NaiveDateTime>,
+    pub reason: Option<String>,
+}
+
+impl SessionData {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if self.patient_pid.is_none() {
+            errors.insert("patient_pid".to_string(), "Patient is required".to_string());
+        }
+
+        match self.scheduled_at {
+            None => {
+                errors.insert(
+                    "scheduled_at".to_string(),
+                    "Scheduled time is required".to_string(),
+                );
+            }
+            Some(scheduled_at)
+                if scheduled_at < Utc::now().naive_utc() - chrono::Duration::hours(1) =>
+            {
+                errors.insert(
+                    "scheduled_at".to_string(),
+                    "Scheduled time cannot be in the past".to_string(),
+                );
+            }
+            _ => {}
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+pub async fn create(
+    app_state: web::Data<AppState>,
+    data: web::Json<SessionData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate() {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let (tenant_db, _) = telemedicine_db(&app_state, &req).await?;
+    let patient_pid = data.patient_pid.unwrap_or_default();
+
+    main::entities::patients::Entity::find_by_pid(patient_pid)
+        .filter(main::entities::patients::Column::DeletedAt.is_null())
+        .one(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch patient {}: {}", patient_pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch patient" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Patient not found" })))?;
+
+    let session = tenant::entities::telemedicine_sessions::ActiveModel {
+        patient_pid: Set(patient_pid),
+        practitioner_id: Set(data.practitioner_id.unwrap_or(claims.sub)),
+        scheduled_at: Set(data.scheduled_at.unwrap_or_default()),
+        reason: Set(data.reason.clone()),
+        status: Set(TelemedicineSessionStatus::Scheduled),
+        created_by: Set(Some(claims.sub)),
+        ..Default::default()
+    }
+    .insert(&tenant_db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to create telemedicine session: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create session" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "session": telemedicine_session_json(&session, None),
+            "message": "Session scheduled successfully",
+        }),
+    ))
+}
+
+pub async fn show(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let (tenant_db, _) = telemedicine_db(&app_state, &req).await?;
+    let session = find_session(&tenant_db, path.into_inner()).await?;
+
+    let encounter = match session.encounter_id {
+        Some(encounter_id) => tenant::entities::encounters::Entity::find_by_id(encounter_id)
+            .one(&tenant_db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to fetch encounter {}: {}", encounter_id, err);
+                ApiResponse::new(500, json!({ "message": "Failed to fetch session" }))
+            })?,
+        None => None,
+    };
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "session": telemedicine_session_json(&session, encounter.as_ref()),
+            "message": "Session fetched successfully",
+        }),
+    ))
+}
+
+/// Issues the assigned practitioner a one-time token for the session's
+/// signalling socket.
+pub async fn join(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let claims = get_logged_in_user_claims(&req)?;
+    let (tenant_db, sso_tenant_id) = telemedicine_db(&app_state, &req).await?;
+    let session = find_session(&tenant_db, path.into_inner()).await?;
+
+    if session.practitioner_id != claims.sub {
+        return Err(ApiResponse::new(
+            403,
+            json!({ "message": "Only the assigned practitioner can join this session" }),
+        ));
+    }
+
+    if !is_session_open(&session.status) {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Session is no longer open" }),
+        ));
+    }
+
+    let room = issue_room_token(
+        &app_state.redis,
+        &RoomTicket {
+            sso_tenant_id,
+            session_pid: session.pid,
+            role: ParticipantRole::Practitioner,
+            user_id: claims.sub,
+        },
+    )
+    .await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "room": room,
+            "session": telemedicine_session_json(&session, None),
+            "message": "Room token issued successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct EndSessionData {
+    pub call_notes: Option<String>,
+}
+
+/// Ends the call, records its duration and files the call notes as a
+/// telemedicine encounter, charging the consultation when the facility
+/// bills for one.
+pub async fn end(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<EndSessionData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let claims = get_logged_in_user_claims(&req)?;
+    let (tenant_db, _) = telemedicine_db(&app_state, &req).await?;
+    let session = find_session(&tenant_db, path.into_inner()).await?;
+
+    if session.practitioner_id != claims.sub {
+        return Err(ApiResponse::new(
+            403,
+            json!({ "message": "Only the assigned practitioner can end this session" }),
+        ));
+    }
+
+    if !is_session_open(&session.status) {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Session is no longer open" }),
+        ));
+    }
+
+    let now = Utc::now().naive_utc();
+    let call_notes = data
+        .call_notes
+        .as_deref()
+        .map(str::trim)
+        .filter(|notes| !notes.is_empty())
+        .map(str::to_string);
+
+    let currency = facility_currency(&req, &app_state).await?;
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start encounter transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to end session" }))
+    })?;
+
+    let encounter = tenant::entities::encounters::ActiveModel {
+        patient_pid: Set(session.patient_pid),
+        encounter_type: Set(EncounterType::Telemedicine),
+        practitioner_id: Set(Some(session.practitioner_id)),
+        started_at: Set(session.started_at.unwrap_or(now)),
+        ended_at: Set(Some(now)),
+        reason: Set(session.reason.clone()),
+        notes: Set(call_notes.clone()),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to create encounter for {}: {}", session.pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to end session" }))
+    })?;
+
+    charge_encounter(&txn, &app_state.main_db, &currency, &encounter).await?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit encounter for {}: {}", session.pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to end session" }))
+    })?;
+
+    let mut active_model: tenant::entities::telemedicine_sessions::ActiveModel =
+        session.clone().into();
+    active_model.status = Set(TelemedicineSessionStatus::Ended);
+    active_model.ended_at = Set(Some(now));
+    active_model.duration_seconds = Set(session
+        .started_at
+        .map(|started_at| (now - started_at).num_seconds().max(0) as i32));
+    active_model.call_notes = Set(call_notes);
+    active_model.encounter_id = Set(Some(encounter.id));
+    let session = update_session(&tenant_db, active_model).await?;
+
+    close_session_room(&app_state.redis, session.pid).await;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "session": telemedicine_session_json(&session, Some(&encounter)),
+            "message": "Session ended successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct CancelSessionData {
+    pub reason: Option<String>,
+}
+
+pub async fn cancel(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<CancelSessionData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let (tenant_db, _) = telemedicine_db(&app_state, &req).await?;
+    let session = find_session(&tenant_db, path.into_inner()).await?;
+
+    if !matches!(
+        session.status,
+        TelemedicineSessionStatus::Scheduled | TelemedicineSessionStatus::Waiting
+    ) {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Only sessions that have not started can be cancelled" }),
+        ));
+    }
+
+    let mut active_model: tenant::entities::telemedicine_sessions::ActiveModel = session.into();
+    active_model.status = Set(TelemedicineSessionStatus::Cancelled);
+    active_model.cancel_reason = Set(data.reason.clone());
+    let session = update_session(&tenant_db, active_model).await?;
+
+    close_session_room(&app_state.redis, session.pid).await;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "session": telemedicine_session_json(&session, None),
+            "message": "Session cancelled successfully",
+        }),
+    ))
+}
+
+/// The facility's database, once telemedicine is confirmed to be enabled for
+/// it, along with its SSO tenant id.
+async fn telemedicine_db(
+    app_state: &web::Data<AppState>,
+    req: &HttpRequest,
+) -> Result<(DatabaseConnection, Uuid), ApiResponse> {
+    let (tenant_id, _, sso_tenant_id) = get_tenant_id(req, app_state).await?;
+    require_tenant_feature(&app_state.main_db, tenant_id, TELEMEDICINE_FEATURE).await?;
+    let tenant_db = get_tenant_db(req, app_state).await?;
+
+    Ok((tenant_db, sso_tenant_id))
+}
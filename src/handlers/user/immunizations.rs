@@ -0,0 +1,68 @@
+use actix_web::{HttpRequest, get, web};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    db::main::entities::sea_orm_active_enums::FileVisibility,
+    handlers::services::{
+        files::authorized_file_url,
+        immunizations::{
+            build_immunization_summary, fetch_patient_immunization_records,
+            immunization_certificate_html,
+        },
+        patients::find_logged_in_patient,
+    },
+    utils::{api_response::ApiResponse, app_state::AppState, html_to_image::generate_png},
+};
+
+const CERTIFICATE_URL_EXPIRY_SECS: u64 = 300;
+
+#[get("")]
+async fn index(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let patient = find_logged_in_patient(&app_state, &req).await?;
+    let summary = build_immunization_summary(&app_state, &patient).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "immunizations": summary,
+            "message": "Immunizations fetched successfully",
+        }),
+    ))
+}
+
+#[get("/certificate")]
+async fn certificate(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let patient = find_logged_in_patient(&app_state, &req).await?;
+    let records = fetch_patient_immunization_records(&app_state.tenant_dbs, patient.pid).await?;
+
+    let html = immunization_certificate_html(&patient, &records);
+    let s3_key = format!("immunization_certificates/{}.png", Uuid::new_v4());
+    let file_pid = generate_png(
+        &html,
+        &req,
+        &app_state,
+        &s3_key,
+        Some(patient.id),
+        FileVisibility::Private,
+    )
+    .await?;
+
+    let (url, _) =
+        authorized_file_url(&app_state, &req, file_pid, CERTIFICATE_URL_EXPIRY_SECS).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "url": url,
+            "expires_in": CERTIFICATE_URL_EXPIRY_SECS,
+            "message": "Immunization certificate generated successfully",
+        }),
+    ))
+}
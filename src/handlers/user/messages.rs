@@ -0,0 +1,305 @@
+use std::collections::HashMap;
+
+use actix_multipart::Multipart;
+use actix_web::{HttpRequest, get, post, web};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        main::migrations::sea_orm::DatabaseConnection,
+        tenant::{
+            self,
+            entities::sea_orm_active_enums::{MessageSenderType, MessageThreadStatus},
+            migrations::sea_orm::{
+                ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, Set,
+            },
+        },
+    },
+    handlers::services::{
+        files::authorized_file_url,
+        messaging::{
+            MessageForm, fetch_thread_messages, find_attachment, mark_thread_read, post_message,
+            thread_message_json, unread_counts,
+        },
+        patients::find_logged_in_patient,
+        tenants::facility_db,
+    },
+    utils::{
+        api_response::ApiResponse, app_state::AppState, jwt::get_logged_in_user_claims,
+        validator_error::ValidationError,
+    },
+};
+
+const ATTACHMENT_URL_EXPIRY_SECS: u64 = 300;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FacilityParams {
+    pub tenant_pid: Uuid,
+}
+
+/// The patient's conversations with a facility's care team.
+#[get("/threads")]
+async fn index(
+    app_state: web::Data<AppState>,
+    query: web::Query<FacilityParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let patient = find_logged_in_patient(&app_state, &req).await?;
+    let (facility, tenant_db) = facility_db(&app_state, query.tenant_pid).await?;
+
+    let threads = tenant::entities::message_threads::Entity::find()
+        .filter(tenant::entities::message_threads::Column::PatientPid.eq(patient.pid))
+        .order_by_desc(tenant::entities::message_threads::Column::LastMessageAt)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to fetch threads for patient {}: {}",
+                patient.pid,
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to fetch threads" }))
+        })?;
+
+    let unread = unread_counts(
+        &tenant_db,
+        threads.iter().map(|thread| thread.id).collect(),
+        MessageSenderType::Patient,
+    )
+    .await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "facility": facility.name,
+            "threads": threads
+                .iter()
+                .map(|thread| {
+                    patient_thread_json(thread, unread.get(&thread.id).copied().unwrap_or(0))
+                })
+                .collect::<Vec<_>>(),
+            "message": "Threads fetched successfully",
+        }),
+    ))
+}
+
+/// Starts a conversation with a facility. Sent as multipart with
+/// `tenant_pid`, `subject`, `body` and optional `attachments`.
+#[post("/threads/create")]
+async fn create(
+    app_state: web::Data<AppState>,
+    payload: Multipart,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let form = MessageForm::from_multipart(payload).await?;
+    if let Err(err) = form.validate(true) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let Some(tenant_pid) = form.tenant_pid else {
+        return Err(ApiResponse::new(
+            400,
+            json!(ValidationError {
+                errors: HashMap::from([(
+                    "tenant_pid".to_string(),
+                    "Facility is required".to_string()
+                )]),
+            }),
+        ));
+    };
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let patient = find_logged_in_patient(&app_state, &req).await?;
+    let (facility, tenant_db) = facility_db(&app_state, tenant_pid).await?;
+
+    let thread = tenant::entities::message_threads::ActiveModel {
+        patient_pid: Set(patient.pid),
+        subject: Set(form.subject.clone().unwrap_or_default().trim().to_string()),
+        status: Set(MessageThreadStatus::Open),
+        last_message_at: Set(Utc::now().naive_utc()),
+        last_message_from: Set(MessageSenderType::Patient),
+        ..Default::default()
+    }
+    .insert(&tenant_db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to create message thread: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to create thread" }))
+    })?;
+
+    let (thread, message, attachments) = post_message(
+        &app_state,
+        &req,
+        Some(facility.id),
+        &tenant_db,
+        &thread,
+        &patient,
+        MessageSenderType::Patient,
+        claims.sub,
+        form,
+    )
+    .await?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "thread": patient_thread_json(&thread, 0),
+            "messages": [thread_message_json(&message, &attachments)],
+            "message": "Message sent successfully",
+        }),
+    ))
+}
+
+/// Opening a thread marks the care team's replies in it as read.
+#[get("/threads/{pid}")]
+async fn show(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    query: web::Query<FacilityParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let claims = get_logged_in_user_claims(&req)?;
+    let patient = find_logged_in_patient(&app_state, &req).await?;
+    let (_, tenant_db) = facility_db(&app_state, query.tenant_pid).await?;
+    let thread = find_patient_thread(&tenant_db, path.into_inner(), patient.pid).await?;
+
+    mark_thread_read(&tenant_db, &thread, MessageSenderType::Patient, claims.sub).await?;
+    let messages = fetch_thread_messages(&tenant_db, &thread).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "thread": patient_thread_json(&thread, 0),
+            "messages": messages,
+            "message": "Thread fetched successfully",
+        }),
+    ))
+}
+
+/// Replying to a closed thread reopens it.
+#[post("/threads/{pid}/reply")]
+async fn reply(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    payload: Multipart,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let form = MessageForm::from_multipart(payload).await?;
+    if let Err(err) = form.validate(false) {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let Some(tenant_pid) = form.tenant_pid else {
+        return Err(ApiResponse::new(
+            400,
+            json!(ValidationError {
+                errors: HashMap::from([(
+                    "tenant_pid".to_string(),
+                    "Facility is required".to_string()
+                )]),
+            }),
+        ));
+    };
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let patient = find_logged_in_patient(&app_state, &req).await?;
+    let (facility, tenant_db) = facility_db(&app_state, tenant_pid).await?;
+    let thread = find_patient_thread(&tenant_db, path.into_inner(), patient.pid).await?;
+
+    let (thread, message, attachments) = post_message(
+        &app_state,
+        &req,
+        Some(facility.id),
+        &tenant_db,
+        &thread,
+        &patient,
+        MessageSenderType::Patient,
+        claims.sub,
+        form,
+    )
+    .await?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "thread": patient_thread_json(&thread, 0),
+            "reply": thread_message_json(&message, &attachments),
+            "message": "Message sent successfully",
+        }),
+    ))
+}
+
+#[get("/attachments/{pid}")]
+async fn attachment(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    query: web::Query<FacilityParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let patient = find_logged_in_patient(&app_state, &req).await?;
+    let (_, tenant_db) = facility_db(&app_state, query.tenant_pid).await?;
+    let (attachment, thread) = find_attachment(&tenant_db, path.into_inner()).await?;
+
+    if thread.patient_pid != patient.pid {
+        return Err(ApiResponse::new(
+            404,
+            json!({ "message": "Attachment not found" }),
+        ));
+    }
+
+    let (url, _) = authorized_file_url(
+        &app_state,
+        &req,
+        attachment.file_pid,
+        ATTACHMENT_URL_EXPIRY_SECS,
+    )
+    .await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "url": url,
+            "original_name": attachment.original_name,
+            "content_type": attachment.content_type,
+            "expires_in": ATTACHMENT_URL_EXPIRY_SECS,
+            "message": "Attachment link generated successfully",
+        }),
+    ))
+}
+
+/// Leaves out staff assignment and reply SLA tracking, which are internal to
+/// the facility.
+fn patient_thread_json(
+    thread: &tenant::entities::message_threads::Model,
+    unread_count: i64,
+) -> Value {
+    json!({
+        "pid": thread.pid,
+        "subject": thread.subject,
+        "status": thread.status,
+        "last_message_at": thread.last_message_at,
+        "last_message_from": thread.last_message_from,
+        "unread_count": unread_count,
+        "closed_at": thread.closed_at,
+        "created_at": thread.created_at,
+    })
+}
+
+async fn find_patient_thread(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+    patient_pid: Uuid,
+) -> Result<tenant::entities::message_threads::Model, ApiResponse> {
+    tenant::entities::message_threads::Entity::find_by_pid(pid)
+        .filter(tenant::entities::message_threads::Column::PatientPid.eq(patient_pid))
+        .one(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch message thread {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch thread" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Thread not found" })))
+}
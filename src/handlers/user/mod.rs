@@ -1,2 +1,7 @@
+pub mod immunizations;
+pub mod messages;
 pub mod profile;
+pub mod screenings;
+pub mod store;
+pub mod telemedicine;
 pub mod tenants;
@@ -7,9 +7,14 @@ use crate::{
         self,
         migrations::sea_orm::{ColumnTrait, EntityTrait, QueryFilter},
     },
+    handlers::services::insurance_dependents::{
+        DependentData, DependentParams, create_dependent, deactivate_dependent, list_dependents,
+        update_dependent,
+    },
     handlers::services::patient_insurance::{
         create_patient_insurance, destroy_patient_insurance, edit_patient_insurance,
         fetch_patient_insurance, fetch_patient_insurances, set_primary_patient_insurance,
+        verify_patient_insurance,
     },
     utils::{
         api_response::ApiResponse,
@@ -97,6 +102,18 @@ async fn set_primary(
     set_primary_patient_insurance(&app_state, insurance_id).await
 }
 
+#[post("/{pid}/verify")]
+async fn verify(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+) -> Result<ApiResponse, ApiResponse> {
+    let (patient_id, _) = get_patient_id(&req, &app_state, None).await?;
+    let insurance_id = path.into_inner();
+
+    verify_patient_insurance(&app_state, insurance_id, Some(patient_id)).await
+}
+
 #[delete("{pid}")]
 async fn destroy(
     app_state: web::Data<AppState>,
@@ -108,3 +125,68 @@ async fn destroy(
 
     destroy_patient_insurance(&app_state, insurance_id).await
 }
+
+#[get("/{pid}/dependents")]
+async fn index_dependents(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    query: web::Query<DependentParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let (patient_id, _) = get_patient_id(&req, &app_state, None).await?;
+
+    list_dependents(
+        &app_state.main_db,
+        path.into_inner(),
+        Some(patient_id),
+        &query,
+    )
+    .await
+}
+
+#[post("/{pid}/dependents")]
+async fn add_dependent(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<DependentData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let (patient_id, _) = get_patient_id(&req, &app_state, None).await?;
+
+    create_dependent(
+        &app_state.main_db,
+        path.into_inner(),
+        Some(patient_id),
+        &data,
+    )
+    .await
+}
+
+#[put("/dependents/{pid}")]
+async fn edit_dependent(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<DependentData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let (patient_id, _) = get_patient_id(&req, &app_state, None).await?;
+
+    update_dependent(
+        &app_state.main_db,
+        path.into_inner(),
+        Some(patient_id),
+        &data,
+    )
+    .await
+}
+
+#[patch("/dependents/{pid}/deactivate")]
+async fn deactivate_dependent_cover(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let (patient_id, _) = get_patient_id(&req, &app_state, None).await?;
+
+    deactivate_dependent(&app_state.main_db, path.into_inner(), Some(patient_id)).await
+}
@@ -12,6 +12,7 @@ use uuid::Uuid;
 use crate::{
     db::main::{
         self,
+        entities::sea_orm_active_enums::FileVisibility,
         migrations::sea_orm::{ActiveModelTrait, ColumnTrait, QueryFilter, Set},
     },
     handlers::{
@@ -218,16 +219,18 @@ pub async fn upsert(
                 if !file_data.is_empty() {
                     let unique_filename = format!("profile/{}-{}", Uuid::new_v4(), filename);
 
-                    let full_s3_key = upload_file(
+                    let file_pid = upload_file(
                         &req,
                         &app_state,
                         &unique_filename,
                         file_data.clone(),
                         &content_type,
+                        None,
+                        FileVisibility::Private,
                     )
                     .await?;
 
-                    data.profile_picture = Some(full_s3_key);
+                    data.profile_picture = Some(file_pid.to_string());
                 }
             }
             "dob" => data.dob = Some(field_to_date(&mut field).await?),
@@ -0,0 +1,257 @@
+use actix_web::{HttpRequest, get, post, web};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        main::{
+            self,
+            migrations::sea_orm::{
+                ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, Set,
+            },
+        },
+        tenant,
+    },
+    handlers::services::{
+        patients::find_logged_in_patient,
+        screeners::{
+            SCREENERS, escalate_screening_result, find_screener, score_screener, screener_json,
+            screening_chart_series,
+        },
+    },
+    utils::{api_response::ApiResponse, app_state::AppState},
+};
+
+/// Shown alongside any self-assessment that answers the self-harm question
+/// positively.
+const CRISIS_ADVICE: &str = "If you are thinking about harming yourself, please reach out now. Call the Kenya Red Cross toll-free line 1199, Befrienders Kenya on +254 722 178 177, or go to the nearest emergency department.";
+
+#[get("/instruments")]
+async fn instruments() -> Result<ApiResponse, ApiResponse> {
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "instruments": SCREENERS.iter().map(|s| screener_json(s)).collect::<Vec<_>>(),
+            "message": "Screening instruments fetched successfully",
+        }),
+    ))
+}
+
+#[get("")]
+async fn index(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let patient = find_logged_in_patient(&app_state, &req).await?;
+
+    let screenings = main::entities::patient_screenings::Entity::find()
+        .filter(main::entities::patient_screenings::Column::PatientId.eq(patient.id))
+        .filter(main::entities::patient_screenings::Column::DeletedAt.is_null())
+        .order_by_desc(main::entities::patient_screenings::Column::CompletedAt)
+        .all(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to fetch screenings for patient {}: {}",
+                patient.pid,
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to fetch screenings" }))
+        })?;
+
+    let chart = screening_chart_series(screenings.iter().map(|s| {
+        (
+            s.instrument.as_str(),
+            s.completed_at,
+            s.total_score,
+            s.severity.as_str(),
+        )
+    }));
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "screenings": screenings.iter().map(patient_screening_json).collect::<Vec<_>>(),
+            "chart": chart,
+            "message": "Screenings fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SelfScreeningData {
+    pub instrument: String,
+    pub answers: Vec<usize>,
+}
+
+#[post("/create")]
+async fn create(
+    app_state: web::Data<AppState>,
+    data: web::Json<SelfScreeningData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let Some(screener) = find_screener(&data.instrument) else {
+        return Err(ApiResponse::new(
+            400,
+            json!({ "message": "Unknown screening instrument" }),
+        ));
+    };
+
+    let score = score_screener(screener, &data.answers)
+        .map_err(|message| ApiResponse::new(400, json!({ "message": message })))?;
+
+    let patient = find_logged_in_patient(&app_state, &req).await?;
+
+    let screening = main::entities::patient_screenings::ActiveModel {
+        patient_id: Set(patient.id),
+        instrument: Set(screener.code.to_string()),
+        answers: Set(json!(data.answers)),
+        total_score: Set(score.total_score),
+        severity: Set(score.severity.to_string()),
+        safety_flag: Set(score.safety_flag),
+        ..Default::default()
+    }
+    .insert(&app_state.main_db)
+    .await
+    .map_err(|err| {
+        log::error!(
+            "Failed to save screening for patient {}: {}",
+            patient.pid,
+            err
+        );
+        ApiResponse::new(500, json!({ "message": "Failed to save screening" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "screening": patient_screening_json(&screening),
+            "crisis_advice": score.safety_flag.then_some(CRISIS_ADVICE),
+            "message": "Screening saved successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ShareScreeningData {
+    pub tenant_pid: Uuid,
+}
+
+/// Copies a self-assessment into a facility's record so its clinicians can
+/// see it; results that need attention are escalated there.
+#[post("/{pid}/share")]
+async fn share(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<ShareScreeningData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let patient = find_logged_in_patient(&app_state, &req).await?;
+    let pid = path.into_inner();
+
+    let screening = main::entities::patient_screenings::Entity::find_by_pid(pid)
+        .filter(main::entities::patient_screenings::Column::PatientId.eq(patient.id))
+        .filter(main::entities::patient_screenings::Column::DeletedAt.is_null())
+        .one(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch screening {}: {}", pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch screening" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Screening not found" })))?;
+
+    let Some(screener) = find_screener(&screening.instrument) else {
+        return Err(ApiResponse::new(
+            400,
+            json!({ "message": "Unknown screening instrument" }),
+        ));
+    };
+
+    let facility = main::entities::tenants::Entity::find_by_pid(data.tenant_pid)
+        .filter(main::entities::tenants::Column::DeletedAt.is_null())
+        .one(&app_state.main_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch tenant {}: {}", data.tenant_pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch facility" }))
+        })?
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Facility not found" })))?;
+
+    let tenant_db = app_state
+        .tenant_db(facility.sso_tenant_id)
+        .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Facility not found" })))?;
+
+    let existing = tenant::entities::screening_results::Entity::find()
+        .filter(tenant::entities::screening_results::Column::SelfScreeningPid.eq(screening.pid))
+        .filter(tenant::entities::screening_results::Column::DeletedAt.is_null())
+        .one(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to check shared screening {}: {}",
+                screening.pid,
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to share screening" }))
+        })?;
+
+    if existing.is_some() {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Screening already shared with this facility" }),
+        ));
+    }
+
+    let answers: Vec<usize> = serde_json::from_value(screening.answers.clone()).unwrap_or_default();
+    let score = score_screener(screener, &answers).map_err(|message| {
+        log::error!(
+            "Stored screening {} no longer scores: {}",
+            screening.pid,
+            message
+        );
+        ApiResponse::new(500, json!({ "message": "Failed to share screening" }))
+    })?;
+
+    let result = tenant::entities::screening_results::ActiveModel {
+        patient_pid: Set(patient.pid),
+        instrument: Set(screening.instrument.clone()),
+        answers: Set(screening.answers.clone()),
+        total_score: Set(screening.total_score),
+        severity: Set(screening.severity.clone()),
+        safety_flag: Set(screening.safety_flag),
+        source: Set("self_reported".to_string()),
+        self_screening_pid: Set(Some(screening.pid)),
+        completed_at: Set(screening.completed_at),
+        ..Default::default()
+    }
+    .insert(&tenant_db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to share screening {}: {}", screening.pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to share screening" }))
+    })?;
+
+    escalate_screening_result(&tenant_db, screener, &score, &result).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "screening": patient_screening_json(&screening),
+            "shared_with": facility.name,
+            "message": "Screening shared successfully",
+        }),
+    ))
+}
+
+fn patient_screening_json(screening: &main::entities::patient_screenings::Model) -> Value {
+    json!({
+        "pid": screening.pid,
+        "instrument": screening.instrument,
+        "answers": screening.answers,
+        "total_score": screening.total_score,
+        "severity": screening.severity,
+        "safety_flag": screening.safety_flag,
+        "completed_at": screening.completed_at,
+    })
+}
@@ -0,0 +1,1273 @@
+use std::collections::HashMap;
+
+use actix_multipart::Multipart;
+use actix_web::{HttpRequest, get, post, web};
+use chrono::Utc;
+use futures::StreamExt;
+use rust_decimal::{Decimal, prelude::FromPrimitive};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        main::{
+            self, entities::sea_orm_active_enums::FileVisibility,
+            migrations::sea_orm::DatabaseConnection,
+        },
+        tenant::{
+            self,
+            entities::sea_orm_active_enums::{
+                DeliveryStatus, OnlineOrderStatus, OnlinePaymentStatus,
+            },
+            migrations::sea_orm::{
+                ActiveModelTrait, ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter,
+                QueryOrder, Set, TransactionTrait,
+            },
+        },
+    },
+    handlers::services::{
+        coupons::{
+            CouponLine, ONLINE_ORDER_SOURCE, applied_coupons_json, apply_coupons,
+            record_redemptions, void_redemptions,
+        },
+        deliveries::{cancel_order_delivery, delivery_eta, delivery_quote, find_order_delivery},
+        online_orders::{
+            PRESCRIPTION_CONTENT_TYPES, fetch_order_items, find_order, notify_order_status,
+            order_json, request_order_payment,
+        },
+        patients::{find_logged_in_patient, sms_phone_number},
+        pharmacy::{product_label, usable_stock},
+        tenants::facility_db,
+    },
+    utils::{
+        api_response::ApiResponse,
+        app_state::AppState,
+        constants::MAX_FILE_SIZE,
+        documents::document_number,
+        jwt::get_logged_in_user_claims,
+        mpesa::callback_token,
+        multipart::{
+            field_to_byte, field_to_f64, field_to_string, field_to_uuid, upload_facility_file,
+        },
+        validation::validate_international_phone_number,
+        validator_error::ValidationError,
+    },
+};
+
+const MAX_CART_QUANTITY: i32 = 100;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FacilityParams {
+    pub tenant_pid: Uuid,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CatalogueParams {
+    pub tenant_pid: Uuid,
+    pub search: Option<String>,
+    pub category: Option<String>,
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+}
+
+/// The facility's online catalogue. Prescription-only products are listed
+/// but need a prescription at checkout.
+#[get("/products")]
+async fn products(
+    app_state: web::Data<AppState>,
+    query: web::Query<CatalogueParams>,
+) -> Result<ApiResponse, ApiResponse> {
+    let (_, tenant_db) = facility_db(&app_state, query.tenant_pid).await?;
+
+    let mut stmt = online_products();
+
+    if let Some(term) = &query.search {
+        use tenant::migrations::{Expr, extension::postgres::PgExpr};
+
+        let like = format!("%{}%", term);
+        stmt = stmt.filter(
+            Condition::any()
+                .add(
+                    Expr::col(tenant::entities::pharmacy_products::Column::GenericName)
+                        .ilike(like.clone()),
+                )
+                .add(
+                    Expr::col(tenant::entities::pharmacy_products::Column::BrandName)
+                        .ilike(like.clone()),
+                ),
+        );
+    }
+
+    if let Some(category) = &query.category {
+        stmt = stmt.filter(tenant::entities::pharmacy_products::Column::Category.eq(category));
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let paginator = stmt
+        .order_by_asc(tenant::entities::pharmacy_products::Column::GenericName)
+        .paginate(&tenant_db, limit);
+
+    let total_items = paginator
+        .num_items()
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let total_pages = (total_items as f64 / limit as f64).ceil() as u64;
+    let has_prev = page > 1;
+    let has_next = page < total_pages;
+
+    let products = paginator
+        .fetch_page(page.saturating_sub(1))
+        .await
+        .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
+
+    let stock = usable_stock(
+        &tenant_db,
+        None,
+        products.iter().map(|product| product.id).collect(),
+    )
+    .await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "products": products
+                .iter()
+                .map(|product| {
+                    catalogue_product_json(
+                        product,
+                        stock.get(&product.id).copied().unwrap_or(0) > 0,
+                    )
+                })
+                .collect::<Vec<_>>(),
+            "page": page,
+            "total_pages": total_pages,
+            "total_items": total_items,
+            "has_prev": has_prev,
+            "has_next": has_next,
+            "message": "Products fetched successfully",
+        }),
+    ))
+}
+
+#[get("/cart")]
+async fn cart(
+    app_state: web::Data<AppState>,
+    query: web::Query<FacilityParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let patient = find_logged_in_patient(&app_state, &req).await?;
+    let (_, tenant_db) = facility_db(&app_state, query.tenant_pid).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "cart": cart_json(&tenant_db, patient.pid).await?,
+            "message": "Cart fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct CartItemData {
+    pub tenant_pid: Option<Uuid>,
+    pub product_pid: Option<Uuid>,
+    /// The quantity wanted; zero takes the product out of the cart.
+    pub quantity: Option<i32>,
+}
+
+impl CartItemData {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if self.tenant_pid.is_none() {
+            errors.insert("tenant_pid".to_string(), "Facility is required".to_string());
+        }
+
+        if self.product_pid.is_none() {
+            errors.insert("product_pid".to_string(), "Product is required".to_string());
+        }
+
+        match self.quantity {
+            None => {
+                errors.insert("quantity".to_string(), "Quantity is required".to_string());
+            }
+            Some(quantity) if !(0..=MAX_CART_QUANTITY).contains(&quantity) => {
+                errors.insert(
+                    "quantity".to_string(),
+                    format!("Quantity must be between 0 and {}", MAX_CART_QUANTITY),
+                );
+            }
+            _ => {}
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+/// Sets how many of a product are in the cart.
+#[post("/cart/items")]
+async fn set_cart_item(
+    app_state: web::Data<AppState>,
+    data: web::Json<CartItemData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate() {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let patient = find_logged_in_patient(&app_state, &req).await?;
+    let (_, tenant_db) = facility_db(&app_state, data.tenant_pid.unwrap_or_default()).await?;
+
+    // Products taken off the store can still be removed from the cart.
+    let quantity = data.quantity.unwrap_or_default();
+    let product = tenant::entities::pharmacy_products::Entity::find_by_pid(
+        data.product_pid.unwrap_or_default(),
+    )
+    .one(&tenant_db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to fetch product: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to update cart" }))
+    })?
+    .filter(|product| quantity == 0 || is_sold_online(product))
+    .ok_or_else(|| ApiResponse::new(404, json!({ "message": "Product not found" })))?;
+
+    let existing = tenant::entities::cart_items::Entity::find()
+        .filter(tenant::entities::cart_items::Column::PatientPid.eq(patient.pid))
+        .filter(tenant::entities::cart_items::Column::ProductId.eq(product.id))
+        .one(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch cart of patient {}: {}", patient.pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to update cart" }))
+        })?;
+
+    let result = match (existing, quantity) {
+        (Some(item), 0) => tenant::entities::cart_items::Entity::delete_by_id(item.id)
+            .exec(&tenant_db)
+            .await
+            .map(|_| ()),
+        (Some(item), quantity) => {
+            let mut active_model: tenant::entities::cart_items::ActiveModel = item.into();
+            active_model.quantity = Set(quantity);
+            active_model.updated_at = Set(Utc::now().naive_utc());
+            active_model.update(&tenant_db).await.map(|_| ())
+        }
+        (None, 0) => Ok(()),
+        (None, quantity) => tenant::entities::cart_items::ActiveModel {
+            patient_pid: Set(patient.pid),
+            product_id: Set(product.id),
+            quantity: Set(quantity),
+            ..Default::default()
+        }
+        .insert(&tenant_db)
+        .await
+        .map(|_| ()),
+    };
+
+    result.map_err(|err| {
+        log::error!("Failed to update cart of patient {}: {}", patient.pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to update cart" }))
+    })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "cart": cart_json(&tenant_db, patient.pid).await?,
+            "message": "Cart updated successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct CouponPreviewData {
+    pub tenant_pid: Option<Uuid>,
+    pub coupon_codes: Vec<String>,
+}
+
+/// What the entered coupons would take off the cart. Nothing is redeemed
+/// here; the coupons are checked again at checkout.
+#[post("/cart/coupons")]
+async fn preview_coupons(
+    app_state: web::Data<AppState>,
+    data: web::Json<CouponPreviewData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let Some(tenant_pid) = data.tenant_pid else {
+        return Err(ApiResponse::new(
+            400,
+            json!(ValidationError {
+                errors: HashMap::from([(
+                    "tenant_pid".to_string(),
+                    "Facility is required".to_string()
+                )]),
+            }),
+        ));
+    };
+
+    let patient = find_logged_in_patient(&app_state, &req).await?;
+    let (_, tenant_db) = facility_db(&app_state, tenant_pid).await?;
+
+    let lines = coupon_lines(&cart_lines(&tenant_db, patient.pid).await?);
+    let subtotal_amount: Decimal = lines.iter().map(|line| line.amount).sum();
+    let applied = apply_coupons(&tenant_db, &data.coupon_codes, patient.pid, &lines).await?;
+    let discount_amount: Decimal = applied.iter().map(|coupon| coupon.discount_amount).sum();
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "coupons": applied_coupons_json(&applied),
+            "subtotal_amount": subtotal_amount,
+            "discount_amount": discount_amount,
+            "total_amount": subtotal_amount - discount_amount,
+            "message": "Coupons applied successfully",
+        }),
+    ))
+}
+
+#[derive(Debug, Default)]
+struct CheckoutForm {
+    tenant_pid: Option<Uuid>,
+    delivery_address: Option<String>,
+    contact_phone: Option<String>,
+    payment_phone: Option<String>,
+    notes: Option<String>,
+    delivery_latitude: Option<f64>,
+    delivery_longitude: Option<f64>,
+    coupon_codes: Vec<String>,
+    prescription: Option<(String, String, Vec<u8>)>,
+}
+
+impl CheckoutForm {
+    async fn from_multipart(mut payload: Multipart) -> Result<Self, ApiResponse> {
+        let mut form = CheckoutForm::default();
+
+        while let Some(Ok(mut field)) = payload.next().await {
+            let content_disposition = field.content_disposition().cloned();
+            let name = content_disposition
+                .as_ref()
+                .and_then(|cd| cd.get_name())
+                .unwrap_or("")
+                .to_string();
+
+            match name.as_str() {
+                "tenant_pid" => form.tenant_pid = Some(field_to_uuid(&mut field).await?),
+                "delivery_address" => {
+                    form.delivery_address = Some(field_to_string(&mut field).await?)
+                }
+                "contact_phone" => form.contact_phone = Some(field_to_string(&mut field).await?),
+                "payment_phone" => form.payment_phone = Some(field_to_string(&mut field).await?),
+                "notes" => form.notes = Some(field_to_string(&mut field).await?),
+                "delivery_latitude" => {
+                    form.delivery_latitude = Some(field_to_f64(&mut field).await?)
+                }
+                "delivery_longitude" => {
+                    form.delivery_longitude = Some(field_to_f64(&mut field).await?)
+                }
+                "coupon_codes" => form.coupon_codes.push(field_to_string(&mut field).await?),
+                "prescription" => {
+                    let file_name = content_disposition
+                        .as_ref()
+                        .and_then(|cd| cd.get_filename())
+                        .and_then(|name| name.rsplit(['/', '\\']).next())
+                        .filter(|name| !name.is_empty())
+                        .map(|name| name.to_string())
+                        .unwrap_or_else(|| format!("{}.bin", Uuid::new_v4()));
+                    let content_type = field
+                        .content_type()
+                        .map(|ct| ct.to_string())
+                        .unwrap_or_else(|| "application/octet-stream".to_string());
+                    let content = field_to_byte(&mut field).await?;
+
+                    if !content.is_empty() {
+                        form.prescription = Some((file_name, content_type, content));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(form)
+    }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if self.tenant_pid.is_none() {
+            errors.insert("tenant_pid".to_string(), "Facility is required".to_string());
+        }
+
+        match self.delivery_address.as_deref().map(str::trim) {
+            None | Some("") => {
+                errors.insert(
+                    "delivery_address".to_string(),
+                    "Delivery address is required".to_string(),
+                );
+            }
+            Some(address) if address.chars().count() > 500 => {
+                errors.insert(
+                    "delivery_address".to_string(),
+                    "Delivery address must be at most 500 characters".to_string(),
+                );
+            }
+            _ => {}
+        }
+
+        for (field, phone) in [
+            ("contact_phone", &self.contact_phone),
+            ("payment_phone", &self.payment_phone),
+        ] {
+            if phone
+                .as_deref()
+                .is_some_and(|phone| !validate_international_phone_number(phone))
+            {
+                errors.insert(field.to_string(), "Enter a valid phone number".to_string());
+            }
+        }
+
+        if self
+            .notes
+            .as_ref()
+            .is_some_and(|n| n.chars().count() > 1000)
+        {
+            errors.insert(
+                "notes".to_string(),
+                "Notes must be at most 1000 characters".to_string(),
+            );
+        }
+
+        errors.extend(location_errors(
+            "delivery_latitude",
+            self.delivery_latitude,
+            "delivery_longitude",
+            self.delivery_longitude,
+        ));
+
+        if let Some((file_name, content_type, content)) = &self.prescription {
+            if !PRESCRIPTION_CONTENT_TYPES.contains(&content_type.as_str()) {
+                errors.insert(
+                    "prescription".to_string(),
+                    format!(
+                        "{} is not a supported file type; upload an image or PDF",
+                        file_name
+                    ),
+                );
+            } else if content.len() as u64 > *MAX_FILE_SIZE {
+                errors.insert(
+                    "prescription".to_string(),
+                    format!("{} is too large", file_name),
+                );
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+/// Turns the cart into an order and sends an M-Pesa prompt for the total.
+/// Prices and the delivery fee are fixed at checkout. The order waits in `placed` until payment
+/// is confirmed and a pharmacist verifies it.
+#[post("/checkout")]
+async fn checkout(
+    app_state: web::Data<AppState>,
+    payload: Multipart,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let form = CheckoutForm::from_multipart(payload).await?;
+    if let Err(err) = form.validate() {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let patient = find_logged_in_patient(&app_state, &req).await?;
+    let (facility, tenant_db) =
+        facility_db(&app_state, form.tenant_pid.unwrap_or_default()).await?;
+
+    let contact_phone = form
+        .contact_phone
+        .as_deref()
+        .map(|phone| phone.replace(' ', ""))
+        .or_else(|| sms_phone_number(&patient));
+    let payment_phone = form
+        .payment_phone
+        .as_deref()
+        .map(|phone| phone.replace(' ', ""))
+        .or_else(|| contact_phone.clone());
+    let (Some(contact_phone), Some(payment_phone)) = (contact_phone, payment_phone) else {
+        return Err(ApiResponse::new(
+            400,
+            json!(ValidationError {
+                errors: HashMap::from([(
+                    "contact_phone".to_string(),
+                    "A phone number is required for delivery updates".to_string(),
+                )]),
+            }),
+        ));
+    };
+
+    let lines = cart_lines(&tenant_db, patient.pid).await?;
+    if lines.is_empty() {
+        return Err(ApiResponse::new(409, json!({ "message": "Cart is empty" })));
+    }
+
+    let unavailable: Vec<String> = lines
+        .iter()
+        .filter(|(_, product)| !is_sold_online(product))
+        .map(|(_, product)| product_label(product))
+        .collect();
+    if !unavailable.is_empty() {
+        return Err(ApiResponse::new(
+            409,
+            json!({
+                "message": "Some products are no longer sold online",
+                "products": unavailable,
+            }),
+        ));
+    }
+
+    let stock = usable_stock(
+        &tenant_db,
+        None,
+        lines.iter().map(|(_, product)| product.id).collect(),
+    )
+    .await?;
+    let short: Vec<Value> = lines
+        .iter()
+        .filter_map(|(item, product)| {
+            let available = stock.get(&product.id).copied().unwrap_or(0);
+            (available < item.quantity as i64).then(|| {
+                json!({
+                    "product": product_label(product),
+                    "requested": item.quantity,
+                    "available": available.max(0),
+                })
+            })
+        })
+        .collect();
+    if !short.is_empty() {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Insufficient stock", "products": short }),
+        ));
+    }
+
+    let needs_prescription = lines
+        .iter()
+        .any(|(_, product)| product.requires_prescription);
+    if needs_prescription && form.prescription.is_none() {
+        return Err(ApiResponse::new(
+            400,
+            json!(ValidationError {
+                errors: HashMap::from([(
+                    "prescription".to_string(),
+                    "A prescription is required for prescription-only items".to_string(),
+                )]),
+            }),
+        ));
+    }
+
+    let quote = delivery_quote(
+        &tenant_db,
+        &facility,
+        form.delivery_latitude,
+        form.delivery_longitude,
+    )
+    .await?;
+
+    let order_number = document_number("ORD");
+    let prescription_document_file_pid = match form.prescription {
+        Some((file_name, content_type, content)) => Some(
+            upload_facility_file(
+                &req,
+                &app_state,
+                Some(facility.id),
+                &format!("online_orders/{}/{}", order_number, file_name),
+                content,
+                &content_type,
+                Some(patient.id),
+                FileVisibility::Tenant,
+            )
+            .await?,
+        ),
+        None => None,
+    };
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start checkout transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to place order" }))
+    })?;
+
+    let coupon_lines = coupon_lines(&lines);
+    let subtotal_amount: Decimal = coupon_lines.iter().map(|line| line.amount).sum();
+    let applied = apply_coupons(&txn, &form.coupon_codes, patient.pid, &coupon_lines).await?;
+    let discount_amount: Decimal = applied.iter().map(|coupon| coupon.discount_amount).sum();
+    // Coupons discount the goods only, never delivery.
+    let total_amount = subtotal_amount - discount_amount + quote.fee;
+
+    // Nothing to pay when coupons cover the whole order.
+    let fully_discounted = total_amount <= Decimal::ZERO;
+
+    let order = tenant::entities::online_orders::ActiveModel {
+        order_number: Set(order_number),
+        patient_pid: Set(patient.pid),
+        status: Set(OnlineOrderStatus::Placed),
+        payment_status: Set(if fully_discounted {
+            OnlinePaymentStatus::Paid
+        } else {
+            OnlinePaymentStatus::Pending
+        }),
+        paid_at: Set(fully_discounted.then(|| Utc::now().naive_utc())),
+        subtotal_amount: Set(subtotal_amount),
+        discount_amount: Set(discount_amount),
+        delivery_fee: Set(quote.fee),
+        total_amount: Set(total_amount),
+        payment_phone: Set(payment_phone),
+        delivery_address: Set(form
+            .delivery_address
+            .as_deref()
+            .unwrap_or_default()
+            .trim()
+            .to_string()),
+        contact_phone: Set(contact_phone),
+        notes: Set(form.notes.clone()),
+        prescription_document_file_pid: Set(prescription_document_file_pid),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to create order: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to place order" }))
+    })?;
+
+    for (item, product) in &lines {
+        tenant::entities::online_order_items::ActiveModel {
+            order_id: Set(order.id),
+            product_id: Set(product.id),
+            quantity: Set(item.quantity),
+            unit_price: Set(product.unit_price),
+            line_total: Set(product.unit_price * Decimal::from(item.quantity)),
+            requires_prescription: Set(product.requires_prescription),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to add item to order {}: {}", order.pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to place order" }))
+        })?;
+    }
+
+    record_redemptions(
+        &txn,
+        &applied,
+        patient.pid,
+        ONLINE_ORDER_SOURCE,
+        order.pid,
+        subtotal_amount,
+    )
+    .await?;
+
+    tenant::entities::deliveries::ActiveModel {
+        order_id: Set(order.id),
+        zone_id: Set(quote.zone.as_ref().map(|zone| zone.id)),
+        status: Set(DeliveryStatus::Pending),
+        latitude: Set(form.delivery_latitude.and_then(Decimal::from_f64)),
+        longitude: Set(form.delivery_longitude.and_then(Decimal::from_f64)),
+        distance_km: Set(quote.distance_km),
+        fee: Set(quote.fee),
+        estimated_minutes: Set(quote.estimated_minutes),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to create delivery for order {}: {}", order.pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to place order" }))
+    })?;
+
+    tenant::entities::cart_items::Entity::delete_many()
+        .filter(tenant::entities::cart_items::Column::PatientPid.eq(patient.pid))
+        .exec(&txn)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to clear cart of patient {}: {}", patient.pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to place order" }))
+        })?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit order {}: {}", order.pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to place order" }))
+    })?;
+
+    notify_order_status(&app_state, &facility.name, &order).await;
+    let order = if fully_discounted {
+        order
+    } else {
+        start_payment(&app_state, &tenant_db, &facility, order).await?
+    };
+    let items = fetch_order_items(&tenant_db, &order).await?;
+
+    Ok(ApiResponse::new(
+        201,
+        json!({
+            "order": order_json(&order, items),
+            "message": "Order placed successfully",
+        }),
+    ))
+}
+
+#[get("/orders")]
+async fn orders(
+    app_state: web::Data<AppState>,
+    query: web::Query<FacilityParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let patient = find_logged_in_patient(&app_state, &req).await?;
+    let (_, tenant_db) = facility_db(&app_state, query.tenant_pid).await?;
+
+    let orders = tenant::entities::online_orders::Entity::find()
+        .filter(tenant::entities::online_orders::Column::PatientPid.eq(patient.pid))
+        .order_by_desc(tenant::entities::online_orders::Column::CreatedAt)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to fetch orders for patient {}: {}",
+                patient.pid,
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to fetch orders" }))
+        })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "orders": orders
+                .iter()
+                .map(|order| order_json(order, Vec::new()))
+                .collect::<Vec<_>>(),
+            "message": "Orders fetched successfully",
+        }),
+    ))
+}
+
+/// An order with its items and progress so far.
+#[get("/orders/{pid}")]
+async fn show_order(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    query: web::Query<FacilityParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let patient = find_logged_in_patient(&app_state, &req).await?;
+    let (_, tenant_db) = facility_db(&app_state, query.tenant_pid).await?;
+    let order = find_patient_order(&tenant_db, path.into_inner(), patient.pid).await?;
+    let items = fetch_order_items(&tenant_db, &order).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "order": order_json(&order, items),
+            "message": "Order fetched successfully",
+        }),
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct OrderActionData {
+    pub tenant_pid: Option<Uuid>,
+    pub payment_phone: Option<String>,
+    pub reason: Option<String>,
+}
+
+impl OrderActionData {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = HashMap::new();
+
+        if self.tenant_pid.is_none() {
+            errors.insert("tenant_pid".to_string(), "Facility is required".to_string());
+        }
+
+        if self
+            .payment_phone
+            .as_deref()
+            .is_some_and(|phone| !validate_international_phone_number(phone))
+        {
+            errors.insert(
+                "payment_phone".to_string(),
+                "Enter a valid phone number".to_string(),
+            );
+        }
+
+        if self
+            .reason
+            .as_ref()
+            .is_some_and(|r| r.chars().count() > 500)
+        {
+            errors.insert(
+                "reason".to_string(),
+                "Reason must be at most 500 characters".to_string(),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+/// Sends a fresh M-Pesa prompt for an order that has not been paid,
+/// optionally to a different phone.
+#[post("/orders/{pid}/pay")]
+async fn pay_order(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<OrderActionData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate() {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let patient = find_logged_in_patient(&app_state, &req).await?;
+    let (facility, tenant_db) =
+        facility_db(&app_state, data.tenant_pid.unwrap_or_default()).await?;
+    let order = find_patient_order(&tenant_db, path.into_inner(), patient.pid).await?;
+
+    if order.status != OnlineOrderStatus::Placed
+        || order.payment_status == OnlinePaymentStatus::Paid
+    {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Order is not awaiting payment" }),
+        ));
+    }
+
+    let order = match &data.payment_phone {
+        Some(payment_phone) => {
+            let order_pid = order.pid;
+            let mut active_model: tenant::entities::online_orders::ActiveModel = order.into();
+            active_model.payment_phone = Set(payment_phone.replace(' ', ""));
+            active_model.updated_at = Set(Utc::now().naive_utc());
+            active_model.update(&tenant_db).await.map_err(|err| {
+                log::error!("Failed to update order {}: {}", order_pid, err);
+                ApiResponse::new(500, json!({ "message": "Failed to request payment" }))
+            })?
+        }
+        None => order,
+    };
+
+    let order = start_payment(&app_state, &tenant_db, &facility, order).await?;
+    let items = fetch_order_items(&tenant_db, &order).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "order": order_json(&order, items),
+            "message": "Payment requested successfully",
+        }),
+    ))
+}
+
+/// Patients may cancel an order until it is paid; after that the pharmacy
+/// handles cancellations so the payment can be refunded.
+#[post("/orders/{pid}/cancel")]
+async fn cancel_order(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<OrderActionData>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    if let Err(err) = data.validate() {
+        return Err(ApiResponse::new(400, json!(err)));
+    }
+
+    let claims = get_logged_in_user_claims(&req)?;
+    let patient = find_logged_in_patient(&app_state, &req).await?;
+    let (facility, tenant_db) =
+        facility_db(&app_state, data.tenant_pid.unwrap_or_default()).await?;
+    let order = find_patient_order(&tenant_db, path.into_inner(), patient.pid).await?;
+
+    if order.status != OnlineOrderStatus::Placed
+        || order.payment_status == OnlinePaymentStatus::Paid
+    {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Paid orders can only be cancelled by the pharmacy" }),
+        ));
+    }
+
+    let txn = tenant_db.begin().await.map_err(|err| {
+        log::error!("Failed to start cancellation transaction: {}", err);
+        ApiResponse::new(500, json!({ "message": "Failed to cancel order" }))
+    })?;
+
+    void_redemptions(&txn, ONLINE_ORDER_SOURCE, order.pid).await?;
+    cancel_order_delivery(&txn, order.id).await?;
+
+    let order_pid = order.pid;
+    let mut active_model: tenant::entities::online_orders::ActiveModel = order.into();
+    active_model.status = Set(OnlineOrderStatus::Cancelled);
+    active_model.cancelled_at = Set(Some(Utc::now().naive_utc()));
+    active_model.cancelled_by = Set(Some(claims.sub));
+    active_model.cancel_reason = Set(data
+        .reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|r| !r.is_empty())
+        .map(str::to_string));
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let order = active_model.update(&txn).await.map_err(|err| {
+        log::error!("Failed to cancel order {}: {}", order_pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to cancel order" }))
+    })?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit cancellation of {}: {}", order_pid, err);
+        ApiResponse::new(500, json!({ "message": "Failed to cancel order" }))
+    })?;
+
+    notify_order_status(&app_state, &facility.name, &order).await;
+    let items = fetch_order_items(&tenant_db, &order).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "order": order_json(&order, items),
+            "message": "Order cancelled successfully",
+        }),
+    ))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct DeliveryQuoteParams {
+    pub tenant_pid: Uuid,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+}
+
+/// What delivery to a map pin would cost and how long it would take.
+#[get("/delivery-quote")]
+async fn quote_delivery(
+    app_state: web::Data<AppState>,
+    query: web::Query<DeliveryQuoteParams>,
+) -> Result<ApiResponse, ApiResponse> {
+    let errors = location_errors("latitude", query.latitude, "longitude", query.longitude);
+    if !errors.is_empty() {
+        return Err(ApiResponse::new(400, json!(ValidationError { errors })));
+    }
+
+    let (facility, tenant_db) = facility_db(&app_state, query.tenant_pid).await?;
+    let quote = delivery_quote(&tenant_db, &facility, query.latitude, query.longitude).await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "zone": quote.zone.as_ref().map(|zone| zone.name.clone()),
+            "distance_km": quote.distance_km,
+            "fee": quote.fee,
+            "estimated_minutes": quote.estimated_minutes,
+            "message": "Delivery quote fetched successfully",
+        }),
+    ))
+}
+
+/// Where the order is: the delivery status, the rider and their last
+/// position, and when they should arrive. The code to give the rider is
+/// shown once the order is on its way.
+#[get("/orders/{pid}/tracking")]
+async fn track_order(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    query: web::Query<FacilityParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let patient = find_logged_in_patient(&app_state, &req).await?;
+    let (_, tenant_db) = facility_db(&app_state, query.tenant_pid).await?;
+    let order = find_patient_order(&tenant_db, path.into_inner(), patient.pid).await?;
+
+    let Some(delivery) = find_order_delivery(&tenant_db, order.id).await? else {
+        return Err(ApiResponse::new(
+            404,
+            json!({ "message": "This order has no delivery" }),
+        ));
+    };
+
+    let rider = match delivery.rider_id {
+        Some(rider_id) => tenant::entities::delivery_riders::Entity::find_by_id(rider_id)
+            .one(&tenant_db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to fetch rider {}: {}", rider_id, err);
+                ApiResponse::new(500, json!({ "message": "Failed to fetch delivery" }))
+            })?,
+        None => None,
+    };
+    let on_the_way = delivery.status == DeliveryStatus::PickedUp;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "order_status": order.status,
+            "delivery": {
+                "status": delivery.status,
+                "rider": rider.as_ref().map(|rider| json!({
+                    "name": rider.name,
+                    "phone": rider.phone,
+                    "vehicle": rider.vehicle,
+                })),
+                "rider_location": rider.as_ref().filter(|_| on_the_way).map(|rider| json!({
+                    "latitude": rider.last_latitude,
+                    "longitude": rider.last_longitude,
+                    "updated_at": rider.last_location_at,
+                })),
+                "eta": delivery_eta(&delivery, rider.as_ref()),
+                "otp_code": delivery.otp_code.as_ref().filter(|_| on_the_way),
+                "fee": delivery.fee,
+                "picked_up_at": delivery.picked_up_at,
+                "delivered_at": delivery.delivered_at,
+                "recipient_name": delivery.recipient_name,
+                "failure_reason": delivery.failure_reason,
+            },
+            "message": "Order tracking fetched successfully",
+        }),
+    ))
+}
+
+/// Requests payment and records the outcome of the request. The payment
+/// itself is confirmed later by the M-Pesa callback.
+async fn start_payment(
+    app_state: &web::Data<AppState>,
+    tenant_db: &DatabaseConnection,
+    facility: &main::entities::tenants::Model,
+    order: tenant::entities::online_orders::Model,
+) -> Result<tenant::entities::online_orders::Model, ApiResponse> {
+    let callback_token = callback_token();
+    let result = request_order_payment(facility.pid, &order, &callback_token).await?;
+    let order_pid = order.pid;
+
+    let mut active_model: tenant::entities::online_orders::ActiveModel = order.into();
+    if result.is_success() {
+        active_model.payment_status = Set(OnlinePaymentStatus::Pending);
+        active_model.checkout_request_id = Set(result.provider_reference.clone());
+        active_model.callback_token = Set(Some(callback_token));
+        active_model.payment_failure_reason = Set(None);
+    } else {
+        active_model.payment_status = Set(OnlinePaymentStatus::Failed);
+        active_model.payment_failure_reason = Set(result.failure_reason.clone());
+    }
+    active_model.updated_at = Set(Utc::now().naive_utc());
+
+    let order = active_model.update(tenant_db).await.map_err(|err| {
+        log::error!(
+            "Failed to record payment request for {}: {}",
+            order_pid,
+            err
+        );
+        ApiResponse::new(500, json!({ "message": "Failed to request payment" }))
+    })?;
+
+    if !result.is_success() {
+        notify_order_status(app_state, &facility.name, &order).await;
+    }
+
+    Ok(order)
+}
+
+/// Active products listed for online sale. Controlled substances are never
+/// sold online, whatever the product's flag says.
+fn online_products()
+-> tenant::migrations::sea_orm::Select<tenant::entities::pharmacy_products::Entity> {
+    tenant::entities::pharmacy_products::Entity::find()
+        .filter(tenant::entities::pharmacy_products::Column::DeletedAt.is_null())
+        .filter(tenant::entities::pharmacy_products::Column::IsActive.eq(true))
+        .filter(tenant::entities::pharmacy_products::Column::IsSoldOnline.eq(true))
+        .filter(tenant::entities::pharmacy_products::Column::IsControlled.eq(false))
+}
+
+fn is_sold_online(product: &tenant::entities::pharmacy_products::Model) -> bool {
+    product.deleted_at.is_none()
+        && product.is_active
+        && product.is_sold_online
+        && !product.is_controlled
+}
+
+/// A map pin is optional, but both coordinates must be given and in range.
+fn location_errors(
+    latitude_field: &str,
+    latitude: Option<f64>,
+    longitude_field: &str,
+    longitude: Option<f64>,
+) -> HashMap<String, String> {
+    let mut errors = HashMap::new();
+
+    match (latitude, longitude) {
+        (Some(_), None) => {
+            errors.insert(
+                longitude_field.to_string(),
+                "Longitude is required with a latitude".to_string(),
+            );
+        }
+        (None, Some(_)) => {
+            errors.insert(
+                latitude_field.to_string(),
+                "Latitude is required with a longitude".to_string(),
+            );
+        }
+        _ => {}
+    }
+
+    if latitude.is_some_and(|lat| !(-90.0..=90.0).contains(&lat)) {
+        errors.insert(
+            latitude_field.to_string(),
+            "Latitude must be between -90 and 90".to_string(),
+        );
+    }
+
+    if longitude.is_some_and(|lng| !(-180.0..=180.0).contains(&lng)) {
+        errors.insert(
+            longitude_field.to_string(),
+            "Longitude must be between -180 and 180".to_string(),
+        );
+    }
+
+    errors
+}
+
+/// The cart lines still on sale, priced for coupon checks.
+fn coupon_lines(
+    lines: &[(
+        tenant::entities::cart_items::Model,
+        tenant::entities::pharmacy_products::Model,
+    )],
+) -> Vec<CouponLine> {
+    lines
+        .iter()
+        .filter(|(_, product)| is_sold_online(product))
+        .map(|(item, product)| CouponLine {
+            product_pid: Some(product.pid),
+            category: product.category.clone(),
+            service_code: None,
+            amount: product.unit_price * Decimal::from(item.quantity),
+        })
+        .collect()
+}
+
+async fn cart_lines(
+    tenant_db: &DatabaseConnection,
+    patient_pid: Uuid,
+) -> Result<
+    Vec<(
+        tenant::entities::cart_items::Model,
+        tenant::entities::pharmacy_products::Model,
+    )>,
+    ApiResponse,
+> {
+    Ok(tenant::entities::cart_items::Entity::find()
+        .find_also_related(tenant::entities::pharmacy_products::Entity)
+        .filter(tenant::entities::cart_items::Column::PatientPid.eq(patient_pid))
+        .order_by_asc(tenant::entities::cart_items::Column::CreatedAt)
+        .all(tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to fetch cart of patient {}: {}", patient_pid, err);
+            ApiResponse::new(500, json!({ "message": "Failed to fetch cart" }))
+        })?
+        .into_iter()
+        .filter_map(|(item, product)| product.map(|product| (item, product)))
+        .collect())
+}
+
+/// The cart with line totals. Products taken off the online store since
+/// they were added are shown but left out of the total.
+async fn cart_json(
+    tenant_db: &DatabaseConnection,
+    patient_pid: Uuid,
+) -> Result<Value, ApiResponse> {
+    let lines = cart_lines(tenant_db, patient_pid).await?;
+    let stock = usable_stock(
+        tenant_db,
+        None,
+        lines.iter().map(|(_, product)| product.id).collect(),
+    )
+    .await?;
+
+    let total: Decimal = lines
+        .iter()
+        .filter(|(_, product)| is_sold_online(product))
+        .map(|(item, product)| product.unit_price * Decimal::from(item.quantity))
+        .sum();
+
+    Ok(json!({
+        "items": lines
+            .iter()
+            .map(|(item, product)| json!({
+                "pid": item.pid,
+                "product": catalogue_product_json(
+                    product,
+                    stock.get(&product.id).copied().unwrap_or(0) >= item.quantity as i64,
+                ),
+                "quantity": item.quantity,
+                "line_total": product.unit_price * Decimal::from(item.quantity),
+                "is_available": is_sold_online(product),
+            }))
+            .collect::<Vec<_>>(),
+        "total_amount": total,
+        "requires_prescription": lines
+            .iter()
+            .any(|(_, product)| product.requires_prescription),
+    }))
+}
+
+fn catalogue_product_json(
+    product: &tenant::entities::pharmacy_products::Model,
+    in_stock: bool,
+) -> Value {
+    json!({
+        "pid": product.pid,
+        "label": product_label(product),
+        "generic_name": product.generic_name,
+        "brand_name": product.brand_name,
+        "strength": product.strength,
+        "dosage_form": product.dosage_form,
+        "pack_size": product.pack_size,
+        "unit": product.unit,
+        "category": product.category,
+        "unit_price": product.unit_price,
+        "requires_prescription": product.requires_prescription,
+        "in_stock": in_stock,
+    })
+}
+
+async fn find_patient_order(
+    tenant_db: &DatabaseConnection,
+    pid: Uuid,
+    patient_pid: Uuid,
+) -> Result<tenant::entities::online_orders::Model, ApiResponse> {
+    let order = find_order(tenant_db, pid).await?;
+    if order.patient_pid != patient_pid {
+        return Err(ApiResponse::new(
+            404,
+            json!({ "message": "Order not found" }),
+        ));
+    }
+
+    Ok(order)
+}
@@ -0,0 +1,132 @@
+use actix_web::{HttpRequest, get, post, web};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    db::tenant::{
+        self,
+        entities::sea_orm_active_enums::TelemedicineSessionStatus,
+        migrations::sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder},
+    },
+    handlers::services::{
+        features::require_tenant_feature,
+        patients::find_logged_in_patient,
+        telemedicine::{
+            ParticipantRole, RoomTicket, TELEMEDICINE_FEATURE, find_session, is_session_open,
+            issue_room_token,
+        },
+        tenants::facility_db,
+    },
+    utils::{api_response::ApiResponse, app_state::AppState, jwt::get_logged_in_user_claims},
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FacilityParams {
+    pub tenant_pid: Uuid,
+}
+
+/// The patient's open virtual appointments at a facility.
+#[get("/sessions")]
+async fn index(
+    app_state: web::Data<AppState>,
+    query: web::Query<FacilityParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let patient = find_logged_in_patient(&app_state, &req).await?;
+    let (facility, tenant_db) = facility_db(&app_state, query.tenant_pid).await?;
+    require_tenant_feature(&app_state.main_db, facility.id, TELEMEDICINE_FEATURE).await?;
+
+    let sessions = tenant::entities::telemedicine_sessions::Entity::find()
+        .filter(tenant::entities::telemedicine_sessions::Column::PatientPid.eq(patient.pid))
+        .filter(
+            tenant::entities::telemedicine_sessions::Column::Status.is_in([
+                TelemedicineSessionStatus::Scheduled,
+                TelemedicineSessionStatus::Waiting,
+                TelemedicineSessionStatus::InCall,
+            ]),
+        )
+        .order_by_asc(tenant::entities::telemedicine_sessions::Column::ScheduledAt)
+        .all(&tenant_db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to fetch sessions for patient {}: {}",
+                patient.pid,
+                err
+            );
+            ApiResponse::new(500, json!({ "message": "Failed to fetch sessions" }))
+        })?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "facility": facility.name,
+            "sessions": sessions.iter().map(patient_session_json).collect::<Vec<_>>(),
+            "message": "Sessions fetched successfully",
+        }),
+    ))
+}
+
+/// Issues the patient a one-time token for the session's signalling socket.
+#[post("/sessions/{pid}/join")]
+async fn join(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    data: web::Json<FacilityParams>,
+    req: HttpRequest,
+) -> Result<ApiResponse, ApiResponse> {
+    let claims = get_logged_in_user_claims(&req)?;
+    let patient = find_logged_in_patient(&app_state, &req).await?;
+    let (facility, tenant_db) = facility_db(&app_state, data.tenant_pid).await?;
+    require_tenant_feature(&app_state.main_db, facility.id, TELEMEDICINE_FEATURE).await?;
+    let session = find_session(&tenant_db, path.into_inner()).await?;
+
+    if session.patient_pid != patient.pid {
+        return Err(ApiResponse::new(
+            404,
+            json!({ "message": "Session not found" }),
+        ));
+    }
+
+    if !is_session_open(&session.status) {
+        return Err(ApiResponse::new(
+            409,
+            json!({ "message": "Session is no longer open" }),
+        ));
+    }
+
+    let room = issue_room_token(
+        &app_state.redis,
+        &RoomTicket {
+            sso_tenant_id: facility.sso_tenant_id,
+            session_pid: session.pid,
+            role: ParticipantRole::Patient,
+            user_id: claims.sub,
+        },
+    )
+    .await?;
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "room": room,
+            "session": patient_session_json(&session),
+            "message": "Room token issued successfully",
+        }),
+    ))
+}
+
+/// Leaves out the practitioner's call notes, which belong to the clinical
+/// record.
+fn patient_session_json(session: &tenant::entities::telemedicine_sessions::Model) -> Value {
+    json!({
+        "pid": session.pid,
+        "scheduled_at": session.scheduled_at,
+        "reason": session.reason,
+        "status": session.status,
+        "started_at": session.started_at,
+        "ended_at": session.ended_at,
+        "duration_seconds": session.duration_seconds,
+    })
+}
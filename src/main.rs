@@ -59,6 +59,9 @@ async fn main() -> Result<(), MainError> {
     dotenv::dotenv().ok();
     env_logger::init();
 
+    // Fail at startup rather than on the first credential read or write.
+    lazy_static::initialize(&utils::constants::CREDENTIALS_ENCRYPTION_KEY);
+
     let minio_endpoint = (utils::constants::MINIO_ENDPOINT).clone();
     let minio_access_key = (utils::constants::MINIO_ACCESS_KEY).clone();
     let minio_secret_key = (utils::constants::MINIO_SECRET_KEY).clone();
@@ -115,9 +118,11 @@ async fn main() -> Result<(), MainError> {
 
     let message_queue = init_message_queue(&redis_url);
 
-    init_cron_jobs(&main_db).await.map_err(|err| MainError {
-        message: err.to_string(),
-    })?;
+    init_cron_jobs(&main_db, &tenant_dbs, &message_queue)
+        .await
+        .map_err(|err| MainError {
+            message: err.to_string(),
+        })?;
 
     let backend = InMemoryBackend::builder().build();
 
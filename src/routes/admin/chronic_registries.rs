@@ -0,0 +1,39 @@
+use actix_web::web::{self};
+
+use crate::{handlers::admin::chronic_registries, middlewares::permissions::Permission};
+
+pub fn config(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/chronic-registries")
+            .service(
+                web::resource("")
+                    .wrap(Permission::new("view_all_chronic_registries".to_string()))
+                    .route(web::get().to(chronic_registries::index)),
+            )
+            .service(
+                web::resource("/show/{pid}")
+                    .wrap(Permission::new("view_chronic_registry".to_string()))
+                    .route(web::get().to(chronic_registries::show)),
+            )
+            .service(
+                web::resource("/create")
+                    .wrap(Permission::new("create_chronic_registry".to_string()))
+                    .route(web::post().to(chronic_registries::create)),
+            )
+            .service(
+                web::resource("/edit/{pid}")
+                    .wrap(Permission::new("update_chronic_registry".to_string()))
+                    .route(web::put().to(chronic_registries::edit)),
+            )
+            .service(
+                web::resource("/destroy/{pid}")
+                    .wrap(Permission::new("soft_delete_chronic_registry".to_string()))
+                    .route(web::delete().to(chronic_registries::destroy)),
+            )
+            .service(
+                web::resource("/restore/{pid}")
+                    .wrap(Permission::new("restore_chronic_registry".to_string()))
+                    .route(web::post().to(chronic_registries::restore)),
+            ),
+    );
+}
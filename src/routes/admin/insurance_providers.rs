@@ -0,0 +1,46 @@
+use actix_web::web::{self};
+
+use crate::{handlers::admin::insurance_providers, middlewares::permissions::Permission};
+
+pub fn config(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/insurance-providers")
+            .service(
+                web::resource("")
+                    .wrap(Permission::new("view_all_insurance_providers".to_string()))
+                    .route(web::get().to(insurance_providers::index)),
+            )
+            .service(
+                web::resource("/show/{pid}")
+                    .wrap(Permission::new("view_insurance_provider".to_string()))
+                    .route(web::get().to(insurance_providers::show)),
+            )
+            .service(
+                web::resource("/create")
+                    .wrap(Permission::new("create_insurance_provider".to_string()))
+                    .route(web::post().to(insurance_providers::create)),
+            )
+            .service(
+                web::resource("/edit/{pid}")
+                    .wrap(Permission::new("update_insurance_provider".to_string()))
+                    .route(web::put().to(insurance_providers::edit)),
+            )
+            .service(
+                web::resource("/plans/{pid}")
+                    .wrap(Permission::new("update_insurance_provider".to_string()))
+                    .route(web::put().to(insurance_providers::update_plans)),
+            )
+            .service(
+                web::resource("/destroy/{pid}")
+                    .wrap(Permission::new(
+                        "soft_delete_insurance_provider".to_string(),
+                    ))
+                    .route(web::delete().to(insurance_providers::destroy)),
+            )
+            .service(
+                web::resource("/restore/{pid}")
+                    .wrap(Permission::new("restore_insurance_provider".to_string()))
+                    .route(web::patch().to(insurance_providers::restore)),
+            ),
+    );
+}
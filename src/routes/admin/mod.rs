@@ -1,3 +1,6 @@
+pub mod billing_line_items;
+pub mod chronic_registries;
+pub mod insurance_providers;
 pub mod patient_insurance;
 pub mod patients;
 pub mod payments;
@@ -7,4 +10,4 @@ pub mod subscriptions;
 pub mod tenant_applications;
 pub mod tenants;
 pub mod users;
-pub mod billing_line_items;
+pub mod vaccine_schedules;
@@ -32,6 +32,31 @@ pub fn config(config: &mut web::ServiceConfig) {
                     ))
                     .route(web::patch().to(admin::patients::insurance::set_primary)),
             )
+            .service(
+                web::resource("/verify/{pid}")
+                    .wrap(Permission::new("verify_patient_insurance".to_string()))
+                    .route(web::post().to(admin::patients::insurance::verify)),
+            )
+            .service(
+                web::resource("/dependents/{pid}")
+                    .wrap(Permission::new("view_insurance_dependents".to_string()))
+                    .route(web::get().to(admin::patients::insurance::index_dependents)),
+            )
+            .service(
+                web::resource("/dependents/create/{pid}")
+                    .wrap(Permission::new("manage_insurance_dependents".to_string()))
+                    .route(web::post().to(admin::patients::insurance::add_dependent)),
+            )
+            .service(
+                web::resource("/dependents/edit/{pid}")
+                    .wrap(Permission::new("manage_insurance_dependents".to_string()))
+                    .route(web::put().to(admin::patients::insurance::edit_dependent)),
+            )
+            .service(
+                web::resource("/dependents/deactivate/{pid}")
+                    .wrap(Permission::new("manage_insurance_dependents".to_string()))
+                    .route(web::patch().to(admin::patients::insurance::deactivate_dependent_cover)),
+            )
             .service(
                 web::resource("/permanent/{pid}")
                     .wrap(Permission::new("delete_patient_insurance".to_string()))
@@ -7,12 +7,15 @@ pub fn config(config: &mut ServiceConfig) {
             .wrap(JwtAuth)
             .configure(routes::admin::patients::config)
             .configure(routes::admin::patient_insurance::config)
+            .configure(routes::admin::insurance_providers::config)
             .configure(routes::admin::tenants::config)
             .configure(routes::admin::tenant_applications::config)
             .configure(routes::admin::users::config)
             .configure(routes::admin::subscription_plans::config)
             .configure(routes::admin::payments::config)
             .configure(routes::admin::subscriptions::config)
-            .configure(routes::admin::billing_line_items::config),
+            .configure(routes::admin::billing_line_items::config)
+            .configure(routes::admin::vaccine_schedules::config)
+            .configure(routes::admin::chronic_registries::config),
     );
 }
@@ -0,0 +1,39 @@
+use actix_web::web::{self};
+
+use crate::{handlers::admin::vaccine_schedules, middlewares::permissions::Permission};
+
+pub fn config(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/vaccine-schedules")
+            .service(
+                web::resource("")
+                    .wrap(Permission::new("view_all_vaccine_schedules".to_string()))
+                    .route(web::get().to(vaccine_schedules::index)),
+            )
+            .service(
+                web::resource("/show/{pid}")
+                    .wrap(Permission::new("view_vaccine_schedule".to_string()))
+                    .route(web::get().to(vaccine_schedules::show)),
+            )
+            .service(
+                web::resource("/create")
+                    .wrap(Permission::new("create_vaccine_schedule".to_string()))
+                    .route(web::post().to(vaccine_schedules::create)),
+            )
+            .service(
+                web::resource("/edit/{pid}")
+                    .wrap(Permission::new("update_vaccine_schedule".to_string()))
+                    .route(web::put().to(vaccine_schedules::edit)),
+            )
+            .service(
+                web::resource("/destroy/{pid}")
+                    .wrap(Permission::new("soft_delete_vaccine_schedule".to_string()))
+                    .route(web::delete().to(vaccine_schedules::destroy)),
+            )
+            .service(
+                web::resource("/restore/{pid}")
+                    .wrap(Permission::new("restore_vaccine_schedule".to_string()))
+                    .route(web::post().to(vaccine_schedules::restore)),
+            ),
+    );
+}
@@ -1,11 +1,11 @@
 use actix_web::web::{self, ServiceConfig};
 
-use crate::{handlers::shared::file::get_file_url, routes};
+use crate::routes;
 
 pub fn config(config: &mut ServiceConfig) {
     config.service(
         web::scope("/api")
-            .service(get_file_url)
+            .configure(routes::files::config)
             .configure(routes::auth::config)
             .configure(routes::public::scope::config)
             .configure(routes::user::scope::config)
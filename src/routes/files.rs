@@ -0,0 +1,20 @@
+use actix_web::web::{self, ServiceConfig};
+
+use crate::{
+    handlers::shared::file,
+    middlewares::{jwt_auth::JwtAuth, permissions::Permission},
+};
+
+pub fn config(config: &mut ServiceConfig) {
+    config.service(
+        web::scope("/files")
+            .wrap(JwtAuth)
+            .service(file::index)
+            .service(file::get_file_url)
+            .service(
+                web::resource("/access-logs/{pid}")
+                    .wrap(Permission::new("view_file_access_logs".to_string()))
+                    .route(web::get().to(file::access_logs)),
+            ),
+    );
+}
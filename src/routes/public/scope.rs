@@ -1,7 +1,13 @@
 use actix_web::web::{self};
 
-use crate::handlers::health::health;
+use crate::handlers::{health::health, queue, shared::insurance_providers, telemedicine};
 
 pub fn config(config: &mut web::ServiceConfig) {
-    config.service(web::scope("/public").service(health));
+    config.service(
+        web::scope("/public")
+            .service(health)
+            .service(telemedicine::signal)
+            .service(queue::live_queue)
+            .service(insurance_providers::index),
+    );
 }
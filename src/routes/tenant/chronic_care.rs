@@ -0,0 +1,69 @@
+use actix_web::web::{self};
+
+use crate::{handlers::tenant::chronic_care, middlewares::permissions::Permission};
+
+pub fn config(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/chronic-care")
+            .service(
+                web::resource("/registries")
+                    .wrap(Permission::new("view_chronic_care_registries".to_string()))
+                    .route(web::get().to(chronic_care::index_registries)),
+            )
+            .service(
+                web::resource("/registries/{registry_pid}/cohort")
+                    .wrap(Permission::new("view_chronic_care_registries".to_string()))
+                    .route(web::get().to(chronic_care::cohort)),
+            )
+            .service(
+                web::resource("/registries/{registry_pid}/due")
+                    .wrap(Permission::new("view_chronic_care_registries".to_string()))
+                    .route(web::get().to(chronic_care::follow_ups_due)),
+            )
+            .service(
+                web::resource("/registries/{registry_pid}/defaulters")
+                    .wrap(Permission::new("view_chronic_care_defaulters".to_string()))
+                    .route(web::get().to(chronic_care::defaulters)),
+            )
+            .service(
+                web::resource("/diagnoses/create")
+                    .wrap(Permission::new("create_patient_diagnosis".to_string()))
+                    .route(web::post().to(chronic_care::create_diagnosis)),
+            )
+            .service(
+                web::resource("/diagnoses/patient/{patient_pid}")
+                    .wrap(Permission::new("view_patient_diagnoses".to_string()))
+                    .route(web::get().to(chronic_care::patient_diagnoses)),
+            )
+            .service(
+                web::resource("/enrolments/create")
+                    .wrap(Permission::new("manage_registry_enrolments".to_string()))
+                    .route(web::post().to(chronic_care::create_enrolment)),
+            )
+            .service(
+                web::resource("/enrolments/{pid}")
+                    .wrap(Permission::new("view_chronic_care_registries".to_string()))
+                    .route(web::get().to(chronic_care::show_enrolment)),
+            )
+            .service(
+                web::resource("/enrolments/{pid}/follow-ups")
+                    .wrap(Permission::new("create_registry_follow_up".to_string()))
+                    .route(web::post().to(chronic_care::create_follow_up)),
+            )
+            .service(
+                web::resource("/enrolments/exit/{pid}")
+                    .wrap(Permission::new("manage_registry_enrolments".to_string()))
+                    .route(web::put().to(chronic_care::exit_enrolment)),
+            )
+            .service(
+                web::resource("/tracing-tasks")
+                    .wrap(Permission::new("view_chronic_care_defaulters".to_string()))
+                    .route(web::get().to(chronic_care::index_tracing_tasks)),
+            )
+            .service(
+                web::resource("/tracing-tasks/edit/{pid}")
+                    .wrap(Permission::new("update_tracing_task".to_string()))
+                    .route(web::put().to(chronic_care::edit_tracing_task)),
+            ),
+    );
+}
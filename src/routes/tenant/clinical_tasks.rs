@@ -0,0 +1,19 @@
+use actix_web::web::{self};
+
+use crate::{handlers::tenant::clinical_tasks, middlewares::permissions::Permission};
+
+pub fn config(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/clinical-tasks")
+            .service(
+                web::resource("")
+                    .wrap(Permission::new("view_clinical_tasks".to_string()))
+                    .route(web::get().to(clinical_tasks::index)),
+            )
+            .service(
+                web::resource("/edit/{pid}")
+                    .wrap(Permission::new("update_clinical_task".to_string()))
+                    .route(web::put().to(clinical_tasks::edit)),
+            ),
+    );
+}
@@ -0,0 +1,44 @@
+use actix_web::web::{self};
+
+use crate::{handlers::tenant::coupons, middlewares::permissions::Permission};
+
+pub fn config(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/coupons")
+            .service(
+                web::resource("")
+                    .wrap(Permission::new("view_coupons".to_string()))
+                    .route(web::get().to(coupons::index)),
+            )
+            .service(
+                web::resource("/create")
+                    .wrap(Permission::new("manage_coupons".to_string()))
+                    .route(web::post().to(coupons::create)),
+            )
+            .service(
+                web::resource("/show/{pid}")
+                    .wrap(Permission::new("view_coupons".to_string()))
+                    .route(web::get().to(coupons::show)),
+            )
+            .service(
+                web::resource("/edit/{pid}")
+                    .wrap(Permission::new("manage_coupons".to_string()))
+                    .route(web::put().to(coupons::edit)),
+            )
+            .service(
+                web::resource("/destroy/{pid}")
+                    .wrap(Permission::new("manage_coupons".to_string()))
+                    .route(web::delete().to(coupons::destroy)),
+            )
+            .service(
+                web::resource("/redemptions")
+                    .wrap(Permission::new("view_coupons".to_string()))
+                    .route(web::get().to(coupons::redemptions)),
+            )
+            .service(
+                web::resource("/report")
+                    .wrap(Permission::new("view_coupon_reports".to_string()))
+                    .route(web::get().to(coupons::report)),
+            ),
+    );
+}
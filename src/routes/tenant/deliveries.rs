@@ -0,0 +1,89 @@
+use actix_web::web::{self};
+
+use crate::{handlers::tenant::deliveries, middlewares::permissions::Permission};
+
+pub fn config(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/deliveries")
+            .service(
+                web::resource("")
+                    .wrap(Permission::new("view_deliveries".to_string()))
+                    .route(web::get().to(deliveries::index)),
+            )
+            .service(
+                web::resource("/show/{pid}")
+                    .wrap(Permission::new("view_deliveries".to_string()))
+                    .route(web::get().to(deliveries::show)),
+            )
+            .service(
+                web::resource("/assign")
+                    .wrap(Permission::new("assign_deliveries".to_string()))
+                    .route(web::post().to(deliveries::assign)),
+            )
+            .service(
+                web::resource("/zones")
+                    .wrap(Permission::new("view_deliveries".to_string()))
+                    .route(web::get().to(deliveries::index_zones)),
+            )
+            .service(
+                web::resource("/zones/create")
+                    .wrap(Permission::new("manage_deliveries".to_string()))
+                    .route(web::post().to(deliveries::create_zone)),
+            )
+            .service(
+                web::resource("/zones/edit/{pid}")
+                    .wrap(Permission::new("manage_deliveries".to_string()))
+                    .route(web::put().to(deliveries::edit_zone)),
+            )
+            .service(
+                web::resource("/zones/destroy/{pid}")
+                    .wrap(Permission::new("manage_deliveries".to_string()))
+                    .route(web::delete().to(deliveries::destroy_zone)),
+            )
+            .service(
+                web::resource("/riders")
+                    .wrap(Permission::new("view_deliveries".to_string()))
+                    .route(web::get().to(deliveries::index_riders)),
+            )
+            .service(
+                web::resource("/riders/create")
+                    .wrap(Permission::new("manage_deliveries".to_string()))
+                    .route(web::post().to(deliveries::create_rider)),
+            )
+            .service(
+                web::resource("/riders/edit/{pid}")
+                    .wrap(Permission::new("manage_deliveries".to_string()))
+                    .route(web::put().to(deliveries::edit_rider)),
+            )
+            .service(
+                web::resource("/riders/destroy/{pid}")
+                    .wrap(Permission::new("manage_deliveries".to_string()))
+                    .route(web::delete().to(deliveries::destroy_rider)),
+            )
+            .service(
+                web::resource("/mine")
+                    .wrap(Permission::new("make_deliveries".to_string()))
+                    .route(web::get().to(deliveries::my_deliveries)),
+            )
+            .service(
+                web::resource("/location")
+                    .wrap(Permission::new("make_deliveries".to_string()))
+                    .route(web::post().to(deliveries::update_location)),
+            )
+            .service(
+                web::resource("/pickup/{pid}")
+                    .wrap(Permission::new("make_deliveries".to_string()))
+                    .route(web::post().to(deliveries::pickup)),
+            )
+            .service(
+                web::resource("/complete/{pid}")
+                    .wrap(Permission::new("make_deliveries".to_string()))
+                    .route(web::post().to(deliveries::complete)),
+            )
+            .service(
+                web::resource("/fail/{pid}")
+                    .wrap(Permission::new("make_deliveries".to_string()))
+                    .route(web::post().to(deliveries::fail)),
+            ),
+    );
+}
@@ -0,0 +1,34 @@
+use actix_web::web::{self};
+
+use crate::{handlers::tenant::immunizations, middlewares::permissions::Permission};
+
+pub fn config(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/immunizations")
+            .service(
+                web::resource("")
+                    .wrap(Permission::new("view_all_immunizations".to_string()))
+                    .route(web::get().to(immunizations::index)),
+            )
+            .service(
+                web::resource("/patient/{patient_pid}")
+                    .wrap(Permission::new("view_patient_immunizations".to_string()))
+                    .route(web::get().to(immunizations::patient_summary)),
+            )
+            .service(
+                web::resource("/create")
+                    .wrap(Permission::new("create_immunization".to_string()))
+                    .route(web::post().to(immunizations::create)),
+            )
+            .service(
+                web::resource("/edit/{pid}")
+                    .wrap(Permission::new("update_immunization".to_string()))
+                    .route(web::put().to(immunizations::edit)),
+            )
+            .service(
+                web::resource("/destroy/{pid}")
+                    .wrap(Permission::new("delete_immunization".to_string()))
+                    .route(web::delete().to(immunizations::destroy)),
+            ),
+    );
+}
@@ -0,0 +1,79 @@
+use actix_web::web::{self};
+
+use crate::{handlers::tenant::inpatient, middlewares::permissions::Permission};
+
+pub fn config(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/inpatient")
+            .service(
+                web::resource("/wards")
+                    .wrap(Permission::new("view_wards".to_string()))
+                    .route(web::get().to(inpatient::index_wards)),
+            )
+            .service(
+                web::resource("/wards/create")
+                    .wrap(Permission::new("manage_wards".to_string()))
+                    .route(web::post().to(inpatient::create_ward)),
+            )
+            .service(
+                web::resource("/wards/edit/{pid}")
+                    .wrap(Permission::new("manage_wards".to_string()))
+                    .route(web::put().to(inpatient::edit_ward)),
+            )
+            .service(
+                web::resource("/wards/{pid}/beds")
+                    .wrap(Permission::new("view_wards".to_string()))
+                    .route(web::get().to(inpatient::ward_beds)),
+            )
+            .service(
+                web::resource("/wards/{pid}/beds/create")
+                    .wrap(Permission::new("manage_wards".to_string()))
+                    .route(web::post().to(inpatient::create_bed)),
+            )
+            .service(
+                web::resource("/beds/edit/{pid}")
+                    .wrap(Permission::new("update_bed_status".to_string()))
+                    .route(web::put().to(inpatient::edit_bed)),
+            )
+            .service(
+                web::resource("/census")
+                    .wrap(Permission::new("view_wards".to_string()))
+                    .route(web::get().to(inpatient::census)),
+            )
+            .service(
+                web::resource("/metrics")
+                    .wrap(Permission::new("view_inpatient_metrics".to_string()))
+                    .route(web::get().to(inpatient::metrics)),
+            )
+            .service(
+                web::resource("/admissions")
+                    .wrap(Permission::new("view_admissions".to_string()))
+                    .route(web::get().to(inpatient::index_admissions)),
+            )
+            .service(
+                web::resource("/admissions/create")
+                    .wrap(Permission::new("admit_patient".to_string()))
+                    .route(web::post().to(inpatient::admit)),
+            )
+            .service(
+                web::resource("/admissions/show/{pid}")
+                    .wrap(Permission::new("view_admissions".to_string()))
+                    .route(web::get().to(inpatient::show_admission)),
+            )
+            .service(
+                web::resource("/admissions/{pid}/transfer")
+                    .wrap(Permission::new("transfer_patient".to_string()))
+                    .route(web::post().to(inpatient::transfer)),
+            )
+            .service(
+                web::resource("/admissions/{pid}/discharge")
+                    .wrap(Permission::new("discharge_patient".to_string()))
+                    .route(web::post().to(inpatient::discharge)),
+            )
+            .service(
+                web::resource("/admissions/{pid}/discharge-summary")
+                    .wrap(Permission::new("view_admissions".to_string()))
+                    .route(web::get().to(inpatient::discharge_summary)),
+            ),
+    );
+}
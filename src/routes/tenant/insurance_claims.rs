@@ -0,0 +1,94 @@
+use actix_web::web::{self};
+
+use crate::{handlers::tenant::insurance_claims, middlewares::permissions::Permission};
+
+pub fn config(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/insurance-claims")
+            .service(
+                web::resource("")
+                    .wrap(Permission::new("view_insurance_claims".to_string()))
+                    .route(web::get().to(insurance_claims::index)),
+            )
+            .service(
+                web::resource("/create")
+                    .wrap(Permission::new("manage_insurance_claims".to_string()))
+                    .route(web::post().to(insurance_claims::create)),
+            )
+            .service(
+                web::resource("/show/{pid}")
+                    .wrap(Permission::new("view_insurance_claims".to_string()))
+                    .route(web::get().to(insurance_claims::show)),
+            )
+            .service(
+                web::resource("/edit/{pid}")
+                    .wrap(Permission::new("manage_insurance_claims".to_string()))
+                    .route(web::put().to(insurance_claims::edit)),
+            )
+            .service(
+                web::resource("/destroy/{pid}")
+                    .wrap(Permission::new("manage_insurance_claims".to_string()))
+                    .route(web::delete().to(insurance_claims::destroy)),
+            )
+            .service(
+                web::resource("/attachments/{pid}")
+                    .wrap(Permission::new("manage_insurance_claims".to_string()))
+                    .route(web::post().to(insurance_claims::upload_attachments)),
+            )
+            .service(
+                web::resource("/attachments/show/{pid}")
+                    .wrap(Permission::new("view_insurance_claims".to_string()))
+                    .route(web::get().to(insurance_claims::attachment)),
+            )
+            .service(
+                web::resource("/submit/{pid}")
+                    .wrap(Permission::new("manage_insurance_claims".to_string()))
+                    .route(web::post().to(insurance_claims::submit)),
+            )
+            .service(
+                web::resource("/query/{pid}")
+                    .wrap(Permission::new("adjudicate_insurance_claims".to_string()))
+                    .route(web::post().to(insurance_claims::query)),
+            )
+            .service(
+                web::resource("/approve/{pid}")
+                    .wrap(Permission::new("adjudicate_insurance_claims".to_string()))
+                    .route(web::post().to(insurance_claims::approve)),
+            )
+            .service(
+                web::resource("/reject/{pid}")
+                    .wrap(Permission::new("adjudicate_insurance_claims".to_string()))
+                    .route(web::post().to(insurance_claims::reject)),
+            )
+            .service(
+                web::resource("/eligibility/{pid}")
+                    .wrap(Permission::new("verify_insurance_eligibility".to_string()))
+                    .route(web::post().to(insurance_claims::verify_eligibility)),
+            )
+            .service(
+                web::resource("/aging")
+                    .wrap(Permission::new("view_insurance_claims".to_string()))
+                    .route(web::get().to(insurance_claims::aging)),
+            )
+            .service(
+                web::resource("/remittances")
+                    .wrap(Permission::new("view_insurance_claims".to_string()))
+                    .route(web::get().to(insurance_claims::index_remittances)),
+            )
+            .service(
+                web::resource("/remittances/create")
+                    .wrap(Permission::new("manage_insurance_remittances".to_string()))
+                    .route(web::post().to(insurance_claims::create_remittance)),
+            )
+            .service(
+                web::resource("/remittances/show/{pid}")
+                    .wrap(Permission::new("view_insurance_claims".to_string()))
+                    .route(web::get().to(insurance_claims::show_remittance)),
+            )
+            .service(
+                web::resource("/remittances/allocate/{pid}")
+                    .wrap(Permission::new("manage_insurance_remittances".to_string()))
+                    .route(web::post().to(insurance_claims::allocate_remittance)),
+            ),
+    );
+}
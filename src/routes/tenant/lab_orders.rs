@@ -0,0 +1,34 @@
+use actix_web::web::{self};
+
+use crate::{handlers::tenant::lab_orders, middlewares::permissions::Permission};
+
+pub fn config(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/lab-orders")
+            .service(
+                web::resource("")
+                    .wrap(Permission::new("view_lab_orders".to_string()))
+                    .route(web::get().to(lab_orders::index)),
+            )
+            .service(
+                web::resource("/create")
+                    .wrap(Permission::new("create_lab_orders".to_string()))
+                    .route(web::post().to(lab_orders::create)),
+            )
+            .service(
+                web::resource("/show/{pid}")
+                    .wrap(Permission::new("view_lab_orders".to_string()))
+                    .route(web::get().to(lab_orders::show)),
+            )
+            .service(
+                web::resource("/complete/{pid}")
+                    .wrap(Permission::new("complete_lab_orders".to_string()))
+                    .route(web::post().to(lab_orders::complete)),
+            )
+            .service(
+                web::resource("/cancel/{pid}")
+                    .wrap(Permission::new("cancel_lab_orders".to_string()))
+                    .route(web::post().to(lab_orders::cancel)),
+            ),
+    );
+}
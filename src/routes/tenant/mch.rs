@@ -0,0 +1,54 @@
+use actix_web::web::{self};
+
+use crate::{handlers::tenant::mch, middlewares::permissions::Permission};
+
+pub fn config(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/mch")
+            .service(
+                web::resource("/anc-profiles")
+                    .wrap(Permission::new("view_all_anc_profiles".to_string()))
+                    .route(web::get().to(mch::index_anc_profiles)),
+            )
+            .service(
+                web::resource("/anc-profiles/create")
+                    .wrap(Permission::new("create_anc_profile".to_string()))
+                    .route(web::post().to(mch::create_anc_profile)),
+            )
+            .service(
+                web::resource("/anc-profiles/{pid}")
+                    .wrap(Permission::new("view_anc_profile".to_string()))
+                    .route(web::get().to(mch::show_anc_profile)),
+            )
+            .service(
+                web::resource("/anc-profiles/edit/{pid}")
+                    .wrap(Permission::new("update_anc_profile".to_string()))
+                    .route(web::put().to(mch::edit_anc_profile)),
+            )
+            .service(
+                web::resource("/anc-profiles/{pid}/visits")
+                    .wrap(Permission::new("create_anc_visit".to_string()))
+                    .route(web::post().to(mch::create_anc_visit)),
+            )
+            .service(
+                web::resource("/anc-profiles/{pid}/baby")
+                    .wrap(Permission::new("link_anc_baby".to_string()))
+                    .route(web::post().to(mch::link_baby)),
+            )
+            .service(
+                web::resource("/growth/create")
+                    .wrap(Permission::new("create_growth_measurement".to_string()))
+                    .route(web::post().to(mch::create_growth_measurement)),
+            )
+            .service(
+                web::resource("/growth/patient/{patient_pid}")
+                    .wrap(Permission::new("view_growth_measurements".to_string()))
+                    .route(web::get().to(mch::patient_growth)),
+            )
+            .service(
+                web::resource("/alerts")
+                    .wrap(Permission::new("view_mch_alerts".to_string()))
+                    .route(web::get().to(mch::alerts)),
+            ),
+    );
+}
@@ -0,0 +1,49 @@
+use actix_web::web::{self};
+
+use crate::{handlers::tenant::messages, middlewares::permissions::Permission};
+
+pub fn config(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/messages")
+            .service(
+                web::resource("/threads")
+                    .wrap(Permission::new("view_message_threads".to_string()))
+                    .route(web::get().to(messages::index)),
+            )
+            .service(
+                web::resource("/threads/create")
+                    .wrap(Permission::new("reply_message_threads".to_string()))
+                    .route(web::post().to(messages::create)),
+            )
+            .service(
+                web::resource("/threads/show/{pid}")
+                    .wrap(Permission::new("view_message_threads".to_string()))
+                    .route(web::get().to(messages::show)),
+            )
+            .service(
+                web::resource("/threads/{pid}/reply")
+                    .wrap(Permission::new("reply_message_threads".to_string()))
+                    .route(web::post().to(messages::reply)),
+            )
+            .service(
+                web::resource("/threads/{pid}/assign")
+                    .wrap(Permission::new("assign_message_threads".to_string()))
+                    .route(web::put().to(messages::assign)),
+            )
+            .service(
+                web::resource("/threads/{pid}/close")
+                    .wrap(Permission::new("reply_message_threads".to_string()))
+                    .route(web::post().to(messages::close)),
+            )
+            .service(
+                web::resource("/attachments/{pid}")
+                    .wrap(Permission::new("view_message_threads".to_string()))
+                    .route(web::get().to(messages::attachment)),
+            )
+            .service(
+                web::resource("/sla")
+                    .wrap(Permission::new("view_message_threads".to_string()))
+                    .route(web::get().to(messages::sla)),
+            ),
+    );
+}
@@ -1,7 +1,25 @@
+pub mod billing_line_items;
+pub mod chronic_care;
+pub mod clinical_tasks;
+pub mod coupons;
+pub mod deliveries;
+pub mod immunizations;
+pub mod inpatient;
+pub mod insurance_claims;
+pub mod lab_orders;
+pub mod mch;
+pub mod messages;
+pub mod patient_bills;
 pub mod payments;
+pub mod pharmacy;
+pub mod preauthorizations;
+pub mod prescriptions;
+pub mod queue;
 pub mod scope;
+pub mod screenings;
+pub mod service_catalogue;
 pub mod subscription_plans;
 pub mod subscriptions;
+pub mod telemedicine;
 pub mod tenants;
 pub mod users;
-pub mod billing_line_items;
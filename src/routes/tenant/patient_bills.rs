@@ -0,0 +1,70 @@
+use actix_web::web::{self};
+
+use crate::{handlers::tenant::patient_bills, middlewares::permissions::Permission};
+
+pub fn config(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/patient-bills")
+            .service(
+                web::resource("")
+                    .wrap(Permission::new("view_patient_bills".to_string()))
+                    .route(web::get().to(patient_bills::index)),
+            )
+            .service(
+                web::resource("/create")
+                    .wrap(Permission::new("manage_patient_bills".to_string()))
+                    .route(web::post().to(patient_bills::create)),
+            )
+            .service(
+                web::resource("/estimate")
+                    .wrap(Permission::new("view_patient_bills".to_string()))
+                    .route(web::post().to(patient_bills::estimate)),
+            )
+            .service(
+                web::resource("/show/{pid}")
+                    .wrap(Permission::new("view_patient_bills".to_string()))
+                    .route(web::get().to(patient_bills::show)),
+            )
+            .service(
+                web::resource("/finalize/{pid}")
+                    .wrap(Permission::new("manage_patient_bills".to_string()))
+                    .route(web::post().to(patient_bills::finalize)),
+            )
+            .service(
+                web::resource("/void/{pid}")
+                    .wrap(Permission::new("void_patient_bills".to_string()))
+                    .route(web::post().to(patient_bills::void)),
+            )
+            .service(
+                web::resource("/pay/{pid}")
+                    .wrap(Permission::new("receive_bill_payments".to_string()))
+                    .route(web::post().to(patient_bills::pay)),
+            )
+            .service(
+                web::resource("/payments/receipt/{pid}")
+                    .wrap(Permission::new("view_patient_bills".to_string()))
+                    .route(web::get().to(patient_bills::receipt)),
+            )
+            .service(
+                web::resource("/cashier/summary")
+                    .wrap(Permission::new("receive_bill_payments".to_string()))
+                    .route(web::get().to(patient_bills::cashier_summary)),
+            )
+            .service(
+                web::resource("/cashier/reconcile")
+                    .wrap(Permission::new("receive_bill_payments".to_string()))
+                    .route(web::post().to(patient_bills::reconcile)),
+            )
+            .service(
+                web::resource("/reconciliations")
+                    .wrap(Permission::new("view_cashier_reconciliations".to_string()))
+                    .route(web::get().to(patient_bills::index_reconciliations)),
+            )
+            .service(
+                web::resource("/settings/mpesa")
+                    .wrap(Permission::new("manage_billing_settings".to_string()))
+                    .route(web::get().to(patient_bills::show_mpesa_settings))
+                    .route(web::post().to(patient_bills::update_mpesa_settings)),
+            ),
+    );
+}
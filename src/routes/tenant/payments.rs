@@ -1,7 +1,7 @@
 use actix_web::web::{self};
 
 use crate::{
-    handlers::tenant::payments,
+    handlers::tenant::{online_orders, patient_bills, payments},
     middlewares::{jwt_auth::JwtAuth, permissions::Permission},
     utils,
 };
@@ -39,6 +39,14 @@ pub fn config(config: &mut web::ServiceConfig) {
                 web::resource(format!("/callbacks/mpesa/{}", secret))
                     .route(web::post().to(payments::mpesa_callback)),
             )
+            .service(
+                web::resource("/callbacks/mpesa/orders/{tenant_pid}/{callback_token}")
+                    .route(web::post().to(online_orders::mpesa_callback)),
+            )
+            .service(
+                web::resource("/callbacks/mpesa/bills/{tenant_pid}/{callback_token}")
+                    .route(web::post().to(patient_bills::mpesa_callback)),
+            )
             .service(
                 web::resource(format!("/webhooks/paypal/{}", secret))
                     .route(web::post().to(payments::paypal_webhook)),
@@ -0,0 +1,293 @@
+use actix_web::web::{self};
+
+use crate::{
+    handlers::tenant::{
+        controlled_substances, dispensing, online_orders, pharmacy, procurement, stock_alerts,
+    },
+    middlewares::permissions::Permission,
+};
+
+pub fn config(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/pharmacy")
+            .service(
+                web::resource("/products")
+                    .wrap(Permission::new("view_pharmacy_inventory".to_string()))
+                    .route(web::get().to(pharmacy::index_products)),
+            )
+            .service(
+                web::resource("/products/create")
+                    .wrap(Permission::new("manage_pharmacy_catalogue".to_string()))
+                    .route(web::post().to(pharmacy::create_product)),
+            )
+            .service(
+                web::resource("/products/show/{pid}")
+                    .wrap(Permission::new("view_pharmacy_inventory".to_string()))
+                    .route(web::get().to(pharmacy::show_product)),
+            )
+            .service(
+                web::resource("/products/edit/{pid}")
+                    .wrap(Permission::new("manage_pharmacy_catalogue".to_string()))
+                    .route(web::put().to(pharmacy::edit_product)),
+            )
+            .service(
+                web::resource("/products/destroy/{pid}")
+                    .wrap(Permission::new("manage_pharmacy_catalogue".to_string()))
+                    .route(web::delete().to(pharmacy::destroy_product)),
+            )
+            .service(
+                web::resource("/stores")
+                    .wrap(Permission::new("view_pharmacy_inventory".to_string()))
+                    .route(web::get().to(pharmacy::index_stores)),
+            )
+            .service(
+                web::resource("/stores/create")
+                    .wrap(Permission::new("manage_pharmacy_catalogue".to_string()))
+                    .route(web::post().to(pharmacy::create_store)),
+            )
+            .service(
+                web::resource("/stores/edit/{pid}")
+                    .wrap(Permission::new("manage_pharmacy_catalogue".to_string()))
+                    .route(web::put().to(pharmacy::edit_store)),
+            )
+            .service(
+                web::resource("/stock")
+                    .wrap(Permission::new("view_pharmacy_inventory".to_string()))
+                    .route(web::get().to(pharmacy::stock_on_hand)),
+            )
+            .service(
+                web::resource("/stock/fefo")
+                    .wrap(Permission::new("view_pharmacy_inventory".to_string()))
+                    .route(web::get().to(pharmacy::fefo_pick)),
+            )
+            .service(
+                web::resource("/movements")
+                    .wrap(Permission::new("view_pharmacy_inventory".to_string()))
+                    .route(web::get().to(pharmacy::index_movements)),
+            )
+            .service(
+                web::resource("/movements/receive")
+                    .wrap(Permission::new("receive_stock".to_string()))
+                    .route(web::post().to(pharmacy::receive)),
+            )
+            .service(
+                web::resource("/movements/transfer")
+                    .wrap(Permission::new("transfer_stock".to_string()))
+                    .route(web::post().to(pharmacy::transfer)),
+            )
+            .service(
+                web::resource("/movements/adjust")
+                    .wrap(Permission::new("adjust_stock".to_string()))
+                    .route(web::post().to(pharmacy::adjust)),
+            )
+            .service(
+                web::resource("/movements/waste")
+                    .wrap(Permission::new("adjust_stock".to_string()))
+                    .route(web::post().to(pharmacy::waste)),
+            )
+            .service(
+                web::resource("/dispensing/pending")
+                    .wrap(Permission::new("dispense_medication".to_string()))
+                    .route(web::get().to(dispensing::pending)),
+            )
+            .service(
+                web::resource("/dispenses")
+                    .wrap(Permission::new("view_pharmacy_inventory".to_string()))
+                    .route(web::get().to(dispensing::index)),
+            )
+            .service(
+                web::resource("/dispenses/create")
+                    .wrap(Permission::new("dispense_medication".to_string()))
+                    .route(web::post().to(dispensing::create)),
+            )
+            .service(
+                web::resource("/dispenses/show/{pid}")
+                    .wrap(Permission::new("view_pharmacy_inventory".to_string()))
+                    .route(web::get().to(dispensing::show)),
+            )
+            .service(
+                web::resource("/dispenses/{pid}/labels")
+                    .wrap(Permission::new("dispense_medication".to_string()))
+                    .route(web::get().to(dispensing::labels)),
+            )
+            .service(
+                web::resource("/suppliers")
+                    .wrap(Permission::new("view_procurement".to_string()))
+                    .route(web::get().to(procurement::index_suppliers)),
+            )
+            .service(
+                web::resource("/suppliers/create")
+                    .wrap(Permission::new("manage_suppliers".to_string()))
+                    .route(web::post().to(procurement::create_supplier)),
+            )
+            .service(
+                web::resource("/suppliers/show/{pid}")
+                    .wrap(Permission::new("view_procurement".to_string()))
+                    .route(web::get().to(procurement::show_supplier)),
+            )
+            .service(
+                web::resource("/suppliers/edit/{pid}")
+                    .wrap(Permission::new("manage_suppliers".to_string()))
+                    .route(web::put().to(procurement::edit_supplier)),
+            )
+            .service(
+                web::resource("/suppliers/destroy/{pid}")
+                    .wrap(Permission::new("manage_suppliers".to_string()))
+                    .route(web::delete().to(procurement::destroy_supplier)),
+            )
+            .service(
+                web::resource("/suppliers/{pid}/prices")
+                    .wrap(Permission::new("view_procurement".to_string()))
+                    .route(web::get().to(procurement::supplier_prices)),
+            )
+            .service(
+                web::resource("/suppliers/{pid}/prices/create")
+                    .wrap(Permission::new("manage_suppliers".to_string()))
+                    .route(web::post().to(procurement::create_supplier_price)),
+            )
+            .service(
+                web::resource("/purchase-orders")
+                    .wrap(Permission::new("view_procurement".to_string()))
+                    .route(web::get().to(procurement::index_orders)),
+            )
+            .service(
+                web::resource("/purchase-orders/create")
+                    .wrap(Permission::new("manage_purchase_orders".to_string()))
+                    .route(web::post().to(procurement::create_order)),
+            )
+            .service(
+                web::resource("/purchase-orders/show/{pid}")
+                    .wrap(Permission::new("view_procurement".to_string()))
+                    .route(web::get().to(procurement::show_order)),
+            )
+            .service(
+                web::resource("/purchase-orders/edit/{pid}")
+                    .wrap(Permission::new("manage_purchase_orders".to_string()))
+                    .route(web::put().to(procurement::edit_order)),
+            )
+            .service(
+                web::resource("/purchase-orders/{pid}/submit")
+                    .wrap(Permission::new("manage_purchase_orders".to_string()))
+                    .route(web::post().to(procurement::submit_order)),
+            )
+            .service(
+                web::resource("/purchase-orders/{pid}/approve")
+                    .wrap(Permission::new("approve_purchase_orders".to_string()))
+                    .route(web::post().to(procurement::approve_order)),
+            )
+            .service(
+                web::resource("/purchase-orders/{pid}/cancel")
+                    .wrap(Permission::new("manage_purchase_orders".to_string()))
+                    .route(web::post().to(procurement::cancel_order)),
+            )
+            .service(
+                web::resource("/purchase-orders/{pid}/receive")
+                    .wrap(Permission::new("receive_stock".to_string()))
+                    .route(web::post().to(procurement::receive_goods)),
+            )
+            .service(
+                web::resource("/grns")
+                    .wrap(Permission::new("view_procurement".to_string()))
+                    .route(web::get().to(procurement::index_grns)),
+            )
+            .service(
+                web::resource("/grns/show/{pid}")
+                    .wrap(Permission::new("view_procurement".to_string()))
+                    .route(web::get().to(procurement::show_grn)),
+            )
+            .service(
+                web::resource("/reorder-report")
+                    .wrap(Permission::new("view_procurement".to_string()))
+                    .route(web::get().to(procurement::reorder_report)),
+            )
+            .service(
+                web::resource("/stock-alerts")
+                    .wrap(Permission::new("view_pharmacy_inventory".to_string()))
+                    .route(web::get().to(stock_alerts::index)),
+            )
+            .service(
+                web::resource("/stock-alerts/{pid}/acknowledge")
+                    .wrap(Permission::new("acknowledge_stock_alerts".to_string()))
+                    .route(web::post().to(stock_alerts::acknowledge)),
+            )
+            .service(
+                web::resource("/controlled-register")
+                    .wrap(Permission::new("view_controlled_register".to_string()))
+                    .route(web::get().to(controlled_substances::index_entries)),
+            )
+            .service(
+                web::resource("/controlled-register/balances")
+                    .wrap(Permission::new("view_controlled_register".to_string()))
+                    .route(web::get().to(controlled_substances::balances)),
+            )
+            .service(
+                web::resource("/controlled-register/export")
+                    .wrap(Permission::new("view_controlled_register".to_string()))
+                    .route(web::get().to(controlled_substances::export)),
+            )
+            .service(
+                web::resource("/controlled-register/{pid}/witness")
+                    .wrap(Permission::new("witness_controlled_register".to_string()))
+                    .route(web::post().to(controlled_substances::witness_entry)),
+            )
+            .service(
+                web::resource("/controlled-reconciliations")
+                    .wrap(Permission::new("view_controlled_register".to_string()))
+                    .route(web::get().to(controlled_substances::index_reconciliations)),
+            )
+            .service(
+                web::resource("/controlled-reconciliations/create")
+                    .wrap(Permission::new("reconcile_controlled_stock".to_string()))
+                    .route(web::post().to(controlled_substances::create_reconciliation)),
+            )
+            .service(
+                web::resource("/controlled-reconciliations/show/{pid}")
+                    .wrap(Permission::new("view_controlled_register".to_string()))
+                    .route(web::get().to(controlled_substances::show_reconciliation)),
+            )
+            .service(
+                web::resource("/controlled-reconciliations/{pid}/witness")
+                    .wrap(Permission::new("witness_controlled_register".to_string()))
+                    .route(web::post().to(controlled_substances::witness_reconciliation)),
+            )
+            .service(
+                web::resource("/controlled-reconciliations/{pid}/investigate")
+                    .wrap(Permission::new(
+                        "investigate_controlled_discrepancies".to_string(),
+                    ))
+                    .route(web::post().to(controlled_substances::investigate)),
+            )
+            .service(
+                web::resource("/controlled-reconciliations/{pid}/resolve")
+                    .wrap(Permission::new(
+                        "investigate_controlled_discrepancies".to_string(),
+                    ))
+                    .route(web::post().to(controlled_substances::resolve)),
+            )
+            .service(
+                web::resource("/online-orders")
+                    .wrap(Permission::new("view_online_orders".to_string()))
+                    .route(web::get().to(online_orders::index)),
+            )
+            .service(
+                web::resource("/online-orders/show/{pid}")
+                    .wrap(Permission::new("view_online_orders".to_string()))
+                    .route(web::get().to(online_orders::show)),
+            )
+            .service(
+                web::resource("/online-orders/{pid}/verify")
+                    .wrap(Permission::new("process_online_orders".to_string()))
+                    .route(web::post().to(online_orders::verify)),
+            )
+            .service(
+                web::resource("/online-orders/{pid}/pack")
+                    .wrap(Permission::new("process_online_orders".to_string()))
+                    .route(web::post().to(online_orders::pack)),
+            )
+            .service(
+                web::resource("/online-orders/{pid}/cancel")
+                    .wrap(Permission::new("cancel_online_orders".to_string()))
+                    .route(web::post().to(online_orders::cancel)),
+            ),
+    );
+}
@@ -0,0 +1,54 @@
+use actix_web::web::{self};
+
+use crate::{handlers::tenant::preauthorizations, middlewares::permissions::Permission};
+
+pub fn config(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/preauthorizations")
+            .service(
+                web::resource("")
+                    .wrap(Permission::new("view_preauthorizations".to_string()))
+                    .route(web::get().to(preauthorizations::index)),
+            )
+            .service(
+                web::resource("/create")
+                    .wrap(Permission::new("manage_preauthorizations".to_string()))
+                    .route(web::post().to(preauthorizations::create)),
+            )
+            .service(
+                web::resource("/show/{pid}")
+                    .wrap(Permission::new("view_preauthorizations".to_string()))
+                    .route(web::get().to(preauthorizations::show)),
+            )
+            .service(
+                web::resource("/destroy/{pid}")
+                    .wrap(Permission::new("manage_preauthorizations".to_string()))
+                    .route(web::delete().to(preauthorizations::destroy)),
+            )
+            .service(
+                web::resource("/attachments/{pid}")
+                    .wrap(Permission::new("manage_preauthorizations".to_string()))
+                    .route(web::post().to(preauthorizations::upload_attachments)),
+            )
+            .service(
+                web::resource("/attachments/show/{pid}")
+                    .wrap(Permission::new("view_preauthorizations".to_string()))
+                    .route(web::get().to(preauthorizations::attachment)),
+            )
+            .service(
+                web::resource("/submit/{pid}")
+                    .wrap(Permission::new("manage_preauthorizations".to_string()))
+                    .route(web::post().to(preauthorizations::submit)),
+            )
+            .service(
+                web::resource("/approve/{pid}")
+                    .wrap(Permission::new("adjudicate_preauthorizations".to_string()))
+                    .route(web::post().to(preauthorizations::approve)),
+            )
+            .service(
+                web::resource("/deny/{pid}")
+                    .wrap(Permission::new("adjudicate_preauthorizations".to_string()))
+                    .route(web::post().to(preauthorizations::deny)),
+            ),
+    );
+}
@@ -0,0 +1,29 @@
+use actix_web::web::{self};
+
+use crate::{handlers::tenant::prescriptions, middlewares::permissions::Permission};
+
+pub fn config(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/prescriptions")
+            .service(
+                web::resource("")
+                    .wrap(Permission::new("view_prescriptions".to_string()))
+                    .route(web::get().to(prescriptions::index)),
+            )
+            .service(
+                web::resource("/create")
+                    .wrap(Permission::new("create_prescription".to_string()))
+                    .route(web::post().to(prescriptions::create)),
+            )
+            .service(
+                web::resource("/show/{pid}")
+                    .wrap(Permission::new("view_prescriptions".to_string()))
+                    .route(web::get().to(prescriptions::show)),
+            )
+            .service(
+                web::resource("/{pid}/cancel")
+                    .wrap(Permission::new("cancel_prescription".to_string()))
+                    .route(web::post().to(prescriptions::cancel)),
+            ),
+    );
+}
@@ -0,0 +1,59 @@
+use actix_web::web::{self};
+
+use crate::{handlers::tenant::queue, middlewares::permissions::Permission};
+
+pub fn config(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/queue")
+            .service(
+                web::resource("/check-in")
+                    .wrap(Permission::new("manage_queue".to_string()))
+                    .route(web::post().to(queue::check_in)),
+            )
+            .service(
+                web::resource("/wait-times")
+                    .wrap(Permission::new("view_queue_metrics".to_string()))
+                    .route(web::get().to(queue::wait_times)),
+            )
+            .service(
+                web::resource("/stages/{stage}")
+                    .wrap(Permission::new("view_queue".to_string()))
+                    .route(web::get().to(queue::stage_queue)),
+            )
+            .service(
+                web::resource("/stages/{stage}/live/token")
+                    .wrap(Permission::new("view_queue".to_string()))
+                    .route(web::post().to(queue::live_queue_token)),
+            )
+            .service(
+                web::resource("/tickets/show/{pid}")
+                    .wrap(Permission::new("view_queue".to_string()))
+                    .route(web::get().to(queue::show_ticket)),
+            )
+            .service(
+                web::resource("/tickets/{pid}/call")
+                    .wrap(Permission::new("manage_queue".to_string()))
+                    .route(web::post().to(queue::call)),
+            )
+            .service(
+                web::resource("/tickets/{pid}/start")
+                    .wrap(Permission::new("manage_queue".to_string()))
+                    .route(web::post().to(queue::start)),
+            )
+            .service(
+                web::resource("/tickets/{pid}/triage")
+                    .wrap(Permission::new("triage_patient".to_string()))
+                    .route(web::post().to(queue::triage)),
+            )
+            .service(
+                web::resource("/tickets/{pid}/advance")
+                    .wrap(Permission::new("manage_queue".to_string()))
+                    .route(web::post().to(queue::advance)),
+            )
+            .service(
+                web::resource("/tickets/{pid}/cancel")
+                    .wrap(Permission::new("manage_queue".to_string()))
+                    .route(web::post().to(queue::cancel)),
+            ),
+    );
+}
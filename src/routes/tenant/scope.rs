@@ -12,7 +12,25 @@ pub fn config(config: &mut ServiceConfig) {
                     .configure(routes::tenant::users::config)
                     .configure(routes::tenant::subscription_plans::config)
                     .configure(routes::tenant::subscriptions::config)
-                    .configure(routes::tenant::billing_line_items::config),
+                    .configure(routes::tenant::billing_line_items::config)
+                    .configure(routes::tenant::immunizations::config)
+                    .configure(routes::tenant::mch::config)
+                    .configure(routes::tenant::chronic_care::config)
+                    .configure(routes::tenant::screenings::config)
+                    .configure(routes::tenant::clinical_tasks::config)
+                    .configure(routes::tenant::inpatient::config)
+                    .configure(routes::tenant::queue::config)
+                    .configure(routes::tenant::telemedicine::config)
+                    .configure(routes::tenant::messages::config)
+                    .configure(routes::tenant::pharmacy::config)
+                    .configure(routes::tenant::prescriptions::config)
+                    .configure(routes::tenant::coupons::config)
+                    .configure(routes::tenant::deliveries::config)
+                    .configure(routes::tenant::service_catalogue::config)
+                    .configure(routes::tenant::lab_orders::config)
+                    .configure(routes::tenant::patient_bills::config)
+                    .configure(routes::tenant::insurance_claims::config)
+                    .configure(routes::tenant::preauthorizations::config),
             ),
     );
 }
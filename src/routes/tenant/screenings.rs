@@ -0,0 +1,34 @@
+use actix_web::web::{self};
+
+use crate::{handlers::tenant::screenings, middlewares::permissions::Permission};
+
+pub fn config(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/screenings")
+            .service(
+                web::resource("/instruments")
+                    .wrap(Permission::new("view_screening_results".to_string()))
+                    .route(web::get().to(screenings::instruments)),
+            )
+            .service(
+                web::resource("/create")
+                    .wrap(Permission::new("create_screening_result".to_string()))
+                    .route(web::post().to(screenings::create)),
+            )
+            .service(
+                web::resource("/patient/{patient_pid}")
+                    .wrap(Permission::new("view_screening_results".to_string()))
+                    .route(web::get().to(screenings::patient_history)),
+            )
+            .service(
+                web::resource("/show/{pid}")
+                    .wrap(Permission::new("view_screening_results".to_string()))
+                    .route(web::get().to(screenings::show)),
+            )
+            .service(
+                web::resource("/review/{pid}")
+                    .wrap(Permission::new("review_screening_result".to_string()))
+                    .route(web::put().to(screenings::review)),
+            ),
+    );
+}
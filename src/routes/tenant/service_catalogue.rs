@@ -0,0 +1,69 @@
+use actix_web::web::{self};
+
+use crate::{handlers::tenant::service_catalogue, middlewares::permissions::Permission};
+
+pub fn config(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/service-catalogue")
+            .service(
+                web::resource("")
+                    .wrap(Permission::new("view_service_catalogue".to_string()))
+                    .route(web::get().to(service_catalogue::index)),
+            )
+            .service(
+                web::resource("/create")
+                    .wrap(Permission::new("manage_service_catalogue".to_string()))
+                    .route(web::post().to(service_catalogue::create)),
+            )
+            .service(
+                web::resource("/show/{pid}")
+                    .wrap(Permission::new("view_service_catalogue".to_string()))
+                    .route(web::get().to(service_catalogue::show)),
+            )
+            .service(
+                web::resource("/edit/{pid}")
+                    .wrap(Permission::new("manage_service_catalogue".to_string()))
+                    .route(web::put().to(service_catalogue::edit)),
+            )
+            .service(
+                web::resource("/destroy/{pid}")
+                    .wrap(Permission::new("manage_service_catalogue".to_string()))
+                    .route(web::delete().to(service_catalogue::destroy)),
+            )
+            .service(
+                web::resource("/quote")
+                    .wrap(Permission::new("view_service_catalogue".to_string()))
+                    .route(web::get().to(service_catalogue::quote)),
+            )
+            .service(
+                web::resource("/price-lists")
+                    .wrap(Permission::new("view_service_catalogue".to_string()))
+                    .route(web::get().to(service_catalogue::index_price_lists)),
+            )
+            .service(
+                web::resource("/price-lists/create")
+                    .wrap(Permission::new("manage_price_lists".to_string()))
+                    .route(web::post().to(service_catalogue::create_price_list)),
+            )
+            .service(
+                web::resource("/price-lists/edit/{pid}")
+                    .wrap(Permission::new("manage_price_lists".to_string()))
+                    .route(web::put().to(service_catalogue::edit_price_list)),
+            )
+            .service(
+                web::resource("/price-lists/destroy/{pid}")
+                    .wrap(Permission::new("manage_price_lists".to_string()))
+                    .route(web::delete().to(service_catalogue::destroy_price_list)),
+            )
+            .service(
+                web::resource("/price-lists/{pid}/prices")
+                    .wrap(Permission::new("view_service_catalogue".to_string()))
+                    .route(web::get().to(service_catalogue::index_prices)),
+            )
+            .service(
+                web::resource("/price-lists/{pid}/prices/create")
+                    .wrap(Permission::new("manage_price_lists".to_string()))
+                    .route(web::post().to(service_catalogue::set_price)),
+            ),
+    );
+}
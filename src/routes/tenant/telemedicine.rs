@@ -0,0 +1,39 @@
+use actix_web::web::{self};
+
+use crate::{handlers::tenant::telemedicine, middlewares::permissions::Permission};
+
+pub fn config(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/telemedicine")
+            .service(
+                web::resource("/sessions")
+                    .wrap(Permission::new("view_telemedicine_sessions".to_string()))
+                    .route(web::get().to(telemedicine::index)),
+            )
+            .service(
+                web::resource("/sessions/create")
+                    .wrap(Permission::new("schedule_telemedicine_session".to_string()))
+                    .route(web::post().to(telemedicine::create)),
+            )
+            .service(
+                web::resource("/sessions/show/{pid}")
+                    .wrap(Permission::new("view_telemedicine_sessions".to_string()))
+                    .route(web::get().to(telemedicine::show)),
+            )
+            .service(
+                web::resource("/sessions/{pid}/join")
+                    .wrap(Permission::new("conduct_telemedicine_session".to_string()))
+                    .route(web::post().to(telemedicine::join)),
+            )
+            .service(
+                web::resource("/sessions/{pid}/end")
+                    .wrap(Permission::new("conduct_telemedicine_session".to_string()))
+                    .route(web::post().to(telemedicine::end)),
+            )
+            .service(
+                web::resource("/sessions/{pid}/cancel")
+                    .wrap(Permission::new("schedule_telemedicine_session".to_string()))
+                    .route(web::post().to(telemedicine::cancel)),
+            ),
+    );
+}
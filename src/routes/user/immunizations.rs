@@ -0,0 +1,11 @@
+use actix_web::web::{self};
+
+use crate::handlers::user::immunizations;
+
+pub fn config(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/immunizations")
+            .service(immunizations::index)
+            .service(immunizations::certificate),
+    );
+}
@@ -10,6 +10,11 @@ pub fn config(config: &mut web::ServiceConfig) {
             .service(insurance::create)
             .service(insurance::edit)
             .service(insurance::set_primary)
+            .service(insurance::verify)
+            .service(insurance::index_dependents)
+            .service(insurance::add_dependent)
+            .service(insurance::edit_dependent)
+            .service(insurance::deactivate_dependent_cover)
             .service(insurance::destroy),
     );
 }
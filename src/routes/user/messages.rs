@@ -0,0 +1,14 @@
+use actix_web::web::{self};
+
+use crate::handlers::user::messages;
+
+pub fn config(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/messages")
+            .service(messages::index)
+            .service(messages::create)
+            .service(messages::show)
+            .service(messages::reply)
+            .service(messages::attachment),
+    );
+}
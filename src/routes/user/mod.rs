@@ -1,4 +1,9 @@
+pub mod immunizations;
 pub mod insurance;
+pub mod messages;
 pub mod profile;
 pub mod scope;
+pub mod screenings;
+pub mod store;
+pub mod telemedicine;
 pub mod tenants;
@@ -7,6 +7,11 @@ pub fn config(config: &mut ServiceConfig) {
             .wrap(JwtAuth)
             .configure(routes::user::profile::config)
             .configure(routes::user::insurance::config)
-            .configure(routes::user::tenants::config),
+            .configure(routes::user::tenants::config)
+            .configure(routes::user::immunizations::config)
+            .configure(routes::user::screenings::config)
+            .configure(routes::user::telemedicine::config)
+            .configure(routes::user::messages::config)
+            .configure(routes::user::store::config),
     );
 }
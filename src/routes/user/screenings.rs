@@ -0,0 +1,13 @@
+use actix_web::web::{self};
+
+use crate::handlers::user::screenings;
+
+pub fn config(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/screenings")
+            .service(screenings::instruments)
+            .service(screenings::index)
+            .service(screenings::create)
+            .service(screenings::share),
+    );
+}
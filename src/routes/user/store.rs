@@ -0,0 +1,20 @@
+use actix_web::web::{self};
+
+use crate::handlers::user::store;
+
+pub fn config(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/store")
+            .service(store::products)
+            .service(store::cart)
+            .service(store::set_cart_item)
+            .service(store::preview_coupons)
+            .service(store::quote_delivery)
+            .service(store::checkout)
+            .service(store::orders)
+            .service(store::show_order)
+            .service(store::track_order)
+            .service(store::pay_order)
+            .service(store::cancel_order),
+    );
+}
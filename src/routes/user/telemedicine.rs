@@ -0,0 +1,11 @@
+use actix_web::web::{self};
+
+use crate::handlers::user::telemedicine;
+
+pub fn config(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/telemedicine")
+            .service(telemedicine::index)
+            .service(telemedicine::join),
+    );
+}
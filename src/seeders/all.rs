@@ -3,7 +3,10 @@ use std::{future::Future, pin::Pin};
 
 use crate::{
     db::{main, tenant},
-    seeders::main::permissions::seed_permissions,
+    seeders::main::{
+        chronic_registries::seed_chronic_registries, features::seed_features,
+        permissions::seed_permissions, vaccine_schedules::seed_vaccine_schedules,
+    },
     utils::api_response::ApiResponse,
 };
 
@@ -17,7 +20,12 @@ pub async fn seed_main_all(
         ) -> Pin<Box<dyn Future<Output = Result<ApiResponse, ApiResponse>> + Send + 'a>>;
 
     // Use explicit lifetime annotation here
-    let seeders: Vec<SeederFn<'_>> = vec![|_db| Box::pin(seed_permissions())];
+    let seeders: Vec<SeederFn<'_>> = vec![
+        |_db| Box::pin(seed_permissions()),
+        |db| Box::pin(seed_vaccine_schedules(db)),
+        |db| Box::pin(seed_chronic_registries(db)),
+        |db| Box::pin(seed_features(db)),
+    ];
 
     for seeder in seeders {
         let res = seeder(db).await?;
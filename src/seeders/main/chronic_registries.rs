@@ -0,0 +1,92 @@
+use serde_json::json;
+
+use crate::{
+    db::main::{
+        self,
+        entities::sea_orm_active_enums::ChronicControlIndicator,
+        migrations::sea_orm::{
+            ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait,
+            QueryFilter, Set,
+        },
+    },
+    utils::api_response::ApiResponse,
+};
+
+/// Seeds the hypertension, diabetes and HIV programmes with their ICD-10
+/// enrolment codes. Existing rows are left untouched so admin edits survive
+/// restarts.
+pub async fn seed_chronic_registries(db: &DatabaseConnection) -> Result<ApiResponse, ApiResponse> {
+    // code, name, diagnosis code prefixes, control indicator, follow-up interval, defaulter after
+    let registries = vec![
+        (
+            "HTN",
+            "Hypertension",
+            vec!["I10", "I11", "I12", "I13", "I15"],
+            ChronicControlIndicator::BloodPressure,
+            30,
+            30,
+        ),
+        (
+            "DM",
+            "Diabetes Mellitus",
+            vec!["E10", "E11", "E13", "E14"],
+            ChronicControlIndicator::Hba1c,
+            30,
+            30,
+        ),
+        (
+            "HIV",
+            "HIV Care and Treatment",
+            vec!["B20", "B21", "B22", "B23", "B24", "Z21"],
+            ChronicControlIndicator::ViralLoad,
+            30,
+            30,
+        ),
+    ];
+
+    for (code, name, diagnosis_codes, control_indicator, interval_days, defaulter_days) in
+        registries
+    {
+        let exists = main::entities::chronic_registries::Entity::find()
+            .filter(main::entities::chronic_registries::Column::Code.eq(code))
+            .count(db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to check chronic registry {}: {}", code, err);
+                ApiResponse::new(
+                    500,
+                    json!({ "message": "Failed to seed chronic registries" }),
+                )
+            })?
+            > 0;
+
+        if exists {
+            continue;
+        }
+
+        main::entities::chronic_registries::ActiveModel {
+            code: Set(code.to_string()),
+            name: Set(name.to_string()),
+            diagnosis_codes: Set(diagnosis_codes.iter().map(|c| c.to_string()).collect()),
+            control_indicator: Set(control_indicator),
+            follow_up_interval_days: Set(interval_days),
+            defaulter_after_days: Set(defaulter_days),
+            is_active: Set(true),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to seed chronic registry {}: {}", code, err);
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to seed chronic registries" }),
+            )
+        })?;
+    }
+
+    Ok(ApiResponse::new(
+        200,
+        json!({ "message": "Chronic registries seeded successfully" }),
+    ))
+}
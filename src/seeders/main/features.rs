@@ -0,0 +1,64 @@
+use serde_json::json;
+
+use crate::{
+    db::main::{
+        self,
+        migrations::sea_orm::{
+            ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait,
+            QueryFilter, Set,
+        },
+    },
+    utils::api_response::ApiResponse,
+};
+
+/// Seeds the modules that are switched on per facility through
+/// `tenant_features`. Existing rows are left untouched so admin edits survive
+/// restarts.
+pub async fn seed_features(db: &DatabaseConnection) -> Result<ApiResponse, ApiResponse> {
+    // code, name, description, premium, display order
+    let features = vec![(
+        "telemedicine",
+        "Telemedicine",
+        "Virtual consultations with WebRTC video calls between patients and practitioners.",
+        true,
+        1,
+    )];
+
+    for (code, name, description, is_premium, display_order) in features {
+        let exists = main::entities::features::Entity::find()
+            .filter(main::entities::features::Column::Code.eq(code))
+            .count(db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to check feature {}: {}", code, err);
+                ApiResponse::new(500, json!({ "message": "Failed to seed features" }))
+            })?
+            > 0;
+
+        if exists {
+            continue;
+        }
+
+        main::entities::features::ActiveModel {
+            code: Set(code.to_string()),
+            name: Set(name.to_string()),
+            description: Set(Some(description.to_string())),
+            is_premium: Set(is_premium),
+            requires_setup: Set(false),
+            is_active: Set(true),
+            display_order: Set(display_order),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to seed feature {}: {}", code, err);
+            ApiResponse::new(500, json!({ "message": "Failed to seed features" }))
+        })?;
+    }
+
+    Ok(ApiResponse::new(
+        200,
+        json!({ "message": "Features seeded successfully" }),
+    ))
+}
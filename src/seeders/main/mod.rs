@@ -1 +1,4 @@
+pub mod chronic_registries;
+pub mod features;
 pub mod permissions;
+pub mod vaccine_schedules;
@@ -43,6 +43,11 @@ pub async fn seed_permissions() -> Result<ApiResponse, ApiResponse> {
             "Allows the user to set or update whether a patient insurance record is primary",
             "Patient Insurances",
         ),
+        (
+            "verify_patient_insurance",
+            "Allows the user to check a patient insurance record with the insurer",
+            "Patient Insurances",
+        ),
         (
             "delete_patient_insurance",
             "Permanently deletes a patient insurance record",
@@ -63,6 +68,626 @@ pub async fn seed_permissions() -> Result<ApiResponse, ApiResponse> {
             "Allows the user to restore a soft-deleted patient insurance record",
             "Patient Insurances",
         ),
+        (
+            "view_insurance_dependents",
+            "Allows the user to view the dependents covered by a patient insurance policy",
+            "Patient Insurances",
+        ),
+        (
+            "manage_insurance_dependents",
+            "Allows the user to add, update and deactivate dependents on a patient insurance policy",
+            "Patient Insurances",
+        ),
+        // Insurance Providers
+        (
+            "view_all_insurance_providers",
+            "Allows the user to view all insurance providers",
+            "Insurance Providers",
+        ),
+        (
+            "view_insurance_provider",
+            "Allows the user to view a specific insurance provider",
+            "Insurance Providers",
+        ),
+        (
+            "create_insurance_provider",
+            "Allows the user to register a new insurance provider",
+            "Insurance Providers",
+        ),
+        (
+            "update_insurance_provider",
+            "Allows the user to update an insurance provider, its API settings and its plans",
+            "Insurance Providers",
+        ),
+        (
+            "soft_delete_insurance_provider",
+            "Allows the user to soft-delete an insurance provider",
+            "Insurance Providers",
+        ),
+        (
+            "restore_insurance_provider",
+            "Allows the user to restore a soft-deleted insurance provider",
+            "Insurance Providers",
+        ),
+        (
+            "view_archived_insurance_providers",
+            "Allows the user to view archived/soft-deleted insurance providers",
+            "Insurance Providers",
+        ),
+        // Files
+        (
+            "download_any_file",
+            "Allows the user to download any registered file regardless of ownership",
+            "Files",
+        ),
+        (
+            "download_tenant_files",
+            "Allows the user to download private files uploaded within their facility",
+            "Files",
+        ),
+        (
+            "view_file_access_logs",
+            "Allows the user to view the access history of a registered file",
+            "Files",
+        ),
+        // Immunizations
+        (
+            "view_all_immunizations",
+            "Allows the user to view all immunization records captured at their facility",
+            "Immunizations",
+        ),
+        (
+            "view_patient_immunizations",
+            "Allows the user to view a patient's immunization history and schedule status",
+            "Immunizations",
+        ),
+        (
+            "create_immunization",
+            "Allows the user to record an administered vaccine dose",
+            "Immunizations",
+        ),
+        (
+            "update_immunization",
+            "Allows the user to update an immunization record",
+            "Immunizations",
+        ),
+        (
+            "delete_immunization",
+            "Allows the user to delete an immunization record",
+            "Immunizations",
+        ),
+        // Maternal & Child Health
+        (
+            "view_all_anc_profiles",
+            "Allows the user to view active antenatal care profiles at their facility",
+            "Maternal & Child Health",
+        ),
+        (
+            "view_anc_profile",
+            "Allows the user to view an ANC profile with its visits and contact schedule",
+            "Maternal & Child Health",
+        ),
+        (
+            "create_anc_profile",
+            "Allows the user to open an antenatal care profile for a patient",
+            "Maternal & Child Health",
+        ),
+        (
+            "update_anc_profile",
+            "Allows the user to update or close an ANC profile",
+            "Maternal & Child Health",
+        ),
+        (
+            "create_anc_visit",
+            "Allows the user to record an antenatal care visit",
+            "Maternal & Child Health",
+        ),
+        (
+            "link_anc_baby",
+            "Allows the user to link a newborn to the mother's ANC profile and insurance",
+            "Maternal & Child Health",
+        ),
+        (
+            "view_growth_measurements",
+            "Allows the user to view a child's growth measurements and z-scores",
+            "Maternal & Child Health",
+        ),
+        (
+            "create_growth_measurement",
+            "Allows the user to record a child's growth measurement",
+            "Maternal & Child Health",
+        ),
+        (
+            "view_mch_alerts",
+            "Allows the user to view missed ANC contacts and malnutrition alerts",
+            "Maternal & Child Health",
+        ),
+        // Vaccine Schedules
+        (
+            "view_all_vaccine_schedules",
+            "Allows the user to view all vaccine schedule entries",
+            "Vaccine Schedules",
+        ),
+        (
+            "view_vaccine_schedule",
+            "Allows the user to view a specific vaccine schedule entry",
+            "Vaccine Schedules",
+        ),
+        (
+            "create_vaccine_schedule",
+            "Allows the user to add a dose to the vaccine schedule",
+            "Vaccine Schedules",
+        ),
+        (
+            "update_vaccine_schedule",
+            "Allows the user to update a vaccine schedule entry",
+            "Vaccine Schedules",
+        ),
+        (
+            "soft_delete_vaccine_schedule",
+            "Allows the user to soft-delete a vaccine schedule entry",
+            "Vaccine Schedules",
+        ),
+        (
+            "restore_vaccine_schedule",
+            "Allows the user to restore a soft-deleted vaccine schedule entry",
+            "Vaccine Schedules",
+        ),
+        (
+            "view_archived_vaccine_schedules",
+            "Allows the user to view archived/soft-deleted vaccine schedule entries",
+            "Vaccine Schedules",
+        ),
+        // Chronic Care
+        (
+            "view_chronic_care_registries",
+            "Allows the user to view chronic care registries, cohorts and follow-up due lists",
+            "Chronic Care",
+        ),
+        (
+            "view_chronic_care_defaulters",
+            "Allows the user to view defaulter reports and tracing tasks",
+            "Chronic Care",
+        ),
+        (
+            "create_patient_diagnosis",
+            "Allows the user to record a patient diagnosis",
+            "Chronic Care",
+        ),
+        (
+            "view_patient_diagnoses",
+            "Allows the user to view a patient's diagnoses and registry enrolments",
+            "Chronic Care",
+        ),
+        (
+            "manage_registry_enrolments",
+            "Allows the user to enrol patients on and exit them from chronic care registries",
+            "Chronic Care",
+        ),
+        (
+            "create_registry_follow_up",
+            "Allows the user to record a chronic care follow-up visit",
+            "Chronic Care",
+        ),
+        (
+            "update_tracing_task",
+            "Allows the user to record defaulter tracing outcomes",
+            "Chronic Care",
+        ),
+        // Chronic Registries
+        (
+            "view_all_chronic_registries",
+            "Allows the user to view all chronic disease registries",
+            "Chronic Registries",
+        ),
+        (
+            "view_chronic_registry",
+            "Allows the user to view a chronic disease registry",
+            "Chronic Registries",
+        ),
+        (
+            "create_chronic_registry",
+            "Allows the user to create a chronic disease registry",
+            "Chronic Registries",
+        ),
+        (
+            "update_chronic_registry",
+            "Allows the user to update a chronic disease registry",
+            "Chronic Registries",
+        ),
+        (
+            "soft_delete_chronic_registry",
+            "Allows the user to soft delete a chronic disease registry",
+            "Chronic Registries",
+        ),
+        (
+            "restore_chronic_registry",
+            "Allows the user to restore a soft deleted chronic disease registry",
+            "Chronic Registries",
+        ),
+        (
+            "view_archived_chronic_registries",
+            "Allows the user to view soft deleted chronic disease registries",
+            "Chronic Registries",
+        ),
+        // Mental Health Screening
+        (
+            "view_screening_results",
+            "Allows the user to view mental health screening results",
+            "Mental Health Screening",
+        ),
+        (
+            "create_screening_result",
+            "Allows the user to administer and score mental health screeners",
+            "Mental Health Screening",
+        ),
+        (
+            "review_screening_result",
+            "Allows the user to sign off on mental health screening results",
+            "Mental Health Screening",
+        ),
+        // Clinical Tasks
+        (
+            "view_clinical_tasks",
+            "Allows the user to view clinical follow-up tasks",
+            "Clinical Tasks",
+        ),
+        (
+            "update_clinical_task",
+            "Allows the user to assign, progress and close clinical tasks",
+            "Clinical Tasks",
+        ),
+        // Inpatient
+        (
+            "view_wards",
+            "Allows the user to view wards, beds and the inpatient census",
+            "Inpatient",
+        ),
+        (
+            "manage_wards",
+            "Allows the user to create and edit wards and beds",
+            "Inpatient",
+        ),
+        (
+            "update_bed_status",
+            "Allows the user to mark beds as free or being cleaned",
+            "Inpatient",
+        ),
+        (
+            "view_admissions",
+            "Allows the user to view admissions and discharge summaries",
+            "Inpatient",
+        ),
+        (
+            "admit_patient",
+            "Allows the user to admit a patient to a bed",
+            "Inpatient",
+        ),
+        (
+            "transfer_patient",
+            "Allows the user to transfer an admitted patient between beds",
+            "Inpatient",
+        ),
+        (
+            "discharge_patient",
+            "Allows the user to discharge an admitted patient",
+            "Inpatient",
+        ),
+        (
+            "view_inpatient_metrics",
+            "Allows the user to view length of stay and occupancy metrics",
+            "Inpatient",
+        ),
+        // Queue Management
+        (
+            "view_queue",
+            "Allows the user to view stage queues and live queue screens",
+            "Queue Management",
+        ),
+        (
+            "manage_queue",
+            "Allows the user to check in, call, advance and cancel queue tickets",
+            "Queue Management",
+        ),
+        (
+            "triage_patient",
+            "Allows the user to record triage and set a patient's priority",
+            "Queue Management",
+        ),
+        (
+            "view_queue_metrics",
+            "Allows the user to view queue wait times",
+            "Queue Management",
+        ),
+        // Telemedicine
+        (
+            "view_telemedicine_sessions",
+            "Allows the user to view virtual appointments and their encounters",
+            "Telemedicine",
+        ),
+        (
+            "schedule_telemedicine_session",
+            "Allows the user to schedule and cancel virtual appointments",
+            "Telemedicine",
+        ),
+        (
+            "conduct_telemedicine_session",
+            "Allows the user to join, run and end virtual consultations assigned to them",
+            "Telemedicine",
+        ),
+        // Secure Messaging
+        (
+            "view_message_threads",
+            "Allows the user to view patient message threads and their attachments",
+            "Secure Messaging",
+        ),
+        (
+            "reply_message_threads",
+            "Allows the user to start, reply to and close patient message threads",
+            "Secure Messaging",
+        ),
+        (
+            "assign_message_threads",
+            "Allows the user to assign patient message threads to care team members",
+            "Secure Messaging",
+        ),
+        // Pharmacy Inventory
+        (
+            "view_pharmacy_inventory",
+            "Allows the user to view pharmacy products, stores, stock on hand and stock movements",
+            "Pharmacy Inventory",
+        ),
+        (
+            "manage_pharmacy_catalogue",
+            "Allows the user to manage pharmacy products and stores",
+            "Pharmacy Inventory",
+        ),
+        (
+            "receive_stock",
+            "Allows the user to receive stock into a pharmacy store",
+            "Pharmacy Inventory",
+        ),
+        (
+            "transfer_stock",
+            "Allows the user to transfer stock between pharmacy stores",
+            "Pharmacy Inventory",
+        ),
+        (
+            "adjust_stock",
+            "Allows the user to record stock adjustments and write off wastage",
+            "Pharmacy Inventory",
+        ),
+        (
+            "dispense_medication",
+            "Allows the user to dispense medication against prescriptions and print labels",
+            "Pharmacy Inventory",
+        ),
+        (
+            "acknowledge_stock_alerts",
+            "Allows the user to acknowledge low stock and expiry alerts",
+            "Pharmacy Inventory",
+        ),
+        // Prescriptions
+        (
+            "view_prescriptions",
+            "Allows the user to view patient prescriptions",
+            "Prescriptions",
+        ),
+        (
+            "create_prescription",
+            "Allows the user to prescribe medication",
+            "Prescriptions",
+        ),
+        (
+            "cancel_prescription",
+            "Allows the user to cancel open prescriptions",
+            "Prescriptions",
+        ),
+        // Controlled Substances
+        (
+            "view_controlled_register",
+            "Allows the user to view and export the controlled substances register",
+            "Controlled Substances",
+        ),
+        (
+            "witness_controlled_register",
+            "Allows the user to co-sign controlled substance movements and stock counts",
+            "Controlled Substances",
+        ),
+        (
+            "reconcile_controlled_stock",
+            "Allows the user to record controlled substance stock counts",
+            "Controlled Substances",
+        ),
+        (
+            "investigate_controlled_discrepancies",
+            "Allows the user to investigate and resolve controlled substance discrepancies",
+            "Controlled Substances",
+        ),
+        // Procurement
+        (
+            "view_procurement",
+            "Allows the user to view suppliers, purchase orders and deliveries",
+            "Procurement",
+        ),
+        (
+            "manage_suppliers",
+            "Allows the user to manage suppliers and their prices",
+            "Procurement",
+        ),
+        (
+            "manage_purchase_orders",
+            "Allows the user to raise, submit and cancel purchase orders",
+            "Procurement",
+        ),
+        (
+            "approve_purchase_orders",
+            "Allows the user to approve submitted purchase orders",
+            "Procurement",
+        ),
+        // Online Store
+        (
+            "view_online_orders",
+            "Allows the user to view online store orders",
+            "Online Store",
+        ),
+        (
+            "process_online_orders",
+            "Allows the user to verify and pack online orders",
+            "Online Store",
+        ),
+        (
+            "cancel_online_orders",
+            "Allows the user to cancel online orders",
+            "Online Store",
+        ),
+        // Coupons
+        (
+            "view_coupons",
+            "Allows the user to view coupons and their redemptions",
+            "Coupons",
+        ),
+        (
+            "manage_coupons",
+            "Allows the user to create, edit and delete coupons",
+            "Coupons",
+        ),
+        (
+            "view_coupon_reports",
+            "Allows the user to view coupon redemption and revenue reports",
+            "Coupons",
+        ),
+        // Deliveries
+        (
+            "view_deliveries",
+            "Allows the user to view deliveries, delivery zones and riders",
+            "Deliveries",
+        ),
+        (
+            "manage_deliveries",
+            "Allows the user to manage delivery zones and riders",
+            "Deliveries",
+        ),
+        (
+            "assign_deliveries",
+            "Allows the user to assign online orders to riders",
+            "Deliveries",
+        ),
+        (
+            "make_deliveries",
+            "Allows the user to pick up and deliver orders as a rider",
+            "Deliveries",
+        ),
+        // Service Catalogue
+        (
+            "view_service_catalogue",
+            "Allows the user to view billable services, price lists and prices",
+            "Service Catalogue",
+        ),
+        (
+            "manage_service_catalogue",
+            "Allows the user to create, edit and delete billable services",
+            "Service Catalogue",
+        ),
+        (
+            "manage_price_lists",
+            "Allows the user to manage price lists and set service prices",
+            "Service Catalogue",
+        ),
+        // Lab Orders
+        (
+            "view_lab_orders",
+            "Allows the user to view lab orders",
+            "Lab Orders",
+        ),
+        (
+            "create_lab_orders",
+            "Allows the user to order lab tests",
+            "Lab Orders",
+        ),
+        (
+            "complete_lab_orders",
+            "Allows the user to mark lab orders as completed",
+            "Lab Orders",
+        ),
+        (
+            "cancel_lab_orders",
+            "Allows the user to cancel lab orders and void their charges",
+            "Lab Orders",
+        ),
+        // Patient Billing
+        (
+            "view_patient_bills",
+            "Allows the user to view patient bills and payments",
+            "Patient Billing",
+        ),
+        (
+            "manage_patient_bills",
+            "Allows the user to open and finalize patient bills",
+            "Patient Billing",
+        ),
+        (
+            "void_patient_bills",
+            "Allows the user to void unpaid patient bills",
+            "Patient Billing",
+        ),
+        (
+            "receive_bill_payments",
+            "Allows the user to take cash and M-Pesa payments and reconcile their day",
+            "Patient Billing",
+        ),
+        (
+            "view_cashier_reconciliations",
+            "Allows the user to view cashier reconciliations",
+            "Patient Billing",
+        ),
+        (
+            "manage_billing_settings",
+            "Allows the user to manage the facility's M-Pesa settings",
+            "Patient Billing",
+        ),
+        // Insurance Claims
+        (
+            "view_insurance_claims",
+            "Allows the user to view insurance claims, remittances and the claims aging report",
+            "Insurance Claims",
+        ),
+        (
+            "manage_insurance_claims",
+            "Allows the user to draft, edit, attach documents to and submit insurance claims",
+            "Insurance Claims",
+        ),
+        (
+            "adjudicate_insurance_claims",
+            "Allows the user to record insurer queries, approvals and rejections on claims",
+            "Insurance Claims",
+        ),
+        (
+            "manage_insurance_remittances",
+            "Allows the user to record insurer remittances and allocate them to claims",
+            "Insurance Claims",
+        ),
+        (
+            "verify_insurance_eligibility",
+            "Allows the user to check a patient's insurance eligibility with the insurer",
+            "Insurance Claims",
+        ),
+        // Pre-authorizations
+        (
+            "view_preauthorizations",
+            "Allows the user to view insurance pre-authorization requests and their documents",
+            "Pre-authorizations",
+        ),
+        (
+            "manage_preauthorizations",
+            "Allows the user to request, attach documents to and submit insurance pre-authorizations",
+            "Pre-authorizations",
+        ),
+        (
+            "adjudicate_preauthorizations",
+            "Allows the user to record insurer approvals and denials of pre-authorizations",
+            "Pre-authorizations",
+        ),
         // Subscription Plans
         (
             "view_all_subscription_plans",
@@ -0,0 +1,256 @@
+use serde_json::json;
+
+use crate::{
+    db::main::{
+        self,
+        entities::sea_orm_active_enums::Gender,
+        migrations::sea_orm::{
+            ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait,
+            QueryFilter, Set,
+        },
+    },
+    utils::api_response::ApiResponse,
+};
+
+/// Seeds the Kenya Expanded Programme on Immunization (KEPI) schedule. Doses
+/// that are only given in targeted counties are seeded inactive so they can be
+/// switched on where they apply. Existing rows are left untouched so admin
+/// edits survive restarts.
+pub async fn seed_vaccine_schedules(db: &DatabaseConnection) -> Result<ApiResponse, ApiResponse> {
+    // code, name, dose number, recommended age (days), overdue after (days), gender, active, notes
+    let kepi_schedule = vec![
+        (
+            "BCG",
+            "BCG",
+            1,
+            0,
+            14,
+            None,
+            true,
+            Some("Given at birth or first contact"),
+        ),
+        (
+            "OPV",
+            "Oral Polio Vaccine",
+            0,
+            0,
+            14,
+            None,
+            true,
+            Some("Birth dose, within 2 weeks of birth"),
+        ),
+        ("OPV", "Oral Polio Vaccine", 1, 42, 14, None, true, None),
+        ("OPV", "Oral Polio Vaccine", 2, 70, 14, None, true, None),
+        ("OPV", "Oral Polio Vaccine", 3, 98, 14, None, true, None),
+        (
+            "PENTA",
+            "DPT-HepB-Hib (Pentavalent)",
+            1,
+            42,
+            14,
+            None,
+            true,
+            None,
+        ),
+        (
+            "PENTA",
+            "DPT-HepB-Hib (Pentavalent)",
+            2,
+            70,
+            14,
+            None,
+            true,
+            None,
+        ),
+        (
+            "PENTA",
+            "DPT-HepB-Hib (Pentavalent)",
+            3,
+            98,
+            14,
+            None,
+            true,
+            None,
+        ),
+        (
+            "PCV10",
+            "Pneumococcal Conjugate Vaccine",
+            1,
+            42,
+            14,
+            None,
+            true,
+            None,
+        ),
+        (
+            "PCV10",
+            "Pneumococcal Conjugate Vaccine",
+            2,
+            70,
+            14,
+            None,
+            true,
+            None,
+        ),
+        (
+            "PCV10",
+            "Pneumococcal Conjugate Vaccine",
+            3,
+            98,
+            14,
+            None,
+            true,
+            None,
+        ),
+        ("ROTA", "Rotavirus Vaccine", 1, 42, 14, None, true, None),
+        ("ROTA", "Rotavirus Vaccine", 2, 70, 14, None, true, None),
+        (
+            "IPV",
+            "Inactivated Polio Vaccine",
+            1,
+            98,
+            14,
+            None,
+            true,
+            None,
+        ),
+        (
+            "MR",
+            "Measles-Rubella",
+            1,
+            274,
+            30,
+            None,
+            true,
+            Some("At 9 months"),
+        ),
+        (
+            "MR",
+            "Measles-Rubella",
+            2,
+            548,
+            30,
+            None,
+            true,
+            Some("At 18 months"),
+        ),
+        (
+            "YF",
+            "Yellow Fever",
+            1,
+            274,
+            30,
+            None,
+            false,
+            Some("Targeted counties only"),
+        ),
+        (
+            "RTSS",
+            "Malaria Vaccine (RTS,S)",
+            1,
+            183,
+            30,
+            None,
+            false,
+            Some("Targeted counties only"),
+        ),
+        (
+            "RTSS",
+            "Malaria Vaccine (RTS,S)",
+            2,
+            213,
+            30,
+            None,
+            false,
+            Some("Targeted counties only"),
+        ),
+        (
+            "RTSS",
+            "Malaria Vaccine (RTS,S)",
+            3,
+            244,
+            30,
+            None,
+            false,
+            Some("Targeted counties only"),
+        ),
+        (
+            "RTSS",
+            "Malaria Vaccine (RTS,S)",
+            4,
+            730,
+            60,
+            None,
+            false,
+            Some("Targeted counties only"),
+        ),
+        (
+            "HPV",
+            "Human Papillomavirus Vaccine",
+            1,
+            3650,
+            365,
+            Some(Gender::Female),
+            true,
+            Some("Girls from 10 years"),
+        ),
+    ];
+
+    for (code, name, dose_number, age_days, overdue_after_days, gender, is_active, notes) in
+        kepi_schedule
+    {
+        let exists = main::entities::vaccine_schedules::Entity::find()
+            .filter(main::entities::vaccine_schedules::Column::VaccineCode.eq(code))
+            .filter(main::entities::vaccine_schedules::Column::DoseNumber.eq(dose_number))
+            .count(db)
+            .await
+            .map_err(|err| {
+                log::error!(
+                    "Failed to check vaccine schedule {} {}: {}",
+                    code,
+                    dose_number,
+                    err
+                );
+                ApiResponse::new(
+                    500,
+                    json!({ "message": "Failed to seed vaccine schedules" }),
+                )
+            })?
+            > 0;
+
+        if exists {
+            continue;
+        }
+
+        main::entities::vaccine_schedules::ActiveModel {
+            vaccine_code: Set(code.to_string()),
+            vaccine_name: Set(name.to_string()),
+            dose_number: Set(dose_number),
+            recommended_age_days: Set(age_days),
+            overdue_after_days: Set(overdue_after_days),
+            gender: Set(gender),
+            notes: Set(notes.map(|n| n.to_string())),
+            is_active: Set(is_active),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .map_err(|err| {
+            log::error!(
+                "Failed to seed vaccine schedule {} {}: {}",
+                code,
+                dose_number,
+                err
+            );
+            ApiResponse::new(
+                500,
+                json!({ "message": "Failed to seed vaccine schedules" }),
+            )
+        })?;
+    }
+
+    Ok(ApiResponse::new(
+        200,
+        json!({ "message": "Vaccine schedules seeded successfully" }),
+    ))
+}
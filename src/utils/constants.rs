@@ -1,5 +1,6 @@
 use std::env;
 
+use base64::{Engine, engine::general_purpose::STANDARD};
 use lazy_static::lazy_static;
 
 lazy_static! {
@@ -19,6 +20,7 @@ lazy_static! {
     pub static ref VASPROT_SHORTCODE: String = vaspro_shortcode();
     pub static ref REDIS_URL: String = redis_url();
     pub static ref SECRET: String = set_secret();
+    pub static ref CREDENTIALS_ENCRYPTION_KEY: [u8; 32] = credentials_encryption_key();
     pub static ref MINIO_ENDPOINT: String = set_minio_endpoint();
     pub static ref MINIO_ACCESS_KEY: String = set_minio_access_key();
     pub static ref MINIO_SECRET_KEY: String = set_minio_secret_key();
@@ -41,6 +43,9 @@ lazy_static! {
     pub static ref APP_ACCENT_COLOR: String = app_accent_color();
     pub static ref APP_TEXT_COLOR: String = app_text_color();
     pub static ref APP_FOOTER_TEXT_COLOR: String = app_footer_text_color();
+    pub static ref WEBRTC_ICE_SERVERS: Vec<String> = webrtc_ice_servers();
+    pub static ref STOCK_EXPIRY_ALERT_DAYS: Vec<i64> = stock_expiry_alert_days();
+    pub static ref DELIVERY_AVERAGE_SPEED_KMH: f64 = delivery_average_speed_kmh();
 }
 
 fn set_app_name() -> String {
@@ -138,6 +143,18 @@ fn set_secret() -> String {
     env::var("SECRET").expect("Environment variable 'SECRET' is required but not set.")
 }
 
+fn credentials_encryption_key() -> [u8; 32] {
+    dotenv::dotenv().ok();
+    let key = env::var("CREDENTIALS_ENCRYPTION_KEY")
+        .expect("Environment variable 'CREDENTIALS_ENCRYPTION_KEY' is required but not set.");
+
+    STANDARD
+        .decode(key.trim())
+        .expect("Failed to decode 'CREDENTIALS_ENCRYPTION_KEY' as base64.")
+        .try_into()
+        .expect("'CREDENTIALS_ENCRYPTION_KEY' must be 32 bytes.")
+}
+
 fn set_minio_endpoint() -> String {
     dotenv::dotenv().ok();
     env::var("MINIO_ENDPOINT")
@@ -264,3 +281,35 @@ fn app_footer_text_color() -> String {
     dotenv::dotenv().ok();
     env::var("APP_FOOTER_TEXT_COLOR").unwrap_or_else(|_| "#666666".to_string())
 }
+
+fn webrtc_ice_servers() -> Vec<String> {
+    dotenv::dotenv().ok();
+    env::var("WEBRTC_ICE_SERVERS")
+        .unwrap_or_else(|_| "stun:stun.l.google.com:19302".to_string())
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<String>>()
+}
+
+fn stock_expiry_alert_days() -> Vec<i64> {
+    dotenv::dotenv().ok();
+    let mut windows = env::var("STOCK_EXPIRY_ALERT_DAYS")
+        .unwrap_or_else(|_| "30,60,90".to_string())
+        .split(',')
+        .filter_map(|s| s.trim().parse::<i64>().ok())
+        .filter(|days| *days > 0)
+        .collect::<Vec<i64>>();
+    windows.sort_unstable();
+    windows.dedup();
+    windows
+}
+
+fn delivery_average_speed_kmh() -> f64 {
+    dotenv::dotenv().ok();
+    env::var("DELIVERY_AVERAGE_SPEED_KMH")
+        .ok()
+        .and_then(|s| s.trim().parse::<f64>().ok())
+        .filter(|speed| *speed > 0.0)
+        .unwrap_or(25.0)
+}
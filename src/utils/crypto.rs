@@ -0,0 +1,101 @@
+use crate::utils::constants::CREDENTIALS_ENCRYPTION_KEY;
+use aes_gcm::{
+    Aes256Gcm, Key, Nonce,
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use serde_json::{Value, json};
+
+/// Marks values produced by [`encrypt_json`] and [`encrypt_string`], so
+/// values stored before encryption was introduced can still be read.
+const ENVELOPE_VERSION: &str = "aes256gcm.v1";
+
+fn cipher() -> Aes256Gcm {
+    Aes256Gcm::new(&Key::<Aes256Gcm>::from(*CREDENTIALS_ENCRYPTION_KEY))
+}
+
+/// Encrypts a JSON value with `CREDENTIALS_ENCRYPTION_KEY`, returning an
+/// envelope that can be stored in a JSON column.
+pub fn encrypt_json(value: &Value) -> Result<Value, String> {
+    let plaintext = serde_json::to_vec(value).map_err(|err| err.to_string())?;
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+
+    let ciphertext = cipher()
+        .encrypt(&nonce, plaintext.as_ref())
+        .map_err(|err| format!("Encryption failed: {}", err))?;
+
+    Ok(json!({
+        "version": ENVELOPE_VERSION,
+        "nonce": STANDARD.encode(nonce),
+        "ciphertext": STANDARD.encode(ciphertext),
+    }))
+}
+
+/// Opens an envelope made by [`encrypt_json`]. Anything else is returned
+/// unchanged, as it was stored before encryption.
+pub fn decrypt_json(value: &Value) -> Result<Value, String> {
+    if value.get("version").and_then(Value::as_str) != Some(ENVELOPE_VERSION) {
+        return Ok(value.clone());
+    }
+
+    let field = |name: &str| {
+        value
+            .get(name)
+            .and_then(Value::as_str)
+            .ok_or_else(|| format!("Encrypted value has no {}", name))
+            .and_then(|encoded| STANDARD.decode(encoded).map_err(|err| err.to_string()))
+    };
+    let nonce: [u8; 12] = field("nonce")?
+        .try_into()
+        .map_err(|_| "Encrypted value has an invalid nonce".to_string())?;
+    let ciphertext = field("ciphertext")?;
+
+    let plaintext = cipher()
+        .decrypt(&Nonce::from(nonce), ciphertext.as_ref())
+        .map_err(|err| format!("Decryption failed: {}", err))?;
+
+    serde_json::from_slice(&plaintext).map_err(|err| err.to_string())
+}
+
+/// Encrypts a string for a text column, as `version:nonce:ciphertext`.
+pub fn encrypt_string(value: &str) -> Result<String, String> {
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+
+    let ciphertext = cipher()
+        .encrypt(&nonce, value.as_bytes())
+        .map_err(|err| format!("Encryption failed: {}", err))?;
+
+    Ok(format!(
+        "{}:{}:{}",
+        ENVELOPE_VERSION,
+        STANDARD.encode(nonce),
+        STANDARD.encode(ciphertext)
+    ))
+}
+
+/// Opens a string made by [`encrypt_string`]. Anything else is returned
+/// unchanged, as it was stored before encryption.
+pub fn decrypt_string(value: &str) -> Result<String, String> {
+    let Some(encoded) = value
+        .strip_prefix(ENVELOPE_VERSION)
+        .and_then(|rest| rest.strip_prefix(':'))
+    else {
+        return Ok(value.to_string());
+    };
+
+    let (nonce, ciphertext) = encoded
+        .split_once(':')
+        .ok_or_else(|| "Encrypted value has no ciphertext".to_string())?;
+    let nonce: [u8; 12] = STANDARD
+        .decode(nonce)
+        .map_err(|err| err.to_string())?
+        .try_into()
+        .map_err(|_| "Encrypted value has an invalid nonce".to_string())?;
+    let ciphertext = STANDARD.decode(ciphertext).map_err(|err| err.to_string())?;
+
+    let plaintext = cipher()
+        .decrypt(&Nonce::from(nonce), ciphertext.as_ref())
+        .map_err(|err| format!("Decryption failed: {}", err))?;
+
+    String::from_utf8(plaintext).map_err(|err| err.to_string())
+}
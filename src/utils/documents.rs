@@ -0,0 +1,9 @@
+use chrono::Utc;
+use uuid::Uuid;
+
+/// A document number such as "PO-20251227-3F9A1C". The date keeps numbers
+/// readable; the random suffix keeps them unique without a counter table.
+pub fn document_number(prefix: &str) -> String {
+    let suffix = Uuid::new_v4().simple().to_string()[..6].to_uppercase();
+    format!("{}-{}-{}", prefix, Utc::now().format("%Y%m%d"), suffix)
+}
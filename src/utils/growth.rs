@@ -0,0 +1,395 @@
+use crate::db::main::entities::sea_orm_active_enums::Gender;
+
+/// One row of a WHO Child Growth Standards LMS table. `x` is age in months for
+/// the age-based indicators and recumbent length in cm for weight-for-length.
+type Lms = (f64, f64, f64, f64);
+
+// WHO Child Growth Standards (2006), sampled at anchor points. Values between
+// anchors are linearly interpolated, which keeps the error well inside the
+// rounding used on clinic growth cards. Swap in the full daily tables if finer
+// precision is ever needed.
+
+const WEIGHT_FOR_AGE_BOYS: [Lms; 14] = [
+    (0.0, 0.3487, 3.3464, 0.14602),
+    (1.0, 0.2297, 4.4709, 0.13395),
+    (2.0, 0.1970, 5.5675, 0.12385),
+    (3.0, 0.1738, 6.3762, 0.11727),
+    (4.0, 0.1553, 7.0023, 0.11316),
+    (5.0, 0.1395, 7.5105, 0.11080),
+    (6.0, 0.1257, 7.9340, 0.10958),
+    (9.0, 0.0917, 8.9014, 0.10881),
+    (12.0, 0.0644, 9.6479, 0.10925),
+    (18.0, 0.0211, 10.9385, 0.11046),
+    (24.0, -0.0137, 12.1515, 0.11220),
+    (36.0, -0.0689, 14.3429, 0.11723),
+    (48.0, -0.1130, 16.3489, 0.12261),
+    (60.0, -0.1506, 18.3366, 0.12748),
+];
+
+const WEIGHT_FOR_AGE_GIRLS: [Lms; 14] = [
+    (0.0, 0.3809, 3.2322, 0.14171),
+    (1.0, 0.1714, 4.1873, 0.13724),
+    (2.0, 0.0962, 5.1282, 0.13000),
+    (3.0, 0.0402, 5.8458, 0.12619),
+    (4.0, -0.0050, 6.4237, 0.12402),
+    (5.0, -0.0430, 6.8985, 0.12274),
+    (6.0, -0.0756, 7.2970, 0.12204),
+    (9.0, -0.1545, 8.2254, 0.12148),
+    (12.0, -0.2024, 8.9481, 0.12268),
+    (18.0, -0.2658, 10.2315, 0.12632),
+    (24.0, -0.2980, 11.4775, 0.13000),
+    (36.0, -0.3414, 13.8503, 0.13559),
+    (48.0, -0.3800, 16.0697, 0.14005),
+    (60.0, -0.4100, 18.2193, 0.14408),
+];
+
+const LENGTH_FOR_AGE_BOYS: [Lms; 14] = [
+    (0.0, 1.0, 49.8842, 0.03795),
+    (1.0, 1.0, 54.7244, 0.03557),
+    (2.0, 1.0, 58.4249, 0.03424),
+    (3.0, 1.0, 61.4292, 0.03328),
+    (4.0, 1.0, 63.8860, 0.03257),
+    (5.0, 1.0, 65.9026, 0.03204),
+    (6.0, 1.0, 67.6236, 0.03165),
+    (9.0, 1.0, 72.0245, 0.03112),
+    (12.0, 1.0, 75.7488, 0.03137),
+    (18.0, 1.0, 82.2587, 0.03275),
+    (24.0, 1.0, 87.1161, 0.03507),
+    (36.0, 1.0, 96.0835, 0.03740),
+    (48.0, 1.0, 103.3273, 0.03908),
+    (60.0, 1.0, 109.9638, 0.04046),
+];
+
+const LENGTH_FOR_AGE_GIRLS: [Lms; 14] = [
+    (0.0, 1.0, 49.1477, 0.03790),
+    (1.0, 1.0, 53.6872, 0.03640),
+    (2.0, 1.0, 57.0673, 0.03568),
+    (3.0, 1.0, 59.8029, 0.03520),
+    (4.0, 1.0, 62.0899, 0.03486),
+    (5.0, 1.0, 64.0301, 0.03463),
+    (6.0, 1.0, 65.7311, 0.03448),
+    (9.0, 1.0, 70.1435, 0.03433),
+    (12.0, 1.0, 74.0150, 0.03469),
+    (18.0, 1.0, 80.7079, 0.03651),
+    (24.0, 1.0, 85.7153, 0.03764),
+    (36.0, 1.0, 95.0515, 0.03996),
+    (48.0, 1.0, 102.7312, 0.04177),
+    (60.0, 1.0, 109.4233, 0.04339),
+];
+
+const WEIGHT_FOR_LENGTH_BOYS: [Lms; 16] = [
+    (45.0, -0.3521, 2.4410, 0.09182),
+    (50.0, -0.3521, 3.3278, 0.08996),
+    (55.0, -0.3521, 4.5436, 0.08694),
+    (60.0, -0.3521, 5.8851, 0.08445),
+    (65.0, -0.3521, 7.2918, 0.08262),
+    (70.0, -0.3521, 8.5506, 0.08149),
+    (75.0, -0.3521, 9.6085, 0.08102),
+    (80.0, -0.3521, 10.5991, 0.08101),
+    (85.0, -0.3521, 11.6851, 0.08137),
+    (90.0, -0.3521, 12.8896, 0.08215),
+    (95.0, -0.3521, 14.1233, 0.08348),
+    (100.0, -0.3521, 15.4223, 0.08546),
+    (105.0, -0.3521, 16.8430, 0.08808),
+    (110.0, -0.3521, 18.4168, 0.09124),
+    (115.0, -0.3521, 20.1630, 0.09474),
+    (120.0, -0.3521, 22.0866, 0.09836),
+];
+
+const WEIGHT_FOR_LENGTH_GIRLS: [Lms; 16] = [
+    (45.0, -0.3833, 2.4607, 0.09029),
+    (50.0, -0.3833, 3.3418, 0.09037),
+    (55.0, -0.3833, 4.4879, 0.09008),
+    (60.0, -0.3833, 5.7257, 0.08927),
+    (65.0, -0.3833, 7.0320, 0.08859),
+    (70.0, -0.3833, 8.2007, 0.08832),
+    (75.0, -0.3833, 9.1886, 0.08846),
+    (80.0, -0.3833, 10.1667, 0.08913),
+    (85.0, -0.3833, 11.2702, 0.09041),
+    (90.0, -0.3833, 12.5160, 0.09213),
+    (95.0, -0.3833, 13.8221, 0.09399),
+    (100.0, -0.3833, 15.1963, 0.09585),
+    (105.0, -0.3833, 16.7263, 0.09779),
+    (110.0, -0.3833, 18.4264, 0.09973),
+    (115.0, -0.3833, 20.2877, 0.10163),
+    (120.0, -0.3833, 22.2722, 0.10339),
+];
+
+/// Children measured standing from this age are converted to recumbent length
+/// before the weight-for-length lookup, as the WHO standards prescribe.
+const STANDING_FROM_DAYS: i64 = 731;
+const STANDING_TO_RECUMBENT_CM: f64 = 0.7;
+const DAYS_PER_MONTH: f64 = 30.4375;
+const MAX_AGE_DAYS: i64 = 1856;
+const LAST_ANCHOR_MONTHS: f64 = 60.0;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GrowthZScores {
+    pub weight_for_age: Option<f64>,
+    pub height_for_age: Option<f64>,
+    pub weight_for_height: Option<f64>,
+}
+
+fn interpolate(table: &[Lms], x: f64) -> Option<(f64, f64, f64)> {
+    let first = table.first()?;
+    let last = table.last()?;
+    if x < first.0 || x > last.0 {
+        return None;
+    }
+
+    let upper = table.iter().position(|row| row.0 >= x)?;
+    if upper == 0 || table[upper].0 == x {
+        let row = table[upper];
+        return Some((row.1, row.2, row.3));
+    }
+
+    let (x0, l0, m0, s0) = table[upper - 1];
+    let (x1, l1, m1, s1) = table[upper];
+    let t = (x - x0) / (x1 - x0);
+
+    Some((l0 + (l1 - l0) * t, m0 + (m1 - m0) * t, s0 + (s1 - s0) * t))
+}
+
+/// LMS z-score. Weight-based indicators use the WHO restricted application
+/// beyond +/-3 SD so extreme values are not exaggerated by the skewed tail.
+fn lms_z_score(value: f64, (l, m, s): (f64, f64, f64), restricted: bool) -> f64 {
+    let z = if l.abs() < f64::EPSILON {
+        (value / m).ln() / s
+    } else {
+        ((value / m).powf(l) - 1.0) / (l * s)
+    };
+
+    if !restricted || z.abs() <= 3.0 {
+        return z;
+    }
+
+    let sd = |n: f64| m * (1.0 + l * s * n).powf(1.0 / l);
+
+    if z > 3.0 {
+        3.0 + (value - sd(3.0)) / (sd(3.0) - sd(2.0))
+    } else {
+        -3.0 + (value - sd(-3.0)) / (sd(-2.0) - sd(-3.0))
+    }
+}
+
+/// Computes WHO z-scores for a child aged `age_days` (0 to 5 years). Indicators
+/// outside the reference range, or for children without a binary sex on file,
+/// are left empty.
+pub fn who_z_scores(
+    gender: Option<&Gender>,
+    age_days: i64,
+    weight_kg: Option<f64>,
+    height_cm: Option<f64>,
+) -> GrowthZScores {
+    let (wfa, lhfa, wfl) = match gender {
+        Some(Gender::Male) => (
+            &WEIGHT_FOR_AGE_BOYS[..],
+            &LENGTH_FOR_AGE_BOYS[..],
+            &WEIGHT_FOR_LENGTH_BOYS[..],
+        ),
+        Some(Gender::Female) => (
+            &WEIGHT_FOR_AGE_GIRLS[..],
+            &LENGTH_FOR_AGE_GIRLS[..],
+            &WEIGHT_FOR_LENGTH_GIRLS[..],
+        ),
+        _ => return GrowthZScores::default(),
+    };
+
+    if !(0..=MAX_AGE_DAYS).contains(&age_days) {
+        return GrowthZScores::default();
+    }
+
+    // The sampled tables stop at 60 months; the standard's last month reuses
+    // that row.
+    let age_months = (age_days as f64 / DAYS_PER_MONTH).min(LAST_ANCHOR_MONTHS);
+
+    let weight_for_age = weight_kg
+        .and_then(|weight| interpolate(wfa, age_months).map(|lms| lms_z_score(weight, lms, true)));
+
+    let height_for_age = height_cm.and_then(|height| {
+        interpolate(lhfa, age_months).map(|lms| lms_z_score(height, lms, false))
+    });
+
+    let weight_for_height = match (weight_kg, height_cm) {
+        (Some(weight), Some(height)) => {
+            let length = if age_days >= STANDING_FROM_DAYS {
+                height + STANDING_TO_RECUMBENT_CM
+            } else {
+                height
+            };
+            interpolate(wfl, length).map(|lms| lms_z_score(weight, lms, true))
+        }
+        _ => None,
+    };
+
+    GrowthZScores {
+        weight_for_age,
+        height_for_age,
+        weight_for_height,
+    }
+}
+
+/// Classifies nutrition status using WHO/IMAM cut-offs: weight-for-height
+/// z-score, MUAC (6-59 months) and bilateral pitting oedema.
+pub fn nutrition_status(
+    z_scores: &GrowthZScores,
+    muac_cm: Option<f64>,
+    oedema: bool,
+    age_days: i64,
+) -> &'static str {
+    let whz = z_scores.weight_for_height;
+    let muac = muac_cm.filter(|_| age_days >= 183);
+
+    if oedema || whz.is_some_and(|z| z < -3.0) || muac.is_some_and(|m| m < 11.5) {
+        "severe_acute_malnutrition"
+    } else if whz.is_some_and(|z| z < -2.0) || muac.is_some_and(|m| m < 12.5) {
+        "moderate_acute_malnutrition"
+    } else if whz.is_some_and(|z| z > 3.0) {
+        "obese"
+    } else if whz.is_some_and(|z| z > 2.0) {
+        "overweight"
+    } else if whz.is_none() && muac.is_none() {
+        "unknown"
+    } else {
+        "normal"
+    }
+}
+
+/// Chronic and underweight flags that sit alongside the acute status.
+pub fn growth_flags(z_scores: &GrowthZScores) -> Vec<String> {
+    let mut flags = Vec::new();
+
+    if let Some(haz) = z_scores.height_for_age {
+        if haz < -3.0 {
+            flags.push("severely_stunted".to_string());
+        } else if haz < -2.0 {
+            flags.push("stunted".to_string());
+        }
+    }
+
+    if let Some(waz) = z_scores.weight_for_age {
+        if waz < -3.0 {
+            flags.push("severely_underweight".to_string());
+        } else if waz < -2.0 {
+            flags.push("underweight".to_string());
+        }
+    }
+
+    flags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The measurement that sits exactly `n` SD from the median.
+    fn sd_value((l, m, s): (f64, f64, f64), n: f64) -> f64 {
+        m * (1.0 + l * s * n).powf(1.0 / l)
+    }
+
+    fn whz(z: f64) -> GrowthZScores {
+        GrowthZScores {
+            weight_for_height: Some(z),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn z_scores_land_on_the_sd_lines() {
+        let lms = interpolate(&WEIGHT_FOR_AGE_BOYS, 0.0).unwrap();
+
+        for n in [-3.0, -2.0, 0.0, 2.0, 3.0] {
+            let z = who_z_scores(Some(&Gender::Male), 0, Some(sd_value(lms, n)), None)
+                .weight_for_age
+                .unwrap();
+            assert!((z - n).abs() < 1e-9, "expected {n}, got {z}");
+        }
+    }
+
+    #[test]
+    fn weight_z_scores_beyond_three_sd_are_restricted() {
+        let lms = interpolate(&WEIGHT_FOR_AGE_GIRLS, 0.0).unwrap();
+        let step = sd_value(lms, -2.0) - sd_value(lms, -3.0);
+
+        let z = who_z_scores(
+            Some(&Gender::Female),
+            0,
+            Some(sd_value(lms, -3.0) - step),
+            None,
+        )
+        .weight_for_age
+        .unwrap();
+        assert!((z + 4.0).abs() < 1e-9, "expected -4, got {z}");
+    }
+
+    #[test]
+    fn z_scores_need_a_binary_sex_and_an_age_in_range() {
+        let empty = who_z_scores(None, 100, Some(6.0), Some(60.0));
+        assert!(empty.weight_for_age.is_none() && empty.weight_for_height.is_none());
+
+        let too_old = who_z_scores(Some(&Gender::Male), MAX_AGE_DAYS + 1, Some(18.0), None);
+        assert!(too_old.weight_for_age.is_none());
+
+        let last_day = who_z_scores(Some(&Gender::Male), MAX_AGE_DAYS, Some(18.0), None);
+        assert!(last_day.weight_for_age.is_some());
+    }
+
+    #[test]
+    fn nutrition_status_cut_offs() {
+        let cases = [
+            (-3.01, "severe_acute_malnutrition"),
+            (-3.0, "moderate_acute_malnutrition"),
+            (-2.01, "moderate_acute_malnutrition"),
+            (-2.0, "normal"),
+            (2.0, "normal"),
+            (2.01, "overweight"),
+            (3.0, "overweight"),
+            (3.01, "obese"),
+        ];
+
+        for (z, expected) in cases {
+            assert_eq!(
+                nutrition_status(&whz(z), None, false, 400),
+                expected,
+                "z = {z}"
+            );
+        }
+    }
+
+    #[test]
+    fn nutrition_status_uses_muac_from_six_months_and_oedema() {
+        let none = GrowthZScores::default();
+
+        assert_eq!(nutrition_status(&none, None, false, 400), "unknown");
+        assert_eq!(
+            nutrition_status(&none, Some(11.4), false, 400),
+            "severe_acute_malnutrition"
+        );
+        assert_eq!(
+            nutrition_status(&none, Some(11.5), false, 400),
+            "moderate_acute_malnutrition"
+        );
+        assert_eq!(nutrition_status(&none, Some(12.5), false, 400), "normal");
+        assert_eq!(nutrition_status(&none, Some(11.4), false, 182), "unknown");
+        assert_eq!(
+            nutrition_status(&whz(0.0), None, true, 400),
+            "severe_acute_malnutrition"
+        );
+    }
+
+    #[test]
+    fn growth_flags_cut_offs() {
+        let flags = |z: f64| {
+            growth_flags(&GrowthZScores {
+                weight_for_age: Some(z),
+                height_for_age: Some(z),
+                weight_for_height: None,
+            })
+        };
+
+        assert!(flags(-2.0).is_empty());
+        assert_eq!(flags(-2.01), ["stunted", "underweight"]);
+        assert_eq!(flags(-3.0), ["stunted", "underweight"]);
+        assert_eq!(flags(-3.01), ["severely_stunted", "severely_underweight"]);
+    }
+}
@@ -0,0 +1,8 @@
+/// Escapes text for use inside HTML element content and quoted attributes.
+pub fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
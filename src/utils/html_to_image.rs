@@ -4,15 +4,33 @@ use actix_web::HttpRequest;
 use serde_json::json;
 use uuid::Uuid;
 
-use crate::utils::{api_response::ApiResponse, app_state::AppState, multipart::upload_file};
+use crate::{
+    db::main::entities::sea_orm_active_enums::FileVisibility,
+    utils::{api_response::ApiResponse, app_state::AppState, multipart::upload_file},
+};
 
 pub async fn generate_receipt_png(
     html: &str,
     req: &HttpRequest,
     app_state: &AppState,
-) -> Result<String, ApiResponse> {
-    let html_path = "/tmp/invoice_temp.html";
-    fs::write(html_path, html).map_err(|err| {
+) -> Result<Uuid, ApiResponse> {
+    let s3_key = format!("invoice/{}.png", Uuid::new_v4());
+
+    generate_png(html, req, app_state, &s3_key, None, FileVisibility::Tenant).await
+}
+
+/// Renders `html` with wkhtmltoimage and uploads the PNG under `s3_key`,
+/// returning the pid of the registered file.
+pub async fn generate_png(
+    html: &str,
+    req: &HttpRequest,
+    app_state: &AppState,
+    s3_key: &str,
+    patient_id: Option<i32>,
+    visibility: FileVisibility,
+) -> Result<Uuid, ApiResponse> {
+    let html_path = format!("/tmp/{}.html", Uuid::new_v4());
+    fs::write(&html_path, html).map_err(|err| {
         log::error!("Failed to write HTML to temp file: {}", err);
         ApiResponse::new(500, json!({ "message": "Internal server error" }))
     })?;
@@ -29,7 +47,7 @@ pub async fn generate_receipt_png(
             "650",
             "--enable-local-file-access",
             "--no-stop-slow-scripts",
-            html_path,
+            &html_path,
             &tmp_png_path,
         ])
         .output()
@@ -54,8 +72,19 @@ pub async fn generate_receipt_png(
         ApiResponse::new(500, json!({ "message": "Internal server error" }))
     })?;
 
-    let s3_key = format!("invoice/{}.png", Uuid::new_v4());
-    let s3_url = upload_file(req, app_state, &s3_key, file_bytes, "image/png").await?;
+    let _ = fs::remove_file(&html_path);
+    let _ = fs::remove_file(&tmp_png_path);
+
+    let file_pid = upload_file(
+        req,
+        app_state,
+        s3_key,
+        file_bytes,
+        "image/png",
+        patient_id,
+        visibility,
+    )
+    .await?;
 
-    Ok(s3_url)
+    Ok(file_pid)
 }
@@ -0,0 +1,446 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{Datelike, Days, NaiveDate, Utc};
+use reqwest::Client;
+use rust_decimal::{Decimal, dec};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{db::main::entities::insurance_providers, utils::crypto::decrypt_json};
+
+/// Providers whose `api_endpoint` is set to this are served by
+/// [`MockInsuranceAdapter`] instead of a real insurer.
+pub const MOCK_ENDPOINT: &str = "mock://insurer";
+
+const REQUEST_TIMEOUT_SECS: u64 = 15;
+
+/// Mock pre-authorizations estimated above this are left pending.
+const MOCK_PREAUTH_REVIEW_AMOUNT: Decimal = dec!(100000);
+
+#[derive(Serialize, Debug, Clone)]
+pub struct EligibilityRequest {
+    pub policy_number: String,
+    pub group_number: Option<String>,
+    pub member_name: Option<String>,
+    pub date_of_birth: Option<NaiveDate>,
+    /// The day cover is checked for.
+    pub service_date: NaiveDate,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EligibilityStatus {
+    /// The member has cover in force on the service date.
+    Eligible,
+    /// The policy exists but its cover has lapsed.
+    Expired,
+    /// The insurer does not recognise the policy, or refuses cover.
+    Ineligible,
+}
+
+/// One planned service in a pre-authorization request.
+#[derive(Serialize, Debug, Clone)]
+pub struct PreauthorizationLine {
+    pub service_code: String,
+    pub description: String,
+    pub quantity: i32,
+    pub unit_price: Decimal,
+    pub total_amount: Decimal,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct PreauthorizationRequest {
+    /// The facility's own reference, so the insurer can quote it back.
+    pub preauth_number: String,
+    pub policy_number: String,
+    pub group_number: Option<String>,
+    pub member_name: Option<String>,
+    pub date_of_birth: Option<NaiveDate>,
+    pub planned_date: Option<NaiveDate>,
+    pub diagnosis_codes: Vec<String>,
+    pub clinical_notes: Option<String>,
+    pub estimated_amount: Decimal,
+    pub currency: String,
+    pub lines: Vec<PreauthorizationLine>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PreauthorizationDecision {
+    Approved,
+    Denied,
+    /// Received but not decided yet, usually awaiting manual review.
+    Pending,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PreauthorizationResponse {
+    pub decision: PreauthorizationDecision,
+    #[serde(default)]
+    pub authorization_number: Option<String>,
+    #[serde(default)]
+    pub approved_amount: Option<Decimal>,
+    #[serde(default)]
+    pub valid_until: Option<NaiveDate>,
+    #[serde(default)]
+    pub reference: Option<String>,
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+/// The insurer's answer. Benefit fields the insurer leaves out are kept as
+/// they are on the card.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EligibilityResponse {
+    pub status: EligibilityStatus,
+    #[serde(default)]
+    pub plan_name: Option<String>,
+    #[serde(default)]
+    pub coverage_start_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub coverage_end_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub copay_amount: Option<Decimal>,
+    #[serde(default)]
+    pub deductible_amount: Option<Decimal>,
+    #[serde(default)]
+    pub deductible_met_ytd: Option<Decimal>,
+    #[serde(default)]
+    pub out_of_pocket_max: Option<Decimal>,
+    #[serde(default)]
+    pub out_of_pocket_met_ytd: Option<Decimal>,
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+#[async_trait]
+pub trait InsuranceAdapter {
+    async fn check_eligibility(
+        &self,
+        request: &EligibilityRequest,
+    ) -> Result<EligibilityResponse, String>;
+
+    async fn submit_preauthorization(
+        &self,
+        request: &PreauthorizationRequest,
+    ) -> Result<PreauthorizationResponse, String>;
+}
+
+/// Posts requests as JSON to `{api_endpoint}/eligibility` and
+/// `{api_endpoint}/preauthorizations`. The
+/// provider's decrypted `api_credentials` may hold a `bearer_token`, an
+/// `api_key` (sent as `X-API-Key`) or a `username` and `password`.
+pub struct HttpInsuranceAdapter {
+    pub client: Client,
+    pub endpoint: String,
+    pub credentials: Option<Value>,
+}
+
+impl HttpInsuranceAdapter {
+    pub fn new(endpoint: String, credentials: Option<Value>) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            client,
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            credentials,
+        }
+    }
+
+    fn credential(&self, key: &str) -> Option<&str> {
+        self.credentials.as_ref()?.get(key)?.as_str()
+    }
+
+    fn post<T: Serialize + ?Sized>(&self, path: &str, body: &T) -> reqwest::RequestBuilder {
+        let builder = self
+            .client
+            .post(format!("{}/{}", self.endpoint, path))
+            .json(body);
+
+        if let Some(token) = self.credential("bearer_token") {
+            builder.bearer_auth(token)
+        } else if let Some(api_key) = self.credential("api_key") {
+            builder.header("X-API-Key", api_key)
+        } else if let Some(username) = self.credential("username") {
+            builder.basic_auth(username, self.credential("password"))
+        } else {
+            builder
+        }
+    }
+}
+
+#[async_trait]
+impl InsuranceAdapter for HttpInsuranceAdapter {
+    async fn check_eligibility(
+        &self,
+        request: &EligibilityRequest,
+    ) -> Result<EligibilityResponse, String> {
+        let response = self
+            .post("eligibility", request)
+            .send()
+            .await
+            .map_err(|err| format!("Insurer could not be reached: {}", err))?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(EligibilityResponse::ineligible("Policy not found"));
+        }
+        if !status.is_success() {
+            return Err(format!("Insurer returned {}", status));
+        }
+
+        response
+            .json::<EligibilityResponse>()
+            .await
+            .map_err(|err| format!("Insurer sent an unreadable response: {}", err))
+    }
+
+    async fn submit_preauthorization(
+        &self,
+        request: &PreauthorizationRequest,
+    ) -> Result<PreauthorizationResponse, String> {
+        let response = self
+            .post("preauthorizations", request)
+            .send()
+            .await
+            .map_err(|err| format!("Insurer could not be reached: {}", err))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("Insurer returned {}", status));
+        }
+
+        response
+            .json::<PreauthorizationResponse>()
+            .await
+            .map_err(|err| format!("Insurer sent an unreadable response: {}", err))
+    }
+}
+
+/// A stand-in insurer for development, demos and the tests below. Policy
+/// numbers starting with `INVALID` are unknown and those starting with
+/// `EXPIRED` lapsed yesterday; any other policy is covered to the end of the
+/// year.
+///
+/// Pre-authorizations on policies starting with `DENY` are denied, those
+/// estimated above 100,000 are left pending for review, and
+/// the rest are approved in full for 30 days.
+pub struct MockInsuranceAdapter;
+
+#[async_trait]
+impl InsuranceAdapter for MockInsuranceAdapter {
+    async fn check_eligibility(
+        &self,
+        request: &EligibilityRequest,
+    ) -> Result<EligibilityResponse, String> {
+        let policy_number = request.policy_number.trim().to_uppercase();
+        let today = Utc::now().date_naive();
+        let year_start = NaiveDate::from_ymd_opt(today.year(), 1, 1).unwrap_or(today);
+        let year_end = NaiveDate::from_ymd_opt(today.year(), 12, 31).unwrap_or(today);
+
+        if policy_number.starts_with("INVALID") {
+            return Ok(EligibilityResponse::ineligible("Policy not found"));
+        }
+
+        let (status, coverage_end_date, message) = if policy_number.starts_with("EXPIRED") {
+            (
+                EligibilityStatus::Expired,
+                today.pred_opt().unwrap_or(today),
+                "Cover has lapsed",
+            )
+        } else {
+            (EligibilityStatus::Eligible, year_end, "Member is eligible")
+        };
+
+        Ok(EligibilityResponse {
+            status,
+            plan_name: Some("Mock Standard".to_string()),
+            coverage_start_date: Some(year_start),
+            coverage_end_date: Some(coverage_end_date),
+            copay_amount: Some(Decimal::new(500, 0)),
+            deductible_amount: Some(Decimal::new(5000, 0)),
+            deductible_met_ytd: Some(Decimal::ZERO),
+            out_of_pocket_max: Some(Decimal::new(50000, 0)),
+            out_of_pocket_met_ytd: Some(Decimal::ZERO),
+            message: Some(message.to_string()),
+        })
+    }
+
+    async fn submit_preauthorization(
+        &self,
+        request: &PreauthorizationRequest,
+    ) -> Result<PreauthorizationResponse, String> {
+        let policy_number = request.policy_number.trim().to_uppercase();
+        let reference = Some(format!("MOCK-REF-{}", request.preauth_number));
+
+        if policy_number.starts_with("INVALID") || policy_number.starts_with("DENY") {
+            return Ok(PreauthorizationResponse {
+                decision: PreauthorizationDecision::Denied,
+                authorization_number: None,
+                approved_amount: None,
+                valid_until: None,
+                reference,
+                message: Some("Services are not covered by the plan".to_string()),
+            });
+        }
+
+        if request.estimated_amount > MOCK_PREAUTH_REVIEW_AMOUNT {
+            return Ok(PreauthorizationResponse {
+                decision: PreauthorizationDecision::Pending,
+                authorization_number: None,
+                approved_amount: None,
+                valid_until: None,
+                reference,
+                message: Some("Referred for medical review".to_string()),
+            });
+        }
+
+        let today = Utc::now().date_naive();
+        Ok(PreauthorizationResponse {
+            decision: PreauthorizationDecision::Approved,
+            authorization_number: Some(format!("MOCK-PA-{}", request.preauth_number)),
+            approved_amount: Some(request.estimated_amount),
+            valid_until: today.checked_add_days(Days::new(30)),
+            reference,
+            message: Some("Pre-authorization approved".to_string()),
+        })
+    }
+}
+
+impl EligibilityResponse {
+    pub fn ineligible(message: &str) -> Self {
+        Self {
+            status: EligibilityStatus::Ineligible,
+            plan_name: None,
+            coverage_start_date: None,
+            coverage_end_date: None,
+            copay_amount: None,
+            deductible_amount: None,
+            deductible_met_ytd: None,
+            out_of_pocket_max: None,
+            out_of_pocket_met_ytd: None,
+            message: Some(message.to_string()),
+        }
+    }
+}
+
+/// The adapter for the provider, or `None` when it has no usable endpoint.
+pub fn get_insurance_adapter(
+    provider: &insurance_providers::Model,
+) -> Option<Box<dyn InsuranceAdapter + Send + Sync>> {
+    let endpoint = provider.api_endpoint.as_deref()?.trim();
+
+    if endpoint == MOCK_ENDPOINT {
+        Some(Box::new(MockInsuranceAdapter))
+    } else if endpoint.starts_with("https://") || endpoint.starts_with("http://") {
+        let credentials = match provider.api_credentials.as_ref().map(decrypt_json) {
+            Some(Ok(credentials)) => Some(credentials),
+            Some(Err(err)) => {
+                log::error!("Failed to decrypt credentials of {}: {}", provider.pid, err);
+                None
+            }
+            None => None,
+        };
+
+        Some(Box::new(HttpInsuranceAdapter::new(
+            endpoint.to_string(),
+            credentials,
+        )))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eligibility_request(policy_number: &str) -> EligibilityRequest {
+        EligibilityRequest {
+            policy_number: policy_number.to_string(),
+            group_number: None,
+            member_name: None,
+            date_of_birth: None,
+            service_date: Utc::now().date_naive(),
+        }
+    }
+
+    fn preauthorization_request(
+        policy_number: &str,
+        estimated_amount: Decimal,
+    ) -> PreauthorizationRequest {
+        PreauthorizationRequest {
+            preauth_number: "PA-20260101-ABC123".to_string(),
+            policy_number: policy_number.to_string(),
+            group_number: None,
+            member_name: None,
+            date_of_birth: None,
+            planned_date: None,
+            diagnosis_codes: Vec::new(),
+            clinical_notes: None,
+            estimated_amount,
+            currency: "KES".to_string(),
+            lines: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn mock_eligibility_follows_policy_prefix() {
+        let adapter = MockInsuranceAdapter;
+        let today = Utc::now().date_naive();
+
+        let eligible = adapter
+            .check_eligibility(&eligibility_request("POL-001"))
+            .await
+            .unwrap();
+        assert_eq!(eligible.status, EligibilityStatus::Eligible);
+        assert!(eligible.coverage_end_date.unwrap() >= today);
+
+        let expired = adapter
+            .check_eligibility(&eligibility_request("expired-001"))
+            .await
+            .unwrap();
+        assert_eq!(expired.status, EligibilityStatus::Expired);
+        assert!(expired.coverage_end_date.unwrap() < today);
+
+        let unknown = adapter
+            .check_eligibility(&eligibility_request("INVALID-001"))
+            .await
+            .unwrap();
+        assert_eq!(unknown.status, EligibilityStatus::Ineligible);
+        assert!(unknown.plan_name.is_none());
+    }
+
+    #[tokio::test]
+    async fn mock_preauthorization_decisions() {
+        let adapter = MockInsuranceAdapter;
+
+        let denied = adapter
+            .submit_preauthorization(&preauthorization_request("DENY-001", dec!(1000)))
+            .await
+            .unwrap();
+        assert_eq!(denied.decision, PreauthorizationDecision::Denied);
+        assert!(denied.authorization_number.is_none());
+
+        let pending = adapter
+            .submit_preauthorization(&preauthorization_request("POL-001", dec!(150000)))
+            .await
+            .unwrap();
+        assert_eq!(pending.decision, PreauthorizationDecision::Pending);
+
+        let approved = adapter
+            .submit_preauthorization(&preauthorization_request("POL-001", dec!(2500)))
+            .await
+            .unwrap();
+        assert_eq!(approved.decision, PreauthorizationDecision::Approved);
+        assert_eq!(approved.approved_amount, Some(dec!(2500)));
+        assert_eq!(
+            approved.authorization_number.as_deref(),
+            Some("MOCK-PA-PA-20260101-ABC123")
+        );
+    }
+}
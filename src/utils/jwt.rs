@@ -5,9 +5,12 @@ use serde_json::json;
 use uuid::Uuid;
 
 use crate::{
-    db::main::{
-        self,
-        migrations::sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QuerySelect},
+    db::{
+        main::{
+            self,
+            migrations::sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QuerySelect},
+        },
+        tenant,
     },
     utils::{api_response::ApiResponse, app_state::AppState},
 };
@@ -109,3 +112,15 @@ pub async fn get_tenant_id(
 
     Ok((tenant_id, tenant_pid, tenant_sso_id))
 }
+
+pub async fn get_tenant_db(
+    req: &HttpRequest,
+    app_state: &web::Data<AppState>,
+) -> Result<tenant::migrations::sea_orm::DatabaseConnection, ApiResponse> {
+    let (_, _, tenant_sso_id) = get_tenant_id(req, app_state).await?;
+
+    app_state.tenant_db(tenant_sso_id).ok_or_else(|| {
+        log::error!("Tenant DB not found for tenant_id: {}", tenant_sso_id);
+        ApiResponse::new(404, json!({ "message": "Tenant database not found" }))
+    })
+}
@@ -1,9 +1,14 @@
 pub mod api_response;
 pub mod app_state;
 pub mod constants;
+pub mod crypto;
+pub mod documents;
+pub mod growth;
+pub mod html;
 pub mod html_to_image;
 pub mod http_client;
 pub mod ids;
+pub mod insurance;
 pub mod jwt;
 pub mod message_queue;
 pub mod migrate;
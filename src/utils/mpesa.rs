@@ -4,6 +4,7 @@ use actix_web::{HttpRequest, web};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use uuid::Uuid;
 
 use crate::utils::{self, api_response::ApiResponse};
 
@@ -31,6 +32,13 @@ pub enum StkPushResponse {
     },
 }
 
+/// A random token for one checkout's callback URL. Callbacks are only
+/// accepted with the token stored against their `CheckoutRequestID`, so a
+/// leaked URL grants nothing beyond that one payment.
+pub fn callback_token() -> String {
+    format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
+
 pub struct MpesaClient {
     pub client: Client,
     pub base_url: String,
@@ -58,6 +66,24 @@ impl MpesaClient {
         }
     }
 
+    /// A client for a facility's own shortcode, on the same Daraja
+    /// environment as the platform's.
+    pub fn with_credentials(
+        consumer_key: String,
+        consumer_secret: String,
+        business_shortcode: String,
+        passkey: String,
+    ) -> Self {
+        Self {
+            client: Client::new(),
+            base_url: (utils::constants::MPESA_BASE_URL).clone(),
+            consumer_key,
+            consumer_secret,
+            business_shortcode,
+            passkey,
+        }
+    }
+
     pub fn get_timestamp(&self) -> String {
         use chrono::prelude::*;
 
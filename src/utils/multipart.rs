@@ -9,27 +9,67 @@ use sea_orm::prelude::Decimal;
 use serde_json::json;
 use uuid::Uuid;
 
-use crate::utils::{
-    api_response::ApiResponse, app_state::AppState, jwt::get_logged_in_user_claims,
+use crate::{
+    db::main::{
+        self,
+        entities::sea_orm_active_enums::FileVisibility,
+        migrations::sea_orm::{
+            ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QuerySelect, Set,
+        },
+    },
+    utils::{api_response::ApiResponse, app_state::AppState, jwt::get_logged_in_user_claims},
 };
 
+/// Stores `content` and registers it in the file registry. Returns the file's
+/// pid; downloads go through the registry so they are authorized and logged.
 pub async fn upload_file(
     req: &HttpRequest,
     app_state: &AppState,
     file_name: &str,
     content: Vec<u8>,
     content_type: &str,
-) -> Result<String, ApiResponse> {
+    patient_id: Option<i32>,
+    visibility: FileVisibility,
+) -> Result<Uuid, ApiResponse> {
+    upload_facility_file(
+        req,
+        app_state,
+        None,
+        file_name,
+        content,
+        content_type,
+        patient_id,
+        visibility,
+    )
+    .await
+}
+
+/// Like [`upload_file`], but registers the file under `facility_id` when
+/// given, so a patient can share a file with the facility's staff.
+#[allow(clippy::too_many_arguments)]
+pub async fn upload_facility_file(
+    req: &HttpRequest,
+    app_state: &AppState,
+    facility_id: Option<i32>,
+    file_name: &str,
+    content: Vec<u8>,
+    content_type: &str,
+    patient_id: Option<i32>,
+    visibility: FileVisibility,
+) -> Result<Uuid, ApiResponse> {
     let claims = get_logged_in_user_claims(&req)?;
+    let is_patient = claims.role_name.trim().to_lowercase() == "user";
 
     let filename = if claims.tenant_pid.is_some() {
         format!("{}/{}", claims.tenant_pid.expect("Tenant ID"), file_name)
-    } else if claims.role_name.trim().to_lowercase() == "user" {
+    } else if is_patient {
         format!("patient/{}", file_name)
     } else {
         format!("admin/{}", file_name)
     };
 
+    let size_bytes = content.len() as i64;
+
     app_state
         .s3_client
         .put_object()
@@ -41,7 +81,64 @@ pub async fn upload_file(
         .await
         .map_err(|err| ApiResponse::new(500, json!({ "message": err.to_string() })))?;
 
-    Ok(filename)
+    let tenant_id = match (facility_id, claims.tenant_pid) {
+        (Some(facility_id), _) => Some(facility_id),
+        (None, Some(tenant_pid)) => main::entities::tenants::Entity::find()
+            .filter(main::entities::tenants::Column::Pid.eq(tenant_pid))
+            .select_only()
+            .column(main::entities::tenants::Column::Id)
+            .into_tuple::<i32>()
+            .one(&app_state.main_db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to fetch tenant for file upload: {}", err);
+                ApiResponse::new(500, json!({ "message": "Failed to register file" }))
+            })?,
+        (None, None) => None,
+    };
+
+    let patient_id = match patient_id {
+        Some(id) => Some(id),
+        None if is_patient => main::entities::patients::Entity::find()
+            .filter(main::entities::patients::Column::SsoUserId.eq(claims.sub))
+            .filter(main::entities::patients::Column::DeletedAt.is_null())
+            .select_only()
+            .column(main::entities::patients::Column::Id)
+            .into_tuple::<i32>()
+            .one(&app_state.main_db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to fetch patient for file upload: {}", err);
+                ApiResponse::new(500, json!({ "message": "Failed to register file" }))
+            })?,
+        None => None,
+    };
+
+    let (category, original_name) = match file_name.rsplit_once('/') {
+        Some((category, name)) => (Some(category.to_string()), name.to_string()),
+        None => (None, file_name.to_string()),
+    };
+
+    let file = main::entities::files::ActiveModel {
+        storage_key: Set(filename.clone()),
+        original_name: Set(Some(original_name)),
+        content_type: Set(content_type.to_string()),
+        size_bytes: Set(size_bytes),
+        category: Set(category),
+        owner_sso_user_id: Set(Some(claims.sub)),
+        tenant_id: Set(tenant_id),
+        patient_id: Set(patient_id),
+        visibility: Set(visibility),
+        ..Default::default()
+    }
+    .insert(&app_state.main_db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to register uploaded file {}: {}", filename, err);
+        ApiResponse::new(500, json!({ "message": "Failed to register file" }))
+    })?;
+
+    Ok(file.pid)
 }
 
 pub async fn download_file(app_state: &AppState, file_name: &str) -> Result<Vec<u8>, ApiResponse> {
@@ -10,6 +10,13 @@ pub fn validate_phone_number(phone_number: &str) -> bool {
     phone_regex.is_match(normalized)
 }
 
+// Accepts numbers with a country code, e.g. "+254 712 345 678"
+pub fn validate_international_phone_number(phone_number: &str) -> bool {
+    let digits = phone_number.replace(' ', "");
+    let digits = digits.strip_prefix('+').unwrap_or(&digits);
+    (9..=15).contains(&digits.len()) && digits.chars().all(|c| c.is_ascii_digit())
+}
+
 pub fn validate_password(password: &str) -> bool {
     if password.len() < 8 {
         return false;